@@ -0,0 +1,23 @@
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    #[cfg(feature = "grpc")]
+    {
+        println!("cargo:rerun-if-changed=proto/markdown_todo_extractor.proto");
+        println!("cargo:rerun-if-changed=proto/vault.proto");
+
+        // Use the vendored protoc so builds don't depend on a system install
+        // SAFETY: build scripts are single-threaded
+        unsafe {
+            std::env::set_var("PROTOC", protoc_bin_vendored::protoc_bin_path()?);
+        }
+        tonic_prost_build::configure()
+            .build_client(true)
+            .compile_protos(&["proto/markdown_todo_extractor.proto"], &["proto"])?;
+        // The typed service is served from the operations' schemas at runtime
+        // (see `grpc_schema.rs`), so only its messages and client are generated
+        tonic_prost_build::configure()
+            .build_server(false)
+            .compile_protos(&["proto/vault.proto"], &["proto"])?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,241 @@
+//! Access audit log for server modes
+//!
+//! With an `[audit]` table, every operation invoked over HTTP, MCP, or gRPC
+//! is appended to a JSON Lines file: when it ran, which client and session
+//! called it, its parameters (with the configured keys redacted), and how it
+//! ended. Entries are only ever appended; rotating or pruning the file is
+//! left to the user.
+
+use crate::config::{AuditConfig, Config};
+use chrono::Local;
+use serde::Serialize;
+use serde_json::Value;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+
+/// Replaces the value of a redacted parameter
+pub const REDACTED: &str = "[redacted]";
+
+/// The log of this process, set by `serve`
+static AUDIT_LOG: OnceLock<AuditLog> = OnceLock::new();
+
+/// Interface an operation was invoked through
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Interface {
+    Http,
+    Mcp,
+    Grpc,
+}
+
+/// One line of the audit log
+#[derive(Debug, Serialize)]
+pub struct AuditEntry {
+    /// When the call finished (RFC 3339, local time)
+    pub timestamp: String,
+    pub interface: Interface,
+    /// Operation as addressed on the interface: the HTTP path, MCP tool
+    /// name, or gRPC operation name
+    pub operation: String,
+    /// MCP client name, or the `X-Client-Id` (else `User-Agent`) header
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client: Option<String>,
+    /// MCP session id (`Mcp-Session-Id`) for MCP over HTTP
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session: Option<String>,
+    /// Request parameters, with redacted keys replaced
+    pub params: Value,
+    /// Whether the call succeeded
+    pub ok: bool,
+    /// Error message of a failed call
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Size of the JSON result in bytes (0 for a failed call)
+    pub result_bytes: usize,
+    pub duration_ms: u64,
+}
+
+impl AuditEntry {
+    /// An entry for a call that started at `started` and has just finished
+    pub fn new(
+        interface: Interface,
+        operation: impl Into<String>,
+        params: Value,
+        started: Instant,
+    ) -> Self {
+        Self {
+            timestamp: Local::now().to_rfc3339(),
+            interface,
+            operation: operation.into(),
+            client: None,
+            session: None,
+            params,
+            ok: true,
+            error: None,
+            result_bytes: 0,
+            duration_ms: started.elapsed().as_millis() as u64,
+        }
+    }
+
+    /// Record the outcome of the call, sizing a successful result as JSON
+    pub fn outcome<T: Serialize, E: ToString>(mut self, result: &Result<T, E>) -> Self {
+        match result {
+            Ok(value) => {
+                self.result_bytes = serde_json::to_vec(value).map_or(0, |json| json.len());
+            }
+            Err(e) => {
+                self.ok = false;
+                self.error = Some(e.to_string());
+            }
+        }
+        self
+    }
+}
+
+/// An open audit log file
+pub struct AuditLog {
+    path: PathBuf,
+    redact: Vec<String>,
+    file: Mutex<File>,
+}
+
+impl AuditLog {
+    /// Open the log configured in `config.audit` for appending, creating it
+    /// if needed; `None` if auditing is off
+    ///
+    /// A relative `log` path is relative to the vault at `base_path`.
+    pub fn open(base_path: &Path, config: &Config) -> io::Result<Option<Self>> {
+        let Some(AuditConfig {
+            ref log,
+            ref redact,
+        }) = config.audit
+        else {
+            return Ok(None);
+        };
+        let path = base_path.join(log);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        Ok(Some(Self {
+            path,
+            redact: redact.clone(),
+            file: Mutex::new(file),
+        }))
+    }
+
+    /// Path of the log file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Redact `entry`'s parameters and append it as one line
+    pub fn append(&self, mut entry: AuditEntry) -> io::Result<()> {
+        redact(&mut entry.params, &self.redact);
+        let mut line = serde_json::to_vec(&entry)?;
+        line.push(b'\n');
+        // One write per entry, so concurrent calls never interleave lines
+        let mut file = self.file.lock().unwrap_or_else(|e| e.into_inner());
+        file.write_all(&line)
+    }
+}
+
+/// Replace the value of every object key in `keys`, at any depth
+fn redact(value: &mut Value, keys: &[String]) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if keys.iter().any(|k| k.eq_ignore_ascii_case(key)) {
+                    *value = Value::String(REDACTED.to_string());
+                } else {
+                    redact(value, keys);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|item| redact(item, keys)),
+        _ => {}
+    }
+}
+
+/// Use `log` for the rest of this process
+///
+/// Only the first log installed is used.
+pub fn install(log: AuditLog) {
+    let _ = AUDIT_LOG.set(log);
+}
+
+/// Whether a log is installed, so callers can skip building entries
+pub fn enabled() -> bool {
+    AUDIT_LOG.get().is_some()
+}
+
+/// Append `entry` to the installed log, if any
+///
+/// A failed write is reported on stderr; it doesn't fail the call.
+pub fn record(entry: AuditEntry) {
+    if let Some(log) = AUDIT_LOG.get()
+        && let Err(e) = log.append(entry)
+    {
+        eprintln!("Failed to write audit log {}: {}", log.path().display(), e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_append_redacts_params() {
+        let vault = TempDir::new().unwrap();
+        let config: Config = toml::from_str(
+            r#"
+[audit]
+log = "logs/audit.jsonl"
+redact = ["content", "Text"]
+"#,
+        )
+        .unwrap();
+        let log = AuditLog::open(vault.path(), &config).unwrap().unwrap();
+
+        let mut entry = AuditEntry::new(
+            Interface::Mcp,
+            "add_task",
+            json!({"file_path": "a.md", "content": "Call the bank", "items": [{"text": "x"}]}),
+            Instant::now(),
+        )
+        .outcome(&Ok::<_, String>(json!({"line_number": 3})));
+        entry.session = Some("abc".to_string());
+        log.append(entry).unwrap();
+        let entry = AuditEntry::new(Interface::Http, "/api/tasks", json!({}), Instant::now())
+            .outcome(&Err::<Value, _>("File not found: b.md"));
+        log.append(entry).unwrap();
+
+        let written = fs::read_to_string(vault.path().join("logs/audit.jsonl")).unwrap();
+        let lines: Vec<Value> = written
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[0]["params"],
+            json!({"file_path": "a.md", "content": REDACTED, "items": [{"text": REDACTED}]})
+        );
+        assert_eq!(lines[0]["interface"], "mcp");
+        assert_eq!(lines[0]["session"], "abc");
+        assert_eq!(lines[0]["result_bytes"], 17);
+        assert_eq!(lines[1]["ok"], false);
+        assert_eq!(lines[1]["error"], "File not found: b.md");
+        assert!(lines[1].get("client").is_none());
+
+        assert!(
+            AuditLog::open(vault.path(), &Config::default())
+                .unwrap()
+                .is_none()
+        );
+    }
+}
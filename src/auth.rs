@@ -0,0 +1,268 @@
+//! API keys for the HTTP server
+//!
+//! With `[[api_keys]]` entries in the config, every HTTP request must present
+//! one of the keys: as `Authorization: Bearer <key>`, as `X-Api-Key: <key>`,
+//! or as the password of HTTP basic auth, which is all CalDAV clients can
+//! send. A key can be limited to some operations, to read-only operations,
+//! and to a folder of the vault. REST calls are checked by [`authorize`]
+//! before the operation runs; the other routes (MCP, tool docs, schemas,
+//! CalDAV, feeds) reach the whole vault, so [`require_unrestricted`] lets only
+//! keys without limits through.
+
+use crate::capabilities::{SUBVAULT_PARAM, take_subvault};
+use crate::config::{ApiKeyConfig, Config};
+use crate::error::{Error, forbidden, invalid_params, unauthorized};
+use crate::operation::Operation;
+use crate::path_resolver::from_slash;
+use axum::extract::{Request, State};
+use axum::http::{HeaderMap, HeaderValue, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use serde_json::Value;
+use std::path::Component;
+use std::sync::Arc;
+
+/// The key presented with a request, if any
+fn presented_key(headers: &HeaderMap) -> Option<String> {
+    let header = |name: header::HeaderName| headers.get(name).and_then(|v| v.to_str().ok());
+    if let Some(key) = header(header::HeaderName::from_static("x-api-key")) {
+        return Some(key.trim().to_string());
+    }
+    let (scheme, credentials) = header(header::AUTHORIZATION)?.trim().split_once(' ')?;
+    if scheme.eq_ignore_ascii_case("bearer") {
+        Some(credentials.trim().to_string())
+    } else if scheme.eq_ignore_ascii_case("basic") {
+        let decoded = String::from_utf8(BASE64.decode(credentials.trim()).ok()?).ok()?;
+        decoded
+            .split_once(':')
+            .map(|(_, password)| password.to_string())
+    } else {
+        None
+    }
+}
+
+/// Compare keys in time that doesn't depend on where they differ
+fn keys_match(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0, |diff, (x, y)| diff | (x ^ y))
+            == 0
+}
+
+/// The configured key presented with a request; `None` if the server has no
+/// keys and is open
+pub fn authenticate<'a>(
+    config: &'a Config,
+    headers: &HeaderMap,
+) -> Result<Option<&'a ApiKeyConfig>, Error> {
+    if config.api_keys.is_empty() {
+        return Ok(None);
+    }
+    let presented = presented_key(headers).ok_or_else(|| unauthorized("API key required"))?;
+    config
+        .api_keys
+        .iter()
+        .find(|key| key.key().is_some_and(|key| keys_match(&key, &presented)))
+        .map(Some)
+        .ok_or_else(|| unauthorized("Unknown API key"))
+}
+
+/// Check that `key` may call `operation`, confining the call's `params` to
+/// the key's folder
+pub fn authorize(
+    key: &ApiKeyConfig,
+    operation: &dyn Operation,
+    params: &mut Value,
+) -> Result<(), Error> {
+    if let Some(operations) = &key.operations
+        && !operations.iter().any(|name| name == operation.name())
+    {
+        return Err(forbidden(format!(
+            "API key '{}' may not call {}",
+            key.name,
+            operation.name()
+        )));
+    }
+    if key.read_only && !operation.read_only() {
+        return Err(forbidden(format!(
+            "API key '{}' is read-only and may not call {}",
+            key.name,
+            operation.name()
+        )));
+    }
+    if let Some(scope) = &key.path {
+        confine(params, scope, &key.name)?;
+    }
+    Ok(())
+}
+
+/// Scope `params` to the folder `scope`: a missing `subvault` becomes the
+/// scope, and a given one must lie inside it
+fn confine(params: &mut Value, scope: &str, name: &str) -> Result<(), Error> {
+    let params = params
+        .as_object_mut()
+        .ok_or_else(|| invalid_params("Request parameters must be an object"))?;
+    let subvault = match take_subvault(params)? {
+        Some(subvault) if !within(&subvault, scope) => {
+            return Err(forbidden(format!(
+                "API key '{}' is limited to '{}'",
+                name, scope
+            )));
+        }
+        Some(subvault) => subvault,
+        None => scope.to_string(),
+    };
+    params.insert(SUBVAULT_PARAM.to_string(), Value::String(subvault));
+    Ok(())
+}
+
+/// Whether the vault-relative `path` is `scope` or inside it
+fn within(path: &str, scope: &str) -> bool {
+    let components = |path: &str| -> Option<Vec<String>> {
+        from_slash(path.trim())
+            .components()
+            .filter(|c| *c != Component::CurDir)
+            .map(|c| match c {
+                Component::Normal(name) => Some(name.to_string_lossy().into_owned()),
+                _ => None,
+            })
+            .collect()
+    };
+    match (components(path), components(scope)) {
+        (Some(path), Some(scope)) => path.starts_with(&scope),
+        _ => false,
+    }
+}
+
+/// Middleware for routes other than the REST operations: with API keys
+/// configured, only an unrestricted key gets through
+pub async fn require_unrestricted(
+    State(config): State<Arc<Config>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    match authenticate(&config, request.headers()) {
+        Ok(None) => next.run(request).await,
+        Ok(Some(key)) if key.is_unrestricted() => next.run(request).await,
+        Ok(Some(key)) => {
+            forbidden(format!("API key '{}' is limited to the REST API", key.name)).into_response()
+        }
+        Err(error) => {
+            let mut response = error.into_response();
+            // Let CalDAV clients and browsers prompt for the key
+            response.headers_mut().insert(
+                header::WWW_AUTHENTICATE,
+                HeaderValue::from_static("Basic realm=\"markdown-todo-extractor\""),
+            );
+            response
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::capabilities::CapabilityRegistry;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn operation(registry: &CapabilityRegistry, name: &str) -> Arc<dyn Operation> {
+        registry
+            .create_operations()
+            .into_iter()
+            .find(|op| op.name() == name)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_authenticate() {
+        let config: Config = toml::from_str(
+            r#"
+[[api_keys]]
+name = "admin"
+key = "s3cret"
+"#,
+        )
+        .unwrap();
+        let headers = |name: &str, value: &str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(
+                header::HeaderName::from_bytes(name.as_bytes()).unwrap(),
+                HeaderValue::from_str(value).unwrap(),
+            );
+            headers
+        };
+
+        let key =
+            |headers: HeaderMap| authenticate(&config, &headers).map(|k| k.unwrap().name.clone());
+        assert_eq!(
+            key(headers("authorization", "Bearer s3cret")).unwrap(),
+            "admin"
+        );
+        assert_eq!(key(headers("x-api-key", "s3cret")).unwrap(), "admin");
+        let basic = format!("Basic {}", BASE64.encode("me:s3cret"));
+        assert_eq!(key(headers("authorization", &basic)).unwrap(), "admin");
+        assert_eq!(
+            key(headers("authorization", "Bearer guess"))
+                .unwrap_err()
+                .code(),
+            "unauthorized"
+        );
+        assert_eq!(key(HeaderMap::new()).unwrap_err().code(), "unauthorized");
+
+        let open = Config::default();
+        assert!(authenticate(&open, &HeaderMap::new()).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_authorize() {
+        let vault = TempDir::new().unwrap();
+        let config: Config = toml::from_str(
+            r#"
+[[api_keys]]
+name = "reader"
+key = "r"
+read_only = true
+path = "Work"
+
+[[api_keys]]
+name = "tagger"
+key = "t"
+operations = ["list-tags"]
+"#,
+        )
+        .unwrap();
+        let registry =
+            CapabilityRegistry::new(vault.path().to_path_buf(), Arc::new(config.clone()));
+        let (reader, tagger) = (&config.api_keys[0], &config.api_keys[1]);
+        let tasks = operation(&registry, "tasks");
+        let add_task = operation(&registry, "add-task");
+
+        let mut params = json!({"status": "incomplete"});
+        authorize(reader, tasks.as_ref(), &mut params).unwrap();
+        assert_eq!(params, json!({"status": "incomplete", "subvault": "Work"}));
+        let mut params = json!({"subvault": "Work/Acme"});
+        authorize(reader, tasks.as_ref(), &mut params).unwrap();
+        assert_eq!(params["subvault"], "Work/Acme");
+        for subvault in ["Home", "Workshop", "Work/../Home"] {
+            let error =
+                authorize(reader, tasks.as_ref(), &mut json!({"subvault": subvault})).unwrap_err();
+            assert_eq!(error.code(), "forbidden", "{}", subvault);
+        }
+        let error = authorize(reader, add_task.as_ref(), &mut json!({})).unwrap_err();
+        assert!(error.to_string().contains("read-only"));
+
+        authorize(
+            tagger,
+            operation(&registry, "list-tags").as_ref(),
+            &mut json!({}),
+        )
+        .unwrap();
+        let error = authorize(tagger, tasks.as_ref(), &mut json!({})).unwrap_err();
+        assert_eq!(error.to_string(), "API key 'tagger' may not call tasks");
+        assert!(!tagger.is_unrestricted());
+    }
+}
@@ -0,0 +1,815 @@
+//! On-disk index of extracted tasks and outlines (`--cache`)
+//!
+//! Each vault gets one JSON file under `~/.cache/markdown-todo-extractor`
+//! (or `$XDG_CACHE_HOME`), named after a hash of the vault's canonical path.
+//! It maps every scanned Markdown file to its tasks and headings along with
+//! the file's modification time and size; a file is re-parsed only when either
+//! changes. A full task scan drops the files that are no longer scanned; the
+//! outline operations, which look at one file or only at notes, keep them.
+//! The `index` command rebuilds, inspects, verifies, or compacts the index.
+//!
+//! Servers started with `--watch` keep the index of their vault resident in
+//! memory instead of reading the file on every request, and the vault watcher
+//! evicts files from it as they change (see `crate::watcher`). The resident
+//! index is only written back to disk when a request parsed or dropped files.
+
+use crate::config::Config;
+use crate::extractor::Task;
+use crate::outline_extractor::Heading;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::UNIX_EPOCH;
+
+/// Set by the `--cache` CLI flag
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Turn on caching for task and outline extraction in this process
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Whether `--cache` was given
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// `markdown-todo-extractor` in `$XDG_CACHE_HOME`, falling back to `~/.cache`
+pub fn cache_dir() -> Option<PathBuf> {
+    std::env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+        .map(|dir| dir.join("markdown-todo-extractor"))
+}
+
+/// Cached tasks and headings of one file; either is filled the first time
+/// it's needed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Entry {
+    /// Modification time in nanoseconds since the Unix epoch
+    modified: u128,
+    len: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tasks: Option<Vec<Task>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    headings: Option<Vec<Heading>>,
+}
+
+impl Entry {
+    fn new((modified, len): (u128, u64)) -> Self {
+        Self {
+            modified,
+            len,
+            tasks: None,
+            headings: None,
+        }
+    }
+
+    fn is_current(&self, (modified, len): (u128, u64)) -> bool {
+        self.modified == modified && self.len == len
+    }
+}
+
+/// How current a vault's index is
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct IndexStatus {
+    /// Files in the index
+    pub files: usize,
+    /// Indexed files whose modification time and size are unchanged
+    pub current: usize,
+    /// Indexed files that changed since they were indexed
+    pub changed: usize,
+    /// Indexed files that no longer exist
+    pub deleted: usize,
+    /// Notes in the vault that aren't indexed yet
+    pub not_indexed: usize,
+    /// Indexed files with their tasks cached
+    pub with_tasks: usize,
+    /// Indexed files with their outline cached
+    pub with_outlines: usize,
+}
+
+/// Whether a cache file can be used
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IndexState {
+    /// Readable and written by this version
+    Ok,
+    /// No index has been written yet
+    Missing,
+    /// Unreadable or not a valid index; it is discarded when opened
+    Corrupt,
+    /// Written by another version or under settings that parse files
+    /// differently; it is discarded when opened
+    Outdated,
+}
+
+/// Problems found among the entries of an index
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct IndexCheck {
+    /// Entries in the index
+    pub files: usize,
+    /// Entries that are up to date
+    pub current: usize,
+    /// Entries for files that changed since they were indexed
+    pub changed: usize,
+    /// Entries for files that no longer exist
+    pub deleted: usize,
+    /// Entries for files that resolve to a path outside the vault
+    pub outside_vault: usize,
+    /// Entries for a file already indexed under another spelling of its path
+    pub duplicates: usize,
+}
+
+impl IndexCheck {
+    /// Whether every entry is current
+    pub fn is_clean(&self) -> bool {
+        self.current == self.files
+    }
+}
+
+/// Contents of a cache file
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheFile {
+    /// Version of the crate that wrote the cache; other versions may parse
+    /// tasks differently, so their caches are discarded
+    version: String,
+    /// `config_key()` of the settings the files were parsed with
+    #[serde(default)]
+    config: String,
+    files: HashMap<PathBuf, Entry>,
+}
+
+/// Hash of the settings that change what a file's tasks parse to; a cache
+/// written under other settings is discarded
+pub(crate) fn config_key(config: &Config) -> String {
+    let settings = format!(
+        "{:?}",
+        (
+            &config.assignee_keys,
+            config.flat_sub_items,
+            &config.code_todo_extensions
+        )
+    );
+    format!("{:016x}", fnv1a(settings.as_bytes()))
+}
+
+/// Index entries keyed by file
+type Files = HashMap<PathBuf, Entry>;
+
+/// Index held in memory for a watched vault
+struct Resident {
+    /// `config_key()` the entries were parsed under
+    config: String,
+    files: Arc<Files>,
+}
+
+/// Indexes held in memory for watched vaults, keyed by cache file
+static RESIDENT: Mutex<Option<HashMap<PathBuf, Resident>>> = Mutex::new(None);
+
+/// Keep the index at `path` in memory from now on, starting from the file
+pub fn keep_resident(path: &Path, config: &Config) {
+    let config = config_key(config);
+    let files = Arc::new(read(path, &config));
+    RESIDENT
+        .lock()
+        .unwrap()
+        .get_or_insert_with(HashMap::new)
+        .insert(path.to_path_buf(), Resident { config, files });
+}
+
+/// Drop the resident entries of `paths`, and of the files under any of them
+/// that is a directory
+pub fn invalidate(paths: &[PathBuf]) {
+    let mut resident = RESIDENT.lock().unwrap();
+    for Resident { files, .. } in resident.iter_mut().flat_map(|indexes| indexes.values_mut()) {
+        if files
+            .keys()
+            .any(|file| paths.iter().any(|path| file.starts_with(path)))
+        {
+            Arc::make_mut(files).retain(|file, _| !paths.iter().any(|path| file.starts_with(path)));
+        }
+    }
+}
+
+/// Drop every resident entry, for when changes may have gone unnoticed
+pub fn invalidate_all() {
+    let mut resident = RESIDENT.lock().unwrap();
+    for Resident { files, .. } in resident.iter_mut().flat_map(|indexes| indexes.values_mut()) {
+        *files = Arc::default();
+    }
+}
+
+/// The resident index at `path`, if it is kept in memory and was parsed
+/// under `config`
+fn resident(path: &Path, config: &str) -> Option<Arc<Files>> {
+    RESIDENT
+        .lock()
+        .unwrap()
+        .as_ref()
+        .and_then(|indexes| indexes.get(path))
+        .filter(|resident| resident.config == config)
+        .map(|resident| Arc::clone(&resident.files))
+}
+
+/// Task cache for one vault
+pub struct TaskCache {
+    path: PathBuf,
+    /// `config_key()` of the settings this run parses with
+    config: String,
+    previous: Arc<Files>,
+    /// Entries for the files scanned in this run
+    current: Mutex<HashMap<PathBuf, Entry>>,
+    /// Whether `previous` is the resident index
+    resident: bool,
+    /// Whether any file was parsed in this run
+    extracted: AtomicBool,
+}
+
+impl TaskCache {
+    /// Open the cache for the vault at `base_path`, if a cache directory exists
+    pub fn for_vault(base_path: &Path, config: &Config) -> Option<Self> {
+        vault_cache_path(base_path).map(|path| Self::open(path, config))
+    }
+
+    /// Open a cache file for parsing with `config`; a missing, unreadable, or
+    /// outdated file, or one written under other settings, starts empty
+    ///
+    /// A resident index is used as is instead of reading the file.
+    pub fn open(path: PathBuf, config: &Config) -> Self {
+        let config = config_key(config);
+        let (previous, resident) = match resident(&path, &config) {
+            Some(files) => (files, true),
+            None => (Arc::new(read(&path, &config)), false),
+        };
+
+        Self {
+            path,
+            config,
+            previous,
+            current: Mutex::new(HashMap::new()),
+            resident,
+            extracted: AtomicBool::new(false),
+        }
+    }
+
+    /// An empty cache that replaces the file at `path` when saved
+    pub fn empty(path: PathBuf, config: &Config) -> Self {
+        Self {
+            path,
+            config: config_key(config),
+            previous: Arc::default(),
+            current: Mutex::new(HashMap::new()),
+            resident: false,
+            extracted: AtomicBool::new(false),
+        }
+    }
+
+    /// Path of the cache file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Number of files looked up in this run
+    pub fn len(&self) -> usize {
+        self.current.lock().unwrap().len()
+    }
+
+    /// Whether no file was looked up in this run
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Tasks of `file`, from the cache if the file is unchanged, otherwise
+    /// from `extract`
+    pub fn get_or_extract(
+        &self,
+        file: &Path,
+        extract: impl FnOnce() -> Result<Vec<Task>, Box<dyn Error>>,
+    ) -> Result<Vec<Task>, Box<dyn Error>> {
+        self.lookup(file, |entry| &mut entry.tasks, extract)
+    }
+
+    /// Headings of `file` as a flat list, from the cache if the file is
+    /// unchanged, otherwise from `extract`
+    pub fn headings_or_extract(
+        &self,
+        file: &Path,
+        extract: impl FnOnce() -> Result<Vec<Heading>, Box<dyn Error>>,
+    ) -> Result<Vec<Heading>, Box<dyn Error>> {
+        self.lookup(file, |entry| &mut entry.headings, extract)
+    }
+
+    /// One field of the entry for `file`, extracted only if neither this run
+    /// nor the saved cache has it for the file as it is now
+    fn lookup<T: Clone>(
+        &self,
+        file: &Path,
+        field: impl Fn(&mut Entry) -> &mut Option<T>,
+        extract: impl FnOnce() -> Result<T, Box<dyn Error>>,
+    ) -> Result<T, Box<dyn Error>> {
+        let stamp = stamp(file)?;
+        let mut entry = {
+            let current = self.current.lock().unwrap();
+            current
+                .get(file)
+                .or_else(|| self.previous.get(file))
+                .filter(|entry| entry.is_current(stamp))
+                .cloned()
+                .unwrap_or_else(|| Entry::new(stamp))
+        };
+
+        // Extract without holding the lock, so files are still parsed in
+        // parallel
+        let value = match field(&mut entry).clone() {
+            Some(value) => value,
+            None => {
+                self.extracted.store(true, Ordering::Relaxed);
+                extract()?
+            }
+        };
+
+        let mut current = self.current.lock().unwrap();
+        let mut entry = match current.remove(file) {
+            // Keep what another lookup of the same file stored meanwhile
+            Some(stored) if stored.is_current(stamp) => stored,
+            _ => entry,
+        };
+        *field(&mut entry) = Some(value.clone());
+        current.insert(file.to_path_buf(), entry);
+        Ok(value)
+    }
+
+    /// How current the saved index is for the vault notes in `notes`
+    pub fn status(&self, notes: &[PathBuf]) -> IndexStatus {
+        let mut status = IndexStatus {
+            files: self.previous.len(),
+            ..Default::default()
+        };
+        for (file, entry) in self.previous.iter() {
+            match stamp(file) {
+                Ok(stamp) if entry.is_current(stamp) => status.current += 1,
+                Ok(_) => status.changed += 1,
+                Err(_) => status.deleted += 1,
+            }
+            status.with_tasks += usize::from(entry.tasks.is_some());
+            status.with_outlines += usize::from(entry.headings.is_some());
+        }
+        status.not_indexed = notes
+            .iter()
+            .filter(|note| !self.previous.contains_key(*note))
+            .count();
+        status
+    }
+
+    /// Write the entries for the files scanned in this run, dropping the rest
+    pub fn save(self) -> Result<(), Box<dyn Error>> {
+        let files = self.current.into_inner().unwrap();
+        let dropped = files.len() != self.previous.len()
+            || self.previous.keys().any(|file| !files.contains_key(file));
+        store(
+            &self.path,
+            &self.config,
+            files,
+            self.resident,
+            dropped || self.extracted.into_inner(),
+        )
+    }
+
+    /// Write the entries looked up in this run, keeping the other cached files
+    pub fn update(self) -> Result<(), Box<dyn Error>> {
+        let mut files = Arc::unwrap_or_clone(self.previous);
+        files.extend(self.current.into_inner().unwrap());
+        store(
+            &self.path,
+            &self.config,
+            files,
+            self.resident,
+            self.extracted.into_inner(),
+        )
+    }
+}
+
+/// Keep `files` as the index at `path`: in memory if it is resident, and on
+/// disk unless it is resident and nothing changed
+fn store(
+    path: &Path,
+    config: &str,
+    files: HashMap<PathBuf, Entry>,
+    resident: bool,
+    changed: bool,
+) -> Result<(), Box<dyn Error>> {
+    if !resident {
+        return write(path, config, &files);
+    }
+    if changed {
+        write(path, config, &files)?;
+    }
+    if let Some(indexes) = RESIDENT.lock().unwrap().as_mut() {
+        let resident = Resident {
+            config: config.to_string(),
+            files: Arc::new(files),
+        };
+        indexes.insert(path.to_path_buf(), resident);
+    }
+    Ok(())
+}
+
+/// Entries of the cache file at `path`; a missing, unreadable, or outdated
+/// file, or one written under settings other than `config`, has none
+fn read(path: &Path, config: &str) -> Files {
+    load(path, config).1
+}
+
+/// State and entries of the cache file at `path`, which is outdated unless
+/// it was written under the settings with `config_key()` `config`
+fn load(path: &Path, config: &str) -> (IndexState, Files) {
+    /// The version and settings alone, which parse even if the entry format
+    /// changed
+    #[derive(Deserialize)]
+    struct Version {
+        version: String,
+        #[serde(default)]
+        config: String,
+    }
+
+    let bytes = match fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return (IndexState::Missing, Files::new());
+        }
+        Err(_) => return (IndexState::Corrupt, Files::new()),
+    };
+    match serde_json::from_slice::<Version>(&bytes) {
+        Ok(Version {
+            version,
+            config: written,
+        }) if version == env!("CARGO_PKG_VERSION") && written == config => {}
+        Ok(_) => return (IndexState::Outdated, Files::new()),
+        Err(_) => return (IndexState::Corrupt, Files::new()),
+    }
+    match serde_json::from_slice::<CacheFile>(&bytes) {
+        Ok(cache) => (IndexState::Ok, cache.files),
+        Err(_) => (IndexState::Corrupt, Files::new()),
+    }
+}
+
+/// Check the entries of an index against the vault at `base_path`, returning
+/// the entries worth keeping: current files inside the vault, once each
+fn check_entries(files: Files, base_path: &Path) -> (IndexCheck, Files) {
+    let vault = fs::canonicalize(base_path).unwrap_or_else(|_| base_path.to_path_buf());
+    let mut check = IndexCheck {
+        files: files.len(),
+        ..Default::default()
+    };
+
+    // Sorted so the same entry of a duplicated file is kept every time
+    let mut files: Vec<_> = files.into_iter().collect();
+    files.sort_by(|(a, _), (b, _)| a.cmp(b));
+    let mut seen = HashSet::new();
+    let mut kept = Files::new();
+    for (file, entry) in files {
+        let (Ok(stamp), Ok(canonical)) = (stamp(&file), fs::canonicalize(&file)) else {
+            check.deleted += 1;
+            continue;
+        };
+        if !canonical.starts_with(&vault) {
+            check.outside_vault += 1;
+        } else if !entry.is_current(stamp) {
+            check.changed += 1;
+        } else if !seen.insert(canonical) {
+            check.duplicates += 1;
+        } else {
+            check.current += 1;
+            kept.insert(file, entry);
+        }
+    }
+    (check, kept)
+}
+
+/// Check the index at `path` against the vault at `base_path` and its
+/// `config`
+pub fn verify(path: &Path, base_path: &Path, config: &Config) -> (IndexState, IndexCheck) {
+    let (state, files) = load(path, &config_key(config));
+    (state, check_entries(files, base_path).0)
+}
+
+/// Rewrite the index at `path` with only its current entries, returning what
+/// was found before
+///
+/// A corrupt or outdated index is replaced with an empty one; a missing
+/// index is left missing.
+pub fn compact(
+    path: &Path,
+    base_path: &Path,
+    config: &Config,
+) -> Result<(IndexState, IndexCheck), Box<dyn Error>> {
+    let config = config_key(config);
+    let (state, files) = load(path, &config);
+    let (check, kept) = check_entries(files, base_path);
+    let clean = state == IndexState::Ok && check.is_clean();
+    if state != IndexState::Missing && !clean {
+        write(path, &config, &kept)?;
+    }
+    Ok((state, check))
+}
+
+/// Cache file for the vault at `base_path`, if a cache directory exists
+pub fn vault_cache_path(base_path: &Path) -> Option<PathBuf> {
+    let vault = fs::canonicalize(base_path).unwrap_or_else(|_| base_path.to_path_buf());
+    let name = format!("{:016x}.json", fnv1a(vault.to_string_lossy().as_bytes()));
+    cache_dir().map(|dir| dir.join(name))
+}
+
+/// Modification time in nanoseconds and size of `file`
+pub(crate) fn stamp(file: &Path) -> std::io::Result<(u128, u64)> {
+    let metadata = fs::metadata(file)?;
+    let modified = metadata
+        .modified()?
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    Ok((modified, metadata.len()))
+}
+
+fn write(path: &Path, config: &str, files: &HashMap<PathBuf, Entry>) -> Result<(), Box<dyn Error>> {
+    static WRITES: AtomicUsize = AtomicUsize::new(0);
+
+    /// `CacheFile` borrowing its entries
+    #[derive(Serialize)]
+    struct Contents<'a> {
+        version: &'a str,
+        config: &'a str,
+        files: &'a HashMap<PathBuf, Entry>,
+    }
+
+    let cache = Contents {
+        version: env!("CARGO_PKG_VERSION"),
+        config,
+        files,
+    };
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    // Write to a temporary file and rename, so concurrent runs (and
+    // concurrent server requests) never read a partial cache
+    let temp = path.with_extension(format!(
+        "{}-{}.tmp",
+        std::process::id(),
+        WRITES.fetch_add(1, Ordering::Relaxed)
+    ));
+    fs::write(&temp, serde_json::to_vec(&cache)?)?;
+    fs::rename(&temp, path)?;
+    Ok(())
+}
+
+/// 64-bit FNV-1a, stable across Rust versions unlike `DefaultHasher`
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::extractor::TaskExtractor;
+    use crate::outline_extractor::OutlineExtractor;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    fn contents(tasks: &[Task]) -> Vec<&str> {
+        let mut contents: Vec<&str> = tasks.iter().map(|t| t.content.as_str()).collect();
+        contents.sort();
+        contents
+    }
+
+    #[test]
+    fn test_cache_reuses_unchanged_files() {
+        let vault = TempDir::new().unwrap();
+        let cache_dir = TempDir::new().unwrap();
+        let cache_file = cache_dir.path().join("vault.json");
+        fs::write(vault.path().join("a.md"), "- [ ] Alpha\n").unwrap();
+        fs::write(vault.path().join("b.md"), "- [ ] Beta\n").unwrap();
+
+        let extractor = TaskExtractor::new(Arc::new(Config::default()));
+        let cache = TaskCache::open(cache_file.clone(), &Config::default());
+        let (tasks, _) = extractor
+            .extract_tasks_with_cache(vault.path(), &cache)
+            .unwrap();
+        assert_eq!(contents(&tasks), vec!["Alpha", "Beta"]);
+        cache.save().unwrap();
+
+        // Tamper with the cached tasks of a.md: an unchanged file must be
+        // served from the cache
+        let mut saved: CacheFile = serde_json::from_slice(&fs::read(&cache_file).unwrap()).unwrap();
+        let entry = saved.files.get_mut(&vault.path().join("a.md")).unwrap();
+        entry.tasks.as_mut().unwrap()[0].content = "Cached alpha".to_string();
+        fs::write(&cache_file, serde_json::to_vec(&saved).unwrap()).unwrap();
+
+        // A changed size invalidates b.md; c.md is new
+        fs::write(vault.path().join("b.md"), "- [ ] Beta two\n").unwrap();
+        fs::write(vault.path().join("c.md"), "- [ ] Gamma\n").unwrap();
+
+        let cache = TaskCache::open(cache_file.clone(), &Config::default());
+        let (tasks, _) = extractor
+            .extract_tasks_with_cache(vault.path(), &cache)
+            .unwrap();
+        assert_eq!(contents(&tasks), vec!["Beta two", "Cached alpha", "Gamma"]);
+        cache.save().unwrap();
+
+        // Deleted files are dropped from the cache
+        fs::remove_file(vault.path().join("c.md")).unwrap();
+        let cache = TaskCache::open(cache_file.clone(), &Config::default());
+        extractor
+            .extract_tasks_with_cache(vault.path(), &cache)
+            .unwrap();
+        cache.save().unwrap();
+        let saved: CacheFile = serde_json::from_slice(&fs::read(&cache_file).unwrap()).unwrap();
+        assert_eq!(saved.files.len(), 2);
+    }
+
+    #[test]
+    fn test_cache_stores_outlines_with_tasks() {
+        let vault = TempDir::new().unwrap();
+        let cache_dir = TempDir::new().unwrap();
+        let cache_file = cache_dir.path().join("vault.json");
+        let a = vault.path().join("a.md");
+        let b = vault.path().join("b.md");
+        fs::write(&a, "# Plan\n- [ ] Alpha\n").unwrap();
+        fs::write(&b, "# Other\n").unwrap();
+
+        let extractor = TaskExtractor::new(Arc::new(Config::default()));
+        let outlines = OutlineExtractor::new();
+        let cache = TaskCache::open(cache_file.clone(), &Config::default());
+        extractor
+            .extract_tasks_with_cache(vault.path(), &cache)
+            .unwrap();
+        let headings = outlines.file_headings(&a, Some(&cache)).unwrap();
+        assert_eq!(headings[0].title, "Plan");
+        cache.save().unwrap();
+
+        // Both the tasks and the outline of a.md are stored
+        let mut saved: CacheFile = serde_json::from_slice(&fs::read(&cache_file).unwrap()).unwrap();
+        let entry = saved.files.get_mut(&a).unwrap();
+        assert!(entry.tasks.is_some());
+        entry.headings.as_mut().unwrap()[0].title = "Cached plan".to_string();
+        fs::write(&cache_file, serde_json::to_vec(&saved).unwrap()).unwrap();
+
+        // An outline lookup serves unchanged files from the cache and keeps
+        // the files it didn't look at
+        let cache = TaskCache::open(cache_file.clone(), &Config::default());
+        let headings = outlines.file_headings(&a, Some(&cache)).unwrap();
+        assert_eq!(headings[0].title, "Cached plan");
+        cache.update().unwrap();
+        let saved: CacheFile = serde_json::from_slice(&fs::read(&cache_file).unwrap()).unwrap();
+        assert_eq!(saved.files.len(), 2);
+        assert!(saved.files[&a].tasks.is_some());
+
+        // A changed file drops its cached tasks along with the outline
+        fs::write(&a, "# Plan two\n").unwrap();
+        let cache = TaskCache::open(cache_file.clone(), &Config::default());
+        let headings = outlines.file_headings(&a, Some(&cache)).unwrap();
+        assert_eq!(headings[0].title, "Plan two");
+        cache.update().unwrap();
+        let saved: CacheFile = serde_json::from_slice(&fs::read(&cache_file).unwrap()).unwrap();
+        assert!(saved.files[&a].tasks.is_none());
+    }
+
+    #[test]
+    fn test_resident_cache() {
+        let vault = TempDir::new().unwrap();
+        let cache_dir = TempDir::new().unwrap();
+        let cache_file = cache_dir.path().join("vault.json");
+        let a = vault.path().join("a.md");
+        fs::write(&a, "- [ ] Alpha\n").unwrap();
+        fs::write(vault.path().join("b.md"), "- [ ] Beta\n").unwrap();
+
+        keep_resident(&cache_file, &Config::default());
+        let extractor = TaskExtractor::new(Arc::new(Config::default()));
+        let cache = TaskCache::open(cache_file.clone(), &Config::default());
+        extractor
+            .extract_tasks_with_cache(vault.path(), &cache)
+            .unwrap();
+        cache.save().unwrap();
+        assert!(cache_file.exists());
+
+        // An unchanged run is served from memory and leaves the file alone
+        fs::remove_file(&cache_file).unwrap();
+        let cache = TaskCache::open(cache_file.clone(), &Config::default());
+        assert!(cache.resident);
+        assert_eq!(cache.previous.len(), 2);
+        extractor
+            .extract_tasks_with_cache(vault.path(), &cache)
+            .unwrap();
+        cache.save().unwrap();
+        assert!(!cache_file.exists());
+
+        invalidate(std::slice::from_ref(&a));
+        let cache = TaskCache::open(cache_file.clone(), &Config::default());
+        assert_eq!(cache.previous.len(), 1);
+        assert!(!cache.previous.contains_key(&a));
+        extractor
+            .extract_tasks_with_cache(vault.path(), &cache)
+            .unwrap();
+        cache.save().unwrap();
+        assert!(cache_file.exists());
+
+        // A directory event drops everything under it
+        invalidate(&[vault.path().to_path_buf()]);
+        assert!(
+            TaskCache::open(cache_file, &Config::default())
+                .previous
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_outdated_cache_is_discarded() {
+        let cache_dir = TempDir::new().unwrap();
+        let cache_file = cache_dir.path().join("vault.json");
+        let entry = r#"{"modified": 1, "len": 1, "tasks": []}"#;
+        fs::write(
+            &cache_file,
+            format!(r#"{{"version": "0.0.0", "files": {{"a.md": {}}}}}"#, entry),
+        )
+        .unwrap();
+        assert!(
+            TaskCache::open(cache_file.clone(), &Config::default())
+                .previous
+                .is_empty()
+        );
+
+        let current = format!(
+            r#"{{"version": "{}", "config": "{}", "files": {{"a.md": {}}}}}"#,
+            env!("CARGO_PKG_VERSION"),
+            config_key(&Config::default()),
+            entry
+        );
+        fs::write(&cache_file, current).unwrap();
+        assert_eq!(
+            TaskCache::open(cache_file.clone(), &Config::default())
+                .previous
+                .len(),
+            1
+        );
+
+        fs::write(&cache_file, "not json").unwrap();
+        assert!(
+            TaskCache::open(cache_file, &Config::default())
+                .previous
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_cache_discarded_when_parse_settings_change() {
+        let vault = TempDir::new().unwrap();
+        let cache_dir = TempDir::new().unwrap();
+        let cache_file = cache_dir.path().join("vault.json");
+        fs::write(
+            vault.path().join("a.md"),
+            "- [ ] Ship it who:: bob\n  - Pack\n    - Labels\n",
+        )
+        .unwrap();
+
+        let scan = |config: Config| {
+            let cache = TaskCache::open(cache_file.clone(), &config);
+            let (tasks, _) = TaskExtractor::new(Arc::new(config))
+                .extract_tasks_with_cache(vault.path(), &cache)
+                .unwrap();
+            cache.save().unwrap();
+            tasks.into_iter().next().unwrap()
+        };
+        let task = scan(Config::default());
+        assert_eq!(task.assignee, None);
+
+        // A cache written under other settings isn't used
+        let task = scan(Config {
+            assignee_keys: vec!["who".to_string()],
+            flat_sub_items: true,
+            ..Config::default()
+        });
+        assert_eq!(task.assignee.as_deref(), Some("bob"));
+        assert_eq!(
+            serde_json::to_value(&task.sub_items).unwrap(),
+            serde_json::json!(["Pack", "Labels"])
+        );
+        assert_eq!(
+            verify(&cache_file, vault.path(), &Config::default()).0,
+            IndexState::Outdated
+        );
+    }
+
+    #[test]
+    fn test_fnv1a() {
+        assert_eq!(fnv1a(b""), 0xcbf29ce484222325);
+        assert_eq!(fnv1a(b"a"), 0xaf63dc4c8601ec8c);
+    }
+}
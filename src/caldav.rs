@@ -0,0 +1,484 @@
+//! Minimal read-only CalDAV server exposing vault tasks as VTODOs
+//!
+//! Serves a single calendar collection at `/caldav/tasks/` so clients such as
+//! Apple Reminders or Tasks.org (via DAVx⁵) can subscribe to vault tasks.
+//! Discovery (`/.well-known/caldav`, principal, calendar home), `PROPFIND`,
+//! `REPORT` (calendar-query and calendar-multiget), and `GET` are supported;
+//! write methods are rejected.
+
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use axum::{
+    Router,
+    body::Bytes,
+    extract::{Path, State},
+    http::{HeaderMap, HeaderValue, Method, StatusCode, header},
+    response::{IntoResponse, Redirect, Response},
+    routing::{any, get},
+};
+use regex::Regex;
+
+use crate::config::Config;
+use crate::extractor::TaskExtractor;
+use crate::http_router::xml_escape;
+use crate::ics;
+
+/// URL of the principal and calendar home
+pub const CALDAV_ROOT: &str = "/caldav/";
+
+/// URL of the task collection
+pub const TASKS_COLLECTION: &str = "/caldav/tasks/";
+
+const DAV_HEADER: &str = "1, calendar-access";
+const ALLOW_HEADER: &str = "OPTIONS, GET, HEAD, PROPFIND, REPORT";
+
+#[derive(Clone)]
+struct CalDavState {
+    base_path: PathBuf,
+    extractor: Arc<TaskExtractor>,
+}
+
+/// A rendered task resource in the collection
+struct TaskResource {
+    href: String,
+    etag: String,
+    ics: String,
+}
+
+impl CalDavState {
+    /// Render every task in the vault as a calendar resource
+    async fn resources(&self) -> Result<Vec<TaskResource>, StatusCode> {
+        let state = self.clone();
+        tokio::task::spawn_blocking(move || state.collect_resources())
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    }
+
+    fn collect_resources(&self) -> Result<Vec<TaskResource>, StatusCode> {
+        // CalDAV has nowhere to report skipped files, so their tasks are
+        // just left out
+        let (tasks, _warnings) = self
+            .extractor
+            .extract_tasks_with_warnings(&self.base_path)
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+        let tasks: Vec<_> = tasks.into_iter().filter(|task| !task.quoted).collect();
+        let uids = ics::task_uids(&self.base_path, &tasks);
+        let resources = tasks
+            .iter()
+            .zip(uids)
+            .map(|(task, uid)| {
+                let ics = ics::calendar([ics::vtodo(task, &uid).as_str()]);
+                TaskResource {
+                    href: format!("{}{}.ics", TASKS_COLLECTION, uid),
+                    etag: format!("\"{:016x}\"", ics::stable_hash(&ics)),
+                    ics,
+                }
+            })
+            .collect();
+
+        Ok(resources)
+    }
+}
+
+/// Build the CalDAV routes for a vault
+///
+/// The returned router carries its own state, so it can be merged into the
+/// HTTP server's router regardless of that router's state type.
+pub fn router<S>(base_path: PathBuf, config: Arc<Config>) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let state = CalDavState {
+        base_path,
+        extractor: Arc::new(TaskExtractor::new(config)),
+    };
+
+    Router::new()
+        .route(
+            "/.well-known/caldav",
+            get(|| async { Redirect::permanent(CALDAV_ROOT) }),
+        )
+        .route(CALDAV_ROOT, any(root_handler))
+        .route(TASKS_COLLECTION, any(collection_handler))
+        .route("/caldav/tasks/{resource}", any(resource_handler))
+        .with_state(state)
+}
+
+/// Wrap `<d:response>` elements in a 207 Multi-Status response
+fn multistatus(responses: &str) -> Response {
+    let body = format!(
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n\
+         <d:multistatus xmlns:d=\"DAV:\" xmlns:c=\"urn:ietf:params:xml:ns:caldav\" \
+         xmlns:cs=\"http://calendarserver.org/ns/\">{}</d:multistatus>",
+        responses
+    );
+
+    (
+        StatusCode::MULTI_STATUS,
+        [
+            (header::CONTENT_TYPE, "application/xml; charset=utf-8"),
+            (header::HeaderName::from_static("dav"), DAV_HEADER),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+fn prop_response(href: &str, props: &str) -> String {
+    format!(
+        "<d:response><d:href>{}</d:href><d:propstat><d:prop>{}</d:prop>\
+         <d:status>HTTP/1.1 200 OK</d:status></d:propstat></d:response>",
+        xml_escape(href),
+        props
+    )
+}
+
+fn principal_props() -> String {
+    format!(
+        "<d:resourcetype><d:collection/><d:principal/></d:resourcetype>\
+         <d:displayname>Vault</d:displayname>\
+         <d:current-user-principal><d:href>{root}</d:href></d:current-user-principal>\
+         <d:principal-URL><d:href>{root}</d:href></d:principal-URL>\
+         <c:calendar-home-set><d:href>{root}</d:href></c:calendar-home-set>",
+        root = CALDAV_ROOT
+    )
+}
+
+fn collection_props(resources: &[TaskResource]) -> String {
+    let mut etags = String::new();
+    for resource in resources {
+        etags.push_str(&resource.etag);
+    }
+
+    format!(
+        "<d:resourcetype><d:collection/><c:calendar/></d:resourcetype>\
+         <d:displayname>Vault Tasks</d:displayname>\
+         <d:current-user-principal><d:href>{root}</d:href></d:current-user-principal>\
+         <d:current-user-privilege-set><d:privilege><d:read/></d:privilege></d:current-user-privilege-set>\
+         <c:supported-calendar-component-set><c:comp name=\"VTODO\"/></c:supported-calendar-component-set>\
+         <cs:getctag>{ctag:016x}</cs:getctag>",
+        root = CALDAV_ROOT,
+        ctag = ics::stable_hash(&etags)
+    )
+}
+
+fn resource_props(resource: &TaskResource, include_data: bool) -> String {
+    let mut props = format!(
+        "<d:resourcetype/><d:getetag>{}</d:getetag>\
+         <d:getcontenttype>text/calendar; charset=utf-8; component=VTODO</d:getcontenttype>",
+        xml_escape(&resource.etag)
+    );
+    if include_data {
+        let _ = write!(
+            props,
+            "<c:calendar-data>{}</c:calendar-data>",
+            xml_escape(&resource.ics)
+        );
+    }
+    props
+}
+
+fn depth(headers: &HeaderMap) -> u8 {
+    match headers.get("depth").and_then(|v| v.to_str().ok()) {
+        Some("0") => 0,
+        _ => 1,
+    }
+}
+
+/// Response for methods not handled by a resource
+fn unsupported(method: &Method) -> Response {
+    let status = match *method {
+        Method::PUT | Method::DELETE | Method::POST | Method::PATCH => StatusCode::FORBIDDEN,
+        _ => StatusCode::METHOD_NOT_ALLOWED,
+    };
+    (status, [(header::ALLOW, ALLOW_HEADER)]).into_response()
+}
+
+fn options() -> Response {
+    (
+        StatusCode::OK,
+        [
+            (header::ALLOW, HeaderValue::from_static(ALLOW_HEADER)),
+            (
+                header::HeaderName::from_static("dav"),
+                HeaderValue::from_static(DAV_HEADER),
+            ),
+        ],
+    )
+        .into_response()
+}
+
+async fn root_handler(method: Method, headers: HeaderMap) -> Response {
+    match method.as_str() {
+        "OPTIONS" => options(),
+        "PROPFIND" => {
+            let mut responses = prop_response(CALDAV_ROOT, &principal_props());
+            if depth(&headers) > 0 {
+                // Collection properties without the item ETags: clients read
+                // the ctag from the collection itself
+                responses.push_str(&prop_response(TASKS_COLLECTION, &collection_props(&[])));
+            }
+            multistatus(&responses)
+        }
+        _ => unsupported(&method),
+    }
+}
+
+async fn collection_handler(
+    State(state): State<CalDavState>,
+    method: Method,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    match method.as_str() {
+        "OPTIONS" => options(),
+        "PROPFIND" => {
+            let resources = match state.resources().await {
+                Ok(resources) => resources,
+                Err(status) => return status.into_response(),
+            };
+
+            let mut responses = prop_response(TASKS_COLLECTION, &collection_props(&resources));
+            if depth(&headers) > 0 {
+                for resource in &resources {
+                    responses.push_str(&prop_response(
+                        &resource.href,
+                        &resource_props(resource, false),
+                    ));
+                }
+            }
+            multistatus(&responses)
+        }
+        "REPORT" => {
+            let resources = match state.resources().await {
+                Ok(resources) => resources,
+                Err(status) => return status.into_response(),
+            };
+
+            let body = String::from_utf8_lossy(&body);
+            let requested = multiget_hrefs(&body);
+
+            let mut responses = String::new();
+            for resource in &resources {
+                if requested
+                    .as_ref()
+                    .is_none_or(|hrefs| hrefs.contains(&resource.href))
+                {
+                    responses.push_str(&prop_response(
+                        &resource.href,
+                        &resource_props(resource, true),
+                    ));
+                }
+            }
+            multistatus(&responses)
+        }
+        _ => unsupported(&method),
+    }
+}
+
+async fn resource_handler(
+    State(state): State<CalDavState>,
+    Path(resource): Path<String>,
+    method: Method,
+) -> Response {
+    match method.as_str() {
+        "OPTIONS" => options(),
+        "GET" | "HEAD" | "PROPFIND" => {
+            let resources = match state.resources().await {
+                Ok(resources) => resources,
+                Err(status) => return status.into_response(),
+            };
+            let href = format!("{}{}", TASKS_COLLECTION, resource);
+            let Some(found) = resources.iter().find(|r| r.href == href) else {
+                return StatusCode::NOT_FOUND.into_response();
+            };
+
+            if method.as_str() == "PROPFIND" {
+                return multistatus(&prop_response(&found.href, &resource_props(found, false)));
+            }
+
+            let body = if method == Method::HEAD {
+                String::new()
+            } else {
+                found.ics.clone()
+            };
+            (
+                StatusCode::OK,
+                [
+                    (header::CONTENT_TYPE, "text/calendar; charset=utf-8"),
+                    (header::ETAG, found.etag.as_str()),
+                ],
+                body,
+            )
+                .into_response()
+        }
+        _ => unsupported(&method),
+    }
+}
+
+/// Extract the hrefs from a calendar-multiget REPORT body
+///
+/// Returns `None` for other reports (e.g. calendar-query), which return every
+/// task: the collection only contains VTODOs and filters are not evaluated.
+fn multiget_hrefs(body: &str) -> Option<Vec<String>> {
+    if !body.contains("calendar-multiget") {
+        return None;
+    }
+
+    let href_pattern = Regex::new(r"<(?:[\w-]+:)?href>\s*([^<]+?)\s*</(?:[\w-]+:)?href>").unwrap();
+    Some(
+        href_pattern
+            .captures_iter(body)
+            .map(|caps| caps[1].replace("&amp;", "&"))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::{Body, to_bytes};
+    use axum::http::Request;
+    use std::fs;
+    use tempfile::TempDir;
+    use tower::ServiceExt;
+
+    fn create_router() -> (TempDir, Router) {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("todo.md"),
+            "- [ ] Write report #work 📅 2025-01-10\n- [x] Send invoice\n",
+        )
+        .unwrap();
+
+        let router = router(temp_dir.path().to_path_buf(), Arc::new(Config::default()));
+        (temp_dir, router)
+    }
+
+    async fn send(
+        router: &Router,
+        method: &str,
+        uri: &str,
+        depth: &str,
+        body: &str,
+    ) -> (StatusCode, String) {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("Depth", depth)
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, String::from_utf8(bytes.to_vec()).unwrap())
+    }
+
+    fn hrefs(body: &str) -> Vec<String> {
+        let pattern = Regex::new(r"<d:href>([^<]+\.ics)</d:href>").unwrap();
+        pattern
+            .captures_iter(body)
+            .map(|caps| caps[1].to_string())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_well_known_redirect() {
+        let (_temp_dir, router) = create_router();
+        let (status, _) = send(&router, "GET", "/.well-known/caldav", "0", "").await;
+        assert_eq!(status, StatusCode::PERMANENT_REDIRECT);
+    }
+
+    #[tokio::test]
+    async fn test_principal_discovery() {
+        let (_temp_dir, router) = create_router();
+        let (status, body) = send(&router, "PROPFIND", CALDAV_ROOT, "1", "").await;
+
+        assert_eq!(status, StatusCode::MULTI_STATUS);
+        assert!(body.contains("<c:calendar-home-set><d:href>/caldav/</d:href>"));
+        assert!(body.contains("<d:href>/caldav/tasks/</d:href>"));
+        assert!(body.contains("<c:comp name=\"VTODO\"/>"));
+    }
+
+    #[tokio::test]
+    async fn test_collection_propfind_lists_tasks() {
+        let (_temp_dir, router) = create_router();
+
+        let (status, body) = send(&router, "PROPFIND", TASKS_COLLECTION, "0", "").await;
+        assert_eq!(status, StatusCode::MULTI_STATUS);
+        assert!(body.contains("<cs:getctag>"));
+        assert!(hrefs(&body).is_empty());
+
+        let (_, body) = send(&router, "PROPFIND", TASKS_COLLECTION, "1", "").await;
+        assert_eq!(hrefs(&body).len(), 2);
+        assert!(body.contains("<d:getetag>&quot;"));
+        assert!(!body.contains("calendar-data"));
+    }
+
+    #[tokio::test]
+    async fn test_report_query_and_multiget() {
+        let (_temp_dir, router) = create_router();
+
+        let query = r#"<c:calendar-query xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav"><d:prop><d:getetag/><c:calendar-data/></d:prop></c:calendar-query>"#;
+        let (status, body) = send(&router, "REPORT", TASKS_COLLECTION, "1", query).await;
+        assert_eq!(status, StatusCode::MULTI_STATUS);
+        let all = hrefs(&body);
+        assert_eq!(all.len(), 2);
+        assert!(body.contains("SUMMARY:Write report #work"));
+        assert!(body.contains("SUMMARY:Send invoice"));
+
+        let multiget = format!(
+            r#"<c:calendar-multiget xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav"><d:prop><c:calendar-data/></d:prop><d:href>{}</d:href></c:calendar-multiget>"#,
+            all[0]
+        );
+        let (_, body) = send(&router, "REPORT", TASKS_COLLECTION, "1", &multiget).await;
+        assert_eq!(hrefs(&body), vec![all[0].clone()]);
+    }
+
+    #[tokio::test]
+    async fn test_get_resource() {
+        let (_temp_dir, router) = create_router();
+
+        let (_, body) = send(&router, "PROPFIND", TASKS_COLLECTION, "1", "").await;
+        let href = hrefs(&body).remove(0);
+
+        let (status, ics) = send(&router, "GET", &href, "0", "").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ics.contains("BEGIN:VTODO\r\n"));
+
+        let (status, _) = send(&router, "GET", "/caldav/tasks/missing.ics", "0", "").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_writes_are_rejected() {
+        let (_temp_dir, router) = create_router();
+        let (status, _) = send(
+            &router,
+            "PUT",
+            "/caldav/tasks/new.ics",
+            "0",
+            "BEGIN:VCALENDAR\r\nEND:VCALENDAR\r\n",
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+
+    #[test]
+    fn test_multiget_hrefs() {
+        assert_eq!(multiget_hrefs("<c:calendar-query/>"), None);
+        assert_eq!(
+            multiget_hrefs(
+                "<C:calendar-multiget><D:href> /caldav/tasks/a.ics </D:href><href>/caldav/tasks/b.ics</href></C:calendar-multiget>"
+            ),
+            Some(vec![
+                "/caldav/tasks/a.ics".to_string(),
+                "/caldav/tasks/b.ics".to_string()
+            ])
+        );
+    }
+}
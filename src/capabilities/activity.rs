@@ -0,0 +1,1597 @@
+//! Activity capability
+//!
+//! Provides time-series views of vault activity, such as per-day task
+//! completion counts for calendar heatmaps, open-task burndown series,
+//! timesheets built from time-tracking annotations, and per-note progress.
+
+use crate::capabilities::daily_notes::pattern::get_daily_note_relative_path;
+use crate::capabilities::tasks::exclude_archived;
+use crate::capabilities::{CapabilityResult, check_strict};
+use crate::config::Config;
+use crate::error::{Error, invalid_date, invalid_params, not_found, scan_error};
+use crate::extractor::{Task, TaskExtractor, TaskStatus};
+use crate::filter::{FilterOptions, filter_tasks};
+use crate::frontmatter::body_after_frontmatter;
+use crate::path_resolver::to_slash;
+use crate::warnings::ExtractionWarning;
+use chrono::{Datelike, Days, Local, Months, NaiveDate};
+use clap::{CommandFactory, FromArgMatches, Parser};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Operation metadata for completion_heatmap
+pub mod completion_heatmap {
+    pub const DESCRIPTION: &str = "Get per-day counts of completed tasks over a date range (defaults to the last year), formatted for GitHub-style calendar heatmaps. Optionally includes words written in daily notes.";
+    #[allow(dead_code)]
+    pub const CLI_NAME: &str = "completion-heatmap";
+    pub const HTTP_PATH: &str = "/api/activity/heatmap";
+}
+
+/// Operation metadata for project_burndown
+pub mod project_burndown {
+    pub const DESCRIPTION: &str = "Reconstruct open-task counts over time for a tag or folder from task created/completed dates. Returns a time series for charting project progress.";
+    #[allow(dead_code)]
+    pub const CLI_NAME: &str = "project-burndown";
+    pub const HTTP_PATH: &str = "/api/activity/burndown";
+}
+
+/// Operation metadata for time_report
+pub mod time_report {
+    pub const DESCRIPTION: &str = "Total the time logged on tasks (`⏱ 1h30m` or `spent:: 45m` annotations), grouped by tag, file, or day. Useful for lightweight timesheets kept in markdown.";
+    #[allow(dead_code)]
+    pub const CLI_NAME: &str = "time-report";
+    pub const HTTP_PATH: &str = "/api/activity/time";
+}
+
+/// Operation metadata for note_task_summary
+pub mod note_task_summary {
+    pub const DESCRIPTION: &str = "Count incomplete, completed, and cancelled tasks in each note (optionally only under a folder or only tasks with given tags) and report each note's percent complete. Use it to render per-project progress bars without fetching every task.";
+    #[allow(dead_code)]
+    pub const CLI_NAME: &str = "note-task-summary";
+    pub const HTTP_PATH: &str = "/api/activity/notes";
+}
+
+/// Maximum number of days in a heatmap (one leap year)
+const MAX_HEATMAP_DAYS: u64 = 366;
+
+/// Maximum number of points in a burndown series
+const MAX_BURNDOWN_POINTS: usize = 1000;
+
+/// Parameters for the completion_heatmap operation
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Parser)]
+#[command(
+    name = "completion-heatmap",
+    about = "Per-day completed task counts for a calendar heatmap"
+)]
+pub struct CompletionHeatmapRequest {
+    /// Path to vault (CLI only - not used in HTTP/MCP)
+    #[arg(index = 1, required = true, help = "Path to vault")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub path: Option<PathBuf>,
+
+    #[arg(long, help = "First day of the heatmap (YYYY-MM-DD)")]
+    #[schemars(
+        description = "First day of the heatmap (YYYY-MM-DD, inclusive). Defaults to 364 days before end_date."
+    )]
+    pub start_date: Option<NaiveDate>,
+
+    #[arg(long, help = "Last day of the heatmap (YYYY-MM-DD)")]
+    #[schemars(
+        description = "Last day of the heatmap (YYYY-MM-DD, inclusive). Defaults to today."
+    )]
+    pub end_date: Option<NaiveDate>,
+
+    #[arg(
+        long,
+        value_delimiter = ',',
+        help = "Only count tasks with all of these tags"
+    )]
+    #[schemars(description = "Only count tasks with all of these tags")]
+    pub tags: Option<Vec<String>>,
+
+    #[arg(long, help = "Also count words written in daily notes")]
+    #[schemars(
+        description = "If true, include the number of words in each day's daily note. Default: false"
+    )]
+    pub include_words: Option<bool>,
+
+    #[arg(long, help = "Fail if any file can't be read instead of skipping it")]
+    #[schemars(
+        description = "If true, fail when a file is skipped (unreadable, invalid frontmatter, larger than max_file_size, or cut off by a truncating scan limit) instead of reporting it in warnings. Default: false"
+    )]
+    pub strict: Option<bool>,
+}
+
+/// A single day cell of a calendar heatmap
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct HeatmapDay {
+    /// Date in YYYY-MM-DD format
+    pub date: NaiveDate,
+    /// Column index: weeks since the Sunday on or before start_date
+    pub week: usize,
+    /// Row index: day of the week, 0 = Sunday through 6 = Saturday
+    pub weekday: u32,
+    /// Number of tasks completed on this day
+    pub count: usize,
+    /// Intensity bucket from 0 (no activity) to 4 (busiest), relative to max_count
+    pub level: u8,
+    /// Words in the day's daily note (only present if include_words=true)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub words: Option<usize>,
+}
+
+/// Response from the completion_heatmap operation
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct CompletionHeatmapResponse {
+    /// First day of the heatmap
+    pub start_date: NaiveDate,
+    /// Last day of the heatmap
+    pub end_date: NaiveDate,
+    /// Total tasks completed in the range
+    pub total_count: usize,
+    /// Highest single-day completion count
+    pub max_count: usize,
+    /// Total words written in daily notes (only present if include_words=true)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_words: Option<usize>,
+    /// One entry per day in the range, oldest first
+    pub days: Vec<HeatmapDay>,
+    /// Files that were skipped (unreadable, invalid frontmatter, larger
+    /// than `max_file_size`, or cut off by a truncating scan limit)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ExtractionWarning>,
+}
+
+/// Spacing between points of a burndown series
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Deserialize, Serialize, JsonSchema, clap::ValueEnum,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum BurndownInterval {
+    /// One point per day
+    Day,
+    /// One point per week
+    #[default]
+    Week,
+    /// One point per calendar month
+    Month,
+}
+
+impl BurndownInterval {
+    fn next(self, date: NaiveDate) -> Option<NaiveDate> {
+        match self {
+            BurndownInterval::Day => date.checked_add_days(Days::new(1)),
+            BurndownInterval::Week => date.checked_add_days(Days::new(7)),
+            BurndownInterval::Month => date.checked_add_months(Months::new(1)),
+        }
+    }
+}
+
+/// Parameters for the project_burndown operation
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Parser)]
+#[command(
+    name = "project-burndown",
+    about = "Open-task counts over time for a tag or folder"
+)]
+pub struct ProjectBurndownRequest {
+    /// Path to vault (CLI only - not used in HTTP/MCP)
+    #[arg(index = 1, required = true, help = "Path to vault")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub path: Option<PathBuf>,
+
+    #[arg(long, help = "Only include tasks with this tag")]
+    #[schemars(description = "Only include tasks with this tag (tag or folder is required)")]
+    pub tag: Option<String>,
+
+    #[arg(
+        long,
+        help = "Only include tasks in this folder (relative to the vault)"
+    )]
+    #[schemars(
+        description = "Only include tasks under this folder, relative to the vault root (tag or folder is required)"
+    )]
+    pub folder: Option<String>,
+
+    #[arg(long, help = "First point of the series (YYYY-MM-DD)")]
+    #[schemars(
+        description = "First point of the series (YYYY-MM-DD). Defaults to the earliest created/completed date in scope."
+    )]
+    pub start_date: Option<NaiveDate>,
+
+    #[arg(long, help = "Last point of the series (YYYY-MM-DD)")]
+    #[schemars(description = "Last point of the series (YYYY-MM-DD). Defaults to today.")]
+    pub end_date: Option<NaiveDate>,
+
+    #[arg(long, value_enum, help = "Spacing between points (default: week)")]
+    #[schemars(
+        description = "Spacing between points: \"day\", \"week\", or \"month\". Default: week"
+    )]
+    pub interval: Option<BurndownInterval>,
+
+    #[arg(long, help = "Fail if any file can't be read instead of skipping it")]
+    #[schemars(
+        description = "If true, fail when a file is skipped (unreadable, invalid frontmatter, larger than max_file_size, or cut off by a truncating scan limit) instead of reporting it in warnings. Default: false"
+    )]
+    pub strict: Option<bool>,
+}
+
+/// A single point of a burndown series
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct BurndownPoint {
+    /// Date of the point; counts are as of the end of this day
+    pub date: NaiveDate,
+    /// Tasks open at the end of the day
+    pub open: usize,
+    /// Tasks created since the previous point
+    pub created: usize,
+    /// Tasks completed since the previous point
+    pub completed: usize,
+}
+
+/// Response from the project_burndown operation
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ProjectBurndownResponse {
+    /// Tag the series was filtered by
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    /// Folder the series was filtered by
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub folder: Option<String>,
+    /// Spacing between points
+    pub interval: BurndownInterval,
+    /// Number of tasks in scope
+    pub total_tasks: usize,
+    /// Tasks without a created date, counted as open from the start of the series
+    pub undated_tasks: usize,
+    /// Closed tasks without a completion date, left out of the series
+    pub skipped_tasks: usize,
+    /// Points in chronological order
+    pub points: Vec<BurndownPoint>,
+    /// Files that were skipped (unreadable, invalid frontmatter, larger
+    /// than `max_file_size`, or cut off by a truncating scan limit)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ExtractionWarning>,
+}
+
+/// How logged time is grouped in a time report
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Deserialize, Serialize, JsonSchema, clap::ValueEnum,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeGroupBy {
+    /// One group per tag; a task with several tags counts towards each
+    #[default]
+    Tag,
+    /// One group per file, relative to the vault
+    File,
+    /// One group per day the time was logged
+    Day,
+}
+
+/// Parameters for the time_report operation
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Parser)]
+#[command(name = "time-report", about = "Total time logged on tasks")]
+pub struct TimeReportRequest {
+    /// Path to vault (CLI only - not used in HTTP/MCP)
+    #[arg(index = 1, required = true, help = "Path to vault")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub path: Option<PathBuf>,
+
+    #[arg(long, value_enum, help = "How to group logged time (default: tag)")]
+    #[schemars(description = "Group logged time by \"tag\", \"file\", or \"day\". Default: tag")]
+    pub group_by: Option<TimeGroupBy>,
+
+    #[arg(
+        long,
+        value_delimiter = ',',
+        help = "Only include tasks with all of these tags"
+    )]
+    #[schemars(description = "Only include tasks with all of these tags")]
+    pub tags: Option<Vec<String>>,
+
+    #[arg(
+        long,
+        help = "Only include time logged on or after this date (YYYY-MM-DD)"
+    )]
+    #[schemars(
+        description = "Only include time logged on or after this date (YYYY-MM-DD). Undated entries are excluded when a date range is given."
+    )]
+    pub start_date: Option<NaiveDate>,
+
+    #[arg(
+        long,
+        help = "Only include time logged on or before this date (YYYY-MM-DD)"
+    )]
+    #[schemars(
+        description = "Only include time logged on or before this date (YYYY-MM-DD). Undated entries are excluded when a date range is given."
+    )]
+    pub end_date: Option<NaiveDate>,
+
+    #[arg(long, help = "Fail if any file can't be read instead of skipping it")]
+    #[schemars(
+        description = "If true, fail when a file is skipped (unreadable, invalid frontmatter, larger than max_file_size, or cut off by a truncating scan limit) instead of reporting it in warnings. Default: false"
+    )]
+    pub strict: Option<bool>,
+}
+
+/// Total time for one tag, file, or day
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct TimeReportGroup {
+    /// Tag name, relative file path, or YYYY-MM-DD date ("untagged"/"undated" for tasks without one)
+    pub key: String,
+    /// Minutes logged in this group
+    pub minutes: u32,
+    /// Human-readable total, e.g. "1h 30m"
+    pub duration: String,
+    /// Number of tasks contributing to this group
+    pub task_count: usize,
+}
+
+/// Response from the time_report operation
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TimeReportResponse {
+    /// How the groups were formed
+    pub group_by: TimeGroupBy,
+    /// Minutes logged across all matching tasks
+    pub total_minutes: u32,
+    /// Human-readable total, e.g. "12h 45m"
+    pub total_duration: String,
+    /// Number of tasks with logged time
+    pub task_count: usize,
+    /// Groups ordered by date (day) or by most time logged (tag, file)
+    pub groups: Vec<TimeReportGroup>,
+    /// Files that were skipped (unreadable, invalid frontmatter, larger
+    /// than `max_file_size`, or cut off by a truncating scan limit)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ExtractionWarning>,
+}
+
+/// Parameters for the note_task_summary operation
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Parser)]
+#[command(
+    name = "note-task-summary",
+    about = "Task counts and percent complete for each note"
+)]
+pub struct NoteTaskSummaryRequest {
+    /// Path to vault (CLI only - not used in HTTP/MCP)
+    #[arg(index = 1, required = true, help = "Path to vault")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub path: Option<PathBuf>,
+
+    #[arg(
+        long,
+        help = "Only include notes in this folder (relative to the vault)"
+    )]
+    #[schemars(description = "Only include notes under this folder, relative to the vault root")]
+    pub folder: Option<String>,
+
+    #[arg(
+        long,
+        value_delimiter = ',',
+        help = "Only count tasks with all of these tags"
+    )]
+    #[schemars(
+        description = "Only count tasks with all of these tags; notes without such tasks are left out"
+    )]
+    pub tags: Option<Vec<String>>,
+
+    #[arg(long, help = "Count tasks inside blockquotes and callouts")]
+    #[schemars(
+        description = "If true, also count tasks quoted inside blockquotes and callouts. Default: false"
+    )]
+    pub include_quoted: Option<bool>,
+
+    #[arg(long, help = "Include notes under archived paths")]
+    #[schemars(
+        description = "If true, include notes under the configured archived_paths. Default: false"
+    )]
+    pub include_archived: Option<bool>,
+
+    #[arg(long, help = "Fail if any file can't be read instead of skipping it")]
+    #[schemars(
+        description = "If true, fail when a file is skipped (unreadable, invalid frontmatter, larger than max_file_size, or cut off by a truncating scan limit) instead of reporting it in warnings. Default: false"
+    )]
+    pub strict: Option<bool>,
+}
+
+/// Task counts for one note
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct NoteTaskSummary {
+    /// Note path relative to the vault
+    pub file_path: String,
+    /// Open tasks, including custom statuses such as `[/]`
+    pub incomplete: usize,
+    pub completed: usize,
+    pub cancelled: usize,
+    /// Completed tasks as a percentage of incomplete and completed ones
+    /// (cancelled tasks don't count), rounded to one decimal
+    pub percent_complete: f64,
+}
+
+/// Response from the note_task_summary operation
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct NoteTaskSummaryResponse {
+    /// Notes with at least one counted task, ordered by path
+    pub notes: Vec<NoteTaskSummary>,
+    /// Open tasks across all notes
+    pub incomplete: usize,
+    /// Completed tasks across all notes
+    pub completed: usize,
+    /// Cancelled tasks across all notes
+    pub cancelled: usize,
+    /// Percent complete across all notes
+    pub percent_complete: f64,
+    /// Files that were skipped (unreadable, invalid frontmatter, larger
+    /// than `max_file_size`, or cut off by a truncating scan limit)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ExtractionWarning>,
+}
+
+/// Completed tasks as a percentage of open and completed ones, to one decimal
+fn percent_complete(incomplete: usize, completed: usize) -> f64 {
+    if incomplete + completed == 0 {
+        return 0.0;
+    }
+    (completed as f64 * 1000.0 / (incomplete + completed) as f64).round() / 10.0
+}
+
+/// Capability for activity operations (heatmaps, time series)
+pub struct ActivityCapability {
+    base_path: PathBuf,
+    config: Arc<Config>,
+    task_extractor: Arc<TaskExtractor>,
+}
+
+impl ActivityCapability {
+    /// Create a new ActivityCapability
+    pub fn new(base_path: PathBuf, config: Arc<Config>) -> Self {
+        Self {
+            base_path,
+            task_extractor: Arc::new(TaskExtractor::new(Arc::clone(&config))),
+            config,
+        }
+    }
+
+    /// Count completed tasks per day over a date range
+    pub async fn completion_heatmap(
+        &self,
+        request: CompletionHeatmapRequest,
+    ) -> CapabilityResult<CompletionHeatmapResponse> {
+        let end_date = request
+            .end_date
+            .unwrap_or_else(|| Local::now().date_naive());
+        let start_date = request
+            .start_date
+            .unwrap_or_else(|| end_date - Days::new(364));
+
+        if start_date > end_date {
+            return Err(invalid_date(
+                "Invalid date range: start_date must be <= end_date",
+            ));
+        }
+        let day_count = (end_date - start_date).num_days() as u64 + 1;
+        if day_count > MAX_HEATMAP_DAYS {
+            return Err(invalid_date(format!(
+                "Date range limited to {} days",
+                MAX_HEATMAP_DAYS
+            )));
+        }
+
+        let (tasks, warnings) = self
+            .task_extractor
+            .extract_tasks_with_warnings(&self.base_path)
+            .map_err(|e| scan_error("Failed to extract tasks", e))?;
+        check_strict(&warnings, request.strict)?;
+        let filter_options = FilterOptions {
+            status: Some(TaskStatus::Completed),
+            tags: request.tags,
+            ..Default::default()
+        };
+
+        let mut counts: HashMap<NaiveDate, usize> = HashMap::new();
+        for task in filter_tasks(tasks, &filter_options) {
+            if let Some(date) = task.completed_date
+                && (start_date..=end_date).contains(&date)
+            {
+                *counts.entry(date).or_default() += 1;
+            }
+        }
+
+        let include_words = request.include_words.unwrap_or(false);
+        let max_count = counts.values().copied().max().unwrap_or(0);
+        let first_sunday =
+            start_date - Days::new(u64::from(start_date.weekday().num_days_from_sunday()));
+
+        let days: Vec<HeatmapDay> = start_date
+            .iter_days()
+            .take(day_count as usize)
+            .map(|date| {
+                let count = counts.get(&date).copied().unwrap_or(0);
+                HeatmapDay {
+                    date,
+                    week: ((date - first_sunday).num_days() / 7) as usize,
+                    weekday: date.weekday().num_days_from_sunday(),
+                    count,
+                    level: heatmap_level(count, max_count),
+                    words: include_words.then(|| self.daily_note_words(date)),
+                }
+            })
+            .collect();
+
+        Ok(CompletionHeatmapResponse {
+            start_date,
+            end_date,
+            total_count: counts.values().sum(),
+            max_count,
+            total_words: include_words.then(|| days.iter().filter_map(|d| d.words).sum()),
+            days,
+            warnings,
+        })
+    }
+
+    /// Reconstruct open-task counts over time for a tag or folder
+    pub async fn project_burndown(
+        &self,
+        request: ProjectBurndownRequest,
+    ) -> CapabilityResult<ProjectBurndownResponse> {
+        if request.tag.is_none() && request.folder.is_none() {
+            return Err(invalid_params("Either tag or folder is required"));
+        }
+
+        let search_path = match request.folder {
+            Some(ref folder) => self.base_path.join(folder),
+            None => self.base_path.clone(),
+        };
+        if !search_path.exists() {
+            return Err(not_found(format!(
+                "Folder not found: {}",
+                request.folder.as_deref().unwrap_or_default()
+            )));
+        }
+
+        let (tasks, warnings) = self
+            .task_extractor
+            .extract_tasks_with_warnings(&search_path)
+            .map_err(|e| scan_error("Failed to extract tasks", e))?;
+        check_strict(&warnings, request.strict)?;
+        let filter_options = FilterOptions {
+            tags: request.tag.clone().map(|tag| vec![tag]),
+            ..Default::default()
+        };
+        let tasks = filter_tasks(tasks, &filter_options);
+
+        // (created, closed) per task; `None` means "before the series" and
+        // "still open" respectively
+        let mut spans: Vec<(Option<NaiveDate>, Option<NaiveDate>)> = Vec::new();
+        let mut skipped_tasks = 0;
+        for task in &tasks {
+            let closed = match task.status {
+                TaskStatus::Completed | TaskStatus::Cancelled => match task.completed_date {
+                    Some(date) => Some(date),
+                    None => {
+                        skipped_tasks += 1;
+                        continue;
+                    }
+                },
+                TaskStatus::Incomplete | TaskStatus::Other(_) | TaskStatus::CodeTodo => None,
+            };
+            spans.push((task.created_date, closed));
+        }
+
+        let end_date = request
+            .end_date
+            .unwrap_or_else(|| Local::now().date_naive());
+        let start_date = request
+            .start_date
+            .or_else(|| spans.iter().flat_map(|(c, d)| [*c, *d]).flatten().min())
+            .unwrap_or(end_date)
+            .min(end_date);
+        let interval = request.interval.unwrap_or_default();
+
+        let mut dates = vec![start_date];
+        while let Some(next) = interval.next(*dates.last().unwrap_or(&start_date)) {
+            if next > end_date {
+                break;
+            }
+            if dates.len() == MAX_BURNDOWN_POINTS {
+                return Err(invalid_params(format!(
+                    "Series limited to {} points; use a longer interval or a shorter range",
+                    MAX_BURNDOWN_POINTS
+                )));
+            }
+            dates.push(next);
+        }
+        if dates.last() != Some(&end_date) {
+            dates.push(end_date);
+        }
+
+        let mut previous: Option<NaiveDate> = None;
+        let points = dates
+            .into_iter()
+            .map(|date| {
+                let since = |d: &Option<NaiveDate>| {
+                    d.is_some_and(|d| d <= date && previous.is_none_or(|p| d > p))
+                };
+                let point = BurndownPoint {
+                    date,
+                    open: spans
+                        .iter()
+                        .filter(|(created, closed)| {
+                            created.is_none_or(|c| c <= date) && closed.is_none_or(|c| c > date)
+                        })
+                        .count(),
+                    created: spans.iter().filter(|(created, _)| since(created)).count(),
+                    completed: spans.iter().filter(|(_, closed)| since(closed)).count(),
+                };
+                previous = Some(date);
+                point
+            })
+            .collect();
+
+        Ok(ProjectBurndownResponse {
+            tag: request.tag,
+            folder: request.folder,
+            interval,
+            total_tasks: tasks.len(),
+            undated_tasks: spans
+                .iter()
+                .filter(|(created, _)| created.is_none())
+                .count(),
+            skipped_tasks,
+            points,
+            warnings,
+        })
+    }
+
+    /// Count each note's tasks by status
+    pub async fn note_task_summary(
+        &self,
+        request: NoteTaskSummaryRequest,
+    ) -> CapabilityResult<NoteTaskSummaryResponse> {
+        let search_path = match request.folder {
+            Some(ref folder) => self.base_path.join(folder),
+            None => self.base_path.clone(),
+        };
+        if !search_path.exists() {
+            return Err(not_found(format!(
+                "Folder not found: {}",
+                request.folder.as_deref().unwrap_or_default()
+            )));
+        }
+
+        let (tasks, warnings) = self
+            .task_extractor
+            .extract_tasks_with_warnings(&search_path)
+            .map_err(|e| scan_error("Failed to extract tasks", e))?;
+        check_strict(&warnings, request.strict)?;
+        let filter_options = FilterOptions {
+            tags: request.tags,
+            include_quoted: request.include_quoted.unwrap_or(false),
+            ..Default::default()
+        };
+        let mut tasks = filter_tasks(tasks, &filter_options);
+        exclude_archived(
+            &mut tasks,
+            &self.config,
+            request.include_archived.unwrap_or(false),
+        );
+
+        // (incomplete, completed, cancelled) per note
+        let mut counts: BTreeMap<String, (usize, usize, usize)> = BTreeMap::new();
+        for task in &tasks {
+            let path = Path::new(&task.file_path);
+            let entry = counts
+                .entry(to_slash(path.strip_prefix(&self.base_path).unwrap_or(path)))
+                .or_default();
+            match task.status {
+                TaskStatus::Completed => entry.1 += 1,
+                TaskStatus::Cancelled => entry.2 += 1,
+                TaskStatus::Incomplete | TaskStatus::Other(_) | TaskStatus::CodeTodo => {
+                    entry.0 += 1
+                }
+            }
+        }
+
+        let notes: Vec<NoteTaskSummary> = counts
+            .into_iter()
+            .map(
+                |(file_path, (incomplete, completed, cancelled))| NoteTaskSummary {
+                    file_path,
+                    incomplete,
+                    completed,
+                    cancelled,
+                    percent_complete: percent_complete(incomplete, completed),
+                },
+            )
+            .collect();
+        let incomplete = notes.iter().map(|note| note.incomplete).sum();
+        let completed = notes.iter().map(|note| note.completed).sum();
+        Ok(NoteTaskSummaryResponse {
+            cancelled: notes.iter().map(|note| note.cancelled).sum(),
+            percent_complete: percent_complete(incomplete, completed),
+            incomplete,
+            completed,
+            notes,
+            warnings,
+        })
+    }
+
+    /// Total time logged on tasks, grouped by tag, file, or day
+    pub async fn time_report(
+        &self,
+        request: TimeReportRequest,
+    ) -> CapabilityResult<TimeReportResponse> {
+        if let (Some(start), Some(end)) = (request.start_date, request.end_date)
+            && start > end
+        {
+            return Err(invalid_date(
+                "Invalid date range: start_date must be <= end_date",
+            ));
+        }
+
+        let (tasks, warnings) = self
+            .task_extractor
+            .extract_tasks_with_warnings(&self.base_path)
+            .map_err(|e| scan_error("Failed to extract tasks", e))?;
+        check_strict(&warnings, request.strict)?;
+        let filter_options = FilterOptions {
+            tags: request.tags,
+            ..Default::default()
+        };
+
+        let has_range = request.start_date.is_some() || request.end_date.is_some();
+        let in_range = |date: NaiveDate| {
+            request.start_date.is_none_or(|start| date >= start)
+                && request.end_date.is_none_or(|end| date <= end)
+        };
+        let group_by = request.group_by.unwrap_or_default();
+
+        let mut groups: HashMap<String, (u32, usize)> = HashMap::new();
+        let mut total_minutes: u32 = 0;
+        let mut task_count = 0;
+        for task in filter_tasks(tasks, &filter_options) {
+            let Some(minutes) = task.time_spent else {
+                continue;
+            };
+            let logged_on = logged_date(&task);
+            match logged_on {
+                Some(date) if !in_range(date) => continue,
+                None if has_range => continue,
+                _ => {}
+            }
+
+            let keys = match group_by {
+                TimeGroupBy::Tag if task.tags.is_empty() => vec!["untagged".to_string()],
+                TimeGroupBy::Tag => {
+                    let mut tags = task.tags.clone();
+                    tags.sort();
+                    tags.dedup();
+                    tags
+                }
+                TimeGroupBy::File => vec![to_slash(
+                    Path::new(&task.file_path)
+                        .strip_prefix(&self.base_path)
+                        .unwrap_or(Path::new(&task.file_path)),
+                )],
+                TimeGroupBy::Day => vec![
+                    logged_on
+                        .map(|date| date.to_string())
+                        .unwrap_or_else(|| "undated".to_string()),
+                ],
+            };
+            for key in keys {
+                let entry = groups.entry(key).or_default();
+                entry.0 = entry.0.saturating_add(minutes);
+                entry.1 += 1;
+            }
+            total_minutes = total_minutes.saturating_add(minutes);
+            task_count += 1;
+        }
+
+        let mut groups: Vec<TimeReportGroup> = groups
+            .into_iter()
+            .map(|(key, (minutes, task_count))| TimeReportGroup {
+                key,
+                minutes,
+                duration: format_duration(minutes),
+                task_count,
+            })
+            .collect();
+        match group_by {
+            TimeGroupBy::Day => groups.sort_by(|a, b| a.key.cmp(&b.key)),
+            TimeGroupBy::Tag | TimeGroupBy::File => {
+                groups.sort_by(|a, b| b.minutes.cmp(&a.minutes).then_with(|| a.key.cmp(&b.key)))
+            }
+        }
+
+        Ok(TimeReportResponse {
+            group_by,
+            total_minutes,
+            total_duration: format_duration(total_minutes),
+            task_count,
+            groups,
+            warnings,
+        })
+    }
+
+    /// Number of words in the body of the daily note for `date` (0 if missing)
+    fn daily_note_words(&self, date: NaiveDate) -> usize {
+        get_daily_note_relative_path(
+            &self.base_path,
+            &date.format("%Y-%m-%d").to_string(),
+            &self.config.daily_note_patterns,
+            &self.config,
+        )
+        .and_then(|relative| std::fs::read_to_string(self.base_path.join(relative)).ok())
+        .map(|content| body_after_frontmatter(&content).split_whitespace().count())
+        .unwrap_or(0)
+    }
+}
+
+/// Day a task's time was logged: its completion date, the date in its file
+/// name (daily notes), or its created date
+fn logged_date(task: &Task) -> Option<NaiveDate> {
+    task.completed_date
+        .or_else(|| {
+            let stem = Path::new(&task.file_name).file_stem()?.to_str()?;
+            stem.char_indices()
+                .filter_map(|(i, _)| stem.get(i..i + 10))
+                .find_map(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok())
+        })
+        .or(task.created_date)
+}
+
+/// Format minutes as "1h 30m", "2h", or "45m"
+fn format_duration(minutes: u32) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, m) => format!("{}m", m),
+        (h, 0) => format!("{}h", h),
+        (h, m) => format!("{}h {}m", h, m),
+    }
+}
+
+/// Bucket a day's count into a GitHub-style intensity level (0-4)
+fn heatmap_level(count: usize, max_count: usize) -> u8 {
+    if count == 0 || max_count == 0 {
+        return 0;
+    }
+    (count * 4).div_ceil(max_count).clamp(1, 4) as u8
+}
+
+/// Operation struct for completion_heatmap (HTTP, CLI, and MCP)
+pub struct CompletionHeatmapOperation {
+    capability: Arc<ActivityCapability>,
+}
+
+impl CompletionHeatmapOperation {
+    pub fn new(capability: Arc<ActivityCapability>) -> Self {
+        Self { capability }
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::operation::Operation for CompletionHeatmapOperation {
+    fn name(&self) -> &'static str {
+        completion_heatmap::CLI_NAME
+    }
+
+    fn path(&self) -> &'static str {
+        completion_heatmap::HTTP_PATH
+    }
+
+    fn description(&self) -> &'static str {
+        completion_heatmap::DESCRIPTION
+    }
+
+    fn read_only(&self) -> bool {
+        true
+    }
+
+    fn get_command(&self) -> clap::Command {
+        // Get command from request struct's Parser derive
+        CompletionHeatmapRequest::command()
+    }
+
+    async fn execute_json(&self, json: serde_json::Value) -> Result<serde_json::Value, Error> {
+        crate::http_router::execute_json_operation(json, |req| {
+            self.capability.completion_heatmap(req)
+        })
+        .await
+    }
+
+    async fn execute_from_args(
+        &self,
+        matches: &clap::ArgMatches,
+        _registry: &crate::capabilities::CapabilityRegistry,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        // Parse request from ArgMatches
+        let request = CompletionHeatmapRequest::from_arg_matches(matches)?;
+
+        // Handle CLI-specific path if present
+        let response = if let Some(ref path) = request.path {
+            let config = Arc::new(Config::load_from_base_path(path.as_path()));
+            let capability = ActivityCapability::new(path.clone(), config);
+            let mut req_without_path = request;
+            req_without_path.path = None;
+            capability.completion_heatmap(req_without_path).await?
+        } else {
+            self.capability.completion_heatmap(request).await?
+        };
+
+        // Serialize to JSON
+        Ok(serde_json::to_string_pretty(&response)?)
+    }
+
+    fn input_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(CompletionHeatmapRequest)).unwrap()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(CompletionHeatmapResponse)).unwrap()
+    }
+}
+
+/// Operation struct for project_burndown (HTTP, CLI, and MCP)
+pub struct ProjectBurndownOperation {
+    capability: Arc<ActivityCapability>,
+}
+
+impl ProjectBurndownOperation {
+    pub fn new(capability: Arc<ActivityCapability>) -> Self {
+        Self { capability }
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::operation::Operation for ProjectBurndownOperation {
+    fn name(&self) -> &'static str {
+        project_burndown::CLI_NAME
+    }
+
+    fn path(&self) -> &'static str {
+        project_burndown::HTTP_PATH
+    }
+
+    fn description(&self) -> &'static str {
+        project_burndown::DESCRIPTION
+    }
+
+    fn read_only(&self) -> bool {
+        true
+    }
+
+    fn get_command(&self) -> clap::Command {
+        // Get command from request struct's Parser derive
+        ProjectBurndownRequest::command()
+    }
+
+    async fn execute_json(&self, json: serde_json::Value) -> Result<serde_json::Value, Error> {
+        crate::http_router::execute_json_operation(json, |req| {
+            self.capability.project_burndown(req)
+        })
+        .await
+    }
+
+    async fn execute_from_args(
+        &self,
+        matches: &clap::ArgMatches,
+        _registry: &crate::capabilities::CapabilityRegistry,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        // Parse request from ArgMatches
+        let request = ProjectBurndownRequest::from_arg_matches(matches)?;
+
+        // Handle CLI-specific path if present
+        let response = if let Some(ref path) = request.path {
+            let config = Arc::new(Config::load_from_base_path(path.as_path()));
+            let capability = ActivityCapability::new(path.clone(), config);
+            let mut req_without_path = request;
+            req_without_path.path = None;
+            capability.project_burndown(req_without_path).await?
+        } else {
+            self.capability.project_burndown(request).await?
+        };
+
+        // Serialize to JSON
+        Ok(serde_json::to_string_pretty(&response)?)
+    }
+
+    fn input_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(ProjectBurndownRequest)).unwrap()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(ProjectBurndownResponse)).unwrap()
+    }
+}
+
+/// Operation struct for note_task_summary (HTTP, CLI, and MCP)
+pub struct NoteTaskSummaryOperation {
+    capability: Arc<ActivityCapability>,
+}
+
+impl NoteTaskSummaryOperation {
+    pub fn new(capability: Arc<ActivityCapability>) -> Self {
+        Self { capability }
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::operation::Operation for NoteTaskSummaryOperation {
+    fn name(&self) -> &'static str {
+        note_task_summary::CLI_NAME
+    }
+
+    fn path(&self) -> &'static str {
+        note_task_summary::HTTP_PATH
+    }
+
+    fn description(&self) -> &'static str {
+        note_task_summary::DESCRIPTION
+    }
+
+    fn read_only(&self) -> bool {
+        true
+    }
+
+    fn get_command(&self) -> clap::Command {
+        // Get command from request struct's Parser derive
+        NoteTaskSummaryRequest::command()
+    }
+
+    async fn execute_json(&self, json: serde_json::Value) -> Result<serde_json::Value, Error> {
+        crate::http_router::execute_json_operation(json, |req| {
+            self.capability.note_task_summary(req)
+        })
+        .await
+    }
+
+    async fn execute_from_args(
+        &self,
+        matches: &clap::ArgMatches,
+        _registry: &crate::capabilities::CapabilityRegistry,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        // Parse request from ArgMatches
+        let request = NoteTaskSummaryRequest::from_arg_matches(matches)?;
+
+        // Handle CLI-specific path if present
+        let response = if let Some(ref path) = request.path {
+            let config = Arc::new(Config::load_from_base_path(path.as_path()));
+            let capability = ActivityCapability::new(path.clone(), config);
+            let mut req_without_path = request;
+            req_without_path.path = None;
+            capability.note_task_summary(req_without_path).await?
+        } else {
+            self.capability.note_task_summary(request).await?
+        };
+
+        // Serialize to JSON
+        Ok(serde_json::to_string_pretty(&response)?)
+    }
+
+    fn input_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(NoteTaskSummaryRequest)).unwrap()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(NoteTaskSummaryResponse)).unwrap()
+    }
+}
+
+/// Operation struct for time_report (HTTP, CLI, and MCP)
+pub struct TimeReportOperation {
+    capability: Arc<ActivityCapability>,
+}
+
+impl TimeReportOperation {
+    pub fn new(capability: Arc<ActivityCapability>) -> Self {
+        Self { capability }
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::operation::Operation for TimeReportOperation {
+    fn name(&self) -> &'static str {
+        time_report::CLI_NAME
+    }
+
+    fn path(&self) -> &'static str {
+        time_report::HTTP_PATH
+    }
+
+    fn description(&self) -> &'static str {
+        time_report::DESCRIPTION
+    }
+
+    fn read_only(&self) -> bool {
+        true
+    }
+
+    fn get_command(&self) -> clap::Command {
+        // Get command from request struct's Parser derive
+        TimeReportRequest::command()
+    }
+
+    async fn execute_json(&self, json: serde_json::Value) -> Result<serde_json::Value, Error> {
+        crate::http_router::execute_json_operation(json, |req| self.capability.time_report(req))
+            .await
+    }
+
+    async fn execute_from_args(
+        &self,
+        matches: &clap::ArgMatches,
+        _registry: &crate::capabilities::CapabilityRegistry,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        // Parse request from ArgMatches
+        let request = TimeReportRequest::from_arg_matches(matches)?;
+
+        // Handle CLI-specific path if present
+        let response = if let Some(ref path) = request.path {
+            let config = Arc::new(Config::load_from_base_path(path.as_path()));
+            let capability = ActivityCapability::new(path.clone(), config);
+            let mut req_without_path = request;
+            req_without_path.path = None;
+            capability.time_report(req_without_path).await?
+        } else {
+            self.capability.time_report(request).await?
+        };
+
+        // Serialize to JSON
+        Ok(serde_json::to_string_pretty(&response)?)
+    }
+
+    fn input_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(TimeReportRequest)).unwrap()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(TimeReportResponse)).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::warnings::WarningKind;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn create_vault() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("project.md"),
+            "- [x] One #work ✅ 2025-01-06\n\
+             - [x] Two ✅ 2025-01-06\n\
+             - [x] Three #work ✅ 2025-01-08\n\
+             - [x] Outside range ✅ 2024-12-01\n\
+             - [x] No date\n\
+             - [ ] Open ✅ 2025-01-06\n",
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("2025-01-06.md"),
+            "---\ntags: [daily]\n---\nWrote some words today.\n",
+        )
+        .unwrap();
+        temp_dir
+    }
+
+    fn heatmap_request(tags: Option<Vec<String>>) -> CompletionHeatmapRequest {
+        CompletionHeatmapRequest {
+            path: None,
+            start_date: Some(date("2025-01-05")),
+            end_date: Some(date("2025-01-11")),
+            tags,
+            include_words: Some(true),
+            strict: None,
+        }
+    }
+
+    #[test]
+    fn test_heatmap_level() {
+        assert_eq!(heatmap_level(0, 0), 0);
+        assert_eq!(heatmap_level(0, 8), 0);
+        assert_eq!(heatmap_level(1, 8), 1);
+        assert_eq!(heatmap_level(3, 8), 2);
+        assert_eq!(heatmap_level(8, 8), 4);
+    }
+
+    #[tokio::test]
+    async fn test_completion_heatmap() {
+        let temp_dir = create_vault();
+        let capability =
+            ActivityCapability::new(temp_dir.path().to_path_buf(), Arc::new(Config::default()));
+
+        let response = capability
+            .completion_heatmap(heatmap_request(None))
+            .await
+            .unwrap();
+
+        assert_eq!(response.days.len(), 7);
+        assert_eq!(response.total_count, 3);
+        assert_eq!(response.max_count, 2);
+        assert_eq!(response.total_words, Some(4));
+
+        // 2025-01-05 is a Sunday, so the range is exactly one week column
+        let monday = &response.days[1];
+        assert_eq!(monday.date, date("2025-01-06"));
+        assert_eq!((monday.week, monday.weekday), (0, 1));
+        assert_eq!((monday.count, monday.level), (2, 4));
+        assert_eq!(monday.words, Some(4));
+
+        let wednesday = &response.days[3];
+        assert_eq!((wednesday.count, wednesday.level), (1, 2));
+        assert_eq!(wednesday.words, Some(0));
+    }
+
+    #[tokio::test]
+    async fn test_completion_heatmap_tag_filter() {
+        let temp_dir = create_vault();
+        let capability =
+            ActivityCapability::new(temp_dir.path().to_path_buf(), Arc::new(Config::default()));
+
+        let response = capability
+            .completion_heatmap(heatmap_request(Some(vec!["work".to_string()])))
+            .await
+            .unwrap();
+
+        assert_eq!(response.total_count, 2);
+        assert_eq!(response.max_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_completion_heatmap_reports_skipped_files() {
+        let temp_dir = create_vault();
+        fs::write(
+            temp_dir.path().join("big.md"),
+            "- [x] Big ✅ 2025-01-06\n".repeat(20),
+        )
+        .unwrap();
+        let config = Config {
+            max_file_size: Some(300),
+            ..Default::default()
+        };
+        let capability = ActivityCapability::new(temp_dir.path().to_path_buf(), Arc::new(config));
+
+        let response = capability
+            .completion_heatmap(heatmap_request(None))
+            .await
+            .unwrap();
+        assert_eq!(response.total_count, 3);
+        assert_eq!(response.warnings.len(), 1);
+        assert!(response.warnings[0].file_path.ends_with("big.md"));
+        assert_eq!(response.warnings[0].kind, WarningKind::Oversized);
+
+        let strict = CompletionHeatmapRequest {
+            strict: Some(true),
+            ..heatmap_request(None)
+        };
+        let error = capability.completion_heatmap(strict).await.unwrap_err();
+        assert_eq!(error.code(), "parse_error");
+    }
+
+    #[tokio::test]
+    async fn test_completion_heatmap_validates_range() {
+        let temp_dir = create_vault();
+        let capability =
+            ActivityCapability::new(temp_dir.path().to_path_buf(), Arc::new(Config::default()));
+
+        let mut request = heatmap_request(None);
+        request.start_date = Some(date("2025-02-01"));
+        assert!(capability.completion_heatmap(request).await.is_err());
+
+        let mut request = heatmap_request(None);
+        request.start_date = Some(date("2023-01-01"));
+        assert!(capability.completion_heatmap(request).await.is_err());
+
+        // The default range is a full year ending today
+        let mut request = heatmap_request(None);
+        request.start_date = None;
+        request.end_date = None;
+        let response = capability.completion_heatmap(request).await.unwrap();
+        assert_eq!(response.days.len(), 365);
+    }
+
+    fn burndown_request(tag: Option<&str>, folder: Option<&str>) -> ProjectBurndownRequest {
+        ProjectBurndownRequest {
+            path: None,
+            tag: tag.map(String::from),
+            folder: folder.map(String::from),
+            start_date: None,
+            end_date: Some(date("2025-01-20")),
+            interval: Some(BurndownInterval::Week),
+            strict: None,
+        }
+    }
+
+    fn create_project_vault() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path().join("Projects/Alpha");
+        fs::create_dir_all(&project).unwrap();
+        fs::write(
+            project.join("plan.md"),
+            "- [x] Design #alpha ➕ 2025-01-01 ✅ 2025-01-03\n\
+             - [x] Build #alpha ➕ 2025-01-02 ✅ 2025-01-10\n\
+             - [ ] Ship #alpha ➕ 2025-01-09\n\
+             - [ ] Undated #alpha\n\
+             - [-] Dropped #alpha\n",
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("other.md"),
+            "- [ ] Elsewhere #beta ➕ 2025-01-01\n",
+        )
+        .unwrap();
+        temp_dir
+    }
+
+    #[tokio::test]
+    async fn test_project_burndown_by_tag() {
+        let temp_dir = create_project_vault();
+        let capability =
+            ActivityCapability::new(temp_dir.path().to_path_buf(), Arc::new(Config::default()));
+
+        let response = capability
+            .project_burndown(burndown_request(Some("alpha"), None))
+            .await
+            .unwrap();
+
+        assert_eq!(response.total_tasks, 5);
+        assert_eq!(response.undated_tasks, 1);
+        assert_eq!(response.skipped_tasks, 1);
+
+        let summary: Vec<_> = response
+            .points
+            .iter()
+            .map(|p| (p.date.to_string(), p.open, p.created, p.completed))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                // Starts at the earliest date in scope; the undated task is open throughout
+                ("2025-01-01".to_string(), 2, 1, 0),
+                ("2025-01-08".to_string(), 2, 1, 1),
+                ("2025-01-15".to_string(), 2, 1, 1),
+                ("2025-01-20".to_string(), 2, 0, 0),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_project_burndown_by_folder() {
+        let temp_dir = create_project_vault();
+        let capability =
+            ActivityCapability::new(temp_dir.path().to_path_buf(), Arc::new(Config::default()));
+
+        let mut request = burndown_request(None, Some("Projects"));
+        request.interval = Some(BurndownInterval::Month);
+        let response = capability.project_burndown(request).await.unwrap();
+
+        assert_eq!(response.total_tasks, 5);
+        // The end date is always included as the final point
+        let dates: Vec<_> = response.points.iter().map(|p| p.date.to_string()).collect();
+        assert_eq!(dates, vec!["2025-01-01", "2025-01-20"]);
+        assert_eq!(response.points[1].open, 2);
+        assert_eq!(response.points[1].created, 2);
+        assert_eq!(response.points[1].completed, 2);
+    }
+
+    #[tokio::test]
+    async fn test_note_task_summary() {
+        let temp_dir = create_project_vault();
+        fs::write(
+            temp_dir.path().join("Projects/callout.md"),
+            "> [!todo]\n> - [x] Quoted #alpha\n- [/] Started #alpha\n",
+        )
+        .unwrap();
+        let capability =
+            ActivityCapability::new(temp_dir.path().to_path_buf(), Arc::new(Config::default()));
+        let request = |folder: Option<&str>, include_quoted| NoteTaskSummaryRequest {
+            path: None,
+            folder: folder.map(str::to_string),
+            tags: Some(vec!["alpha".to_string()]),
+            include_quoted: Some(include_quoted),
+            include_archived: None,
+            strict: None,
+        };
+
+        let response = capability
+            .note_task_summary(request(None, false))
+            .await
+            .unwrap();
+        assert_eq!(
+            response.notes,
+            vec![
+                NoteTaskSummary {
+                    file_path: "Projects/Alpha/plan.md".to_string(),
+                    incomplete: 2,
+                    completed: 2,
+                    cancelled: 1,
+                    percent_complete: 50.0,
+                },
+                NoteTaskSummary {
+                    file_path: "Projects/callout.md".to_string(),
+                    incomplete: 1,
+                    completed: 0,
+                    cancelled: 0,
+                    percent_complete: 0.0,
+                },
+            ]
+        );
+        assert_eq!((response.incomplete, response.completed), (3, 2));
+        assert_eq!(response.percent_complete, 40.0);
+
+        let response = capability
+            .note_task_summary(request(Some("Projects"), true))
+            .await
+            .unwrap();
+        assert_eq!(response.notes[1].percent_complete, 50.0);
+        assert_eq!(percent_complete(2, 1), 33.3);
+
+        assert_eq!(
+            capability
+                .note_task_summary(request(Some("Missing"), false))
+                .await
+                .unwrap_err()
+                .code(),
+            "not_found"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_project_burndown_requires_scope() {
+        let temp_dir = create_project_vault();
+        let capability =
+            ActivityCapability::new(temp_dir.path().to_path_buf(), Arc::new(Config::default()));
+
+        assert!(
+            capability
+                .project_burndown(burndown_request(None, None))
+                .await
+                .is_err()
+        );
+        assert!(
+            capability
+                .project_burndown(burndown_request(None, Some("Missing")))
+                .await
+                .is_err()
+        );
+    }
+
+    fn create_timesheet_vault() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("project.md"),
+            "- [x] Design #alpha ⏱ 1h30m ✅ 2025-01-06\n\
+             - [x] Review #alpha #beta [spent:: 45m] ✅ 2025-01-07\n\
+             - [ ] Untracked #alpha\n\
+             - [ ] Undated spent:: 2h\n",
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("2025-01-07.md"),
+            "- [ ] Standup #meeting ⏱ 15m\n",
+        )
+        .unwrap();
+        temp_dir
+    }
+
+    fn time_request(group_by: TimeGroupBy) -> TimeReportRequest {
+        TimeReportRequest {
+            path: None,
+            group_by: Some(group_by),
+            tags: None,
+            start_date: None,
+            end_date: None,
+            strict: None,
+        }
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(0), "0m");
+        assert_eq!(format_duration(45), "45m");
+        assert_eq!(format_duration(120), "2h");
+        assert_eq!(format_duration(90), "1h 30m");
+    }
+
+    #[tokio::test]
+    async fn test_time_report_by_tag() {
+        let temp_dir = create_timesheet_vault();
+        let capability =
+            ActivityCapability::new(temp_dir.path().to_path_buf(), Arc::new(Config::default()));
+
+        let response = capability
+            .time_report(time_request(TimeGroupBy::Tag))
+            .await
+            .unwrap();
+
+        assert_eq!(response.total_minutes, 270);
+        assert_eq!(response.total_duration, "4h 30m");
+        assert_eq!(response.task_count, 4);
+
+        let summary: Vec<_> = response
+            .groups
+            .iter()
+            .map(|g| (g.key.as_str(), g.minutes, g.task_count))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("alpha", 135, 2),
+                ("untagged", 120, 1),
+                ("beta", 45, 1),
+                ("meeting", 15, 1),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_time_report_by_day_and_file() {
+        let temp_dir = create_timesheet_vault();
+        let capability =
+            ActivityCapability::new(temp_dir.path().to_path_buf(), Arc::new(Config::default()));
+
+        let response = capability
+            .time_report(time_request(TimeGroupBy::Day))
+            .await
+            .unwrap();
+        let summary: Vec<_> = response
+            .groups
+            .iter()
+            .map(|g| (g.key.as_str(), g.minutes))
+            .collect();
+        // The daily note's date is used for tasks without a completion date
+        assert_eq!(
+            summary,
+            vec![("2025-01-06", 90), ("2025-01-07", 60), ("undated", 120)]
+        );
+
+        let response = capability
+            .time_report(time_request(TimeGroupBy::File))
+            .await
+            .unwrap();
+        let summary: Vec<_> = response
+            .groups
+            .iter()
+            .map(|g| (g.key.as_str(), g.minutes))
+            .collect();
+        assert_eq!(summary, vec![("project.md", 255), ("2025-01-07.md", 15)]);
+    }
+
+    #[tokio::test]
+    async fn test_time_report_date_range_excludes_undated() {
+        let temp_dir = create_timesheet_vault();
+        let capability =
+            ActivityCapability::new(temp_dir.path().to_path_buf(), Arc::new(Config::default()));
+
+        let mut request = time_request(TimeGroupBy::Day);
+        request.start_date = Some(date("2025-01-07"));
+        let response = capability.time_report(request).await.unwrap();
+        assert_eq!(response.total_minutes, 60);
+        assert_eq!(response.groups.len(), 1);
+
+        let mut request = time_request(TimeGroupBy::Day);
+        request.start_date = Some(date("2025-01-08"));
+        request.end_date = Some(date("2025-01-07"));
+        assert!(capability.time_report(request).await.is_err());
+    }
+}
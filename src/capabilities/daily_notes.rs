@@ -1,24 +1,29 @@
 //! Daily Notes capability
 //!
-//! Provides operations for querying Obsidian daily notes by date or date range.
-//! Supports configurable date patterns and leverages multi-file reading for efficiency.
+//! Provides operations for querying Obsidian daily notes by date or date range,
+//! and for appending to a day's note. Supports configurable date patterns and
+//! leverages multi-file reading for efficiency.
 
 pub mod date_utils;
 pub mod pattern;
 
 use crate::capabilities::CapabilityResult;
 use crate::capabilities::files::{FileCapability, ReadFilesRequest};
+use crate::completion_dates::stamp_before_write;
 use crate::config::Config;
-use crate::error::{internal_error, invalid_params};
+use crate::error::{internal_error, invalid_date, invalid_params};
+use crate::path_resolver::{PathResolver, to_slash};
+use chrono::{Days, NaiveDate};
 use clap::{CommandFactory, FromArgMatches};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::fs;
 use std::path::PathBuf;
 use std::sync::Arc;
 
 // Re-export for internal use
 use date_utils::{date_range, today, validate_date};
-use pattern::get_daily_note_relative_path;
+use pattern::{daily_note_target, find_daily_note_in, get_daily_note_relative_path};
 
 /// Operation metadata for get_daily_note
 pub mod get_daily_note {
@@ -36,6 +41,14 @@ pub mod search_daily_notes {
     pub const HTTP_PATH: &str = "/api/daily-notes/search";
 }
 
+/// Operation metadata for append_daily_note
+pub mod append_daily_note {
+    pub const DESCRIPTION: &str = "Append a block of text to the daily note for a date (default today). Creates the note from the first configured daily note pattern if it doesn't exist. The block is separated from existing content by a blank line.";
+    #[allow(dead_code)]
+    pub const CLI_NAME: &str = "append-daily-note";
+    pub const HTTP_PATH: &str = "/api/daily-notes/append";
+}
+
 /// Parameters for the get_daily_note operation
 #[derive(Debug, Deserialize, Serialize, JsonSchema, clap::Parser)]
 #[command(name = "get-daily-note", about = "Get daily note for a specific date")]
@@ -145,6 +158,41 @@ pub struct DailyNoteResult {
     pub error: Option<String>,
 }
 
+/// Parameters for the append_daily_note operation
+#[derive(Debug, Deserialize, Serialize, JsonSchema, clap::Parser)]
+#[command(
+    name = "append-daily-note",
+    about = "Append text to the daily note for a date"
+)]
+pub struct AppendDailyNoteRequest {
+    /// Path to vault (CLI only - not used in HTTP/MCP)
+    #[arg(index = 1, required = true, help = "Path to vault")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub vault_path: Option<PathBuf>,
+
+    /// Text to append
+    #[arg(index = 2, required = true, help = "Text to append")]
+    #[schemars(description = "Markdown text to append; may span several lines")]
+    pub content: String,
+
+    /// Date in YYYY-MM-DD format
+    #[arg(long, help = "Date in YYYY-MM-DD format (default: today)")]
+    #[schemars(description = "Date in YYYY-MM-DD format (e.g., 2025-01-20). Defaults to today")]
+    pub date: Option<String>,
+}
+
+/// Response from the append_daily_note operation
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AppendDailyNoteResponse {
+    /// Date in YYYY-MM-DD format
+    pub date: String,
+    /// File path relative to vault root
+    pub file_path: String,
+    /// Whether the note was created by this request
+    pub created: bool,
+}
+
 /// Capability for daily note operations
 pub struct DailyNoteCapability {
     base_path: PathBuf,
@@ -173,7 +221,7 @@ impl DailyNoteCapability {
     ) -> CapabilityResult<GetDailyNoteResponse> {
         // Validate date format
         if !validate_date(&request.date) {
-            return Err(invalid_params("Date must be in YYYY-MM-DD format"));
+            return Err(invalid_date("Date must be in YYYY-MM-DD format"));
         }
 
         // Find the daily note file
@@ -247,6 +295,57 @@ impl DailyNoteCapability {
         }
     }
 
+    /// Append a block of text to a day's note, creating the note if needed
+    pub async fn append_daily_note(
+        &self,
+        request: AppendDailyNoteRequest,
+    ) -> CapabilityResult<AppendDailyNoteResponse> {
+        let date = request.date.unwrap_or_else(today);
+        if !validate_date(&date) {
+            return Err(invalid_date("Date must be in YYYY-MM-DD format"));
+        }
+        let block = request.content.trim_end_matches(['\n', '\r']);
+        if block.trim().is_empty() {
+            return Err(invalid_params("Content must not be empty"));
+        }
+
+        let relative =
+            daily_note_target(&self.base_path, &date, &self.config).map_err(internal_error)?;
+        let path = self.base_path.join(&relative);
+        stamp_before_write(&path, &self.config)?;
+        let existing = match fs::read_to_string(&path) {
+            Ok(content) => Some(content),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => return Err(internal_error(format!("Failed to read daily note: {}", e))),
+        };
+
+        let mut content = existing.clone().unwrap_or_default();
+        if !content.is_empty() {
+            // Start a new paragraph so the block doesn't merge with the last one
+            if !content.ends_with('\n') {
+                content.push('\n');
+            }
+            if !content.ends_with("\n\n") {
+                content.push('\n');
+            }
+        }
+        content.push_str(block);
+        content.push('\n');
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| internal_error(format!("Failed to create folder: {}", e)))?;
+        }
+        fs::write(&path, content)
+            .map_err(|e| internal_error(format!("Failed to write daily note: {}", e)))?;
+
+        Ok(AppendDailyNoteResponse {
+            date,
+            file_path: to_slash(&relative),
+            created: existing.is_none(),
+        })
+    }
+
     /// Search for daily notes in a date range
     pub async fn search_daily_notes(
         &self,
@@ -255,93 +354,81 @@ impl DailyNoteCapability {
         // Determine date range
         let end_date = request.end_date.unwrap_or_else(today);
         let start_date = request.start_date.unwrap_or_else(|| {
-            // Default to 30 days before end_date
-            let dates = date_range(&end_date, &end_date);
-            if dates.is_empty() {
-                end_date.clone()
-            } else {
-                // Try to go back 30 days
-                let all_dates = date_utils::date_range("2000-01-01", &end_date);
-                let start_idx = all_dates.len().saturating_sub(30);
-                all_dates
-                    .get(start_idx)
-                    .cloned()
-                    .unwrap_or(end_date.clone())
-            }
+            // Default to the 30 days ending at end_date
+            NaiveDate::parse_from_str(&end_date, "%Y-%m-%d")
+                .ok()
+                .and_then(|end| end.checked_sub_days(Days::new(29)))
+                .map_or_else(|| end_date.clone(), |start| start.to_string())
         });
 
         // Validate dates
         if !validate_date(&start_date) {
-            return Err(invalid_params("start_date must be in YYYY-MM-DD format"));
+            return Err(invalid_date("start_date must be in YYYY-MM-DD format"));
         }
         if !validate_date(&end_date) {
-            return Err(invalid_params("end_date must be in YYYY-MM-DD format"));
+            return Err(invalid_date("end_date must be in YYYY-MM-DD format"));
         }
 
         // Check date range limit (365 days max)
-        let dates = date_range(&start_date, &end_date);
+        let mut dates = date_range(&start_date, &end_date);
         if dates.is_empty() {
-            return Err(invalid_params(
+            return Err(invalid_date(
                 "Invalid date range: start_date must be <= end_date",
             ));
         }
         if dates.len() > 365 {
-            return Err(invalid_params("Date range limited to 365 days"));
+            return Err(invalid_date("Date range limited to 365 days"));
         }
+        let dates_searched = dates.len();
 
         // Determine sort order
         let sort_desc = request.sort.as_deref() != Some("asc");
         let limit = request.limit.unwrap_or(100);
         let include_content = request.include_content.unwrap_or(false);
 
-        // Collect all daily notes in the range
-        let mut found_notes: Vec<DailyNoteResult> = Vec::new();
+        // Walk the dates in the requested order so the page fills first;
+        // notes past the limit are only counted, never built or read
+        if sort_desc {
+            dates.reverse();
+        }
+        let resolver = PathResolver::new(&self.base_path)?;
+        let mut notes: Vec<DailyNoteResult> = Vec::new();
+        let mut total_count = 0;
 
-        for date in &dates {
-            match get_daily_note_relative_path(
+        for date in dates {
+            let Ok(Some(path)) = find_daily_note_in(
+                &resolver,
                 &self.base_path,
-                date,
+                &date,
                 &self.config.daily_note_patterns,
                 &self.config,
-            ) {
-                Some(file_path) => {
-                    let file_name = PathBuf::from(&file_path)
-                        .file_name()
-                        .map(|n| n.to_string_lossy().to_string())
-                        .unwrap_or_else(|| file_path.clone());
-
-                    found_notes.push(DailyNoteResult {
-                        date: date.clone(),
-                        file_path,
-                        file_name,
-                        content: None,
-                        error: None,
-                    });
-                }
-                None => {
-                    // Note doesn't exist - skip it
-                }
+            ) else {
+                // Note doesn't exist - skip it
+                continue;
+            };
+            let Ok(relative) = path.strip_prefix(&self.base_path) else {
+                continue;
+            };
+            total_count += 1;
+            if notes.len() >= limit {
+                continue;
             }
-        }
-
-        // Sort found notes
-        if sort_desc {
-            found_notes.sort_by(|a, b| b.date.cmp(&a.date));
-        } else {
-            found_notes.sort_by(|a, b| a.date.cmp(&b.date));
-        }
 
-        // Count total found notes
-        let total_count = found_notes.len();
-        let dates_searched = dates.len();
-
-        // Apply limit to found notes only
-        let mut notes = found_notes;
-        if notes.len() > limit {
-            notes.truncate(limit);
+            let file_path = to_slash(relative);
+            let file_name = relative
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| file_path.clone());
+            notes.push(DailyNoteResult {
+                date,
+                file_path,
+                file_name,
+                content: None,
+                error: None,
+            });
         }
 
-        // If include_content is true, read all found notes in batch
+        // If include_content is true, read the notes on the page in one batch
         if include_content {
             let file_paths: Vec<String> = notes.iter().map(|n| n.file_path.clone()).collect();
 
@@ -413,6 +500,17 @@ impl SearchDailyNotesOperation {
     }
 }
 
+/// Operation struct for append_daily_note (HTTP, CLI, and MCP)
+pub struct AppendDailyNoteOperation {
+    capability: Arc<DailyNoteCapability>,
+}
+
+impl AppendDailyNoteOperation {
+    pub fn new(capability: Arc<DailyNoteCapability>) -> Self {
+        Self { capability }
+    }
+}
+
 #[async_trait::async_trait]
 impl crate::operation::Operation for GetDailyNoteOperation {
     fn name(&self) -> &'static str {
@@ -427,6 +525,10 @@ impl crate::operation::Operation for GetDailyNoteOperation {
         get_daily_note::DESCRIPTION
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     fn get_command(&self) -> clap::Command {
         GetDailyNoteRequest::command()
     }
@@ -434,7 +536,7 @@ impl crate::operation::Operation for GetDailyNoteOperation {
     async fn execute_json(
         &self,
         json: serde_json::Value,
-    ) -> Result<serde_json::Value, rmcp::model::ErrorData> {
+    ) -> Result<serde_json::Value, crate::error::Error> {
         crate::http_router::execute_json_operation(json, |req| self.capability.get_daily_note(req))
             .await
     }
@@ -465,6 +567,11 @@ impl crate::operation::Operation for GetDailyNoteOperation {
         use schemars::schema_for;
         serde_json::to_value(schema_for!(GetDailyNoteRequest)).unwrap()
     }
+
+    fn output_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(GetDailyNoteResponse)).unwrap()
+    }
 }
 
 #[async_trait::async_trait]
@@ -481,6 +588,10 @@ impl crate::operation::Operation for SearchDailyNotesOperation {
         search_daily_notes::DESCRIPTION
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     fn get_command(&self) -> clap::Command {
         SearchDailyNotesRequest::command()
     }
@@ -488,7 +599,7 @@ impl crate::operation::Operation for SearchDailyNotesOperation {
     async fn execute_json(
         &self,
         json: serde_json::Value,
-    ) -> Result<serde_json::Value, rmcp::model::ErrorData> {
+    ) -> Result<serde_json::Value, crate::error::Error> {
         crate::http_router::execute_json_operation(json, |req| {
             self.capability.search_daily_notes(req)
         })
@@ -521,6 +632,72 @@ impl crate::operation::Operation for SearchDailyNotesOperation {
         use schemars::schema_for;
         serde_json::to_value(schema_for!(SearchDailyNotesRequest)).unwrap()
     }
+
+    fn output_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(SearchDailyNotesResponse)).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::operation::Operation for AppendDailyNoteOperation {
+    fn name(&self) -> &'static str {
+        append_daily_note::CLI_NAME
+    }
+
+    fn path(&self) -> &'static str {
+        append_daily_note::HTTP_PATH
+    }
+
+    fn description(&self) -> &'static str {
+        append_daily_note::DESCRIPTION
+    }
+
+    fn get_command(&self) -> clap::Command {
+        AppendDailyNoteRequest::command()
+    }
+
+    async fn execute_json(
+        &self,
+        json: serde_json::Value,
+    ) -> Result<serde_json::Value, crate::error::Error> {
+        crate::http_router::execute_json_operation(json, |req| {
+            self.capability.append_daily_note(req)
+        })
+        .await
+    }
+
+    async fn execute_from_args(
+        &self,
+        matches: &clap::ArgMatches,
+        _registry: &crate::capabilities::CapabilityRegistry,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        let request = AppendDailyNoteRequest::from_arg_matches(matches)?;
+
+        let response = if let Some(ref vault_path) = request.vault_path {
+            let config = Arc::new(Config::load_from_base_path(vault_path.as_path()));
+            let file_cap = Arc::new(FileCapability::new(vault_path.clone(), Arc::clone(&config)));
+            let capability =
+                DailyNoteCapability::new(vault_path.clone(), Arc::clone(&config), file_cap);
+            let mut req_without_path = request;
+            req_without_path.vault_path = None;
+            capability.append_daily_note(req_without_path).await?
+        } else {
+            self.capability.append_daily_note(request).await?
+        };
+
+        Ok(serde_json::to_string_pretty(&response)?)
+    }
+
+    fn input_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(AppendDailyNoteRequest)).unwrap()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(AppendDailyNoteResponse)).unwrap()
+    }
 }
 
 #[cfg(test)]
@@ -572,7 +749,7 @@ mod tests {
 
         let config = Arc::new(Config {
             exclude_paths: vec![],
-            daily_note_patterns: crate::config::default_daily_note_patterns(),
+            ..Default::default()
         });
         let file_cap = Arc::new(FileCapability::new(
             base_path.to_path_buf(),
@@ -626,7 +803,7 @@ mod tests {
 
         let config = Arc::new(Config {
             exclude_paths: vec![],
-            daily_note_patterns: crate::config::default_daily_note_patterns(),
+            ..Default::default()
         });
         let file_cap = Arc::new(FileCapability::new(
             base_path.to_path_buf(),
@@ -662,7 +839,7 @@ mod tests {
 
         let config = Arc::new(Config {
             exclude_paths: vec![],
-            daily_note_patterns: crate::config::default_daily_note_patterns(),
+            ..Default::default()
         });
         let file_cap = Arc::new(FileCapability::new(
             base_path.to_path_buf(),
@@ -700,7 +877,7 @@ mod tests {
 
         let config = Arc::new(Config {
             exclude_paths: vec![],
-            daily_note_patterns: crate::config::default_daily_note_patterns(),
+            ..Default::default()
         });
         let file_cap = Arc::new(FileCapability::new(
             base_path.to_path_buf(),
@@ -739,7 +916,7 @@ mod tests {
 
         let config = Arc::new(Config {
             exclude_paths: vec![],
-            daily_note_patterns: crate::config::default_daily_note_patterns(),
+            ..Default::default()
         });
         let file_cap = Arc::new(FileCapability::new(
             base_path.to_path_buf(),
@@ -762,6 +939,44 @@ mod tests {
         assert_eq!(response.total_count, 5); // Found all 5 notes
     }
 
+    #[tokio::test]
+    async fn test_search_daily_notes_limit_reads_only_page() {
+        let temp_dir = TempDir::new().unwrap();
+        let base_path = temp_dir.path();
+
+        for day in [1u32, 10, 17, 24] {
+            fs::write(
+                base_path.join(format!("2025-01-{:02}.md", day)),
+                format!("# Jan {}", day),
+            )
+            .unwrap();
+        }
+
+        let config = Arc::new(Config::default());
+        let file_cap = Arc::new(FileCapability::new(
+            base_path.to_path_buf(),
+            Arc::clone(&config),
+        ));
+        let capability = DailyNoteCapability::new(base_path.to_path_buf(), config, file_cap);
+
+        // The default range is the 30 days ending at end_date
+        let request = SearchDailyNotesRequest {
+            vault_path: None,
+            start_date: None,
+            end_date: Some("2025-01-31".to_string()),
+            limit: Some(2),
+            sort: Some("asc".to_string()),
+            include_content: Some(true),
+        };
+
+        let response = capability.search_daily_notes(request).await.unwrap();
+        assert_eq!(response.dates_searched, 30);
+        assert_eq!(response.total_count, 3);
+        let dates: Vec<_> = response.notes.iter().map(|n| n.date.as_str()).collect();
+        assert_eq!(dates, vec!["2025-01-10", "2025-01-17"]);
+        assert_eq!(response.notes[0].content.as_deref(), Some("# Jan 10"));
+    }
+
     #[tokio::test]
     async fn test_search_daily_notes_invalid_date_range() {
         let temp_dir = TempDir::new().unwrap();
@@ -769,7 +984,7 @@ mod tests {
 
         let config = Arc::new(Config {
             exclude_paths: vec![],
-            daily_note_patterns: crate::config::default_daily_note_patterns(),
+            ..Default::default()
         });
         let file_cap = Arc::new(FileCapability::new(
             base_path.to_path_buf(),
@@ -797,7 +1012,7 @@ mod tests {
 
         let config = Arc::new(Config {
             exclude_paths: vec![],
-            daily_note_patterns: crate::config::default_daily_note_patterns(),
+            ..Default::default()
         });
         let file_cap = Arc::new(FileCapability::new(
             base_path.to_path_buf(),
@@ -819,4 +1034,71 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("365 days"));
     }
+
+    #[tokio::test]
+    async fn test_append_daily_note() {
+        let temp_dir = TempDir::new().unwrap();
+        let base_path = temp_dir.path();
+        fs::write(base_path.join("2025-01-20.md"), "# Jan 20\n- [ ] Existing").unwrap();
+
+        let config = Arc::new(Config {
+            daily_note_patterns: vec![
+                "Daily/YYYY/YYYY-MM-DD.md".to_string(),
+                "YYYY-MM-DD.md".to_string(),
+            ],
+            ..Default::default()
+        });
+        let file_cap = Arc::new(FileCapability::new(
+            base_path.to_path_buf(),
+            Arc::clone(&config),
+        ));
+        let capability = DailyNoteCapability::new(base_path.to_path_buf(), config, file_cap);
+
+        let append = |date: &str, content: &str| AppendDailyNoteRequest {
+            vault_path: None,
+            content: content.to_string(),
+            date: Some(date.to_string()),
+        };
+
+        // Existing notes are found by any pattern
+        let response = capability
+            .append_daily_note(append("2025-01-20", "## Log\nShipped it\n"))
+            .await
+            .unwrap();
+        assert!(!response.created);
+        assert_eq!(response.file_path, "2025-01-20.md");
+        assert_eq!(
+            fs::read_to_string(base_path.join("2025-01-20.md")).unwrap(),
+            "# Jan 20\n- [ ] Existing\n\n## Log\nShipped it\n"
+        );
+
+        // Missing notes are created from the first pattern
+        let response = capability
+            .append_daily_note(append("2025-01-21", "First entry"))
+            .await
+            .unwrap();
+        assert!(response.created);
+        assert_eq!(response.file_path, "Daily/2025/2025-01-21.md");
+        capability
+            .append_daily_note(append("2025-01-21", "Second entry"))
+            .await
+            .unwrap();
+        assert_eq!(
+            fs::read_to_string(base_path.join("Daily/2025/2025-01-21.md")).unwrap(),
+            "First entry\n\nSecond entry\n"
+        );
+
+        assert!(
+            capability
+                .append_daily_note(append("2025-13-01", "x"))
+                .await
+                .is_err()
+        );
+        assert!(
+            capability
+                .append_daily_note(append("2025-01-22", "  \n"))
+                .await
+                .is_err()
+        );
+    }
 }
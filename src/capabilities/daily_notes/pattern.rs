@@ -1,21 +1,44 @@
 //! Pattern matching and file discovery for daily notes
 //!
-//! Handles pattern substitution (YYYY/MM/DD) and file discovery with security checks.
+//! Handles pattern substitution (YYYY/MM/DD, plus `dddd`/`MMMM` day and month
+//! names in the configured locale) and file discovery with security checks.
 
 use crate::capabilities::daily_notes::date_utils::parse_date;
 use crate::config::Config;
-use std::path::{Path, PathBuf};
+use crate::locale::Locale;
+use crate::path_resolver::{PathResolver, to_slash};
+use chrono::{Datelike, NaiveDate};
+use std::path::{Component, Path, PathBuf};
 
-/// Apply a pattern by substituting YYYY, MM, DD placeholders with date components
+/// Apply a pattern by substituting YYYY, MM, DD placeholders with date
+/// components, and dddd and MMMM with the weekday and month names in `locale`
 ///
-/// Example: "YYYY/MM/DD.md" with date "2025-01-20" → "2025/01/20.md"
-pub fn apply_pattern(pattern: &str, date: &str) -> Option<String> {
+/// Example: "YYYY/MM/DD.md" with date "2025-01-20" → "2025/01/20.md", and
+/// "YYYY-MM-DD dddd.md" in German → "2025-01-20 Montag.md"
+pub fn apply_pattern(pattern: &str, date: &str, locale: Locale) -> Option<String> {
     let (year, month, day) = parse_date(date)?;
-
-    let result = pattern
-        .replace("YYYY", &format!("{:04}", year))
-        .replace("MM", &format!("{:02}", month))
-        .replace("DD", &format!("{:02}", day));
+    let weekday = NaiveDate::from_ymd_opt(year as i32, month, day)?.weekday();
+
+    let mut result = String::with_capacity(pattern.len());
+    let mut rest = pattern;
+    while let Some(c) = rest.chars().next() {
+        // Longer tokens first, so MMMM isn't read as MM twice
+        let (value, token_len) = if rest.starts_with("YYYY") {
+            (format!("{:04}", year), 4)
+        } else if rest.starts_with("MMMM") {
+            (locale.month_name(month)?.to_string(), 4)
+        } else if rest.starts_with("dddd") {
+            (locale.weekday_name(weekday).to_string(), 4)
+        } else if rest.starts_with("MM") {
+            (format!("{:02}", month), 2)
+        } else if rest.starts_with("DD") {
+            (format!("{:02}", day), 2)
+        } else {
+            (c.to_string(), c.len_utf8())
+        };
+        result.push_str(&value);
+        rest = &rest[token_len..];
+    }
 
     Some(result)
 }
@@ -29,12 +52,25 @@ pub fn find_daily_note(
     date: &str,
     patterns: &[String],
     config: &Config,
+) -> Result<Option<PathBuf>, String> {
+    let resolver = PathResolver::new(base_path).map_err(|e| e.to_string())?;
+    find_daily_note_in(&resolver, base_path, date, patterns, config)
+}
+
+/// Find a daily note file for a specific date with a resolver for
+/// `base_path`, so lookups across many dates share it
+pub fn find_daily_note_in(
+    resolver: &PathResolver,
+    base_path: &Path,
+    date: &str,
+    patterns: &[String],
+    config: &Config,
 ) -> Result<Option<PathBuf>, String> {
     let mut found_paths: Vec<PathBuf> = Vec::new();
 
     for pattern in patterns {
-        let substituted =
-            apply_pattern(pattern, date).ok_or_else(|| format!("Invalid pattern: {}", pattern))?;
+        let substituted = apply_pattern(pattern, date, config.locale)
+            .ok_or_else(|| format!("Invalid pattern: {}", pattern))?;
 
         let full_path = base_path.join(&substituted);
 
@@ -42,23 +78,9 @@ pub fn find_daily_note(
         if full_path.exists() && full_path.is_file() {
             // Check if path should be excluded
             let relative_path = full_path.strip_prefix(base_path).unwrap_or(&full_path);
-            if !config.should_exclude(relative_path) {
-                // Security check: ensure path is within base directory
-                match full_path.canonicalize() {
-                    Ok(canonical_path) => {
-                        let canonical_base = base_path
-                            .canonicalize()
-                            .map_err(|e| format!("Failed to resolve base path: {}", e))?;
-
-                        if canonical_path.starts_with(&canonical_base) {
-                            found_paths.push(full_path);
-                        }
-                    }
-                    Err(_) => {
-                        // Skip files that can't be canonicalized
-                        continue;
-                    }
-                }
+            // Security check: ensure path is within base directory
+            if !config.should_exclude(relative_path) && resolver.contains(&full_path) {
+                found_paths.push(full_path);
             }
         }
     }
@@ -73,6 +95,37 @@ pub fn find_daily_note(
     }
 }
 
+/// Relative path of the daily note for a date: the existing note, or the file
+/// the first configured pattern names if there is none yet
+///
+/// Patterns that would place the note outside the vault are rejected.
+pub fn daily_note_target(base_path: &Path, date: &str, config: &Config) -> Result<PathBuf, String> {
+    if let Some(existing) = find_daily_note(base_path, date, &config.daily_note_patterns, config)?
+        && let Ok(relative) = existing.strip_prefix(base_path)
+    {
+        return Ok(relative.to_path_buf());
+    }
+
+    let pattern = config
+        .daily_note_patterns
+        .first()
+        .ok_or("No daily_note_patterns configured")?;
+    let relative = PathBuf::from(
+        apply_pattern(pattern, date, config.locale)
+            .ok_or_else(|| format!("Invalid pattern: {}", pattern))?,
+    );
+    if !relative
+        .components()
+        .all(|c| matches!(c, Component::Normal(_)))
+    {
+        return Err(format!(
+            "Daily note pattern '{}' must be a path inside the vault",
+            pattern
+        ));
+    }
+    Ok(relative)
+}
+
 /// Get the relative path for a daily note (for use in FileCapability)
 ///
 /// Returns None if no file is found
@@ -84,10 +137,7 @@ pub fn get_daily_note_relative_path(
 ) -> Option<String> {
     let full_path = find_daily_note(base_path, date, patterns, config).ok()??;
 
-    full_path
-        .strip_prefix(base_path)
-        .ok()
-        .map(|p| p.to_string_lossy().to_string())
+    full_path.strip_prefix(base_path).ok().map(to_slash)
 }
 
 #[cfg(test)]
@@ -98,19 +148,36 @@ mod tests {
 
     #[test]
     fn test_apply_pattern() {
+        let en = Locale::En;
         assert_eq!(
-            apply_pattern("YYYY-MM-DD.md", "2025-01-20"),
+            apply_pattern("YYYY-MM-DD.md", "2025-01-20", en),
             Some("2025-01-20.md".to_string())
         );
         assert_eq!(
-            apply_pattern("Daily/YYYY/MM-DD.md", "2025-01-20"),
+            apply_pattern("Daily/YYYY/MM-DD.md", "2025-01-20", en),
             Some("Daily/2025/01-20.md".to_string())
         );
         assert_eq!(
-            apply_pattern("YYYY/MM/DD.md", "2025-01-20"),
+            apply_pattern("YYYY/MM/DD.md", "2025-01-20", en),
             Some("2025/01/20.md".to_string())
         );
-        assert_eq!(apply_pattern("YYYY-MM-DD.md", "invalid"), None);
+        assert_eq!(apply_pattern("YYYY-MM-DD.md", "invalid", en), None);
+    }
+
+    #[test]
+    fn test_apply_pattern_names() {
+        assert_eq!(
+            apply_pattern("YYYY-MM-DD dddd.md", "2025-01-20", Locale::De),
+            Some("2025-01-20 Montag.md".to_string())
+        );
+        assert_eq!(
+            apply_pattern("Journal/YYYY/MMMM/DD dddd.md", "2025-08-01", Locale::Fr),
+            Some("Journal/2025/août/01 vendredi.md".to_string())
+        );
+        assert_eq!(
+            apply_pattern("MMMM DD, YYYY.md", "2025-01-20", Locale::En),
+            Some("January 20, 2025.md".to_string())
+        );
     }
 
     #[test]
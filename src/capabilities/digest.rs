@@ -0,0 +1,352 @@
+//! Digest capability
+//!
+//! Renders a plain-text digest of overdue tasks and tasks due today and
+//! emails it through the `[smtp]` server configured for the vault.
+
+use crate::capabilities::tasks::exclude_archived;
+use crate::capabilities::{CapabilityResult, check_strict};
+use crate::config::Config;
+use crate::error::{Error, conflict, internal_error, scan_error};
+use crate::extractor::{Task, TaskExtractor, TaskStatus};
+use crate::filter::{FilterOptions, filter_tasks};
+use crate::warnings::ExtractionWarning;
+use chrono::{Local, NaiveDate};
+use clap::{CommandFactory, FromArgMatches, Parser};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Operation metadata for send_digest
+pub mod send_digest {
+    pub const DESCRIPTION: &str = "Email a digest of overdue tasks and tasks due today to the recipients in the vault's [smtp] configuration. Use dry_run to preview the message without sending it.";
+    #[allow(dead_code)]
+    pub const CLI_NAME: &str = "send-digest";
+    pub const HTTP_PATH: &str = "/api/digest/send";
+}
+
+/// Parameters for the send_digest operation
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Parser)]
+#[command(
+    name = "send-digest",
+    about = "Email a digest of overdue tasks and tasks due today"
+)]
+pub struct SendDigestRequest {
+    /// Path to vault (CLI only - not used in HTTP/MCP)
+    #[arg(index = 1, required = true, help = "Path to vault")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub path: Option<PathBuf>,
+
+    #[arg(long, help = "Day the digest is for (YYYY-MM-DD, default: today)")]
+    #[schemars(description = "Day the digest is for (YYYY-MM-DD). Default: today")]
+    pub date: Option<NaiveDate>,
+
+    #[arg(long, help = "Render the digest without sending it")]
+    #[schemars(
+        description = "If true, render the digest and return it without sending. Works without [smtp] configuration. Default: false"
+    )]
+    pub dry_run: Option<bool>,
+
+    #[arg(long, help = "Don't send anything when no tasks are overdue or due")]
+    #[schemars(
+        description = "If true, don't send an email when no tasks are overdue or due today. Default: false"
+    )]
+    pub skip_if_empty: Option<bool>,
+
+    #[arg(long, help = "Fail if any file can't be read instead of skipping it")]
+    #[schemars(
+        description = "If true, fail when a file is skipped (unreadable, invalid frontmatter, larger than max_file_size, or cut off by a truncating scan limit) instead of reporting it in warnings. Default: false"
+    )]
+    pub strict: Option<bool>,
+}
+
+/// Response from the send_digest operation
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SendDigestResponse {
+    /// Whether the email was sent
+    pub sent: bool,
+    /// Addresses the digest was (or would be) sent to
+    pub recipients: Vec<String>,
+    pub subject: String,
+    /// Plain-text message body
+    pub body: String,
+    pub overdue_count: usize,
+    pub due_today_count: usize,
+    /// Files that were skipped (unreadable, invalid frontmatter, larger
+    /// than `max_file_size`, or cut off by a truncating scan limit)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ExtractionWarning>,
+}
+
+/// Capability for the email digest
+pub struct DigestCapability {
+    base_path: PathBuf,
+    config: Arc<Config>,
+    task_extractor: Arc<TaskExtractor>,
+}
+
+impl DigestCapability {
+    /// Create a new DigestCapability
+    pub fn new(base_path: PathBuf, config: Arc<Config>) -> Self {
+        Self {
+            base_path,
+            task_extractor: Arc::new(TaskExtractor::new(Arc::clone(&config))),
+            config,
+        }
+    }
+
+    /// Render the digest and send it unless this is a dry run
+    pub async fn send_digest(
+        &self,
+        request: SendDigestRequest,
+    ) -> CapabilityResult<SendDigestResponse> {
+        let dry_run = request.dry_run.unwrap_or(false);
+        let smtp = match (&self.config.smtp, dry_run) {
+            (Some(smtp), _) => Some(smtp.clone()),
+            (None, true) => None,
+            (None, false) => {
+                return Err(conflict(
+                    "No [smtp] section in .markdown-todo-extractor.toml",
+                ));
+            }
+        };
+
+        let today = request.date.unwrap_or_else(|| Local::now().date_naive());
+        let (tasks, warnings) = self
+            .task_extractor
+            .extract_tasks_with_warnings(&self.base_path)
+            .map_err(|e| scan_error("Failed to extract tasks", e))?;
+        check_strict(&warnings, request.strict)?;
+        let (overdue, due_today) = digest_tasks(tasks, &self.config, today);
+
+        let subject = format!(
+            "Tasks for {}: {} overdue, {} due today",
+            today,
+            overdue.len(),
+            due_today.len()
+        );
+        let body = render_digest(&overdue, &due_today);
+        let recipients = smtp.as_ref().map(|s| s.to.clone()).unwrap_or_default();
+
+        let empty = overdue.is_empty() && due_today.is_empty();
+        let skip = dry_run || (empty && request.skip_if_empty.unwrap_or(false));
+        let sent = match smtp {
+            Some(smtp) if !skip => {
+                let (subject, body) = (subject.clone(), body.clone());
+                tokio::task::spawn_blocking(move || crate::smtp::send_mail(&smtp, &subject, &body))
+                    .await
+                    .map_err(|e| internal_error(e.to_string()))?
+                    .map_err(|e| internal_error(format!("Failed to send digest: {}", e)))?;
+                true
+            }
+            _ => false,
+        };
+
+        Ok(SendDigestResponse {
+            sent,
+            recipients,
+            subject,
+            body,
+            overdue_count: overdue.len(),
+            due_today_count: due_today.len(),
+            warnings,
+        })
+    }
+}
+
+/// Split incomplete tasks into overdue (oldest first) and due on `today`
+fn digest_tasks(tasks: Vec<Task>, config: &Config, today: NaiveDate) -> (Vec<Task>, Vec<Task>) {
+    let mut tasks = filter_tasks(
+        tasks,
+        &FilterOptions {
+            status: Some(TaskStatus::Incomplete),
+            due_before: today.succ_opt().map(Into::into),
+            ..Default::default()
+        },
+    );
+    exclude_archived(&mut tasks, config, false);
+    tasks.sort_by_key(|task| task.due_date);
+
+    tasks
+        .into_iter()
+        .partition(|task| task.due_date.is_some_and(|due| due < today))
+}
+
+/// Render the digest as plain text
+fn render_digest(overdue: &[Task], due_today: &[Task]) -> String {
+    if overdue.is_empty() && due_today.is_empty() {
+        return "Nothing is overdue or due today.\n".to_string();
+    }
+
+    let mut body = String::new();
+    for (title, tasks) in [("Overdue", overdue), ("Due today", due_today)] {
+        if tasks.is_empty() {
+            continue;
+        }
+        if !body.is_empty() {
+            body.push('\n');
+        }
+        body.push_str(&format!("{} ({})\n", title, tasks.len()));
+        for task in tasks {
+            body.push_str(&format!("- {}", task.content));
+            if title == "Overdue"
+                && let Some(due) = task.due_date
+            {
+                body.push_str(&format!(" (due {})", due));
+            }
+            body.push_str(&format!(" [{}:{}]\n", task.file_name, task.line_number));
+        }
+    }
+    body
+}
+
+/// Operation struct for send_digest (HTTP, CLI, and MCP)
+pub struct SendDigestOperation {
+    capability: Arc<DigestCapability>,
+}
+
+impl SendDigestOperation {
+    pub fn new(capability: Arc<DigestCapability>) -> Self {
+        Self { capability }
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::operation::Operation for SendDigestOperation {
+    fn name(&self) -> &'static str {
+        send_digest::CLI_NAME
+    }
+
+    fn path(&self) -> &'static str {
+        send_digest::HTTP_PATH
+    }
+
+    fn description(&self) -> &'static str {
+        send_digest::DESCRIPTION
+    }
+
+    fn get_command(&self) -> clap::Command {
+        // Get command from request struct's Parser derive
+        SendDigestRequest::command()
+    }
+
+    async fn execute_json(&self, json: serde_json::Value) -> Result<serde_json::Value, Error> {
+        crate::http_router::execute_json_operation(json, |req| self.capability.send_digest(req))
+            .await
+    }
+
+    async fn execute_from_args(
+        &self,
+        matches: &clap::ArgMatches,
+        _registry: &crate::capabilities::CapabilityRegistry,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        // Parse request from ArgMatches
+        let request = SendDigestRequest::from_arg_matches(matches)?;
+
+        // Handle CLI-specific path if present
+        let response = if let Some(ref path) = request.path {
+            let config = Arc::new(Config::load_from_base_path(path.as_path()));
+            let capability = DigestCapability::new(path.clone(), config);
+            let mut req_without_path = request;
+            req_without_path.path = None;
+            capability.send_digest(req_without_path).await?
+        } else {
+            self.capability.send_digest(request).await?
+        };
+
+        // Serialize to JSON
+        Ok(serde_json::to_string_pretty(&response)?)
+    }
+
+    fn input_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(SendDigestRequest)).unwrap()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(SendDigestResponse)).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn create_vault() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("todo.md"),
+            "- [ ] Pay rent 📅 2025-01-01\n\
+             - [ ] Call mom 📅 2025-01-06\n\
+             - [ ] File taxes 📅 2024-12-20\n\
+             - [x] Old chore 📅 2025-01-02\n\
+             - [ ] Later 📅 2025-01-07\n\
+             - [ ] Someday\n",
+        )
+        .unwrap();
+        temp_dir
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_renders_digest() {
+        let temp_dir = create_vault();
+        let capability =
+            DigestCapability::new(temp_dir.path().to_path_buf(), Arc::new(Config::default()));
+
+        let response = capability
+            .send_digest(SendDigestRequest {
+                date: Some(date("2025-01-06")),
+                dry_run: Some(true),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        assert!(!response.sent);
+        assert_eq!(response.overdue_count, 2);
+        assert_eq!(response.due_today_count, 1);
+        assert_eq!(
+            response.subject,
+            "Tasks for 2025-01-06: 2 overdue, 1 due today"
+        );
+
+        let lines: Vec<&str> = response.body.lines().collect();
+        assert_eq!(lines[0], "Overdue (2)");
+        assert!(lines[1].starts_with("- File taxes (due 2024-12-20) ["));
+        assert!(lines[2].starts_with("- Pay rent (due 2025-01-01) ["));
+        assert_eq!(lines[3], "");
+        assert_eq!(lines[4], "Due today (1)");
+        assert!(lines[5].starts_with("- Call mom ["));
+        assert!(lines[5].ends_with("todo.md:2]"));
+    }
+
+    #[tokio::test]
+    async fn test_send_requires_smtp_config() {
+        let temp_dir = create_vault();
+        let capability =
+            DigestCapability::new(temp_dir.path().to_path_buf(), Arc::new(Config::default()));
+
+        assert!(
+            capability
+                .send_digest(SendDigestRequest::default())
+                .await
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_render_empty_digest() {
+        assert_eq!(
+            render_digest(&[], &[]),
+            "Nothing is overdue or due today.\n"
+        );
+    }
+}
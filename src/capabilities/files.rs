@@ -1,6 +1,7 @@
 use crate::capabilities::CapabilityResult;
 use crate::config::Config;
-use crate::error::{internal_error, invalid_params};
+use crate::error::{forbidden, internal_error, invalid_params, not_found};
+use crate::path_resolver::{PathError, PathResolver, to_slash};
 use clap::{CommandFactory, FromArgMatches};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -38,6 +39,44 @@ pub struct ListFilesRequest {
     #[arg(long, help = "Include file sizes in output")]
     #[schemars(description = "Include file sizes in output (optional, defaults to false)")]
     pub include_sizes: Option<bool>,
+
+    #[arg(long, help = "Maximum number of files to list")]
+    #[schemars(
+        description = "Maximum number of files to list, in tree order (directories first, then alphabetical). Directories are only shown when they lead to a listed file (optional, defaults to all)"
+    )]
+    pub limit: Option<usize>,
+
+    #[arg(long, help = "Number of files to skip")]
+    #[schemars(
+        description = "Number of files to skip, in tree order, before applying limit, for paging (optional, defaults to 0)"
+    )]
+    pub offset: Option<usize>,
+
+    #[arg(long, value_enum, help = "How to draw the visual tree")]
+    #[schemars(
+        description = "How to draw visual_tree: indent (names indented two spaces per level), ascii (|-- branches), unicode (box-drawing branches), markdown (nested list), or paths (one vault-relative file path per line) (optional, defaults to indent)"
+    )]
+    pub style: Option<TreeStyle>,
+}
+
+/// How list_files draws its visual tree
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema, clap::ValueEnum,
+)]
+#[serde(rename_all = "snake_case")]
+#[value(rename_all = "snake_case")]
+pub enum TreeStyle {
+    /// Names indented two spaces per level
+    #[default]
+    Indent,
+    /// `|--` and `` `-- `` branches
+    Ascii,
+    /// Box-drawing branches (`├──`, `└──`)
+    Unicode,
+    /// A nested markdown list
+    Markdown,
+    /// One vault-relative file path per line, without directories
+    Paths,
 }
 
 /// A node in the file tree
@@ -55,10 +94,13 @@ pub struct FileTreeNode {
 /// Response from the list_files operation
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct ListFilesResponse {
-    /// Visual tree representation with indented structure
+    /// Visual tree representation, drawn in the requested style
     pub visual_tree: String,
     pub total_files: usize,
     pub total_directories: usize,
+    /// Whether more files follow this page
+    #[serde(default)]
+    pub truncated: bool,
 }
 
 /// Operation metadata for read_files
@@ -142,37 +184,21 @@ impl FileCapability {
         &self,
         request: ListFilesRequest,
     ) -> CapabilityResult<ListFilesResponse> {
-        // Resolve the search path
-        let search_path = if let Some(ref subpath) = request.subpath {
-            let requested_path = PathBuf::from(subpath);
-            self.base_path.join(&requested_path)
-        } else {
-            self.base_path.clone()
-        };
-
-        // Canonicalize paths for security check
-        let canonical_base = self
-            .base_path
-            .canonicalize()
-            .map_err(|e| internal_error(format!("Failed to resolve base path: {}", e)))?;
-
-        let canonical_search = search_path
-            .canonicalize()
-            .map_err(|_e| invalid_params(format!("Path not found: {:?}", request.subpath)))?;
-
-        // Security: Ensure path is within base directory
-        if !canonical_search.starts_with(&canonical_base) {
-            return Err(invalid_params(
-                "Invalid path: path must be within the vault",
-            ));
-        }
+        // Resolve the search path; symlinks may not lead outside the vault
+        let resolver = PathResolver::new(&self.base_path)?;
+        let search_path = resolver
+            .resolve(request.subpath.as_deref().unwrap_or(""))
+            .map_err(|e| match e {
+                PathError::NotFound => not_found(format!("Path not found: {:?}", request.subpath)),
+                PathError::OutsideVault => forbidden("Invalid path: path must be within the vault"),
+            })?;
 
         // Build the file tree
         let include_sizes = request.include_sizes.unwrap_or(false);
 
-        let (root, total_files, total_directories) = build_file_tree(
-            &canonical_search,
-            &canonical_base,
+        let (mut root, total_files, total_directories) = build_file_tree(
+            &search_path,
+            resolver.base(),
             &self.config,
             0,
             request.max_depth,
@@ -180,13 +206,24 @@ impl FileCapability {
         )
         .map_err(|e| internal_error(format!("Failed to build file tree: {}", e)))?;
 
+        // Page through the files in tree order
+        let mut truncated = false;
+        if root.is_directory && (request.limit.is_some() || request.offset.is_some()) {
+            let offset = request.offset.unwrap_or(0);
+            let limit = request.limit.unwrap_or(usize::MAX);
+            truncated = total_files > offset.saturating_add(limit);
+            let (mut skip, mut take) = (offset, limit);
+            page_tree(&mut root, &mut skip, &mut take);
+        }
+
         // Generate visual tree representation
-        let visual_tree = format_tree_visual(&root, 0);
+        let visual_tree = format_tree_visual(&root, request.style.unwrap_or_default());
 
         Ok(ListFilesResponse {
             visual_tree,
             total_files,
             total_directories,
+            truncated,
         })
     }
 
@@ -253,37 +290,10 @@ impl FileCapability {
             return Err(invalid_params("file_paths cannot be empty"));
         }
 
-        // Canonicalize base path once
-        let canonical_base = self
-            .base_path
-            .canonicalize()
-            .map_err(|e| internal_error(format!("Failed to resolve base path: {}", e)))?;
-
-        // Validate each path
+        // Resolve the base path once
+        let resolver = PathResolver::new(&self.base_path)?;
         for file_path in file_paths {
-            let requested_path = PathBuf::from(file_path);
-            let full_path = self.base_path.join(&requested_path);
-
-            // Check existence
-            let canonical_full = full_path
-                .canonicalize()
-                .map_err(|_| invalid_params(format!("File not found: {}", file_path)))?;
-
-            // Security check
-            if !canonical_full.starts_with(&canonical_base) {
-                return Err(invalid_params(format!(
-                    "Invalid path '{}': must be within vault",
-                    file_path
-                )));
-            }
-
-            // File type check
-            if canonical_full.extension().and_then(|s| s.to_str()) != Some("md") {
-                return Err(invalid_params(format!(
-                    "Invalid file type '{}': only .md files allowed",
-                    file_path
-                )));
-            }
+            resolver.resolve_note(file_path)?;
         }
 
         Ok(())
@@ -291,38 +301,11 @@ impl FileCapability {
 
     /// Read a single file (internal helper)
     fn read_single_file(&self, file_path: &str) -> CapabilityResult<String> {
-        // 1. Construct the full path
-        let requested_path = PathBuf::from(file_path);
-        let full_path = self.base_path.join(&requested_path);
-
-        // 2. Canonicalize paths for security check
-        let canonical_base = self
-            .base_path
-            .canonicalize()
-            .map_err(|e| internal_error(format!("Failed to resolve base path: {}", e)))?;
-
-        let canonical_full = full_path
-            .canonicalize()
-            .map_err(|_| invalid_params(format!("File not found: {}", file_path)))?;
-
-        // 3. Security: Ensure path is within base directory
-        if !canonical_full.starts_with(&canonical_base) {
-            return Err(invalid_params(format!(
-                "Invalid path '{}': must be within vault",
-                file_path
-            )));
-        }
+        // 1. Resolve the path inside the vault and check it's a markdown file
+        let full_path = PathResolver::new(&self.base_path)?.resolve_note(file_path)?;
 
-        // 4. Validate it's a markdown file
-        if canonical_full.extension().and_then(|s| s.to_str()) != Some("md") {
-            return Err(invalid_params(format!(
-                "Invalid file type '{}': only .md files allowed",
-                file_path
-            )));
-        }
-
-        // 5. Read the file content
-        let content = std::fs::read_to_string(&canonical_full)
+        // 2. Read the file content
+        let content = std::fs::read_to_string(&full_path)
             .map_err(|e| internal_error(format!("Failed to read file: {}", e)))?;
 
         Ok(content)
@@ -374,6 +357,10 @@ impl crate::operation::Operation for ListFilesOperation {
         list_files::DESCRIPTION
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     fn get_command(&self) -> clap::Command {
         // Get command from request struct's Parser derive
         ListFilesRequest::command()
@@ -382,7 +369,7 @@ impl crate::operation::Operation for ListFilesOperation {
     async fn execute_json(
         &self,
         json: serde_json::Value,
-    ) -> Result<serde_json::Value, rmcp::model::ErrorData> {
+    ) -> Result<serde_json::Value, crate::error::Error> {
         crate::http_router::execute_json_operation(json, |req| self.capability.list_files(req))
             .await
     }
@@ -414,6 +401,11 @@ impl crate::operation::Operation for ListFilesOperation {
         use schemars::schema_for;
         serde_json::to_value(schema_for!(ListFilesRequest)).unwrap()
     }
+
+    fn output_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(ListFilesResponse)).unwrap()
+    }
 }
 
 #[async_trait::async_trait]
@@ -430,6 +422,10 @@ impl crate::operation::Operation for ReadFilesOperation {
         read_files::DESCRIPTION
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     fn get_command(&self) -> clap::Command {
         // Get command from request struct's Parser derive
         ReadFilesRequest::command()
@@ -438,7 +434,7 @@ impl crate::operation::Operation for ReadFilesOperation {
     async fn execute_json(
         &self,
         json: serde_json::Value,
-    ) -> Result<serde_json::Value, rmcp::model::ErrorData> {
+    ) -> Result<serde_json::Value, crate::error::Error> {
         crate::http_router::execute_json_operation(json, |req| self.capability.read_files(req))
             .await
     }
@@ -470,26 +466,90 @@ impl crate::operation::Operation for ReadFilesOperation {
         use schemars::schema_for;
         serde_json::to_value(schema_for!(ReadFilesRequest)).unwrap()
     }
+
+    fn output_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(ReadFilesResponse)).unwrap()
+    }
 }
 
-/// Helper function to format a file tree as visual indented text
-fn format_tree_visual(node: &FileTreeNode, indent_level: usize) -> String {
+/// Helper function to format a file tree as visual text in `style`
+fn format_tree_visual(root: &FileTreeNode, style: TreeStyle) -> String {
     let mut output = String::new();
-    let indent = "  ".repeat(indent_level);
+    if style == TreeStyle::Paths {
+        push_paths(root, &mut output);
+        return output;
+    }
+
+    let (root_marker, prefix) = match style {
+        TreeStyle::Markdown => ("- ", "  "),
+        TreeStyle::Indent => ("", "  "),
+        _ => ("", ""),
+    };
+    output.push_str(&format!("{}{}\n", root_marker, display_name(root)));
+    push_children(root, style, prefix, &mut output);
+    output
+}
 
-    // Add current node
+/// Name of a node as drawn, with a trailing `/` for directories
+fn display_name(node: &FileTreeNode) -> String {
     if node.is_directory {
-        output.push_str(&format!("{}{}/\n", indent, node.name));
+        format!("{}/", node.name)
     } else {
-        output.push_str(&format!("{}{}\n", indent, node.name));
+        node.name.clone()
+    }
+}
+
+/// Branch drawn before a child in `style`, and the prefix its own children
+/// continue with
+fn branch(style: TreeStyle, last: bool) -> (&'static str, &'static str) {
+    match (style, last) {
+        (TreeStyle::Ascii, false) => ("|-- ", "|   "),
+        (TreeStyle::Ascii, true) => ("`-- ", "    "),
+        (TreeStyle::Unicode, false) => ("├── ", "│   "),
+        (TreeStyle::Unicode, true) => ("└── ", "    "),
+        (TreeStyle::Markdown, _) => ("- ", "  "),
+        (TreeStyle::Indent | TreeStyle::Paths, _) => ("", "  "),
     }
+}
+
+/// Add a line for each descendant of `node`, after `prefix`
+fn push_children(node: &FileTreeNode, style: TreeStyle, prefix: &str, output: &mut String) {
+    for (i, child) in node.children.iter().enumerate() {
+        let (marker, continuation) = branch(style, i + 1 == node.children.len());
+        output.push_str(&format!("{}{}{}\n", prefix, marker, display_name(child)));
+        push_children(child, style, &format!("{}{}", prefix, continuation), output);
+    }
+}
 
-    // Recursively add children
+/// Add the path of every file under `node`, one per line
+fn push_paths(node: &FileTreeNode, output: &mut String) {
+    if !node.is_directory {
+        output.push_str(&node.path);
+        output.push('\n');
+    }
     for child in &node.children {
-        output.push_str(&format_tree_visual(child, indent_level + 1));
+        push_paths(child, output);
     }
+}
 
-    output
+/// Keep the files after the first `skip` and up to `take` of them, in tree
+/// order, and the directories that lead to them
+fn page_tree(node: &mut FileTreeNode, skip: &mut usize, take: &mut usize) {
+    node.children.retain_mut(|child| {
+        if child.is_directory {
+            page_tree(child, skip, take);
+            !child.children.is_empty()
+        } else if *skip > 0 {
+            *skip -= 1;
+            false
+        } else if *take > 0 {
+            *take -= 1;
+            true
+        } else {
+            false
+        }
+    });
 }
 
 /// Helper function to recursively build file tree
@@ -521,11 +581,7 @@ fn build_file_tree(
                     .unwrap_or_default()
                     .to_string_lossy()
                     .to_string(),
-                path: path
-                    .strip_prefix(base_path)
-                    .unwrap_or(path)
-                    .to_string_lossy()
-                    .to_string(),
+                path: to_slash(path.strip_prefix(base_path).unwrap_or(path)),
                 is_directory: is_dir,
                 size_bytes: size,
                 children: vec![],
@@ -557,11 +613,7 @@ fn build_file_tree(
                     .unwrap_or_default()
                     .to_string_lossy()
                     .to_string(),
-                path: path
-                    .strip_prefix(base_path)
-                    .unwrap_or(path)
-                    .to_string_lossy()
-                    .to_string(),
+                path: to_slash(path.strip_prefix(base_path).unwrap_or(path)),
                 is_directory: false,
                 size_bytes: size,
                 children: vec![],
@@ -623,11 +675,7 @@ fn build_file_tree(
                 .unwrap_or_default()
                 .to_string_lossy()
                 .to_string(),
-            path: path
-                .strip_prefix(base_path)
-                .unwrap_or(path)
-                .to_string_lossy()
-                .to_string(),
+            path: to_slash(path.strip_prefix(base_path).unwrap_or(path)),
             is_directory: true,
             size_bytes: None,
             children,
@@ -636,3 +684,99 @@ fn build_file_tree(
         total_directories,
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_list_files_paging() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("Work")).unwrap();
+        fs::create_dir_all(temp_dir.path().join("Empty")).unwrap();
+        for name in ["Work/b.md", "Work/a.md", "c.md", "d.md"] {
+            fs::write(temp_dir.path().join(name), "").unwrap();
+        }
+        let capability =
+            FileCapability::new(temp_dir.path().to_path_buf(), Arc::new(Config::default()));
+        let request = |limit, offset| ListFilesRequest {
+            path: None,
+            subpath: None,
+            max_depth: None,
+            include_sizes: None,
+            limit,
+            offset,
+            style: None,
+        };
+
+        let all = capability.list_files(request(None, None)).await.unwrap();
+        assert_eq!(all.total_files, 4);
+        assert!(!all.truncated);
+        assert!(all.visual_tree.contains("Empty/"));
+
+        let page = capability
+            .list_files(request(Some(2), Some(1)))
+            .await
+            .unwrap();
+        let lines: Vec<&str> = page.visual_tree.lines().skip(1).collect();
+        assert_eq!(lines, vec!["  Work/", "    b.md", "  c.md"]);
+        assert_eq!(page.total_files, 4);
+        assert!(page.truncated);
+
+        let last = capability
+            .list_files(request(Some(2), Some(2)))
+            .await
+            .unwrap();
+        assert_eq!(last.visual_tree.lines().skip(1).count(), 2);
+        assert!(!last.truncated);
+    }
+
+    #[tokio::test]
+    async fn test_list_files_styles() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("Vault/Work/Old")).unwrap();
+        for name in ["Work/Old/x.md", "Work/a.md", "b.md"] {
+            fs::write(temp_dir.path().join("Vault").join(name), "").unwrap();
+        }
+        let capability =
+            FileCapability::new(temp_dir.path().join("Vault"), Arc::new(Config::default()));
+        let tree = |style| {
+            let capability = &capability;
+            async move {
+                let request = ListFilesRequest {
+                    path: None,
+                    subpath: None,
+                    max_depth: None,
+                    include_sizes: None,
+                    limit: None,
+                    offset: None,
+                    style: Some(style),
+                };
+                capability.list_files(request).await.unwrap().visual_tree
+            }
+        };
+
+        assert_eq!(
+            tree(TreeStyle::Indent).await,
+            "Vault/\n  Work/\n    Old/\n      x.md\n    a.md\n  b.md\n"
+        );
+        assert_eq!(
+            tree(TreeStyle::Ascii).await,
+            "Vault/\n|-- Work/\n|   |-- Old/\n|   |   `-- x.md\n|   `-- a.md\n`-- b.md\n"
+        );
+        assert_eq!(
+            tree(TreeStyle::Unicode).await,
+            "Vault/\n├── Work/\n│   ├── Old/\n│   │   └── x.md\n│   └── a.md\n└── b.md\n"
+        );
+        assert_eq!(
+            tree(TreeStyle::Markdown).await,
+            "- Vault/\n  - Work/\n    - Old/\n      - x.md\n    - a.md\n  - b.md\n"
+        );
+        assert_eq!(
+            tree(TreeStyle::Paths).await,
+            "Work/Old/x.md\nWork/a.md\nb.md\n"
+        );
+    }
+}
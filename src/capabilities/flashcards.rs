@@ -0,0 +1,520 @@
+//! Flashcard extraction
+//!
+//! Collects question/answer pairs from notes for spaced repetition. Two
+//! forms are recognized:
+//!
+//! - `Q:` / `A:` lines (optionally as list items). The answer runs until the
+//!   next blank line.
+//! - Bullets tagged `#flashcard`, either `- Term :: Definition #flashcard` or
+//!   a question bullet whose nested items are the answer.
+//!
+//! Cards can also be rendered as a tab-separated file that Anki imports
+//! directly.
+
+use crate::capabilities::{CapabilityResult, check_strict};
+use crate::config::Config;
+use crate::error::{Error, scan_error};
+use crate::frontmatter::frontmatter_line_count;
+use crate::path_resolver::to_slash;
+use crate::tag_extractor::collect_markdown_files;
+use crate::warnings::{ExtractionWarning, Warnings};
+use clap::{CommandFactory, FromArgMatches, Parser};
+use rayon::prelude::*;
+use regex::Regex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Operation metadata for extract_flashcards
+pub mod extract_flashcards {
+    pub const DESCRIPTION: &str = "Extract flashcards from notes: 'Q:'/'A:' line pairs and bullets tagged #flashcard ('- Term :: Definition #flashcard', or a question bullet whose nested items are the answer). Optionally returns the cards as Anki-importable TSV.";
+    #[allow(dead_code)]
+    pub const CLI_NAME: &str = "extract-flashcards";
+    pub const HTTP_PATH: &str = "/api/flashcards";
+}
+
+/// Tag that marks a bullet as a flashcard unless the request names another
+const DEFAULT_FLASHCARD_TAG: &str = "flashcard";
+
+/// Parameters for the extract_flashcards operation
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Parser)]
+#[command(
+    name = "extract-flashcards",
+    about = "Extract Q/A flashcards from notes"
+)]
+pub struct ExtractFlashcardsRequest {
+    /// Path to vault (CLI only - not used in HTTP/MCP)
+    #[arg(index = 1, required = true, help = "Path to vault")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub path: Option<PathBuf>,
+
+    #[arg(long, help = "Tag marking flashcard bullets (default: flashcard)")]
+    #[schemars(description = "Tag (without #) marking flashcard bullets. Default: flashcard")]
+    pub tag: Option<String>,
+
+    #[arg(long, help = "Include notes under archived paths")]
+    #[schemars(
+        description = "If true, include notes under the configured archived_paths. Default: false"
+    )]
+    pub include_archived: Option<bool>,
+
+    #[arg(long, help = "Also return the cards as Anki-importable TSV")]
+    #[schemars(
+        description = "If true, also return the cards as a tab-separated file (question, answer, tags) that Anki can import. Default: false"
+    )]
+    pub include_tsv: Option<bool>,
+
+    #[arg(long, help = "Fail if any file can't be read instead of skipping it")]
+    #[schemars(
+        description = "If true, fail when a file is skipped (unreadable, invalid frontmatter, larger than max_file_size, or cut off by a truncating scan limit) instead of reporting it in warnings. Default: false"
+    )]
+    pub strict: Option<bool>,
+}
+
+/// A question and its answer
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct Flashcard {
+    pub question: String,
+    /// Multi-line answers are joined with newlines
+    pub answer: String,
+    pub file_path: String,
+    /// 1-based line of the question
+    pub line_number: usize,
+    /// Other tags on a flashcard bullet
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub tags: Vec<String>,
+}
+
+/// Response from the extract_flashcards operation
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ExtractFlashcardsResponse {
+    /// Cards ordered by file path and line
+    pub cards: Vec<Flashcard>,
+    pub total_count: usize,
+    /// Anki import file, when requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tsv: Option<String>,
+    /// Files that were skipped (unreadable, invalid frontmatter, larger
+    /// than `max_file_size`, or cut off by a truncating scan limit)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ExtractionWarning>,
+}
+
+/// Capability for flashcard extraction
+pub struct FlashcardCapability {
+    base_path: PathBuf,
+    config: Arc<Config>,
+    tag_pattern: Regex,
+}
+
+impl FlashcardCapability {
+    /// Create a new FlashcardCapability
+    pub fn new(base_path: PathBuf, config: Arc<Config>) -> Self {
+        Self {
+            base_path,
+            config,
+            tag_pattern: Regex::new(r"(?:^|\s)#([\w/-]+)").unwrap(),
+        }
+    }
+
+    /// Extract flashcards from every note in the vault
+    pub async fn extract_flashcards(
+        &self,
+        request: ExtractFlashcardsRequest,
+    ) -> CapabilityResult<ExtractFlashcardsResponse> {
+        let tag = request
+            .tag
+            .as_deref()
+            .map(|tag| tag.trim_start_matches('#'))
+            .unwrap_or(DEFAULT_FLASHCARD_TAG);
+        let config = self
+            .config
+            .search_scope(request.include_archived.unwrap_or(false));
+        let warnings = Warnings::default();
+        let files = collect_markdown_files(&self.base_path, &config, &warnings)
+            .map_err(|e| scan_error("Failed to collect files", e))?;
+        let warnings = warnings.into_vec();
+        check_strict(&warnings, request.strict)?;
+
+        let mut cards: Vec<Flashcard> = files
+            .par_iter()
+            .flat_map_iter(|path| {
+                let content = fs::read_to_string(path).unwrap_or_default();
+                let relative = to_slash(path.strip_prefix(&self.base_path).unwrap_or(path));
+                self.parse_flashcards(&content, &relative, tag)
+            })
+            .collect();
+        cards.sort_by(|a, b| (&a.file_path, a.line_number).cmp(&(&b.file_path, b.line_number)));
+
+        let tsv = request
+            .include_tsv
+            .unwrap_or(false)
+            .then(|| anki_tsv(&cards));
+
+        Ok(ExtractFlashcardsResponse {
+            total_count: cards.len(),
+            cards,
+            tsv,
+            warnings,
+        })
+    }
+
+    /// Find the flashcards in one note
+    fn parse_flashcards(&self, content: &str, file_path: &str, tag: &str) -> Vec<Flashcard> {
+        let mut cards: Vec<Flashcard> = Vec::new();
+        // `Q:` waiting for its `A:`
+        let mut question: Option<(usize, String)> = None;
+        // Card whose answer continues on the following lines
+        let mut answer_open = false;
+        // Indentation of a flashcard bullet whose nested items are the answer
+        let mut bullet_indent: Option<usize> = None;
+        let mut fence: Option<&str> = None;
+
+        let skip = frontmatter_line_count(content);
+        for (index, line) in content.lines().enumerate().skip(skip) {
+            let trimmed = line.trim_start();
+            if let Some(marker) = fence {
+                if trimmed.starts_with(marker) {
+                    fence = None;
+                }
+                continue;
+            }
+            if let Some(marker) = ["```", "~~~"].into_iter().find(|m| trimmed.starts_with(m)) {
+                fence = Some(marker);
+                question = None;
+                answer_open = false;
+                bullet_indent = None;
+                continue;
+            }
+
+            let indent = line.len() - trimmed.len();
+            let item = strip_list_marker(trimmed);
+
+            if let Some(parent) = bullet_indent {
+                if !trimmed.is_empty() && indent > parent {
+                    append_line(cards.last_mut(), item);
+                    continue;
+                }
+                bullet_indent = None;
+            }
+
+            if trimmed.is_empty() {
+                question = None;
+                answer_open = false;
+            } else if let Some(text) = item.strip_prefix("Q:") {
+                question = Some((index + 1, text.trim().to_string()));
+                answer_open = false;
+            } else if let Some(text) = item.strip_prefix("A:")
+                && let Some((line_number, asked)) = question.take()
+            {
+                cards.push(Flashcard {
+                    question: asked,
+                    answer: text.trim().to_string(),
+                    file_path: file_path.to_string(),
+                    line_number,
+                    tags: Vec::new(),
+                });
+                answer_open = true;
+            } else if answer_open {
+                append_line(cards.last_mut(), item);
+            } else if let Some((_, text)) = question.as_mut() {
+                text.push('\n');
+                text.push_str(item.trim());
+            } else if item.len() < trimmed.len() && self.has_tag(item, tag) {
+                let (text, tags) = self.split_tags(item, tag);
+                let (question, answer) = match split_definition(&text) {
+                    Some((term, definition)) => (term, definition),
+                    None => {
+                        bullet_indent = Some(indent);
+                        (text.clone(), String::new())
+                    }
+                };
+                cards.push(Flashcard {
+                    question,
+                    answer,
+                    file_path: file_path.to_string(),
+                    line_number: index + 1,
+                    tags,
+                });
+            }
+        }
+
+        cards.retain(|card| !card.question.is_empty() && !card.answer.is_empty());
+        cards
+    }
+
+    fn has_tag(&self, text: &str, tag: &str) -> bool {
+        self.tag_pattern
+            .captures_iter(text)
+            .any(|caps| caps[1].eq_ignore_ascii_case(tag))
+    }
+
+    /// Remove `#tags` from a bullet, returning the text and the tags other than `tag`
+    fn split_tags(&self, text: &str, tag: &str) -> (String, Vec<String>) {
+        let tags = self
+            .tag_pattern
+            .captures_iter(text)
+            .map(|caps| caps[1].to_string())
+            .filter(|t| !t.eq_ignore_ascii_case(tag))
+            .collect();
+        let text = self.tag_pattern.replace_all(text, "");
+        (text.split_whitespace().collect::<Vec<_>>().join(" "), tags)
+    }
+}
+
+/// A list item's text without its `-`, `*`, `+`, or `1.` marker
+fn strip_list_marker(line: &str) -> &str {
+    if let Some(rest) = line
+        .strip_prefix("- ")
+        .or_else(|| line.strip_prefix("* "))
+        .or_else(|| line.strip_prefix("+ "))
+    {
+        return rest.trim_start();
+    }
+    let digits = line.len() - line.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    match line[digits..].strip_prefix(". ") {
+        Some(rest) if digits > 0 => rest.trim_start(),
+        _ => line,
+    }
+}
+
+/// Add a line to the answer of the card being read
+fn append_line(card: Option<&mut Flashcard>, text: &str) {
+    if let Some(card) = card {
+        if !card.answer.is_empty() {
+            card.answer.push('\n');
+        }
+        card.answer.push_str(text.trim());
+    }
+}
+
+/// Split `Term :: Definition`, ignoring `::` inside `[field:: value]` brackets
+fn split_definition(text: &str) -> Option<(String, String)> {
+    let mut depth = 0usize;
+    for (i, c) in text.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth = depth.saturating_sub(1),
+            ':' if depth == 0 && text[i..].starts_with("::") => {
+                return Some((
+                    text[..i].trim().to_string(),
+                    text[i + 2..].trim().to_string(),
+                ));
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Escape a field for Anki's HTML import, keeping line breaks
+fn anki_field(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('\t', " ")
+        .replace('\n', "<br>")
+}
+
+/// Render cards as an Anki text import: question, answer, tags
+fn anki_tsv(cards: &[Flashcard]) -> String {
+    let mut tsv = String::from("#separator:tab\n#html:true\n#tags column:3\n");
+    for card in cards {
+        tsv.push_str(&format!(
+            "{}\t{}\t{}\n",
+            anki_field(&card.question),
+            anki_field(&card.answer),
+            card.tags.join(" ")
+        ));
+    }
+    tsv
+}
+
+/// Operation struct for extract_flashcards (HTTP, CLI, and MCP)
+pub struct ExtractFlashcardsOperation {
+    capability: Arc<FlashcardCapability>,
+}
+
+impl ExtractFlashcardsOperation {
+    pub fn new(capability: Arc<FlashcardCapability>) -> Self {
+        Self { capability }
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::operation::Operation for ExtractFlashcardsOperation {
+    fn name(&self) -> &'static str {
+        extract_flashcards::CLI_NAME
+    }
+
+    fn path(&self) -> &'static str {
+        extract_flashcards::HTTP_PATH
+    }
+
+    fn description(&self) -> &'static str {
+        extract_flashcards::DESCRIPTION
+    }
+
+    fn read_only(&self) -> bool {
+        true
+    }
+
+    fn get_command(&self) -> clap::Command {
+        // Get command from request struct's Parser derive
+        ExtractFlashcardsRequest::command()
+    }
+
+    async fn execute_json(&self, json: serde_json::Value) -> Result<serde_json::Value, Error> {
+        crate::http_router::execute_json_operation(json, |req| {
+            self.capability.extract_flashcards(req)
+        })
+        .await
+    }
+
+    async fn execute_from_args(
+        &self,
+        matches: &clap::ArgMatches,
+        _registry: &crate::capabilities::CapabilityRegistry,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        // Parse request from ArgMatches
+        let request = ExtractFlashcardsRequest::from_arg_matches(matches)?;
+
+        // Handle CLI-specific path if present
+        let response = if let Some(ref path) = request.path {
+            let config = Arc::new(Config::load_from_base_path(path.as_path()));
+            let capability = FlashcardCapability::new(path.clone(), config);
+            let mut req_without_path = request;
+            req_without_path.path = None;
+            capability.extract_flashcards(req_without_path).await?
+        } else {
+            self.capability.extract_flashcards(request).await?
+        };
+
+        // Serialize to JSON
+        Ok(serde_json::to_string_pretty(&response)?)
+    }
+
+    fn input_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(ExtractFlashcardsRequest)).unwrap()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(ExtractFlashcardsResponse)).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn capability() -> FlashcardCapability {
+        FlashcardCapability::new(PathBuf::from("vault"), Arc::new(Config::default()))
+    }
+
+    fn cards(content: &str) -> Vec<(usize, String, String, Vec<String>)> {
+        capability()
+            .parse_flashcards(content, "deck.md", DEFAULT_FLASHCARD_TAG)
+            .into_iter()
+            .map(|card| (card.line_number, card.question, card.answer, card.tags))
+            .collect()
+    }
+
+    #[test]
+    fn test_question_answer_pairs() {
+        let content = "---\ntags: [bio]\n---\n\
+                       Q: What is ATP?\n\
+                       A: The cell's energy currency\n\
+                       stored in phosphate bonds\n\
+                       \n\
+                       - Q: Unanswered question\n\
+                       \n\
+                       - Q: Capital of France?\n\
+                       - A: Paris\n\
+                       ```\nQ: In code\nA: Ignored\n```\n";
+
+        assert_eq!(
+            cards(content),
+            vec![
+                (
+                    4,
+                    "What is ATP?".to_string(),
+                    "The cell's energy currency\nstored in phosphate bonds".to_string(),
+                    vec![]
+                ),
+                (
+                    10,
+                    "Capital of France?".to_string(),
+                    "Paris".to_string(),
+                    vec![]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tagged_bullets() {
+        let content = "- Mitosis :: Cell division #flashcard #bio\n\
+                       - Name the noble gases #Flashcard\n\
+                       \x20 - Helium, neon\n\
+                       \x20 - Argon\n\
+                       - Next item\n\
+                       - Untagged :: not a card\n\
+                       - Empty question #flashcard\n\
+                       - Status [due:: 2025-01-01] #flashcard\n";
+
+        assert_eq!(
+            cards(content),
+            vec![
+                (
+                    1,
+                    "Mitosis".to_string(),
+                    "Cell division".to_string(),
+                    vec!["bio".to_string()]
+                ),
+                (
+                    2,
+                    "Name the noble gases".to_string(),
+                    "Helium, neon\nArgon".to_string(),
+                    vec![]
+                ),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_extract_flashcards_tsv() {
+        let vault = TempDir::new().unwrap();
+        fs::write(vault.path().join("b.md"), "Q: 1 < 2?\nA: Yes\n& always\n").unwrap();
+        fs::write(
+            vault.path().join("a.md"),
+            "- Term :: Meaning #card #vocab\n",
+        )
+        .unwrap();
+        let capability =
+            FlashcardCapability::new(vault.path().to_path_buf(), Arc::new(Config::default()));
+
+        let response = capability
+            .extract_flashcards(ExtractFlashcardsRequest {
+                tag: Some("#card".to_string()),
+                include_tsv: Some(true),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(response.total_count, 2);
+        assert_eq!(response.cards[0].file_path, "a.md");
+        assert_eq!(
+            response.tsv.unwrap(),
+            "#separator:tab\n#html:true\n#tags column:3\n\
+             Term\tMeaning\tvocab\n\
+             1 &lt; 2?\tYes<br>&amp; always\t\n"
+        );
+    }
+}
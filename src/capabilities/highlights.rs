@@ -0,0 +1,414 @@
+//! Highlight extraction
+//!
+//! Collects the passages a reader marked as worth keeping: blockquotes
+//! (`> ...`, except Obsidian callouts such as `> [!note]`) and
+//! `==highlighted==` spans. Each highlight carries its note, line, and the
+//! nearest heading above it, for reviewing highlights across the vault.
+
+use crate::capabilities::{CapabilityResult, check_strict};
+use crate::config::Config;
+use crate::error::{Error, scan_error};
+use crate::frontmatter::frontmatter_line_count;
+use crate::outline_extractor::{Heading, OutlineExtractor};
+use crate::path_resolver::to_slash;
+use crate::tag_extractor::collect_markdown_files;
+use crate::warnings::{ExtractionWarning, Warnings};
+use clap::{CommandFactory, FromArgMatches, Parser};
+use rayon::prelude::*;
+use regex::Regex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Operation metadata for extract_highlights
+pub mod extract_highlights {
+    pub const DESCRIPTION: &str = "Extract highlights across the vault: blockquoted passages (> ..., excluding callouts) and ==highlighted== spans, each with its note, line number, and nearest heading. Filter by kind and page with limit/offset.";
+    #[allow(dead_code)]
+    pub const CLI_NAME: &str = "extract-highlights";
+    pub const HTTP_PATH: &str = "/api/highlights";
+}
+
+/// Default number of highlights returned by extract_highlights
+const DEFAULT_HIGHLIGHT_LIMIT: usize = 100;
+
+/// Kind of highlighted passage
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema, clap::ValueEnum,
+)]
+#[serde(rename_all = "snake_case")]
+#[value(rename_all = "snake_case")]
+pub enum HighlightKind {
+    /// A blockquote; consecutive `>` lines form one passage
+    Quote,
+    /// An `==inline highlight==`
+    Highlight,
+}
+
+/// Parameters for the extract_highlights operation
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Parser)]
+#[command(
+    name = "extract-highlights",
+    about = "Extract blockquotes and ==highlights== from notes"
+)]
+pub struct ExtractHighlightsRequest {
+    /// Path to vault (CLI only - not used in HTTP/MCP)
+    #[arg(index = 1, required = true, help = "Path to vault")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub path: Option<PathBuf>,
+
+    #[arg(long, help = "Only return this kind: quote or highlight")]
+    #[schemars(description = "Only return this kind ('quote' or 'highlight'). Default: both")]
+    pub kind: Option<HighlightKind>,
+
+    #[arg(long, help = "Include notes under archived paths")]
+    #[schemars(
+        description = "If true, include notes under the configured archived_paths. Default: false"
+    )]
+    pub include_archived: Option<bool>,
+
+    #[arg(long, help = "Maximum number of highlights to return")]
+    #[schemars(description = "Maximum number of highlights to return (defaults to 100)")]
+    pub limit: Option<usize>,
+
+    #[arg(long, help = "Number of highlights to skip")]
+    #[schemars(description = "Number of highlights to skip before returning results. Default: 0")]
+    pub offset: Option<usize>,
+
+    #[arg(long, help = "Fail if any file can't be read instead of skipping it")]
+    #[schemars(
+        description = "If true, fail when a file is skipped (unreadable, invalid frontmatter, larger than max_file_size, or cut off by a truncating scan limit) instead of reporting it in warnings. Default: false"
+    )]
+    pub strict: Option<bool>,
+}
+
+/// A highlighted passage
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct Highlight {
+    pub kind: HighlightKind,
+    /// Passage text, without `>` markers or `==` delimiters
+    pub text: String,
+    pub file_path: String,
+    /// 1-based line where the passage starts
+    pub line_number: usize,
+    /// Title of the closest heading above the passage
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub heading: Option<String>,
+}
+
+/// Response from the extract_highlights operation
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ExtractHighlightsResponse {
+    /// Highlights ordered by file path and line
+    pub highlights: Vec<Highlight>,
+    /// Number of highlights before limit/offset
+    pub total_count: usize,
+    /// Whether more highlights follow this page
+    pub truncated: bool,
+    /// Files that were skipped (unreadable, invalid frontmatter, larger
+    /// than `max_file_size`, or cut off by a truncating scan limit)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ExtractionWarning>,
+}
+
+/// Capability for highlight extraction
+pub struct HighlightCapability {
+    base_path: PathBuf,
+    config: Arc<Config>,
+    outline_extractor: OutlineExtractor,
+    highlight_pattern: Regex,
+    inline_code_pattern: Regex,
+}
+
+impl HighlightCapability {
+    /// Create a new HighlightCapability
+    pub fn new(base_path: PathBuf, config: Arc<Config>) -> Self {
+        Self {
+            base_path,
+            config,
+            outline_extractor: OutlineExtractor::new(),
+            highlight_pattern: Regex::new(r"==([^=\s](?:[^=]*[^=\s])?)==").unwrap(),
+            inline_code_pattern: Regex::new(r"`[^`]*`").unwrap(),
+        }
+    }
+
+    /// Extract highlights from every note in the vault
+    pub async fn extract_highlights(
+        &self,
+        request: ExtractHighlightsRequest,
+    ) -> CapabilityResult<ExtractHighlightsResponse> {
+        let config = self
+            .config
+            .search_scope(request.include_archived.unwrap_or(false));
+        let warnings = Warnings::default();
+        let files = collect_markdown_files(&self.base_path, &config, &warnings)
+            .map_err(|e| scan_error("Failed to collect files", e))?;
+        let warnings = warnings.into_vec();
+        check_strict(&warnings, request.strict)?;
+
+        let mut highlights: Vec<Highlight> = files
+            .par_iter()
+            .flat_map_iter(|path| {
+                let content = fs::read_to_string(path).unwrap_or_default();
+                let relative = to_slash(path.strip_prefix(&self.base_path).unwrap_or(path));
+                self.parse_highlights(&content, &relative)
+            })
+            .filter(|highlight| request.kind.is_none_or(|kind| highlight.kind == kind))
+            .collect();
+        highlights
+            .sort_by(|a, b| (&a.file_path, a.line_number).cmp(&(&b.file_path, b.line_number)));
+
+        let total_count = highlights.len();
+        let offset = request.offset.unwrap_or(0);
+        let limit = request.limit.unwrap_or(DEFAULT_HIGHLIGHT_LIMIT);
+        let highlights: Vec<Highlight> = highlights.into_iter().skip(offset).take(limit).collect();
+
+        Ok(ExtractHighlightsResponse {
+            truncated: offset + highlights.len() < total_count,
+            highlights,
+            total_count,
+            warnings,
+        })
+    }
+
+    /// Find the highlights in one note, in line order
+    fn parse_highlights(&self, content: &str, file_path: &str) -> Vec<Highlight> {
+        let headings = self.outline_extractor.extract_headings(content);
+        let mut highlights = Vec::new();
+        // Open blockquote: first line and lines so far; `None` text for a callout
+        let mut quote: Option<(usize, Option<Vec<&str>>)> = None;
+        let mut fence: Option<&str> = None;
+
+        let skip = frontmatter_line_count(content);
+        for (index, line) in content.lines().enumerate().skip(skip) {
+            let trimmed = line.trim_start();
+            if let Some(marker) = fence {
+                if trimmed.starts_with(marker) {
+                    fence = None;
+                }
+                continue;
+            }
+            if let Some(marker) = ["```", "~~~"].into_iter().find(|m| trimmed.starts_with(m)) {
+                fence = Some(marker);
+                self.close_quote(&mut quote, &headings, file_path, &mut highlights);
+                continue;
+            }
+
+            match trimmed.strip_prefix('>') {
+                Some(rest) => {
+                    let rest = rest.strip_prefix(' ').unwrap_or(rest);
+                    match &mut quote {
+                        Some((_, Some(lines))) => lines.push(rest),
+                        Some((_, None)) => {}
+                        None => {
+                            let callout = rest.trim_start().starts_with("[!");
+                            quote = Some((index + 1, (!callout).then(|| vec![rest])));
+                        }
+                    }
+                }
+                None => self.close_quote(&mut quote, &headings, file_path, &mut highlights),
+            }
+
+            let without_code = self.inline_code_pattern.replace_all(line, "");
+            for caps in self.highlight_pattern.captures_iter(&without_code) {
+                highlights.push(Highlight {
+                    kind: HighlightKind::Highlight,
+                    text: caps[1].to_string(),
+                    file_path: file_path.to_string(),
+                    line_number: index + 1,
+                    heading: nearest_heading(&headings, index + 1),
+                });
+            }
+        }
+        self.close_quote(&mut quote, &headings, file_path, &mut highlights);
+
+        highlights.sort_by_key(|highlight| highlight.line_number);
+        highlights
+    }
+
+    /// Record the open blockquote, if any, unless it's a callout or empty
+    fn close_quote(
+        &self,
+        quote: &mut Option<(usize, Option<Vec<&str>>)>,
+        headings: &[Heading],
+        file_path: &str,
+        highlights: &mut Vec<Highlight>,
+    ) {
+        let Some((line_number, Some(lines))) = quote.take() else {
+            return;
+        };
+        let text = lines.join("\n").trim().to_string();
+        if text.is_empty() {
+            return;
+        }
+        highlights.push(Highlight {
+            kind: HighlightKind::Quote,
+            text,
+            file_path: file_path.to_string(),
+            line_number,
+            heading: nearest_heading(headings, line_number),
+        });
+    }
+}
+
+/// Title of the last heading before `line_number`
+fn nearest_heading(headings: &[Heading], line_number: usize) -> Option<String> {
+    headings
+        .iter()
+        .take_while(|heading| heading.line_number < line_number)
+        .last()
+        .map(|heading| heading.title.clone())
+}
+
+/// Operation struct for extract_highlights (HTTP, CLI, and MCP)
+pub struct ExtractHighlightsOperation {
+    capability: Arc<HighlightCapability>,
+}
+
+impl ExtractHighlightsOperation {
+    pub fn new(capability: Arc<HighlightCapability>) -> Self {
+        Self { capability }
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::operation::Operation for ExtractHighlightsOperation {
+    fn name(&self) -> &'static str {
+        extract_highlights::CLI_NAME
+    }
+
+    fn path(&self) -> &'static str {
+        extract_highlights::HTTP_PATH
+    }
+
+    fn description(&self) -> &'static str {
+        extract_highlights::DESCRIPTION
+    }
+
+    fn read_only(&self) -> bool {
+        true
+    }
+
+    fn get_command(&self) -> clap::Command {
+        // Get command from request struct's Parser derive
+        ExtractHighlightsRequest::command()
+    }
+
+    async fn execute_json(&self, json: serde_json::Value) -> Result<serde_json::Value, Error> {
+        crate::http_router::execute_json_operation(json, |req| {
+            self.capability.extract_highlights(req)
+        })
+        .await
+    }
+
+    async fn execute_from_args(
+        &self,
+        matches: &clap::ArgMatches,
+        _registry: &crate::capabilities::CapabilityRegistry,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        // Parse request from ArgMatches
+        let request = ExtractHighlightsRequest::from_arg_matches(matches)?;
+
+        // Handle CLI-specific path if present
+        let response = if let Some(ref path) = request.path {
+            let config = Arc::new(Config::load_from_base_path(path.as_path()));
+            let capability = HighlightCapability::new(path.clone(), config);
+            let mut req_without_path = request;
+            req_without_path.path = None;
+            capability.extract_highlights(req_without_path).await?
+        } else {
+            self.capability.extract_highlights(request).await?
+        };
+
+        // Serialize to JSON
+        Ok(serde_json::to_string_pretty(&response)?)
+    }
+
+    fn input_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(ExtractHighlightsRequest)).unwrap()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(ExtractHighlightsResponse)).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_highlights() {
+        let capability =
+            HighlightCapability::new(PathBuf::from("vault"), Arc::new(Config::default()));
+        let content = "---\ntitle: Book\n---\n\
+                       Intro with ==a key idea== and ==another==.\n\
+                       # Chapter 1\n\
+                       > The first line\n\
+                       > continues here\n\
+                       \n\
+                       > [!note] A callout\n\
+                       > is not a quote\n\
+                       \n\
+                       ## Section\n\
+                       Not == a highlight == or `==code==`\n\
+                       ```\n> quoted code\n```\n\
+                       >\n\
+                       > Last ==quote==";
+
+        let highlights = capability.parse_highlights(content, "book.md");
+        let found: Vec<(HighlightKind, &str, usize, Option<&str>)> = highlights
+            .iter()
+            .map(|h| (h.kind, h.text.as_str(), h.line_number, h.heading.as_deref()))
+            .collect();
+
+        assert_eq!(
+            found,
+            vec![
+                (HighlightKind::Highlight, "a key idea", 4, None),
+                (HighlightKind::Highlight, "another", 4, None),
+                (
+                    HighlightKind::Quote,
+                    "The first line\ncontinues here",
+                    6,
+                    Some("Chapter 1")
+                ),
+                (HighlightKind::Quote, "Last ==quote==", 17, Some("Section")),
+                (HighlightKind::Highlight, "quote", 18, Some("Section")),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_extract_highlights_filter_and_page() {
+        let vault = TempDir::new().unwrap();
+        fs::write(vault.path().join("b.md"), "==one==\n> quote\n").unwrap();
+        fs::write(vault.path().join("a.md"), "==two== ==three==\n").unwrap();
+        let capability =
+            HighlightCapability::new(vault.path().to_path_buf(), Arc::new(Config::default()));
+
+        let response = capability
+            .extract_highlights(ExtractHighlightsRequest {
+                kind: Some(HighlightKind::Highlight),
+                limit: Some(2),
+                offset: Some(1),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(response.total_count, 3);
+        assert!(!response.truncated);
+        let texts: Vec<&str> = response
+            .highlights
+            .iter()
+            .map(|h| h.text.as_str())
+            .collect();
+        assert_eq!(texts, vec!["three", "one"]);
+    }
+}
@@ -0,0 +1,281 @@
+//! Quick capture capability
+//!
+//! Appends a timestamped line, or a task stamped with its created date, to
+//! the vault's inbox note (`inbox_note` in the config). The note is opened in
+//! append mode and never rewritten, so captures can't clobber edits made to
+//! the inbox in the meantime.
+
+use crate::capabilities::CapabilityResult;
+use crate::completion_dates::stamp_before_write;
+use crate::config::Config;
+use crate::error::{Error, internal_error, invalid_params};
+use crate::path_resolver::{PathResolver, from_slash, to_slash};
+use chrono::{Local, NaiveDateTime};
+use clap::{CommandFactory, FromArgMatches, Parser};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Component, PathBuf};
+use std::sync::Arc;
+
+/// Operation metadata for capture
+pub mod capture {
+    pub const DESCRIPTION: &str = "Quick capture: append a line to the vault's inbox note (inbox_note in the config, default Inbox.md), prefixed with the current date and time. With task, it is added as a '- [ ]' task with a '➕ YYYY-MM-DD' created date instead. The inbox is created if missing.";
+    #[allow(dead_code)]
+    pub const CLI_NAME: &str = "capture";
+    pub const HTTP_PATH: &str = "/api/capture";
+}
+
+/// Parameters for the capture operation
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Parser)]
+#[command(name = "capture", about = "Append a line or task to the inbox note")]
+pub struct CaptureRequest {
+    /// Path to vault (CLI only - not used in HTTP/MCP)
+    #[arg(index = 1, required = true, help = "Path to vault")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub path: Option<PathBuf>,
+
+    #[arg(index = 2, required = true, help = "Text to capture")]
+    #[schemars(description = "Text to capture; line breaks are folded into spaces")]
+    pub text: String,
+
+    #[arg(long, help = "Capture as a task")]
+    #[schemars(
+        description = "If true, add the text as a '- [ ]' task with today's created date. Default: false"
+    )]
+    pub task: Option<bool>,
+}
+
+/// Response from the capture operation
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct CaptureResponse {
+    /// Vault-relative path of the inbox note
+    pub file_path: String,
+    /// 1-based line of the captured entry
+    pub line_number: usize,
+    /// The line as written
+    pub line: String,
+    /// Whether the inbox note was created by this capture
+    pub created: bool,
+}
+
+/// The inbox line for `text` captured at `now`
+fn capture_line(text: &str, task: bool, now: NaiveDateTime) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if task {
+        format!("- [ ] {} ➕ {}", text, now.format("%Y-%m-%d"))
+    } else {
+        format!("- {} {}", now.format("%Y-%m-%d %H:%M"), text)
+    }
+}
+
+/// Capability for quick capture into the inbox note
+pub struct CaptureCapability {
+    base_path: PathBuf,
+    config: Arc<Config>,
+}
+
+impl CaptureCapability {
+    /// Create a new CaptureCapability
+    pub fn new(base_path: PathBuf, config: Arc<Config>) -> Self {
+        Self { base_path, config }
+    }
+
+    /// Append an entry to the inbox note
+    pub async fn capture(&self, request: CaptureRequest) -> CapabilityResult<CaptureResponse> {
+        if request.text.trim().is_empty() {
+            return Err(invalid_params("Text must not be empty"));
+        }
+
+        let inbox = from_slash(&self.config.inbox_note);
+        if inbox.extension().and_then(|ext| ext.to_str()) != Some("md")
+            || !inbox
+                .components()
+                .all(|c| matches!(c, Component::Normal(_)))
+        {
+            return Err(invalid_params(format!(
+                "inbox_note '{}' must be a .md path inside the vault",
+                self.config.inbox_note
+            )));
+        }
+        let path = self.base_path.join(&inbox);
+        let existing = if path.exists() {
+            // An existing inbox must not lead outside the vault through a symlink
+            let path = PathResolver::new(&self.base_path)?.resolve_note(&to_slash(&inbox))?;
+            stamp_before_write(&path, &self.config)?;
+            Some(
+                fs::read_to_string(&path)
+                    .map_err(|e| internal_error(format!("Failed to read inbox: {}", e)))?,
+            )
+        } else {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)
+                    .map_err(|e| internal_error(format!("Failed to create folder: {}", e)))?;
+            }
+            None
+        };
+
+        let line = capture_line(
+            &request.text,
+            request.task.unwrap_or(false),
+            Local::now().naive_local(),
+        );
+        let existing_text = existing.as_deref().unwrap_or_default();
+        let mut entry = String::new();
+        if !existing_text.is_empty() && !existing_text.ends_with('\n') {
+            entry.push('\n');
+        }
+        entry.push_str(&line);
+        entry.push('\n');
+
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| file.write_all(entry.as_bytes()))
+            .map_err(|e| internal_error(format!("Failed to write inbox: {}", e)))?;
+
+        Ok(CaptureResponse {
+            file_path: to_slash(&inbox),
+            line_number: existing_text.lines().count() + 1,
+            line,
+            created: existing.is_none(),
+        })
+    }
+}
+
+/// Operation struct for capture (HTTP, CLI, and MCP)
+pub struct CaptureOperation {
+    capability: Arc<CaptureCapability>,
+}
+
+impl CaptureOperation {
+    pub fn new(capability: Arc<CaptureCapability>) -> Self {
+        Self { capability }
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::operation::Operation for CaptureOperation {
+    fn name(&self) -> &'static str {
+        capture::CLI_NAME
+    }
+
+    fn path(&self) -> &'static str {
+        capture::HTTP_PATH
+    }
+
+    fn description(&self) -> &'static str {
+        capture::DESCRIPTION
+    }
+
+    fn get_command(&self) -> clap::Command {
+        // Get command from request struct's Parser derive
+        CaptureRequest::command()
+    }
+
+    async fn execute_json(&self, json: serde_json::Value) -> Result<serde_json::Value, Error> {
+        crate::http_router::execute_json_operation(json, |req| self.capability.capture(req)).await
+    }
+
+    async fn execute_from_args(
+        &self,
+        matches: &clap::ArgMatches,
+        _registry: &crate::capabilities::CapabilityRegistry,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        // Parse request from ArgMatches
+        let request = CaptureRequest::from_arg_matches(matches)?;
+
+        // Handle CLI-specific path if present
+        let response = if let Some(ref path) = request.path {
+            let config = Arc::new(Config::load_from_base_path(path.as_path()));
+            let capability = CaptureCapability::new(path.clone(), config);
+            let mut req_without_path = request;
+            req_without_path.path = None;
+            capability.capture(req_without_path).await?
+        } else {
+            self.capability.capture(request).await?
+        };
+
+        // Serialize to JSON
+        Ok(serde_json::to_string_pretty(&response)?)
+    }
+
+    fn input_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(CaptureRequest)).unwrap()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(CaptureResponse)).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_capture_line() {
+        let now = NaiveDate::from_ymd_opt(2025, 1, 20)
+            .unwrap()
+            .and_hms_opt(9, 5, 0)
+            .unwrap();
+        assert_eq!(
+            capture_line("Idea:\n  ship it", false, now),
+            "- 2025-01-20 09:05 Idea: ship it"
+        );
+        assert_eq!(
+            capture_line("Call Alice", true, now),
+            "- [ ] Call Alice ➕ 2025-01-20"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_capture() {
+        let vault = TempDir::new().unwrap();
+        let config = Config {
+            inbox_note: "GTD/Inbox.md".to_string(),
+            ..Config::default()
+        };
+        let capability = CaptureCapability::new(vault.path().to_path_buf(), Arc::new(config));
+        let capture = |text: &str, task| CaptureRequest {
+            text: text.to_string(),
+            task: Some(task),
+            ..Default::default()
+        };
+
+        let response = capability.capture(capture("First", false)).await.unwrap();
+        assert!(response.created);
+        assert_eq!(response.file_path, "GTD/Inbox.md");
+        assert_eq!(response.line_number, 1);
+
+        let inbox = vault.path().join("GTD/Inbox.md");
+        fs::write(&inbox, "# Inbox\n- first").unwrap();
+        let response = capability
+            .capture(capture("Call Alice", true))
+            .await
+            .unwrap();
+        assert!(!response.created);
+        assert_eq!(response.line_number, 3);
+        let content = fs::read_to_string(&inbox).unwrap();
+        assert_eq!(content, format!("# Inbox\n- first\n{}\n", response.line));
+        assert!(response.line.starts_with("- [ ] Call Alice ➕ "));
+
+        assert!(capability.capture(capture(" \n", false)).await.is_err());
+        let escaping = CaptureCapability::new(
+            vault.path().to_path_buf(),
+            Arc::new(Config {
+                inbox_note: "../Inbox.md".to_string(),
+                ..Config::default()
+            }),
+        );
+        let err = escaping.capture(capture("x", false)).await.unwrap_err();
+        assert_eq!(err.code(), "invalid_params");
+    }
+}
@@ -0,0 +1,950 @@
+//! Wikilink resolution, outgoing links, and link graph metrics
+//!
+//! Resolves `[[Note#Heading|alias]]` links against the vault the way
+//! Obsidian does, using note names, partial paths, and frontmatter aliases,
+//! lists the links leaving a note, and summarizes the resulting link graph.
+
+use crate::capabilities::{CapabilityResult, check_strict};
+use crate::config::Config;
+use crate::error::{Error, internal_error, invalid_params, scan_error};
+use crate::link_extractor::{
+    LinkExtractor, LinkIndex, ResolvedBy, WikiLink, find_anchor_line, is_attachment, parse_wikilink,
+};
+use crate::path_resolver::{PathResolver, to_slash};
+use crate::unicode::fold;
+use crate::warnings::{ExtractionWarning, Warnings};
+use clap::{CommandFactory, FromArgMatches, Parser};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Operation metadata for resolve_link
+pub mod resolve_link {
+    pub const DESCRIPTION: &str = "Resolve a wikilink such as [[Note#Heading|alias]] to the note it points to, using note names, partial paths, and frontmatter aliases like Obsidian. Returns the file path, the line of the linked heading or block, and whether the target exists.";
+    #[allow(dead_code)]
+    pub const CLI_NAME: &str = "resolve-link";
+    pub const HTTP_PATH: &str = "/api/links/resolve";
+}
+
+/// Operation metadata for outgoing_links
+pub mod outgoing_links {
+    pub const DESCRIPTION: &str = "List the wikilinks and Markdown links leaving a note, in order, with their line numbers. Each link is marked as resolved (with the note or attachment it points to) or unresolved, embeds (![[...]]) are flagged, and heading/block anchors are checked.";
+    #[allow(dead_code)]
+    pub const CLI_NAME: &str = "outgoing-links";
+    pub const HTTP_PATH: &str = "/api/links/outgoing";
+}
+
+/// Operation metadata for graph_metrics
+pub mod graph_metrics {
+    pub const DESCRIPTION: &str = "Analyze the vault's link graph: per-note in-degree and out-degree, PageRank centrality, and connected components. Returns the most central notes (hubs), clusters disconnected from the main graph, and orphan notes with no links.";
+    #[allow(dead_code)]
+    pub const CLI_NAME: &str = "graph-metrics";
+    pub const HTTP_PATH: &str = "/api/links/graph";
+}
+
+/// Default number of hubs and clusters returned by graph_metrics
+const DEFAULT_GRAPH_LIMIT: usize = 10;
+
+/// Parameters for the resolve_link operation
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Parser)]
+#[command(name = "resolve-link", about = "Resolve a wikilink to a note")]
+pub struct ResolveLinkRequest {
+    /// Path to vault (CLI only - not used in HTTP/MCP)
+    #[arg(index = 1, required = true, help = "Path to vault")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub path: Option<PathBuf>,
+
+    #[arg(
+        index = 2,
+        required = true,
+        help = "Wikilink to resolve, e.g. '[[Note#Heading]]'"
+    )]
+    #[schemars(
+        description = "Wikilink to resolve, with or without brackets (e.g. '[[Note#Heading|alias]]', 'folder/Note', '[[Note#^block-id]]')"
+    )]
+    pub link: String,
+
+    #[arg(long, help = "Vault-relative path of the note containing the link")]
+    #[schemars(
+        description = "Vault-relative path of the note containing the link (optional). Used to break ties between notes with the same name and to resolve same-note links like [[#Heading]]"
+    )]
+    pub source: Option<String>,
+
+    #[arg(long, help = "Fail if any file can't be read instead of skipping it")]
+    #[schemars(
+        description = "If true, fail when a file is skipped (unreadable, invalid frontmatter, larger than max_file_size, or cut off by a truncating scan limit) instead of reporting it in warnings. Default: false"
+    )]
+    pub strict: Option<bool>,
+}
+
+/// Response from the resolve_link operation
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ResolveLinkResponse {
+    /// The parsed link
+    pub link: WikiLink,
+    /// Whether the link points to an existing note
+    pub exists: bool,
+    /// Vault-relative path of the resolved note
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_path: Option<String>,
+    /// Whether the note was matched by name/path or by alias
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolved_by: Option<ResolvedBy>,
+    /// 1-based line of the linked heading or block
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line_number: Option<usize>,
+    /// Whether the heading or block was found (absent when the link has no anchor)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub anchor_found: Option<bool>,
+    /// Other notes the link could refer to
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub other_candidates: Vec<String>,
+    /// Files that were skipped (unreadable, invalid frontmatter, larger
+    /// than `max_file_size`, or cut off by a truncating scan limit)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ExtractionWarning>,
+}
+
+/// Parameters for the outgoing_links operation
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Parser)]
+#[command(name = "outgoing-links", about = "List the links leaving a note")]
+pub struct OutgoingLinksRequest {
+    /// Path to vault (CLI only - not used in HTTP/MCP)
+    #[arg(index = 1, required = true, help = "Path to vault")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub path: Option<PathBuf>,
+
+    #[arg(index = 2, required = true, help = "Note path relative to vault root")]
+    #[schemars(description = "Vault-relative path of the note whose links to list")]
+    pub file_path: String,
+
+    #[arg(long, help = "Fail if any file can't be read instead of skipping it")]
+    #[schemars(
+        description = "If true, fail when a file is skipped (unreadable, invalid frontmatter, larger than max_file_size, or cut off by a truncating scan limit) instead of reporting it in warnings. Default: false"
+    )]
+    pub strict: Option<bool>,
+}
+
+/// A link leaving a note
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct OutgoingLink {
+    /// 1-based line of the link
+    pub line_number: usize,
+    /// The parsed link; Markdown link paths are rewritten as vault-relative targets
+    pub link: WikiLink,
+    /// Whether the link is a Markdown link (`[text](note.md)`) rather than a wikilink
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub markdown: bool,
+    /// Whether the link points to an existing note or attachment
+    pub resolved: bool,
+    /// Vault-relative path of the note or attachment it points to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_path: Option<String>,
+    /// Whether the note was matched by name/path or by alias
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolved_by: Option<ResolvedBy>,
+    /// Whether the heading or block exists (absent when the link has no anchor
+    /// or doesn't resolve to a note)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub anchor_found: Option<bool>,
+}
+
+/// Response from the outgoing_links operation
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct OutgoingLinksResponse {
+    /// Vault-relative path of the note
+    pub file_path: String,
+    /// Links by line, wikilinks before Markdown links on the same line
+    pub links: Vec<OutgoingLink>,
+    pub total_count: usize,
+    pub resolved_count: usize,
+    pub unresolved_count: usize,
+    /// Links written as embeds (`![[...]]`), resolved or not
+    pub embed_count: usize,
+    /// Files that were skipped (unreadable, invalid frontmatter, larger
+    /// than `max_file_size`, or cut off by a truncating scan limit)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ExtractionWarning>,
+}
+
+/// Parameters for the graph_metrics operation
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Parser)]
+#[command(name = "graph-metrics", about = "Analyze the vault's link graph")]
+pub struct GraphMetricsRequest {
+    /// Path to vault (CLI only - not used in HTTP/MCP)
+    #[arg(index = 1, required = true, help = "Path to vault")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub path: Option<PathBuf>,
+
+    #[arg(long, help = "Number of hubs and clusters to return")]
+    #[schemars(description = "Number of hubs and clusters to return (defaults to 10)")]
+    pub limit: Option<usize>,
+
+    #[arg(long, help = "Fail if any file can't be read instead of skipping it")]
+    #[schemars(
+        description = "If true, fail when a file is skipped (unreadable, invalid frontmatter, larger than max_file_size, or cut off by a truncating scan limit) instead of reporting it in warnings. Default: false"
+    )]
+    pub strict: Option<bool>,
+}
+
+/// Link metrics for a single note
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct NoteMetrics {
+    /// Vault-relative path
+    pub file_path: String,
+    /// Number of distinct notes linking here
+    pub in_degree: usize,
+    /// Number of distinct notes linked from here
+    pub out_degree: usize,
+    /// PageRank centrality (scores over the whole vault sum to 1)
+    pub pagerank: f64,
+}
+
+/// A group of notes linked to each other but not to the rest of the vault
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct NoteCluster {
+    pub size: usize,
+    /// Vault-relative paths, sorted
+    pub notes: Vec<String>,
+}
+
+/// Response from the graph_metrics operation
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GraphMetricsResponse {
+    pub note_count: usize,
+    /// Distinct note-to-note links
+    pub link_count: usize,
+    /// Links to notes that don't exist
+    pub unresolved_link_count: usize,
+    /// Number of connected components, orphans included
+    pub component_count: usize,
+    /// Size of the largest connected component
+    pub largest_component_size: usize,
+    /// Most central notes, by PageRank
+    pub hubs: Vec<NoteMetrics>,
+    /// Components other than the largest, biggest first (orphans excluded)
+    pub clusters: Vec<NoteCluster>,
+    /// Notes with no links in or out, sorted
+    pub orphans: Vec<String>,
+    /// Files that were skipped (unreadable, invalid frontmatter, larger
+    /// than `max_file_size`, or cut off by a truncating scan limit)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ExtractionWarning>,
+}
+
+/// Capability for link operations
+pub struct LinkCapability {
+    base_path: PathBuf,
+    config: Arc<Config>,
+    link_extractor: LinkExtractor,
+}
+
+impl LinkCapability {
+    /// Create a new LinkCapability
+    pub fn new(base_path: PathBuf, config: Arc<Config>) -> Self {
+        Self {
+            base_path,
+            config,
+            link_extractor: LinkExtractor::new(),
+        }
+    }
+
+    /// Resolve a wikilink to a note and anchor line
+    pub async fn resolve_link(
+        &self,
+        request: ResolveLinkRequest,
+    ) -> CapabilityResult<ResolveLinkResponse> {
+        let link = parse_wikilink(&request.link)
+            .ok_or_else(|| invalid_params(format!("Invalid wikilink: {}", request.link)))?;
+        let source = request.source.as_deref().map(Path::new);
+
+        // Same-note links ([[#Heading]]) point into the source note
+        let warnings = Warnings::default();
+        let (file_path, resolved_by, other_candidates) = if link.target.is_empty() {
+            let source = source.ok_or_else(|| {
+                invalid_params("Links without a note name need a source note to resolve against")
+            })?;
+            let exists = self.base_path.join(source).is_file();
+            (
+                exists.then(|| source.to_path_buf()),
+                exists.then_some(ResolvedBy::Path),
+                Vec::new(),
+            )
+        } else {
+            let index = LinkIndex::build(&self.base_path, &self.config, &warnings)
+                .map_err(|e| scan_error("Failed to index notes", e))?;
+            match index.resolve(&link.target, source) {
+                Some(resolution) => (
+                    Some(resolution.path.to_path_buf()),
+                    Some(resolution.resolved_by),
+                    resolution
+                        .other_candidates
+                        .iter()
+                        .map(|path| to_slash(path))
+                        .collect(),
+                ),
+                None => (None, None, Vec::new()),
+            }
+        };
+        let warnings = warnings.into_vec();
+        check_strict(&warnings, request.strict)?;
+
+        let has_anchor = link.heading.is_some() || link.block.is_some();
+        let line_number = match file_path {
+            Some(ref path) if has_anchor => {
+                let content = fs::read_to_string(self.base_path.join(path))
+                    .map_err(|e| internal_error(format!("Failed to read note: {}", e)))?;
+                find_anchor_line(&content, &link)
+            }
+            _ => None,
+        };
+
+        Ok(ResolveLinkResponse {
+            exists: file_path.is_some(),
+            anchor_found: (has_anchor && file_path.is_some()).then_some(line_number.is_some()),
+            file_path: file_path.map(|path| to_slash(&path)),
+            resolved_by,
+            line_number,
+            other_candidates,
+            link,
+            warnings,
+        })
+    }
+
+    /// List the links leaving a note and what each resolves to
+    pub async fn outgoing_links(
+        &self,
+        request: OutgoingLinksRequest,
+    ) -> CapabilityResult<OutgoingLinksResponse> {
+        let resolver = PathResolver::new(&self.base_path)?;
+        let path = resolver.resolve_note(&request.file_path)?;
+        let source = resolver.relative(&path).unwrap_or(&path).to_path_buf();
+        let content = fs::read_to_string(&path)
+            .map_err(|e| internal_error(format!("Failed to read note: {}", e)))?;
+        let occurrences = self
+            .link_extractor
+            .extract_link_occurrences(&content, &source);
+
+        let warnings = Warnings::default();
+        let index = LinkIndex::build(&self.base_path, &self.config, &warnings)
+            .map_err(|e| scan_error("Failed to index notes", e))?;
+        let warnings = warnings.into_vec();
+        check_strict(&warnings, request.strict)?;
+        let attachments = if occurrences.iter().any(|o| is_attachment(&o.link.target)) {
+            attachments_by_name(&self.base_path, &self.config)
+        } else {
+            HashMap::new()
+        };
+
+        // Notes read to check anchors, starting with this one
+        let mut contents: HashMap<PathBuf, Option<String>> = HashMap::new();
+        contents.insert(source.clone(), Some(content));
+
+        let links: Vec<OutgoingLink> = occurrences
+            .into_iter()
+            .map(|occurrence| {
+                let link = occurrence.link;
+                let attachment = is_attachment(&link.target);
+                let (target, resolved_by) = if link.target.is_empty() {
+                    (Some(source.clone()), Some(ResolvedBy::Path))
+                } else if attachment {
+                    let found = resolve_attachment(&attachments, &link.target, &source).cloned();
+                    let resolved_by = found.as_ref().map(|_| ResolvedBy::Path);
+                    (found, resolved_by)
+                } else {
+                    match index.resolve(&link.target, Some(&source)) {
+                        Some(resolution) => (
+                            Some(resolution.path.to_path_buf()),
+                            Some(resolution.resolved_by),
+                        ),
+                        None => (None, None),
+                    }
+                };
+
+                let has_anchor = link.heading.is_some() || link.block.is_some();
+                let anchor_found = match target {
+                    Some(ref target) if has_anchor && !attachment => {
+                        let target_content = contents.entry(target.clone()).or_insert_with(|| {
+                            fs::read_to_string(self.base_path.join(target)).ok()
+                        });
+                        Some(
+                            target_content
+                                .as_deref()
+                                .is_some_and(|c| find_anchor_line(c, &link).is_some()),
+                        )
+                    }
+                    _ => None,
+                };
+
+                OutgoingLink {
+                    line_number: occurrence.line_number,
+                    markdown: occurrence.markdown,
+                    resolved: target.is_some(),
+                    file_path: target.map(|path| to_slash(&path)),
+                    resolved_by,
+                    anchor_found,
+                    link,
+                }
+            })
+            .collect();
+
+        let resolved_count = links.iter().filter(|link| link.resolved).count();
+        Ok(OutgoingLinksResponse {
+            file_path: to_slash(&source),
+            total_count: links.len(),
+            resolved_count,
+            unresolved_count: links.len() - resolved_count,
+            embed_count: links.iter().filter(|link| link.link.embed).count(),
+            links,
+            warnings,
+        })
+    }
+
+    /// Compute degree, PageRank, and component metrics over the link graph
+    pub async fn graph_metrics(
+        &self,
+        request: GraphMetricsRequest,
+    ) -> CapabilityResult<GraphMetricsResponse> {
+        let warnings = Warnings::default();
+        let graph = self
+            .link_extractor
+            .build_graph(&self.base_path, &self.config, &warnings)
+            .map_err(|e| scan_error("Failed to build link graph", e))?;
+        let warnings = warnings.into_vec();
+        check_strict(&warnings, request.strict)?;
+        let limit = request.limit.unwrap_or(DEFAULT_GRAPH_LIMIT);
+        let path_of = |id: usize| to_slash(&graph.notes[id]);
+
+        let in_degrees = graph.in_degrees();
+        let pagerank = graph.pagerank();
+        let mut ranked: Vec<usize> = (0..graph.notes.len()).collect();
+        ranked.sort_by(|&a, &b| pagerank[b].total_cmp(&pagerank[a]).then(a.cmp(&b)));
+        let hubs = ranked
+            .into_iter()
+            .take(limit)
+            .map(|id| NoteMetrics {
+                file_path: path_of(id),
+                in_degree: in_degrees[id],
+                out_degree: graph.outgoing[id].len(),
+                pagerank: (pagerank[id] * 1_000_000.0).round() / 1_000_000.0,
+            })
+            .collect();
+
+        let components = graph.components();
+        let (orphans, clusters): (Vec<_>, Vec<_>) = components
+            .iter()
+            .skip(1)
+            .partition(|component| component.len() == 1);
+        let mut orphans: Vec<String> = orphans.into_iter().map(|c| path_of(c[0])).collect();
+        // A vault without any links is all orphans
+        if components.first().is_some_and(|c| c.len() == 1) {
+            orphans.insert(0, path_of(components[0][0]));
+        }
+        orphans.sort();
+
+        Ok(GraphMetricsResponse {
+            note_count: graph.notes.len(),
+            link_count: graph.outgoing.iter().map(Vec::len).sum(),
+            unresolved_link_count: graph.unresolved.iter().sum(),
+            component_count: components.len(),
+            largest_component_size: components.first().map_or(0, Vec::len),
+            hubs,
+            clusters: clusters
+                .into_iter()
+                .take(limit)
+                .map(|component| NoteCluster {
+                    size: component.len(),
+                    notes: component.iter().map(|&id| path_of(id)).collect(),
+                })
+                .collect(),
+            orphans,
+            warnings,
+        })
+    }
+}
+
+/// Non-note files in the vault by case-folded file name, as vault-relative
+/// paths; hidden and excluded paths are skipped
+fn attachments_by_name(base_path: &Path, config: &Config) -> HashMap<String, Vec<PathBuf>> {
+    let mut by_name: HashMap<String, Vec<PathBuf>> = HashMap::new();
+    let mut dirs = vec![base_path.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.filter_map(Result::ok) {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.starts_with('.') || config.should_exclude(&path) {
+                continue;
+            }
+            if path.is_dir() {
+                dirs.push(path);
+            } else if let Ok(relative) = path.strip_prefix(base_path) {
+                by_name
+                    .entry(fold(&name))
+                    .or_default()
+                    .push(relative.to_path_buf());
+            }
+        }
+    }
+    by_name
+}
+
+/// Resolve an attachment link like Obsidian: by file name, or by the end of
+/// the path when the target has a `/`, preferring the linking note's folder
+/// and then the shortest path
+fn resolve_attachment<'a>(
+    by_name: &'a HashMap<String, Vec<PathBuf>>,
+    target: &str,
+    source: &Path,
+) -> Option<&'a PathBuf> {
+    let wanted = fold(target.trim_start_matches('/'));
+    let name = wanted.rsplit('/').next().unwrap_or(&wanted);
+    by_name
+        .get(name)?
+        .iter()
+        .filter(|path| {
+            let key = fold(&to_slash(path));
+            key == wanted || key.ends_with(&format!("/{}", wanted))
+        })
+        .min_by_key(|path| {
+            (
+                path.parent() != source.parent(),
+                path.components().count(),
+                (*path).clone(),
+            )
+        })
+}
+
+/// Operation struct for resolve_link (HTTP, CLI, and MCP)
+pub struct ResolveLinkOperation {
+    capability: Arc<LinkCapability>,
+}
+
+impl ResolveLinkOperation {
+    pub fn new(capability: Arc<LinkCapability>) -> Self {
+        Self { capability }
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::operation::Operation for ResolveLinkOperation {
+    fn name(&self) -> &'static str {
+        resolve_link::CLI_NAME
+    }
+
+    fn path(&self) -> &'static str {
+        resolve_link::HTTP_PATH
+    }
+
+    fn description(&self) -> &'static str {
+        resolve_link::DESCRIPTION
+    }
+
+    fn read_only(&self) -> bool {
+        true
+    }
+
+    fn get_command(&self) -> clap::Command {
+        // Get command from request struct's Parser derive
+        ResolveLinkRequest::command()
+    }
+
+    async fn execute_json(&self, json: serde_json::Value) -> Result<serde_json::Value, Error> {
+        crate::http_router::execute_json_operation(json, |req| self.capability.resolve_link(req))
+            .await
+    }
+
+    async fn execute_from_args(
+        &self,
+        matches: &clap::ArgMatches,
+        _registry: &crate::capabilities::CapabilityRegistry,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        // Parse request from ArgMatches
+        let request = ResolveLinkRequest::from_arg_matches(matches)?;
+
+        // Handle CLI-specific path if present
+        let response = if let Some(ref path) = request.path {
+            let config = Arc::new(Config::load_from_base_path(path.as_path()));
+            let capability = LinkCapability::new(path.clone(), config);
+            let mut req_without_path = request;
+            req_without_path.path = None;
+            capability.resolve_link(req_without_path).await?
+        } else {
+            self.capability.resolve_link(request).await?
+        };
+
+        // Serialize to JSON
+        Ok(serde_json::to_string_pretty(&response)?)
+    }
+
+    fn input_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(ResolveLinkRequest)).unwrap()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(ResolveLinkResponse)).unwrap()
+    }
+}
+
+/// Operation struct for outgoing_links (HTTP, CLI, and MCP)
+pub struct OutgoingLinksOperation {
+    capability: Arc<LinkCapability>,
+}
+
+impl OutgoingLinksOperation {
+    pub fn new(capability: Arc<LinkCapability>) -> Self {
+        Self { capability }
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::operation::Operation for OutgoingLinksOperation {
+    fn name(&self) -> &'static str {
+        outgoing_links::CLI_NAME
+    }
+
+    fn path(&self) -> &'static str {
+        outgoing_links::HTTP_PATH
+    }
+
+    fn description(&self) -> &'static str {
+        outgoing_links::DESCRIPTION
+    }
+
+    fn read_only(&self) -> bool {
+        true
+    }
+
+    fn get_command(&self) -> clap::Command {
+        // Get command from request struct's Parser derive
+        OutgoingLinksRequest::command()
+    }
+
+    async fn execute_json(&self, json: serde_json::Value) -> Result<serde_json::Value, Error> {
+        crate::http_router::execute_json_operation(json, |req| self.capability.outgoing_links(req))
+            .await
+    }
+
+    async fn execute_from_args(
+        &self,
+        matches: &clap::ArgMatches,
+        _registry: &crate::capabilities::CapabilityRegistry,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        // Parse request from ArgMatches
+        let request = OutgoingLinksRequest::from_arg_matches(matches)?;
+
+        // Handle CLI-specific path if present
+        let response = if let Some(ref path) = request.path {
+            let config = Arc::new(Config::load_from_base_path(path.as_path()));
+            let capability = LinkCapability::new(path.clone(), config);
+            let mut req_without_path = request;
+            req_without_path.path = None;
+            capability.outgoing_links(req_without_path).await?
+        } else {
+            self.capability.outgoing_links(request).await?
+        };
+
+        // Serialize to JSON
+        Ok(serde_json::to_string_pretty(&response)?)
+    }
+
+    fn input_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(OutgoingLinksRequest)).unwrap()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(OutgoingLinksResponse)).unwrap()
+    }
+}
+
+/// Operation struct for graph_metrics (HTTP, CLI, and MCP)
+pub struct GraphMetricsOperation {
+    capability: Arc<LinkCapability>,
+}
+
+impl GraphMetricsOperation {
+    pub fn new(capability: Arc<LinkCapability>) -> Self {
+        Self { capability }
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::operation::Operation for GraphMetricsOperation {
+    fn name(&self) -> &'static str {
+        graph_metrics::CLI_NAME
+    }
+
+    fn path(&self) -> &'static str {
+        graph_metrics::HTTP_PATH
+    }
+
+    fn description(&self) -> &'static str {
+        graph_metrics::DESCRIPTION
+    }
+
+    fn read_only(&self) -> bool {
+        true
+    }
+
+    fn get_command(&self) -> clap::Command {
+        // Get command from request struct's Parser derive
+        GraphMetricsRequest::command()
+    }
+
+    async fn execute_json(&self, json: serde_json::Value) -> Result<serde_json::Value, Error> {
+        crate::http_router::execute_json_operation(json, |req| self.capability.graph_metrics(req))
+            .await
+    }
+
+    async fn execute_from_args(
+        &self,
+        matches: &clap::ArgMatches,
+        _registry: &crate::capabilities::CapabilityRegistry,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        // Parse request from ArgMatches
+        let request = GraphMetricsRequest::from_arg_matches(matches)?;
+
+        // Handle CLI-specific path if present
+        let response = if let Some(ref path) = request.path {
+            let config = Arc::new(Config::load_from_base_path(path.as_path()));
+            let capability = LinkCapability::new(path.clone(), config);
+            let mut req_without_path = request;
+            req_without_path.path = None;
+            capability.graph_metrics(req_without_path).await?
+        } else {
+            self.capability.graph_metrics(request).await?
+        };
+
+        // Serialize to JSON
+        Ok(serde_json::to_string_pretty(&response)?)
+    }
+
+    fn input_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(GraphMetricsRequest)).unwrap()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(GraphMetricsResponse)).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_vault() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("projects")).unwrap();
+        fs::write(
+            temp_dir.path().join("projects/Alpha.md"),
+            "---\naliases: [Project A]\n---\n# Alpha\n\n## Goals\n\nShip it ^ship\n",
+        )
+        .unwrap();
+        fs::write(temp_dir.path().join("Inbox.md"), "# Inbox\n\n## Today\n").unwrap();
+        temp_dir
+    }
+
+    fn request(link: &str, source: Option<&str>) -> ResolveLinkRequest {
+        ResolveLinkRequest {
+            path: None,
+            link: link.to_string(),
+            source: source.map(String::from),
+            strict: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resolve_link_with_heading_and_alias() {
+        let temp_dir = create_vault();
+        let capability =
+            LinkCapability::new(temp_dir.path().to_path_buf(), Arc::new(Config::default()));
+
+        let response = capability
+            .resolve_link(request("[[Project A#goals|plan]]", None))
+            .await
+            .unwrap();
+        assert!(response.exists);
+        assert_eq!(response.file_path.as_deref(), Some("projects/Alpha.md"));
+        assert_eq!(response.resolved_by, Some(ResolvedBy::Alias));
+        assert_eq!(response.line_number, Some(6));
+        assert_eq!(response.anchor_found, Some(true));
+        assert_eq!(response.link.alias.as_deref(), Some("plan"));
+
+        let response = capability
+            .resolve_link(request("alpha#^ship", None))
+            .await
+            .unwrap();
+        assert_eq!(response.resolved_by, Some(ResolvedBy::Path));
+        assert_eq!(response.line_number, Some(8));
+
+        let response = capability
+            .resolve_link(request("[[Alpha#Missing]]", None))
+            .await
+            .unwrap();
+        assert!(response.exists);
+        assert_eq!(response.anchor_found, Some(false));
+        assert_eq!(response.line_number, None);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_link_missing_and_same_note() {
+        let temp_dir = create_vault();
+        let capability =
+            LinkCapability::new(temp_dir.path().to_path_buf(), Arc::new(Config::default()));
+
+        let response = capability
+            .resolve_link(request("[[Nowhere]]", None))
+            .await
+            .unwrap();
+        assert!(!response.exists);
+        assert_eq!(response.file_path, None);
+        assert_eq!(response.anchor_found, None);
+
+        let response = capability
+            .resolve_link(request("[[#Today]]", Some("Inbox.md")))
+            .await
+            .unwrap();
+        assert_eq!(response.file_path.as_deref(), Some("Inbox.md"));
+        assert_eq!(response.line_number, Some(3));
+
+        assert!(
+            capability
+                .resolve_link(request("[[#Today]]", None))
+                .await
+                .is_err()
+        );
+        assert!(
+            capability
+                .resolve_link(request("[[]]", None))
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_outgoing_links() {
+        let temp_dir = create_vault();
+        fs::create_dir_all(temp_dir.path().join("assets")).unwrap();
+        fs::write(temp_dir.path().join("assets/Diagram.png"), "").unwrap();
+        fs::write(
+            temp_dir.path().join("projects/Plan.md"),
+            "# Plan\n\nSee [[Project A#Goals]] and [[Nowhere]].\n![[diagram.png]] ![[missing.png]]\n\
+             [notes](Alpha.md#^ship) [[#Plan]] [[Inbox#Missing]]\n`[[In code]]`\n",
+        )
+        .unwrap();
+        let capability =
+            LinkCapability::new(temp_dir.path().to_path_buf(), Arc::new(Config::default()));
+
+        let response = capability
+            .outgoing_links(OutgoingLinksRequest {
+                path: None,
+                file_path: "projects/Plan.md".to_string(),
+                strict: None,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(response.file_path, "projects/Plan.md");
+        assert_eq!(response.total_count, 7);
+        assert_eq!(response.resolved_count, 5);
+        assert_eq!(response.unresolved_count, 2);
+        assert_eq!(response.embed_count, 2);
+
+        let summary: Vec<(usize, &str, Option<&str>, Option<bool>)> = response
+            .links
+            .iter()
+            .map(|link| {
+                (
+                    link.line_number,
+                    link.link.target.as_str(),
+                    link.file_path.as_deref(),
+                    link.anchor_found,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (3, "Project A", Some("projects/Alpha.md"), Some(true)),
+                (3, "Nowhere", None, None),
+                (4, "diagram.png", Some("assets/Diagram.png"), None),
+                (4, "missing.png", None, None),
+                (5, "", Some("projects/Plan.md"), Some(true)),
+                (5, "Inbox", Some("Inbox.md"), Some(false)),
+                (5, "projects/Alpha", Some("projects/Alpha.md"), Some(true)),
+            ]
+        );
+        assert_eq!(response.links[0].resolved_by, Some(ResolvedBy::Alias));
+        assert!(response.links[2].link.embed);
+        assert!(response.links[6].markdown);
+
+        let missing = capability
+            .outgoing_links(OutgoingLinksRequest {
+                path: None,
+                file_path: "Nope.md".to_string(),
+                strict: None,
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(missing.code(), "not_found");
+    }
+
+    #[tokio::test]
+    async fn test_graph_metrics() {
+        let temp_dir = create_vault();
+        fs::write(
+            temp_dir.path().join("Index.md"),
+            "- [[Alpha]]\n- [[Inbox]]\n- [[Nowhere]]\n",
+        )
+        .unwrap();
+        fs::write(temp_dir.path().join("Island A.md"), "[[Island B]]\n").unwrap();
+        fs::write(temp_dir.path().join("Island B.md"), "").unwrap();
+        fs::write(temp_dir.path().join("Lonely.md"), "No links\n").unwrap();
+        let capability =
+            LinkCapability::new(temp_dir.path().to_path_buf(), Arc::new(Config::default()));
+
+        let response = capability
+            .graph_metrics(GraphMetricsRequest {
+                path: None,
+                limit: None,
+                strict: None,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(response.note_count, 6);
+        assert_eq!(response.link_count, 3);
+        assert_eq!(response.unresolved_link_count, 1);
+        assert_eq!(response.component_count, 3);
+        assert_eq!(response.largest_component_size, 3);
+        assert_eq!(response.clusters.len(), 1);
+        assert_eq!(
+            response.clusters[0].notes,
+            vec!["Island A.md", "Island B.md"]
+        );
+        assert_eq!(response.orphans, vec!["Lonely.md"]);
+
+        let index = response
+            .hubs
+            .iter()
+            .find(|hub| hub.file_path == "Index.md")
+            .unwrap();
+        assert_eq!((index.in_degree, index.out_degree), (0, 2));
+        assert!(response.hubs[0].in_degree > 0);
+    }
+}
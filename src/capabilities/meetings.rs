@@ -0,0 +1,1073 @@
+//! Meetings capability
+//!
+//! Collects action items from meeting notes (notes carrying the configured
+//! meeting tag) and groups them by the person they are assigned to, and
+//! gathers everything about one person for a 1:1.
+
+use crate::capabilities::{CapabilityResult, check_strict};
+use crate::config::Config;
+use crate::error::{Error, internal_error, invalid_params, scan_error};
+use crate::extractor::{Task, TaskExtractor, TaskStatus};
+use crate::frontmatter::body_after_frontmatter;
+use crate::link_extractor::{LinkExtractor, LinkIndex, note_aliases, parse_wikilink};
+use crate::outline_extractor::OutlineExtractor;
+use crate::path_resolver::to_slash;
+use crate::tag_extractor::{TagExtractor, collect_markdown_files, note_title};
+use crate::warnings::{ExtractionWarning, Warnings};
+use chrono::NaiveDate;
+use clap::{CommandFactory, FromArgMatches, Parser};
+use regex::Regex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Operation metadata for extract_action_items
+pub mod extract_action_items {
+    pub const DESCRIPTION: &str = "Extract action items from meeting notes (tagged #meeting by default). Collects items under the \"Action Items\" heading and tasks with @mentions, and returns them grouped by assigned person along with each meeting's frontmatter attendees.";
+    #[allow(dead_code)]
+    pub const CLI_NAME: &str = "action-items";
+    pub const HTTP_PATH: &str = "/api/meetings/action-items";
+}
+
+/// Operation metadata for person_report
+pub mod person_report {
+    pub const DESCRIPTION: &str = "Prepare for a 1:1: given a name, @handle, or [[Person]] page, gather the tasks that mention the person (or live on their page), the meetings they attended (frontmatter attendees), and the notes that link to their page.";
+    #[allow(dead_code)]
+    pub const CLI_NAME: &str = "person-report";
+    pub const HTTP_PATH: &str = "/api/meetings/person";
+}
+
+/// Default heading whose list items are treated as action items
+const DEFAULT_SECTION: &str = "Action Items";
+
+/// Parameters for the extract_action_items operation
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Parser)]
+#[command(
+    name = "action-items",
+    about = "Extract action items from meeting notes, grouped by person"
+)]
+pub struct ExtractActionItemsRequest {
+    /// Path to vault (CLI only - not used in HTTP/MCP)
+    #[arg(index = 1, required = true, help = "Path to vault")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub path: Option<PathBuf>,
+
+    #[arg(long, help = "Tag identifying meeting notes (default: meeting)")]
+    #[schemars(
+        description = "Tag identifying meeting notes, without # (optional, defaults to the configured meeting_tag, \"meeting\")"
+    )]
+    pub tag: Option<String>,
+
+    #[arg(long, help = "Heading containing action items (default: Action Items)")]
+    #[schemars(
+        description = "Heading whose list items are action items, case-insensitive (optional, defaults to \"Action Items\")"
+    )]
+    pub section: Option<String>,
+
+    #[arg(long, help = "Only return items assigned to this person")]
+    #[schemars(description = "Only return items assigned to this person (case-insensitive)")]
+    pub person: Option<String>,
+
+    #[arg(long, help = "Include completed and cancelled tasks")]
+    #[schemars(
+        description = "If true, include completed and cancelled tasks. Default: false (open items only)"
+    )]
+    pub include_completed: Option<bool>,
+
+    #[arg(long, help = "Fail if any file can't be read instead of skipping it")]
+    #[schemars(
+        description = "If true, fail when a file is skipped (unreadable, invalid frontmatter, larger than max_file_size, or cut off by a truncating scan limit) instead of reporting it in warnings. Default: false"
+    )]
+    pub strict: Option<bool>,
+}
+
+/// An action item found in a meeting note
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct ActionItem {
+    /// Item text with task metadata removed
+    pub content: String,
+    /// Task status, if the item is a checkbox task
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<TaskStatus>,
+    /// Due date, if the item is a task with one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<NaiveDate>,
+    /// Meeting note path relative to vault root
+    pub file_path: String,
+    /// Line number of the item (1-based)
+    pub line_number: usize,
+    /// Title of the meeting note
+    pub meeting: String,
+}
+
+/// Action items assigned to one person
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PersonAssignments {
+    /// Person name as first written (without @)
+    pub person: String,
+    /// Items assigned to this person
+    pub items: Vec<ActionItem>,
+}
+
+/// Summary of a meeting note
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MeetingNote {
+    /// File path relative to vault root
+    pub file_path: String,
+    /// Note title
+    pub title: String,
+    /// Meeting date from frontmatter `date`, if present
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    /// Attendees from frontmatter `attendees`
+    pub attendees: Vec<String>,
+    /// Number of action items found in the note
+    pub item_count: usize,
+}
+
+/// Response from the extract_action_items operation
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ExtractActionItemsResponse {
+    /// Meeting notes that were scanned, sorted by path
+    pub meetings: Vec<MeetingNote>,
+    /// Items grouped by assigned person, sorted by name
+    pub assignments: Vec<PersonAssignments>,
+    /// Items without an @mention or `Name:` prefix matching an attendee
+    pub unassigned: Vec<ActionItem>,
+    /// Files that were skipped (unreadable, invalid frontmatter, larger
+    /// than `max_file_size`, or cut off by a truncating scan limit)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ExtractionWarning>,
+}
+
+/// Parameters for the person_report operation
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Parser)]
+#[command(
+    name = "person-report",
+    about = "Gather tasks, meetings, and backlinks for a person"
+)]
+pub struct PersonReportRequest {
+    /// Path to vault (CLI only - not used in HTTP/MCP)
+    #[arg(index = 1, required = true, help = "Path to vault")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub path: Option<PathBuf>,
+
+    #[arg(index = 2, required = true, help = "Name, @handle, or [[Person]] page")]
+    #[schemars(
+        description = "The person: a name (\"Alice\"), an @handle, or a link to their page (\"[[People/Alice]]\")"
+    )]
+    pub person: String,
+
+    #[arg(long, help = "Include completed and cancelled tasks")]
+    #[schemars(
+        description = "If true, include completed and cancelled tasks. Default: false (open tasks only)"
+    )]
+    pub include_completed: Option<bool>,
+
+    #[arg(long, help = "Include notes under archived paths")]
+    #[schemars(
+        description = "If true, include notes under the configured archived_paths. Default: false"
+    )]
+    pub include_archived: Option<bool>,
+
+    #[arg(long, help = "Fail if any file can't be read instead of skipping it")]
+    #[schemars(
+        description = "If true, fail when a file is skipped (unreadable, invalid frontmatter, larger than max_file_size, or cut off by a truncating scan limit) instead of reporting it in warnings. Default: false"
+    )]
+    pub strict: Option<bool>,
+}
+
+/// A meeting the person attended
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct AttendedMeeting {
+    /// Meeting note path relative to vault root
+    pub file_path: String,
+    /// Note title
+    pub title: String,
+    /// Meeting date from frontmatter `date`, if present
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+}
+
+/// A line linking to the person's page
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct Backlink {
+    /// Linking note path relative to vault root
+    pub file_path: String,
+    /// Line number of the link (1-based)
+    pub line_number: usize,
+    /// The line containing the link
+    pub line: String,
+}
+
+/// Response from the person_report operation
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct PersonReportResponse {
+    /// The person's name, without link brackets or @
+    pub person: String,
+    /// The person's page, if one exists (path relative to vault root)
+    pub page: Option<String>,
+    /// Names the person was matched by: the name, page name, and page aliases
+    pub names: Vec<String>,
+    /// Tasks mentioning the person, or on their page
+    pub tasks: Vec<Task>,
+    /// Meetings listing the person as an attendee, most recent first
+    pub meetings: Vec<AttendedMeeting>,
+    /// Links to the person's page from other notes
+    pub backlinks: Vec<Backlink>,
+    /// Files that were skipped (unreadable, invalid frontmatter, larger
+    /// than `max_file_size`, or cut off by a truncating scan limit)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ExtractionWarning>,
+}
+
+/// Capability for meeting note operations
+pub struct MeetingCapability {
+    base_path: PathBuf,
+    config: Arc<Config>,
+    tag_extractor: Arc<TagExtractor>,
+    task_extractor: Arc<TaskExtractor>,
+    outline_extractor: Arc<OutlineExtractor>,
+    link_extractor: Arc<LinkExtractor>,
+    mention_pattern: Regex,
+    bullet_pattern: Regex,
+}
+
+impl MeetingCapability {
+    /// Create a new MeetingCapability
+    pub fn new(base_path: PathBuf, config: Arc<Config>) -> Self {
+        Self {
+            base_path,
+            tag_extractor: Arc::new(TagExtractor::new(Arc::clone(&config))),
+            task_extractor: Arc::new(TaskExtractor::new(Arc::clone(&config))),
+            outline_extractor: Arc::new(OutlineExtractor::new()),
+            link_extractor: Arc::new(LinkExtractor::new()),
+            config,
+            // Preceded by start or whitespace so email addresses don't match
+            mention_pattern: Regex::new(r"(?:^|\s)@([\w][\w.-]*\w|\w)").unwrap(),
+            bullet_pattern: Regex::new(r"^(?:[-*+]|\d+[.)])\s+(.+)$").unwrap(),
+        }
+    }
+
+    /// Extract action items from meeting notes, grouped by person
+    pub async fn extract_action_items(
+        &self,
+        request: ExtractActionItemsRequest,
+    ) -> CapabilityResult<ExtractActionItemsResponse> {
+        let tag = request
+            .tag
+            .as_deref()
+            .unwrap_or(&self.config.meeting_tag)
+            .trim_start_matches('#')
+            .to_lowercase();
+        let section = request.section.as_deref().unwrap_or(DEFAULT_SECTION);
+        let include_completed = request.include_completed.unwrap_or(false);
+
+        let warnings = Warnings::default();
+        let mut files = collect_markdown_files(&self.base_path, &self.config, &warnings)
+            .map_err(|e| scan_error("Failed to list files", e))?;
+        let warnings = warnings.into_vec();
+        check_strict(&warnings, request.strict)?;
+        files.sort();
+
+        let mut meetings = Vec::new();
+        // Keyed by lowercased name so spellings group together
+        let mut assignments: BTreeMap<String, PersonAssignments> = BTreeMap::new();
+        let mut unassigned = Vec::new();
+
+        for file_path in files {
+            let Ok(content) = fs::read_to_string(&file_path) else {
+                continue;
+            };
+            let tags = self
+                .tag_extractor
+                .extract_tags_from_content(&content, true)
+                .unwrap_or_default();
+            let is_meeting = tags.iter().any(|t| {
+                let t = t.to_lowercase();
+                t == tag || t.strip_prefix(&tag).is_some_and(|r| r.starts_with('/'))
+            });
+            if !is_meeting {
+                continue;
+            }
+
+            let (meeting, items) = self.meeting_items(&file_path, &content, section);
+
+            let mut item_count = 0;
+            for item in items {
+                if !include_completed
+                    && matches!(
+                        item.status,
+                        Some(TaskStatus::Completed | TaskStatus::Cancelled)
+                    )
+                {
+                    continue;
+                }
+                item_count += 1;
+
+                let people = self.assignees(&item.content, &meeting.attendees);
+                if people.is_empty() {
+                    unassigned.push(item);
+                    continue;
+                }
+                for person in people {
+                    assignments
+                        .entry(person.to_lowercase())
+                        .or_insert_with(|| PersonAssignments {
+                            person,
+                            items: Vec::new(),
+                        })
+                        .items
+                        .push(item.clone());
+                }
+            }
+
+            meetings.push(MeetingNote {
+                item_count,
+                ..meeting
+            });
+        }
+
+        let mut assignments: Vec<PersonAssignments> = assignments.into_values().collect();
+        if let Some(person) = &request.person {
+            let person = person.trim_start_matches('@').to_lowercase();
+            assignments.retain(|a| a.person.to_lowercase() == person);
+            unassigned.clear();
+        }
+
+        Ok(ExtractActionItemsResponse {
+            meetings,
+            assignments,
+            unassigned,
+            warnings,
+        })
+    }
+
+    /// Gather tasks, meetings, and backlinks for one person
+    pub async fn person_report(
+        &self,
+        request: PersonReportRequest,
+    ) -> CapabilityResult<PersonReportResponse> {
+        let target = person_target(&request.person);
+        let person = target
+            .rsplit('/')
+            .next()
+            .unwrap_or(&target)
+            .trim()
+            .to_string();
+        if person.is_empty() {
+            return Err(invalid_params("person must not be empty"));
+        }
+        let include_completed = request.include_completed.unwrap_or(false);
+        let config = self
+            .config
+            .search_scope(request.include_archived.unwrap_or(false));
+
+        let warnings = Warnings::default();
+        let index = LinkIndex::build(&self.base_path, &config, &warnings)
+            .map_err(|e| scan_error("Failed to index notes", e))?;
+        let page = index
+            .resolve(&target, None)
+            .map(|resolution| resolution.path.to_path_buf());
+
+        // The person is known by their name, their page's name, and its aliases
+        let mut names = vec![person.clone()];
+        if let Some(page) = &page {
+            names.push(
+                page.file_stem()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .to_string(),
+            );
+            if let Ok(content) = fs::read_to_string(self.base_path.join(page)) {
+                names.extend(note_aliases(&content));
+            }
+        }
+        let mut seen = Vec::new();
+        names.retain(|name| {
+            let key = person_key(name);
+            !key.is_empty() && !seen.contains(&key) && {
+                seen.push(key);
+                true
+            }
+        });
+        let keys: Vec<String> = names.iter().map(|name| person_key(name)).collect();
+        let name_pattern = Regex::new(&format!(
+            r"(?i)\b(?:{})\b",
+            names
+                .iter()
+                .map(|name| regex::escape(name))
+                .collect::<Vec<_>>()
+                .join("|")
+        ))
+        .map_err(|e| internal_error(e.to_string()))?;
+
+        let mut files = collect_markdown_files(&self.base_path, &config, &warnings)
+            .map_err(|e| scan_error("Failed to list files", e))?;
+        let warnings = warnings.into_vec();
+        check_strict(&warnings, request.strict)?;
+        files.sort();
+
+        let mut tasks = Vec::new();
+        let mut meetings = Vec::new();
+        let mut backlinks = Vec::new();
+
+        for file_path in files {
+            let Ok(content) = fs::read_to_string(&file_path) else {
+                continue;
+            };
+            let relative = file_path
+                .strip_prefix(&self.base_path)
+                .unwrap_or(&file_path)
+                .to_path_buf();
+            let is_page = page.as_ref() == Some(&relative);
+            let links_to_page = |text: &str| {
+                page.as_ref().is_some_and(|page| {
+                    self.link_extractor
+                        .extract_links(text, &relative)
+                        .iter()
+                        .filter(|link| !link.target.is_empty())
+                        .any(|link| {
+                            index
+                                .resolve(&link.target, Some(&relative))
+                                .is_some_and(|r| r.path == page)
+                        })
+                })
+            };
+
+            // Tasks that mention the person, or any open item on their page
+            for task in self
+                .task_extractor
+                .extract_tasks_from_content(&content, &file_path)
+            {
+                if !include_completed
+                    && matches!(task.status, TaskStatus::Completed | TaskStatus::Cancelled)
+                {
+                    continue;
+                }
+                let mentioned = is_page
+                    || self
+                        .mention_pattern
+                        .captures_iter(&task.raw_line)
+                        .any(|caps| keys.contains(&person_key(&caps[1])))
+                    || name_pattern.is_match(&task.content)
+                    || links_to_page(&task.raw_line);
+                if mentioned {
+                    tasks.push(task);
+                }
+            }
+
+            // Meetings listing the person as an attendee
+            let frontmatter = self
+                .tag_extractor
+                .extract_frontmatter(&content)
+                .ok()
+                .flatten();
+            let yaml = frontmatter
+                .as_deref()
+                .and_then(|fm| serde_yaml::from_str::<serde_yaml::Value>(fm).ok());
+            let attended = yaml.as_ref().is_some_and(|yaml| {
+                parse_attendees(yaml).iter().any(|attendee| {
+                    let name = attendee.rsplit('/').next().unwrap_or(attendee);
+                    keys.contains(&person_key(name))
+                })
+            });
+            if attended {
+                meetings.push(AttendedMeeting {
+                    file_path: to_slash(&relative),
+                    title: note_title(
+                        frontmatter.as_deref(),
+                        body_after_frontmatter(&content),
+                        &file_path,
+                    ),
+                    date: yaml.as_ref().and_then(frontmatter_date),
+                });
+            }
+
+            // Lines linking to the person's page, outside code blocks
+            if page.is_some() && !is_page {
+                let mut in_code_block = false;
+                for (index, line) in content.lines().enumerate() {
+                    let trimmed = line.trim_start();
+                    if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                        in_code_block = !in_code_block;
+                        continue;
+                    }
+                    if !in_code_block && links_to_page(line) {
+                        backlinks.push(Backlink {
+                            file_path: to_slash(&relative),
+                            line_number: index + 1,
+                            line: line.trim().to_string(),
+                        });
+                    }
+                }
+            }
+        }
+
+        meetings.sort_by(|a, b| {
+            b.date
+                .cmp(&a.date)
+                .then_with(|| a.file_path.cmp(&b.file_path))
+        });
+
+        Ok(PersonReportResponse {
+            person,
+            page: page.map(|page| to_slash(&page)),
+            names,
+            tasks,
+            meetings,
+            backlinks,
+            warnings,
+        })
+    }
+
+    /// Summarize a meeting note and collect its candidate action items
+    ///
+    /// Items are every list item under `section` plus any task elsewhere in the
+    /// note that @mentions someone.
+    fn meeting_items(
+        &self,
+        file_path: &Path,
+        content: &str,
+        section: &str,
+    ) -> (MeetingNote, Vec<ActionItem>) {
+        let frontmatter = self
+            .tag_extractor
+            .extract_frontmatter(content)
+            .ok()
+            .flatten();
+        let yaml = frontmatter
+            .as_deref()
+            .and_then(|fm| serde_yaml::from_str::<serde_yaml::Value>(fm).ok());
+
+        let relative_path = to_slash(file_path.strip_prefix(&self.base_path).unwrap_or(file_path));
+        let meeting = MeetingNote {
+            file_path: relative_path.clone(),
+            title: note_title(
+                frontmatter.as_deref(),
+                body_after_frontmatter(content),
+                file_path,
+            ),
+            date: yaml.as_ref().and_then(frontmatter_date),
+            attendees: yaml.as_ref().map(parse_attendees).unwrap_or_default(),
+            item_count: 0,
+        };
+
+        // 1-based line ranges covered by the action item section(s)
+        let ranges: Vec<(usize, usize)> = self
+            .outline_extractor
+            .get_section_from_content(content, section, true)
+            .iter()
+            .map(|s| (s.start_line + 1, s.end_line))
+            .collect();
+        let in_section = |line: usize| ranges.iter().any(|(s, e)| (*s..=*e).contains(&line));
+
+        let tasks = self
+            .task_extractor
+            .extract_tasks_from_content(content, file_path);
+
+        let mut items: Vec<ActionItem> = tasks
+            .iter()
+            .filter(|t| in_section(t.line_number) || self.mention_pattern.is_match(&t.content))
+            .map(|t| ActionItem {
+                content: t.content.clone(),
+                status: Some(t.status),
+                due_date: t.due_date,
+                file_path: relative_path.clone(),
+                line_number: t.line_number,
+                meeting: meeting.title.clone(),
+            })
+            .collect();
+
+        // Plain (non-checkbox) top-level bullets in the section are items too
+        for (index, line) in content.lines().enumerate() {
+            let line_number = index + 1;
+            if !in_section(line_number) || tasks.iter().any(|t| t.line_number == line_number) {
+                continue;
+            }
+            if let Some(caps) = self.bullet_pattern.captures(line) {
+                items.push(ActionItem {
+                    content: caps[1].trim().to_string(),
+                    status: None,
+                    due_date: None,
+                    file_path: relative_path.clone(),
+                    line_number,
+                    meeting: meeting.title.clone(),
+                });
+            }
+        }
+        items.sort_by_key(|item| item.line_number);
+
+        (meeting, items)
+    }
+
+    /// People an item is assigned to: @mentions, or a leading `Name:` that
+    /// matches an attendee
+    fn assignees(&self, content: &str, attendees: &[String]) -> Vec<String> {
+        let mut people: Vec<String> = Vec::new();
+        for caps in self.mention_pattern.captures_iter(content) {
+            let person = caps[1].to_string();
+            if !people.iter().any(|p| p.eq_ignore_ascii_case(&person)) {
+                people.push(person);
+            }
+        }
+
+        if people.is_empty()
+            && let Some((prefix, _)) = content.split_once(':')
+            && let Some(attendee) = attendees
+                .iter()
+                .find(|a| a.eq_ignore_ascii_case(prefix.trim()))
+        {
+            people.push(attendee.clone());
+        }
+
+        people
+    }
+}
+
+/// Frontmatter `date` as written
+fn frontmatter_date(yaml: &serde_yaml::Value) -> Option<String> {
+    match yaml.get("date")? {
+        serde_yaml::Value::String(s) => Some(s.clone()),
+        other => serde_yaml::to_string(other)
+            .ok()
+            .map(|s| s.trim().to_string()),
+    }
+}
+
+/// The note a person refers to: `Alice`, `@alice`, or `[[People/Alice|Al]]`
+/// (`People/Alice`)
+fn person_target(text: &str) -> String {
+    let text = text.trim();
+    match parse_wikilink(text) {
+        Some(link) => link.target,
+        None => text.trim_start_matches('@').to_string(),
+    }
+}
+
+/// Comparison key for names: lowercase letters and digits only, so
+/// `@alice.smith` matches "Alice Smith"
+fn person_key(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Read attendee names from frontmatter `attendees` (list or comma-separated)
+///
+/// Wiki-link brackets, aliases, and leading `@` are stripped.
+fn parse_attendees(yaml: &serde_yaml::Value) -> Vec<String> {
+    let names: Vec<String> = match yaml.get("attendees") {
+        Some(serde_yaml::Value::Sequence(items)) => items
+            .iter()
+            .filter_map(|v| v.as_str().map(String::from))
+            .collect(),
+        Some(serde_yaml::Value::String(s)) => s.split(',').map(String::from).collect(),
+        _ => Vec::new(),
+    };
+
+    names
+        .iter()
+        .map(|name| {
+            let name = name.trim().trim_start_matches("[[").trim_end_matches("]]");
+            let name = name.split('|').next_back().unwrap_or(name);
+            name.trim().trim_start_matches('@').to_string()
+        })
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+/// Operation struct for extract_action_items (HTTP, CLI, and MCP)
+pub struct ExtractActionItemsOperation {
+    capability: Arc<MeetingCapability>,
+}
+
+impl ExtractActionItemsOperation {
+    pub fn new(capability: Arc<MeetingCapability>) -> Self {
+        Self { capability }
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::operation::Operation for ExtractActionItemsOperation {
+    fn name(&self) -> &'static str {
+        extract_action_items::CLI_NAME
+    }
+
+    fn path(&self) -> &'static str {
+        extract_action_items::HTTP_PATH
+    }
+
+    fn description(&self) -> &'static str {
+        extract_action_items::DESCRIPTION
+    }
+
+    fn read_only(&self) -> bool {
+        true
+    }
+
+    fn get_command(&self) -> clap::Command {
+        // Get command from request struct's Parser derive
+        ExtractActionItemsRequest::command()
+    }
+
+    async fn execute_json(&self, json: serde_json::Value) -> Result<serde_json::Value, Error> {
+        crate::http_router::execute_json_operation(json, |req| {
+            self.capability.extract_action_items(req)
+        })
+        .await
+    }
+
+    async fn execute_from_args(
+        &self,
+        matches: &clap::ArgMatches,
+        _registry: &crate::capabilities::CapabilityRegistry,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        // Parse request from ArgMatches
+        let request = ExtractActionItemsRequest::from_arg_matches(matches)?;
+
+        // Handle CLI-specific path if present
+        let response = if let Some(ref path) = request.path {
+            let config = Arc::new(Config::load_from_base_path(path.as_path()));
+            let capability = MeetingCapability::new(path.clone(), config);
+            let mut req_without_path = request;
+            req_without_path.path = None;
+            capability.extract_action_items(req_without_path).await?
+        } else {
+            self.capability.extract_action_items(request).await?
+        };
+
+        // Serialize to JSON
+        Ok(serde_json::to_string_pretty(&response)?)
+    }
+
+    fn input_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(ExtractActionItemsRequest)).unwrap()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(ExtractActionItemsResponse)).unwrap()
+    }
+}
+
+/// Operation struct for person_report (HTTP, CLI, and MCP)
+pub struct PersonReportOperation {
+    capability: Arc<MeetingCapability>,
+}
+
+impl PersonReportOperation {
+    pub fn new(capability: Arc<MeetingCapability>) -> Self {
+        Self { capability }
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::operation::Operation for PersonReportOperation {
+    fn name(&self) -> &'static str {
+        person_report::CLI_NAME
+    }
+
+    fn path(&self) -> &'static str {
+        person_report::HTTP_PATH
+    }
+
+    fn description(&self) -> &'static str {
+        person_report::DESCRIPTION
+    }
+
+    fn read_only(&self) -> bool {
+        true
+    }
+
+    fn get_command(&self) -> clap::Command {
+        // Get command from request struct's Parser derive
+        PersonReportRequest::command()
+    }
+
+    async fn execute_json(&self, json: serde_json::Value) -> Result<serde_json::Value, Error> {
+        crate::http_router::execute_json_operation(json, |req| self.capability.person_report(req))
+            .await
+    }
+
+    async fn execute_from_args(
+        &self,
+        matches: &clap::ArgMatches,
+        _registry: &crate::capabilities::CapabilityRegistry,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        // Parse request from ArgMatches
+        let request = PersonReportRequest::from_arg_matches(matches)?;
+
+        // Handle CLI-specific path if present
+        let response = if let Some(ref path) = request.path {
+            let config = Arc::new(Config::load_from_base_path(path.as_path()));
+            let capability = MeetingCapability::new(path.clone(), config);
+            let mut req_without_path = request;
+            req_without_path.path = None;
+            capability.person_report(req_without_path).await?
+        } else {
+            self.capability.person_report(request).await?
+        };
+
+        // Serialize to JSON
+        Ok(serde_json::to_string_pretty(&response)?)
+    }
+
+    fn input_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(PersonReportRequest)).unwrap()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(PersonReportResponse)).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn request() -> ExtractActionItemsRequest {
+        ExtractActionItemsRequest {
+            path: None,
+            tag: None,
+            section: None,
+            person: None,
+            include_completed: None,
+            strict: None,
+        }
+    }
+
+    fn create_vault() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("Meetings")).unwrap();
+        fs::write(
+            temp_dir.path().join("Meetings/standup.md"),
+            "---\n\
+             title: Weekly Sync\n\
+             date: 2025-01-06\n\
+             tags: [meeting]\n\
+             attendees: [\"[[Alice]]\", \"@bob\"]\n\
+             ---\n\
+             # Notes\n\
+             - [ ] @carol review the budget 📅 2025-01-10\n\
+             - [ ] Unrelated task\n\
+             \n\
+             ## Action Items\n\
+             - [ ] @alice send the deck\n\
+             - [x] @bob book a room\n\
+             - Bob: update the roadmap\n\
+             - Follow up with legal\n\
+             \n\
+             ## Next\n\
+             - Not an action item\n",
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("notes.md"),
+            "# Not a meeting\n\n## Action Items\n- [ ] @alice ignored\n",
+        )
+        .unwrap();
+        temp_dir
+    }
+
+    #[test]
+    fn test_parse_attendees() {
+        let yaml: serde_yaml::Value =
+            serde_yaml::from_str("attendees: \"[[People/Alice|Alice]], @bob, \"").unwrap();
+        assert_eq!(parse_attendees(&yaml), vec!["Alice", "bob"]);
+    }
+
+    #[test]
+    fn test_assignees() {
+        let capability = MeetingCapability::new(PathBuf::from("."), Arc::new(Config::default()));
+        let attendees = vec!["Bob".to_string()];
+
+        assert_eq!(
+            capability.assignees("@alice and @Bob. email a@b.com", &attendees),
+            vec!["alice", "Bob"]
+        );
+        assert_eq!(
+            capability.assignees("bob: ship it", &attendees),
+            vec!["Bob"]
+        );
+        assert!(
+            capability
+                .assignees("Carol: ship it", &attendees)
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_extract_action_items() {
+        let temp_dir = create_vault();
+        let capability =
+            MeetingCapability::new(temp_dir.path().to_path_buf(), Arc::new(Config::default()));
+
+        let response = capability.extract_action_items(request()).await.unwrap();
+
+        assert_eq!(response.meetings.len(), 1);
+        let meeting = &response.meetings[0];
+        assert_eq!(meeting.title, "Weekly Sync");
+        assert_eq!(meeting.date.as_deref(), Some("2025-01-06"));
+        assert_eq!(meeting.attendees, vec!["Alice", "bob"]);
+        assert_eq!(meeting.item_count, 4);
+
+        let people: Vec<_> = response
+            .assignments
+            .iter()
+            .map(|a| (a.person.as_str(), a.items.len()))
+            .collect();
+        assert_eq!(people, vec![("alice", 1), ("bob", 1), ("carol", 1)]);
+
+        let carol = &response.assignments[2].items[0];
+        assert_eq!(carol.due_date, NaiveDate::from_ymd_opt(2025, 1, 10));
+        assert_eq!(carol.file_path, "Meetings/standup.md");
+        assert_eq!(carol.line_number, 8);
+
+        let bob = &response.assignments[1].items[0];
+        assert_eq!(bob.content, "Bob: update the roadmap");
+        assert_eq!(bob.status, None);
+
+        assert_eq!(response.unassigned.len(), 1);
+        assert_eq!(response.unassigned[0].content, "Follow up with legal");
+    }
+
+    #[tokio::test]
+    async fn test_extract_action_items_filters() {
+        let temp_dir = create_vault();
+        let capability =
+            MeetingCapability::new(temp_dir.path().to_path_buf(), Arc::new(Config::default()));
+
+        let response = capability
+            .extract_action_items(ExtractActionItemsRequest {
+                person: Some("@Bob".to_string()),
+                include_completed: Some(true),
+                ..request()
+            })
+            .await
+            .unwrap();
+        assert_eq!(response.assignments.len(), 1);
+        assert_eq!(response.assignments[0].items.len(), 2);
+        assert!(response.unassigned.is_empty());
+
+        let response = capability
+            .extract_action_items(ExtractActionItemsRequest {
+                tag: Some("standup".to_string()),
+                ..request()
+            })
+            .await
+            .unwrap();
+        assert!(response.meetings.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_person_report() {
+        let temp_dir = create_vault();
+        fs::create_dir_all(temp_dir.path().join("People")).unwrap();
+        fs::write(
+            temp_dir.path().join("People/Alice Smith.md"),
+            "---\naliases: [Ali]\n---\n# Alice Smith\n- [ ] Ask about promotion\n",
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("project.md"),
+            "Owner: [[Alice Smith]]\n\
+             ```\n[[Alice Smith]] in code\n```\n\
+             - [ ] Send Ali the draft\n\
+             - [ ] Review with [[People/Alice Smith|her]]\n\
+             - [ ] Talk to Bob\n\
+             - [x] Thank Alice Smith\n",
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("Meetings/1on1.md"),
+            "---\ndate: 2025-02-01\nattendees: [\"[[Alice Smith]]\"]\n---\n# 1:1\n",
+        )
+        .unwrap();
+
+        let capability =
+            MeetingCapability::new(temp_dir.path().to_path_buf(), Arc::new(Config::default()));
+        let response = capability
+            .person_report(PersonReportRequest {
+                path: None,
+                person: "[[Alice Smith]]".to_string(),
+                include_completed: None,
+                include_archived: None,
+                strict: None,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(response.person, "Alice Smith");
+        assert_eq!(response.page.as_deref(), Some("People/Alice Smith.md"));
+        assert_eq!(response.names, vec!["Alice Smith", "Ali"]);
+
+        let tasks: Vec<&str> = response.tasks.iter().map(|t| t.content.as_str()).collect();
+        assert_eq!(
+            tasks,
+            vec![
+                "Ask about promotion",
+                "Send Ali the draft",
+                "Review with [[People/Alice Smith|her]]"
+            ]
+        );
+
+        let meetings: Vec<&str> = response
+            .meetings
+            .iter()
+            .map(|m| m.file_path.as_str())
+            .collect();
+        assert_eq!(meetings, vec!["Meetings/1on1.md"]);
+
+        let backlinks: Vec<(&str, usize)> = response
+            .backlinks
+            .iter()
+            .map(|b| (b.file_path.as_str(), b.line_number))
+            .collect();
+        // Frontmatter links count, as in Obsidian; code blocks don't
+        assert_eq!(
+            backlinks,
+            vec![
+                ("Meetings/1on1.md", 3),
+                ("project.md", 1),
+                ("project.md", 6)
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_person_report_by_handle() {
+        let temp_dir = create_vault();
+        let capability =
+            MeetingCapability::new(temp_dir.path().to_path_buf(), Arc::new(Config::default()));
+        let response = capability
+            .person_report(PersonReportRequest {
+                path: None,
+                person: "@bob".to_string(),
+                include_completed: Some(true),
+                include_archived: None,
+                strict: None,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(response.page, None);
+        let tasks: Vec<&str> = response.tasks.iter().map(|t| t.content.as_str()).collect();
+        assert_eq!(tasks, vec!["@bob book a room"]);
+        assert_eq!(response.meetings.len(), 1);
+        assert_eq!(response.meetings[0].date.as_deref(), Some("2025-01-06"));
+        assert!(response.backlinks.is_empty());
+
+        assert_eq!(person_key("Alice.Smith"), "alicesmith");
+    }
+}
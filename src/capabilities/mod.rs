@@ -1,34 +1,141 @@
+pub mod activity;
 pub mod daily_notes;
+pub mod digest;
 pub mod files;
+pub mod flashcards;
+pub mod highlights;
+pub mod inbox;
+pub mod links;
+pub mod meetings;
 pub mod outline;
+pub mod properties;
+pub mod search;
 pub mod tags;
+pub mod task_write;
 pub mod tasks;
+pub mod views;
 
 use crate::config::Config;
-use rmcp::model::ErrorData;
-use std::path::PathBuf;
+use crate::error::{forbidden, invalid_params, not_found, parse_error};
+use crate::path_resolver::{PathError, PathResolver, from_slash};
+use crate::warnings::ExtractionWarning;
+use std::path::{Component, Path, PathBuf};
 use std::sync::Arc;
 
+use self::activity::ActivityCapability;
 use self::daily_notes::DailyNoteCapability;
+use self::digest::DigestCapability;
 use self::files::FileCapability;
+use self::flashcards::FlashcardCapability;
+use self::highlights::HighlightCapability;
+use self::inbox::CaptureCapability;
+use self::links::LinkCapability;
+use self::meetings::MeetingCapability;
 use self::outline::OutlineCapability;
+use self::properties::PropertyCapability;
+use self::search::SearchCapability;
 use self::tags::TagCapability;
+use self::task_write::TaskWriteCapability;
 use self::tasks::TaskCapability;
+use self::views::ViewCapability;
 
 /// Result type for capability operations
-pub type CapabilityResult<T> = Result<T, ErrorData>;
+pub type CapabilityResult<T> = Result<T, crate::error::Error>;
+
+/// Request parameter that scopes an HTTP or MCP call to a subdirectory
+pub const SUBVAULT_PARAM: &str = "subvault";
+
+/// Description of the `subvault` parameter for tool schemas
+pub const SUBVAULT_DESCRIPTION: &str =
+    "Vault subdirectory (relative path) to scope this call to; only notes inside it are visible";
+
+/// Remove the `subvault` parameter from JSON request parameters
+pub fn take_subvault(
+    params: &mut serde_json::Map<String, serde_json::Value>,
+) -> CapabilityResult<Option<String>> {
+    match params.remove(SUBVAULT_PARAM) {
+        None => Ok(None),
+        Some(serde_json::Value::String(subvault)) => Ok(Some(subvault)),
+        Some(serde_json::Value::Null) => Ok(None),
+        Some(_) => Err(invalid_params("subvault must be a string")),
+    }
+}
+
+/// Resolve a subvault to a directory inside `base_path`
+///
+/// The subvault must be a relative path without `..` components that stays
+/// inside the vault once symlinks are resolved. The result is under
+/// `base_path` as given, so scoped results report the same paths as unscoped
+/// ones.
+pub fn resolve_subvault(base_path: &Path, subvault: &str) -> CapabilityResult<PathBuf> {
+    let relative = from_slash(subvault.trim());
+    if relative
+        .components()
+        .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir))
+    {
+        return Err(forbidden(format!(
+            "Invalid subvault '{}': must be a relative path inside the vault",
+            subvault
+        )));
+    }
+
+    let path = PathResolver::new(base_path)?
+        .resolve(subvault.trim())
+        .map_err(|e| match e {
+            PathError::NotFound => not_found(format!("Subvault '{}' does not exist", subvault)),
+            PathError::OutsideVault => {
+                forbidden(format!("Subvault '{}' is outside the vault", subvault))
+            }
+        })?;
+    if !path.is_dir() {
+        return Err(invalid_params(format!(
+            "Subvault '{}' is not a directory",
+            subvault
+        )));
+    }
+    Ok(path)
+}
+
+/// Fail a `strict: true` request if any files were skipped
+pub(crate) fn check_strict(
+    warnings: &[ExtractionWarning],
+    strict: Option<bool>,
+) -> CapabilityResult<()> {
+    if !strict.unwrap_or(false) || warnings.is_empty() {
+        return Ok(());
+    }
+    let files: Vec<String> = warnings.iter().map(|w| w.to_string()).collect();
+    Err(parse_error(format!(
+        "{} file(s) could not be processed: {}",
+        warnings.len(),
+        files.join("; ")
+    )))
+}
 
 /// Registry for managing capabilities
 ///
 /// This registry holds all capabilities and provides getter methods for
 /// accessing them. All capabilities are initialized at startup.
 pub struct CapabilityRegistry {
+    base_path: PathBuf,
+    config: Arc<Config>,
     // Capability instances
     task_capability: Arc<TaskCapability>,
+    task_write_capability: Arc<TaskWriteCapability>,
     tag_capability: Arc<TagCapability>,
     file_capability: Arc<FileCapability>,
     daily_note_capability: Arc<DailyNoteCapability>,
     outline_capability: Arc<OutlineCapability>,
+    activity_capability: Arc<ActivityCapability>,
+    meeting_capability: Arc<MeetingCapability>,
+    view_capability: Arc<ViewCapability>,
+    search_capability: Arc<SearchCapability>,
+    link_capability: Arc<LinkCapability>,
+    digest_capability: Arc<DigestCapability>,
+    flashcard_capability: Arc<FlashcardCapability>,
+    highlight_capability: Arc<HighlightCapability>,
+    property_capability: Arc<PropertyCapability>,
+    capture_capability: Arc<CaptureCapability>,
 }
 
 impl CapabilityRegistry {
@@ -42,19 +149,75 @@ impl CapabilityRegistry {
         ));
 
         Self {
+            base_path: base_path.clone(),
+            config: Arc::clone(&config),
             task_capability: Arc::new(TaskCapability::new(base_path.clone(), Arc::clone(&config))),
+            task_write_capability: Arc::new(TaskWriteCapability::new(
+                base_path.clone(),
+                Arc::clone(&config),
+            )),
             tag_capability: Arc::new(TagCapability::new(base_path.clone(), Arc::clone(&config))),
             file_capability,
             daily_note_capability,
-            outline_capability: Arc::new(OutlineCapability::new(base_path, Arc::clone(&config))),
+            outline_capability: Arc::new(OutlineCapability::new(
+                base_path.clone(),
+                Arc::clone(&config),
+            )),
+            activity_capability: Arc::new(ActivityCapability::new(
+                base_path.clone(),
+                Arc::clone(&config),
+            )),
+            meeting_capability: Arc::new(MeetingCapability::new(
+                base_path.clone(),
+                Arc::clone(&config),
+            )),
+            view_capability: Arc::new(ViewCapability::new(base_path.clone(), Arc::clone(&config))),
+            search_capability: Arc::new(SearchCapability::new(
+                base_path.clone(),
+                Arc::clone(&config),
+            )),
+            link_capability: Arc::new(LinkCapability::new(base_path.clone(), Arc::clone(&config))),
+            digest_capability: Arc::new(DigestCapability::new(
+                base_path.clone(),
+                Arc::clone(&config),
+            )),
+            flashcard_capability: Arc::new(FlashcardCapability::new(
+                base_path.clone(),
+                Arc::clone(&config),
+            )),
+            highlight_capability: Arc::new(HighlightCapability::new(
+                base_path.clone(),
+                Arc::clone(&config),
+            )),
+            property_capability: Arc::new(PropertyCapability::new(
+                base_path.clone(),
+                Arc::clone(&config),
+            )),
+            capture_capability: Arc::new(CaptureCapability::new(base_path, Arc::clone(&config))),
         }
     }
 
+    /// A registry with the same configuration, rooted at a subvault
+    pub fn scoped(&self, subvault: &str) -> CapabilityResult<CapabilityRegistry> {
+        let path = resolve_subvault(&self.base_path, subvault)?;
+        Ok(CapabilityRegistry::new(path, Arc::clone(&self.config)))
+    }
+
+    /// Get the vault configuration
+    pub fn config(&self) -> Arc<Config> {
+        Arc::clone(&self.config)
+    }
+
     /// Get the task capability
     pub fn tasks(&self) -> Arc<TaskCapability> {
         Arc::clone(&self.task_capability)
     }
 
+    /// Get the task write capability
+    pub fn task_write(&self) -> Arc<TaskWriteCapability> {
+        Arc::clone(&self.task_write_capability)
+    }
+
     /// Get the tag capability
     pub fn tags(&self) -> Arc<TagCapability> {
         Arc::clone(&self.tag_capability)
@@ -75,6 +238,56 @@ impl CapabilityRegistry {
         Arc::clone(&self.outline_capability)
     }
 
+    /// Get the activity capability
+    pub fn activity(&self) -> Arc<ActivityCapability> {
+        Arc::clone(&self.activity_capability)
+    }
+
+    /// Get the meeting capability
+    pub fn meetings(&self) -> Arc<MeetingCapability> {
+        Arc::clone(&self.meeting_capability)
+    }
+
+    /// Get the view capability
+    pub fn views(&self) -> Arc<ViewCapability> {
+        Arc::clone(&self.view_capability)
+    }
+
+    /// Get the search capability
+    pub fn search(&self) -> Arc<SearchCapability> {
+        Arc::clone(&self.search_capability)
+    }
+
+    /// Get the link capability
+    pub fn links(&self) -> Arc<LinkCapability> {
+        Arc::clone(&self.link_capability)
+    }
+
+    /// Get the digest capability
+    pub fn digest(&self) -> Arc<DigestCapability> {
+        Arc::clone(&self.digest_capability)
+    }
+
+    /// Get the flashcard capability
+    pub fn flashcards(&self) -> Arc<FlashcardCapability> {
+        Arc::clone(&self.flashcard_capability)
+    }
+
+    /// Get the highlight capability
+    pub fn highlights(&self) -> Arc<HighlightCapability> {
+        Arc::clone(&self.highlight_capability)
+    }
+
+    /// Get the property capability
+    pub fn properties(&self) -> Arc<PropertyCapability> {
+        Arc::clone(&self.property_capability)
+    }
+
+    /// Get the capture capability
+    pub fn capture(&self) -> Arc<CaptureCapability> {
+        Arc::clone(&self.capture_capability)
+    }
+
     /// Create all operations for automatic registration
     ///
     /// This is the single source of truth for which operations are exposed via HTTP, CLI, and MCP.
@@ -83,10 +296,19 @@ impl CapabilityRegistry {
         vec![
             // Task operations
             Arc::new(tasks::SearchTasksOperation::new(self.tasks())),
+            Arc::new(tasks::AskTasksOperation::new(self.tasks())),
+            Arc::new(tasks::QueryTasksOperation::new(self.tasks())),
+            Arc::new(tasks::ExportIcsOperation::new(self.tasks())),
+            Arc::new(tasks::SuggestTaskMergesOperation::new(self.tasks())),
+            Arc::new(task_write::AddTaskOperation::new(self.task_write())),
+            Arc::new(task_write::CompleteTaskOperation::new(self.task_write())),
+            Arc::new(task_write::MoveTaskOperation::new(self.task_write())),
             // Tag operations
             Arc::new(tags::ExtractTagsOperation::new(self.tags())),
             Arc::new(tags::ListTagsOperation::new(self.tags())),
             Arc::new(tags::SearchByTagsOperation::new(self.tags())),
+            Arc::new(tags::SuggestTagsOperation::new(self.tags())),
+            Arc::new(tags::TagsByFolderOperation::new(self.tags())),
             // File operations
             Arc::new(files::ListFilesOperation::new(self.files())),
             Arc::new(files::ReadFilesOperation::new(self.files())),
@@ -95,10 +317,172 @@ impl CapabilityRegistry {
             Arc::new(daily_notes::SearchDailyNotesOperation::new(
                 self.daily_notes(),
             )),
+            Arc::new(daily_notes::AppendDailyNoteOperation::new(
+                self.daily_notes(),
+            )),
             // Outline operations
             Arc::new(outline::GetOutlineOperation::new(self.outline())),
             Arc::new(outline::GetSectionOperation::new(self.outline())),
             Arc::new(outline::SearchHeadingsOperation::new(self.outline())),
+            Arc::new(outline::RenameHeadingOperation::new(self.outline())),
+            // Activity operations
+            Arc::new(activity::CompletionHeatmapOperation::new(self.activity())),
+            Arc::new(activity::ProjectBurndownOperation::new(self.activity())),
+            Arc::new(activity::TimeReportOperation::new(self.activity())),
+            Arc::new(activity::NoteTaskSummaryOperation::new(self.activity())),
+            // Meeting operations
+            Arc::new(meetings::ExtractActionItemsOperation::new(self.meetings())),
+            Arc::new(meetings::PersonReportOperation::new(self.meetings())),
+            // View operations
+            Arc::new(views::ListViewsOperation::new(self.views())),
+            Arc::new(views::RunViewOperation::new(self.views())),
+            Arc::new(views::MaterializeViewOperation::new(self.views())),
+            // Search operations
+            Arc::new(search::SearchContentOperation::new(self.search())),
+            // Link operations
+            Arc::new(links::ResolveLinkOperation::new(self.links())),
+            Arc::new(links::OutgoingLinksOperation::new(self.links())),
+            Arc::new(links::GraphMetricsOperation::new(self.links())),
+            // Digest operations
+            Arc::new(digest::SendDigestOperation::new(self.digest())),
+            // Flashcard operations
+            Arc::new(flashcards::ExtractFlashcardsOperation::new(
+                self.flashcards(),
+            )),
+            // Highlight operations
+            Arc::new(highlights::ExtractHighlightsOperation::new(
+                self.highlights(),
+            )),
+            // Property operations
+            Arc::new(properties::SetPropertyOperation::new(self.properties())),
+            // Capture operations
+            Arc::new(inbox::CaptureOperation::new(self.capture())),
         ]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_resolve_subvault() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path().join("vault");
+        fs::create_dir_all(vault.join("Work/Projects")).unwrap();
+        fs::write(vault.join("note.md"), "").unwrap();
+        fs::create_dir(temp_dir.path().join("outside")).unwrap();
+
+        let base = vault.canonicalize().unwrap();
+        assert_eq!(
+            resolve_subvault(&vault, "Work/Projects").unwrap(),
+            base.join("Work/Projects")
+        );
+        assert_eq!(
+            resolve_subvault(&vault, "./Work").unwrap(),
+            base.join("Work")
+        );
+
+        for bad in [
+            "../outside",
+            "Work/../../outside",
+            "/etc",
+            "Missing",
+            "note.md",
+        ] {
+            assert!(resolve_subvault(&vault, bad).is_err(), "{} accepted", bad);
+        }
+
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(temp_dir.path().join("outside"), vault.join("escape"))
+                .unwrap();
+            assert!(resolve_subvault(&vault, "escape").is_err());
+
+            // A vault reached through a symlink keeps its symlinked path
+            let link = temp_dir.path().join("link");
+            std::os::unix::fs::symlink(&vault, &link).unwrap();
+            assert_eq!(resolve_subvault(&link, "Work").unwrap(), link.join("Work"));
+        }
+    }
+
+    #[test]
+    fn test_read_only_operations() {
+        let registry = CapabilityRegistry::new(PathBuf::from("."), Arc::new(Config::default()));
+        let mut read_only: Vec<_> = registry
+            .create_operations()
+            .iter()
+            .filter(|op| op.read_only())
+            .map(|op| op.name())
+            .collect();
+        read_only.sort_unstable();
+
+        // A new operation is writable until it is added here and overrides
+        // read_only()
+        assert_eq!(
+            read_only,
+            [
+                "action-items",
+                "ask",
+                "completion-heatmap",
+                "extract-flashcards",
+                "extract-highlights",
+                "get-daily-note",
+                "graph-metrics",
+                "ics",
+                "list-files",
+                "list-tags",
+                "list-views",
+                "note-task-summary",
+                "outgoing-links",
+                "outline",
+                "person-report",
+                "project-burndown",
+                "query",
+                "read-files",
+                "resolve-link",
+                "run-view",
+                "search",
+                "search-daily-notes",
+                "search-headings",
+                "search-tags",
+                "section",
+                "suggest-tags",
+                "suggest-task-merges",
+                "tags",
+                "tags-by-folder",
+                "tasks",
+                "time-report",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_take_subvault() {
+        let params = |value: serde_json::Value| value.as_object().unwrap().clone();
+
+        let mut json = params(json!({"subvault": "Work", "status": "incomplete"}));
+        assert_eq!(take_subvault(&mut json).unwrap().as_deref(), Some("Work"));
+        assert_eq!(json, params(json!({"status": "incomplete"})));
+
+        assert_eq!(take_subvault(&mut json).unwrap(), None);
+        assert!(take_subvault(&mut params(json!({"subvault": 3}))).is_err());
+    }
+
+    #[test]
+    fn test_check_strict() {
+        let warnings = vec![ExtractionWarning::oversized(Path::new("big.md"), 200, 100)];
+        assert!(check_strict(&warnings, None).is_ok());
+        assert!(check_strict(&warnings, Some(false)).is_ok());
+        assert!(check_strict(&[], Some(true)).is_ok());
+
+        let error = check_strict(&warnings, Some(true)).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "1 file(s) could not be processed: big.md (oversized): 200 bytes exceeds max_file_size of 100 bytes"
+        );
+    }
+}
@@ -1,11 +1,20 @@
-use crate::capabilities::CapabilityResult;
+use crate::cache::{self, TaskCache};
+use crate::capabilities::{CapabilityResult, check_strict};
+use crate::completion_dates::stamp_before_write;
 use crate::config::Config;
-use crate::error::{internal_error, invalid_params};
+use crate::error::{conflict, internal_error, invalid_params, not_found, scan_error};
+use crate::link_extractor::{LinkExtractor, LinkIndex, WikiLink, find_anchor_line};
 use crate::outline_extractor::{Heading, HeadingMatch, OutlineExtractor, Section};
+use crate::path_resolver::{PathResolver, to_slash};
+use crate::tag_extractor::collect_markdown_files;
+use crate::unicode::{fold, fold_eq};
+use crate::warnings::{ExtractionWarning, Warnings};
 use clap::{CommandFactory, FromArgMatches};
+use rayon::prelude::*;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 /// Operation metadata for get_outline
@@ -109,6 +118,22 @@ pub mod search_headings {
     pub const HTTP_PATH: &str = "/api/outline/search";
 }
 
+/// Sort order for search_headings results
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema, clap::ValueEnum,
+)]
+#[serde(rename_all = "snake_case")]
+#[value(rename_all = "snake_case")]
+pub enum HeadingSortBy {
+    /// By file path, then line
+    #[default]
+    FilePath,
+    /// Shallowest headings first, then by file path and line
+    Level,
+    /// Alphabetical by heading title (case-insensitive), then by file path and line
+    Title,
+}
+
 /// Parameters for the search_headings operation
 #[derive(Debug, Deserialize, Serialize, JsonSchema, clap::Parser)]
 #[command(
@@ -139,10 +164,34 @@ pub struct SearchHeadingsRequest {
     #[schemars(description = "Maximum heading level to include (1-6, optional)")]
     pub max_level: Option<u8>,
 
+    #[arg(long, help = "Include notes under archived paths")]
+    #[schemars(
+        description = "If true, include notes under the configured archived_paths. Default: false"
+    )]
+    pub include_archived: Option<bool>,
+
+    #[arg(long, value_enum, help = "Sort by file path, heading level, or title")]
+    #[schemars(
+        description = "Sort order: \"file_path\" (then line), \"level\" (shallowest first), or \"title\" (alphabetical). Default: file_path"
+    )]
+    pub sort_by: Option<HeadingSortBy>,
+
     /// Limit number of results
     #[arg(long, help = "Maximum number of results")]
     #[schemars(description = "Maximum number of results to return")]
     pub limit: Option<usize>,
+
+    #[arg(long, help = "Number of sorted results to skip")]
+    #[schemars(
+        description = "Number of sorted results to skip before applying limit, for paging (optional, defaults to 0)"
+    )]
+    pub offset: Option<usize>,
+
+    #[arg(long, help = "Fail if any file can't be read instead of skipping it")]
+    #[schemars(
+        description = "If true, fail when a file is skipped (unreadable, invalid frontmatter, larger than max_file_size, or cut off by a truncating scan limit) instead of reporting it in warnings. Default: false"
+    )]
+    pub strict: Option<bool>,
 }
 
 /// Response from the search_headings operation
@@ -150,11 +199,97 @@ pub struct SearchHeadingsRequest {
 pub struct SearchHeadingsResponse {
     /// Matching headings found
     pub matches: Vec<HeadingMatch>,
-    /// Total number of matches
+    /// Number of matches returned
     pub total_count: usize,
+    /// Whether more matches follow this page
+    #[serde(default)]
+    pub truncated: bool,
+    /// Files that were skipped (unreadable, invalid frontmatter, larger
+    /// than `max_file_size`, or cut off by a truncating scan limit)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ExtractionWarning>,
+}
+
+/// Operation metadata for rename_heading
+pub mod rename_heading {
+    pub const DESCRIPTION: &str = "Rename a heading in a markdown file and update links to it across the vault: [[Note#Old Heading]], [[#Old Heading]] within the note, and [text](Note.md#Old%20Heading). Returns every file whose links changed; with dry_run nothing is written.";
+    pub const CLI_NAME: &str = "rename-heading";
+    pub const HTTP_PATH: &str = "/api/outline/rename";
+}
+
+/// Parameters for the rename_heading operation
+#[derive(Debug, Deserialize, Serialize, JsonSchema, clap::Parser)]
+#[command(
+    name = "rename-heading",
+    about = "Rename a heading and update links to it"
+)]
+pub struct RenameHeadingRequest {
+    /// Path to vault (CLI only - not used in HTTP/MCP)
+    #[arg(index = 1, required = true, help = "Path to vault root")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub vault_path: Option<PathBuf>,
+
+    /// File path relative to vault root
+    #[arg(index = 2, required = true, help = "File path relative to vault root")]
+    #[schemars(description = "File path relative to vault root")]
+    pub file_path: String,
+
+    /// Heading to rename
+    #[arg(index = 3, required = true, help = "Heading title to rename")]
+    #[schemars(
+        description = "Title of the heading to rename (case-insensitive). Use Parent#Child to pick a heading under a specific parent; otherwise the first match is renamed"
+    )]
+    pub heading: String,
+
+    /// New heading title
+    #[arg(index = 4, required = true, help = "New heading title")]
+    #[schemars(description = "New title, without the leading #s")]
+    pub new_title: String,
+
+    #[arg(long, help = "Report the changes without writing them")]
+    #[schemars(
+        description = "If true, return the changes without writing any file. Default: false"
+    )]
+    pub dry_run: Option<bool>,
+
+    #[arg(long, help = "Fail if any file can't be read instead of skipping it")]
+    #[schemars(
+        description = "If true, fail when a file is skipped (unreadable, invalid frontmatter, larger than max_file_size, or cut off by a truncating scan limit) instead of reporting it in warnings. Default: false"
+    )]
+    pub strict: Option<bool>,
+}
+
+/// Links rewritten in one note
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct LinkUpdate {
+    pub file_path: String,
+    /// Number of links updated in the note
+    pub links: usize,
+}
+
+/// Response from the rename_heading operation
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RenameHeadingResponse {
+    /// File path relative to vault root
+    pub file_path: String,
+    /// 1-based line of the renamed heading
+    pub line_number: usize,
+    pub previous_title: String,
+    pub title: String,
+    /// Notes whose links were updated, by path
+    pub updated_files: Vec<LinkUpdate>,
+    /// Total number of links updated
+    pub updated_links: usize,
+    pub dry_run: bool,
+    /// Files that were skipped (unreadable, invalid frontmatter, larger
+    /// than `max_file_size`, or cut off by a truncating scan limit)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ExtractionWarning>,
 }
 
-/// Capability for outline operations (get_outline, get_section, search_headings)
+/// Capability for outline operations (get_outline, get_section, search_headings,
+/// rename_heading)
 pub struct OutlineCapability {
     base_path: PathBuf,
     config: Arc<Config>,
@@ -173,37 +308,25 @@ impl OutlineCapability {
 
     /// Validate and resolve a file path within the vault
     fn resolve_file_path(&self, file_path: &str) -> CapabilityResult<PathBuf> {
-        // Construct full path
-        let requested_path = PathBuf::from(file_path);
-        let full_path = self.base_path.join(&requested_path);
-
-        // Canonicalize paths for security check
-        let canonical_base = self
-            .base_path
-            .canonicalize()
-            .map_err(|e| internal_error(format!("Failed to resolve base path: {}", e)))?;
-
-        let canonical_full = full_path
-            .canonicalize()
-            .map_err(|_| invalid_params(format!("File not found: {}", file_path)))?;
-
-        // Security: Ensure path is within base directory
-        if !canonical_full.starts_with(&canonical_base) {
-            return Err(invalid_params(format!(
-                "Invalid path '{}': must be within vault",
-                file_path
-            )));
-        }
+        PathResolver::new(&self.base_path)?.resolve_note(file_path)
+    }
 
-        // Validate it's a markdown file
-        if canonical_full.extension().and_then(|s| s.to_str()) != Some("md") {
-            return Err(invalid_params(format!(
-                "Invalid file type '{}': only .md files allowed",
-                file_path
-            )));
+    /// The vault's index, when `--cache` is on
+    fn open_cache(&self) -> Option<TaskCache> {
+        if cache::is_enabled() {
+            TaskCache::for_vault(&self.base_path, &self.config)
+        } else {
+            None
         }
+    }
 
-        Ok(canonical_full)
+    /// Store the outlines looked up through `cache`
+    fn update_cache(cache: Option<TaskCache>) {
+        if let Some(cache) = cache
+            && let Err(e) = cache.update()
+        {
+            eprintln!("Warning: Could not write task cache: {}", e);
+        }
     }
 
     /// Get outline from a file
@@ -215,10 +338,12 @@ impl OutlineCapability {
 
         let hierarchical = request.hierarchical.unwrap_or(false);
 
+        let cache = self.open_cache();
         let headings = self
             .outline_extractor
-            .get_outline(&file_path, hierarchical)
+            .get_outline(&file_path, hierarchical, cache.as_ref())
             .map_err(|e| internal_error(format!("Failed to extract outline: {}", e)))?;
+        Self::update_cache(cache);
 
         let total_count = headings.len();
         let file_name = file_path
@@ -244,10 +369,17 @@ impl OutlineCapability {
 
         let include_subsections = request.include_subsections.unwrap_or(false);
 
+        let cache = self.open_cache();
         let sections = self
             .outline_extractor
-            .get_section(&file_path, &request.heading, include_subsections)
+            .get_section(
+                &file_path,
+                &request.heading,
+                include_subsections,
+                cache.as_ref(),
+            )
             .map_err(|e| internal_error(format!("Failed to extract section: {}", e)))?;
+        Self::update_cache(cache);
 
         let section_count = sections.len();
         let file_name = file_path
@@ -281,23 +413,232 @@ impl OutlineCapability {
             return Err(invalid_params("max_level must be between 1 and 6"));
         }
 
-        let matches = self
+        let sort_by = request.sort_by.unwrap_or_default();
+        let offset = request.offset.unwrap_or(0);
+        // Matches come back in file path order, so that sort can stop early;
+        // one extra match tells whether the page is the last
+        let scan_limit = match (sort_by, request.limit) {
+            (HeadingSortBy::FilePath, Some(limit)) => {
+                Some(offset.saturating_add(limit).saturating_add(1))
+            }
+            _ => None,
+        };
+
+        let cache = self.open_cache();
+        let warnings = Warnings::default();
+        let mut matches = self
             .outline_extractor
             .search_headings(
                 &self.base_path,
                 &request.pattern,
                 request.min_level,
                 request.max_level,
-                request.limit,
-                &self.config,
+                scan_limit,
+                &self
+                    .config
+                    .search_scope(request.include_archived.unwrap_or(false)),
+                cache.as_ref(),
+                &warnings,
             )
-            .map_err(|e| internal_error(format!("Failed to search headings: {}", e)))?;
+            .map_err(|e| scan_error("Failed to search headings", e))?;
+        Self::update_cache(cache);
+        let warnings = warnings.into_vec();
+        check_strict(&warnings, request.strict)?;
+
+        // Stable sorts keep file path and line order among equal keys
+        match sort_by {
+            HeadingSortBy::FilePath => {}
+            HeadingSortBy::Level => matches.sort_by_key(|m| m.heading.level),
+            HeadingSortBy::Title => matches.sort_by_cached_key(|m| fold(&m.heading.title)),
+        }
 
+        let limit = request.limit.unwrap_or(usize::MAX);
+        let truncated = matches.len() > offset.saturating_add(limit);
+        let matches: Vec<HeadingMatch> = matches.into_iter().skip(offset).take(limit).collect();
         let total_count = matches.len();
 
         Ok(SearchHeadingsResponse {
             matches,
             total_count,
+            truncated,
+            warnings,
+        })
+    }
+
+    /// Rename a heading and point the links to it at the new title
+    pub async fn rename_heading(
+        &self,
+        request: RenameHeadingRequest,
+    ) -> CapabilityResult<RenameHeadingResponse> {
+        let title = request.new_title.trim();
+        if title.is_empty() || title.contains(['\n', '\r']) {
+            return Err(invalid_params("New title must be a single, non-empty line"));
+        }
+        // Obsidian can't link to headings containing these
+        if let Some(c) = title.chars().find(|c| "#|[]^".contains(*c)) {
+            return Err(invalid_params(format!(
+                "New title can't contain '{}', which breaks heading links",
+                c
+            )));
+        }
+
+        let resolver = PathResolver::new(&self.base_path)?;
+        let path = resolver.resolve_note(&request.file_path)?;
+        let relative = resolver.relative(&path).unwrap_or(&path).to_path_buf();
+        let file_path = to_slash(&relative);
+        let dry_run = request.dry_run.unwrap_or(false);
+        if !dry_run {
+            stamp_before_write(&path, &self.config)?;
+        }
+        let content = fs::read_to_string(&path)
+            .map_err(|e| internal_error(format!("Failed to read note: {}", e)))?;
+
+        let anchor = |heading: String| WikiLink {
+            target: String::new(),
+            heading: Some(heading),
+            block: None,
+            alias: None,
+            embed: false,
+        };
+        let wanted = request.heading.trim().trim_start_matches('#').trim();
+        let not_found_error = || {
+            not_found(format!(
+                "Heading '{}' not found in {}",
+                request.heading, file_path
+            ))
+        };
+        let line_number =
+            find_anchor_line(&content, &anchor(wanted.to_string())).ok_or_else(not_found_error)?;
+        let headings = self.outline_extractor.extract_headings(&content);
+        let heading = headings
+            .iter()
+            .find(|h| h.line_number == line_number)
+            .ok_or_else(not_found_error)?;
+        if let Some(other) = headings
+            .iter()
+            .find(|h| h.line_number != line_number && fold_eq(&h.title, title))
+        {
+            return Err(conflict(format!(
+                "{} already has a heading '{}' on line {}",
+                file_path, other.title, other.line_number
+            )));
+        }
+
+        // The title follows the heading's #s on its line
+        let offset: usize = content
+            .split_inclusive('\n')
+            .take(line_number - 1)
+            .map(str::len)
+            .sum();
+        let level = heading.level as usize;
+        let start = content[offset + level..]
+            .find(&heading.title)
+            .map(|i| offset + level + i)
+            .ok_or_else(|| internal_error("Heading title not found on its line"))?;
+        let renamed = format!(
+            "{}{}{}",
+            &content[..start],
+            title,
+            &content[start + heading.title.len()..]
+        );
+
+        // A link points at the heading if its anchor, or one of the parent
+        // paths in a nested anchor, resolves to the renamed line
+        let warnings = Warnings::default();
+        let index = LinkIndex::build(&self.base_path, &self.config, &warnings)
+            .map_err(|e| scan_error("Failed to index notes", e))?;
+        let link_extractor = LinkExtractor::new();
+        let rewrite = |source: &Path, link: &WikiLink| -> Option<String> {
+            let points_here = if link.target.is_empty() {
+                source == relative
+            } else {
+                index
+                    .resolve(&link.target, Some(source))
+                    .is_some_and(|resolution| resolution.path == relative)
+            };
+            if !points_here {
+                return None;
+            }
+
+            let parts: Vec<&str> = link.heading.as_deref()?.split('#').collect();
+            let mut changed = false;
+            let parts: Vec<&str> = (0..parts.len())
+                .map(|i| {
+                    let prefix = anchor(parts[..=i].join("#"));
+                    if find_anchor_line(&content, &prefix) == Some(line_number) {
+                        changed = true;
+                        title
+                    } else {
+                        parts[i]
+                    }
+                })
+                .collect();
+            changed.then(|| parts.join("#"))
+        };
+
+        let files = collect_markdown_files(&self.base_path, &self.config, &warnings)
+            .map_err(|e| scan_error("Failed to list notes", e))?;
+        let warnings = warnings.into_vec();
+        check_strict(&warnings, request.strict)?;
+        let mut updates: Vec<(PathBuf, String, String, usize)> = files
+            .par_iter()
+            .filter_map(|file| {
+                let source = file.strip_prefix(&self.base_path).unwrap_or(file);
+                if source == relative {
+                    return None;
+                }
+                let rewrite_note = |note: &str| {
+                    link_extractor
+                        .rewrite_heading_anchors(note, source, |link| rewrite(source, link))
+                };
+                let (mut updated, mut count) = rewrite_note(&fs::read_to_string(file).ok()?);
+                if count == 0 {
+                    return None;
+                }
+                // A note that will be written is stamped first, and its links
+                // rewritten again in the stamped text
+                if !dry_run {
+                    let stamped = stamp_before_write(file, &self.config).and_then(|()| {
+                        fs::read_to_string(file).map_err(|e| {
+                            internal_error(format!("Failed to read {}: {}", file.display(), e))
+                        })
+                    });
+                    match stamped {
+                        Ok(note) => (updated, count) = rewrite_note(&note),
+                        Err(e) => return Some(Err(e)),
+                    }
+                }
+                Some(Ok((file.clone(), to_slash(source), updated, count)))
+            })
+            .collect::<CapabilityResult<_>>()?;
+        let (renamed, count) =
+            link_extractor
+                .rewrite_heading_anchors(&renamed, &relative, |link| rewrite(&relative, link));
+        updates.push((path.clone(), file_path.clone(), renamed, count));
+        updates.sort_by(|a, b| a.1.cmp(&b.1));
+
+        if !dry_run {
+            for (file, _, updated, _) in &updates {
+                fs::write(file, updated).map_err(|e| {
+                    internal_error(format!("Failed to write {}: {}", file.display(), e))
+                })?;
+            }
+        }
+
+        let updated_files: Vec<LinkUpdate> = updates
+            .into_iter()
+            .filter(|(_, _, _, links)| *links > 0)
+            .map(|(_, file_path, _, links)| LinkUpdate { file_path, links })
+            .collect();
+        Ok(RenameHeadingResponse {
+            file_path,
+            line_number,
+            previous_title: heading.title.clone(),
+            title: title.to_string(),
+            updated_links: updated_files.iter().map(|update| update.links).sum(),
+            updated_files,
+            dry_run,
+            warnings,
         })
     }
 }
@@ -324,6 +665,17 @@ impl GetSectionOperation {
     }
 }
 
+/// Operation struct for rename_heading (HTTP, CLI, and MCP)
+pub struct RenameHeadingOperation {
+    capability: Arc<OutlineCapability>,
+}
+
+impl RenameHeadingOperation {
+    pub fn new(capability: Arc<OutlineCapability>) -> Self {
+        Self { capability }
+    }
+}
+
 /// Operation struct for search_headings (HTTP, CLI, and MCP)
 pub struct SearchHeadingsOperation {
     capability: Arc<OutlineCapability>,
@@ -349,6 +701,10 @@ impl crate::operation::Operation for GetOutlineOperation {
         get_outline::DESCRIPTION
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     fn get_command(&self) -> clap::Command {
         GetOutlineRequest::command()
     }
@@ -356,7 +712,7 @@ impl crate::operation::Operation for GetOutlineOperation {
     async fn execute_json(
         &self,
         json: serde_json::Value,
-    ) -> Result<serde_json::Value, rmcp::model::ErrorData> {
+    ) -> Result<serde_json::Value, crate::error::Error> {
         crate::http_router::execute_json_operation(json, |req| self.capability.get_outline(req))
             .await
     }
@@ -386,6 +742,11 @@ impl crate::operation::Operation for GetOutlineOperation {
         use schemars::schema_for;
         serde_json::to_value(schema_for!(GetOutlineRequest)).unwrap()
     }
+
+    fn output_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(GetOutlineResponse)).unwrap()
+    }
 }
 
 #[async_trait::async_trait]
@@ -402,6 +763,10 @@ impl crate::operation::Operation for GetSectionOperation {
         get_section::DESCRIPTION
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     fn get_command(&self) -> clap::Command {
         GetSectionRequest::command()
     }
@@ -409,7 +774,7 @@ impl crate::operation::Operation for GetSectionOperation {
     async fn execute_json(
         &self,
         json: serde_json::Value,
-    ) -> Result<serde_json::Value, rmcp::model::ErrorData> {
+    ) -> Result<serde_json::Value, crate::error::Error> {
         crate::http_router::execute_json_operation(json, |req| self.capability.get_section(req))
             .await
     }
@@ -439,6 +804,11 @@ impl crate::operation::Operation for GetSectionOperation {
         use schemars::schema_for;
         serde_json::to_value(schema_for!(GetSectionRequest)).unwrap()
     }
+
+    fn output_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(GetSectionResponse)).unwrap()
+    }
 }
 
 #[async_trait::async_trait]
@@ -455,6 +825,10 @@ impl crate::operation::Operation for SearchHeadingsOperation {
         search_headings::DESCRIPTION
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     fn get_command(&self) -> clap::Command {
         SearchHeadingsRequest::command()
     }
@@ -462,7 +836,7 @@ impl crate::operation::Operation for SearchHeadingsOperation {
     async fn execute_json(
         &self,
         json: serde_json::Value,
-    ) -> Result<serde_json::Value, rmcp::model::ErrorData> {
+    ) -> Result<serde_json::Value, crate::error::Error> {
         crate::http_router::execute_json_operation(json, |req| self.capability.search_headings(req))
             .await
     }
@@ -492,4 +866,294 @@ impl crate::operation::Operation for SearchHeadingsOperation {
         use schemars::schema_for;
         serde_json::to_value(schema_for!(SearchHeadingsRequest)).unwrap()
     }
+
+    fn output_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(SearchHeadingsResponse)).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::operation::Operation for RenameHeadingOperation {
+    fn name(&self) -> &'static str {
+        rename_heading::CLI_NAME
+    }
+
+    fn path(&self) -> &'static str {
+        rename_heading::HTTP_PATH
+    }
+
+    fn description(&self) -> &'static str {
+        rename_heading::DESCRIPTION
+    }
+
+    fn get_command(&self) -> clap::Command {
+        RenameHeadingRequest::command()
+    }
+
+    async fn execute_json(
+        &self,
+        json: serde_json::Value,
+    ) -> Result<serde_json::Value, crate::error::Error> {
+        crate::http_router::execute_json_operation(json, |req| self.capability.rename_heading(req))
+            .await
+    }
+
+    async fn execute_from_args(
+        &self,
+        matches: &clap::ArgMatches,
+        _registry: &crate::capabilities::CapabilityRegistry,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        let request = RenameHeadingRequest::from_arg_matches(matches)?;
+
+        // Handle CLI-specific vault path if present
+        let response = if let Some(ref vault_path) = request.vault_path {
+            let config = Arc::new(Config::load_from_base_path(vault_path.as_path()));
+            let capability = OutlineCapability::new(vault_path.clone(), config);
+            let mut req_without_path = request;
+            req_without_path.vault_path = None;
+            capability.rename_heading(req_without_path).await?
+        } else {
+            self.capability.rename_heading(request).await?
+        };
+
+        Ok(serde_json::to_string_pretty(&response)?)
+    }
+
+    fn input_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(RenameHeadingRequest)).unwrap()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(RenameHeadingResponse)).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_search_headings_sort_and_page() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("b")).unwrap();
+        fs::write(
+            temp_dir.path().join("b/one.md"),
+            "## Plan beta\n# Plan alpha\n",
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("a.md"),
+            "### Plan gamma\n## Plan Alpha\n",
+        )
+        .unwrap();
+        let capability =
+            OutlineCapability::new(temp_dir.path().to_path_buf(), Arc::new(Config::default()));
+        let search = |sort_by, limit, offset| {
+            capability.search_headings(SearchHeadingsRequest {
+                vault_path: None,
+                pattern: "plan".to_string(),
+                min_level: None,
+                max_level: None,
+                include_archived: None,
+                sort_by,
+                limit,
+                offset,
+                strict: None,
+            })
+        };
+        let titles = |response: &SearchHeadingsResponse| -> Vec<String> {
+            response
+                .matches
+                .iter()
+                .map(|m| m.heading.title.clone())
+                .collect()
+        };
+
+        let by_path = search(None, None, None).await.unwrap();
+        assert_eq!(
+            titles(&by_path),
+            vec!["Plan gamma", "Plan Alpha", "Plan beta", "Plan alpha"]
+        );
+
+        let by_level = search(Some(HeadingSortBy::Level), None, None)
+            .await
+            .unwrap();
+        assert_eq!(
+            titles(&by_level),
+            vec!["Plan alpha", "Plan Alpha", "Plan beta", "Plan gamma"]
+        );
+
+        let by_title = search(Some(HeadingSortBy::Title), Some(2), Some(1))
+            .await
+            .unwrap();
+        assert_eq!(titles(&by_title), vec!["Plan alpha", "Plan beta"]);
+        assert_eq!(by_title.total_count, 2);
+        assert!(by_title.truncated);
+
+        let last_page = search(None, Some(2), Some(2)).await.unwrap();
+        assert_eq!(titles(&last_page), vec!["Plan beta", "Plan alpha"]);
+        assert!(!last_page.truncated);
+    }
+
+    #[tokio::test]
+    async fn test_search_headings_reports_unreadable_files() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("a.md"), "# Plan\n").unwrap();
+        fs::write(temp_dir.path().join("binary.md"), b"# Plan \xff\xfe\n").unwrap();
+        let capability =
+            OutlineCapability::new(temp_dir.path().to_path_buf(), Arc::new(Config::default()));
+        let search = |strict| {
+            capability.search_headings(SearchHeadingsRequest {
+                vault_path: None,
+                pattern: "plan".to_string(),
+                min_level: None,
+                max_level: None,
+                include_archived: None,
+                sort_by: None,
+                limit: None,
+                offset: None,
+                strict,
+            })
+        };
+
+        let response = search(None).await.unwrap();
+        assert_eq!(response.total_count, 1);
+        assert_eq!(response.warnings.len(), 1);
+        assert!(response.warnings[0].file_path.ends_with("binary.md"));
+        assert_eq!(
+            response.warnings[0].kind,
+            crate::warnings::WarningKind::Unreadable
+        );
+
+        let error = search(Some(true)).await.unwrap_err();
+        assert_eq!(error.code(), "parse_error");
+    }
+
+    #[tokio::test]
+    async fn test_rename_heading_stamps_completion_dates() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        fs::write(vault.join("Alpha.md"), "## Goals\n- [x] Done\n").unwrap();
+        fs::write(vault.join("Log.md"), "- [x] Linked [[Alpha#Goals]]\n").unwrap();
+        fs::write(vault.join("Other.md"), "- [x] Untouched\n").unwrap();
+        let modified = |name: &str| {
+            chrono::DateTime::<chrono::Local>::from(
+                fs::metadata(vault.join(name)).unwrap().modified().unwrap(),
+            )
+            .date_naive()
+        };
+        let (alpha, log) = (modified("Alpha.md"), modified("Log.md"));
+        let config = Config {
+            stamp_completion_dates: true,
+            ..Config::default()
+        };
+        let capability = OutlineCapability::new(vault.to_path_buf(), Arc::new(config));
+        let request = |dry_run| RenameHeadingRequest {
+            vault_path: None,
+            file_path: "Alpha.md".to_string(),
+            heading: "Goals".to_string(),
+            new_title: "Objectives".to_string(),
+            dry_run: Some(dry_run),
+            strict: None,
+        };
+
+        // A dry run writes nothing, stamps included
+        capability.rename_heading(request(true)).await.unwrap();
+        assert_eq!(
+            fs::read_to_string(vault.join("Alpha.md")).unwrap(),
+            "## Goals\n- [x] Done\n"
+        );
+
+        capability.rename_heading(request(false)).await.unwrap();
+        assert_eq!(
+            fs::read_to_string(vault.join("Alpha.md")).unwrap(),
+            format!("## Objectives\n- [x] Done ✅ {}\n", alpha)
+        );
+        assert_eq!(
+            fs::read_to_string(vault.join("Log.md")).unwrap(),
+            format!("- [x] Linked [[Alpha#Objectives]] ✅ {}\n", log)
+        );
+        assert_eq!(
+            fs::read_to_string(vault.join("Other.md")).unwrap(),
+            "- [x] Untouched\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rename_heading() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        fs::create_dir_all(vault.join("projects")).unwrap();
+        fs::write(
+            vault.join("projects/Alpha.md"),
+            "# Alpha\n## Goals {#goals}\nSee [[#Goals]].\n## Notes\n### Goals\n",
+        )
+        .unwrap();
+        fs::write(
+            vault.join("Log.md"),
+            "[[Alpha#goals|plan]] [[Alpha#Notes#Goals]] [x](projects/Alpha.md#Goals)\n[[Beta#Goals]]\n",
+        )
+        .unwrap();
+        fs::write(vault.join("Beta.md"), "## Goals\n").unwrap();
+        let capability = OutlineCapability::new(vault.to_path_buf(), Arc::new(Config::default()));
+        let request = |heading: &str, new_title: &str, dry_run| RenameHeadingRequest {
+            vault_path: None,
+            file_path: "projects/Alpha.md".to_string(),
+            heading: heading.to_string(),
+            new_title: new_title.to_string(),
+            dry_run: Some(dry_run),
+            strict: None,
+        };
+
+        let preview = capability
+            .rename_heading(request("goals", "Objectives", true))
+            .await
+            .unwrap();
+        assert_eq!(preview.line_number, 2);
+        assert_eq!(preview.updated_links, 3);
+        let log = fs::read_to_string(vault.join("Log.md")).unwrap();
+        assert!(log.starts_with("[[Alpha#goals|plan]]"));
+
+        let response = capability
+            .rename_heading(request("goals", "Objectives", false))
+            .await
+            .unwrap();
+        assert_eq!(response.previous_title, "Goals");
+        let files: Vec<_> = response
+            .updated_files
+            .iter()
+            .map(|u| (u.file_path.as_str(), u.links))
+            .collect();
+        assert_eq!(files, vec![("Log.md", 2), ("projects/Alpha.md", 1)]);
+        assert_eq!(
+            fs::read_to_string(vault.join("projects/Alpha.md")).unwrap(),
+            "# Alpha\n## Objectives {#goals}\nSee [[#Objectives]].\n## Notes\n### Goals\n"
+        );
+        // The nested Notes#Goals heading and other notes' headings keep their links
+        assert_eq!(
+            fs::read_to_string(vault.join("Log.md")).unwrap(),
+            "[[Alpha#Objectives|plan]] [[Alpha#Notes#Goals]] [x](projects/Alpha.md#Objectives)\n[[Beta#Goals]]\n"
+        );
+
+        let err = capability
+            .rename_heading(request("Notes", "Objectives", false))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), "conflict");
+        let err = capability
+            .rename_heading(request("Missing", "Other", false))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), "not_found");
+        let err = capability
+            .rename_heading(request("Notes", "A | B", false))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), "invalid_params");
+    }
 }
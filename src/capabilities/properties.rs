@@ -0,0 +1,327 @@
+//! Frontmatter property capability
+//!
+//! Sets note properties (top-level YAML frontmatter keys). Edits go through
+//! `frontmatter::set_property`, so only the edited key's lines change and
+//! the rest of the note is written back byte for byte.
+
+use crate::capabilities::CapabilityResult;
+use crate::completion_dates::stamp_before_write;
+use crate::config::Config;
+use crate::error::{Error, internal_error, invalid_params};
+use crate::frontmatter::{parse_frontmatter, set_property};
+use crate::path_resolver::{PathResolver, to_slash};
+use clap::{CommandFactory, FromArgMatches, Parser};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Operation metadata for set_property
+pub mod set_property {
+    pub const DESCRIPTION: &str = "Set a property (top-level YAML frontmatter key) on a note, e.g. status: done. The value is read as YAML, so numbers, booleans, and [lists] keep their type. With append, the value is added to a list property instead (e.g. add a tag). Other frontmatter and the note body are left untouched; a note without frontmatter gets a new block.";
+    #[allow(dead_code)]
+    pub const CLI_NAME: &str = "set-property";
+    pub const HTTP_PATH: &str = "/api/properties/set";
+}
+
+/// Parameters for the set_property operation
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Parser)]
+#[command(name = "set-property", about = "Set a frontmatter property on a note")]
+pub struct SetPropertyRequest {
+    /// Path to vault (CLI only - not used in HTTP/MCP)
+    #[arg(index = 1, required = true, help = "Path to vault")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub path: Option<PathBuf>,
+
+    #[arg(index = 2, required = true, help = "Note path relative to vault root")]
+    #[schemars(description = "Vault-relative path of the note to edit")]
+    pub file_path: String,
+
+    #[arg(index = 3, required = true, help = "Property name")]
+    #[schemars(description = "Property name (top-level frontmatter key), e.g. 'status'")]
+    pub key: String,
+
+    #[arg(index = 4, required = true, help = "Property value (parsed as YAML)")]
+    #[schemars(
+        description = "Value, parsed as YAML: '3' is a number, 'true' a boolean, '[a, b]' a list, anything else a string"
+    )]
+    pub value: String,
+
+    #[arg(
+        long,
+        help = "Add the value to a list property instead of replacing it"
+    )]
+    #[schemars(
+        description = "If true, add the value (or each value of a list) to the property's list, skipping values already present. A scalar property becomes a list. Default: false"
+    )]
+    pub append: Option<bool>,
+}
+
+/// Response from the set_property operation
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SetPropertyResponse {
+    pub file_path: String,
+    pub key: String,
+    /// Value before the edit, absent if the property wasn't set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_value: Option<serde_json::Value>,
+    /// Value as written
+    pub value: serde_json::Value,
+    /// False if the property already had this value and the note was left alone
+    pub changed: bool,
+}
+
+/// Capability for editing note properties
+pub struct PropertyCapability {
+    base_path: PathBuf,
+    config: Arc<Config>,
+}
+
+impl PropertyCapability {
+    /// Create a new PropertyCapability
+    pub fn new(base_path: PathBuf, config: Arc<Config>) -> Self {
+        Self { base_path, config }
+    }
+
+    /// `existing` with `value` (or each item of a list value) appended
+    ///
+    /// For the configured tag keys, comma-separated strings are split the way
+    /// tag extraction reads them and a leading `#` is dropped.
+    fn appended(
+        &self,
+        key: &str,
+        existing: Option<&Value>,
+        value: Value,
+    ) -> CapabilityResult<Value> {
+        let is_tag_key = self.config.frontmatter_tag_keys.iter().any(|k| k == key);
+        let split = |value: Value| -> Vec<Value> {
+            match value {
+                Value::Sequence(items) => items,
+                Value::String(s) if is_tag_key => s
+                    .split(',')
+                    .map(|tag| tag.trim().trim_start_matches('#'))
+                    .filter(|tag| !tag.is_empty())
+                    .map(|tag| Value::String(tag.to_string()))
+                    .collect(),
+                Value::Null => Vec::new(),
+                other => vec![other],
+            }
+        };
+
+        let mut items = match existing {
+            Some(Value::Mapping(_)) => {
+                return Err(invalid_params(format!(
+                    "Property '{}' is a mapping; only lists can be appended to",
+                    key
+                )));
+            }
+            Some(existing) => split(existing.clone()),
+            None => Vec::new(),
+        };
+        for item in split(value) {
+            if !items.contains(&item) {
+                items.push(item);
+            }
+        }
+        Ok(Value::Sequence(items))
+    }
+
+    /// Set (or append to) a property in a note's frontmatter
+    pub async fn set_property(
+        &self,
+        request: SetPropertyRequest,
+    ) -> CapabilityResult<SetPropertyResponse> {
+        let key = request.key.trim();
+        if key.is_empty() || key.contains(['\n', '\r']) {
+            return Err(invalid_params(
+                "Property name must be a single, non-empty line",
+            ));
+        }
+        let value = match serde_yaml::from_str(&request.value) {
+            // `#tag` parses as a YAML comment
+            Ok(Value::Null) if request.value.trim_start().starts_with('#') => {
+                Value::String(request.value.trim().to_string())
+            }
+            Ok(value) => value,
+            Err(_) => Value::String(request.value.clone()),
+        };
+        if let Value::Mapping(_) = value {
+            // `a: b` reads as a mapping, which is almost never what was meant
+            return Err(invalid_params(
+                "Property value must be a scalar or a list; quote it to store text containing ': '",
+            ));
+        }
+
+        let resolver = PathResolver::new(&self.base_path)?;
+        let path = resolver.resolve_note(&request.file_path)?;
+        let file_path = to_slash(resolver.relative(&path).unwrap_or(&path));
+        stamp_before_write(&path, &self.config)?;
+        let content = fs::read_to_string(&path)
+            .map_err(|e| internal_error(format!("Failed to read note: {}", e)))?;
+
+        let frontmatter = parse_frontmatter(&content)
+            .map_err(|e| invalid_params(format!("Cannot edit {}: {}", file_path, e)))?;
+        let previous = frontmatter.get(key);
+        let value = if request.append.unwrap_or(false) {
+            self.appended(key, previous, value)?
+        } else {
+            value
+        };
+
+        let changed = previous != Some(&value);
+        if changed {
+            let updated = set_property(&content, key, &value)
+                .map_err(|e| invalid_params(format!("Cannot edit {}: {}", file_path, e)))?;
+            fs::write(&path, updated)
+                .map_err(|e| internal_error(format!("Failed to write note: {}", e)))?;
+        }
+
+        let to_json = |value: &Value| {
+            serde_json::to_value(value)
+                .map_err(|e| internal_error(format!("Failed to convert property: {}", e)))
+        };
+        Ok(SetPropertyResponse {
+            file_path,
+            key: key.to_string(),
+            previous_value: previous.map(to_json).transpose()?,
+            value: to_json(&value)?,
+            changed,
+        })
+    }
+}
+
+/// Operation struct for set_property (HTTP, CLI, and MCP)
+pub struct SetPropertyOperation {
+    capability: Arc<PropertyCapability>,
+}
+
+impl SetPropertyOperation {
+    pub fn new(capability: Arc<PropertyCapability>) -> Self {
+        Self { capability }
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::operation::Operation for SetPropertyOperation {
+    fn name(&self) -> &'static str {
+        set_property::CLI_NAME
+    }
+
+    fn path(&self) -> &'static str {
+        set_property::HTTP_PATH
+    }
+
+    fn description(&self) -> &'static str {
+        set_property::DESCRIPTION
+    }
+
+    fn get_command(&self) -> clap::Command {
+        // Get command from request struct's Parser derive
+        SetPropertyRequest::command()
+    }
+
+    async fn execute_json(&self, json: serde_json::Value) -> Result<serde_json::Value, Error> {
+        crate::http_router::execute_json_operation(json, |req| self.capability.set_property(req))
+            .await
+    }
+
+    async fn execute_from_args(
+        &self,
+        matches: &clap::ArgMatches,
+        _registry: &crate::capabilities::CapabilityRegistry,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        // Parse request from ArgMatches
+        let request = SetPropertyRequest::from_arg_matches(matches)?;
+
+        // Handle CLI-specific path if present
+        let response = if let Some(ref path) = request.path {
+            let config = Arc::new(Config::load_from_base_path(path.as_path()));
+            let capability = PropertyCapability::new(path.clone(), config);
+            let mut req_without_path = request;
+            req_without_path.path = None;
+            capability.set_property(req_without_path).await?
+        } else {
+            self.capability.set_property(request).await?
+        };
+
+        // Serialize to JSON
+        Ok(serde_json::to_string_pretty(&response)?)
+    }
+
+    fn input_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(SetPropertyRequest)).unwrap()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(SetPropertyResponse)).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn request(key: &str, value: &str, append: bool) -> SetPropertyRequest {
+        SetPropertyRequest {
+            file_path: "plan.md".to_string(),
+            key: key.to_string(),
+            value: value.to_string(),
+            append: Some(append),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_set_property() {
+        let vault = TempDir::new().unwrap();
+        let note = vault.path().join("plan.md");
+        fs::write(
+            &note,
+            "---\n# owner: me\ntags: work, q1\nstatus: open\n---\n- [ ] Ship\n",
+        )
+        .unwrap();
+        let capability =
+            PropertyCapability::new(vault.path().to_path_buf(), Arc::new(Config::default()));
+
+        let response = capability
+            .set_property(request("status", "done", false))
+            .await
+            .unwrap();
+        assert_eq!(response.previous_value, Some(serde_json::json!("open")));
+        assert!(response.changed);
+
+        let response = capability
+            .set_property(request("tags", "#rust, q1", true))
+            .await
+            .unwrap();
+        assert_eq!(response.value, serde_json::json!(["work", "q1", "rust"]));
+        assert_eq!(
+            fs::read_to_string(&note).unwrap(),
+            "---\n# owner: me\ntags:\n- work\n- q1\n- rust\nstatus: done\n---\n- [ ] Ship\n"
+        );
+
+        // Setting the same value leaves the note alone
+        let response = capability
+            .set_property(request("status", "done", false))
+            .await
+            .unwrap();
+        assert!(!response.changed);
+
+        let err = capability
+            .set_property(request("status", "a: b", false))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), "invalid_params");
+        let outside = SetPropertyRequest {
+            file_path: "../plan.md".to_string(),
+            ..request("status", "done", false)
+        };
+        assert!(capability.set_property(outside).await.is_err());
+    }
+}
@@ -0,0 +1,863 @@
+//! Full-text search over note contents
+//!
+//! Queries are made of terms and `"quoted phrases"`, combined with `AND`
+//! (the default between adjacent terms) and `OR`. Matching is
+//! case-insensitive substring matching. With `regex: true` the whole query
+//! is instead one regular expression, matched case-insensitively line by line.
+//! Results are ranked with BM25 and include the character spans of every
+//! match so UIs can highlight them.
+
+use crate::capabilities::{CapabilityResult, check_strict};
+use crate::config::Config;
+use crate::error::{Error, invalid_params, scan_error};
+use crate::frontmatter::body_after_frontmatter;
+use crate::path_resolver::to_slash;
+use crate::tag_extractor::{collect_markdown_files, note_excerpt};
+use crate::warnings::{ExtractionWarning, WarningKind, Warnings};
+use clap::{CommandFactory, FromArgMatches, Parser};
+use rayon::prelude::*;
+use regex::{Regex, RegexBuilder};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Operation metadata for search_content
+pub mod search_content {
+    pub const DESCRIPTION: &str = "Full-text search across note contents. Supports \"quoted phrases\" and AND/OR (terms are ANDed by default), or a regular expression with regex=true. Results are ranked by relevance and include character offsets of each match for highlighting.";
+    #[allow(dead_code)]
+    pub const CLI_NAME: &str = "search";
+    pub const HTTP_PATH: &str = "/api/search";
+}
+
+/// Default number of files returned by search_content
+const DEFAULT_SEARCH_LIMIT: usize = 20;
+
+/// Default number of matching lines returned per file
+const DEFAULT_MATCHES_PER_FILE: usize = 5;
+
+/// BM25 term frequency saturation
+const BM25_K1: f64 = 1.2;
+
+/// BM25 document length normalization
+const BM25_B: f64 = 0.75;
+
+/// Parameters for the search_content operation
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Parser)]
+#[command(name = "search", about = "Full-text search across note contents")]
+pub struct SearchContentRequest {
+    /// Path to vault (CLI only - not used in HTTP/MCP)
+    #[arg(index = 1, required = true, help = "Path to vault")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub path: Option<PathBuf>,
+
+    #[arg(index = 2, required = true, help = "Search query")]
+    #[schemars(
+        description = "Search query: terms and \"quoted phrases\", combined with AND (default) and OR, e.g. '\"release notes\" OR changelog'"
+    )]
+    pub query: String,
+
+    #[arg(long, help = "Treat the query as a regular expression")]
+    #[schemars(
+        description = "If true, the query is a single regular expression (Rust regex syntax, case-insensitive unless it starts with (?-i)) matched against each line instead of terms and phrases. Default: false"
+    )]
+    pub regex: Option<bool>,
+
+    #[arg(long, help = "Include notes under archived paths")]
+    #[schemars(
+        description = "If true, include notes under the configured archived_paths. Default: false"
+    )]
+    pub include_archived: Option<bool>,
+
+    #[arg(long, help = "Maximum number of files to return")]
+    #[schemars(description = "Maximum number of files to return (defaults to 20)")]
+    pub limit: Option<usize>,
+
+    #[arg(long, help = "Include a short excerpt of each note")]
+    #[schemars(
+        description = "If true, each result includes an excerpt of up to 200 characters from the note's first paragraph after the frontmatter. Default: false"
+    )]
+    pub include_excerpt: Option<bool>,
+
+    #[arg(long, help = "Maximum number of matching lines per file")]
+    #[schemars(description = "Maximum number of matching lines returned per file (defaults to 5)")]
+    pub max_matches_per_file: Option<usize>,
+
+    #[arg(long, help = "Fail if any file can't be read instead of skipping it")]
+    #[schemars(
+        description = "If true, fail when a file is skipped (unreadable, invalid frontmatter, larger than max_file_size, or cut off by a truncating scan limit) instead of reporting it in warnings. Default: false"
+    )]
+    pub strict: Option<bool>,
+}
+
+/// A highlighted range within a line, in characters (end exclusive)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct MatchSpan {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// A line containing at least one match
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct LineMatch {
+    /// 1-based line number
+    pub line_number: usize,
+    pub line: String,
+    /// Matched ranges within `line`, sorted and non-overlapping
+    pub spans: Vec<MatchSpan>,
+}
+
+/// A file matching the query
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SearchResult {
+    pub file_path: String,
+    pub file_name: String,
+    /// Relevance score (higher is better)
+    pub score: f64,
+    /// Total number of matches in the file
+    pub match_count: usize,
+    pub matches: Vec<LineMatch>,
+    /// The note's first paragraph, with `include_excerpt`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub excerpt: Option<String>,
+}
+
+/// Response from the search_content operation
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SearchContentResponse {
+    pub query: String,
+    /// Number of files that matched, before the limit was applied
+    pub total_matches: usize,
+    pub results: Vec<SearchResult>,
+    /// Files that were skipped (unreadable, invalid frontmatter, larger
+    /// than `max_file_size`, or cut off by a truncating scan limit)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ExtractionWarning>,
+}
+
+/// A parsed query: any of the groups matches, and a group matches when all
+/// of its terms do
+#[derive(Debug, PartialEq)]
+struct Query {
+    groups: Vec<Vec<String>>,
+}
+
+impl Query {
+    /// Every distinct term in the query, in order of first appearance
+    fn terms(&self) -> Vec<&str> {
+        let mut terms: Vec<&str> = Vec::new();
+        for term in self.groups.iter().flatten() {
+            if !terms.contains(&term.as_str()) {
+                terms.push(term);
+            }
+        }
+        terms
+    }
+}
+
+/// Parse a query into OR-separated groups of ANDed terms
+///
+/// Terms are lowercased; `AND`/`OR` are only operators when written in
+/// capitals, so they can still be searched for as ordinary words.
+fn parse_query(query: &str) -> Option<Query> {
+    let mut groups = vec![Vec::new()];
+    let mut chars = query.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        let (token, quoted) = if c == '"' {
+            chars.next();
+            let phrase: String = chars.by_ref().take_while(|&c| c != '"').collect();
+            (
+                phrase.split_whitespace().collect::<Vec<_>>().join(" "),
+                true,
+            )
+        } else {
+            let mut word = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() || c == '"' {
+                    break;
+                }
+                word.push(c);
+                chars.next();
+            }
+            (word, false)
+        };
+
+        match token.as_str() {
+            "" => {}
+            "OR" if !quoted => groups.push(Vec::new()),
+            "AND" if !quoted => {}
+            _ => groups
+                .last_mut()
+                .unwrap()
+                .push(lowercase_chars(&token).iter().collect()),
+        }
+    }
+
+    groups.retain(|group: &Vec<String>| !group.is_empty());
+    (!groups.is_empty()).then_some(Query { groups })
+}
+
+/// Compile the query of a regex search
+///
+/// The `regex` crate runs in linear time, so any pattern that compiles is
+/// safe to run over every note; overly large patterns fail to compile.
+fn compile_pattern(pattern: &str) -> CapabilityResult<Regex> {
+    RegexBuilder::new(pattern)
+        .case_insensitive(true)
+        .size_limit(1 << 20)
+        .build()
+        .map_err(|e| invalid_params(format!("Invalid regular expression: {}", e)))
+}
+
+/// What one query term matches
+enum Matcher {
+    /// A lowercased term or phrase, matched as a substring
+    Text(Vec<char>),
+    /// A regular expression; empty matches are ignored
+    Pattern(Regex),
+}
+
+impl Matcher {
+    /// Character spans of the matches in `line`, given its lowercased characters
+    fn find_all(&self, line: &str, lowered: &[char]) -> Vec<MatchSpan> {
+        match self {
+            Matcher::Text(needle) => find_all(lowered, needle),
+            Matcher::Pattern(regex) => {
+                // Convert byte offsets to character offsets, counting from
+                // the previous match
+                let mut spans = Vec::new();
+                let (mut byte, mut chars) = (0, 0);
+                for found in regex.find_iter(line).filter(|m| !m.is_empty()) {
+                    let start = chars + line[byte..found.start()].chars().count();
+                    let end = start + found.as_str().chars().count();
+                    spans.push(MatchSpan { start, end });
+                    (byte, chars) = (found.end(), end);
+                }
+                spans
+            }
+        }
+    }
+}
+
+/// Lowercase a string one character at a time so character offsets in the
+/// result line up with the original
+fn lowercase_chars(text: &str) -> Vec<char> {
+    text.chars()
+        .map(|c| c.to_lowercase().next().unwrap_or(c))
+        .collect()
+}
+
+/// Character offsets of every non-overlapping occurrence of `needle`
+fn find_all(haystack: &[char], needle: &[char]) -> Vec<MatchSpan> {
+    let mut spans = Vec::new();
+    if needle.is_empty() || needle.len() > haystack.len() {
+        return spans;
+    }
+
+    let mut start = 0;
+    while start + needle.len() <= haystack.len() {
+        if haystack[start..start + needle.len()] == *needle {
+            spans.push(MatchSpan {
+                start,
+                end: start + needle.len(),
+            });
+            start += needle.len();
+        } else {
+            start += 1;
+        }
+    }
+    spans
+}
+
+/// Sort spans and merge any that overlap or touch
+fn merge_spans(mut spans: Vec<MatchSpan>) -> Vec<MatchSpan> {
+    spans.sort_by_key(|span| (span.start, span.end));
+    let mut merged: Vec<MatchSpan> = Vec::with_capacity(spans.len());
+    for span in spans {
+        match merged.last_mut() {
+            Some(last) if span.start <= last.end => last.end = last.end.max(span.end),
+            _ => merged.push(span),
+        }
+    }
+    merged
+}
+
+/// Matches of a query's terms within one file
+struct FileHits {
+    path: PathBuf,
+    /// Number of words in the file, for length normalization
+    length: usize,
+    /// Occurrences of each query term (indexed like `Query::terms`)
+    term_counts: Vec<usize>,
+    lines: Vec<LineMatch>,
+    excerpt: Option<String>,
+}
+
+/// Find every occurrence of the query's terms in a file's content
+fn scan_content(path: &Path, content: &str, terms: &[Matcher]) -> FileHits {
+    let mut term_counts = vec![0; terms.len()];
+    let mut lines = Vec::new();
+
+    for (index, line) in content.lines().enumerate() {
+        let lowered = lowercase_chars(line);
+        let mut spans = Vec::new();
+        for (term, count) in terms.iter().zip(term_counts.iter_mut()) {
+            let found = term.find_all(line, &lowered);
+            *count += found.len();
+            spans.extend(found);
+        }
+        if !spans.is_empty() {
+            lines.push(LineMatch {
+                line_number: index + 1,
+                line: line.to_string(),
+                spans: merge_spans(spans),
+            });
+        }
+    }
+
+    FileHits {
+        path: path.to_path_buf(),
+        length: content.split_whitespace().count(),
+        term_counts,
+        lines,
+        excerpt: None,
+    }
+}
+
+/// BM25 score of a file for the given terms
+///
+/// `document_frequencies` holds, for each term, how many of the
+/// `document_count` searched files contain it.
+fn bm25(
+    hits: &FileHits,
+    terms: &[usize],
+    document_frequencies: &[usize],
+    document_count: usize,
+    average_length: f64,
+) -> f64 {
+    let n = document_count as f64;
+    let length_ratio = if average_length > 0.0 {
+        hits.length as f64 / average_length
+    } else {
+        1.0
+    };
+
+    terms
+        .iter()
+        .map(|&term| {
+            let tf = hits.term_counts[term] as f64;
+            let df = document_frequencies[term] as f64;
+            let idf = ((n - df + 0.5) / (df + 0.5) + 1.0).ln();
+            idf * tf * (BM25_K1 + 1.0) / (tf + BM25_K1 * (1.0 - BM25_B + BM25_B * length_ratio))
+        })
+        .sum()
+}
+
+/// Capability for full-text search
+pub struct SearchCapability {
+    base_path: PathBuf,
+    config: Arc<Config>,
+}
+
+impl SearchCapability {
+    /// Create a new SearchCapability
+    pub fn new(base_path: PathBuf, config: Arc<Config>) -> Self {
+        Self { base_path, config }
+    }
+
+    /// Search note contents, ranking files by relevance
+    pub async fn search_content(
+        &self,
+        request: SearchContentRequest,
+    ) -> CapabilityResult<SearchContentResponse> {
+        let (query, matchers) = if request.regex.unwrap_or(false) {
+            if request.query.is_empty() {
+                return Err(invalid_params("Regular expression must not be empty"));
+            }
+            let pattern = compile_pattern(&request.query)?;
+            let query = Query {
+                groups: vec![vec![request.query.clone()]],
+            };
+            (query, vec![Matcher::Pattern(pattern)])
+        } else {
+            let query = parse_query(&request.query)
+                .ok_or_else(|| invalid_params("Search query must contain at least one term"))?;
+            let matchers = query
+                .terms()
+                .iter()
+                .map(|term| Matcher::Text(term.chars().collect()))
+                .collect();
+            (query, matchers)
+        };
+        let terms = query.terms();
+
+        let config = self
+            .config
+            .search_scope(request.include_archived.unwrap_or(false));
+        let warnings = Warnings::default();
+        let files = collect_markdown_files(&self.base_path, &config, &warnings)
+            .map_err(|e| scan_error("Failed to collect files", e))?;
+
+        let include_excerpt = request.include_excerpt.unwrap_or(false);
+        let scanned: Vec<FileHits> = files
+            .par_iter()
+            .filter_map(|path| {
+                let content = fs::read_to_string(path)
+                    .map_err(|e| {
+                        warnings.push(ExtractionWarning::new(path, WarningKind::Unreadable, e))
+                    })
+                    .ok()?;
+                let mut hits = scan_content(path, &content, &matchers);
+                if include_excerpt && !hits.lines.is_empty() {
+                    hits.excerpt = note_excerpt(body_after_frontmatter(&content));
+                }
+                Some(hits)
+            })
+            .collect();
+        let warnings = warnings.into_vec();
+        check_strict(&warnings, request.strict)?;
+
+        let document_count = scanned.len();
+        let average_length = if document_count > 0 {
+            scanned.iter().map(|hits| hits.length).sum::<usize>() as f64 / document_count as f64
+        } else {
+            0.0
+        };
+        let document_frequencies: Vec<usize> = (0..terms.len())
+            .map(|term| {
+                scanned
+                    .iter()
+                    .filter(|hits| hits.term_counts[term] > 0)
+                    .count()
+            })
+            .collect();
+
+        // Index each group's terms so a file can be scored by the best group it satisfies
+        let groups: Vec<Vec<usize>> = query
+            .groups
+            .iter()
+            .map(|group| {
+                group
+                    .iter()
+                    .map(|term| terms.iter().position(|t| t == term).unwrap())
+                    .collect()
+            })
+            .collect();
+
+        let matches_per_file = request
+            .max_matches_per_file
+            .unwrap_or(DEFAULT_MATCHES_PER_FILE);
+
+        let mut results: Vec<SearchResult> = scanned
+            .into_iter()
+            .filter_map(|hits| {
+                let score = groups
+                    .iter()
+                    .filter(|group| group.iter().all(|&term| hits.term_counts[term] > 0))
+                    .map(|group| {
+                        bm25(
+                            &hits,
+                            group,
+                            &document_frequencies,
+                            document_count,
+                            average_length,
+                        )
+                    })
+                    .max_by(f64::total_cmp)?;
+
+                let relative = hits
+                    .path
+                    .strip_prefix(&self.base_path)
+                    .unwrap_or(&hits.path);
+                let match_count = hits.lines.iter().map(|line| line.spans.len()).sum();
+                let mut lines = hits.lines;
+                lines.truncate(matches_per_file);
+
+                Some(SearchResult {
+                    file_path: to_slash(relative),
+                    file_name: hits
+                        .path
+                        .file_name()
+                        .map(|name| name.to_string_lossy().to_string())
+                        .unwrap_or_default(),
+                    score: (score * 1000.0).round() / 1000.0,
+                    match_count,
+                    matches: lines,
+                    excerpt: hits.excerpt,
+                })
+            })
+            .collect();
+
+        results.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.file_path.cmp(&b.file_path))
+        });
+
+        let total_matches = results.len();
+        results.truncate(request.limit.unwrap_or(DEFAULT_SEARCH_LIMIT));
+
+        Ok(SearchContentResponse {
+            query: request.query,
+            total_matches,
+            results,
+            warnings,
+        })
+    }
+}
+
+/// Operation struct for search_content (HTTP, CLI, and MCP)
+pub struct SearchContentOperation {
+    capability: Arc<SearchCapability>,
+}
+
+impl SearchContentOperation {
+    pub fn new(capability: Arc<SearchCapability>) -> Self {
+        Self { capability }
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::operation::Operation for SearchContentOperation {
+    fn name(&self) -> &'static str {
+        search_content::CLI_NAME
+    }
+
+    fn path(&self) -> &'static str {
+        search_content::HTTP_PATH
+    }
+
+    fn description(&self) -> &'static str {
+        search_content::DESCRIPTION
+    }
+
+    fn read_only(&self) -> bool {
+        true
+    }
+
+    fn get_command(&self) -> clap::Command {
+        // Get command from request struct's Parser derive
+        SearchContentRequest::command()
+    }
+
+    async fn execute_json(&self, json: serde_json::Value) -> Result<serde_json::Value, Error> {
+        crate::http_router::execute_json_operation(json, |req| self.capability.search_content(req))
+            .await
+    }
+
+    async fn execute_from_args(
+        &self,
+        matches: &clap::ArgMatches,
+        _registry: &crate::capabilities::CapabilityRegistry,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        // Parse request from ArgMatches
+        let request = SearchContentRequest::from_arg_matches(matches)?;
+
+        // Handle CLI-specific path if present
+        let response = if let Some(ref path) = request.path {
+            let config = Arc::new(Config::load_from_base_path(path.as_path()));
+            let capability = SearchCapability::new(path.clone(), config);
+            let mut req_without_path = request;
+            req_without_path.path = None;
+            capability.search_content(req_without_path).await?
+        } else {
+            self.capability.search_content(request).await?
+        };
+
+        // Serialize to JSON
+        Ok(serde_json::to_string_pretty(&response)?)
+    }
+
+    fn input_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(SearchContentRequest)).unwrap()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(SearchContentResponse)).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn request(query: &str) -> SearchContentRequest {
+        SearchContentRequest {
+            path: None,
+            query: query.to_string(),
+            regex: None,
+            include_archived: None,
+            include_excerpt: None,
+            limit: None,
+            max_matches_per_file: None,
+            strict: None,
+        }
+    }
+
+    fn create_vault() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("release.md"),
+            "# Release notes\n\nThe release notes cover the release.\nSee the changelog.\n",
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("meeting.md"),
+            "# Meeting\n\nWe discussed notes about the upcoming release and lots of other things \
+             that make this note much longer than the others in the vault.\n",
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("changelog.md"),
+            "# Changelog\n\n- Fixed a bug\n",
+        )
+        .unwrap();
+        temp_dir
+    }
+
+    #[test]
+    fn test_parse_query() {
+        let query = parse_query("Release \"Release  Notes\" OR changelog AND bug").unwrap();
+        assert_eq!(
+            query.groups,
+            vec![
+                vec!["release".to_string(), "release notes".to_string()],
+                vec!["changelog".to_string(), "bug".to_string()],
+            ]
+        );
+        assert_eq!(
+            query.terms(),
+            vec!["release", "release notes", "changelog", "bug"]
+        );
+
+        // Lowercase operators are ordinary terms; empty groups are dropped
+        let query = parse_query("OR this or that OR").unwrap();
+        assert_eq!(
+            query.groups,
+            vec![vec![
+                "this".to_string(),
+                "or".to_string(),
+                "that".to_string()
+            ]]
+        );
+
+        assert!(parse_query("  \"\" OR ").is_none());
+    }
+
+    #[test]
+    fn test_spans_use_character_offsets() {
+        let term = Matcher::Text("café".chars().collect());
+        let hits = scan_content(Path::new("a.md"), "Le Café, café!", &[term]);
+        assert_eq!(hits.term_counts, vec![2]);
+        assert_eq!(
+            hits.lines[0].spans,
+            vec![
+                MatchSpan { start: 3, end: 7 },
+                MatchSpan { start: 9, end: 13 }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_regex_spans_use_character_offsets() {
+        let pattern = Matcher::Pattern(compile_pattern(r"caf\w|x*").unwrap());
+        let hits = scan_content(Path::new("a.md"), "Le Café, café!\nnone", &[pattern]);
+        assert_eq!(hits.term_counts, vec![2]);
+        assert_eq!(hits.lines.len(), 1);
+        assert_eq!(
+            hits.lines[0].spans,
+            vec![
+                MatchSpan { start: 3, end: 7 },
+                MatchSpan { start: 9, end: 13 }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge_spans() {
+        let merged = merge_spans(vec![
+            MatchSpan { start: 4, end: 9 },
+            MatchSpan { start: 0, end: 3 },
+            MatchSpan { start: 0, end: 7 },
+            MatchSpan { start: 12, end: 14 },
+        ]);
+        assert_eq!(
+            merged,
+            vec![
+                MatchSpan { start: 0, end: 9 },
+                MatchSpan { start: 12, end: 14 }
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_search_ranks_and_highlights() {
+        let temp_dir = create_vault();
+        let capability =
+            SearchCapability::new(temp_dir.path().to_path_buf(), Arc::new(Config::default()));
+
+        let response = capability
+            .search_content(request("release notes"))
+            .await
+            .unwrap();
+
+        assert_eq!(response.total_matches, 2);
+        assert_eq!(response.results[0].file_path, "release.md");
+        assert_eq!(response.results[1].file_path, "meeting.md");
+        assert!(response.results[0].score > response.results[1].score);
+
+        let heading = &response.results[0].matches[0];
+        assert_eq!(heading.line_number, 1);
+        assert_eq!(
+            heading.spans,
+            vec![
+                MatchSpan { start: 2, end: 9 },
+                MatchSpan { start: 10, end: 15 }
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_search_phrase_and_or() {
+        let temp_dir = create_vault();
+        let capability =
+            SearchCapability::new(temp_dir.path().to_path_buf(), Arc::new(Config::default()));
+
+        // The phrase only appears verbatim in release.md
+        let response = capability
+            .search_content(request("\"release notes\""))
+            .await
+            .unwrap();
+        let files: Vec<_> = response
+            .results
+            .iter()
+            .map(|r| r.file_path.as_str())
+            .collect();
+        assert_eq!(files, vec!["release.md"]);
+
+        let response = capability
+            .search_content(request("bug OR meeting"))
+            .await
+            .unwrap();
+        let mut files: Vec<_> = response
+            .results
+            .iter()
+            .map(|r| r.file_path.as_str())
+            .collect();
+        files.sort();
+        assert_eq!(files, vec!["changelog.md", "meeting.md"]);
+
+        assert!(capability.search_content(request("\"\"")).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_search_regex() {
+        let temp_dir = create_vault();
+        let capability =
+            SearchCapability::new(temp_dir.path().to_path_buf(), Arc::new(Config::default()));
+
+        let mut regex = request(r"^# (release|change)");
+        regex.regex = Some(true);
+        let response = capability.search_content(regex).await.unwrap();
+        let mut files: Vec<_> = response
+            .results
+            .iter()
+            .map(|r| r.file_path.as_str())
+            .collect();
+        files.sort();
+        assert_eq!(files, vec!["changelog.md", "release.md"]);
+        let release = response
+            .results
+            .iter()
+            .find(|r| r.file_path == "release.md")
+            .unwrap();
+        assert_eq!(
+            release.matches[0].spans,
+            vec![MatchSpan { start: 0, end: 9 }]
+        );
+
+        // Quotes and OR are part of the pattern, not query syntax
+        let mut regex = request("\"bug\" OR x");
+        regex.regex = Some(true);
+        let response = capability.search_content(regex).await.unwrap();
+        assert_eq!(response.total_matches, 0);
+
+        for pattern in ["(unclosed", ""] {
+            let mut invalid = request(pattern);
+            invalid.regex = Some(true);
+            let error = capability.search_content(invalid).await.unwrap_err();
+            assert_eq!(error.code(), "invalid_params");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_search_excerpt() {
+        let temp_dir = create_vault();
+        let capability =
+            SearchCapability::new(temp_dir.path().to_path_buf(), Arc::new(Config::default()));
+
+        let response = capability.search_content(request("bug")).await.unwrap();
+        assert!(response.results[0].excerpt.is_none());
+
+        let mut with_excerpt = request("changelog");
+        with_excerpt.include_excerpt = Some(true);
+        let response = capability.search_content(with_excerpt).await.unwrap();
+        let release = response
+            .results
+            .iter()
+            .find(|r| r.file_path == "release.md")
+            .unwrap();
+        assert_eq!(
+            release.excerpt.as_deref(),
+            Some("The release notes cover the release. See the changelog.")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_search_reports_unreadable_files() {
+        let temp_dir = create_vault();
+        fs::write(temp_dir.path().join("binary.md"), b"release \xff\xfe\n").unwrap();
+        let capability =
+            SearchCapability::new(temp_dir.path().to_path_buf(), Arc::new(Config::default()));
+
+        let response = capability.search_content(request("release")).await.unwrap();
+        assert_eq!(response.total_matches, 2);
+        assert_eq!(response.warnings.len(), 1);
+        assert!(response.warnings[0].file_path.ends_with("binary.md"));
+        assert_eq!(response.warnings[0].kind, WarningKind::Unreadable);
+
+        let mut strict = request("release");
+        strict.strict = Some(true);
+        let error = capability.search_content(strict).await.unwrap_err();
+        assert_eq!(error.code(), "parse_error");
+    }
+
+    #[tokio::test]
+    async fn test_search_skips_archived_notes() {
+        let temp_dir = create_vault();
+        let config = Arc::new(Config {
+            archived_paths: vec!["changelog.md".to_string()],
+            ..Default::default()
+        });
+        let capability = SearchCapability::new(temp_dir.path().to_path_buf(), config);
+
+        let response = capability.search_content(request("bug")).await.unwrap();
+        assert_eq!(response.total_matches, 0);
+
+        let mut archived = request("bug");
+        archived.include_archived = Some(true);
+        let response = capability.search_content(archived).await.unwrap();
+        assert_eq!(response.results[0].file_path, "changelog.md");
+    }
+}
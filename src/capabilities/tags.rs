@@ -1,16 +1,20 @@
-use crate::capabilities::CapabilityResult;
+use crate::capabilities::{CapabilityResult, check_strict};
 use crate::config::Config;
-use crate::error::internal_error;
-use crate::tag_extractor::{TagCount, TagExtractor, TaggedFile};
+use crate::error::{internal_error, invalid_params, scan_error};
+use crate::path_resolver::PathResolver;
+use crate::tag_extractor::{
+    FolderTags, TagCount, TagExtractor, TagListOptions, TagSuggestion, TagTreeNode, TaggedFile,
+};
+use crate::warnings::{ExtractionWarning, Warnings};
 use clap::{CommandFactory, FromArgMatches};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 /// Operation metadata for extract_tags
 pub mod extract_tags {
-    pub const DESCRIPTION: &str = "Extract all unique tags from YAML frontmatter in Markdown files";
+    pub const DESCRIPTION: &str = "Extract all unique tags from YAML frontmatter in Markdown files (optionally including inline #tags)";
     #[allow(dead_code)]
     pub const CLI_NAME: &str = "tags";
     pub const HTTP_PATH: &str = "/api/tags";
@@ -31,12 +35,28 @@ pub struct ExtractTagsRequest {
         description = "Subpath within the base directory to search (optional, defaults to base path)"
     )]
     pub subpath: Option<String>,
+
+    #[arg(long, help = "Also include inline #tags from note bodies")]
+    #[schemars(
+        description = "If true, also index inline #tags found in note bodies (outside code blocks) in addition to frontmatter tags. Default: false"
+    )]
+    pub include_inline: Option<bool>,
+
+    #[arg(long, help = "Fail if any file can't be read instead of skipping it")]
+    #[schemars(
+        description = "If true, fail when a file is skipped (unreadable, invalid frontmatter, larger than max_file_size, or cut off by a truncating scan limit) instead of reporting it in warnings. Default: false"
+    )]
+    pub strict: Option<bool>,
 }
 
 /// Response from the extract_tags operation
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct ExtractTagsResponse {
     pub tags: Vec<String>,
+    /// Files that were skipped (unreadable, invalid frontmatter, larger
+    /// than `max_file_size`, or cut off by a truncating scan limit)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ExtractionWarning>,
 }
 
 /// Operation metadata for list_tags
@@ -47,6 +67,17 @@ pub mod list_tags {
     pub const HTTP_PATH: &str = "/api/tags/list";
 }
 
+/// Sort order for list_tags results
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, JsonSchema, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum TagSortOrder {
+    /// Most common tags first, ties broken alphabetically
+    #[default]
+    Count,
+    /// Alphabetical by tag name
+    Name,
+}
+
 /// Parameters for the list_tags operation
 #[derive(Debug, Deserialize, Serialize, JsonSchema, clap::Parser)]
 #[command(name = "list-tags", about = "List all tags with document counts")]
@@ -70,6 +101,49 @@ pub struct ListTagsRequest {
     #[arg(long, help = "Maximum number of tags to return")]
     #[schemars(description = "Maximum number of tags to return (optional, defaults to all)")]
     pub limit: Option<usize>,
+
+    #[arg(
+        long,
+        help = "Nest parent/child tags into a tree with aggregated counts"
+    )]
+    #[schemars(
+        description = "If true, also return `tree`: tags like project/alpha/backend nested by path segment, with document counts aggregated at each level. min_count prunes tree nodes and limit applies to top-level nodes. Default: false"
+    )]
+    pub tree: Option<bool>,
+
+    #[arg(
+        long,
+        value_delimiter = ',',
+        help = "Subpaths to skip (e.g. Templates,Archive)"
+    )]
+    #[schemars(
+        description = "Subpaths within the vault to skip when counting tags, e.g. template or archive folders (optional)"
+    )]
+    pub exclude_subpaths: Option<Vec<String>>,
+
+    #[arg(long, value_delimiter = ',', help = "Tags to exclude from the results")]
+    #[schemars(
+        description = "Tags to exclude (case-insensitive). Nested tags under an excluded tag are excluded too (optional)"
+    )]
+    pub exclude_tags: Option<Vec<String>>,
+
+    #[arg(long, value_enum, help = "Sort by document count or tag name")]
+    #[schemars(
+        description = "Sort order: \"count\" (most common first) or \"name\" (alphabetical). Default: count"
+    )]
+    pub sort: Option<TagSortOrder>,
+
+    #[arg(long, help = "Also include inline #tags from note bodies")]
+    #[schemars(
+        description = "If true, also index inline #tags found in note bodies (outside code blocks) in addition to frontmatter tags. Default: false"
+    )]
+    pub include_inline: Option<bool>,
+
+    #[arg(long, help = "Fail if any file can't be read instead of skipping it")]
+    #[schemars(
+        description = "If true, fail when a file is skipped (unreadable, invalid frontmatter, larger than max_file_size, or cut off by a truncating scan limit) instead of reporting it in warnings. Default: false"
+    )]
+    pub strict: Option<bool>,
 }
 
 /// Response from the list_tags operation
@@ -77,15 +151,22 @@ pub struct ListTagsRequest {
 pub struct ListTagsResponse {
     /// List of tags with their document counts
     pub tags: Vec<TagCount>,
+    /// Hierarchical tag tree (only present when `tree` is requested)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tree: Option<Vec<TagTreeNode>>,
     /// Total number of unique tags found (before filtering/limiting)
     pub total_unique_tags: usize,
     /// Whether the results were truncated due to limit parameter
     pub truncated: bool,
+    /// Files that were skipped (unreadable, invalid frontmatter, larger
+    /// than `max_file_size`, or cut off by a truncating scan limit)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ExtractionWarning>,
 }
 
 /// Operation metadata for search_by_tags
 pub mod search_by_tags {
-    pub const DESCRIPTION: &str = "Search for files by YAML frontmatter tags with AND/OR matching. Returns files that match the specified tags.";
+    pub const DESCRIPTION: &str = "Search for files by YAML frontmatter tags with AND/OR matching. Supports glob patterns (project/*) and tag prefixes. Returns files that match the specified tags.";
     #[allow(dead_code)]
     pub const CLI_NAME: &str = "search-tags";
     pub const HTTP_PATH: &str = "/api/tags/search";
@@ -104,10 +185,23 @@ pub struct SearchByTagsRequest {
     #[schemars(skip)]
     pub path: Option<PathBuf>,
 
-    #[arg(long, value_delimiter = ',', help = "Tags to search for")]
-    #[schemars(description = "Tags to search for")]
+    #[arg(
+        long,
+        value_delimiter = ',',
+        help = "Tags or tag glob patterns to search for"
+    )]
+    #[serde(default)]
+    #[schemars(
+        description = "Tags to search for. Glob patterns are supported (e.g. \"project/*\" matches project/alpha and project/alpha/backend)"
+    )]
     pub tags: Vec<String>,
 
+    #[arg(long, help = "Match tags starting with this prefix (e.g. project/)")]
+    #[schemars(
+        description = "Match any tag starting with this prefix (e.g. \"project/\"). Combined with tags using the same AND/OR logic"
+    )]
+    pub tag_prefix: Option<String>,
+
     #[arg(
         long,
         help = "File must have ALL tags (AND logic). Default: false (OR logic)"
@@ -121,9 +215,33 @@ pub struct SearchByTagsRequest {
     #[schemars(description = "Subpath within the base directory to search (optional)")]
     pub subpath: Option<String>,
 
+    #[arg(long, help = "Include notes under archived paths")]
+    #[schemars(
+        description = "If true, include notes under the configured archived_paths. Default: false"
+    )]
+    pub include_archived: Option<bool>,
+
     #[arg(long, help = "Limit the number of files returned")]
     #[schemars(description = "Limit the number of files returned")]
     pub limit: Option<usize>,
+
+    #[arg(long, help = "Also include inline #tags from note bodies")]
+    #[schemars(
+        description = "If true, also index inline #tags found in note bodies (outside code blocks) in addition to frontmatter tags. Default: false"
+    )]
+    pub include_inline: Option<bool>,
+
+    #[arg(long, help = "Include a short excerpt of each note")]
+    #[schemars(
+        description = "If true, each file includes an excerpt of up to 200 characters: the text around the first inline occurrence of a matched tag, or else the note's first paragraph after the frontmatter. Default: false"
+    )]
+    pub include_excerpt: Option<bool>,
+
+    #[arg(long, help = "Fail if any file can't be read instead of skipping it")]
+    #[schemars(
+        description = "If true, fail when a file is skipped (unreadable, invalid frontmatter, larger than max_file_size, or cut off by a truncating scan limit) instead of reporting it in warnings. Default: false"
+    )]
+    pub strict: Option<bool>,
 }
 
 /// Response from the search_by_tags operation
@@ -131,11 +249,137 @@ pub struct SearchByTagsRequest {
 pub struct SearchByTagsResponse {
     pub files: Vec<TaggedFile>,
     pub total_count: usize,
+    /// Files that were skipped (unreadable, invalid frontmatter, larger
+    /// than `max_file_size`, or cut off by a truncating scan limit)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ExtractionWarning>,
+}
+
+/// Operation metadata for suggest_tags
+pub mod suggest_tags {
+    pub const DESCRIPTION: &str = "Suggest existing vault tags for a note or raw text based on term overlap with notes already carrying those tags. Helps keep the tag taxonomy consistent when filing new notes.";
+    #[allow(dead_code)]
+    pub const CLI_NAME: &str = "suggest-tags";
+    pub const HTTP_PATH: &str = "/api/tags/suggest";
+}
+
+/// Parameters for the suggest_tags operation
+#[derive(Debug, Deserialize, Serialize, JsonSchema, clap::Parser)]
+#[command(
+    name = "suggest-tags",
+    about = "Suggest existing tags for a note or text"
+)]
+pub struct SuggestTagsRequest {
+    /// Path to scan (CLI only - not used in HTTP/MCP)
+    #[arg(index = 1, required = true, help = "Path to file or folder to scan")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub path: Option<PathBuf>,
+
+    #[arg(long, help = "Note to suggest tags for (relative to the vault root)")]
+    #[schemars(
+        description = "Path to the note to suggest tags for, relative to the vault root. Either file_path or text is required"
+    )]
+    pub file_path: Option<String>,
+
+    #[arg(long, help = "Raw text to suggest tags for")]
+    #[schemars(
+        description = "Raw note text to suggest tags for. Either file_path or text is required"
+    )]
+    pub text: Option<String>,
+
+    #[arg(long, help = "Maximum number of suggestions to return")]
+    #[schemars(description = "Maximum number of suggestions to return (optional, defaults to 10)")]
+    pub limit: Option<usize>,
+
+    #[arg(long, help = "Fail if any file can't be read instead of skipping it")]
+    #[schemars(
+        description = "If true, fail when a file is skipped (unreadable, invalid frontmatter, larger than max_file_size, or cut off by a truncating scan limit) instead of reporting it in warnings. Default: false"
+    )]
+    pub strict: Option<bool>,
+}
+
+/// Response from the suggest_tags operation
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SuggestTagsResponse {
+    /// Suggested tags, most relevant first
+    pub suggestions: Vec<TagSuggestion>,
+    /// Files that were skipped (unreadable, invalid frontmatter, larger
+    /// than `max_file_size`, or cut off by a truncating scan limit)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ExtractionWarning>,
+}
+
+/// Operation metadata for tags_by_folder
+pub mod tags_by_folder {
+    pub const DESCRIPTION: &str = "Report the most common tags for each folder in the vault (top-level by default, or deeper with depth). Shows how different areas of the vault are organized.";
+    #[allow(dead_code)]
+    pub const CLI_NAME: &str = "tags-by-folder";
+    pub const HTTP_PATH: &str = "/api/tags/by-folder";
+}
+
+/// Parameters for the tags_by_folder operation
+#[derive(Debug, Deserialize, Serialize, JsonSchema, clap::Parser)]
+#[command(
+    name = "tags-by-folder",
+    about = "Show the most common tags per folder"
+)]
+pub struct TagsByFolderRequest {
+    /// Path to scan (CLI only - not used in HTTP/MCP)
+    #[arg(index = 1, required = true, help = "Path to file or folder to scan")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub path: Option<PathBuf>,
+
+    #[arg(long, help = "Subpath within the vault to analyze")]
+    #[schemars(
+        description = "Subpath within the vault to analyze (optional, defaults to entire vault)"
+    )]
+    pub subpath: Option<String>,
+
+    #[arg(long, help = "Folder depth to group by (default: 1)")]
+    #[schemars(
+        description = "Folder depth to group by. 1 groups by top-level folder, 2 by second-level folder, etc. (optional, defaults to 1)"
+    )]
+    pub depth: Option<usize>,
+
+    #[arg(
+        long,
+        help = "Maximum number of tags to return per folder (default: 10)"
+    )]
+    #[schemars(
+        description = "Maximum number of tags to return per folder (optional, defaults to 10)"
+    )]
+    pub limit_per_folder: Option<usize>,
+
+    #[arg(long, help = "Also include inline #tags from note bodies")]
+    #[schemars(
+        description = "If true, also count inline #tags found in note bodies (outside code blocks). Default: false"
+    )]
+    pub include_inline: Option<bool>,
+
+    #[arg(long, help = "Fail if any file can't be read instead of skipping it")]
+    #[schemars(
+        description = "If true, fail when a file is skipped (unreadable, invalid frontmatter, larger than max_file_size, or cut off by a truncating scan limit) instead of reporting it in warnings. Default: false"
+    )]
+    pub strict: Option<bool>,
+}
+
+/// Response from the tags_by_folder operation
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TagsByFolderResponse {
+    /// Per-folder tag statistics, sorted by folder path
+    pub folders: Vec<FolderTags>,
+    /// Files that were skipped (unreadable, invalid frontmatter, larger
+    /// than `max_file_size`, or cut off by a truncating scan limit)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ExtractionWarning>,
 }
 
 /// Capability for tag operations (extract, list, search)
 pub struct TagCapability {
     base_path: PathBuf,
+    config: Arc<Config>,
     tag_extractor: Arc<TagExtractor>,
 }
 
@@ -144,7 +388,8 @@ impl TagCapability {
     pub fn new(base_path: PathBuf, config: Arc<Config>) -> Self {
         Self {
             base_path,
-            tag_extractor: Arc::new(TagExtractor::new(config)),
+            tag_extractor: Arc::new(TagExtractor::new(Arc::clone(&config))),
+            config,
         }
     }
 
@@ -161,12 +406,13 @@ impl TagCapability {
         };
 
         // Extract tags from the search path
-        let tags = self
+        let (tags, warnings) = self
             .tag_extractor
-            .extract_tags(&search_path)
-            .map_err(|e| internal_error(format!("Failed to extract tags: {}", e)))?;
+            .extract_tags_with_warnings(&search_path, request.include_inline.unwrap_or(false))
+            .map_err(|e| scan_error("Failed to extract tags", e))?;
+        check_strict(&warnings, request.strict)?;
 
-        Ok(ExtractTagsResponse { tags })
+        Ok(ExtractTagsResponse { tags, warnings })
     }
 
     /// List all tags with document counts
@@ -178,11 +424,27 @@ impl TagCapability {
             self.base_path.clone()
         };
 
+        let options = TagListOptions {
+            include_inline: request.include_inline.unwrap_or(false),
+            exclude_subpaths: request
+                .exclude_subpaths
+                .unwrap_or_default()
+                .iter()
+                .map(|p| search_path.join(p))
+                .collect(),
+            exclude_tags: request.exclude_tags.unwrap_or_default(),
+        };
+
         // Extract tags with counts
+        let warnings = Warnings::default();
         let mut tags = self
             .tag_extractor
-            .extract_tags_with_counts(&search_path)
-            .map_err(|e| internal_error(format!("Failed to extract tags: {}", e)))?;
+            .extract_tags_with_counts(&search_path, &options, &warnings)
+            .map_err(|e| scan_error("Failed to extract tags", e))?;
+
+        if let Some(TagSortOrder::Name) = request.sort {
+            tags.sort_by(|a, b| a.tag.cmp(&b.tag));
+        }
 
         // Track total before filtering
         let total_unique_tags = tags.len();
@@ -204,10 +466,117 @@ impl TagCapability {
             false
         };
 
+        // Build the hierarchical view if requested
+        let tree = if request.tree.unwrap_or(false) {
+            let mut tree = self
+                .tag_extractor
+                .extract_tag_tree(&search_path, &options, &warnings)
+                .map_err(|e| scan_error("Failed to build tag tree", e))?;
+            if let Some(TagSortOrder::Name) = request.sort {
+                sort_tag_tree_by_name(&mut tree);
+            }
+            if let Some(min_count) = request.min_count {
+                prune_tag_tree(&mut tree, min_count);
+            }
+            if let Some(limit) = request.limit {
+                tree.truncate(limit);
+            }
+            Some(tree)
+        } else {
+            None
+        };
+        let warnings = warnings.into_vec();
+        check_strict(&warnings, request.strict)?;
+
         Ok(ListTagsResponse {
             tags,
+            tree,
             total_unique_tags,
             truncated,
+            warnings,
+        })
+    }
+
+    /// Report the most common tags per folder
+    pub async fn tags_by_folder(
+        &self,
+        request: TagsByFolderRequest,
+    ) -> CapabilityResult<TagsByFolderResponse> {
+        let search_path = if let Some(ref subpath) = request.subpath {
+            self.base_path.join(subpath)
+        } else {
+            self.base_path.clone()
+        };
+
+        let depth = request.depth.unwrap_or(1);
+        if depth == 0 {
+            return Err(invalid_params("depth must be at least 1"));
+        }
+        let limit_per_folder = request.limit_per_folder.unwrap_or(10);
+        let options = TagListOptions {
+            include_inline: request.include_inline.unwrap_or(false),
+            ..Default::default()
+        };
+
+        let warnings = Warnings::default();
+        let mut folders = self
+            .tag_extractor
+            .extract_tags_by_folder(&search_path, depth, &options, &warnings)
+            .map_err(|e| scan_error("Failed to extract tags", e))?;
+        let warnings = warnings.into_vec();
+        check_strict(&warnings, request.strict)?;
+
+        for folder in &mut folders {
+            folder.tags.truncate(limit_per_folder);
+        }
+
+        Ok(TagsByFolderResponse { folders, warnings })
+    }
+
+    /// Validate and resolve a file path within the vault
+    fn resolve_file_path(&self, file_path: &str) -> CapabilityResult<PathBuf> {
+        PathResolver::new(&self.base_path)?.resolve_note(file_path)
+    }
+
+    /// Suggest existing tags for a note or raw text
+    pub async fn suggest_tags(
+        &self,
+        request: SuggestTagsRequest,
+    ) -> CapabilityResult<SuggestTagsResponse> {
+        let limit = request.limit.unwrap_or(10);
+
+        let (text, exclude_file) = match (request.file_path, request.text) {
+            (Some(file_path), None) => {
+                let resolved = self.resolve_file_path(&file_path)?;
+                let content = std::fs::read_to_string(&resolved)
+                    .map_err(|e| internal_error(format!("Failed to read file: {}", e)))?;
+                (content, Some(resolved))
+            }
+            (None, Some(text)) => (text, None),
+            _ => {
+                return Err(invalid_params(
+                    "Exactly one of file_path or text must be provided",
+                ));
+            }
+        };
+
+        let warnings = Warnings::default();
+        let suggestions = self
+            .tag_extractor
+            .suggest_tags(
+                &self.base_path,
+                &text,
+                exclude_file.as_deref(),
+                limit,
+                &warnings,
+            )
+            .map_err(|e| scan_error("Failed to suggest tags", e))?;
+        let warnings = warnings.into_vec();
+        check_strict(&warnings, request.strict)?;
+
+        Ok(SuggestTagsResponse {
+            suggestions,
+            warnings,
         })
     }
 
@@ -224,12 +593,39 @@ impl TagCapability {
         };
 
         let match_all = request.match_all.unwrap_or(false);
+        let include_inline = request.include_inline.unwrap_or(false);
+
+        // A tag prefix is matched as an escaped glob pattern
+        let mut search_tags = request.tags;
+        if let Some(prefix) = request.tag_prefix {
+            search_tags.push(format!("{}*", glob::Pattern::escape(&prefix)));
+        }
+        if search_tags.is_empty() {
+            return Err(invalid_params("Either tags or tag_prefix must be provided"));
+        }
 
         // Search for files by tags
+        let warnings = Warnings::default();
         let mut files = self
             .tag_extractor
-            .search_by_tags(&search_path, &request.tags, match_all)
-            .map_err(|e| internal_error(format!("Failed to search by tags: {}", e)))?;
+            .search_by_tags(
+                &search_path,
+                &search_tags,
+                match_all,
+                include_inline,
+                &warnings,
+            )
+            .map_err(|e| scan_error("Failed to search by tags", e))?;
+        let warnings = warnings.into_vec();
+        check_strict(&warnings, request.strict)?;
+        if !request.include_archived.unwrap_or(false) {
+            files.retain(|file| !self.config.is_archived(Path::new(&file.file_path)));
+        }
+        if !request.include_excerpt.unwrap_or(false) {
+            for file in &mut files {
+                file.excerpt = None;
+            }
+        }
 
         let total_count = files.len();
 
@@ -238,7 +634,49 @@ impl TagCapability {
             files.truncate(limit);
         }
 
-        Ok(SearchByTagsResponse { files, total_count })
+        Ok(SearchByTagsResponse {
+            files,
+            total_count,
+            warnings,
+        })
+    }
+}
+
+/// Operation struct for suggest_tags (HTTP, CLI, and MCP)
+pub struct SuggestTagsOperation {
+    capability: Arc<TagCapability>,
+}
+
+impl SuggestTagsOperation {
+    pub fn new(capability: Arc<TagCapability>) -> Self {
+        Self { capability }
+    }
+}
+
+/// Operation struct for tags_by_folder (HTTP, CLI, and MCP)
+pub struct TagsByFolderOperation {
+    capability: Arc<TagCapability>,
+}
+
+impl TagsByFolderOperation {
+    pub fn new(capability: Arc<TagCapability>) -> Self {
+        Self { capability }
+    }
+}
+
+/// Sort tree nodes alphabetically at every level
+fn sort_tag_tree_by_name(nodes: &mut [TagTreeNode]) {
+    nodes.sort_by(|a, b| a.name.cmp(&b.name));
+    for node in nodes {
+        sort_tag_tree_by_name(&mut node.children);
+    }
+}
+
+/// Remove tree nodes whose aggregated document count is below `min_count`
+fn prune_tag_tree(nodes: &mut Vec<TagTreeNode>, min_count: usize) {
+    nodes.retain(|node| node.document_count >= min_count);
+    for node in nodes {
+        prune_tag_tree(&mut node.children, min_count);
     }
 }
 
@@ -289,6 +727,10 @@ impl crate::operation::Operation for ExtractTagsOperation {
         extract_tags::DESCRIPTION
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     fn get_command(&self) -> clap::Command {
         // Get command from request struct's Parser derive
         ExtractTagsRequest::command()
@@ -297,7 +739,7 @@ impl crate::operation::Operation for ExtractTagsOperation {
     async fn execute_json(
         &self,
         json: serde_json::Value,
-    ) -> Result<serde_json::Value, rmcp::model::ErrorData> {
+    ) -> Result<serde_json::Value, crate::error::Error> {
         crate::http_router::execute_json_operation(json, |req| self.capability.extract_tags(req))
             .await
     }
@@ -329,6 +771,11 @@ impl crate::operation::Operation for ExtractTagsOperation {
         use schemars::schema_for;
         serde_json::to_value(schema_for!(ExtractTagsRequest)).unwrap()
     }
+
+    fn output_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(ExtractTagsResponse)).unwrap()
+    }
 }
 
 #[async_trait::async_trait]
@@ -345,6 +792,10 @@ impl crate::operation::Operation for ListTagsOperation {
         list_tags::DESCRIPTION
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     fn get_command(&self) -> clap::Command {
         // Get command from request struct's Parser derive
         ListTagsRequest::command()
@@ -353,7 +804,7 @@ impl crate::operation::Operation for ListTagsOperation {
     async fn execute_json(
         &self,
         json: serde_json::Value,
-    ) -> Result<serde_json::Value, rmcp::model::ErrorData> {
+    ) -> Result<serde_json::Value, crate::error::Error> {
         crate::http_router::execute_json_operation(json, |req| self.capability.list_tags(req)).await
     }
 
@@ -384,6 +835,11 @@ impl crate::operation::Operation for ListTagsOperation {
         use schemars::schema_for;
         serde_json::to_value(schema_for!(ListTagsRequest)).unwrap()
     }
+
+    fn output_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(ListTagsResponse)).unwrap()
+    }
 }
 
 #[async_trait::async_trait]
@@ -400,6 +856,10 @@ impl crate::operation::Operation for SearchByTagsOperation {
         search_by_tags::DESCRIPTION
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     fn get_command(&self) -> clap::Command {
         // Get command from request struct's Parser derive
         SearchByTagsRequest::command()
@@ -408,7 +868,7 @@ impl crate::operation::Operation for SearchByTagsOperation {
     async fn execute_json(
         &self,
         json: serde_json::Value,
-    ) -> Result<serde_json::Value, rmcp::model::ErrorData> {
+    ) -> Result<serde_json::Value, crate::error::Error> {
         crate::http_router::execute_json_operation(json, |req| self.capability.search_by_tags(req))
             .await
     }
@@ -440,4 +900,139 @@ impl crate::operation::Operation for SearchByTagsOperation {
         use schemars::schema_for;
         serde_json::to_value(schema_for!(SearchByTagsRequest)).unwrap()
     }
+
+    fn output_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(SearchByTagsResponse)).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::operation::Operation for SuggestTagsOperation {
+    fn name(&self) -> &'static str {
+        suggest_tags::CLI_NAME
+    }
+
+    fn path(&self) -> &'static str {
+        suggest_tags::HTTP_PATH
+    }
+
+    fn description(&self) -> &'static str {
+        suggest_tags::DESCRIPTION
+    }
+
+    fn read_only(&self) -> bool {
+        true
+    }
+
+    fn get_command(&self) -> clap::Command {
+        // Get command from request struct's Parser derive
+        SuggestTagsRequest::command()
+    }
+
+    async fn execute_json(
+        &self,
+        json: serde_json::Value,
+    ) -> Result<serde_json::Value, crate::error::Error> {
+        crate::http_router::execute_json_operation(json, |req| self.capability.suggest_tags(req))
+            .await
+    }
+
+    async fn execute_from_args(
+        &self,
+        matches: &clap::ArgMatches,
+        _registry: &crate::capabilities::CapabilityRegistry,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        // Parse request from ArgMatches
+        let request = SuggestTagsRequest::from_arg_matches(matches)?;
+
+        // Handle CLI-specific path if present
+        let response = if let Some(ref path) = request.path {
+            let config = Arc::new(Config::load_from_base_path(path.as_path()));
+            let capability = TagCapability::new(path.clone(), config);
+            let mut req_without_path = request;
+            req_without_path.path = None;
+            capability.suggest_tags(req_without_path).await?
+        } else {
+            self.capability.suggest_tags(request).await?
+        };
+
+        // Serialize to JSON
+        Ok(serde_json::to_string_pretty(&response)?)
+    }
+
+    fn input_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(SuggestTagsRequest)).unwrap()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(SuggestTagsResponse)).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::operation::Operation for TagsByFolderOperation {
+    fn name(&self) -> &'static str {
+        tags_by_folder::CLI_NAME
+    }
+
+    fn path(&self) -> &'static str {
+        tags_by_folder::HTTP_PATH
+    }
+
+    fn description(&self) -> &'static str {
+        tags_by_folder::DESCRIPTION
+    }
+
+    fn read_only(&self) -> bool {
+        true
+    }
+
+    fn get_command(&self) -> clap::Command {
+        // Get command from request struct's Parser derive
+        TagsByFolderRequest::command()
+    }
+
+    async fn execute_json(
+        &self,
+        json: serde_json::Value,
+    ) -> Result<serde_json::Value, crate::error::Error> {
+        crate::http_router::execute_json_operation(json, |req| self.capability.tags_by_folder(req))
+            .await
+    }
+
+    async fn execute_from_args(
+        &self,
+        matches: &clap::ArgMatches,
+        _registry: &crate::capabilities::CapabilityRegistry,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        // Parse request from ArgMatches
+        let request = TagsByFolderRequest::from_arg_matches(matches)?;
+
+        // Handle CLI-specific path if present
+        let response = if let Some(ref path) = request.path {
+            let config = Arc::new(Config::load_from_base_path(path.as_path()));
+            let capability = TagCapability::new(path.clone(), config);
+            let mut req_without_path = request;
+            req_without_path.path = None;
+            capability.tags_by_folder(req_without_path).await?
+        } else {
+            self.capability.tags_by_folder(request).await?
+        };
+
+        // Serialize to JSON
+        Ok(serde_json::to_string_pretty(&response)?)
+    }
+
+    fn input_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(TagsByFolderRequest)).unwrap()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(TagsByFolderResponse)).unwrap()
+    }
 }
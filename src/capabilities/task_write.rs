@@ -0,0 +1,990 @@
+//! Task write capability
+//!
+//! Adds tasks to notes, edits task lines in place, and moves tasks between
+//! notes. Every write is checked against the note as it is on disk: the path
+//! must stay inside the vault, and a line being edited must still hold the
+//! expected checkbox when the file is written.
+
+use crate::capabilities::CapabilityResult;
+use crate::completion_dates::stamp_before_write;
+use crate::config::Config;
+use crate::error::{Error, conflict, internal_error, invalid_params, not_found};
+use crate::export::{LineEdit, apply_line_edits, mark_line_completed};
+use crate::outline_extractor::OutlineExtractor;
+use crate::path_resolver::{PathResolver, to_slash};
+use chrono::{Local, NaiveDate};
+use clap::{CommandFactory, FromArgMatches, Parser};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Operation metadata for complete_task
+pub mod complete_task {
+    pub const DESCRIPTION: &str = "Mark a task as done: flips '- [ ]' to '- [x]' on the given line of a note and appends a '✅ YYYY-MM-DD' completion date. Takes the file_path and line_number reported by search_tasks. Fails if the line is not an incomplete task.";
+    #[allow(dead_code)]
+    pub const CLI_NAME: &str = "complete-task";
+    pub const HTTP_PATH: &str = "/api/tasks/complete";
+}
+
+/// Operation metadata for add_task
+pub mod add_task {
+    pub const DESCRIPTION: &str = "Add a new '- [ ]' task to a note, appended at the end of the file or at the end of the section under a named heading. Due date and tags are written in Obsidian Tasks syntax ('#tag', '📅 YYYY-MM-DD'). Returns the line number of the new task.";
+    #[allow(dead_code)]
+    pub const CLI_NAME: &str = "add-task";
+    pub const HTTP_PATH: &str = "/api/tasks/add";
+}
+
+/// Operation metadata for move_task
+pub mod move_task {
+    pub const DESCRIPTION: &str = "Move a task and its indented sub-items from one note to another (or to another section of the same note), appended at the end of the target note or of a named heading's section. Takes the file_path and line_number reported by search_tasks. Optionally leaves a '> moved to [[Target]]' breadcrumb where the task was.";
+    #[allow(dead_code)]
+    pub const CLI_NAME: &str = "move-task";
+    pub const HTTP_PATH: &str = "/api/tasks/move";
+}
+
+/// Parameters for the add_task operation
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Parser)]
+#[command(name = "add-task", about = "Add a task to a note")]
+pub struct AddTaskRequest {
+    /// Path to vault (CLI only - not used in HTTP/MCP)
+    #[arg(index = 1, required = true, help = "Path to vault")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub path: Option<PathBuf>,
+
+    #[arg(index = 2, required = true, help = "Note path relative to vault root")]
+    #[schemars(description = "Vault-relative path of an existing note to add the task to")]
+    pub file_path: String,
+
+    #[arg(index = 3, required = true, help = "Task description")]
+    #[schemars(description = "Task description, on a single line")]
+    pub content: String,
+
+    #[arg(long, help = "Add the task under this heading instead of at the end")]
+    #[schemars(
+        description = "Title of the heading to add the task under (case-insensitive, without #). The task goes after the last line of that heading's own section, before any subheading. Default: end of the note"
+    )]
+    pub heading: Option<String>,
+
+    #[arg(long, help = "Due date (YYYY-MM-DD)")]
+    #[schemars(description = "Due date (YYYY-MM-DD), written as '📅 YYYY-MM-DD'")]
+    pub due: Option<NaiveDate>,
+
+    #[arg(long, value_delimiter = ',', help = "Tags to add (comma-separated)")]
+    #[schemars(description = "Tags to add, with or without a leading #")]
+    pub tags: Option<Vec<String>>,
+}
+
+/// Response from the add_task operation
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AddTaskResponse {
+    pub file_path: String,
+    /// 1-based line of the new task
+    pub line_number: usize,
+    /// The line as written
+    pub line: String,
+}
+
+/// Parameters for the complete_task operation
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Parser)]
+#[command(name = "complete-task", about = "Mark a task as completed")]
+pub struct CompleteTaskRequest {
+    /// Path to vault (CLI only - not used in HTTP/MCP)
+    #[arg(index = 1, required = true, help = "Path to vault")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub path: Option<PathBuf>,
+
+    #[arg(index = 2, required = true, help = "Note path relative to vault root")]
+    #[schemars(description = "Vault-relative path of the note containing the task")]
+    pub file_path: String,
+
+    #[arg(index = 3, required = true, help = "1-based line number of the task")]
+    #[schemars(description = "1-based line number of the task, as returned by search_tasks")]
+    pub line_number: usize,
+
+    #[arg(long, help = "Completion date (YYYY-MM-DD, default: today)")]
+    #[schemars(description = "Completion date (YYYY-MM-DD). Default: today")]
+    pub date: Option<NaiveDate>,
+}
+
+/// Response from the complete_task operation
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct CompleteTaskResponse {
+    pub file_path: String,
+    pub line_number: usize,
+    /// The line before the edit
+    pub previous_line: String,
+    /// The line as written
+    pub line: String,
+    pub completed_date: NaiveDate,
+}
+
+/// Parameters for the move_task operation
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Parser)]
+#[command(name = "move-task", about = "Move a task to another note or section")]
+pub struct MoveTaskRequest {
+    /// Path to vault (CLI only - not used in HTTP/MCP)
+    #[arg(index = 1, required = true, help = "Path to vault")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub path: Option<PathBuf>,
+
+    #[arg(index = 2, required = true, help = "Note path relative to vault root")]
+    #[schemars(description = "Vault-relative path of the note containing the task")]
+    pub file_path: String,
+
+    #[arg(index = 3, required = true, help = "1-based line number of the task")]
+    #[schemars(description = "1-based line number of the task, as returned by search_tasks")]
+    pub line_number: usize,
+
+    #[arg(
+        index = 4,
+        required = true,
+        help = "Target note path relative to vault root"
+    )]
+    #[schemars(
+        description = "Vault-relative path of an existing note to move the task to. May be the same note when heading is given"
+    )]
+    pub target: String,
+
+    #[arg(long, help = "Move the task under this heading of the target note")]
+    #[schemars(
+        description = "Title of the heading in the target note to move the task under (case-insensitive, without #). Default: end of the note"
+    )]
+    pub heading: Option<String>,
+
+    #[arg(long, help = "Leave a '> moved to [[Target]]' line where the task was")]
+    #[schemars(
+        description = "If true, replace the task in the source note with a '> moved to [[Target]]' line. Default: false"
+    )]
+    pub breadcrumb: Option<bool>,
+}
+
+/// Response from the move_task operation
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct MoveTaskResponse {
+    pub file_path: String,
+    pub target: String,
+    /// 1-based line of the task in the target note
+    pub line_number: usize,
+    /// The task and its sub-items as written to the target
+    pub lines: Vec<String>,
+    /// The line left in the source note, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub breadcrumb: Option<String>,
+}
+
+/// A checklist item in the format the task extractor reads
+pub(crate) fn task_line(content: &str, tags: &[String], due: Option<NaiveDate>) -> String {
+    // Multi-line text becomes one line
+    let mut line = format!(
+        "- [ ] {}",
+        content.split_whitespace().collect::<Vec<_>>().join(" ")
+    );
+    for tag in tags {
+        line.push_str(" #");
+        line.push_str(tag);
+    }
+    if let Some(due) = due {
+        line.push_str(&format!(" 📅 {}", due.format("%Y-%m-%d")));
+    }
+    line
+}
+
+/// The checkbox character of a task line (`' '` for `- [ ] ...`)
+fn checkbox(line: &str) -> Option<char> {
+    let rest = line.trim_start().strip_prefix('-')?.trim_start();
+    let mut chars = rest.strip_prefix('[')?.chars();
+    let state = chars.next()?;
+    (chars.next()? == ']').then_some(state)
+}
+
+/// Where to insert lines in a note: after the last non-blank line of a
+/// heading's own section (before any subheading), or at the end of the note
+///
+/// Returns a 0-based index into `lines`, the lines of `content`.
+fn insertion_point(
+    content: &str,
+    lines: &[&str],
+    heading: Option<&str>,
+    file_path: &str,
+) -> CapabilityResult<usize> {
+    let Some(heading) = heading else {
+        return Ok(lines.len());
+    };
+
+    let title = heading.trim().trim_start_matches('#').trim();
+    let section = OutlineExtractor::new()
+        .get_section_from_content(content, title, false)
+        .into_iter()
+        .next()
+        .ok_or_else(|| not_found(format!("Heading '{}' not found in {}", title, file_path)))?;
+    // After the last non-blank line of the section, or right under the heading
+    Ok((section.start_line..section.end_line)
+        .rev()
+        .find(|&i| !lines[i].trim().is_empty())
+        .map_or(section.start_line, |i| i + 1))
+}
+
+/// The line ending a note uses
+fn line_ending(content: &str) -> &'static str {
+    if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    }
+}
+
+/// Write `lines` back to a note, ending it with a newline
+fn write_lines(path: &Path, lines: &[&str], newline: &str) -> CapabilityResult<()> {
+    let mut updated = lines.join(newline);
+    updated.push_str(newline);
+    fs::write(path, updated).map_err(|e| internal_error(format!("Failed to write note: {}", e)))
+}
+
+/// Capability for editing tasks
+pub struct TaskWriteCapability {
+    base_path: PathBuf,
+    config: Arc<Config>,
+}
+
+impl TaskWriteCapability {
+    /// Create a new TaskWriteCapability
+    pub fn new(base_path: PathBuf, config: Arc<Config>) -> Self {
+        Self { base_path, config }
+    }
+
+    /// Insert a new task at the end of the note or of a heading's section
+    pub async fn add_task(&self, request: AddTaskRequest) -> CapabilityResult<AddTaskResponse> {
+        if request.content.trim().is_empty() {
+            return Err(invalid_params("Task content must not be empty"));
+        }
+        if request.content.contains(['\n', '\r']) {
+            return Err(invalid_params("Task content must be a single line"));
+        }
+        let tags: Vec<String> = request
+            .tags
+            .iter()
+            .flatten()
+            .map(|tag| tag.trim().trim_start_matches('#').to_string())
+            .filter(|tag| !tag.is_empty())
+            .collect();
+        if let Some(tag) = tags.iter().find(|tag| tag.contains(char::is_whitespace)) {
+            return Err(invalid_params(format!("Invalid tag '{}'", tag)));
+        }
+
+        let resolver = PathResolver::new(&self.base_path)?;
+        let path = resolver.resolve_note(&request.file_path)?;
+        let file_path = to_slash(resolver.relative(&path).unwrap_or(&path));
+        stamp_before_write(&path, &self.config)?;
+        let content = fs::read_to_string(&path)
+            .map_err(|e| internal_error(format!("Failed to read note: {}", e)))?;
+
+        let mut lines: Vec<&str> = content.lines().collect();
+        let index = insertion_point(&content, &lines, request.heading.as_deref(), &file_path)?;
+
+        let line = task_line(&request.content, &tags, request.due);
+        lines.insert(index, &line);
+        write_lines(&path, &lines, line_ending(&content))?;
+
+        Ok(AddTaskResponse {
+            file_path,
+            line_number: index + 1,
+            line,
+        })
+    }
+
+    /// Check off the task on `line_number` and stamp it with the completion date
+    pub async fn complete_task(
+        &self,
+        request: CompleteTaskRequest,
+    ) -> CapabilityResult<CompleteTaskResponse> {
+        let resolver = PathResolver::new(&self.base_path)?;
+        let path = resolver.resolve_note(&request.file_path)?;
+        let file_path = to_slash(resolver.relative(&path).unwrap_or(&path));
+        stamp_before_write(&path, &self.config)?;
+        let content = fs::read_to_string(&path)
+            .map_err(|e| internal_error(format!("Failed to read note: {}", e)))?;
+
+        let line_number = request.line_number;
+        let previous_line = line_number
+            .checked_sub(1)
+            .and_then(|i| content.lines().nth(i))
+            .ok_or_else(|| {
+                invalid_params(format!(
+                    "Line {} is out of range: {} has {} lines",
+                    line_number,
+                    file_path,
+                    content.lines().count()
+                ))
+            })?
+            .to_string();
+
+        match checkbox(&previous_line) {
+            Some(' ') => {}
+            Some('x' | 'X') => {
+                return Err(conflict(format!(
+                    "Task on line {} of {} is already completed",
+                    line_number, file_path
+                )));
+            }
+            Some(_) | None => {
+                return Err(invalid_params(format!(
+                    "Line {} of {} is not an incomplete task: {}",
+                    line_number,
+                    file_path,
+                    previous_line.trim()
+                )));
+            }
+        }
+
+        let completed_date = request.date.unwrap_or_else(|| Local::now().date_naive());
+        let line = mark_line_completed(&previous_line, completed_date);
+        let edit = LineEdit {
+            line_number,
+            expected: previous_line.clone(),
+            replacement: line.clone(),
+        };
+        let applied = apply_line_edits(&path, &[edit])
+            .map_err(|e| internal_error(format!("Failed to write note: {}", e)))?;
+        if applied == 0 {
+            return Err(conflict(format!(
+                "Line {} of {} changed while it was being edited",
+                line_number, file_path
+            )));
+        }
+
+        Ok(CompleteTaskResponse {
+            file_path,
+            line_number,
+            previous_line,
+            line,
+            completed_date,
+        })
+    }
+
+    /// Move the task on `line_number` and its sub-items to another note or section
+    pub async fn move_task(&self, request: MoveTaskRequest) -> CapabilityResult<MoveTaskResponse> {
+        let resolver = PathResolver::new(&self.base_path)?;
+        let source_path = resolver.resolve_note(&request.file_path)?;
+        let target_path = resolver.resolve_note(&request.target)?;
+        let file_path = to_slash(resolver.relative(&source_path).unwrap_or(&source_path));
+        let target = to_slash(resolver.relative(&target_path).unwrap_or(&target_path));
+        let same_note = file_path == target;
+        if same_note && request.heading.is_none() {
+            return Err(invalid_params(
+                "Moving a task within the same note needs a heading to move it under",
+            ));
+        }
+
+        stamp_before_write(&source_path, &self.config)?;
+        if !same_note {
+            stamp_before_write(&target_path, &self.config)?;
+        }
+        let content = fs::read_to_string(&source_path)
+            .map_err(|e| internal_error(format!("Failed to read note: {}", e)))?;
+        let mut lines: Vec<&str> = content.lines().collect();
+        let start = request.line_number.wrapping_sub(1);
+        let Some(line) = lines.get(start).filter(|line| checkbox(line).is_some()) else {
+            return Err(invalid_params(format!(
+                "Line {} of {} is not a task",
+                request.line_number, file_path
+            )));
+        };
+
+        // Sub-items are the following lines indented deeper than the task
+        let indent = &line[..line.len() - line.trim_start().len()];
+        let end = lines[start + 1..]
+            .iter()
+            .position(|l| l.trim().is_empty() || l.len() - l.trim_start().len() <= indent.len())
+            .map_or(lines.len(), |i| start + 1 + i);
+        let moved: Vec<String> = lines[start..end]
+            .iter()
+            .map(|l| l.strip_prefix(indent).unwrap_or(l.trim_start()).to_string())
+            .collect();
+
+        let link = target.strip_suffix(".md").unwrap_or(&target).to_string();
+        let breadcrumb = request.breadcrumb.unwrap_or(false).then(|| {
+            let link = match &request.heading {
+                Some(heading) => {
+                    format!("{}#{}", link, heading.trim().trim_start_matches('#').trim())
+                }
+                None => link,
+            };
+            format!("{}> moved to [[{}]]", indent, link)
+        });
+        match &breadcrumb {
+            Some(breadcrumb) => {
+                lines.splice(start..end, [breadcrumb.as_str()]);
+            }
+            None => {
+                lines.drain(start..end);
+            }
+        }
+
+        let newline = line_ending(&content);
+        let target_content = if same_note {
+            lines.join("\n")
+        } else {
+            fs::read_to_string(&target_path)
+                .map_err(|e| internal_error(format!("Failed to read note: {}", e)))?
+        };
+        let mut target_lines: Vec<&str> = if same_note {
+            lines.clone()
+        } else {
+            target_content.lines().collect()
+        };
+        let index = insertion_point(
+            &target_content,
+            &target_lines,
+            request.heading.as_deref(),
+            &target,
+        )?;
+        target_lines.splice(index..index, moved.iter().map(String::as_str));
+
+        // The target is written first, so a failed write can duplicate the
+        // task but never lose it
+        if same_note {
+            write_lines(&source_path, &target_lines, newline)?;
+        } else {
+            write_lines(&target_path, &target_lines, line_ending(&target_content))?;
+            write_lines(&source_path, &lines, newline)?;
+        }
+
+        Ok(MoveTaskResponse {
+            file_path,
+            target,
+            line_number: index + 1,
+            lines: moved,
+            breadcrumb: breadcrumb.map(|b| b.trim_start().to_string()),
+        })
+    }
+}
+
+/// Operation struct for move_task (HTTP, CLI, and MCP)
+pub struct MoveTaskOperation {
+    capability: Arc<TaskWriteCapability>,
+}
+
+impl MoveTaskOperation {
+    pub fn new(capability: Arc<TaskWriteCapability>) -> Self {
+        Self { capability }
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::operation::Operation for MoveTaskOperation {
+    fn name(&self) -> &'static str {
+        move_task::CLI_NAME
+    }
+
+    fn path(&self) -> &'static str {
+        move_task::HTTP_PATH
+    }
+
+    fn description(&self) -> &'static str {
+        move_task::DESCRIPTION
+    }
+
+    fn get_command(&self) -> clap::Command {
+        // Get command from request struct's Parser derive
+        MoveTaskRequest::command()
+    }
+
+    async fn execute_json(&self, json: serde_json::Value) -> Result<serde_json::Value, Error> {
+        crate::http_router::execute_json_operation(json, |req| self.capability.move_task(req)).await
+    }
+
+    async fn execute_from_args(
+        &self,
+        matches: &clap::ArgMatches,
+        _registry: &crate::capabilities::CapabilityRegistry,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        // Parse request from ArgMatches
+        let request = MoveTaskRequest::from_arg_matches(matches)?;
+
+        // Handle CLI-specific path if present
+        let response = if let Some(ref path) = request.path {
+            let capability = TaskWriteCapability::new(
+                path.clone(),
+                Arc::new(Config::load_from_base_path(path.as_path())),
+            );
+            let mut req_without_path = request;
+            req_without_path.path = None;
+            capability.move_task(req_without_path).await?
+        } else {
+            self.capability.move_task(request).await?
+        };
+
+        // Serialize to JSON
+        Ok(serde_json::to_string_pretty(&response)?)
+    }
+
+    fn input_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(MoveTaskRequest)).unwrap()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(MoveTaskResponse)).unwrap()
+    }
+}
+
+/// Operation struct for add_task (HTTP, CLI, and MCP)
+pub struct AddTaskOperation {
+    capability: Arc<TaskWriteCapability>,
+}
+
+impl AddTaskOperation {
+    pub fn new(capability: Arc<TaskWriteCapability>) -> Self {
+        Self { capability }
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::operation::Operation for AddTaskOperation {
+    fn name(&self) -> &'static str {
+        add_task::CLI_NAME
+    }
+
+    fn path(&self) -> &'static str {
+        add_task::HTTP_PATH
+    }
+
+    fn description(&self) -> &'static str {
+        add_task::DESCRIPTION
+    }
+
+    fn get_command(&self) -> clap::Command {
+        // Get command from request struct's Parser derive
+        AddTaskRequest::command()
+    }
+
+    async fn execute_json(&self, json: serde_json::Value) -> Result<serde_json::Value, Error> {
+        crate::http_router::execute_json_operation(json, |req| self.capability.add_task(req)).await
+    }
+
+    async fn execute_from_args(
+        &self,
+        matches: &clap::ArgMatches,
+        _registry: &crate::capabilities::CapabilityRegistry,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        // Parse request from ArgMatches
+        let request = AddTaskRequest::from_arg_matches(matches)?;
+
+        // Handle CLI-specific path if present
+        let response = if let Some(ref path) = request.path {
+            let capability = TaskWriteCapability::new(
+                path.clone(),
+                Arc::new(Config::load_from_base_path(path.as_path())),
+            );
+            let mut req_without_path = request;
+            req_without_path.path = None;
+            capability.add_task(req_without_path).await?
+        } else {
+            self.capability.add_task(request).await?
+        };
+
+        // Serialize to JSON
+        Ok(serde_json::to_string_pretty(&response)?)
+    }
+
+    fn input_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(AddTaskRequest)).unwrap()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(AddTaskResponse)).unwrap()
+    }
+}
+
+/// Operation struct for complete_task (HTTP, CLI, and MCP)
+pub struct CompleteTaskOperation {
+    capability: Arc<TaskWriteCapability>,
+}
+
+impl CompleteTaskOperation {
+    pub fn new(capability: Arc<TaskWriteCapability>) -> Self {
+        Self { capability }
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::operation::Operation for CompleteTaskOperation {
+    fn name(&self) -> &'static str {
+        complete_task::CLI_NAME
+    }
+
+    fn path(&self) -> &'static str {
+        complete_task::HTTP_PATH
+    }
+
+    fn description(&self) -> &'static str {
+        complete_task::DESCRIPTION
+    }
+
+    fn get_command(&self) -> clap::Command {
+        // Get command from request struct's Parser derive
+        CompleteTaskRequest::command()
+    }
+
+    async fn execute_json(&self, json: serde_json::Value) -> Result<serde_json::Value, Error> {
+        crate::http_router::execute_json_operation(json, |req| self.capability.complete_task(req))
+            .await
+    }
+
+    async fn execute_from_args(
+        &self,
+        matches: &clap::ArgMatches,
+        _registry: &crate::capabilities::CapabilityRegistry,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        // Parse request from ArgMatches
+        let request = CompleteTaskRequest::from_arg_matches(matches)?;
+
+        // Handle CLI-specific path if present
+        let response = if let Some(ref path) = request.path {
+            let capability = TaskWriteCapability::new(
+                path.clone(),
+                Arc::new(Config::load_from_base_path(path.as_path())),
+            );
+            let mut req_without_path = request;
+            req_without_path.path = None;
+            capability.complete_task(req_without_path).await?
+        } else {
+            self.capability.complete_task(request).await?
+        };
+
+        // Serialize to JSON
+        Ok(serde_json::to_string_pretty(&response)?)
+    }
+
+    fn input_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(CompleteTaskRequest)).unwrap()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(CompleteTaskResponse)).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn request(file_path: &str, line_number: usize) -> CompleteTaskRequest {
+        CompleteTaskRequest {
+            file_path: file_path.to_string(),
+            line_number,
+            date: NaiveDate::from_ymd_opt(2025, 1, 20),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_complete_task() {
+        let vault = TempDir::new().unwrap();
+        fs::create_dir(vault.path().join("Projects")).unwrap();
+        let note = vault.path().join("Projects/todo.md");
+        fs::write(
+            &note,
+            "# Todo\r\n- [ ] Ship it 📅 2025-01-21\r\n  - [ ] Sub step\r\n- [x] Done\r\nNot a task\r\n",
+        )
+        .unwrap();
+        let capability =
+            TaskWriteCapability::new(vault.path().to_path_buf(), Arc::new(Config::default()));
+
+        let response = capability
+            .complete_task(request("Projects/todo.md", 2))
+            .await
+            .unwrap();
+        assert_eq!(response.file_path, "Projects/todo.md");
+        assert_eq!(response.previous_line, "- [ ] Ship it 📅 2025-01-21");
+        assert_eq!(response.line, "- [x] Ship it 📅 2025-01-21 ✅ 2025-01-20");
+
+        // Indented sub-tasks can be completed too
+        capability
+            .complete_task(request("Projects/todo.md", 3))
+            .await
+            .unwrap();
+        assert_eq!(
+            fs::read_to_string(&note).unwrap(),
+            "# Todo\r\n- [x] Ship it 📅 2025-01-21 ✅ 2025-01-20\r\n  - [x] Sub step ✅ 2025-01-20\r\n- [x] Done\r\nNot a task\r\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_complete_task_stamps_completion_dates() {
+        let vault = TempDir::new().unwrap();
+        let note = vault.path().join("todo.md");
+        fs::write(&note, "- [x] Legacy\n- [ ] Ship it\n").unwrap();
+        let modified =
+            chrono::DateTime::<Local>::from(fs::metadata(&note).unwrap().modified().unwrap())
+                .date_naive();
+        let config = Config {
+            stamp_completion_dates: true,
+            ..Config::default()
+        };
+        let capability = TaskWriteCapability::new(vault.path().to_path_buf(), Arc::new(config));
+
+        capability
+            .complete_task(request("todo.md", 2))
+            .await
+            .unwrap();
+        assert_eq!(
+            fs::read_to_string(&note).unwrap(),
+            format!(
+                "- [x] Legacy ✅ {}\n- [x] Ship it ✅ 2025-01-20\n",
+                modified
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_complete_task_safety_checks() {
+        let vault = TempDir::new().unwrap();
+        let outside = TempDir::new().unwrap();
+        fs::write(
+            vault.path().join("todo.md"),
+            "# Todo\n- [x] Done\n- [-] Dropped\n",
+        )
+        .unwrap();
+        fs::write(outside.path().join("secret.md"), "- [ ] Hidden\n").unwrap();
+        let capability =
+            TaskWriteCapability::new(vault.path().to_path_buf(), Arc::new(Config::default()));
+
+        let code = |result: CapabilityResult<CompleteTaskResponse>| result.unwrap_err().code();
+        assert_eq!(
+            code(capability.complete_task(request("todo.md", 2)).await),
+            "conflict"
+        );
+        for line_number in [0, 1, 3, 9] {
+            assert_eq!(
+                code(
+                    capability
+                        .complete_task(request("todo.md", line_number))
+                        .await
+                ),
+                "invalid_params"
+            );
+        }
+        let escape = format!(
+            "../{}/secret.md",
+            outside.path().file_name().unwrap().to_string_lossy()
+        );
+        assert_eq!(
+            code(capability.complete_task(request(&escape, 1)).await),
+            "forbidden"
+        );
+        assert_eq!(
+            fs::read_to_string(outside.path().join("secret.md")).unwrap(),
+            "- [ ] Hidden\n"
+        );
+    }
+
+    fn add(file_path: &str, content: &str, heading: Option<&str>) -> AddTaskRequest {
+        AddTaskRequest {
+            file_path: file_path.to_string(),
+            content: content.to_string(),
+            heading: heading.map(String::from),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_add_task() {
+        let vault = TempDir::new().unwrap();
+        let note = vault.path().join("plan.md");
+        fs::write(
+            &note,
+            "# Plan\n\n## Today\n- [ ] Existing\n\n### Later\n- [ ] Nested\n\n## Empty\n## Notes\nSome text",
+        )
+        .unwrap();
+        let capability =
+            TaskWriteCapability::new(vault.path().to_path_buf(), Arc::new(Config::default()));
+
+        let response = capability
+            .add_task(AddTaskRequest {
+                due: NaiveDate::from_ymd_opt(2025, 1, 21),
+                tags: Some(vec!["#work".to_string(), "urgent".to_string()]),
+                ..add("plan.md", "Call  Alice", Some("today"))
+            })
+            .await
+            .unwrap();
+        assert_eq!(response.line_number, 5);
+        assert_eq!(
+            response.line,
+            "- [ ] Call Alice #work #urgent 📅 2025-01-21"
+        );
+
+        let response = capability
+            .add_task(add("plan.md", "Fill me", Some("## Empty")))
+            .await
+            .unwrap();
+        assert_eq!(response.line_number, 11);
+
+        let response = capability
+            .add_task(add("plan.md", "At the end", None))
+            .await
+            .unwrap();
+        assert_eq!(response.line_number, 14);
+
+        assert_eq!(
+            fs::read_to_string(&note).unwrap(),
+            "# Plan\n\n## Today\n- [ ] Existing\n- [ ] Call Alice #work #urgent 📅 2025-01-21\n\n### Later\n- [ ] Nested\n\n## Empty\n- [ ] Fill me\n## Notes\nSome text\n- [ ] At the end\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_add_task_errors() {
+        let vault = TempDir::new().unwrap();
+        fs::write(vault.path().join("plan.md"), "# Plan\r\n").unwrap();
+        let capability =
+            TaskWriteCapability::new(vault.path().to_path_buf(), Arc::new(Config::default()));
+
+        let code = |result: CapabilityResult<AddTaskResponse>| result.unwrap_err().code();
+        assert_eq!(
+            code(
+                capability
+                    .add_task(add("plan.md", "x", Some("Missing")))
+                    .await
+            ),
+            "not_found"
+        );
+        assert_eq!(
+            code(
+                capability
+                    .add_task(add("plan.md", "two\nlines", None))
+                    .await
+            ),
+            "invalid_params"
+        );
+        assert_eq!(
+            code(capability.add_task(add("new.md", "x", None)).await),
+            "not_found"
+        );
+
+        capability
+            .add_task(add("plan.md", "Keep CRLF", Some("Plan")))
+            .await
+            .unwrap();
+        assert_eq!(
+            fs::read_to_string(vault.path().join("plan.md")).unwrap(),
+            "# Plan\r\n- [ ] Keep CRLF\r\n"
+        );
+    }
+
+    fn move_request(line_number: usize, target: &str, heading: Option<&str>) -> MoveTaskRequest {
+        MoveTaskRequest {
+            file_path: "inbox.md".to_string(),
+            line_number,
+            target: target.to_string(),
+            heading: heading.map(String::from),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_move_task() {
+        let vault = TempDir::new().unwrap();
+        fs::create_dir(vault.path().join("Projects")).unwrap();
+        let inbox = vault.path().join("inbox.md");
+        let project = vault.path().join("Projects/launch.md");
+        fs::write(
+            &inbox,
+            "# Inbox\n- [ ] Book venue\n  - [ ] Compare prices\n    notes\n- [ ] Stay\n\n## Later\n",
+        )
+        .unwrap();
+        fs::write(
+            &project,
+            "# Launch\r\n## Tasks\r\n- [ ] Existing\r\n\r\n## Notes\r\n",
+        )
+        .unwrap();
+        let capability =
+            TaskWriteCapability::new(vault.path().to_path_buf(), Arc::new(Config::default()));
+
+        let response = capability
+            .move_task(MoveTaskRequest {
+                breadcrumb: Some(true),
+                ..move_request(2, "Projects/launch.md", Some("tasks"))
+            })
+            .await
+            .unwrap();
+        assert_eq!(response.line_number, 4);
+        assert_eq!(
+            response.breadcrumb.as_deref(),
+            Some("> moved to [[Projects/launch#tasks]]")
+        );
+        assert_eq!(
+            fs::read_to_string(&project).unwrap(),
+            "# Launch\r\n## Tasks\r\n- [ ] Existing\r\n- [ ] Book venue\r\n  - [ ] Compare prices\r\n    notes\r\n\r\n## Notes\r\n"
+        );
+        assert_eq!(
+            fs::read_to_string(&inbox).unwrap(),
+            "# Inbox\n> moved to [[Projects/launch#tasks]]\n- [ ] Stay\n\n## Later\n"
+        );
+
+        // Within the same note, sub-items are dedented along with the task
+        fs::write(
+            &inbox,
+            "# Inbox\n- [ ] Parent\n  - [x] Child\n    - [ ] Grandchild\n## Later\n",
+        )
+        .unwrap();
+        let response = capability
+            .move_task(move_request(3, "inbox.md", Some("Later")))
+            .await
+            .unwrap();
+        assert_eq!(response.line_number, 4);
+        assert_eq!(
+            fs::read_to_string(&inbox).unwrap(),
+            "# Inbox\n- [ ] Parent\n## Later\n- [x] Child\n  - [ ] Grandchild\n"
+        );
+
+        let code = |result: CapabilityResult<MoveTaskResponse>| result.unwrap_err().code();
+        assert_eq!(
+            code(
+                capability
+                    .move_task(move_request(1, "Projects/launch.md", None))
+                    .await
+            ),
+            "invalid_params"
+        );
+        assert_eq!(
+            code(
+                capability
+                    .move_task(move_request(2, "inbox.md", None))
+                    .await
+            ),
+            "invalid_params"
+        );
+        assert_eq!(
+            code(
+                capability
+                    .move_task(move_request(2, "Projects/launch.md", Some("Missing")))
+                    .await
+            ),
+            "not_found"
+        );
+        assert_eq!(
+            fs::read_to_string(&inbox).unwrap(),
+            "# Inbox\n- [ ] Parent\n## Later\n- [x] Child\n  - [ ] Grandchild\n"
+        );
+    }
+
+    #[test]
+    fn test_checkbox() {
+        assert_eq!(checkbox("- [ ] Task"), Some(' '));
+        assert_eq!(checkbox("  -[x] Task"), Some('x'));
+        assert_eq!(checkbox("- [>] Task"), Some('>'));
+        assert_eq!(checkbox("- Not a task"), None);
+        assert_eq!(checkbox("[ ] Task"), None);
+    }
+}
@@ -1,24 +1,170 @@
-use crate::capabilities::CapabilityResult;
+use crate::capabilities::{CapabilityResult, check_strict};
 use crate::config::Config;
-use crate::error::internal_error;
-use crate::extractor::{Task, TaskExtractor};
-use crate::filter::{FilterOptions, filter_tasks};
+use crate::error::{Error, internal_error, invalid_params, scan_error};
+use crate::extractor::{Priority, Task, TaskExtractor, TaskStatus};
+use crate::filter::{DateValue, FilterOptions, compile_text_regex, filter_tasks};
+use crate::ics;
+use crate::nl_query::{InterpretedQuery, interpret_query, levenshtein};
+use crate::path_resolver::to_slash;
+use crate::recurrence::expand_recurrences;
+use crate::task_csv;
+use crate::tasks_query::parse_tasks_query;
+use crate::unicode::fold;
+use crate::warnings::{ExtractionWarning, print_warnings};
+use chrono::{Days, Local, NaiveDate};
 use clap::{CommandFactory, FromArgMatches, Parser};
-use rmcp::model::ErrorData;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
-use std::sync::Arc;
+use serde_json::Value;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{self, AtomicUsize};
+use std::sync::{Arc, Mutex};
+
+/// Days ahead to expand recurring tasks when no horizon is given
+const DEFAULT_RECURRENCE_DAYS: u64 = 30;
 
 /// Operation metadata for search_tasks
 pub mod search_tasks {
-    pub const DESCRIPTION: &str =
-        "Search for tasks in Markdown files with optional filtering by status, dates, and tags";
+    pub const DESCRIPTION: &str = "Search for tasks in Markdown files with optional filtering by status, dates, and tags. Results are paged with limit and offset; total_count and has_more tell whether more tasks match";
     #[allow(dead_code)]
     pub const CLI_NAME: &str = "tasks";
     pub const HTTP_PATH: &str = "/api/tasks";
 }
 
+/// Operation metadata for ask_tasks
+pub mod ask_tasks {
+    pub const DESCRIPTION: &str = "Search tasks with a free-text query such as \"incomplete work tasks due before next Friday\". Dates (today, tomorrow, next friday, this week, in 3 days, YYYY-MM-DD) and tags (fuzzy-matched) are interpreted server-side; the response includes the structured filter that was applied.";
+    #[allow(dead_code)]
+    pub const CLI_NAME: &str = "ask";
+    pub const HTTP_PATH: &str = "/api/tasks/ask";
+}
+
+/// Operation metadata for query_tasks
+pub mod query_tasks {
+    pub const DESCRIPTION: &str = "Search tasks with an Obsidian Tasks plugin query, one instruction per line, e.g. \"not done\\ndue before today\\ntags include #work\\npath includes Projects\". Supports done/not done, due/scheduled/starts/done dates, no due date, tags include / do not include, path/description/heading includes, priority is (above), is recurring, is (not) blocked, and limit. Unsupported instructions fail with invalid_params; the response includes the structured filter that was applied.";
+    #[allow(dead_code)]
+    pub const CLI_NAME: &str = "query";
+    pub const HTTP_PATH: &str = "/api/tasks/query";
+}
+
+/// Operation metadata for export_ics
+pub mod export_ics {
+    pub const DESCRIPTION: &str = "Export tasks with due dates as an iCalendar (.ics) feed, as to-dos (VTODO) or all-day events on the due date (VEVENT), so due dates show up in calendar apps. Only incomplete tasks unless include_completed is set. Over HTTP the body is the calendar itself (text/calendar)";
+    #[allow(dead_code)]
+    pub const CLI_NAME: &str = "ics";
+    pub const HTTP_PATH: &str = "/api/tasks.ics";
+}
+
+pub mod suggest_task_merges {
+    pub const DESCRIPTION: &str = "Find probable duplicate tasks across notes (e.g. the same chore captured in two daily notes) by comparing task text without case, tags, or punctuation. Returns pairs of tasks with their similarity from 0 to 1 for review; nothing is changed";
+    #[allow(dead_code)]
+    pub const CLI_NAME: &str = "suggest-task-merges";
+    pub const HTTP_PATH: &str = "/api/tasks/merges";
+}
+
+/// Similarity above which suggest_task_merges reports a pair by default
+const DEFAULT_MIN_SIMILARITY: f64 = 0.85;
+
+/// Field to sort search_tasks results by
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema, clap::ValueEnum,
+)]
+#[serde(rename_all = "snake_case")]
+#[value(rename_all = "snake_case")]
+pub enum TaskSortBy {
+    /// By file path, then line
+    #[default]
+    FilePath,
+    /// By line number, then file path
+    LineNumber,
+    /// By due date; tasks without one come last
+    DueDate,
+    /// By priority (lowest to urgent); tasks without one come last
+    Priority,
+    /// By completion date; tasks without one come last
+    CompletedDate,
+}
+
+/// Direction of a sort
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema, clap::ValueEnum,
+)]
+#[serde(rename_all = "snake_case")]
+#[value(rename_all = "snake_case")]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl SortOrder {
+    fn apply(self, ordering: Ordering) -> Ordering {
+        match self {
+            SortOrder::Asc => ordering,
+            SortOrder::Desc => ordering.reverse(),
+        }
+    }
+}
+
+/// How search_tasks results are grouped
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema, clap::ValueEnum,
+)]
+#[serde(rename_all = "snake_case")]
+#[value(rename_all = "snake_case")]
+pub enum TaskGroupBy {
+    /// One group per note, by path
+    File,
+    /// One group per tag, alphabetical; a task with several tags is in each
+    /// of their groups, and tasks without tags are in "untagged"
+    Tag,
+    /// One group per due date, earliest first, then "undated"
+    DueDate,
+    /// One group per status: incomplete, other statuses, completed,
+    /// cancelled, code TODOs
+    Status,
+    /// One group per priority, most urgent first, then "none"
+    Priority,
+}
+
+/// How the tasks CLI prints its results
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+#[value(rename_all = "snake_case")]
+pub enum TaskOutputFormat {
+    /// The task (or group) array as JSON
+    #[default]
+    Json,
+    /// One CSV row per task, with the columns given by `--columns`
+    Csv,
+}
+
+/// Calendar component each exported task becomes
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema, clap::ValueEnum,
+)]
+#[serde(rename_all = "snake_case")]
+#[value(rename_all = "snake_case")]
+pub enum IcsComponent {
+    /// A to-do with a due date, for task and reminder apps
+    #[default]
+    Vtodo,
+    /// An all-day event on the due date, for calendar views
+    Vevent,
+}
+
+/// Tasks sharing a group_by value
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TaskGroup {
+    /// Vault-relative path, tag, YYYY-MM-DD date, status, or priority
+    /// ("untagged", "undated", or "none" for tasks without one)
+    pub key: String,
+    /// Number of tasks in the group
+    pub count: usize,
+    pub tasks: Vec<Task>,
+}
+
 /// Parameters for the search_tasks operation
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Parser)]
 #[command(
@@ -34,37 +180,85 @@ pub struct SearchTasksRequest {
 
     #[arg(long)]
     #[schemars(description = "Filter by task status (incomplete, completed, cancelled)")]
-    pub status: Option<String>,
+    pub status: Option<TaskStatus>,
+
+    #[arg(long, help = "Filter by priority (lowest, low, medium, high, urgent)")]
+    #[schemars(
+        description = "Only include tasks with exactly this priority (lowest, low, medium, high, urgent)"
+    )]
+    pub priority: Option<Priority>,
+
+    #[arg(long, help = "Only include tasks with at least this priority")]
+    #[schemars(
+        description = "Only include tasks with at least this priority, e.g. high for high and urgent tasks. Tasks without a priority are excluded"
+    )]
+    pub min_priority: Option<Priority>,
+
+    #[arg(
+        long,
+        help = "Filter by due date (YYYY-MM-DD, today, tomorrow, yesterday, this-week, next-7-days, or overdue)"
+    )]
+    #[schemars(
+        description = "Filter by exact due date (YYYY-MM-DD), or tasks due within a span resolved when the search runs: today, tomorrow, yesterday, this-week, next-7-days, or overdue"
+    )]
+    pub due_on: Option<DateValue>,
+
+    #[arg(long, help = "Filter tasks due before date (YYYY-MM-DD or a keyword)")]
+    #[schemars(
+        description = "Filter tasks due before date (YYYY-MM-DD), or before the first day of a keyword's span (today, tomorrow, yesterday, this-week, next-7-days; overdue means before today)"
+    )]
+    pub due_before: Option<DateValue>,
+
+    #[arg(long, help = "Filter tasks due after date (YYYY-MM-DD or a keyword)")]
+    #[schemars(
+        description = "Filter tasks due after date (YYYY-MM-DD), or after the last day of a keyword's span (today, tomorrow, yesterday, this-week, next-7-days, overdue)"
+    )]
+    pub due_after: Option<DateValue>,
+
+    #[arg(long, help = "Filter tasks scheduled on date (⏳ YYYY-MM-DD)")]
+    #[schemars(description = "Filter by exact scheduled date (⏳ YYYY-MM-DD)")]
+    pub scheduled_on: Option<NaiveDate>,
 
-    #[arg(long, help = "Filter by exact due date (YYYY-MM-DD)")]
-    #[schemars(description = "Filter by exact due date (YYYY-MM-DD)")]
-    pub due_on: Option<String>,
+    #[arg(long, help = "Filter tasks scheduled before date (YYYY-MM-DD)")]
+    #[schemars(description = "Filter tasks scheduled (⏳) before date (YYYY-MM-DD)")]
+    pub scheduled_before: Option<NaiveDate>,
 
-    #[arg(long, help = "Filter tasks due before date (YYYY-MM-DD)")]
-    #[schemars(description = "Filter tasks due before date (YYYY-MM-DD)")]
-    pub due_before: Option<String>,
+    #[arg(long, help = "Filter tasks scheduled after date (YYYY-MM-DD)")]
+    #[schemars(description = "Filter tasks scheduled (⏳) after date (YYYY-MM-DD)")]
+    pub scheduled_after: Option<NaiveDate>,
 
-    #[arg(long, help = "Filter tasks due after date (YYYY-MM-DD)")]
-    #[schemars(description = "Filter tasks due after date (YYYY-MM-DD)")]
-    pub due_after: Option<String>,
+    #[arg(long, help = "Filter tasks starting after date (🛫 YYYY-MM-DD)")]
+    #[schemars(
+        description = "Filter tasks whose start date (🛫) is after date (YYYY-MM-DD). Tasks without a start date are excluded"
+    )]
+    pub starts_after: Option<NaiveDate>,
 
-    #[arg(long, help = "Filter tasks completed on a specific date (YYYY-MM-DD)")]
-    #[schemars(description = "Filter tasks completed on a specific date (YYYY-MM-DD)")]
-    pub completed_on: Option<String>,
+    #[arg(
+        long,
+        help = "Filter tasks completed on a date (YYYY-MM-DD, today, yesterday, this-week, ...)"
+    )]
+    #[schemars(
+        description = "Filter tasks completed on a specific date (YYYY-MM-DD), or within a span resolved when the search runs: today, tomorrow, yesterday, this-week, next-7-days, or overdue (before today)"
+    )]
+    pub completed_on: Option<DateValue>,
 
     #[arg(
         long,
-        help = "Filter tasks completed before a specific date (YYYY-MM-DD)"
+        help = "Filter tasks completed before a date (YYYY-MM-DD or a keyword)"
+    )]
+    #[schemars(
+        description = "Filter tasks completed before a specific date (YYYY-MM-DD), or before the first day of a keyword's span (today, tomorrow, yesterday, this-week, next-7-days; overdue means before today)"
     )]
-    #[schemars(description = "Filter tasks completed before a specific date (YYYY-MM-DD)")]
-    pub completed_before: Option<String>,
+    pub completed_before: Option<DateValue>,
 
     #[arg(
         long,
-        help = "Filter tasks completed after a specific date (YYYY-MM-DD)"
+        help = "Filter tasks completed after a date (YYYY-MM-DD or a keyword)"
     )]
-    #[schemars(description = "Filter tasks completed after a specific date (YYYY-MM-DD)")]
-    pub completed_after: Option<String>,
+    #[schemars(
+        description = "Filter tasks completed after a specific date (YYYY-MM-DD), or after the last day of a keyword's span (today, tomorrow, yesterday, this-week, next-7-days, overdue)"
+    )]
+    pub completed_after: Option<DateValue>,
 
     #[arg(
         long,
@@ -78,20 +272,428 @@ pub struct SearchTasksRequest {
     #[schemars(description = "Exclude tasks with these tags (must not have any)")]
     pub exclude_tags: Option<Vec<String>>,
 
+    #[arg(long, help = "Only include tasks without a due date")]
+    #[schemars(description = "If true, only include tasks without a due date")]
+    pub no_due_date: Option<bool>,
+
+    #[arg(long, help = "Filter by assignee (@owner(name) or assignee:: name)")]
+    #[schemars(
+        description = "Only include tasks assigned to this person, via @owner(name) or assignee:: name (case-insensitive)"
+    )]
+    pub assignee: Option<String>,
+
+    #[arg(long, help = "Only include tasks without an assignee")]
+    #[schemars(description = "If true, only include tasks without an assignee")]
+    pub unassigned: Option<bool>,
+
+    #[arg(
+        long,
+        help = "Only include tasks waiting on an open task (false: only unblocked)"
+    )]
+    #[schemars(
+        description = "If true, only include tasks whose ⛔ dependencies include a task that is not done or cancelled; if false, only tasks that are not blocked"
+    )]
+    pub blocked: Option<bool>,
+
+    #[arg(long, help = "Only include recurring tasks (🔁)")]
+    #[schemars(
+        description = "If true, only include recurring tasks, i.e. those with a 🔁 rule such as `🔁 every week`"
+    )]
+    pub recurring: Option<bool>,
+
+    #[arg(
+        long,
+        help = "Only include tasks in notes matching this glob (e.g. Projects/**)"
+    )]
+    #[schemars(
+        description = "Only include tasks in notes whose vault-relative path matches this glob, e.g. 'Projects/**' for everything under Projects or 'Daily/2025-*.md'. '*' stays within a folder; '**' spans folders"
+    )]
+    pub path_glob: Option<String>,
+
+    #[arg(
+        long,
+        help = "Only include tasks whose text contains this (case-insensitive)"
+    )]
+    #[schemars(
+        description = "Only include tasks whose text contains this, ignoring case, e.g. 'invoice'. See regex"
+    )]
+    pub query: Option<String>,
+
+    #[arg(long, help = "Treat --query as a regular expression")]
+    #[schemars(
+        description = "If true, query is a regular expression (Rust regex syntax, case-insensitive unless it starts with (?-i)) matched against the task text. Default: false"
+    )]
+    pub regex: Option<bool>,
+
+    #[arg(
+        long,
+        help = "Only include tasks under a heading containing this (e.g. Backlog or 'Project X > Backlog')"
+    )]
+    #[schemars(
+        description = "Only include tasks under a heading containing this text, ignoring case. Nested headings can be given as a breadcrumb, e.g. 'Project X > Backlog'"
+    )]
+    pub heading: Option<String>,
+
+    #[arg(long, help = "Include tasks quoted in blockquotes and callouts")]
+    #[schemars(
+        description = "If true, include tasks inside blockquotes and callouts (`> - [ ] item`). Default: false"
+    )]
+    pub include_quoted: Option<bool>,
+
+    #[arg(long, help = "Include notes under archived paths")]
+    #[schemars(
+        description = "If true, include notes under the configured archived_paths. Default: false"
+    )]
+    pub include_archived: Option<bool>,
+
+    #[arg(long, help = "Fill in each task's summary from its section")]
+    #[schemars(
+        description = "If true, fill each task's summary with the nearest heading above it (or the first sentence of the note's introduction). Default: false"
+    )]
+    pub include_summary: Option<bool>,
+
+    #[arg(long, help = "Explain how each task line was parsed")]
+    #[schemars(
+        description = "If true, fill each task's diagnostics with the line pattern that matched, the metadata removed from its text, constructs that may not have been read as intended (invalid dates, unrecognized markers, repeated markers, tags inside links), and a confidence of high, medium, or low. Default: false"
+    )]
+    pub debug_parse: Option<bool>,
+
+    #[arg(long, help = "Add future occurrences of recurring tasks")]
+    #[schemars(
+        description = "If true, add the future occurrences of incomplete recurring tasks (🔁 every week) as virtual tasks with computed due dates, up to recurrence_horizon. Occurrences are marked with occurrence: true. Default: false"
+    )]
+    pub expand_recurrences: Option<bool>,
+
+    #[arg(
+        long,
+        help = "Last date to expand recurring tasks to (YYYY-MM-DD, default: 30 days from today)"
+    )]
+    #[schemars(
+        description = "Last due date to generate occurrences for when expand_recurrences is set (YYYY-MM-DD). Default: 30 days from today"
+    )]
+    pub recurrence_horizon: Option<NaiveDate>,
+
+    #[arg(
+        long,
+        value_enum,
+        help = "Sort by file_path, line_number, due_date, priority, or completed_date"
+    )]
+    #[schemars(
+        description = "Field to sort by before paging: \"file_path\" (then line), \"line_number\", \"due_date\", \"priority\", or \"completed_date\". Tasks without the date or priority come last. Ties keep file path and line order. Default: file_path"
+    )]
+    pub sort_by: Option<TaskSortBy>,
+
+    #[arg(long, value_enum, help = "Sort direction: asc or desc")]
+    #[schemars(description = "Sort direction: \"asc\" or \"desc\". Default: asc")]
+    pub sort_order: Option<SortOrder>,
+
+    #[arg(
+        long,
+        value_enum,
+        help = "Group tasks by file, tag, due_date, status, or priority"
+    )]
+    #[schemars(
+        description = "Return the page of tasks in groups instead of a flat list: by \"file\", \"tag\" (a task is listed under each of its tags), \"due_date\", \"status\", or \"priority\". Tasks keep their sort order within a group. Default: no grouping"
+    )]
+    pub group_by: Option<TaskGroupBy>,
+
     #[arg(long, help = "Limit the number of tasks returned")]
     #[schemars(description = "Limit the number of tasks returned")]
     pub limit: Option<usize>,
+
+    #[arg(long, help = "Number of matching tasks to skip")]
+    #[schemars(
+        description = "Number of matching tasks to skip before applying limit, for paging (optional, defaults to 0)"
+    )]
+    pub offset: Option<usize>,
+
+    #[arg(long, help = "Fail if any file can't be read instead of skipping it")]
+    #[schemars(
+        description = "If true, fail when a file is skipped (unreadable, invalid frontmatter, larger than max_file_size, or cut off by a truncating scan limit) instead of reporting it in warnings. Default: false"
+    )]
+    pub strict: Option<bool>,
+
+    /// Output format (CLI only - HTTP uses the Accept header)
+    #[arg(long, value_enum, help = "Output format: json (default) or csv")]
+    #[serde(skip)]
+    #[schemars(skip)]
+    pub format: Option<TaskOutputFormat>,
+
+    /// CSV columns (CLI only - HTTP takes a columns parameter)
+    #[arg(
+        long,
+        value_delimiter = ',',
+        help = "Task fields to write as CSV columns (default: file_path,line_number,status,content,due_date,priority,tags)"
+    )]
+    #[serde(skip)]
+    #[schemars(skip)]
+    pub columns: Option<Vec<String>>,
 }
 
 /// Response from the search_tasks operation
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct TaskSearchResponse {
+    /// Tasks on this page (empty when `group_by` is set)
+    pub tasks: Vec<Task>,
+    /// Tasks on this page in groups, when `group_by` is set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub groups: Option<Vec<TaskGroup>>,
+    /// Number of tasks matching the filters, before limit/offset
+    #[serde(default)]
+    pub total_count: usize,
+    /// Whether more tasks follow this page
+    #[serde(default)]
+    pub has_more: bool,
+    /// Files that were skipped (unreadable, invalid frontmatter, larger
+    /// than `max_file_size`, or cut off by a truncating scan limit)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ExtractionWarning>,
+}
+
+/// Parameters for the ask_tasks operation
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Parser)]
+#[command(name = "ask", about = "Search tasks with a free-text query")]
+pub struct AskTasksRequest {
+    /// Path to scan (CLI only - not used in HTTP/MCP)
+    #[arg(index = 1, required = true, help = "Path to file or folder to scan")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub path: Option<PathBuf>,
+
+    #[arg(index = 2, required = true, help = "Free-text query")]
+    #[schemars(
+        description = "Free-text constraints, e.g. \"incomplete work tasks due before next Friday\" or \"completed this week without #personal\""
+    )]
+    pub query: String,
+
+    #[arg(long, help = "Include notes under archived paths")]
+    #[schemars(
+        description = "If true, include notes under the configured archived_paths. Default: false"
+    )]
+    pub include_archived: Option<bool>,
+
+    #[arg(long, help = "Limit the number of tasks returned")]
+    #[schemars(description = "Limit the number of tasks returned")]
+    pub limit: Option<usize>,
+
+    #[arg(long, help = "Fail if any file can't be read instead of skipping it")]
+    #[schemars(
+        description = "If true, fail when a file is skipped (unreadable, invalid frontmatter, larger than max_file_size, or cut off by a truncating scan limit) instead of reporting it in warnings. Default: false"
+    )]
+    pub strict: Option<bool>,
+}
+
+/// Response from the ask_tasks operation
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AskTasksResponse {
+    /// How the query was interpreted
+    pub interpreted: InterpretedQuery,
+    /// Tasks matching the interpreted filter
+    pub tasks: Vec<Task>,
+    /// Files that were skipped (unreadable, invalid frontmatter, larger
+    /// than `max_file_size`, or cut off by a truncating scan limit)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ExtractionWarning>,
+}
+
+/// Parameters for the query_tasks operation
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Parser)]
+#[command(name = "query", about = "Search tasks with an Obsidian Tasks query")]
+pub struct QueryTasksRequest {
+    /// Path to scan (CLI only - not used in HTTP/MCP)
+    #[arg(index = 1, required = true, help = "Path to file or folder to scan")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub path: Option<PathBuf>,
+
+    #[arg(
+        index = 2,
+        required = true,
+        help = "Tasks query, one instruction per line (e.g. $'not done\\ndue before today')"
+    )]
+    #[schemars(
+        description = "Obsidian Tasks query, one instruction per line, e.g. \"not done\\ndue before today\\ntags include #work\""
+    )]
+    pub query: String,
+
+    #[arg(long, help = "Include notes under archived paths")]
+    #[schemars(
+        description = "If true, include notes under the configured archived_paths. Default: false"
+    )]
+    pub include_archived: Option<bool>,
+
+    #[arg(
+        long,
+        help = "Limit the number of tasks returned (overrides a limit line)"
+    )]
+    #[schemars(
+        description = "Limit the number of tasks returned. Overrides a limit instruction in the query"
+    )]
+    pub limit: Option<usize>,
+
+    #[arg(long, help = "Fail if any file can't be read instead of skipping it")]
+    #[schemars(
+        description = "If true, fail when a file is skipped (unreadable, invalid frontmatter, larger than max_file_size, or cut off by a truncating scan limit) instead of reporting it in warnings. Default: false"
+    )]
+    pub strict: Option<bool>,
+}
+
+/// Response from the query_tasks operation
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct QueryTasksResponse {
+    /// Filters the query was translated into
+    pub filter: FilterOptions,
+    /// Tasks matching the filter
     pub tasks: Vec<Task>,
+    /// Files that were skipped (unreadable, invalid frontmatter, larger
+    /// than `max_file_size`, or cut off by a truncating scan limit)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ExtractionWarning>,
+}
+
+/// Parameters for the export_ics operation
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Parser)]
+#[command(
+    name = "ics",
+    about = "Export tasks with due dates as an iCalendar file"
+)]
+pub struct ExportIcsRequest {
+    /// Path to scan (CLI only - not used in HTTP/MCP)
+    #[arg(index = 1, required = true, help = "Path to file or folder to scan")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub path: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_enum,
+        help = "Component per task: vtodo (default) or vevent"
+    )]
+    #[schemars(
+        description = "Component per task: 'vtodo' (a to-do with a due date) or 'vevent' (an all-day event on the due date). Default: vtodo"
+    )]
+    pub component: Option<IcsComponent>,
+
+    #[arg(long, help = "Only export tasks with this tag")]
+    #[schemars(description = "Only export tasks with this tag")]
+    pub tag: Option<String>,
+
+    #[arg(long, help = "Export completed and cancelled tasks too")]
+    #[schemars(
+        description = "If true, export tasks of every status; otherwise only incomplete tasks. Default: false"
+    )]
+    pub include_completed: Option<bool>,
+
+    #[arg(long, help = "Include notes under archived paths")]
+    #[schemars(
+        description = "If true, include notes under the configured archived_paths. Default: false"
+    )]
+    pub include_archived: Option<bool>,
+
+    #[arg(long, help = "Fail if any file can't be read instead of skipping it")]
+    #[schemars(
+        description = "If true, fail when a file is skipped (unreadable, invalid frontmatter, larger than max_file_size, or cut off by a truncating scan limit) instead of reporting it in warnings. Default: false"
+    )]
+    pub strict: Option<bool>,
+
+    /// File to write the calendar to (CLI only)
+    #[arg(
+        long,
+        short,
+        help = "Write the calendar to this file instead of stdout"
+    )]
+    #[serde(skip)]
+    #[schemars(skip)]
+    pub output: Option<PathBuf>,
+}
+
+/// Response from the export_ics operation
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ExportIcsResponse {
+    /// Number of tasks in the calendar
+    pub count: usize,
+    /// The iCalendar text, with CRLF line endings
+    pub calendar: String,
+    /// Files that were skipped (unreadable, invalid frontmatter, larger
+    /// than `max_file_size`, or cut off by a truncating scan limit)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ExtractionWarning>,
+}
+
+/// Parameters for the suggest_task_merges operation
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Parser)]
+#[command(
+    name = "suggest-task-merges",
+    about = "Find probable duplicate tasks across notes"
+)]
+pub struct SuggestTaskMergesRequest {
+    /// Path to scan (CLI only - not used in HTTP/MCP)
+    #[arg(index = 1, required = true, help = "Path to file or folder to scan")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub path: Option<PathBuf>,
+
+    #[arg(long, help = "Lowest similarity to report, from 0 to 1 (default 0.85)")]
+    #[schemars(
+        description = "Lowest similarity to report, from 0 (nothing in common) to 1 (same text once case, tags, and punctuation are ignored). Default: 0.85"
+    )]
+    pub min_similarity: Option<f64>,
+
+    #[arg(long, help = "Compare completed and cancelled tasks too")]
+    #[schemars(
+        description = "If true, compare tasks of every status; otherwise only incomplete tasks. Default: false"
+    )]
+    pub include_completed: Option<bool>,
+
+    #[arg(long, help = "Include tasks inside blockquotes and callouts")]
+    #[schemars(
+        description = "If true, compare tasks quoted inside blockquotes and callouts. Default: false"
+    )]
+    pub include_quoted: Option<bool>,
+
+    #[arg(long, help = "Include notes under archived paths")]
+    #[schemars(
+        description = "If true, include notes under the configured archived_paths. Default: false"
+    )]
+    pub include_archived: Option<bool>,
+
+    #[arg(long, help = "Limit the number of pairs returned")]
+    #[schemars(description = "Limit the number of pairs returned, most similar first")]
+    pub limit: Option<usize>,
+
+    #[arg(long, help = "Fail if any file can't be read instead of skipping it")]
+    #[schemars(
+        description = "If true, fail when a file is skipped (unreadable, invalid frontmatter, larger than max_file_size, or cut off by a truncating scan limit) instead of reporting it in warnings. Default: false"
+    )]
+    pub strict: Option<bool>,
+}
+
+/// Two tasks in different notes that are probably the same task
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TaskMergeSuggestion {
+    /// 1 minus the edit distance between the tasks' normalized text over the
+    /// longer text's length, rounded to two decimals
+    pub similarity: f64,
+    /// The task that comes first by file path
+    pub first: Task,
+    pub second: Task,
+}
+
+/// Response from the suggest_task_merges operation
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SuggestTaskMergesResponse {
+    /// Pairs of probable duplicates, most similar first
+    pub suggestions: Vec<TaskMergeSuggestion>,
+    /// Files that were skipped (unreadable, invalid frontmatter, larger
+    /// than `max_file_size`, or cut off by a truncating scan limit)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ExtractionWarning>,
 }
 
 /// Capability for task operations (search, filter, extract)
 pub struct TaskCapability {
     base_path: PathBuf,
+    config: Arc<Config>,
     task_extractor: Arc<TaskExtractor>,
 }
 
@@ -100,7 +702,8 @@ impl TaskCapability {
     pub fn new(base_path: PathBuf, config: Arc<Config>) -> Self {
         Self {
             base_path,
-            task_extractor: Arc::new(TaskExtractor::new(config)),
+            task_extractor: Arc::new(TaskExtractor::new(Arc::clone(&config))),
+            config,
         }
     }
 
@@ -109,42 +712,489 @@ impl TaskCapability {
         &self,
         request: SearchTasksRequest,
     ) -> CapabilityResult<TaskSearchResponse> {
-        // Extract tasks from the base path using the pre-compiled extractor
-        let tasks = self
-            .task_extractor
-            .extract_tasks(&self.base_path)
-            .map_err(|e| internal_error(format!("Failed to extract tasks: {}", e)))?;
+        self.search_tasks_streaming(request, None).await
+    }
 
-        // Apply filters
+    /// Search for tasks, passing each batch of matching tasks to `on_match`
+    /// as soon as its file is scanned
+    ///
+    /// Batches hold exactly the tasks of the returned page, with their dates,
+    /// summaries, and diagnostics filled in, and stop once `limit` tasks were
+    /// sent. The page is the first matches found in scan order rather than
+    /// the first by file path, but is sorted by file path. Requests with
+    /// `sort_by`, `sort_order`, `offset`, `group_by`, `blocked`, or `strict`
+    /// are not streamed: `on_match` is never called and the response is the
+    /// same as for `search_tasks`.
+    pub async fn search_tasks_streaming(
+        &self,
+        request: SearchTasksRequest,
+        on_match: Option<&(dyn Fn(Vec<Task>) + Sync)>,
+    ) -> CapabilityResult<TaskSearchResponse> {
+        if let Some(ref glob) = request.path_glob
+            && let Err(e) = glob::Pattern::new(glob)
+        {
+            return Err(invalid_params(format!(
+                "Invalid path_glob '{}': {}",
+                glob, e
+            )));
+        }
+        let text_regex = request.regex.unwrap_or(false);
+        if let Some(ref query) = request.query
+            && text_regex
+            && let Err(e) = compile_text_regex(query)
+        {
+            return Err(invalid_params(format!("Invalid regular expression: {}", e)));
+        }
+
+        // Repeating tasks are materialized before filtering so date filters
+        // apply to each occurrence
+        let horizon = request.expand_recurrences.unwrap_or(false).then(|| {
+            request
+                .recurrence_horizon
+                .unwrap_or_else(|| Local::now().date_naive() + Days::new(DEFAULT_RECURRENCE_DAYS))
+        });
+        let include_archived = request.include_archived.unwrap_or(false);
         let filter_options = FilterOptions {
             status: request.status,
+            priority: request.priority,
+            min_priority: request.min_priority,
             due_on: request.due_on,
             due_before: request.due_before,
             due_after: request.due_after,
+            scheduled_on: request.scheduled_on,
+            scheduled_before: request.scheduled_before,
+            scheduled_after: request.scheduled_after,
+            starts_after: request.starts_after,
             completed_on: request.completed_on,
             completed_before: request.completed_before,
             completed_after: request.completed_after,
             tags: request.tags,
             exclude_tags: request.exclude_tags,
+            no_due_date: request.no_due_date.unwrap_or(false),
+            include_quoted: request.include_quoted.unwrap_or(false),
+            assignee: request.assignee,
+            unassigned: request.unassigned.unwrap_or(false),
+            recurring: request.recurring.unwrap_or(false),
+            path_glob: request.path_glob,
+            text: request.query,
+            text_regex,
+            heading: request.heading,
+            blocked: request.blocked,
+            path_includes: None,
+            path_root: None,
+        }
+        .within(&self.base_path);
+        let select = |mut tasks: Vec<Task>| {
+            if let Some(horizon) = horizon {
+                tasks = expand_recurrences(tasks, horizon);
+            }
+            let mut tasks = filter_tasks(tasks, &filter_options);
+            exclude_archived(&mut tasks, &self.config, include_archived);
+            tasks
         };
-        let mut filtered_tasks = filter_tasks(tasks, &filter_options);
 
-        // Apply limit (use provided limit, or default from env/50)
         let limit = request.limit.unwrap_or_else(get_default_limit);
-        filtered_tasks.truncate(limit);
+        let include_summary = request.include_summary.unwrap_or(false);
+        let debug_parse = request.debug_parse.unwrap_or(false);
+        let finish = |tasks: &mut Vec<Task>| {
+            // Summaries re-read the files, so only do it for the tasks returned
+            if include_summary {
+                self.task_extractor.summarize_tasks(tasks);
+            }
+            if debug_parse {
+                self.task_extractor.diagnose_tasks(tasks);
+            }
+        };
+
+        // A streamed search returns the first `limit` matches in the order
+        // files are read. Searches that need every match before they can pick
+        // the page (sorting, offsets, groups, the blocked filter) or that may
+        // still fail after the scan (strict) run as usual instead
+        let on_match = on_match.filter(|_| {
+            filter_options.blocked.is_none()
+                && request.sort_by.is_none()
+                && request.sort_order.is_none()
+                && request.offset.unwrap_or(0) == 0
+                && request.group_by.is_none()
+                && !request.strict.unwrap_or(false)
+        });
+        if let Some(on_match) = on_match {
+            let total_count = AtomicUsize::new(0);
+            let page = Mutex::new(Vec::new());
+            let on_file = |tasks: Vec<Task>| {
+                let matches = select(tasks);
+                total_count.fetch_add(matches.len(), atomic::Ordering::Relaxed);
+                let mut page = page.lock().unwrap();
+                let room = limit.saturating_sub(page.len());
+                if room == 0 || matches.is_empty() {
+                    return;
+                }
+                let mut batch: Vec<Task> = matches.into_iter().take(room).collect();
+                finish(&mut batch);
+                page.extend(batch.iter().cloned());
+                on_match(batch);
+            };
+            let (_, warnings) = self
+                .task_extractor
+                .extract_tasks_observed(&self.base_path, Some(&on_file))
+                .map_err(|e| scan_error("Failed to extract tasks", e))?;
+
+            let mut tasks = page.into_inner().unwrap();
+            sort_tasks(&mut tasks, TaskSortBy::default(), SortOrder::default());
+            let total_count = total_count.into_inner();
+            return Ok(TaskSearchResponse {
+                has_more: tasks.len() < total_count,
+                tasks,
+                groups: None,
+                total_count,
+                warnings,
+            });
+        }
+
+        // Extract tasks from the base path using the pre-compiled extractor
+        let (tasks, warnings) = self
+            .task_extractor
+            .extract_tasks_with_warnings(&self.base_path)
+            .map_err(|e| scan_error("Failed to extract tasks", e))?;
+        check_strict(&warnings, request.strict)?;
+        let mut filtered_tasks = select(tasks);
+
+        // Sort before paging so the limit takes the right subset
+        sort_tasks(
+            &mut filtered_tasks,
+            request.sort_by.unwrap_or_default(),
+            request.sort_order.unwrap_or_default(),
+        );
 
+        // Apply offset and limit (use provided limit, or default from env/50)
+        let total_count = filtered_tasks.len();
+        let offset = request.offset.unwrap_or(0);
+        let mut tasks: Vec<Task> = filtered_tasks
+            .into_iter()
+            .skip(offset)
+            .take(limit)
+            .collect();
+        finish(&mut tasks);
+
+        let has_more = offset + tasks.len() < total_count;
+        let groups = request
+            .group_by
+            .map(|group_by| group_tasks(std::mem::take(&mut tasks), group_by, &self.base_path));
         Ok(TaskSearchResponse {
-            tasks: filtered_tasks,
+            tasks,
+            groups,
+            total_count,
+            has_more,
+            warnings,
         })
     }
-}
 
-/// Get the default limit for task results
-/// Reads from MARKDOWN_TODO_EXTRACTOR_DEFAULT_LIMIT env var, defaults to 50
-fn get_default_limit() -> usize {
-    std::env::var("MARKDOWN_TODO_EXTRACTOR_DEFAULT_LIMIT")
-        .ok()
-        .and_then(|s| s.parse().ok())
+    /// Search for tasks with a free-text query
+    pub async fn ask_tasks(&self, request: AskTasksRequest) -> CapabilityResult<AskTasksResponse> {
+        let (tasks, warnings) = self
+            .task_extractor
+            .extract_tasks_with_warnings(&self.base_path)
+            .map_err(|e| scan_error("Failed to extract tasks", e))?;
+        check_strict(&warnings, request.strict)?;
+
+        // Fuzzy tag matching only considers tags that actually appear on tasks
+        let known_tags: Vec<String> = tasks
+            .iter()
+            .flat_map(|task| task.tags.iter().cloned())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let interpreted = interpret_query(
+            &request.query,
+            &known_tags,
+            Local::now().date_naive(),
+            self.config.locale,
+        );
+
+        let mut filtered_tasks = filter_tasks(tasks, &interpreted.filter);
+        exclude_archived(
+            &mut filtered_tasks,
+            &self.config,
+            request.include_archived.unwrap_or(false),
+        );
+        let limit = request.limit.unwrap_or_else(get_default_limit);
+        filtered_tasks.truncate(limit);
+
+        Ok(AskTasksResponse {
+            interpreted,
+            tasks: filtered_tasks,
+            warnings,
+        })
+    }
+
+    /// Search for tasks with an Obsidian Tasks query
+    pub async fn query_tasks(
+        &self,
+        request: QueryTasksRequest,
+    ) -> CapabilityResult<QueryTasksResponse> {
+        // Parse before scanning so a bad query fails fast
+        let parsed = parse_tasks_query(
+            &request.query,
+            Local::now().date_naive(),
+            self.config.locale,
+        )
+        .map_err(invalid_params)?;
+
+        let (tasks, warnings) = self
+            .task_extractor
+            .extract_tasks_with_warnings(&self.base_path)
+            .map_err(|e| scan_error("Failed to extract tasks", e))?;
+        check_strict(&warnings, request.strict)?;
+
+        let mut filtered_tasks =
+            filter_tasks(tasks, &parsed.filter.clone().within(&self.base_path));
+        exclude_archived(
+            &mut filtered_tasks,
+            &self.config,
+            request.include_archived.unwrap_or(false),
+        );
+        let limit = request
+            .limit
+            .or(parsed.limit)
+            .unwrap_or_else(get_default_limit);
+        filtered_tasks.truncate(limit);
+
+        Ok(QueryTasksResponse {
+            filter: parsed.filter,
+            tasks: filtered_tasks,
+            warnings,
+        })
+    }
+
+    /// Render tasks with due dates as an iCalendar feed
+    pub async fn export_ics(
+        &self,
+        request: ExportIcsRequest,
+    ) -> CapabilityResult<ExportIcsResponse> {
+        let (tasks, warnings) = self
+            .task_extractor
+            .extract_tasks_with_warnings(&self.base_path)
+            .map_err(|e| scan_error("Failed to extract tasks", e))?;
+        check_strict(&warnings, request.strict)?;
+
+        let filter_options = FilterOptions {
+            status: (!request.include_completed.unwrap_or(false)).then_some(TaskStatus::Incomplete),
+            tags: request.tag.map(|tag| vec![tag]),
+            ..FilterOptions::default()
+        };
+        let mut tasks = filter_tasks(tasks, &filter_options);
+        tasks.retain(|task| task.due_date.is_some());
+        exclude_archived(
+            &mut tasks,
+            &self.config,
+            request.include_archived.unwrap_or(false),
+        );
+        sort_tasks(&mut tasks, TaskSortBy::DueDate, SortOrder::Asc);
+
+        let uids = ics::task_uids(&self.base_path, &tasks);
+        let components: Vec<String> = tasks
+            .iter()
+            .zip(&uids)
+            .filter_map(|(task, uid)| match request.component.unwrap_or_default() {
+                IcsComponent::Vtodo => Some(ics::vtodo(task, uid)),
+                IcsComponent::Vevent => ics::vevent(task, uid),
+            })
+            .collect();
+
+        Ok(ExportIcsResponse {
+            count: components.len(),
+            calendar: ics::calendar(components.iter().map(String::as_str)),
+            warnings,
+        })
+    }
+
+    /// Find pairs of tasks in different notes with similar text
+    pub async fn suggest_task_merges(
+        &self,
+        request: SuggestTaskMergesRequest,
+    ) -> CapabilityResult<SuggestTaskMergesResponse> {
+        let (tasks, warnings) = self
+            .task_extractor
+            .extract_tasks_with_warnings(&self.base_path)
+            .map_err(|e| scan_error("Failed to extract tasks", e))?;
+        check_strict(&warnings, request.strict)?;
+
+        let filter_options = FilterOptions {
+            status: (!request.include_completed.unwrap_or(false)).then_some(TaskStatus::Incomplete),
+            include_quoted: request.include_quoted.unwrap_or(false),
+            ..FilterOptions::default()
+        };
+        let mut tasks = filter_tasks(tasks, &filter_options);
+        exclude_archived(
+            &mut tasks,
+            &self.config,
+            request.include_archived.unwrap_or(false),
+        );
+        sort_tasks(&mut tasks, TaskSortBy::FilePath, SortOrder::Asc);
+
+        let min_similarity = request.min_similarity.unwrap_or(DEFAULT_MIN_SIMILARITY);
+        let keys: Vec<String> = tasks.iter().map(|task| merge_key(&task.content)).collect();
+        let mut pairs = Vec::new();
+        for (i, first) in tasks.iter().enumerate() {
+            for (j, second) in tasks.iter().enumerate().skip(i + 1) {
+                if first.file_path == second.file_path {
+                    continue;
+                }
+                if let Some(score) = similarity(&keys[i], &keys[j], min_similarity) {
+                    pairs.push((score, i, j));
+                }
+            }
+        }
+
+        // Most similar first, then in file and line order
+        pairs.sort_by(|a, b| b.0.total_cmp(&a.0).then((a.1, a.2).cmp(&(b.1, b.2))));
+        pairs.truncate(request.limit.unwrap_or_else(get_default_limit));
+        let suggestions = pairs
+            .into_iter()
+            .map(|(score, i, j)| TaskMergeSuggestion {
+                similarity: (score * 100.0).round() / 100.0,
+                first: tasks[i].clone(),
+                second: tasks[j].clone(),
+            })
+            .collect();
+
+        Ok(SuggestTaskMergesResponse {
+            suggestions,
+            warnings,
+        })
+    }
+}
+
+/// Sort tasks by `sort_by`, keeping file path and line order among ties
+///
+/// Directory listing order isn't stable, so every sort starts from file path
+/// and line order.
+fn sort_tasks(tasks: &mut [Task], sort_by: TaskSortBy, order: SortOrder) {
+    // Tasks missing the sort value come last in either direction
+    fn present_first<T: Ord>(a: Option<T>, b: Option<T>, order: SortOrder) -> Ordering {
+        match (a, b) {
+            (Some(a), Some(b)) => order.apply(a.cmp(&b)),
+            (a, b) => b.is_some().cmp(&a.is_some()),
+        }
+    }
+
+    tasks.sort_by(|a, b| (&a.file_path, a.line_number).cmp(&(&b.file_path, b.line_number)));
+    match sort_by {
+        TaskSortBy::FilePath => {
+            if order == SortOrder::Desc {
+                tasks.reverse();
+            }
+        }
+        TaskSortBy::LineNumber => {
+            tasks.sort_by(|a, b| order.apply(a.line_number.cmp(&b.line_number)));
+        }
+        TaskSortBy::DueDate => {
+            tasks.sort_by(|a, b| present_first(a.due_date, b.due_date, order));
+        }
+        TaskSortBy::Priority => {
+            tasks.sort_by(|a, b| present_first(a.priority, b.priority, order));
+        }
+        TaskSortBy::CompletedDate => {
+            tasks.sort_by(|a, b| present_first(a.completed_date, b.completed_date, order));
+        }
+    }
+}
+
+/// Split tasks into groups, keeping their order within each group
+fn group_tasks(tasks: Vec<Task>, group_by: TaskGroupBy, base_path: &Path) -> Vec<TaskGroup> {
+    // Groups are ordered by rank, then key
+    let mut groups: BTreeMap<(u8, String), Vec<Task>> = BTreeMap::new();
+    for task in tasks {
+        let keys: Vec<(u8, String)> = match group_by {
+            TaskGroupBy::File => {
+                let path = Path::new(&task.file_path);
+                vec![(0, to_slash(path.strip_prefix(base_path).unwrap_or(path)))]
+            }
+            TaskGroupBy::Tag if task.tags.is_empty() => vec![(1, "untagged".to_string())],
+            TaskGroupBy::Tag => {
+                let tags: BTreeSet<&String> = task.tags.iter().collect();
+                tags.into_iter().map(|tag| (0, tag.clone())).collect()
+            }
+            TaskGroupBy::DueDate => vec![match task.due_date {
+                Some(date) => (0, date.to_string()),
+                None => (1, "undated".to_string()),
+            }],
+            TaskGroupBy::Status => {
+                let rank = match task.status {
+                    TaskStatus::Incomplete => 0,
+                    TaskStatus::Other(_) => 1,
+                    TaskStatus::Completed => 2,
+                    TaskStatus::Cancelled => 3,
+                    TaskStatus::CodeTodo => 4,
+                };
+                vec![(rank, task.status.to_string())]
+            }
+            TaskGroupBy::Priority => vec![match task.priority {
+                Some(priority) => (5 - priority as u8, priority.to_string()),
+                None => (6, "none".to_string()),
+            }],
+        };
+        let Some((last, rest)) = keys.split_last() else {
+            continue;
+        };
+        for key in rest {
+            groups.entry(key.clone()).or_default().push(task.clone());
+        }
+        groups.entry(last.clone()).or_default().push(task);
+    }
+
+    groups
+        .into_iter()
+        .map(|((_, key), tasks)| TaskGroup {
+            key,
+            count: tasks.len(),
+            tasks,
+        })
+        .collect()
+}
+
+/// Task text as suggest_task_merges compares it: case-folded, without tags
+/// or punctuation
+fn merge_key(content: &str) -> String {
+    fold(content)
+        .split_whitespace()
+        .filter(|word| !word.starts_with('#'))
+        .map(|word| {
+            word.chars()
+                .filter(|c| c.is_alphanumeric())
+                .collect::<String>()
+        })
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Similarity of two merge keys from 0 to 1, if it's at least `min`
+fn similarity(a: &str, b: &str, min: f64) -> Option<f64> {
+    let (a_len, b_len) = (a.chars().count(), b.chars().count());
+    let longest = a_len.max(b_len);
+    // The edit distance is at least the difference in length, so most pairs
+    // are ruled out without computing it
+    if longest == 0 || (a_len.min(b_len) as f64) < min * longest as f64 {
+        return None;
+    }
+    let score = 1.0 - levenshtein(a, b) as f64 / longest as f64;
+    (score >= min).then_some(score)
+}
+
+/// Drop tasks from notes under `archived_paths` unless `include_archived` is set
+pub(crate) fn exclude_archived(tasks: &mut Vec<Task>, config: &Config, include_archived: bool) {
+    if !include_archived {
+        tasks.retain(|task| !config.is_archived(Path::new(&task.file_path)));
+    }
+}
+
+/// Get the default limit for task results
+/// Reads from MARKDOWN_TODO_EXTRACTOR_DEFAULT_LIMIT env var, defaults to 50
+pub(crate) fn get_default_limit() -> usize {
+    std::env::var("MARKDOWN_TODO_EXTRACTOR_DEFAULT_LIMIT")
+        .ok()
+        .and_then(|s| s.parse().ok())
         .unwrap_or(50)
 }
 
@@ -173,12 +1223,16 @@ impl crate::operation::Operation for SearchTasksOperation {
         search_tasks::DESCRIPTION
     }
 
+    fn read_only(&self) -> bool {
+        true
+    }
+
     fn get_command(&self) -> clap::Command {
         // Get command from request struct's Parser derive
         SearchTasksRequest::command()
     }
 
-    async fn execute_json(&self, json: serde_json::Value) -> Result<serde_json::Value, ErrorData> {
+    async fn execute_json(&self, json: serde_json::Value) -> Result<serde_json::Value, Error> {
         crate::http_router::execute_json_operation(json, |req| self.capability.search_tasks(req))
             .await
     }
@@ -190,6 +1244,11 @@ impl crate::operation::Operation for SearchTasksOperation {
     ) -> Result<String, Box<dyn std::error::Error>> {
         // Parse directly from ArgMatches using clap's from_arg_matches
         let request = SearchTasksRequest::from_arg_matches(matches)?;
+        let format = request.format.unwrap_or_default();
+        if format == TaskOutputFormat::Csv && request.group_by.is_some() {
+            return Err("--format csv can't be combined with --group-by".into());
+        }
+        let columns = task_csv::columns_from(request.columns.clone().map(Value::from).as_ref());
 
         // For CLI usage, if a path was provided, we need to create a new capability
         // with that path instead of using the registry's default
@@ -207,12 +1266,884 @@ impl crate::operation::Operation for SearchTasksOperation {
             self.capability.search_tasks(request).await?
         };
 
-        // Serialize to JSON
-        Ok(serde_json::to_string_pretty(&response.tasks)?)
+        // The CLI prints a bare task (or group) array, so skipped files and
+        // further pages are reported on stderr
+        print_warnings(&response.warnings);
+        if response.has_more {
+            eprintln!(
+                "More tasks match than shown ({} in total); pass --offset to see the next page",
+                response.total_count
+            );
+        }
+        if format == TaskOutputFormat::Csv {
+            let csv = task_csv::render_csv(&response.tasks, &columns)?;
+            // The CLI adds the final line break
+            return Ok(csv.strip_suffix("\r\n").unwrap_or(&csv).to_string());
+        }
+        match response.groups {
+            Some(groups) => Ok(serde_json::to_string_pretty(&groups)?),
+            None => Ok(serde_json::to_string_pretty(&response.tasks)?),
+        }
+    }
+
+    fn render_csv(&self, request: &Value, response: &Value) -> Option<Result<String, Error>> {
+        let render = || {
+            if response.get("groups").is_some() {
+                return Err(invalid_params("CSV output can't be combined with group_by"));
+            }
+            let tasks: Vec<Task> = serde_json::from_value(response["tasks"].clone())
+                .map_err(|e| internal_error(format!("Failed to read tasks: {}", e)))?;
+            let columns = task_csv::columns_from(request.get("columns"));
+            task_csv::render_csv(&tasks, &columns).map_err(invalid_params)
+        };
+        Some(render())
     }
 
     fn input_schema(&self) -> serde_json::Value {
         use schemars::schema_for;
         serde_json::to_value(schema_for!(SearchTasksRequest)).unwrap()
     }
+
+    fn output_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(TaskSearchResponse)).unwrap()
+    }
+}
+
+/// Operation struct for ask_tasks (HTTP, CLI, and MCP)
+pub struct AskTasksOperation {
+    capability: Arc<TaskCapability>,
+}
+
+impl AskTasksOperation {
+    pub fn new(capability: Arc<TaskCapability>) -> Self {
+        Self { capability }
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::operation::Operation for AskTasksOperation {
+    fn name(&self) -> &'static str {
+        ask_tasks::CLI_NAME
+    }
+
+    fn path(&self) -> &'static str {
+        ask_tasks::HTTP_PATH
+    }
+
+    fn description(&self) -> &'static str {
+        ask_tasks::DESCRIPTION
+    }
+
+    fn read_only(&self) -> bool {
+        true
+    }
+
+    fn get_command(&self) -> clap::Command {
+        // Get command from request struct's Parser derive
+        AskTasksRequest::command()
+    }
+
+    async fn execute_json(&self, json: serde_json::Value) -> Result<serde_json::Value, Error> {
+        crate::http_router::execute_json_operation(json, |req| self.capability.ask_tasks(req)).await
+    }
+
+    async fn execute_from_args(
+        &self,
+        matches: &clap::ArgMatches,
+        _registry: &crate::capabilities::CapabilityRegistry,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        // Parse directly from ArgMatches using clap's from_arg_matches
+        let request = AskTasksRequest::from_arg_matches(matches)?;
+
+        // For CLI usage, if a path was provided, we need to create a new capability
+        // with that path instead of using the registry's default
+        let response = if let Some(ref path) = request.path {
+            // Create a new capability with the provided path
+            let config = Arc::new(Config::load_from_base_path(path.as_path()));
+            let capability = TaskCapability::new(path.clone(), config);
+
+            // Clear the path from request since it's not part of the search parameters
+            let mut req_without_path = request;
+            req_without_path.path = None;
+            capability.ask_tasks(req_without_path).await?
+        } else {
+            // Use the registry's capability (for when path comes from registry)
+            self.capability.ask_tasks(request).await?
+        };
+
+        // Serialize to JSON
+        Ok(serde_json::to_string_pretty(&response)?)
+    }
+
+    fn input_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(AskTasksRequest)).unwrap()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(AskTasksResponse)).unwrap()
+    }
+}
+
+/// Operation struct for query_tasks (HTTP, CLI, and MCP)
+pub struct QueryTasksOperation {
+    capability: Arc<TaskCapability>,
+}
+
+impl QueryTasksOperation {
+    pub fn new(capability: Arc<TaskCapability>) -> Self {
+        Self { capability }
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::operation::Operation for QueryTasksOperation {
+    fn name(&self) -> &'static str {
+        query_tasks::CLI_NAME
+    }
+
+    fn path(&self) -> &'static str {
+        query_tasks::HTTP_PATH
+    }
+
+    fn description(&self) -> &'static str {
+        query_tasks::DESCRIPTION
+    }
+
+    fn read_only(&self) -> bool {
+        true
+    }
+
+    fn get_command(&self) -> clap::Command {
+        // Get command from request struct's Parser derive
+        QueryTasksRequest::command()
+    }
+
+    async fn execute_json(&self, json: serde_json::Value) -> Result<serde_json::Value, Error> {
+        crate::http_router::execute_json_operation(json, |req| self.capability.query_tasks(req))
+            .await
+    }
+
+    async fn execute_from_args(
+        &self,
+        matches: &clap::ArgMatches,
+        _registry: &crate::capabilities::CapabilityRegistry,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        // Parse directly from ArgMatches using clap's from_arg_matches
+        let request = QueryTasksRequest::from_arg_matches(matches)?;
+
+        // For CLI usage, if a path was provided, we need to create a new capability
+        // with that path instead of using the registry's default
+        let response = if let Some(ref path) = request.path {
+            // Create a new capability with the provided path
+            let config = Arc::new(Config::load_from_base_path(path.as_path()));
+            let capability = TaskCapability::new(path.clone(), config);
+
+            // Clear the path from request since it's not part of the search parameters
+            let mut req_without_path = request;
+            req_without_path.path = None;
+            capability.query_tasks(req_without_path).await?
+        } else {
+            // Use the registry's capability (for when path comes from registry)
+            self.capability.query_tasks(request).await?
+        };
+
+        // Serialize to JSON
+        Ok(serde_json::to_string_pretty(&response)?)
+    }
+
+    fn input_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(QueryTasksRequest)).unwrap()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(QueryTasksResponse)).unwrap()
+    }
+}
+
+/// Operation struct for export_ics (HTTP, CLI, and MCP)
+pub struct ExportIcsOperation {
+    capability: Arc<TaskCapability>,
+}
+
+impl ExportIcsOperation {
+    pub fn new(capability: Arc<TaskCapability>) -> Self {
+        Self { capability }
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::operation::Operation for ExportIcsOperation {
+    fn name(&self) -> &'static str {
+        export_ics::CLI_NAME
+    }
+
+    fn path(&self) -> &'static str {
+        export_ics::HTTP_PATH
+    }
+
+    fn description(&self) -> &'static str {
+        export_ics::DESCRIPTION
+    }
+
+    fn read_only(&self) -> bool {
+        true
+    }
+
+    fn get_command(&self) -> clap::Command {
+        // Get command from request struct's Parser derive
+        ExportIcsRequest::command()
+    }
+
+    async fn execute_json(&self, json: serde_json::Value) -> Result<serde_json::Value, Error> {
+        crate::http_router::execute_json_operation(json, |req| self.capability.export_ics(req))
+            .await
+    }
+
+    async fn execute_from_args(
+        &self,
+        matches: &clap::ArgMatches,
+        _registry: &crate::capabilities::CapabilityRegistry,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        // Parse directly from ArgMatches using clap's from_arg_matches
+        let mut request = ExportIcsRequest::from_arg_matches(matches)?;
+        let output = request.output.take();
+
+        // For CLI usage, if a path was provided, we need to create a new capability
+        // with that path instead of using the registry's default
+        let response = if let Some(ref path) = request.path {
+            // Create a new capability with the provided path
+            let config = Arc::new(Config::load_from_base_path(path.as_path()));
+            let capability = TaskCapability::new(path.clone(), config);
+
+            // Clear the path from request since it's not part of the search parameters
+            let mut req_without_path = request;
+            req_without_path.path = None;
+            capability.export_ics(req_without_path).await?
+        } else {
+            // Use the registry's capability (for when path comes from registry)
+            self.capability.export_ics(request).await?
+        };
+        print_warnings(&response.warnings);
+
+        // The calendar goes to stdout as-is, or to the file with a summary
+        match output {
+            Some(output) => {
+                std::fs::write(&output, &response.calendar)?;
+                Ok(format!(
+                    "Wrote {} tasks to {}",
+                    response.count,
+                    output.display()
+                ))
+            }
+            None => Ok(response.calendar.trim_end().to_string()),
+        }
+    }
+
+    fn render_http(&self, response: &Value) -> Option<(&'static str, String)> {
+        let calendar = response.get("calendar")?.as_str()?;
+        Some(("text/calendar; charset=utf-8", calendar.to_string()))
+    }
+
+    fn input_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(ExportIcsRequest)).unwrap()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(ExportIcsResponse)).unwrap()
+    }
+}
+
+/// Operation struct for suggest_task_merges (HTTP, CLI, and MCP)
+pub struct SuggestTaskMergesOperation {
+    capability: Arc<TaskCapability>,
+}
+
+impl SuggestTaskMergesOperation {
+    pub fn new(capability: Arc<TaskCapability>) -> Self {
+        Self { capability }
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::operation::Operation for SuggestTaskMergesOperation {
+    fn name(&self) -> &'static str {
+        suggest_task_merges::CLI_NAME
+    }
+
+    fn path(&self) -> &'static str {
+        suggest_task_merges::HTTP_PATH
+    }
+
+    fn description(&self) -> &'static str {
+        suggest_task_merges::DESCRIPTION
+    }
+
+    fn read_only(&self) -> bool {
+        true
+    }
+
+    fn get_command(&self) -> clap::Command {
+        // Get command from request struct's Parser derive
+        SuggestTaskMergesRequest::command()
+    }
+
+    async fn execute_json(&self, json: serde_json::Value) -> Result<serde_json::Value, Error> {
+        crate::http_router::execute_json_operation(json, |req| {
+            self.capability.suggest_task_merges(req)
+        })
+        .await
+    }
+
+    async fn execute_from_args(
+        &self,
+        matches: &clap::ArgMatches,
+        _registry: &crate::capabilities::CapabilityRegistry,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        // Parse directly from ArgMatches using clap's from_arg_matches
+        let request = SuggestTaskMergesRequest::from_arg_matches(matches)?;
+
+        // For CLI usage, if a path was provided, we need to create a new capability
+        // with that path instead of using the registry's default
+        let response = if let Some(ref path) = request.path {
+            // Create a new capability with the provided path
+            let config = Arc::new(Config::load_from_base_path(path.as_path()));
+            let capability = TaskCapability::new(path.clone(), config);
+
+            // Clear the path from request since it's not part of the search parameters
+            let mut req_without_path = request;
+            req_without_path.path = None;
+            capability.suggest_task_merges(req_without_path).await?
+        } else {
+            // Use the registry's capability (for when path comes from registry)
+            self.capability.suggest_task_merges(request).await?
+        };
+
+        // Serialize to JSON
+        Ok(serde_json::to_string_pretty(&response)?)
+    }
+
+    fn input_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(SuggestTaskMergesRequest)).unwrap()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(SuggestTaskMergesResponse)).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn page(offset: Option<usize>, limit: Option<usize>) -> SearchTasksRequest {
+        serde_json::from_value(serde_json::json!({ "offset": offset, "limit": limit })).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_search_tasks_path_glob() {
+        let vault = TempDir::new().unwrap();
+        fs::create_dir_all(vault.path().join("Projects/Launch")).unwrap();
+        fs::create_dir_all(vault.path().join("Archive/Projects")).unwrap();
+        fs::write(vault.path().join("Projects/plan.md"), "- [ ] Plan\n").unwrap();
+        fs::write(vault.path().join("Projects/Launch/ship.md"), "- [ ] Ship\n").unwrap();
+        fs::write(vault.path().join("Archive/Projects/old.md"), "- [ ] Old\n").unwrap();
+        fs::write(vault.path().join("inbox.md"), "- [ ] Inbox\n").unwrap();
+        let capability =
+            TaskCapability::new(vault.path().to_path_buf(), Arc::new(Config::default()));
+        let search = |glob: &str| {
+            serde_json::from_value::<SearchTasksRequest>(serde_json::json!({ "path_glob": glob }))
+                .unwrap()
+        };
+        let contents = |response: TaskSearchResponse| -> Vec<String> {
+            response.tasks.into_iter().map(|t| t.content).collect()
+        };
+
+        let response = capability
+            .search_tasks(search("Projects/**"))
+            .await
+            .unwrap();
+        assert_eq!(contents(response), ["Ship", "Plan"]);
+        let response = capability
+            .search_tasks(search("Projects/*.md"))
+            .await
+            .unwrap();
+        assert_eq!(contents(response), ["Plan"]);
+        let response = capability.search_tasks(search("**/*.md")).await.unwrap();
+        assert_eq!(response.total_count, 4);
+
+        let error = capability.search_tasks(search("[")).await.unwrap_err();
+        assert_eq!(error.code(), "invalid_params");
+    }
+
+    #[tokio::test]
+    async fn test_search_tasks_query() {
+        let vault = TempDir::new().unwrap();
+        fs::write(
+            vault.path().join("todo.md"),
+            "- [ ] Send INVOICE to Acme\n- [ ] Pay invoices #finance\n- [ ] Call mom\n",
+        )
+        .unwrap();
+        let capability =
+            TaskCapability::new(vault.path().to_path_buf(), Arc::new(Config::default()));
+        let search = |params: serde_json::Value| {
+            let request: SearchTasksRequest = serde_json::from_value(params).unwrap();
+            capability.search_tasks(request)
+        };
+        let contents = |response: TaskSearchResponse| -> Vec<String> {
+            response.tasks.into_iter().map(|t| t.content).collect()
+        };
+
+        let response = search(serde_json::json!({ "query": "invoice" }))
+            .await
+            .unwrap();
+        assert_eq!(
+            contents(response),
+            ["Send INVOICE to Acme", "Pay invoices #finance"]
+        );
+        let response = search(serde_json::json!({ "query": r"invoice\b", "regex": true }))
+            .await
+            .unwrap();
+        assert_eq!(contents(response), ["Send INVOICE to Acme"]);
+        // Without regex, the pattern is plain text
+        let response = search(serde_json::json!({ "query": r"invoice\b" }))
+            .await
+            .unwrap();
+        assert!(response.tasks.is_empty());
+
+        let error = search(serde_json::json!({ "query": "(", "regex": true }))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code(), "invalid_params");
+    }
+
+    #[tokio::test]
+    async fn test_search_tasks_streaming() {
+        let vault = TempDir::new().unwrap();
+        fs::create_dir(vault.path().join("Work")).unwrap();
+        fs::write(
+            vault.path().join("a.md"),
+            "- [ ] Alpha #work\n- [x] Done #work\n",
+        )
+        .unwrap();
+        fs::write(vault.path().join("Work/b.md"), "- [ ] Beta #work\n").unwrap();
+        fs::write(vault.path().join("c.md"), "- [ ] Gamma\n").unwrap();
+        let capability =
+            TaskCapability::new(vault.path().to_path_buf(), Arc::new(Config::default()));
+        let request = |extra: serde_json::Value| -> SearchTasksRequest {
+            let mut params = serde_json::json!({ "status": "incomplete", "tags": ["work"] });
+            params
+                .as_object_mut()
+                .unwrap()
+                .extend(extra.as_object().unwrap().clone());
+            serde_json::from_value(params).unwrap()
+        };
+
+        let streamed = std::sync::Mutex::new(Vec::new());
+        let on_match = |tasks: Vec<Task>| streamed.lock().unwrap().extend(tasks);
+        let response = capability
+            .search_tasks_streaming(request(serde_json::json!({ "limit": 1 })), Some(&on_match))
+            .await
+            .unwrap();
+
+        // The streamed batches are the page: one of the two matches
+        let contents = |tasks: &[Task]| -> Vec<String> {
+            let mut contents: Vec<String> = tasks.iter().map(|t| t.content.clone()).collect();
+            contents.sort();
+            contents
+        };
+        let streamed = streamed.into_inner().unwrap();
+        assert_eq!(streamed.len(), 1);
+        assert_eq!(contents(&streamed), contents(&response.tasks));
+        assert_eq!(response.total_count, 2);
+        assert!(response.has_more);
+
+        // Without a limit, every match is streamed and returned
+        let streamed = std::sync::Mutex::new(Vec::new());
+        let on_match = |tasks: Vec<Task>| streamed.lock().unwrap().extend(tasks);
+        let response = capability
+            .search_tasks_streaming(request(serde_json::json!({})), Some(&on_match))
+            .await
+            .unwrap();
+        let streamed = streamed.into_inner().unwrap();
+        assert_eq!(contents(&streamed), ["Alpha #work", "Beta #work"]);
+        assert_eq!(contents(&response.tasks), contents(&streamed));
+        assert_eq!(response.tasks[0].content, "Beta #work");
+        assert!(!response.has_more);
+
+        // Sorted and offset searches need every match first, so aren't streamed
+        let streamed = std::sync::Mutex::new(Vec::new());
+        let on_match = |tasks: Vec<Task>| streamed.lock().unwrap().extend(tasks);
+        let response = capability
+            .search_tasks_streaming(
+                request(serde_json::json!({ "limit": 1, "offset": 1, "sort_by": "file_path" })),
+                Some(&on_match),
+            )
+            .await
+            .unwrap();
+        assert!(streamed.into_inner().unwrap().is_empty());
+        assert_eq!(response.tasks.len(), 1);
+        assert_eq!(response.total_count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_search_tasks_streaming_marks_blocked() {
+        let vault = TempDir::new().unwrap();
+        fs::write(
+            vault.path().join("a.md"),
+            "- [ ] Ship ⛔ review\n- [ ] Announce ⛔ draft\n",
+        )
+        .unwrap();
+        fs::write(
+            vault.path().join("b.md"),
+            "- [ ] Review 🆔 review\n- [x] Draft 🆔 draft\n",
+        )
+        .unwrap();
+        let capability =
+            TaskCapability::new(vault.path().to_path_buf(), Arc::new(Config::default()));
+        let request: SearchTasksRequest =
+            serde_json::from_value(serde_json::json!({ "status": "incomplete" })).unwrap();
+
+        let streamed = std::sync::Mutex::new(Vec::new());
+        let on_match = |tasks: Vec<Task>| streamed.lock().unwrap().extend(tasks);
+        let response = capability
+            .search_tasks_streaming(request, Some(&on_match))
+            .await
+            .unwrap();
+
+        // Dependent tasks wait for the whole scan, so their flag is right
+        let blocked = |tasks: &[Task]| -> Vec<(String, bool)> {
+            let mut blocked: Vec<(String, bool)> = tasks
+                .iter()
+                .map(|t| (t.content.clone(), t.blocked))
+                .collect();
+            blocked.sort();
+            blocked
+        };
+        let expected = [
+            ("Announce".to_string(), false),
+            ("Review".to_string(), false),
+            ("Ship".to_string(), true),
+        ];
+        assert_eq!(blocked(&streamed.into_inner().unwrap()), expected);
+        assert_eq!(blocked(&response.tasks), expected);
+    }
+
+    #[tokio::test]
+    async fn test_query_tasks() {
+        let vault = TempDir::new().unwrap();
+        let root = vault.path().join("Projects");
+        fs::create_dir_all(root.join("Launch")).unwrap();
+        fs::write(
+            root.join("Launch/plan.md"),
+            "- [ ] Book venue #work\n- [x] Draft invite #work\n- [ ] Buy snacks\n",
+        )
+        .unwrap();
+        fs::write(root.join("home.md"), "- [ ] Fix sink #work\n").unwrap();
+        // The scanned folder's own name doesn't count as part of the path
+        let capability = TaskCapability::new(root, Arc::new(Config::default()));
+        let query = |query: &str| QueryTasksRequest {
+            path: None,
+            query: query.to_string(),
+            include_archived: None,
+            limit: None,
+            strict: None,
+        };
+
+        let response = capability
+            .query_tasks(query("not done\ntags include #work\npath includes launch"))
+            .await
+            .unwrap();
+        let contents: Vec<_> = response.tasks.iter().map(|t| t.content.as_str()).collect();
+        assert_eq!(contents, ["Book venue #work"]);
+        assert_eq!(response.filter.path_includes.as_deref(), Some("launch"));
+
+        let response = capability
+            .query_tasks(query("path includes projects"))
+            .await
+            .unwrap();
+        assert!(response.tasks.is_empty());
+
+        let err = capability
+            .query_tasks(query("group by folder"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidParams(_)));
+    }
+
+    #[tokio::test]
+    async fn test_export_ics() {
+        let vault = TempDir::new().unwrap();
+        fs::write(
+            vault.path().join("plan.md"),
+            "- [ ] Renew passport #admin 📅 2025-03-01\n\
+             - [ ] Pay rent #home 📅 2025-02-01\n\
+             - [x] Book flights #admin 📅 2025-01-15\n\
+             - [ ] Someday #admin\n",
+        )
+        .unwrap();
+        let capability =
+            TaskCapability::new(vault.path().to_path_buf(), Arc::new(Config::default()));
+        let export = |component, tag: Option<&str>, include_completed| ExportIcsRequest {
+            path: None,
+            component,
+            tag: tag.map(str::to_string),
+            include_completed,
+            include_archived: None,
+            strict: None,
+            output: None,
+        };
+
+        // Incomplete tasks with due dates, soonest first
+        let response = capability
+            .export_ics(export(None, None, None))
+            .await
+            .unwrap();
+        assert_eq!(response.count, 2);
+        let calendar = &response.calendar;
+        assert!(calendar.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert_eq!(calendar.matches("BEGIN:VTODO").count(), 2);
+        assert!(calendar.find("Pay rent").unwrap() < calendar.find("Renew passport").unwrap());
+        assert!(!calendar.contains("Someday"));
+
+        let response = capability
+            .export_ics(export(
+                Some(IcsComponent::Vevent),
+                Some("admin"),
+                Some(true),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.count, 2);
+        assert_eq!(response.calendar.matches("BEGIN:VEVENT").count(), 2);
+        assert!(response.calendar.contains("DTSTART;VALUE=DATE:20250115"));
+        assert!(!response.calendar.contains("Pay rent"));
+    }
+
+    #[tokio::test]
+    async fn test_search_tasks_pages() {
+        let vault = TempDir::new().unwrap();
+        fs::write(
+            vault.path().join("b.md"),
+            "- [ ] Four
+- [ ] Five
+",
+        )
+        .unwrap();
+        fs::write(
+            vault.path().join("a.md"),
+            "- [ ] One
+- [ ] Two
+- [x] Three
+",
+        )
+        .unwrap();
+        let capability =
+            TaskCapability::new(vault.path().to_path_buf(), Arc::new(Config::default()));
+        let contents = |response: &TaskSearchResponse| -> Vec<String> {
+            response.tasks.iter().map(|t| t.content.clone()).collect()
+        };
+
+        let first = capability.search_tasks(page(None, Some(2))).await.unwrap();
+        assert_eq!(contents(&first), ["One", "Two"]);
+        assert_eq!(first.total_count, 5);
+        assert!(first.has_more);
+
+        let last = capability
+            .search_tasks(page(Some(4), Some(2)))
+            .await
+            .unwrap();
+        assert_eq!(contents(&last), ["Five"]);
+        assert!(!last.has_more);
+
+        let past_end = capability.search_tasks(page(Some(9), None)).await.unwrap();
+        assert!(past_end.tasks.is_empty());
+        assert_eq!(past_end.total_count, 5);
+        assert!(!past_end.has_more);
+    }
+
+    #[test]
+    fn test_sort_tasks() {
+        let extractor = TaskExtractor::new(Arc::new(Config::default()));
+        let mut tasks = extractor.extract_tasks_from_content(
+            "- [ ] Late 📅 2025-03-01 ⏫\n- [ ] Undated\n- [x] Early 📅 2025-01-01 ✅ 2025-01-02\n- [ ] Low 📅 2025-02-01 🔽\n",
+            Path::new("b.md"),
+        );
+        tasks.extend(extractor.extract_tasks_from_content("- [ ] First\n", Path::new("a.md")));
+        let sorted = |tasks: &mut Vec<Task>, sort_by, order| -> Vec<String> {
+            sort_tasks(tasks, sort_by, order);
+            tasks.iter().map(|t| t.content.clone()).collect()
+        };
+
+        assert_eq!(
+            sorted(&mut tasks, TaskSortBy::DueDate, SortOrder::Asc),
+            ["Early", "Low", "Late", "First", "Undated"]
+        );
+        assert_eq!(
+            sorted(&mut tasks, TaskSortBy::DueDate, SortOrder::Desc),
+            ["Late", "Low", "Early", "First", "Undated"]
+        );
+        assert_eq!(
+            sorted(&mut tasks, TaskSortBy::Priority, SortOrder::Desc),
+            ["Late", "Low", "First", "Undated", "Early"]
+        );
+        assert_eq!(
+            sorted(&mut tasks, TaskSortBy::LineNumber, SortOrder::Asc),
+            ["First", "Late", "Undated", "Early", "Low"]
+        );
+        assert_eq!(
+            sorted(&mut tasks, TaskSortBy::FilePath, SortOrder::Desc),
+            ["Low", "Early", "Undated", "Late", "First"]
+        );
+    }
+
+    #[test]
+    fn test_group_tasks() {
+        let extractor = TaskExtractor::new(Arc::new(Config::default()));
+        let base = Path::new("vault");
+        let mut tasks = extractor.extract_tasks_from_content(
+            "- [ ] Urgent #work #home ⏫ 📅 2025-02-01\n- [x] Done #work\n- [ ] Plain\n",
+            &base.join("notes/b.md"),
+        );
+        tasks.extend(
+            extractor
+                .extract_tasks_from_content("- [ ] Early 📅 2025-01-01 ⏬\n", &base.join("a.md")),
+        );
+        let grouped = |group_by| -> Vec<(String, Vec<String>)> {
+            group_tasks(tasks.clone(), group_by, base)
+                .into_iter()
+                .map(|group| {
+                    assert_eq!(group.count, group.tasks.len());
+                    let contents = group
+                        .tasks
+                        .iter()
+                        .map(|t| t.content.split(' ').next().unwrap().to_string())
+                        .collect();
+                    (group.key, contents)
+                })
+                .collect()
+        };
+        let group = |key: &str, contents: &[&str]| -> (String, Vec<String>) {
+            (
+                key.to_string(),
+                contents.iter().map(|c| c.to_string()).collect(),
+            )
+        };
+
+        assert_eq!(
+            grouped(TaskGroupBy::File),
+            [
+                group("a.md", &["Early"]),
+                group("notes/b.md", &["Urgent", "Done", "Plain"])
+            ]
+        );
+        assert_eq!(
+            grouped(TaskGroupBy::Tag),
+            [
+                group("home", &["Urgent"]),
+                group("work", &["Urgent", "Done"]),
+                group("untagged", &["Plain", "Early"])
+            ]
+        );
+        assert_eq!(
+            grouped(TaskGroupBy::DueDate),
+            [
+                group("2025-01-01", &["Early"]),
+                group("2025-02-01", &["Urgent"]),
+                group("undated", &["Done", "Plain"])
+            ]
+        );
+        assert_eq!(
+            grouped(TaskGroupBy::Status),
+            [
+                group("incomplete", &["Urgent", "Plain", "Early"]),
+                group("completed", &["Done"])
+            ]
+        );
+        assert_eq!(
+            grouped(TaskGroupBy::Priority),
+            [
+                group("urgent", &["Urgent"]),
+                group("lowest", &["Early"]),
+                group("none", &["Done", "Plain"])
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_suggest_task_merges() {
+        let vault = TempDir::new().unwrap();
+        fs::write(
+            vault.path().join("2025-01-01.md"),
+            "- [ ] Call the plumber #home\n- [ ] Renew passport\n- [ ] Buy milk\n",
+        )
+        .unwrap();
+        fs::write(
+            vault.path().join("2025-01-02.md"),
+            "- [ ] call the plumber!\n- [ ] Renew passports\n- [x] Buy milk\n- [ ] Call the plumber\n",
+        )
+        .unwrap();
+        let capability =
+            TaskCapability::new(vault.path().to_path_buf(), Arc::new(Config::default()));
+        let request = |json| -> SuggestTaskMergesRequest { serde_json::from_value(json).unwrap() };
+        let pairs = |response: SuggestTaskMergesResponse| -> Vec<(f64, String, usize)> {
+            response
+                .suggestions
+                .into_iter()
+                .map(|s| {
+                    assert!(s.first.file_path.ends_with("2025-01-01.md"));
+                    (s.similarity, s.first.content, s.second.line_number)
+                })
+                .collect()
+        };
+
+        // Tasks in the same note are never paired
+        let response = capability
+            .suggest_task_merges(request(serde_json::json!({})))
+            .await
+            .unwrap();
+        assert_eq!(
+            pairs(response),
+            [
+                (1.0, "Call the plumber #home".to_string(), 1),
+                (1.0, "Call the plumber #home".to_string(), 4),
+                (0.93, "Renew passport".to_string(), 2),
+            ]
+        );
+
+        let response = capability
+            .suggest_task_merges(request(serde_json::json!({
+                "min_similarity": 1.0,
+                "include_completed": true,
+                "limit": 2
+            })))
+            .await
+            .unwrap();
+        assert_eq!(
+            pairs(response),
+            [
+                (1.0, "Call the plumber #home".to_string(), 1),
+                (1.0, "Call the plumber #home".to_string(), 4),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_similarity() {
+        assert_eq!(merge_key("Call the  Plumber! #home"), "call the plumber");
+        assert_eq!(similarity("buy milk", "buy milk", 0.85), Some(1.0));
+        assert_eq!(similarity("buy milk", "buy eggs", 0.85), None);
+        assert_eq!(similarity("", "", 0.0), None);
+        assert_eq!(similarity("a", "a very long task", 0.1), None);
+    }
 }
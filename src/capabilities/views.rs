@@ -0,0 +1,907 @@
+//! Saved searches ("smart views")
+//!
+//! Views are named task filters defined in `.markdown-todo-extractor.toml`
+//! (`[views.<name>]`), so common searches can be run by name instead of
+//! repeating their parameters. `materialize_view` writes a view's results
+//! into a note, between `<!-- view:<name> -->` and `<!-- /view:<name> -->`
+//! markers, so the list can be read without any plugin.
+
+use crate::capabilities::tasks::{exclude_archived, get_default_limit};
+use crate::capabilities::{CapabilityResult, check_strict};
+use crate::completion_dates::stamp_before_write;
+use crate::config::{Config, ViewConfig};
+use crate::error::{Error, conflict, internal_error, invalid_params, not_found, scan_error};
+use crate::extractor::{Task, TaskExtractor};
+use crate::filter::{FilterOptions, filter_tasks};
+use crate::nl_query::interpret_query;
+use crate::path_resolver::{PathResolver, from_slash, to_slash};
+use crate::scheduler::render_task;
+use crate::warnings::ExtractionWarning;
+use chrono::Local;
+use clap::{CommandFactory, FromArgMatches, Parser};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Component, PathBuf};
+use std::sync::Arc;
+
+/// Operation metadata for list_views
+pub mod list_views {
+    pub const DESCRIPTION: &str =
+        "List the saved task searches (views) defined in the vault configuration";
+    #[allow(dead_code)]
+    pub const CLI_NAME: &str = "list-views";
+    pub const HTTP_PATH: &str = "/api/views";
+}
+
+/// Operation metadata for run_view
+pub mod run_view {
+    pub const DESCRIPTION: &str =
+        "Run a saved task search (view) by name. Use list_views to see the available views.";
+    #[allow(dead_code)]
+    pub const CLI_NAME: &str = "run-view";
+    pub const HTTP_PATH: &str = "/api/views/run";
+}
+
+/// Operation metadata for materialize_view
+pub mod materialize_view {
+    pub const DESCRIPTION: &str = "Run a saved task search (view) and write its results into a note, replacing the block between <!-- view:<name> --> and <!-- /view:<name> --> markers (appended to the note, or a new note, the first time). Tasks are written as plain bullets linking to their notes, so they are not picked up as tasks again.";
+    #[allow(dead_code)]
+    pub const CLI_NAME: &str = "materialize-view";
+    pub const HTTP_PATH: &str = "/api/views/materialize";
+}
+
+/// Parameters for the list_views operation
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Parser)]
+#[command(name = "list-views", about = "List saved task searches")]
+pub struct ListViewsRequest {
+    /// Path to vault (CLI only - not used in HTTP/MCP)
+    #[arg(index = 1, required = true, help = "Path to vault")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub path: Option<PathBuf>,
+}
+
+/// A saved search as defined in the configuration
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ViewInfo {
+    /// Name used to run the view
+    pub name: String,
+    /// Human-readable description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Free-text query, interpreted each time the view runs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
+    /// Explicit filters (these override the query)
+    pub filter: FilterOptions,
+    /// Default number of tasks returned
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+    /// Note that materialize_view writes the results into
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+/// Response from the list_views operation
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ListViewsResponse {
+    /// Views sorted by name
+    pub views: Vec<ViewInfo>,
+}
+
+/// Parameters for the run_view operation
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Parser)]
+#[command(name = "run-view", about = "Run a saved task search by name")]
+pub struct RunViewRequest {
+    /// Path to vault (CLI only - not used in HTTP/MCP)
+    #[arg(index = 1, required = true, help = "Path to vault")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub path: Option<PathBuf>,
+
+    #[arg(index = 2, required = true, help = "Name of the view")]
+    #[schemars(description = "Name of the view to run")]
+    pub name: String,
+
+    #[arg(long, help = "Include notes under archived paths")]
+    #[schemars(
+        description = "If true, include notes under the configured archived_paths. Default: false"
+    )]
+    pub include_archived: Option<bool>,
+
+    #[arg(long, help = "Limit the number of tasks returned")]
+    #[schemars(description = "Limit the number of tasks returned (overrides the view's limit)")]
+    pub limit: Option<usize>,
+
+    #[arg(long, help = "Fail if any file can't be read instead of skipping it")]
+    #[schemars(
+        description = "If true, fail when a file is skipped (unreadable, invalid frontmatter, larger than max_file_size, or cut off by a truncating scan limit) instead of reporting it in warnings. Default: false"
+    )]
+    pub strict: Option<bool>,
+}
+
+/// Response from the run_view operation
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RunViewResponse {
+    /// Name of the view that ran
+    pub name: String,
+    /// Filters that were applied, with the query resolved against today
+    pub filter: FilterOptions,
+    /// Tasks matching the view
+    pub tasks: Vec<Task>,
+    /// Files that were skipped (unreadable, invalid frontmatter, larger
+    /// than `max_file_size`, or cut off by a truncating scan limit)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ExtractionWarning>,
+}
+
+/// Parameters for the materialize_view operation
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Parser)]
+#[command(
+    name = "materialize-view",
+    about = "Write the results of a saved task search into a note"
+)]
+pub struct MaterializeViewRequest {
+    /// Path to vault (CLI only - not used in HTTP/MCP)
+    #[arg(index = 1, required = true, help = "Path to vault")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub path: Option<PathBuf>,
+
+    #[arg(index = 2, required = true, help = "Name of the view")]
+    #[schemars(description = "Name of the view to run")]
+    pub name: String,
+
+    #[arg(
+        long,
+        help = "Note to write the results into, relative to the vault (defaults to the view's note)"
+    )]
+    #[schemars(
+        description = "Vault-relative .md note to write the results into. Default: the note set in the view's configuration"
+    )]
+    pub note: Option<String>,
+
+    #[arg(long, help = "Include notes under archived paths")]
+    #[schemars(
+        description = "If true, include notes under the configured archived_paths. Default: false"
+    )]
+    pub include_archived: Option<bool>,
+
+    #[arg(long, help = "Limit the number of tasks written")]
+    #[schemars(description = "Limit the number of tasks written (overrides the view's limit)")]
+    pub limit: Option<usize>,
+
+    #[arg(long, help = "Fail if any file can't be read instead of skipping it")]
+    #[schemars(
+        description = "If true, fail when a file is skipped (unreadable, invalid frontmatter, larger than max_file_size, or cut off by a truncating scan limit) instead of reporting it in warnings. Default: false"
+    )]
+    pub strict: Option<bool>,
+}
+
+/// Response from the materialize_view operation
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct MaterializeViewResponse {
+    /// Name of the view that ran
+    pub name: String,
+    /// Vault-relative path of the note written
+    pub file_path: String,
+    /// Number of tasks in the block
+    pub task_count: usize,
+    /// Whether the note was created
+    pub created: bool,
+    /// Whether the note changed; an unchanged note is not rewritten
+    pub changed: bool,
+    /// Files that were skipped (unreadable, invalid frontmatter, larger
+    /// than `max_file_size`, or cut off by a truncating scan limit)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ExtractionWarning>,
+}
+
+/// Capability for saved searches
+pub struct ViewCapability {
+    base_path: PathBuf,
+    config: Arc<Config>,
+    task_extractor: Arc<TaskExtractor>,
+}
+
+impl ViewCapability {
+    /// Create a new ViewCapability
+    pub fn new(base_path: PathBuf, config: Arc<Config>) -> Self {
+        Self {
+            base_path,
+            task_extractor: Arc::new(TaskExtractor::new(Arc::clone(&config))),
+            config,
+        }
+    }
+
+    /// List the configured views
+    pub async fn list_views(
+        &self,
+        _request: ListViewsRequest,
+    ) -> CapabilityResult<ListViewsResponse> {
+        let views = self
+            .config
+            .views
+            .iter()
+            .map(|(name, view)| ViewInfo {
+                name: name.clone(),
+                description: view.description.clone(),
+                query: view.query.clone(),
+                filter: view.filter.clone(),
+                limit: view.limit,
+                note: view.note.clone(),
+            })
+            .collect();
+
+        Ok(ListViewsResponse { views })
+    }
+
+    /// Run a view by name
+    pub async fn run_view(&self, request: RunViewRequest) -> CapabilityResult<RunViewResponse> {
+        let view = self.config.views.get(&request.name).ok_or_else(|| {
+            not_found(format!(
+                "Unknown view '{}'. Available views: {}",
+                request.name,
+                self.config
+                    .views
+                    .keys()
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))
+        })?;
+
+        let (tasks, warnings) = self
+            .task_extractor
+            .extract_tasks_with_warnings(&self.base_path)
+            .map_err(|e| scan_error("Failed to extract tasks", e))?;
+        check_strict(&warnings, request.strict)?;
+
+        let filter = match view.query {
+            Some(ref query) => {
+                let known_tags: Vec<String> = tasks
+                    .iter()
+                    .flat_map(|task| task.tags.iter().cloned())
+                    .collect::<BTreeSet<_>>()
+                    .into_iter()
+                    .collect();
+                let interpreted = interpret_query(
+                    query,
+                    &known_tags,
+                    Local::now().date_naive(),
+                    self.config.locale,
+                )
+                .filter;
+                overlay_filter(interpreted, view)
+            }
+            None => view.filter.clone(),
+        };
+
+        let mut tasks = filter_tasks(tasks, &filter.clone().within(&self.base_path));
+        exclude_archived(
+            &mut tasks,
+            &self.config,
+            request.include_archived.unwrap_or(false),
+        );
+        let limit = request
+            .limit
+            .or(view.limit)
+            .unwrap_or_else(get_default_limit);
+        tasks.truncate(limit);
+
+        Ok(RunViewResponse {
+            name: request.name,
+            filter,
+            tasks,
+            warnings,
+        })
+    }
+
+    /// Run a view by name and write its results into a note
+    pub async fn materialize_view(
+        &self,
+        request: MaterializeViewRequest,
+    ) -> CapabilityResult<MaterializeViewResponse> {
+        let note = request
+            .note
+            .clone()
+            .or_else(|| {
+                self.config
+                    .views
+                    .get(&request.name)
+                    .and_then(|view| view.note.clone())
+            })
+            .ok_or_else(|| {
+                invalid_params(format!(
+                    "No note given and view '{}' has no note configured",
+                    request.name
+                ))
+            })?;
+        let relative = from_slash(&note);
+        if relative.extension().and_then(|ext| ext.to_str()) != Some("md")
+            || !relative
+                .components()
+                .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+        {
+            return Err(invalid_params(format!(
+                "Note '{}' must be a .md path inside the vault",
+                note
+            )));
+        }
+
+        let response = self
+            .run_view(RunViewRequest {
+                path: None,
+                name: request.name,
+                include_archived: request.include_archived,
+                limit: request.limit,
+                strict: request.strict,
+            })
+            .await?;
+        let block = render_block(&response.name, &response.tasks);
+
+        let mut path = self.base_path.join(&relative);
+        let existing = if path.exists() {
+            // An existing note must not lead outside the vault through a symlink
+            path = PathResolver::new(&self.base_path)?.resolve_note(&to_slash(&relative))?;
+            stamp_before_write(&path, &self.config)?;
+            Some(
+                fs::read_to_string(&path)
+                    .map_err(|e| internal_error(format!("Failed to read note: {}", e)))?,
+            )
+        } else {
+            None
+        };
+
+        let content = match existing {
+            Some(ref existing) => replace_block(existing, &response.name, &block)
+                .map_err(|e| conflict(format!("Note '{}' {}", note, e)))?,
+            None => block,
+        };
+        let changed = existing.as_deref() != Some(content.as_str());
+        if changed {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)
+                    .map_err(|e| internal_error(format!("Failed to create folder: {}", e)))?;
+            }
+            fs::write(&path, content)
+                .map_err(|e| internal_error(format!("Failed to write note: {}", e)))?;
+        }
+
+        Ok(MaterializeViewResponse {
+            name: response.name,
+            file_path: to_slash(&relative),
+            task_count: response.tasks.len(),
+            created: existing.is_none(),
+            changed,
+            warnings: response.warnings,
+        })
+    }
+}
+
+/// Markers opening and closing the materialized block of view `name`
+fn block_markers(name: &str) -> (String, String) {
+    (
+        format!("<!-- view:{} -->", name),
+        format!("<!-- /view:{} -->", name),
+    )
+}
+
+/// The materialized block of view `name`, markers included
+///
+/// Tasks are plain bullets (see `scheduler::render_report`), so the block
+/// doesn't feed back into the view's own results.
+fn render_block(name: &str, tasks: &[Task]) -> String {
+    let (start, end) = block_markers(name);
+    let mut block = format!("{}\n", start);
+    if tasks.is_empty() {
+        block.push_str("_No matching tasks._\n");
+    }
+    for task in tasks {
+        let value = serde_json::to_value(task).unwrap_or_default();
+        block.push_str(&render_task(&value));
+        block.push('\n');
+    }
+    block.push_str(&end);
+    block.push('\n');
+    block
+}
+
+/// Replace the block of view `name` in `content` with `block`, or append
+/// `block` as a new paragraph if the note has none yet
+fn replace_block(content: &str, name: &str, block: &str) -> Result<String, String> {
+    let (start, end) = block_markers(name);
+    let Some(open) = content.find(&start) else {
+        let mut content = content.to_string();
+        if !content.is_empty() {
+            if !content.ends_with('\n') {
+                content.push('\n');
+            }
+            if !content.ends_with("\n\n") {
+                content.push('\n');
+            }
+        }
+        content.push_str(block);
+        return Ok(content);
+    };
+    let close = content[open..]
+        .find(&end)
+        .map(|offset| open + offset + end.len())
+        .ok_or_else(|| format!("has {} without a closing {}", start, end))?;
+    // The block brings its own line break after the closing marker
+    let rest = &content[close..];
+    let rest = rest
+        .strip_prefix("\r\n")
+        .or_else(|| rest.strip_prefix('\n'))
+        .unwrap_or(rest);
+    Ok(format!("{}{}{}", &content[..open], block, rest))
+}
+
+/// Apply a view's explicit filters on top of those derived from its query
+fn overlay_filter(base: FilterOptions, view: &ViewConfig) -> FilterOptions {
+    let explicit = view.filter.clone();
+    FilterOptions {
+        status: explicit.status.or(base.status),
+        priority: explicit.priority.or(base.priority),
+        min_priority: explicit.min_priority.or(base.min_priority),
+        due_on: explicit.due_on.or(base.due_on),
+        due_before: explicit.due_before.or(base.due_before),
+        due_after: explicit.due_after.or(base.due_after),
+        scheduled_on: explicit.scheduled_on.or(base.scheduled_on),
+        scheduled_before: explicit.scheduled_before.or(base.scheduled_before),
+        scheduled_after: explicit.scheduled_after.or(base.scheduled_after),
+        starts_after: explicit.starts_after.or(base.starts_after),
+        completed_on: explicit.completed_on.or(base.completed_on),
+        completed_before: explicit.completed_before.or(base.completed_before),
+        completed_after: explicit.completed_after.or(base.completed_after),
+        tags: explicit.tags.or(base.tags),
+        exclude_tags: explicit.exclude_tags.or(base.exclude_tags),
+        no_due_date: explicit.no_due_date || base.no_due_date,
+        include_quoted: explicit.include_quoted || base.include_quoted,
+        assignee: explicit.assignee.or(base.assignee),
+        unassigned: explicit.unassigned || base.unassigned,
+        recurring: explicit.recurring || base.recurring,
+        path_glob: explicit.path_glob.or(base.path_glob),
+        path_includes: explicit.path_includes.or(base.path_includes),
+        path_root: None,
+        text: explicit.text.or(base.text),
+        text_regex: explicit.text_regex || base.text_regex,
+        heading: explicit.heading.or(base.heading),
+        blocked: explicit.blocked.or(base.blocked),
+    }
+}
+
+/// Operation struct for list_views (HTTP, CLI, and MCP)
+pub struct ListViewsOperation {
+    capability: Arc<ViewCapability>,
+}
+
+impl ListViewsOperation {
+    pub fn new(capability: Arc<ViewCapability>) -> Self {
+        Self { capability }
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::operation::Operation for ListViewsOperation {
+    fn name(&self) -> &'static str {
+        list_views::CLI_NAME
+    }
+
+    fn path(&self) -> &'static str {
+        list_views::HTTP_PATH
+    }
+
+    fn description(&self) -> &'static str {
+        list_views::DESCRIPTION
+    }
+
+    fn read_only(&self) -> bool {
+        true
+    }
+
+    fn get_command(&self) -> clap::Command {
+        // Get command from request struct's Parser derive
+        ListViewsRequest::command()
+    }
+
+    async fn execute_json(&self, json: serde_json::Value) -> Result<serde_json::Value, Error> {
+        crate::http_router::execute_json_operation(json, |req| self.capability.list_views(req))
+            .await
+    }
+
+    async fn execute_from_args(
+        &self,
+        matches: &clap::ArgMatches,
+        _registry: &crate::capabilities::CapabilityRegistry,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        // Parse request from ArgMatches
+        let request = ListViewsRequest::from_arg_matches(matches)?;
+
+        // Handle CLI-specific path if present
+        let response = if let Some(ref path) = request.path {
+            let config = Arc::new(Config::load_from_base_path(path.as_path()));
+            let capability = ViewCapability::new(path.clone(), config);
+            let mut req_without_path = request;
+            req_without_path.path = None;
+            capability.list_views(req_without_path).await?
+        } else {
+            self.capability.list_views(request).await?
+        };
+
+        // Serialize to JSON
+        Ok(serde_json::to_string_pretty(&response)?)
+    }
+
+    fn input_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(ListViewsRequest)).unwrap()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(ListViewsResponse)).unwrap()
+    }
+}
+
+/// Operation struct for run_view (HTTP, CLI, and MCP)
+pub struct RunViewOperation {
+    capability: Arc<ViewCapability>,
+}
+
+impl RunViewOperation {
+    pub fn new(capability: Arc<ViewCapability>) -> Self {
+        Self { capability }
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::operation::Operation for RunViewOperation {
+    fn name(&self) -> &'static str {
+        run_view::CLI_NAME
+    }
+
+    fn path(&self) -> &'static str {
+        run_view::HTTP_PATH
+    }
+
+    fn description(&self) -> &'static str {
+        run_view::DESCRIPTION
+    }
+
+    fn read_only(&self) -> bool {
+        true
+    }
+
+    fn get_command(&self) -> clap::Command {
+        // Get command from request struct's Parser derive
+        RunViewRequest::command()
+    }
+
+    async fn execute_json(&self, json: serde_json::Value) -> Result<serde_json::Value, Error> {
+        crate::http_router::execute_json_operation(json, |req| self.capability.run_view(req)).await
+    }
+
+    async fn execute_from_args(
+        &self,
+        matches: &clap::ArgMatches,
+        _registry: &crate::capabilities::CapabilityRegistry,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        // Parse request from ArgMatches
+        let request = RunViewRequest::from_arg_matches(matches)?;
+
+        // Handle CLI-specific path if present
+        let response = if let Some(ref path) = request.path {
+            let config = Arc::new(Config::load_from_base_path(path.as_path()));
+            let capability = ViewCapability::new(path.clone(), config);
+            let mut req_without_path = request;
+            req_without_path.path = None;
+            capability.run_view(req_without_path).await?
+        } else {
+            self.capability.run_view(request).await?
+        };
+
+        // Serialize to JSON
+        Ok(serde_json::to_string_pretty(&response)?)
+    }
+
+    fn input_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(RunViewRequest)).unwrap()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(RunViewResponse)).unwrap()
+    }
+}
+
+/// Operation struct for materialize_view (HTTP, CLI, and MCP)
+pub struct MaterializeViewOperation {
+    capability: Arc<ViewCapability>,
+}
+
+impl MaterializeViewOperation {
+    pub fn new(capability: Arc<ViewCapability>) -> Self {
+        Self { capability }
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::operation::Operation for MaterializeViewOperation {
+    fn name(&self) -> &'static str {
+        materialize_view::CLI_NAME
+    }
+
+    fn path(&self) -> &'static str {
+        materialize_view::HTTP_PATH
+    }
+
+    fn description(&self) -> &'static str {
+        materialize_view::DESCRIPTION
+    }
+
+    fn get_command(&self) -> clap::Command {
+        // Get command from request struct's Parser derive
+        MaterializeViewRequest::command()
+    }
+
+    async fn execute_json(&self, json: serde_json::Value) -> Result<serde_json::Value, Error> {
+        crate::http_router::execute_json_operation(json, |req| {
+            self.capability.materialize_view(req)
+        })
+        .await
+    }
+
+    async fn execute_from_args(
+        &self,
+        matches: &clap::ArgMatches,
+        _registry: &crate::capabilities::CapabilityRegistry,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        // Parse request from ArgMatches
+        let request = MaterializeViewRequest::from_arg_matches(matches)?;
+
+        // Handle CLI-specific path if present
+        let response = if let Some(ref path) = request.path {
+            let config = Arc::new(Config::load_from_base_path(path.as_path()));
+            let capability = ViewCapability::new(path.clone(), config);
+            let mut req_without_path = request;
+            req_without_path.path = None;
+            capability.materialize_view(req_without_path).await?
+        } else {
+            self.capability.materialize_view(request).await?
+        };
+
+        // Serialize to JSON
+        Ok(serde_json::to_string_pretty(&response)?)
+    }
+
+    fn input_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(MaterializeViewRequest)).unwrap()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
+        use schemars::schema_for;
+        serde_json::to_value(schema_for!(MaterializeViewResponse)).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extractor::TaskStatus;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn create_vault() -> (TempDir, Arc<Config>) {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("tasks.md"),
+            "- [ ] Inbox item\n\
+             - [ ] Scheduled 📅 2025-01-10\n\
+             - [ ] Work item #work\n\
+             - [x] Done item\n",
+        )
+        .unwrap();
+        fs::create_dir_all(temp_dir.path().join("Archive")).unwrap();
+        fs::write(temp_dir.path().join("Archive/old.md"), "- [ ] Old item\n").unwrap();
+        let config: Config = toml::from_str(
+            r#"
+archived_paths = ["Archive/"]
+
+[views.inbox]
+description = "Open tasks without a due date"
+status = "incomplete"
+no_due_date = true
+
+[views.work]
+query = "open work tasks"
+limit = 1
+note = "Views/Work.md"
+"#,
+        )
+        .unwrap();
+        (temp_dir, Arc::new(config))
+    }
+
+    fn run_request(name: &str) -> RunViewRequest {
+        RunViewRequest {
+            path: None,
+            name: name.to_string(),
+            include_archived: None,
+            limit: None,
+            strict: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_views() {
+        let (temp_dir, config) = create_vault();
+        let capability = ViewCapability::new(temp_dir.path().to_path_buf(), config);
+
+        let response = capability
+            .list_views(ListViewsRequest { path: None })
+            .await
+            .unwrap();
+
+        let names: Vec<_> = response.views.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, vec!["inbox", "work"]);
+        assert_eq!(response.views[1].query.as_deref(), Some("open work tasks"));
+    }
+
+    #[tokio::test]
+    async fn test_run_view_with_filters() {
+        let (temp_dir, config) = create_vault();
+        let capability = ViewCapability::new(temp_dir.path().to_path_buf(), config);
+
+        let response = capability.run_view(run_request("inbox")).await.unwrap();
+
+        let contents: Vec<_> = response.tasks.iter().map(|t| t.content.as_str()).collect();
+        assert_eq!(contents, vec!["Inbox item", "Work item #work"]);
+        assert_eq!(response.filter.status, Some(TaskStatus::Incomplete));
+    }
+
+    #[tokio::test]
+    async fn test_run_view_include_archived() {
+        let (temp_dir, config) = create_vault();
+        let capability = ViewCapability::new(temp_dir.path().to_path_buf(), config);
+
+        let mut request = run_request("inbox");
+        request.include_archived = Some(true);
+        let response = capability.run_view(request).await.unwrap();
+
+        let mut contents: Vec<_> = response.tasks.iter().map(|t| t.content.as_str()).collect();
+        contents.sort();
+        assert_eq!(contents, vec!["Inbox item", "Old item", "Work item #work"]);
+    }
+
+    #[tokio::test]
+    async fn test_run_view_with_query() {
+        let (temp_dir, config) = create_vault();
+        let capability = ViewCapability::new(temp_dir.path().to_path_buf(), config);
+
+        let response = capability.run_view(run_request("work")).await.unwrap();
+        assert_eq!(response.filter.tags, Some(vec!["work".to_string()]));
+        assert_eq!(response.tasks.len(), 1);
+        assert_eq!(response.tasks[0].content, "Work item #work");
+
+        let mut request = run_request("unknown");
+        request.limit = Some(5);
+        let error = capability.run_view(request).await.unwrap_err();
+        assert!(error.to_string().contains("inbox, work"));
+    }
+
+    fn materialize_request(name: &str, note: Option<&str>) -> MaterializeViewRequest {
+        MaterializeViewRequest {
+            path: None,
+            name: name.to_string(),
+            note: note.map(str::to_string),
+            include_archived: None,
+            limit: None,
+            strict: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_materialize_view() {
+        let (temp_dir, config) = create_vault();
+        let capability = ViewCapability::new(temp_dir.path().to_path_buf(), config);
+        let note = temp_dir.path().join("Views/Work.md");
+
+        let response = capability
+            .materialize_view(materialize_request("work", None))
+            .await
+            .unwrap();
+        assert!(response.created && response.changed);
+        assert_eq!(response.file_path, "Views/Work.md");
+        assert_eq!(
+            fs::read_to_string(&note).unwrap(),
+            "<!-- view:work -->\n- Work item #work ([[tasks]])\n<!-- /view:work -->\n"
+        );
+
+        // The block is replaced in place, and the rest of the note is kept
+        fs::write(
+            &note,
+            "# Work\n\n<!-- view:work -->\n- stale\n<!-- /view:work -->\nNotes\n",
+        )
+        .unwrap();
+        let response = capability
+            .materialize_view(materialize_request("work", None))
+            .await
+            .unwrap();
+        assert!(!response.created && response.changed);
+        assert_eq!(
+            fs::read_to_string(&note).unwrap(),
+            "# Work\n\n<!-- view:work -->\n- Work item #work ([[tasks]])\n<!-- /view:work -->\nNotes\n"
+        );
+        let response = capability
+            .materialize_view(materialize_request("work", None))
+            .await
+            .unwrap();
+        assert!(!response.changed);
+
+        // A second view gets its own block, and its bullets aren't tasks
+        let response = capability
+            .materialize_view(materialize_request("inbox", Some("Views/Work.md")))
+            .await
+            .unwrap();
+        assert_eq!(response.task_count, 2);
+        let content = fs::read_to_string(&note).unwrap();
+        assert!(content.ends_with(
+            "Notes\n\n<!-- view:inbox -->\n- Inbox item ([[tasks]])\n- Work item #work ([[tasks]])\n<!-- /view:inbox -->\n"
+        ));
+        let response = capability.run_view(run_request("inbox")).await.unwrap();
+        assert_eq!(response.tasks.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_materialize_view_errors() {
+        let (temp_dir, config) = create_vault();
+        let capability = ViewCapability::new(temp_dir.path().to_path_buf(), config);
+
+        let error = capability
+            .materialize_view(materialize_request("inbox", None))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code(), "invalid_params");
+        let error = capability
+            .materialize_view(materialize_request("inbox", Some("../out.md")))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code(), "invalid_params");
+
+        fs::write(
+            temp_dir.path().join("open.md"),
+            "<!-- view:inbox -->\n- x\n",
+        )
+        .unwrap();
+        let error = capability
+            .materialize_view(materialize_request("inbox", Some("open.md")))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code(), "conflict");
+    }
+
+    #[test]
+    fn test_overlay_filter_prefers_explicit() {
+        let view = ViewConfig {
+            filter: FilterOptions {
+                status: Some(TaskStatus::Completed),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let base = FilterOptions {
+            status: Some(TaskStatus::Incomplete),
+            tags: Some(vec!["work".to_string()]),
+            ..Default::default()
+        };
+
+        let filter = overlay_filter(base, &view);
+        assert_eq!(filter.status, Some(TaskStatus::Completed));
+        assert_eq!(filter.tags, Some(vec!["work".to_string()]));
+    }
+}
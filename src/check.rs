@@ -0,0 +1,405 @@
+//! `check` command: enforce a task policy from git hooks
+//!
+//! Given the changed files (as arguments or one per line on stdin), finds the
+//! incomplete tasks that were added since a git revision and reports those
+//! breaking the `[check]` rules, e.g. a task without a due date. Exits with
+//! status 1 if any do:
+//!
+//! ```sh
+//! # .git/hooks/pre-commit
+//! git diff --cached --name-only --diff-filter=AM | markdown-todo-extractor check --staged
+//! ```
+
+use crate::capabilities::CapabilityRegistry;
+use crate::config::{CheckConfig, Config};
+use crate::extractor::{Task, TaskExtractor, TaskStatus};
+use crate::git_dates::GitRepo;
+use crate::operation::Operation;
+use crate::unicode::fold;
+use clap::{CommandFactory, FromArgMatches, Parser};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Check newly added tasks against the task policy
+#[derive(Parser, Debug)]
+#[command(
+    name = "check",
+    about = "Check tasks added in changed files against the [check] policy (for git hooks)"
+)]
+pub struct CheckCommand {
+    /// Changed files to check (default: read file names from stdin)
+    #[arg(index = 1)]
+    pub files: Vec<PathBuf>,
+
+    /// Revision that tasks are compared against; tasks not in it are new
+    #[arg(long, default_value = "HEAD")]
+    pub base: String,
+
+    /// Check the staged version of each file instead of the working tree
+    #[arg(long)]
+    pub staged: bool,
+}
+
+/// A new task that breaks the policy
+#[derive(Debug, Clone, PartialEq)]
+pub struct Violation {
+    pub file_path: String,
+    pub line_number: usize,
+    pub content: String,
+    /// Broken rules, e.g. "missing due date"
+    pub problems: Vec<String>,
+}
+
+/// Result of checking a set of files
+#[derive(Debug, Default)]
+pub struct CheckReport {
+    /// Number of new incomplete tasks found
+    pub checked: usize,
+    pub violations: Vec<Violation>,
+}
+
+impl CheckReport {
+    /// Human-readable report
+    pub fn render(&self) -> String {
+        if self.violations.is_empty() {
+            return format!("{} new task(s) checked, no problems found", self.checked);
+        }
+
+        let mut report = format!(
+            "{} of {} new task(s) break the task policy:\n",
+            self.violations.len(),
+            self.checked
+        );
+        for violation in &self.violations {
+            report.push_str(&format!(
+                "\n{}:{}: {}\n",
+                violation.file_path, violation.line_number, violation.content
+            ));
+            for problem in &violation.problems {
+                report.push_str(&format!("  - {}\n", problem));
+            }
+        }
+        report
+    }
+}
+
+/// Check the new incomplete tasks in `files`
+///
+/// Tasks are new if their line doesn't appear in the file at `base` (all of a
+/// file's tasks are new when it isn't in git). With `staged`, the staged
+/// version of each file is checked instead of the working tree.
+pub fn check_files(
+    base_path: &Path,
+    files: &[PathBuf],
+    config: &Config,
+    base: &str,
+    staged: bool,
+) -> CheckReport {
+    let extractor = TaskExtractor::new(Arc::new(config.clone()));
+    let repo = GitRepo::discover(base_path);
+    let mut report = CheckReport::default();
+
+    for file in files {
+        if !config.is_scannable_markdown(file) || config.should_exclude(file) {
+            continue;
+        }
+
+        let current = match (&repo, staged) {
+            (Some(repo), true) => repo.show("", file),
+            _ => fs::read_to_string(file).ok(),
+        };
+        // Deleted files have nothing to check
+        let Some(current) = current else {
+            continue;
+        };
+        let previous = repo
+            .as_ref()
+            .and_then(|repo| repo.show(base, file))
+            .unwrap_or_default();
+
+        let tasks = new_tasks(
+            extractor.extract_tasks_from_content(&previous, file),
+            extractor.extract_tasks_from_content(&current, file),
+        );
+        for task in tasks {
+            report.checked += 1;
+            let problems = policy_problems(&task, &config.check);
+            if !problems.is_empty() {
+                report.violations.push(Violation {
+                    file_path: file.display().to_string(),
+                    line_number: task.line_number,
+                    content: task.content,
+                    problems,
+                });
+            }
+        }
+    }
+
+    report
+}
+
+/// Incomplete tasks in `current` whose line isn't in `previous`
+///
+/// Lines are compared as a multiset, so duplicating an existing task line
+/// counts as a new task.
+fn new_tasks(previous: Vec<Task>, current: Vec<Task>) -> Vec<Task> {
+    let mut existing: HashMap<String, usize> = HashMap::new();
+    for task in previous {
+        *existing
+            .entry(task.raw_line.trim().to_string())
+            .or_default() += 1;
+    }
+
+    current
+        .into_iter()
+        .filter(|task| {
+            match existing.get_mut(task.raw_line.trim()) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    return false;
+                }
+                _ => {}
+            }
+            task.status == TaskStatus::Incomplete
+        })
+        .collect()
+}
+
+/// Normalize a tag for comparison: no leading `#`, case-folded
+fn normalize_tag(tag: &str) -> String {
+    fold(tag.trim_start_matches('#'))
+}
+
+/// Rules broken by a task
+fn policy_problems(task: &Task, policy: &CheckConfig) -> Vec<String> {
+    let tags: Vec<String> = task.tags.iter().map(|tag| normalize_tag(tag)).collect();
+    let has_any = |wanted: &[String]| wanted.iter().any(|tag| tags.contains(&normalize_tag(tag)));
+    if has_any(&policy.exempt_tags) {
+        return Vec::new();
+    }
+
+    let mut problems = Vec::new();
+    if policy.require_due_date && task.due_date.is_none() {
+        problems.push("missing due date".to_string());
+    }
+    if policy.require_tags && tags.is_empty() {
+        problems.push("missing tag".to_string());
+    }
+    if !policy.allowed_tags.is_empty() && !tags.is_empty() && !has_any(&policy.allowed_tags) {
+        problems.push(format!(
+            "needs one of the tags: {}",
+            policy.allowed_tags.join(", ")
+        ));
+    }
+    problems
+}
+
+/// CliOperation implementation for the check command
+#[derive(Default)]
+pub struct CheckOperation;
+
+impl CheckOperation {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait::async_trait]
+impl Operation for CheckOperation {
+    fn name(&self) -> &'static str {
+        "check"
+    }
+
+    fn path(&self) -> &'static str {
+        // CheckOperation is CLI-only and doesn't have an HTTP endpoint
+        ""
+    }
+
+    fn description(&self) -> &'static str {
+        "Check tasks added in changed files against the [check] policy"
+    }
+
+    fn get_command(&self) -> clap::Command {
+        CheckCommand::command()
+    }
+
+    async fn execute_json(&self, _json: Value) -> Result<Value, crate::error::Error> {
+        // CheckOperation is CLI-only and doesn't support JSON execution
+        Err(crate::error::unsupported(
+            "check command is only available via CLI",
+        ))
+    }
+
+    async fn execute_from_args(
+        &self,
+        matches: &clap::ArgMatches,
+        _registry: &CapabilityRegistry,
+    ) -> Result<String, Box<dyn Error>> {
+        let cmd = CheckCommand::from_arg_matches(matches)?;
+        let files = if cmd.files.is_empty() {
+            std::io::stdin()
+                .lock()
+                .lines()
+                .map(|line| line.map(|line| PathBuf::from(line.trim())))
+                .filter(|path| !matches!(path, Ok(path) if path.as_os_str().is_empty()))
+                .collect::<Result<Vec<_>, _>>()?
+        } else {
+            cmd.files
+        };
+
+        // The global --path sets the vault root holding the configuration
+        let base_path = matches
+            .try_get_one::<String>("path")
+            .ok()
+            .flatten()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from("."));
+        let config = Config::load_from_base_path(&base_path);
+
+        let report = check_files(&base_path, &files, &config, &cmd.base, cmd.staged);
+        if !report.violations.is_empty() {
+            // Hooks only look at the exit status; print the report for people
+            eprint!("{}", report.render());
+            std::process::exit(1);
+        }
+        Ok(report.render())
+    }
+
+    fn input_schema(&self) -> Value {
+        // CheckOperation is CLI-only and doesn't have a meaningful JSON schema
+        json!({
+            "type": "object",
+            "properties": {}
+        })
+    }
+
+    fn output_schema(&self) -> Value {
+        // CheckOperation is CLI-only and doesn't return a JSON response
+        json!({
+            "type": "object",
+            "properties": {}
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(args)
+            .env("GIT_AUTHOR_NAME", "Test")
+            .env("GIT_AUTHOR_EMAIL", "test@example.com")
+            .env("GIT_COMMITTER_NAME", "Test")
+            .env("GIT_COMMITTER_EMAIL", "test@example.com")
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    fn task(line: &str) -> Task {
+        TaskExtractor::new(Arc::new(Config::default()))
+            .extract_tasks_from_content(line, Path::new("todo.md"))
+            .remove(0)
+    }
+
+    #[test]
+    fn test_policy_problems() {
+        let policy = CheckConfig::default();
+        assert_eq!(
+            policy_problems(&task("- [ ] Bare"), &policy),
+            vec!["missing due date", "missing tag"]
+        );
+        assert!(policy_problems(&task("- [ ] Fine #work 📅 2025-01-10"), &policy).is_empty());
+
+        let policy = CheckConfig {
+            require_due_date: false,
+            allowed_tags: vec!["#work".to_string()],
+            exempt_tags: vec!["someday".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            policy_problems(&task("- [ ] Wrong tag #home"), &policy),
+            vec!["needs one of the tags: #work"]
+        );
+        assert!(policy_problems(&task("- [ ] Right tag #Work"), &policy).is_empty());
+        assert!(policy_problems(&task("- [ ] Later #someday"), &policy).is_empty());
+    }
+
+    #[test]
+    fn test_new_tasks() {
+        let previous = vec![task("- [ ] Old"), task("- [ ] Twice")];
+        let current = vec![
+            task("- [ ] Old"),
+            task("- [ ] Twice"),
+            task("- [ ] Twice"),
+            task("- [ ] Brand new"),
+            task("- [x] Done already"),
+        ];
+        let contents: Vec<String> = new_tasks(previous, current)
+            .into_iter()
+            .map(|t| t.content)
+            .collect();
+        assert_eq!(contents, vec!["Twice", "Brand new"]);
+    }
+
+    #[test]
+    fn test_check_files_against_head() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let file = root.join("todo.md");
+        git(root, &["init", "-q"]);
+        fs::write(&file, "- [ ] Legacy task without metadata\n").unwrap();
+        git(root, &["add", "-A"]);
+        git(root, &["commit", "-q", "-m", "init"]);
+
+        fs::write(
+            &file,
+            "- [ ] Legacy task without metadata\n\
+             - [ ] Staged task #work 📅 2025-01-10\n",
+        )
+        .unwrap();
+        git(root, &["add", "-A"]);
+        fs::write(
+            &file,
+            "- [ ] Legacy task without metadata\n\
+             - [ ] Staged task #work 📅 2025-01-10\n\
+             - [ ] Unstaged task\n",
+        )
+        .unwrap();
+
+        let config = Config::default();
+        let files = vec![file.clone(), root.join("deleted.md"), root.join("a.txt")];
+
+        let report = check_files(root, &files, &config, "HEAD", true);
+        assert_eq!(report.checked, 1);
+        assert!(report.violations.is_empty());
+
+        let report = check_files(root, &files, &config, "HEAD", false);
+        assert_eq!(report.checked, 2);
+        assert_eq!(
+            report.violations,
+            vec![Violation {
+                file_path: file.display().to_string(),
+                line_number: 3,
+                content: "Unstaged task".to_string(),
+                problems: vec!["missing due date".to_string(), "missing tag".to_string()],
+            }]
+        );
+        assert!(
+            report
+                .render()
+                .contains("1 of 2 new task(s) break the task policy")
+        );
+    }
+}
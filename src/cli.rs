@@ -1,6 +1,5 @@
 use crate::capabilities::CapabilityRegistry;
 use clap::{CommandFactory, FromArgMatches, Parser, Subcommand as ClapSubcommand};
-use rmcp::model::ErrorData;
 use std::path::PathBuf;
 
 /// Server mode options for MCP
@@ -21,6 +20,11 @@ pub enum ServerMode {
         /// Port for HTTP MCP server
         #[arg(long, default_value = "8000")]
         port: u16,
+
+        /// Also serve the operation registry over gRPC on this port
+        #[cfg(feature = "grpc")]
+        #[arg(long)]
+        grpc_port: Option<u16>,
     },
 }
 
@@ -40,9 +44,19 @@ pub struct ServeCommand {
     /// Server mode (stdio or http)
     #[command(subcommand)]
     pub mode: ServerMode,
+
+    /// Seconds an operation may run before it fails with a timeout error (0 for no limit)
+    #[arg(long, global = true, default_value_t = 30)]
+    pub timeout: u64,
+
+    /// Keep extracted tasks and outlines in memory and watch the vault so only
+    /// changed files are parsed again (implies --cache)
+    #[arg(long, global = true)]
+    pub watch: bool,
 }
 
 /// CliOperation implementation for serve command
+#[derive(Default)]
 pub struct ServeOperation;
 
 impl ServeOperation {
@@ -70,13 +84,14 @@ impl crate::operation::Operation for ServeOperation {
         ServeCommand::command()
     }
 
-    async fn execute_json(&self, _json: serde_json::Value) -> Result<serde_json::Value, ErrorData> {
+    async fn execute_json(
+        &self,
+        _json: serde_json::Value,
+    ) -> Result<serde_json::Value, crate::error::Error> {
         // ServeOperation is CLI-only and doesn't support JSON execution
-        Err(ErrorData {
-            code: rmcp::model::ErrorCode(-32601),
-            message: std::borrow::Cow::from("serve command is only available via CLI"),
-            data: None,
-        })
+        Err(crate::error::unsupported(
+            "serve command is only available via CLI",
+        ))
     }
 
     async fn execute_from_args(
@@ -99,4 +114,12 @@ impl crate::operation::Operation for ServeOperation {
             "properties": {}
         })
     }
+
+    fn output_schema(&self) -> serde_json::Value {
+        // ServeOperation is CLI-only and doesn't return a JSON response
+        serde_json::json!({
+            "type": "object",
+            "properties": {}
+        })
+    }
 }
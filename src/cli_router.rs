@@ -1,4 +1,8 @@
 use crate::capabilities::CapabilityRegistry;
+use crate::config::Config;
+use crate::profiles::Profiles;
+use serde_json::{Map, Value};
+use std::path::PathBuf;
 use std::sync::Arc;
 
 /// Build a clap Command dynamically from all registered operations
@@ -14,11 +18,53 @@ pub fn build_cli(operations: &[Arc<dyn crate::operation::Operation>]) -> clap::C
                 .short('p')
                 .help("Base path to search (defaults to current directory)")
                 .global(true),
+        )
+        .arg(
+            clap::Arg::new("vault")
+                .long("vault")
+                .action(clap::ArgAction::Append)
+                .value_delimiter(',')
+                .help(
+                    "Run against these vaults instead of a path: profile names, vault paths, \
+                     or \"all\" (repeatable or comma-separated)",
+                )
+                .global(true),
+        )
+        .arg(
+            clap::Arg::new("cache")
+                .long("cache")
+                .action(clap::ArgAction::SetTrue)
+                .help(
+                    "Cache extracted tasks and outlines under ~/.cache/markdown-todo-extractor \
+                     and only re-parse files whose modification time or size changed",
+                )
+                .global(true),
         );
 
-    // Add each operation's command definition
+    // Add each operation's command definition. The vault path positional is
+    // optional when --vault is given; a command with one more required
+    // positional (e.g. `search <path> <query>`) then fills that one first.
+    // clap can't skip a leading positional before two required ones, so
+    // commands like `get-section <path> <file> <heading>` keep a required path.
     for operation in operations {
-        cmd = cmd.subcommand(operation.get_command());
+        let mut subcommand = operation.get_command();
+        let required: Vec<_> = subcommand
+            .get_positionals()
+            .filter(|arg| arg.is_required_set())
+            .collect();
+        let vault_path = required
+            .iter()
+            .find(|arg| arg.get_index() == Some(1))
+            .filter(|_| required.len() <= 2)
+            .map(|arg| arg.get_id().clone());
+        if let Some(id) = vault_path {
+            subcommand = subcommand
+                .allow_missing_positional(true)
+                .mut_arg(id, |arg| {
+                    arg.required(false).required_unless_present("vault")
+                });
+        }
+        cmd = cmd.subcommand(subcommand);
     }
 
     cmd
@@ -32,6 +78,18 @@ pub async fn execute_cli(
 ) -> Result<(), Box<dyn std::error::Error>> {
     // Find the matching operation
     if let Some((subcommand_name, sub_matches)) = matches.subcommand() {
+        if sub_matches.get_flag("cache") {
+            crate::cache::enable();
+        }
+
+        if let Some(values) = sub_matches.get_many::<String>("vault") {
+            let values: Vec<String> = values.cloned().collect();
+            let vaults = Profiles::load().resolve(&values)?;
+            let output = execute_in_vaults(subcommand_name, sub_matches, &vaults).await?;
+            println!("{}", serde_json::to_string_pretty(&output)?);
+            return Ok(());
+        }
+
         for operation in operations {
             if operation.name() == subcommand_name {
                 let output = operation.execute_from_args(sub_matches, registry).await?;
@@ -44,3 +102,223 @@ pub async fn execute_cli(
 
     Err("No command specified".into())
 }
+
+/// Run an operation against several vaults in parallel and merge the results
+///
+/// Each vault gets its own configuration and capability registry. The
+/// subcommand's matches must not carry a path, so every operation falls back
+/// to the vault-rooted capability it was built with.
+pub async fn execute_in_vaults(
+    name: &str,
+    matches: &clap::ArgMatches,
+    vaults: &[(String, PathBuf)],
+) -> Result<Value, Box<dyn std::error::Error>> {
+    let mut handles = Vec::with_capacity(vaults.len());
+    for (vault, path) in vaults {
+        let config = Arc::new(Config::load_from_base_path(path));
+        let registry = CapabilityRegistry::new(path.clone(), config);
+        let operation = registry
+            .create_operations()
+            .into_iter()
+            .find(|op| op.name() == name)
+            .ok_or_else(|| format!("{} does not support --vault", name))?;
+
+        let vault = vault.clone();
+        let matches = matches.clone();
+        handles.push(tokio::spawn(async move {
+            let output = operation
+                .execute_from_args(&matches, &registry)
+                .await
+                .map_err(|e| format!("{}: {}", vault, e))?;
+            let value: Value =
+                serde_json::from_str(&output).map_err(|e| format!("{}: {}", vault, e))?;
+            Ok::<_, String>((vault, value))
+        }));
+    }
+
+    let mut results = Vec::with_capacity(handles.len());
+    for handle in handles {
+        results.push(handle.await??);
+    }
+
+    Ok(merge_vault_results(results))
+}
+
+/// Merge per-vault responses into one
+///
+/// Lists are concatenated, tagging each object item with a `vault` field;
+/// counts are summed. For object responses this is done field by field. Any
+/// other value is returned per vault, keyed by vault name.
+pub fn merge_vault_results(results: Vec<(String, Value)>) -> Value {
+    let values: Vec<(&str, &Value)> = results
+        .iter()
+        .map(|(vault, value)| (vault.as_str(), value))
+        .collect();
+    if !values.iter().all(|(_, value)| value.is_object()) {
+        return merge_values(&values);
+    }
+
+    let mut keys: Vec<&String> = Vec::new();
+    for (_, value) in &values {
+        for key in value.as_object().into_iter().flat_map(Map::keys) {
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+    }
+
+    let merged = keys
+        .into_iter()
+        .map(|key| {
+            let field: Vec<(&str, &Value)> = values
+                .iter()
+                .filter_map(|&(vault, value)| Some((vault, value.get(key)?)))
+                .collect();
+            (key.clone(), merge_values(&field))
+        })
+        .collect();
+
+    Value::Object(merged)
+}
+
+/// Merge one value from each vault: concatenate lists, sum counts, or key by vault
+fn merge_values(values: &[(&str, &Value)]) -> Value {
+    if values.iter().all(|(_, value)| value.is_array()) {
+        Value::Array(
+            values
+                .iter()
+                .flat_map(|&(vault, value)| {
+                    value
+                        .as_array()
+                        .into_iter()
+                        .flatten()
+                        .map(move |item| match item {
+                            Value::Object(fields) => {
+                                let mut fields = fields.clone();
+                                fields.insert("vault".to_string(), Value::from(vault));
+                                Value::Object(fields)
+                            }
+                            other => other.clone(),
+                        })
+                })
+                .collect(),
+        )
+    } else if values.iter().all(|(_, value)| value.is_u64()) {
+        Value::from(
+            values
+                .iter()
+                .filter_map(|(_, value)| value.as_u64())
+                .sum::<u64>(),
+        )
+    } else {
+        Value::Object(
+            values
+                .iter()
+                .map(|&(vault, value)| (vault.to_string(), value.clone()))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_merge_vault_results() {
+        let merged = merge_vault_results(vec![
+            (
+                "work".to_string(),
+                json!({"files": [{"name": "a.md"}], "total_count": 1, "tags": ["x"], "root": "/w"}),
+            ),
+            (
+                "personal".to_string(),
+                json!({"files": [{"name": "b.md"}, {"name": "c.md"}], "total_count": 2, "tags": ["y"], "root": "/p"}),
+            ),
+        ]);
+
+        assert_eq!(
+            merged,
+            json!({
+                "files": [
+                    {"name": "a.md", "vault": "work"},
+                    {"name": "b.md", "vault": "personal"},
+                    {"name": "c.md", "vault": "personal"},
+                ],
+                "total_count": 3,
+                "tags": ["x", "y"],
+                "root": {"work": "/w", "personal": "/p"},
+            })
+        );
+    }
+
+    #[test]
+    fn test_vault_flag_makes_path_optional() {
+        let registry = CapabilityRegistry::new(PathBuf::from("."), Arc::new(Config::default()));
+        let cli = build_cli(&registry.create_operations());
+        cli.clone().debug_assert();
+
+        assert!(
+            cli.clone()
+                .try_get_matches_from(["markdown-todo-extractor", "tasks"])
+                .is_err()
+        );
+        let matches = cli
+            .clone()
+            .try_get_matches_from([
+                "markdown-todo-extractor",
+                "tasks",
+                "--vault",
+                "work,personal",
+                "--vault",
+                "other",
+            ])
+            .unwrap();
+        let (_, sub_matches) = matches.subcommand().unwrap();
+        let vaults: Vec<_> = sub_matches.get_many::<String>("vault").unwrap().collect();
+        assert_eq!(vaults, vec!["work", "personal", "other"]);
+
+        // A lone positional goes to the required query, not the vault path
+        let matches = cli
+            .try_get_matches_from(["markdown-todo-extractor", "search", "--vault", "all", "x"])
+            .unwrap();
+        let (_, sub_matches) = matches.subcommand().unwrap();
+        assert_eq!(sub_matches.get_one::<String>("query").unwrap(), "x");
+        assert!(sub_matches.get_one::<PathBuf>("path").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_execute_in_vaults() {
+        let work = TempDir::new().unwrap();
+        let personal = TempDir::new().unwrap();
+        fs::write(work.path().join("todo.md"), "- [ ] Ship release\n").unwrap();
+        fs::write(personal.path().join("todo.md"), "- [ ] Buy milk\n").unwrap();
+
+        let registry = CapabilityRegistry::new(PathBuf::from("."), Arc::new(Config::default()));
+        let matches = build_cli(&registry.create_operations())
+            .try_get_matches_from(["markdown-todo-extractor", "tasks", "--vault", "all"])
+            .unwrap();
+        let (name, sub_matches) = matches.subcommand().unwrap();
+
+        let vaults = vec![
+            ("work".to_string(), work.path().to_path_buf()),
+            ("personal".to_string(), personal.path().to_path_buf()),
+        ];
+        let output = execute_in_vaults(name, sub_matches, &vaults).await.unwrap();
+
+        // The tasks command prints a bare list
+        let tasks: Vec<(&str, &str)> = output
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| (t["vault"].as_str().unwrap(), t["content"].as_str().unwrap()))
+            .collect();
+        assert_eq!(
+            tasks,
+            vec![("work", "Ship release"), ("personal", "Buy milk")]
+        );
+    }
+}
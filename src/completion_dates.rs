@@ -0,0 +1,310 @@
+//! Backfilled task completion dates
+//!
+//! Completed tasks without a `✅ YYYY-MM-DD` marker never match the
+//! `completed_on`/`completed_before`/`completed_after` filters. This module
+//! appends one, dated from the commit that last touched the line (`git blame`)
+//! or, for uncommitted lines and vaults outside git, the note's modification
+//! time.
+//!
+//! With `stamp_completion_dates = true`, write operations call
+//! [`stamp_before_write`] on each note before editing it, while its
+//! modification time still says when it was last changed. `fix <path>`
+//! (CLI only) stamps every note of a vault.
+
+use crate::capabilities::{CapabilityRegistry, CapabilityResult};
+use crate::config::Config;
+use crate::error::internal_error;
+use crate::export::{LineEdit, append_to_line, apply_line_edits};
+use crate::extractor::{TaskExtractor, TaskStatus};
+use crate::git_dates::GitRepo;
+use crate::operation::Operation;
+use crate::path_resolver::to_slash;
+use crate::tag_extractor::collect_markdown_files;
+use crate::warnings::{ExtractionWarning, Warnings};
+use chrono::{DateTime, Local, NaiveDate};
+use clap::{CommandFactory, FromArgMatches, Parser};
+use serde::Serialize;
+use serde_json::{Value, json};
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Where a backfilled completion date came from
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DateSource {
+    /// The commit that last touched the line
+    Git,
+    /// The note's modification time
+    Modified,
+}
+
+/// A completed task given a completion date
+#[derive(Debug, Serialize, PartialEq)]
+pub struct StampedTask {
+    pub line_number: usize,
+    pub completed_date: NaiveDate,
+    pub source: DateSource,
+    /// The task line as written
+    pub line: String,
+}
+
+/// Append `✅ date` to a completed task line
+fn stamp_line(line: &str, date: NaiveDate) -> String {
+    append_to_line(line, &format!("✅ {}", date.format("%Y-%m-%d")))
+}
+
+/// Give each completed task in the note at `path` that lacks a completion
+/// date one, writing the note unless `dry_run`
+///
+/// Dates come from `repo`'s blame of the line when it is committed, and from
+/// the note's modification time otherwise. Lines that change while the note
+/// is being stamped are left alone.
+pub fn stamp_note(
+    path: &Path,
+    extractor: &TaskExtractor,
+    repo: Option<&GitRepo>,
+    dry_run: bool,
+) -> io::Result<Vec<StampedTask>> {
+    let content = fs::read_to_string(path)?;
+    let lines: Vec<&str> = content.lines().collect();
+    let unstamped: Vec<usize> = extractor
+        .extract_tasks_from_content(&content, path)
+        .into_iter()
+        .filter(|task| task.status == TaskStatus::Completed && task.completed_date.is_none())
+        .map(|task| task.line_number)
+        .collect();
+    if unstamped.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let modified = DateTime::<Local>::from(fs::metadata(path)?.modified()?).date_naive();
+    let blame = repo.and_then(|repo| repo.blame(path)).unwrap_or_default();
+    let stamped: Vec<StampedTask> = unstamped
+        .into_iter()
+        .filter_map(|line_number| {
+            let line = lines.get(line_number - 1)?;
+            let (completed_date, source) = match blame.get(&line_number) {
+                Some(origin) => (origin.date, DateSource::Git),
+                None => (modified, DateSource::Modified),
+            };
+            Some(StampedTask {
+                line_number,
+                completed_date,
+                source,
+                line: stamp_line(line, completed_date),
+            })
+        })
+        .collect();
+    if dry_run {
+        return Ok(stamped);
+    }
+
+    let edits: Vec<LineEdit> = stamped
+        .iter()
+        .map(|task| LineEdit {
+            line_number: task.line_number,
+            expected: lines[task.line_number - 1].to_string(),
+            replacement: task.line.clone(),
+        })
+        .collect();
+    apply_line_edits(path, &edits)?;
+    Ok(stamped)
+}
+
+/// Stamp the note at `path` if `stamp_completion_dates` is set, before a
+/// write operation edits it
+pub fn stamp_before_write(path: &Path, config: &Arc<Config>) -> CapabilityResult<()> {
+    if !config.stamp_completion_dates || !path.is_file() {
+        return Ok(());
+    }
+    let extractor = TaskExtractor::new(Arc::clone(config));
+    let repo = GitRepo::discover(path);
+    stamp_note(path, &extractor, repo.as_ref(), false)
+        .map_err(|e| internal_error(format!("Failed to stamp completion dates: {}", e)))?;
+    Ok(())
+}
+
+/// Add missing completion dates to completed tasks
+#[derive(Parser, Debug)]
+#[command(
+    name = "fix",
+    about = "Add missing ✅ completion dates to completed tasks, dated from git history or the note's modification time"
+)]
+pub struct FixCommand {
+    /// Vault to fix
+    #[arg(index = 1, required = true)]
+    pub path: PathBuf,
+
+    /// Report the dates that would be added without writing any notes
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+/// A note with tasks that were (or would be) stamped
+#[derive(Debug, Serialize)]
+pub struct FixedNote {
+    /// Vault-relative path of the note
+    pub file_path: String,
+    pub tasks: Vec<StampedTask>,
+}
+
+/// Result of `fix`, as printed on stdout
+#[derive(Debug, Serialize)]
+pub struct FixReport {
+    pub dry_run: bool,
+    pub stamped: usize,
+    pub notes: Vec<FixedNote>,
+    /// Notes left unstamped by a truncating scan limit
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ExtractionWarning>,
+}
+
+/// Stamp every note of the vault at `base_path`
+pub fn fix_vault(
+    base_path: &Path,
+    config: Arc<Config>,
+    dry_run: bool,
+) -> Result<FixReport, Box<dyn Error>> {
+    let warnings = Warnings::default();
+    let mut files = collect_markdown_files(base_path, &config, &warnings)?;
+    files.sort();
+    let extractor = TaskExtractor::new(config);
+    let repo = GitRepo::discover(base_path);
+
+    let mut notes = Vec::new();
+    for file in files {
+        let tasks = stamp_note(&file, &extractor, repo.as_ref(), dry_run)
+            .map_err(|e| format!("Failed to fix {}: {}", file.display(), e))?;
+        if !tasks.is_empty() {
+            notes.push(FixedNote {
+                file_path: to_slash(file.strip_prefix(base_path).unwrap_or(&file)),
+                tasks,
+            });
+        }
+    }
+
+    Ok(FixReport {
+        dry_run,
+        stamped: notes.iter().map(|note| note.tasks.len()).sum(),
+        notes,
+        warnings: warnings.into_vec(),
+    })
+}
+
+/// CliOperation implementation for the fix command
+#[derive(Default)]
+pub struct FixOperation;
+
+impl FixOperation {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait::async_trait]
+impl Operation for FixOperation {
+    fn name(&self) -> &'static str {
+        "fix"
+    }
+
+    fn path(&self) -> &'static str {
+        // FixOperation is CLI-only and doesn't have an HTTP endpoint
+        ""
+    }
+
+    fn description(&self) -> &'static str {
+        "Add missing completion dates to completed tasks"
+    }
+
+    fn get_command(&self) -> clap::Command {
+        FixCommand::command()
+    }
+
+    async fn execute_json(&self, _json: Value) -> Result<Value, crate::error::Error> {
+        // FixOperation is CLI-only and doesn't support JSON execution
+        Err(crate::error::unsupported(
+            "fix command is only available via CLI",
+        ))
+    }
+
+    async fn execute_from_args(
+        &self,
+        matches: &clap::ArgMatches,
+        _registry: &CapabilityRegistry,
+    ) -> Result<String, Box<dyn Error>> {
+        let cmd = FixCommand::from_arg_matches(matches)?;
+        let config = Arc::new(Config::load_from_base_path(&cmd.path));
+        let report = fix_vault(&cmd.path, config, cmd.dry_run)?;
+        Ok(serde_json::to_string_pretty(&report)?)
+    }
+
+    fn input_schema(&self) -> Value {
+        // FixOperation is CLI-only and doesn't have a meaningful JSON schema
+        json!({
+            "type": "object",
+            "properties": {}
+        })
+    }
+
+    fn output_schema(&self) -> Value {
+        // FixOperation is CLI-only and doesn't return a JSON response
+        json!({
+            "type": "object",
+            "properties": {}
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_fix_vault() {
+        let vault = TempDir::new().unwrap();
+        let note = vault.path().join("done.md");
+        fs::write(
+            &note,
+            "- [x] Old task\n- [x] Stamped ✅ 2024-05-01\n- [ ] Open\n> - [X] Quoted  \r\n",
+        )
+        .unwrap();
+        fs::write(vault.path().join("open.md"), "- [ ] Nothing to do\n").unwrap();
+        let modified =
+            DateTime::<Local>::from(fs::metadata(&note).unwrap().modified().unwrap()).date_naive();
+        let config = Arc::new(Config::default());
+
+        let report = fix_vault(vault.path(), Arc::clone(&config), true).unwrap();
+        assert_eq!(report.stamped, 2);
+        assert_eq!(report.notes[0].file_path, "done.md");
+        assert_eq!(report.notes[0].tasks[0].source, DateSource::Modified);
+        assert_eq!(report.notes[0].tasks[0].completed_date, modified);
+        assert!(
+            fs::read_to_string(&note)
+                .unwrap()
+                .starts_with("- [x] Old task\n")
+        );
+
+        fix_vault(vault.path(), Arc::clone(&config), false).unwrap();
+        assert_eq!(
+            fs::read_to_string(&note).unwrap(),
+            format!(
+                "- [x] Old task ✅ {0}\n- [x] Stamped ✅ 2024-05-01\n- [ ] Open\n> - [X] Quoted ✅ {0}\r\n",
+                modified
+            )
+        );
+        assert_eq!(fix_vault(vault.path(), config, false).unwrap().stamped, 0);
+    }
+
+    #[test]
+    fn test_stamp_line_keeps_block_id() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+        assert_eq!(
+            stamp_line("- [x] Ship it ^ship-1", date),
+            "- [x] Ship it ✅ 2025-01-06 ^ship-1"
+        );
+    }
+}
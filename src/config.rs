@@ -1,26 +1,486 @@
+use crate::filter::FilterOptions;
+use crate::locale::Locale;
 use glob::Pattern;
 use serde::Deserialize;
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 pub fn default_daily_note_patterns() -> Vec<String> {
     vec!["YYYY-MM-DD.md".to_string()]
 }
 
+pub fn default_frontmatter_tag_keys() -> Vec<String> {
+    vec!["tags".to_string(), "tag".to_string()]
+}
+
+pub fn default_generated_suffixes() -> Vec<String> {
+    vec![".excalidraw.md".to_string()]
+}
+
+pub fn default_assignee_keys() -> Vec<String> {
+    vec!["assignee".to_string(), "owner".to_string()]
+}
+
+pub fn default_meeting_tag() -> String {
+    "meeting".to_string()
+}
+
+pub fn default_inbox_note() -> String {
+    "Inbox.md".to_string()
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct Config {
     #[serde(default)]
     pub exclude_paths: Vec<String>,
 
+    /// Paths of archived notes, matched like `exclude_paths`. Search-type
+    /// operations skip them unless a request sets `include_archived`.
+    #[serde(default)]
+    pub archived_paths: Vec<String>,
+
     #[serde(default = "default_daily_note_patterns")]
     pub daily_note_patterns: Vec<String>,
+
+    /// Language of the day and month names in `daily_note_patterns` and of
+    /// the day words understood by free-text queries
+    #[serde(default)]
+    pub locale: Locale,
+
+    /// File name suffixes of plugin-generated notes (e.g. Excalidraw drawings)
+    /// that are skipped when scanning for tasks, tags, and headings
+    #[serde(default = "default_generated_suffixes")]
+    pub generated_suffixes: Vec<String>,
+
+    /// Frontmatter keys whose values are treated as tags (values are merged)
+    #[serde(default = "default_frontmatter_tag_keys")]
+    pub frontmatter_tag_keys: Vec<String>,
+
+    /// Tag identifying meeting notes for `extract_action_items`
+    #[serde(default = "default_meeting_tag")]
+    pub meeting_tag: String,
+
+    /// Vault-relative path of the note `capture` appends to
+    #[serde(default = "default_inbox_note")]
+    pub inbox_note: String,
+
+    /// Fill in missing task created/completed dates and note modification
+    /// times from git history when the vault is a git repository
+    #[serde(default)]
+    pub git_dates: bool,
+
+    /// Answer task, tag, and heading searches on the whole vault from the
+    /// SQLite index built by `reindex` (requires the `sqlite` feature)
+    #[serde(default)]
+    pub sqlite_index: bool,
+
+    /// Before a write operation edits a note, append a `✅` date to its
+    /// completed tasks that lack one, dated from git history or the note's
+    /// modification time
+    #[serde(default)]
+    pub stamp_completion_dates: bool,
+
+    /// Report each task's `sub_items` as a flat list of their text, as
+    /// before they were a tree
+    #[serde(default)]
+    pub flat_sub_items: bool,
+
+    /// Source file extensions (e.g. `rs`, `py`) whose `TODO:`/`FIXME:`
+    /// comments are extracted as tasks with status `code_todo`. Empty (the
+    /// default) scans Markdown only.
+    #[serde(default)]
+    pub code_todo_extensions: Vec<String>,
+
+    /// Keys that assign a task to someone, written `@key(name)` or
+    /// `key:: name` (also `[key:: name]`)
+    #[serde(default = "default_assignee_keys")]
+    pub assignee_keys: Vec<String>,
+
+    /// Files larger than this many bytes are skipped with an `oversized`
+    /// warning. Unset (the default) reads files of any size.
+    #[serde(default)]
+    pub max_file_size: Option<u64>,
+
+    /// Maximum number of files a scan may read. Unset (the default) means no
+    /// limit.
+    #[serde(default)]
+    pub max_files: Option<usize>,
+
+    /// Maximum directory depth below the scanned path (0 scans only its own
+    /// files). Unset (the default) means no limit.
+    #[serde(default)]
+    pub max_depth: Option<usize>,
+
+    /// Maximum combined size in bytes of the files a scan reads. Unset (the
+    /// default) means no limit.
+    #[serde(default)]
+    pub max_total_bytes: Option<u64>,
+
+    /// What happens when a scan exceeds `max_files`, `max_depth`, or
+    /// `max_total_bytes`
+    #[serde(default)]
+    pub scan_limit_action: ScanLimitAction,
+
+    /// Scheduled reports run while the server is running (`[[reports]]` tables)
+    #[serde(default)]
+    pub reports: Vec<ReportConfig>,
+
+    /// Saved searches run by name with `run_view` (`[views.<name>]` tables)
+    #[serde(default)]
+    pub views: BTreeMap<String, ViewConfig>,
+
+    /// Mail server for `send_digest` (`[smtp]` table)
+    #[serde(default)]
+    pub smtp: Option<SmtpConfig>,
+
+    /// Push notifications for tasks becoming due or overdue, sent while the
+    /// server is running (`[notifications]` table)
+    #[serde(default)]
+    pub notifications: Option<NotificationConfig>,
+
+    /// Policy for new tasks enforced by the `check` command (`[check]` table)
+    #[serde(default)]
+    pub check: CheckConfig,
+
+    /// Log of operations invoked over HTTP, MCP, and gRPC while the server is
+    /// running (`[audit]` table)
+    #[serde(default)]
+    pub audit: Option<AuditConfig>,
+
+    /// API keys the HTTP server requires, each with its own permissions
+    /// (`[[api_keys]]` entries); without any, the server is open
+    #[serde(default)]
+    pub api_keys: Vec<ApiKeyConfig>,
+}
+
+/// A named task search, reusable across CLI, HTTP, and MCP
+///
+/// Filters are the same as `search_tasks` (`status`, `due_before`, `tags`,
+/// `no_due_date`, ...). A free-text `query` is interpreted each time the view
+/// runs, so relative dates like "due this week" stay current; explicit
+/// filters take precedence over anything derived from it.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ViewConfig {
+    /// Shown by `list_views`
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// Free-text query, as accepted by `ask_tasks`
+    #[serde(default)]
+    pub query: Option<String>,
+
+    /// Default number of tasks returned
+    #[serde(default)]
+    pub limit: Option<usize>,
+
+    /// Vault-relative note that `materialize_view` writes the results into
+    #[serde(default)]
+    pub note: Option<String>,
+
+    /// Structured filters
+    #[serde(flatten)]
+    pub filter: FilterOptions,
+}
+
+/// A saved query that is run on a cron schedule in server mode
+///
+/// The result is rendered to Markdown and written to a vault note and/or
+/// POSTed to a webhook.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReportConfig {
+    /// Name used in logs, the rendered heading, and webhook payloads
+    pub name: String,
+
+    /// Cron expression (5 or 6 fields, e.g. "0 8 * * MON"), evaluated in local time
+    pub schedule: String,
+
+    /// Operation to run, by CLI name (e.g. "tasks", "list-tags")
+    pub operation: String,
+
+    /// Operation parameters, as accepted by the HTTP API
+    #[serde(default)]
+    pub params: serde_json::Map<String, serde_json::Value>,
+
+    /// Vault-relative note to (over)write; `{date}` is replaced with the run date
+    #[serde(default)]
+    pub note: Option<String>,
+
+    /// URL that receives the report as a JSON POST
+    #[serde(default)]
+    pub webhook: Option<String>,
+}
+
+pub fn default_smtp_port() -> u16 {
+    587
+}
+
+/// What a scan does when it exceeds a limit
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScanLimitAction {
+    /// Fail the request
+    #[default]
+    Abort,
+    /// Return the results found so far with a `scan_limit` warning
+    Truncate,
+}
+
+/// How the SMTP connection is secured
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SmtpSecurity {
+    /// Upgrade a plain connection with STARTTLS (usually port 587)
+    #[default]
+    StartTls,
+    /// TLS from the start (usually port 465)
+    Tls,
+    /// No encryption, e.g. a local relay
+    None,
+}
+
+/// Mail server and recipients for the task digest email
+///
+/// The digest (overdue tasks and tasks due today) is sent on demand with
+/// `send_digest`, and on `schedule` while the server is running.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SmtpConfig {
+    pub host: String,
+
+    #[serde(default = "default_smtp_port")]
+    pub port: u16,
+
+    #[serde(default)]
+    pub security: SmtpSecurity,
+
+    /// Login for AUTH PLAIN; no authentication when unset
+    #[serde(default)]
+    pub username: Option<String>,
+
+    #[serde(default)]
+    pub password: Option<String>,
+
+    /// Environment variable holding the password, to keep it out of the vault
+    #[serde(default)]
+    pub password_env: Option<String>,
+
+    /// Sender, e.g. "Vault <me@example.com>"
+    pub from: String,
+
+    /// Recipients
+    pub to: Vec<String>,
+
+    /// Cron expression for sending the digest while the server is running
+    /// (e.g. "0 7 * * *"), evaluated in local time
+    #[serde(default)]
+    pub schedule: Option<String>,
+}
+
+impl SmtpConfig {
+    /// The password, read from `password_env` if set
+    pub fn password(&self) -> Option<String> {
+        secret(&self.password, &self.password_env)
+    }
+}
+
+pub fn default_check_interval_minutes() -> u64 {
+    15
+}
+
+pub fn default_ntfy_server() -> String {
+    "https://ntfy.sh".to_string()
+}
+
+/// Push notifications for due and overdue tasks
+///
+/// The vault is checked every `check_interval_minutes`; each newly due or
+/// overdue task is announced once, through every configured service.
+#[derive(Debug, Clone, Deserialize)]
+pub struct NotificationConfig {
+    #[serde(default = "default_check_interval_minutes")]
+    pub check_interval_minutes: u64,
+
+    /// Local time range without notifications, e.g. "22:00-07:00". Tasks
+    /// that become due meanwhile are announced when it ends.
+    #[serde(default)]
+    pub quiet_hours: Option<String>,
+
+    #[serde(default)]
+    pub ntfy: Option<NtfyConfig>,
+
+    #[serde(default)]
+    pub pushover: Option<PushoverConfig>,
+}
+
+/// Append-only audit log of server calls (`[audit]`)
+#[derive(Debug, Clone, Deserialize)]
+pub struct AuditConfig {
+    /// JSON Lines file to append to, relative to the vault unless absolute
+    pub log: PathBuf,
+
+    /// Parameter names whose values are logged as `"[redacted]"`, at any depth
+    /// and regardless of case
+    #[serde(default)]
+    pub redact: Vec<String>,
+}
+
+/// An ntfy topic (`[notifications.ntfy]`)
+#[derive(Debug, Clone, Deserialize)]
+pub struct NtfyConfig {
+    #[serde(default = "default_ntfy_server")]
+    pub server: String,
+
+    pub topic: String,
+
+    /// Access token for protected topics
+    #[serde(default)]
+    pub token: Option<String>,
+
+    /// Environment variable holding the access token
+    #[serde(default)]
+    pub token_env: Option<String>,
+}
+
+/// A Pushover application and user (`[notifications.pushover]`)
+#[derive(Debug, Clone, Deserialize)]
+pub struct PushoverConfig {
+    /// User or group key
+    pub user: String,
+
+    /// Application API token
+    #[serde(default)]
+    pub token: Option<String>,
+
+    /// Environment variable holding the application API token
+    #[serde(default)]
+    pub token_env: Option<String>,
+}
+
+/// An API key for the HTTP server (`[[api_keys]]`)
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApiKeyConfig {
+    /// Name of the key, shown in errors
+    pub name: String,
+
+    /// The key clients present
+    #[serde(default)]
+    pub key: Option<String>,
+
+    /// Environment variable holding the key
+    #[serde(default)]
+    pub key_env: Option<String>,
+
+    /// Operations the key may call, by CLI name (e.g. `tasks`); all if unset
+    #[serde(default)]
+    pub operations: Option<Vec<String>>,
+
+    /// Only allow operations that don't change the vault or send anything
+    #[serde(default)]
+    pub read_only: bool,
+
+    /// Vault folder the key is confined to, as if every call passed it as
+    /// `subvault`
+    #[serde(default)]
+    pub path: Option<String>,
+}
+
+impl ApiKeyConfig {
+    /// The key, read from `key_env` if set
+    pub fn key(&self) -> Option<String> {
+        secret(&self.key, &self.key_env)
+    }
+
+    /// Whether the key may call everything, everywhere
+    pub fn is_unrestricted(&self) -> bool {
+        self.operations.is_none() && !self.read_only && self.path.is_none()
+    }
+}
+
+/// A secret given directly or through an environment variable (which wins)
+fn secret(value: &Option<String>, env: &Option<String>) -> Option<String> {
+    env.as_ref()
+        .and_then(|var| std::env::var(var).ok())
+        .or_else(|| value.clone())
+}
+
+pub fn default_true() -> bool {
+    true
+}
+
+/// Rules the `check` command applies to newly added incomplete tasks
+#[derive(Debug, Clone, Deserialize)]
+pub struct CheckConfig {
+    /// New tasks must have a due date
+    #[serde(default = "default_true")]
+    pub require_due_date: bool,
+
+    /// New tasks must have at least one tag
+    #[serde(default = "default_true")]
+    pub require_tags: bool,
+
+    /// If set, new tasks must have at least one of these tags
+    #[serde(default)]
+    pub allowed_tags: Vec<String>,
+
+    /// Tasks with any of these tags are exempt from the rules, e.g. `someday`
+    #[serde(default)]
+    pub exempt_tags: Vec<String>,
+}
+
+impl Default for CheckConfig {
+    fn default() -> Self {
+        Self {
+            require_due_date: true,
+            require_tags: true,
+            allowed_tags: Vec::new(),
+            exempt_tags: Vec::new(),
+        }
+    }
+}
+
+impl NtfyConfig {
+    /// The access token, read from `token_env` if set
+    pub fn token(&self) -> Option<String> {
+        secret(&self.token, &self.token_env)
+    }
+}
+
+impl PushoverConfig {
+    /// The application API token, read from `token_env` if set
+    pub fn token(&self) -> Option<String> {
+        secret(&self.token, &self.token_env)
+    }
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
             exclude_paths: Vec::new(),
+            archived_paths: Vec::new(),
             daily_note_patterns: default_daily_note_patterns(),
+            locale: Locale::default(),
+            generated_suffixes: default_generated_suffixes(),
+            frontmatter_tag_keys: default_frontmatter_tag_keys(),
+            meeting_tag: default_meeting_tag(),
+            inbox_note: default_inbox_note(),
+            git_dates: false,
+            sqlite_index: false,
+            stamp_completion_dates: false,
+            flat_sub_items: false,
+            code_todo_extensions: Vec::new(),
+            assignee_keys: default_assignee_keys(),
+            max_file_size: None,
+            max_files: None,
+            max_depth: None,
+            max_total_bytes: None,
+            scan_limit_action: ScanLimitAction::default(),
+            reports: Vec::new(),
+            views: BTreeMap::new(),
+            smtp: None,
+            notifications: None,
+            check: CheckConfig::default(),
+            audit: None,
+            api_keys: Vec::new(),
         }
     }
 }
@@ -54,6 +514,8 @@ impl Config {
     /// Merge configuration from environment variables
     /// MARKDOWN_TODO_EXTRACTOR_EXCLUDE_PATHS: comma-separated list of exclusion patterns
     /// MARKDOWN_TODO_EXTRACTOR_DAILY_NOTE_PATTERNS: comma-separated list of daily note patterns
+    /// MARKDOWN_TODO_EXTRACTOR_FRONTMATTER_TAG_KEYS: comma-separated list of frontmatter tag keys
+    /// MARKDOWN_TODO_EXTRACTOR_MAX_FILES, _MAX_DEPTH, _MAX_TOTAL_BYTES: scan limits, overriding the file
     fn merge_from_env(&mut self) {
         self.merge_from_env_var("MARKDOWN_TODO_EXTRACTOR_EXCLUDE_PATHS");
 
@@ -68,6 +530,32 @@ impl Config {
             // Extend existing patterns with env var patterns
             self.daily_note_patterns.extend(env_daily_patterns);
         }
+
+        // Merge frontmatter tag keys from environment variable
+        if let Ok(env_keys) = std::env::var("MARKDOWN_TODO_EXTRACTOR_FRONTMATTER_TAG_KEYS") {
+            for key in env_keys.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+                if !self.frontmatter_tag_keys.iter().any(|k| k == key) {
+                    self.frontmatter_tag_keys.push(key.to_string());
+                }
+            }
+        }
+
+        // Scan limits set for the process (e.g. in an MCP server's
+        // environment) apply to every vault, even one without a config file
+        let limit = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .and_then(|value| value.trim().parse::<u64>().ok())
+        };
+        if let Some(max) = limit("MARKDOWN_TODO_EXTRACTOR_MAX_FILES") {
+            self.max_files = Some(max as usize);
+        }
+        if let Some(max) = limit("MARKDOWN_TODO_EXTRACTOR_MAX_DEPTH") {
+            self.max_depth = Some(max as usize);
+        }
+        if let Some(max) = limit("MARKDOWN_TODO_EXTRACTOR_MAX_TOTAL_BYTES") {
+            self.max_total_bytes = Some(max);
+        }
     }
 
     /// Merge configuration from a specific environment variable
@@ -84,27 +572,82 @@ impl Config {
         }
     }
 
+    /// Check if a path is a Markdown note whose contents should be scanned
+    ///
+    /// Plugin-generated notes matching `generated_suffixes` are skipped: an
+    /// Excalidraw drawing embeds megabytes of JSON and holds no tasks.
+    pub fn is_scannable_markdown(&self, path: &Path) -> bool {
+        if path.extension().and_then(|s| s.to_str()) != Some("md") {
+            return false;
+        }
+
+        let file_name = path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_lowercase();
+        !self
+            .generated_suffixes
+            .iter()
+            .any(|suffix| file_name.ends_with(&suffix.to_lowercase()))
+    }
+
+    /// Check if a file is source code to scan for `TODO:`/`FIXME:` comments
+    pub fn is_code_file(&self, path: &Path) -> bool {
+        path.extension()
+            .and_then(|s| s.to_str())
+            .is_some_and(|ext| {
+                self.code_todo_extensions
+                    .iter()
+                    .any(|wanted| wanted.trim_start_matches('.').eq_ignore_ascii_case(ext))
+            })
+    }
+
     /// Check if a given path should be excluded based on configured patterns
     pub fn should_exclude(&self, path: &Path) -> bool {
-        let path_str = path.to_string_lossy();
-
-        for pattern_str in &self.exclude_paths {
-            // Try to compile the pattern
-            if let Ok(pattern) = Pattern::new(pattern_str)
-                && pattern.matches(&path_str)
-            {
-                return true;
-            }
+        matches_any(&self.exclude_paths, path)
+    }
 
-            // Also check if the path contains the pattern as a substring
-            // This handles simple cases like "Template" or "Recipes"
-            if path_str.contains(pattern_str) {
-                return true;
-            }
+    /// Check if a given path is archived based on `archived_paths`
+    pub fn is_archived(&self, path: &Path) -> bool {
+        matches_any(&self.archived_paths, path)
+    }
+
+    /// Configuration for a search-type operation
+    ///
+    /// Archived paths are added to the exclusions unless `include_archived`
+    /// is set.
+    pub fn search_scope(&self, include_archived: bool) -> Config {
+        let mut config = self.clone();
+        if !include_archived {
+            config
+                .exclude_paths
+                .extend(self.archived_paths.iter().cloned());
+        }
+        config
+    }
+}
+
+/// Check a path against glob patterns, also accepting a plain substring match
+fn matches_any(patterns: &[String], path: &Path) -> bool {
+    let path_str = path.to_string_lossy();
+
+    for pattern_str in patterns {
+        // Try to compile the pattern
+        if let Ok(pattern) = Pattern::new(pattern_str)
+            && pattern.matches(&path_str)
+        {
+            return true;
         }
 
-        false
+        // Also check if the path contains the pattern as a substring
+        // This handles simple cases like "Template" or "Recipes"
+        if path_str.contains(pattern_str) {
+            return true;
+        }
     }
+
+    false
 }
 
 #[cfg(test)]
@@ -116,7 +659,7 @@ mod tests {
     fn test_should_exclude_substring() {
         let config = Config {
             exclude_paths: vec!["Template".to_string(), "Recipes".to_string()],
-            daily_note_patterns: default_daily_note_patterns(),
+            ..Default::default()
         };
 
         assert!(config.should_exclude(&PathBuf::from("/vault/Templates/note.md")));
@@ -128,7 +671,7 @@ mod tests {
     fn test_should_exclude_glob_pattern() {
         let config = Config {
             exclude_paths: vec!["**/Template/**".to_string(), "**/Recipes/**".to_string()],
-            daily_note_patterns: default_daily_note_patterns(),
+            ..Default::default()
         };
 
         assert!(config.should_exclude(&PathBuf::from("/vault/Template/note.md")));
@@ -137,6 +680,26 @@ mod tests {
         assert!(!config.should_exclude(&PathBuf::from("/vault/Notes/note.md")));
     }
 
+    #[test]
+    fn test_archived_paths_search_scope() {
+        let config = Config {
+            exclude_paths: vec!["Template".to_string()],
+            archived_paths: vec!["**/Archive/**".to_string()],
+            ..Default::default()
+        };
+        let archived = PathBuf::from("/vault/Archive/2023/old.md");
+
+        assert!(config.is_archived(&archived));
+        assert!(!config.should_exclude(&archived));
+        assert!(config.search_scope(false).should_exclude(&archived));
+        assert!(!config.search_scope(true).should_exclude(&archived));
+        assert!(
+            config
+                .search_scope(true)
+                .should_exclude(&PathBuf::from("/vault/Template/t.md"))
+        );
+    }
+
     #[test]
     fn test_default_config() {
         let config = Config::default();
@@ -144,6 +707,203 @@ mod tests {
         assert!(!config.should_exclude(&PathBuf::from("/vault/anything.md")));
     }
 
+    #[test]
+    fn test_frontmatter_tag_keys_from_toml() {
+        let config: Config =
+            toml::from_str(r#"frontmatter_tag_keys = ["tags", "keywords"]"#).unwrap();
+        assert_eq!(config.frontmatter_tag_keys, vec!["tags", "keywords"]);
+
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(config.frontmatter_tag_keys, vec!["tags", "tag"]);
+    }
+
+    #[test]
+    fn test_reports_from_toml() {
+        let config: Config = toml::from_str(
+            r#"
+[[reports]]
+name = "Weekly review"
+schedule = "0 8 * * MON"
+operation = "tasks"
+note = "Reviews/{date}.md"
+webhook = "https://example.com/hook"
+
+[reports.params]
+status = "incomplete"
+due_before = "2025-01-01"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.reports.len(), 1);
+        let report = &config.reports[0];
+        assert_eq!(report.name, "Weekly review");
+        assert_eq!(report.schedule, "0 8 * * MON");
+        assert_eq!(report.operation, "tasks");
+        assert_eq!(report.params["status"], "incomplete");
+        assert_eq!(report.note.as_deref(), Some("Reviews/{date}.md"));
+        assert_eq!(report.webhook.as_deref(), Some("https://example.com/hook"));
+
+        let config: Config = toml::from_str("").unwrap();
+        assert!(config.reports.is_empty());
+    }
+
+    #[test]
+    fn test_views_from_toml() {
+        let config: Config = toml::from_str(
+            r#"
+[views.inbox]
+description = "Open tasks without a due date"
+status = "incomplete"
+no_due_date = true
+
+[views.this-week]
+query = "due this week"
+tags = ["work"]
+limit = 20
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.views.len(), 2);
+        let inbox = &config.views["inbox"];
+        assert_eq!(
+            inbox.description.as_deref(),
+            Some("Open tasks without a due date")
+        );
+        assert_eq!(inbox.filter.status, Some(crate::TaskStatus::Incomplete));
+        assert!(inbox.filter.no_due_date);
+
+        let week = &config.views["this-week"];
+        assert_eq!(week.query.as_deref(), Some("due this week"));
+        assert_eq!(week.filter.tags, Some(vec!["work".to_string()]));
+        assert_eq!(week.limit, Some(20));
+        assert!(!week.filter.no_due_date);
+    }
+
+    #[test]
+    fn test_is_scannable_markdown() {
+        let config = Config::default();
+
+        assert!(config.is_scannable_markdown(Path::new("/vault/note.md")));
+        assert!(!config.is_scannable_markdown(Path::new("/vault/image.png")));
+        assert!(
+            !config.is_scannable_markdown(Path::new("/vault/Drawing 2025-01-01.excalidraw.md"))
+        );
+        assert!(!config.is_scannable_markdown(Path::new("/vault/Sketch.Excalidraw.md")));
+
+        let config: Config = toml::from_str(r#"generated_suffixes = [".kanban.md"]"#).unwrap();
+        assert!(config.is_scannable_markdown(Path::new("/vault/Drawing.excalidraw.md")));
+        assert!(!config.is_scannable_markdown(Path::new("/vault/Board.kanban.md")));
+    }
+
+    #[test]
+    fn test_smtp_from_toml() {
+        let config: Config = toml::from_str(
+            r#"
+[smtp]
+host = "smtp.example.com"
+username = "me@example.com"
+password = "secret"
+from = "Vault <me@example.com>"
+to = ["me@example.com"]
+schedule = "0 7 * * *"
+"#,
+        )
+        .unwrap();
+
+        let smtp = config.smtp.unwrap();
+        assert_eq!(smtp.host, "smtp.example.com");
+        assert_eq!(smtp.port, 587);
+        assert_eq!(smtp.security, SmtpSecurity::StartTls);
+        assert_eq!(smtp.password().as_deref(), Some("secret"));
+        assert_eq!(smtp.to, vec!["me@example.com"]);
+        assert_eq!(smtp.schedule.as_deref(), Some("0 7 * * *"));
+
+        let config: Config = toml::from_str(
+            r#"
+[smtp]
+host = "localhost"
+port = 465
+security = "tls"
+from = "me@example.com"
+to = []
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.smtp.unwrap().security, SmtpSecurity::Tls);
+    }
+
+    #[test]
+    fn test_notifications_from_toml() {
+        let config: Config = toml::from_str(
+            r#"
+[notifications]
+quiet_hours = "22:00-07:00"
+
+[notifications.ntfy]
+topic = "vault-tasks"
+
+[notifications.pushover]
+user = "u123"
+token = "a456"
+"#,
+        )
+        .unwrap();
+
+        let notifications = config.notifications.unwrap();
+        assert_eq!(notifications.check_interval_minutes, 15);
+        assert_eq!(notifications.quiet_hours.as_deref(), Some("22:00-07:00"));
+        let ntfy = notifications.ntfy.unwrap();
+        assert_eq!(ntfy.server, "https://ntfy.sh");
+        assert_eq!(ntfy.topic, "vault-tasks");
+        assert_eq!(ntfy.token(), None);
+        assert_eq!(
+            notifications.pushover.unwrap().token().as_deref(),
+            Some("a456")
+        );
+    }
+
+    #[test]
+    fn test_check_from_toml() {
+        let config: Config = toml::from_str("").unwrap();
+        assert!(config.check.require_due_date);
+        assert!(config.check.require_tags);
+
+        let config: Config = toml::from_str(
+            r#"
+[check]
+require_due_date = false
+allowed_tags = ["work", "home"]
+exempt_tags = ["someday"]
+"#,
+        )
+        .unwrap();
+        assert!(!config.check.require_due_date);
+        assert!(config.check.require_tags);
+        assert_eq!(config.check.allowed_tags, vec!["work", "home"]);
+        assert_eq!(config.check.exempt_tags, vec!["someday"]);
+    }
+
+    #[test]
+    fn test_code_todo_extensions() {
+        let config: Config = toml::from_str(r#"code_todo_extensions = ["rs", ".PY"]"#).unwrap();
+        assert!(config.is_code_file(Path::new("src/main.rs")));
+        assert!(config.is_code_file(Path::new("scripts/sync.py")));
+        assert!(!config.is_code_file(Path::new("notes/todo.md")));
+        assert!(!config.is_code_file(Path::new("Makefile")));
+        assert!(!Config::default().is_code_file(Path::new("src/main.rs")));
+    }
+
+    #[test]
+    fn test_git_dates_from_toml() {
+        let config: Config = toml::from_str("git_dates = true").unwrap();
+        assert!(config.git_dates);
+
+        let config: Config = toml::from_str("").unwrap();
+        assert!(!config.git_dates);
+    }
+
     #[test]
     fn test_merge_from_env() {
         // Use a unique env var name for this test to avoid parallel test conflicts
@@ -156,7 +916,7 @@ mod tests {
 
         let mut config = Config {
             exclude_paths: vec!["Template".to_string()],
-            daily_note_patterns: default_daily_note_patterns(),
+            ..Default::default()
         };
 
         config.merge_from_env_var(TEST_VAR);
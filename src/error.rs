@@ -1,23 +1,278 @@
+//! Crate-wide error type for capabilities and operations
+//!
+//! Every error has a stable string [`code`](Error::code) and maps to the same
+//! kind of failure on each interface: an HTTP status, a JSON-RPC error code
+//! for MCP, a gRPC status, and a CLI exit code.
+
+use crate::scan_limits::ScanLimitExceeded;
 use rmcp::model::{ErrorCode, ErrorData};
 use std::borrow::Cow;
 
-/// Creates a JSON-RPC error with a custom error code and message
-pub fn json_error(code: i32, msg: impl Into<String>) -> ErrorData {
-    ErrorData {
-        code: ErrorCode(code),
-        message: Cow::from(msg.into()),
-        data: None,
+/// Errors returned by capabilities and operations
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// A request parameter is missing, malformed, or out of range
+    #[error("{0}")]
+    InvalidParams(String),
+
+    /// A date parameter isn't a valid `YYYY-MM-DD` date or date range
+    #[error("{0}")]
+    InvalidDate(String),
+
+    /// A file, folder, view, or other named resource doesn't exist
+    #[error("{0}")]
+    NotFound(String),
+
+    /// A path points outside the vault or at a file type that can't be read
+    #[error("{0}")]
+    Forbidden(String),
+
+    /// The server requires an API key and the request had none, or an
+    /// unknown one
+    #[error("{0}")]
+    Unauthorized(String),
+
+    /// The request conflicts with the vault's configuration or state
+    #[error("{0}")]
+    Conflict(String),
+
+    /// Vault content couldn't be parsed
+    #[error("{0}")]
+    ParseError(String),
+
+    /// The operation isn't available on this interface (e.g. CLI-only)
+    #[error("{0}")]
+    Unsupported(String),
+
+    /// The operation ran past the server's operation timeout
+    #[error("{0}")]
+    Timeout(String),
+
+    /// A scan went over `max_files`, `max_depth`, or `max_total_bytes`
+    #[error(transparent)]
+    ScanLimit(#[from] ScanLimitExceeded),
+
+    /// Reading or writing a file failed
+    #[error(transparent)]
+    IoError(#[from] std::io::Error),
+
+    /// Any other server-side failure
+    #[error("{0}")]
+    Internal(String),
+}
+
+impl Error {
+    /// Stable machine-readable code, e.g. `not_found`
+    pub fn code(&self) -> &'static str {
+        match self {
+            Error::InvalidParams(_) => "invalid_params",
+            Error::InvalidDate(_) => "invalid_date",
+            Error::NotFound(_) => "not_found",
+            Error::Forbidden(_) => "forbidden",
+            Error::Unauthorized(_) => "unauthorized",
+            Error::Conflict(_) => "conflict",
+            Error::ParseError(_) => "parse_error",
+            Error::Unsupported(_) => "unsupported",
+            Error::Timeout(_) => "timeout",
+            Error::ScanLimit(_) => "scan_limit_exceeded",
+            Error::IoError(_) => "io_error",
+            Error::Internal(_) => "internal",
+        }
+    }
+
+    /// HTTP status code for REST responses
+    pub fn http_status(&self) -> u16 {
+        match self {
+            Error::InvalidParams(_) | Error::InvalidDate(_) => 400,
+            Error::Unauthorized(_) => 401,
+            Error::Forbidden(_) => 403,
+            Error::NotFound(_) => 404,
+            Error::Conflict(_) => 409,
+            Error::ScanLimit(_) => 413,
+            Error::ParseError(_) => 422,
+            Error::Unsupported(_) => 501,
+            Error::Timeout(_) => 504,
+            Error::IoError(_) | Error::Internal(_) => 500,
+        }
+    }
+
+    /// JSON-RPC error code for MCP responses
+    ///
+    /// Client errors use the standard invalid params (-32602) and method not
+    /// found (-32601) codes, missing resources use MCP's -32002, and the
+    /// remaining kinds use codes from the server-defined range.
+    pub fn rpc_code(&self) -> i32 {
+        match self {
+            Error::InvalidParams(_) | Error::InvalidDate(_) => -32602,
+            Error::NotFound(_) => -32002,
+            Error::Forbidden(_) => -32003,
+            Error::Conflict(_) => -32004,
+            Error::ParseError(_) => -32005,
+            Error::ScanLimit(_) => -32006,
+            Error::Timeout(_) => -32007,
+            Error::Unauthorized(_) => -32008,
+            Error::Unsupported(_) => -32601,
+            Error::IoError(_) | Error::Internal(_) => -32603,
+        }
+    }
+
+    /// Process exit code for the CLI (2 matches clap's usage errors)
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Internal(_) => 1,
+            Error::InvalidParams(_) | Error::InvalidDate(_) => 2,
+            Error::NotFound(_) => 3,
+            Error::Forbidden(_) => 4,
+            Error::Conflict(_) => 5,
+            Error::ParseError(_) => 6,
+            Error::IoError(_) => 7,
+            Error::Unsupported(_) => 8,
+            Error::ScanLimit(_) => 9,
+            Error::Timeout(_) => 10,
+            Error::Unauthorized(_) => 11,
+        }
+    }
+}
+
+/// CLI exit code for any error, 1 unless it is an [`Error`] (or a scan limit
+/// from a CLI-only command)
+pub fn exit_code(error: &(dyn std::error::Error + 'static)) -> i32 {
+    if error.is::<ScanLimitExceeded>() {
+        return 9;
+    }
+    error.downcast_ref::<Error>().map_or(1, Error::exit_code)
+}
+
+impl From<Error> for ErrorData {
+    fn from(error: Error) -> Self {
+        ErrorData {
+            code: ErrorCode(error.rpc_code()),
+            message: Cow::from(error.to_string()),
+            data: Some(serde_json::json!({ "code": error.code() })),
+        }
+    }
+}
+
+impl axum::response::IntoResponse for Error {
+    fn into_response(self) -> axum::response::Response {
+        let status = axum::http::StatusCode::from_u16(self.http_status())
+            .unwrap_or(axum::http::StatusCode::INTERNAL_SERVER_ERROR);
+        let body = serde_json::json!({
+            "error": { "code": self.code(), "message": self.to_string() }
+        });
+        (status, axum::Json(body)).into_response()
     }
 }
 
-/// Creates a JSON-RPC internal error (-32603)
+/// Creates an internal error
 /// Use for server-side errors like I/O failures, parsing errors, etc.
-pub fn internal_error(msg: impl Into<String>) -> ErrorData {
-    json_error(-32603, msg)
+pub fn internal_error(msg: impl Into<String>) -> Error {
+    Error::Internal(msg.into())
 }
 
-/// Creates a JSON-RPC invalid params error (-32602)
-/// Use for invalid input parameters, paths outside bounds, etc.
-pub fn invalid_params(msg: impl Into<String>) -> ErrorData {
-    json_error(-32602, msg)
+/// Creates an invalid params error
+/// Use for invalid input parameters
+pub fn invalid_params(msg: impl Into<String>) -> Error {
+    Error::InvalidParams(msg.into())
+}
+
+/// Creates an invalid date error
+/// Use for malformed dates and date ranges
+pub fn invalid_date(msg: impl Into<String>) -> Error {
+    Error::InvalidDate(msg.into())
+}
+
+/// Creates a not found error
+/// Use for missing files, folders, views, etc.
+pub fn not_found(msg: impl Into<String>) -> Error {
+    Error::NotFound(msg.into())
+}
+
+/// Creates a forbidden error
+/// Use for paths outside the vault and disallowed file types
+pub fn forbidden(msg: impl Into<String>) -> Error {
+    Error::Forbidden(msg.into())
+}
+
+/// Creates an unauthorized error
+/// Use for missing or unknown API keys
+pub fn unauthorized(msg: impl Into<String>) -> Error {
+    Error::Unauthorized(msg.into())
+}
+
+/// Creates a conflict error
+/// Use when the vault's configuration or state doesn't allow the request
+pub fn conflict(msg: impl Into<String>) -> Error {
+    Error::Conflict(msg.into())
+}
+
+/// Creates a parse error
+/// Use for vault content that can't be parsed
+pub fn parse_error(msg: impl Into<String>) -> Error {
+    Error::ParseError(msg.into())
+}
+
+/// Creates an unsupported error
+/// Use for CLI-only operations called over HTTP/MCP
+pub fn unsupported(msg: impl Into<String>) -> Error {
+    Error::Unsupported(msg.into())
+}
+
+/// Creates a timeout error
+/// Use when an operation runs past the operation timeout
+pub fn timeout(msg: impl Into<String>) -> Error {
+    Error::Timeout(msg.into())
+}
+
+/// Creates an error for a failed vault scan
+/// Use when extracting from the vault; scan limits keep their own code
+pub fn scan_error(context: &str, error: Box<dyn std::error::Error>) -> Error {
+    match error.downcast::<ScanLimitExceeded>() {
+        Ok(exceeded) => Error::ScanLimit(*exceeded),
+        Err(error) => Error::Internal(format!("{}: {}", context, error)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_mappings() {
+        let error = not_found("File not found: a.md");
+        assert_eq!(error.code(), "not_found");
+        assert_eq!(error.http_status(), 404);
+        assert_eq!(error.exit_code(), 3);
+
+        let data = ErrorData::from(error);
+        assert_eq!(data.code, ErrorCode(-32002));
+        assert_eq!(data.message, "File not found: a.md");
+        assert_eq!(data.data, Some(serde_json::json!({ "code": "not_found" })));
+
+        let io = Error::from(std::io::Error::other("disk on fire"));
+        assert_eq!(io.code(), "io_error");
+        assert_eq!(io.to_string(), "disk on fire");
+
+        let boxed: Box<dyn std::error::Error> = Box::new(forbidden("outside the vault"));
+        assert_eq!(exit_code(boxed.as_ref()), 4);
+        let boxed: Box<dyn std::error::Error> = "plain".into();
+        assert_eq!(exit_code(boxed.as_ref()), 1);
+    }
+
+    #[test]
+    fn test_scan_error() {
+        let exceeded = ScanLimitExceeded {
+            path: "/home".into(),
+            limit: "max_files",
+            max: 10,
+        };
+        let error = scan_error("Failed to extract tasks", Box::new(exceeded));
+        assert_eq!(error.code(), "scan_limit_exceeded");
+        assert_eq!(error.http_status(), 413);
+        assert_eq!(error.exit_code(), 9);
+
+        let error = scan_error("Failed to extract tasks", "disk on fire".into());
+        assert_eq!(error.code(), "internal");
+        assert_eq!(error.to_string(), "Failed to extract tasks: disk on fire");
+    }
 }
@@ -0,0 +1,455 @@
+use super::{ExportedTask, LineEdit, apply_line_edits, set_inline_field};
+use crate::config::Config;
+use crate::extractor::{Task, TaskExtractor, TaskStatus};
+use crate::warnings::ExtractionWarning;
+use chrono::NaiveDate;
+use serde::Serialize;
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Inline field used to store the created issue URL on the task line
+pub const GITHUB_FIELD: &str = "github";
+
+const DEFAULT_API_URL: &str = "https://api.github.com";
+
+type GithubResult<T> = Result<T, Box<dyn Error + Send + Sync>>;
+
+/// Arguments for `export github`
+#[derive(Debug, clap::Args)]
+pub struct GithubExportArgs {
+    /// Path to file or folder to scan
+    #[arg(index = 1, required = true)]
+    pub path: PathBuf,
+
+    /// Repository to create issues in (owner/name)
+    #[arg(long)]
+    pub repo: String,
+
+    /// GitHub token with permission to create issues
+    #[arg(long, env = "GITHUB_TOKEN", hide_env_values = true)]
+    pub token: String,
+
+    /// Only tasks carrying this tag are exported
+    #[arg(long, default_value = "gh")]
+    pub tag: String,
+
+    /// Report what would be created without calling the API or editing files
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+/// Fields sent when creating a GitHub issue
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct NewIssue {
+    pub title: String,
+    pub body: String,
+    pub labels: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub milestone: Option<u64>,
+}
+
+/// A repository milestone
+#[derive(Debug, Clone, PartialEq)]
+pub struct Milestone {
+    pub number: u64,
+    pub title: String,
+    pub due_on: Option<NaiveDate>,
+}
+
+/// The subset of the GitHub API used by the export
+pub trait GithubApi {
+    /// List the repository's open milestones
+    fn list_milestones(&self) -> GithubResult<Vec<Milestone>>;
+
+    /// Create a milestone due on `due_on`
+    fn create_milestone(&self, title: &str, due_on: NaiveDate) -> GithubResult<Milestone>;
+
+    /// Create an issue and return its URL
+    fn create_issue(&self, issue: &NewIssue) -> GithubResult<String>;
+}
+
+/// GitHub REST client for a single repository
+pub struct GithubClient {
+    token: String,
+    repo_url: String,
+}
+
+impl GithubClient {
+    pub fn new(token: impl Into<String>, repo: &str) -> Self {
+        Self {
+            token: token.into(),
+            repo_url: format!("{}/repos/{}", DEFAULT_API_URL, repo),
+        }
+    }
+
+    fn get(&self, path: &str) -> ureq::RequestBuilder<ureq::typestate::WithoutBody> {
+        ureq::get(&format!("{}{}", self.repo_url, path))
+            .header("Authorization", &format!("Bearer {}", self.token))
+            .header("Accept", "application/vnd.github+json")
+            .header("User-Agent", env!("CARGO_PKG_NAME"))
+    }
+
+    fn post(&self, path: &str) -> ureq::RequestBuilder<ureq::typestate::WithBody> {
+        ureq::post(&format!("{}{}", self.repo_url, path))
+            .header("Authorization", &format!("Bearer {}", self.token))
+            .header("Accept", "application/vnd.github+json")
+            .header("User-Agent", env!("CARGO_PKG_NAME"))
+    }
+}
+
+fn parse_milestone(value: &Value) -> Option<Milestone> {
+    Some(Milestone {
+        number: value["number"].as_u64()?,
+        title: value["title"].as_str()?.to_string(),
+        due_on: value["due_on"]
+            .as_str()
+            .and_then(|s| s.get(..10))
+            .and_then(|s| s.parse().ok()),
+    })
+}
+
+impl GithubApi for GithubClient {
+    fn list_milestones(&self) -> GithubResult<Vec<Milestone>> {
+        let milestones: Value = self
+            .get("/milestones?state=open&per_page=100")
+            .call()?
+            .body_mut()
+            .read_json()?;
+
+        Ok(milestones
+            .as_array()
+            .map(|items| items.iter().filter_map(parse_milestone).collect())
+            .unwrap_or_default())
+    }
+
+    fn create_milestone(&self, title: &str, due_on: NaiveDate) -> GithubResult<Milestone> {
+        let created: Value = self
+            .post("/milestones")
+            .send_json(json!({
+                "title": title,
+                "due_on": format!("{}T00:00:00Z", due_on.format("%Y-%m-%d")),
+            }))?
+            .body_mut()
+            .read_json()?;
+
+        parse_milestone(&created).ok_or_else(|| "GitHub returned an invalid milestone".into())
+    }
+
+    fn create_issue(&self, issue: &NewIssue) -> GithubResult<String> {
+        let created: Value = self
+            .post("/issues")
+            .send_json(issue)?
+            .body_mut()
+            .read_json()?;
+
+        created["html_url"]
+            .as_str()
+            .map(String::from)
+            .ok_or_else(|| "GitHub response did not include an issue URL".into())
+    }
+}
+
+/// Summary of an export run, printed as JSON
+#[derive(Debug, Default, Serialize)]
+pub struct GithubExportReport {
+    pub dry_run: bool,
+    /// Tasks turned into issues (`remote_id` is the issue URL)
+    pub created: Vec<ExportedTask>,
+    /// Titles of milestones created for due dates
+    pub milestones_created: Vec<String>,
+    /// Per-task failures; other tasks are still exported
+    pub errors: Vec<String>,
+    /// Files that were skipped (unreadable, invalid frontmatter, larger
+    /// than `max_file_size`, or cut off by a truncating scan limit)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ExtractionWarning>,
+}
+
+/// Build the issue for a task
+///
+/// Tags are moved from the title into labels (except the export tag itself)
+/// and sub-items become a checklist in the body.
+pub fn new_issue(task: &Task, export_tag: &str, milestone: Option<u64>) -> NewIssue {
+    let title = task
+        .content
+        .split_whitespace()
+        .filter(|word| !word.starts_with('#') || word.len() == 1)
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let mut body = format!("From {} (line {})", task.file_name, task.line_number);
+    if !task.sub_items.is_empty() {
+        body.push_str("\n\n");
+        for item in task.sub_items.texts() {
+            body.push_str(&format!("- {}\n", item));
+        }
+    }
+
+    NewIssue {
+        title,
+        body,
+        labels: task
+            .tags
+            .iter()
+            .filter(|tag| !tag.eq_ignore_ascii_case(export_tag))
+            .cloned()
+            .collect(),
+        milestone,
+    }
+}
+
+/// Find or create the milestone for a due date
+///
+/// An open milestone matches if it is due that day or is titled with the date.
+fn milestone_for(
+    due: NaiveDate,
+    milestones: &mut Vec<Milestone>,
+    api: &dyn GithubApi,
+    report: &mut GithubExportReport,
+) -> GithubResult<u64> {
+    let title = due.format("%Y-%m-%d").to_string();
+    if let Some(existing) = milestones
+        .iter()
+        .find(|m| m.due_on == Some(due) || m.title == title)
+    {
+        return Ok(existing.number);
+    }
+
+    let created = api.create_milestone(&title, due)?;
+    report.milestones_created.push(created.title.clone());
+    let number = created.number;
+    milestones.push(created);
+    Ok(number)
+}
+
+/// Create issues for incomplete tasks tagged with `export_tag`
+///
+/// Tasks that already have a `[github:: url]` field are skipped. Returns the
+/// report and the task line edits that record each issue URL.
+pub fn export_tasks(
+    tasks: &[Task],
+    api: &dyn GithubApi,
+    export_tag: &str,
+    dry_run: bool,
+) -> (GithubExportReport, BTreeMap<String, Vec<LineEdit>>) {
+    let mut report = GithubExportReport {
+        dry_run,
+        ..Default::default()
+    };
+    let mut edits: BTreeMap<String, Vec<LineEdit>> = BTreeMap::new();
+
+    let candidates: Vec<&Task> = tasks
+        .iter()
+        .filter(|task| task.status == TaskStatus::Incomplete)
+        .filter(|task| !task.fields.contains_key(GITHUB_FIELD))
+        .filter(|task| task.tags.iter().any(|t| t.eq_ignore_ascii_case(export_tag)))
+        .collect();
+
+    if dry_run {
+        report.created = candidates
+            .iter()
+            .map(|task| ExportedTask::new(task, ""))
+            .collect();
+        return (report, edits);
+    }
+
+    // Only fetch milestones when a task needs one
+    let mut milestones = if candidates.iter().any(|t| t.due_date.is_some()) {
+        match api.list_milestones() {
+            Ok(milestones) => milestones,
+            Err(e) => {
+                report
+                    .errors
+                    .push(format!("failed to list milestones: {}", e));
+                Vec::new()
+            }
+        }
+    } else {
+        Vec::new()
+    };
+
+    for task in candidates {
+        let milestone = match task.due_date {
+            Some(due) => match milestone_for(due, &mut milestones, api, &mut report) {
+                Ok(number) => Some(number),
+                Err(e) => {
+                    report.errors.push(format!(
+                        "{}:{}: failed to create milestone: {}",
+                        task.file_path, task.line_number, e
+                    ));
+                    None
+                }
+            },
+            None => None,
+        };
+
+        match api.create_issue(&new_issue(task, export_tag, milestone)) {
+            Ok(url) => {
+                edits
+                    .entry(task.file_path.clone())
+                    .or_default()
+                    .push(LineEdit {
+                        line_number: task.line_number,
+                        expected: task.raw_line.clone(),
+                        replacement: set_inline_field(&task.raw_line, GITHUB_FIELD, &url),
+                    });
+                report.created.push(ExportedTask::new(task, &url));
+            }
+            Err(e) => report.errors.push(format!(
+                "{}:{}: failed to create issue: {}",
+                task.file_path, task.line_number, e
+            )),
+        }
+    }
+
+    (report, edits)
+}
+
+/// Run `export github` from parsed CLI arguments
+pub fn run(args: GithubExportArgs) -> GithubResult<GithubExportReport> {
+    let config = Arc::new(Config::load_from_base_path(&args.path));
+    let extractor = TaskExtractor::new(config);
+    let (tasks, warnings) = extractor
+        .extract_tasks_with_warnings(&args.path)
+        .map_err(|e| e.to_string())?;
+
+    let client = GithubClient::new(args.token, &args.repo);
+    let (mut report, edits) = export_tasks(&tasks, &client, &args.tag, args.dry_run);
+    report.warnings = warnings;
+
+    // Record issue URLs even if some tasks failed so they aren't exported twice
+    for (file_path, file_edits) in edits {
+        if let Err(e) = apply_line_edits(Path::new(&file_path), &file_edits) {
+            report
+                .errors
+                .push(format!("{}: failed to update file: {}", file_path, e));
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    #[derive(Default)]
+    struct FakeGithub {
+        milestones: Vec<Milestone>,
+        created_milestones: RefCell<Vec<(String, NaiveDate)>>,
+        issues: RefCell<Vec<NewIssue>>,
+    }
+
+    impl GithubApi for FakeGithub {
+        fn list_milestones(&self) -> GithubResult<Vec<Milestone>> {
+            Ok(self.milestones.clone())
+        }
+
+        fn create_milestone(&self, title: &str, due_on: NaiveDate) -> GithubResult<Milestone> {
+            let mut created = self.created_milestones.borrow_mut();
+            created.push((title.to_string(), due_on));
+            Ok(Milestone {
+                number: 100 + created.len() as u64,
+                title: title.to_string(),
+                due_on: Some(due_on),
+            })
+        }
+
+        fn create_issue(&self, issue: &NewIssue) -> GithubResult<String> {
+            let mut issues = self.issues.borrow_mut();
+            issues.push(issue.clone());
+            Ok(format!("https://github.com/o/r/issues/{}", issues.len()))
+        }
+    }
+
+    fn date(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    fn tasks(content: &str) -> Vec<Task> {
+        TaskExtractor::default().extract_tasks_from_content(content, Path::new("vault/todo.md"))
+    }
+
+    #[test]
+    fn test_new_issue_maps_tags_to_labels() {
+        let tasks = tasks("- [ ] Fix login bug #gh #bug #auth\n  - Check the session cookie");
+        let issue = new_issue(&tasks[0], "gh", Some(3));
+
+        assert_eq!(issue.title, "Fix login bug");
+        assert_eq!(issue.labels, vec!["bug", "auth"]);
+        assert_eq!(issue.milestone, Some(3));
+        assert_eq!(
+            issue.body,
+            "From todo.md (line 1)\n\n- Check the session cookie\n"
+        );
+    }
+
+    #[test]
+    fn test_export_only_tagged_unexported_tasks() {
+        let tasks = tasks(
+            "- [ ] Export me #gh\n\
+             - [ ] Not tagged #work\n\
+             - [x] Done #gh\n\
+             - [ ] Already exported #gh [github:: https://github.com/o/r/issues/9]",
+        );
+        let api = FakeGithub::default();
+
+        let (report, edits) = export_tasks(&tasks, &api, "gh", false);
+
+        assert_eq!(api.issues.borrow().len(), 1);
+        assert_eq!(report.created.len(), 1);
+        assert_eq!(
+            report.created[0].remote_id,
+            "https://github.com/o/r/issues/1"
+        );
+        assert_eq!(
+            edits["vault/todo.md"][0].replacement,
+            "- [ ] Export me #gh [github:: https://github.com/o/r/issues/1]"
+        );
+    }
+
+    #[test]
+    fn test_due_dates_map_to_milestones() {
+        let tasks = tasks(
+            "- [ ] Existing milestone #gh 📅 2025-01-10\n\
+             - [ ] New milestone #gh 📅 2025-02-01\n\
+             - [ ] Same new milestone #gh 📅 2025-02-01\n\
+             - [ ] No due date #gh",
+        );
+        let api = FakeGithub {
+            milestones: vec![Milestone {
+                number: 7,
+                title: "Sprint 1".to_string(),
+                due_on: Some(date("2025-01-10")),
+            }],
+            ..Default::default()
+        };
+
+        let (report, _) = export_tasks(&tasks, &api, "gh", false);
+
+        let milestones: Vec<_> = api.issues.borrow().iter().map(|i| i.milestone).collect();
+        assert_eq!(milestones, vec![Some(7), Some(101), Some(101), None]);
+        assert_eq!(
+            *api.created_milestones.borrow(),
+            vec![("2025-02-01".to_string(), date("2025-02-01"))]
+        );
+        assert_eq!(report.milestones_created, vec!["2025-02-01"]);
+    }
+
+    #[test]
+    fn test_dry_run_makes_no_calls() {
+        let tasks = tasks("- [ ] Export me #gh 📅 2025-01-10");
+        let api = FakeGithub::default();
+
+        let (report, edits) = export_tasks(&tasks, &api, "gh", true);
+
+        assert!(api.issues.borrow().is_empty());
+        assert!(api.created_milestones.borrow().is_empty());
+        assert!(edits.is_empty());
+        assert_eq!(report.created.len(), 1);
+    }
+}
@@ -0,0 +1,380 @@
+use super::{ExportedTask, LineEdit, apply_line_edits, set_inline_field};
+use crate::config::Config;
+use crate::extractor::{Priority, Task, TaskExtractor, TaskStatus};
+use crate::warnings::ExtractionWarning;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use chrono::NaiveDate;
+use serde::Serialize;
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Inline field used to store the Jira issue key on the task line
+pub const JIRA_FIELD: &str = "jira";
+
+type JiraResult<T> = Result<T, Box<dyn Error + Send + Sync>>;
+
+/// Arguments for `export jira`
+#[derive(Debug, clap::Args)]
+pub struct JiraExportArgs {
+    /// Path to file or folder to scan
+    #[arg(index = 1, required = true)]
+    pub path: PathBuf,
+
+    /// Jira site URL, e.g. `https://example.atlassian.net`
+    #[arg(long, env = "JIRA_URL")]
+    pub url: String,
+
+    /// Account email the API token belongs to
+    #[arg(long, env = "JIRA_EMAIL")]
+    pub email: String,
+
+    /// Jira API token
+    #[arg(long, env = "JIRA_API_TOKEN", hide_env_values = true)]
+    pub token: String,
+
+    /// Key of the project to create issues in (e.g. OPS)
+    #[arg(long)]
+    pub project: String,
+
+    /// Issue type of created issues
+    #[arg(long, default_value = "Task")]
+    pub issue_type: String,
+
+    /// Only tasks carrying this tag are exported
+    #[arg(long, default_value = "jira")]
+    pub tag: String,
+
+    /// Report what would be created or updated without calling the API or editing files
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+/// Fields sent when creating or updating a Jira issue
+#[derive(Debug, Clone, PartialEq)]
+pub struct JiraIssue {
+    pub summary: String,
+    pub description: String,
+    pub labels: Vec<String>,
+    /// Name of a default Jira priority (`Highest` to `Lowest`)
+    pub priority: Option<&'static str>,
+    pub due_date: Option<NaiveDate>,
+}
+
+/// The subset of the Jira API used by the export
+pub trait JiraApi {
+    /// Create an issue and return its key
+    fn create_issue(&self, issue: &JiraIssue) -> JiraResult<String>;
+
+    /// Overwrite an existing issue's fields
+    fn update_issue(&self, key: &str, issue: &JiraIssue) -> JiraResult<()>;
+}
+
+/// Jira Cloud REST client (API v2, basic auth with an API token)
+pub struct JiraClient {
+    api_url: String,
+    authorization: String,
+    project: String,
+    issue_type: String,
+}
+
+impl JiraClient {
+    pub fn new(url: &str, email: &str, token: &str, project: &str, issue_type: &str) -> Self {
+        Self {
+            api_url: format!("{}/rest/api/2", url.trim_end_matches('/')),
+            authorization: format!("Basic {}", BASE64.encode(format!("{}:{}", email, token))),
+            project: project.to_string(),
+            issue_type: issue_type.to_string(),
+        }
+    }
+
+    /// The `fields` object shared by create and update
+    fn fields(issue: &JiraIssue) -> serde_json::Map<String, Value> {
+        let mut fields = serde_json::Map::new();
+        fields.insert("summary".to_string(), json!(issue.summary));
+        fields.insert("description".to_string(), json!(issue.description));
+        fields.insert("labels".to_string(), json!(issue.labels));
+        // Clear the due date on update when it was removed from the task
+        fields.insert(
+            "duedate".to_string(),
+            json!(issue.due_date.map(|d| d.format("%Y-%m-%d").to_string())),
+        );
+        if let Some(priority) = issue.priority {
+            fields.insert("priority".to_string(), json!({ "name": priority }));
+        }
+        fields
+    }
+}
+
+impl JiraApi for JiraClient {
+    fn create_issue(&self, issue: &JiraIssue) -> JiraResult<String> {
+        let mut fields = Self::fields(issue);
+        fields.insert("project".to_string(), json!({ "key": self.project }));
+        fields.insert("issuetype".to_string(), json!({ "name": self.issue_type }));
+
+        let created: Value = ureq::post(&format!("{}/issue", self.api_url))
+            .header("Authorization", &self.authorization)
+            .header("Accept", "application/json")
+            .send_json(json!({ "fields": fields }))?
+            .body_mut()
+            .read_json()?;
+
+        created["key"]
+            .as_str()
+            .map(String::from)
+            .ok_or_else(|| "Jira response did not include an issue key".into())
+    }
+
+    fn update_issue(&self, key: &str, issue: &JiraIssue) -> JiraResult<()> {
+        ureq::put(&format!("{}/issue/{}", self.api_url, key))
+            .header("Authorization", &self.authorization)
+            .header("Accept", "application/json")
+            .send_json(json!({ "fields": Self::fields(issue) }))?;
+        Ok(())
+    }
+}
+
+/// Summary of an export run, printed as JSON
+#[derive(Debug, Default, Serialize)]
+pub struct JiraExportReport {
+    pub dry_run: bool,
+    /// Tasks turned into issues (`remote_id` is the issue key)
+    pub created: Vec<ExportedTask>,
+    /// Previously exported tasks whose issues were updated
+    pub updated: Vec<ExportedTask>,
+    /// Per-task failures; other tasks are still exported
+    pub errors: Vec<String>,
+    /// Files that were skipped (unreadable, invalid frontmatter, larger
+    /// than `max_file_size`, or cut off by a truncating scan limit)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ExtractionWarning>,
+}
+
+/// Jira's default priority for a task priority
+fn jira_priority(priority: Option<Priority>) -> Option<&'static str> {
+    priority.map(|priority| match priority {
+        Priority::Urgent => "Highest",
+        Priority::High => "High",
+        Priority::Medium => "Medium",
+        Priority::Low => "Low",
+        Priority::Lowest => "Lowest",
+    })
+}
+
+/// Build the issue for a task
+///
+/// Tags are moved from the summary into labels (except the export tag itself)
+/// and sub-items become a bullet list in the description.
+pub fn jira_issue(task: &Task, export_tag: &str) -> JiraIssue {
+    let summary = task
+        .content
+        .split_whitespace()
+        .filter(|word| !word.starts_with('#') || word.len() == 1)
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let mut description = format!("From {} (line {})", task.file_name, task.line_number);
+    if !task.sub_items.is_empty() {
+        description.push_str("\n\n");
+        for item in task.sub_items.texts() {
+            description.push_str(&format!("* {}\n", item));
+        }
+    }
+
+    JiraIssue {
+        summary,
+        description,
+        labels: task
+            .tags
+            .iter()
+            .filter(|tag| !tag.eq_ignore_ascii_case(export_tag))
+            .cloned()
+            .collect(),
+        priority: jira_priority(task.priority),
+        due_date: task.due_date,
+    }
+}
+
+/// Create or update issues for incomplete tasks tagged with `export_tag`
+///
+/// Tasks without a `[jira:: KEY]` field get a new issue; tasks with one have
+/// that issue updated to match. Returns the report and the task line edits
+/// that record each new issue key.
+pub fn export_tasks(
+    tasks: &[Task],
+    api: &dyn JiraApi,
+    export_tag: &str,
+    dry_run: bool,
+) -> (JiraExportReport, BTreeMap<String, Vec<LineEdit>>) {
+    let mut report = JiraExportReport {
+        dry_run,
+        ..Default::default()
+    };
+    let mut edits: BTreeMap<String, Vec<LineEdit>> = BTreeMap::new();
+
+    let candidates = tasks
+        .iter()
+        .filter(|task| task.status == TaskStatus::Incomplete)
+        .filter(|task| task.tags.iter().any(|t| t.eq_ignore_ascii_case(export_tag)));
+
+    for task in candidates {
+        let existing = task.fields.get(JIRA_FIELD).map(|key| key.trim());
+        if dry_run {
+            match existing {
+                Some(key) => report.updated.push(ExportedTask::new(task, key)),
+                None => report.created.push(ExportedTask::new(task, "")),
+            }
+            continue;
+        }
+
+        let issue = jira_issue(task, export_tag);
+        match existing {
+            Some(key) => match api.update_issue(key, &issue) {
+                Ok(()) => report.updated.push(ExportedTask::new(task, key)),
+                Err(e) => report.errors.push(format!(
+                    "{}:{}: failed to update {}: {}",
+                    task.file_path, task.line_number, key, e
+                )),
+            },
+            None => match api.create_issue(&issue) {
+                Ok(key) => {
+                    edits
+                        .entry(task.file_path.clone())
+                        .or_default()
+                        .push(LineEdit {
+                            line_number: task.line_number,
+                            expected: task.raw_line.clone(),
+                            replacement: set_inline_field(&task.raw_line, JIRA_FIELD, &key),
+                        });
+                    report.created.push(ExportedTask::new(task, &key));
+                }
+                Err(e) => report.errors.push(format!(
+                    "{}:{}: failed to create issue: {}",
+                    task.file_path, task.line_number, e
+                )),
+            },
+        }
+    }
+
+    (report, edits)
+}
+
+/// Run `export jira` from parsed CLI arguments
+pub fn run(args: JiraExportArgs) -> JiraResult<JiraExportReport> {
+    let config = Arc::new(Config::load_from_base_path(&args.path));
+    let extractor = TaskExtractor::new(config);
+    let (tasks, warnings) = extractor
+        .extract_tasks_with_warnings(&args.path)
+        .map_err(|e| e.to_string())?;
+
+    let client = JiraClient::new(
+        &args.url,
+        &args.email,
+        &args.token,
+        &args.project,
+        &args.issue_type,
+    );
+    let (mut report, edits) = export_tasks(&tasks, &client, &args.tag, args.dry_run);
+    report.warnings = warnings;
+
+    // Record issue keys even if some tasks failed so they aren't exported twice
+    for (file_path, file_edits) in edits {
+        if let Err(e) = apply_line_edits(Path::new(&file_path), &file_edits) {
+            report
+                .errors
+                .push(format!("{}: failed to update file: {}", file_path, e));
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    #[derive(Default)]
+    struct FakeJira {
+        created: RefCell<Vec<JiraIssue>>,
+        updated: RefCell<Vec<(String, JiraIssue)>>,
+    }
+
+    impl JiraApi for FakeJira {
+        fn create_issue(&self, issue: &JiraIssue) -> JiraResult<String> {
+            let mut created = self.created.borrow_mut();
+            created.push(issue.clone());
+            Ok(format!("OPS-{}", created.len()))
+        }
+
+        fn update_issue(&self, key: &str, issue: &JiraIssue) -> JiraResult<()> {
+            self.updated
+                .borrow_mut()
+                .push((key.to_string(), issue.clone()));
+            Ok(())
+        }
+    }
+
+    fn tasks(content: &str) -> Vec<Task> {
+        TaskExtractor::default().extract_tasks_from_content(content, Path::new("vault/todo.md"))
+    }
+
+    #[test]
+    fn test_jira_issue_maps_priority_and_due_date() {
+        let tasks = tasks("- [ ] Rotate keys #jira #security 🔼 📅 2025-03-01\n  - Staging first");
+        let issue = jira_issue(&tasks[0], "jira");
+
+        assert_eq!(issue.summary, "Rotate keys");
+        assert_eq!(issue.labels, vec!["security"]);
+        assert_eq!(issue.priority, Some("High"));
+        assert_eq!(issue.due_date, NaiveDate::from_ymd_opt(2025, 3, 1));
+        assert_eq!(
+            issue.description,
+            "From todo.md (line 1)\n\n* Staging first\n"
+        );
+    }
+
+    #[test]
+    fn test_export_creates_new_and_updates_existing() {
+        let tasks = tasks(
+            "- [ ] New issue #jira\n\
+             - [ ] Not tagged #work\n\
+             - [x] Done #jira\n\
+             - [ ] Renamed task #jira [jira:: OPS-9]",
+        );
+        let api = FakeJira::default();
+
+        let (report, edits) = export_tasks(&tasks, &api, "jira", false);
+
+        assert_eq!(api.created.borrow().len(), 1);
+        assert_eq!(report.created[0].remote_id, "OPS-1");
+        assert_eq!(
+            edits["vault/todo.md"][0].replacement,
+            "- [ ] New issue #jira [jira:: OPS-1]"
+        );
+
+        let updated = api.updated.borrow();
+        assert_eq!(updated.len(), 1);
+        assert_eq!(updated[0].0, "OPS-9");
+        assert_eq!(updated[0].1.summary, "Renamed task");
+        assert_eq!(report.updated[0].remote_id, "OPS-9");
+        assert_eq!(edits["vault/todo.md"].len(), 1);
+    }
+
+    #[test]
+    fn test_dry_run_makes_no_calls() {
+        let tasks = tasks("- [ ] Create me #jira\n- [ ] Update me #jira [jira:: OPS-2]");
+        let api = FakeJira::default();
+
+        let (report, edits) = export_tasks(&tasks, &api, "jira", true);
+
+        assert!(api.created.borrow().is_empty());
+        assert!(api.updated.borrow().is_empty());
+        assert!(edits.is_empty());
+        assert_eq!(report.created.len(), 1);
+        assert_eq!(report.updated[0].remote_id, "OPS-2");
+    }
+}
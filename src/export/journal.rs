@@ -0,0 +1,170 @@
+use crate::cache::fnv1a;
+use crate::capabilities::daily_notes::pattern::find_daily_note;
+use crate::config::Config;
+use crate::frontmatter::body_after_frontmatter;
+use crate::path_resolver::to_slash;
+use crate::tag_extractor::TagExtractor;
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use serde::Serialize;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Arguments for `export journal`
+#[derive(Debug, clap::Args)]
+pub struct JournalExportArgs {
+    /// Path to the vault
+    #[arg(index = 1, required = true)]
+    pub path: PathBuf,
+
+    /// First day to export (YYYY-MM-DD)
+    #[arg(long)]
+    pub from: NaiveDate,
+
+    /// Last day to export (YYYY-MM-DD, defaults to today)
+    #[arg(long)]
+    pub to: Option<NaiveDate>,
+}
+
+/// A Day One JSON export (the `Journal.json` inside a Day One export zip)
+#[derive(Debug, Serialize)]
+pub struct DayOneJournal {
+    pub metadata: DayOneMetadata,
+    pub entries: Vec<DayOneEntry>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DayOneMetadata {
+    pub version: &'static str,
+}
+
+/// One journal entry per daily note
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DayOneEntry {
+    /// 32 hex digits derived from the note path, stable across exports
+    pub uuid: String,
+    /// Noon UTC on the note's date, so the entry keeps its day in any time zone
+    pub creation_date: String,
+    /// The note's modification time
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modified_date: Option<String>,
+    /// Frontmatter and inline tags
+    pub tags: Vec<String>,
+    /// Note body without frontmatter
+    pub text: String,
+    pub starred: bool,
+}
+
+fn format_utc(time: DateTime<Utc>) -> String {
+    time.format("%Y-%m-%dT%H:%M:%SZ").to_string()
+}
+
+/// Collect the daily notes between `from` and `to` (inclusive) as Day One entries
+///
+/// Days without a daily note, and notes with an empty body, are skipped.
+pub fn journal_entries(
+    base_path: &Path,
+    config: &Config,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<DayOneJournal, String> {
+    if from > to {
+        return Err("--from must not be after --to".to_string());
+    }
+
+    let tag_extractor = TagExtractor::new(Arc::new(config.clone()));
+    let mut entries = Vec::new();
+
+    for date in from.iter_days().take_while(|date| *date <= to) {
+        let day = date.format("%Y-%m-%d").to_string();
+        let Some(note) = find_daily_note(base_path, &day, &config.daily_note_patterns, config)?
+        else {
+            continue;
+        };
+
+        let content = fs::read_to_string(&note)
+            .map_err(|e| format!("Failed to read {}: {}", note.display(), e))?;
+        let text = body_after_frontmatter(&content).trim();
+        if text.is_empty() {
+            continue;
+        }
+
+        let relative = to_slash(note.strip_prefix(base_path).unwrap_or(&note));
+        let modified_date = fs::metadata(&note)
+            .and_then(|m| m.modified())
+            .ok()
+            .map(|time| format_utc(time.into()));
+
+        entries.push(DayOneEntry {
+            uuid: format!(
+                "{:016X}{:016X}",
+                fnv1a(relative.as_bytes()),
+                fnv1a(day.as_bytes())
+            ),
+            creation_date: format!("{}T12:00:00Z", day),
+            modified_date,
+            tags: tag_extractor
+                .extract_tags_from_content(&content, true)
+                .unwrap_or_default(),
+            text: text.to_string(),
+            starred: false,
+        });
+    }
+
+    Ok(DayOneJournal {
+        metadata: DayOneMetadata { version: "1.0" },
+        entries,
+    })
+}
+
+/// Run `export journal` from parsed CLI arguments
+pub fn run(args: JournalExportArgs) -> Result<DayOneJournal, Box<dyn Error + Send + Sync>> {
+    let config = Config::load_from_base_path(&args.path);
+    let to = args.to.unwrap_or_else(|| Local::now().date_naive());
+    Ok(journal_entries(&args.path, &config, args.from, to)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_journal_entries() {
+        let vault = TempDir::new().unwrap();
+        fs::write(
+            vault.path().join("2025-01-20.md"),
+            "---\ntags: [travel]\n---\n# Monday\nFlew to Lisbon #trip\n",
+        )
+        .unwrap();
+        fs::write(vault.path().join("2025-01-21.md"), "---\ntags: []\n---\n\n").unwrap();
+        fs::write(vault.path().join("2025-01-22.md"), "Quiet day\n").unwrap();
+        fs::write(vault.path().join("2025-01-25.md"), "Out of range\n").unwrap();
+
+        let date = |d| NaiveDate::from_ymd_opt(2025, 1, d).unwrap();
+        let journal =
+            journal_entries(vault.path(), &Config::default(), date(19), date(22)).unwrap();
+
+        assert_eq!(journal.entries.len(), 2);
+        let first = &journal.entries[0];
+        assert_eq!(first.creation_date, "2025-01-20T12:00:00Z");
+        assert_eq!(first.text, "# Monday\nFlew to Lisbon #trip");
+        assert_eq!(first.tags, vec!["travel", "trip"]);
+        assert_eq!(first.uuid.len(), 32);
+        assert!(first.modified_date.is_some());
+        assert_eq!(journal.entries[1].text, "Quiet day");
+        assert_ne!(first.uuid, journal.entries[1].uuid);
+
+        // Entry IDs are stable across exports
+        let again = journal_entries(vault.path(), &Config::default(), date(20), date(20)).unwrap();
+        assert_eq!(again.entries[0].uuid, first.uuid);
+
+        let json = serde_json::to_value(&journal).unwrap();
+        assert_eq!(json["metadata"]["version"], "1.0");
+        assert!(json["entries"][0]["creationDate"].is_string());
+
+        assert!(journal_entries(vault.path(), &Config::default(), date(22), date(19)).is_err());
+    }
+}
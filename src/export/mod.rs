@@ -0,0 +1,333 @@
+//! Export tasks to external services
+//!
+//! Exports are CLI-only (`markdown-todo-extractor export <target> <path>`).
+//! Each target records the remote identifier back on the task line as an
+//! inline field (e.g. `[todoist:: 123]`) so repeated runs don't create
+//! duplicates. The `journal` target instead prints daily notes as a Day One
+//! journal and changes nothing in the vault.
+
+pub mod github;
+pub mod jira;
+pub mod journal;
+pub mod todoist;
+
+use crate::capabilities::CapabilityRegistry;
+use crate::extractor::Task;
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
+use serde::Serialize;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Export tasks to an external service
+#[derive(Parser, Debug)]
+#[command(name = "export", about = "Export tasks to external services")]
+pub struct ExportCommand {
+    #[command(subcommand)]
+    pub target: ExportTarget,
+}
+
+/// Supported export targets
+#[derive(Debug, Subcommand)]
+pub enum ExportTarget {
+    /// Push incomplete tasks to Todoist (optionally syncing completion both ways)
+    Todoist(todoist::TodoistExportArgs),
+    /// Create GitHub issues from tasks carrying an export tag
+    Github(github::GithubExportArgs),
+    /// Create or update Jira issues from tasks carrying an export tag
+    Jira(jira::JiraExportArgs),
+    /// Write daily notes in a date range as Day One-compatible JSON
+    Journal(journal::JournalExportArgs),
+}
+
+/// A task touched by an export, as reported on stdout
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ExportedTask {
+    pub file_path: String,
+    pub line_number: usize,
+    pub content: String,
+    pub remote_id: String,
+}
+
+impl ExportedTask {
+    pub fn new(task: &Task, remote_id: &str) -> Self {
+        Self {
+            file_path: task.file_path.clone(),
+            line_number: task.line_number,
+            content: task.content.clone(),
+            remote_id: remote_id.to_string(),
+        }
+    }
+}
+
+/// Replacement for a single task line, applied only if the line is unchanged
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineEdit {
+    pub line_number: usize,
+    pub expected: String,
+    pub replacement: String,
+}
+
+/// Append `text` to a task line, before its block ID (`^id`) if it ends in
+/// one, since a block ID is only recognized at the end of the line
+pub fn append_to_line(line: &str, text: &str) -> String {
+    let line = line.trim_end();
+    match line.rsplit_once(char::is_whitespace) {
+        Some((body, word)) if is_block_id(word) => {
+            format!("{} {} {}", body.trim_end(), text, word)
+        }
+        _ => format!("{} {}", line, text),
+    }
+}
+
+fn is_block_id(word: &str) -> bool {
+    word.strip_prefix('^').is_some_and(|id| {
+        !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    })
+}
+
+/// Set an inline field (`[key:: value]`) on a task line
+///
+/// An existing field with the same key is replaced; otherwise the field is
+/// appended to the end of the line, before any block ID.
+pub fn set_inline_field(line: &str, key: &str, value: &str) -> String {
+    let field = format!("[{}:: {}]", key, value);
+    let prefix = format!("[{}::", key);
+
+    if let Some(start) = line.find(&prefix)
+        && let Some(len) = line[start..].find(']')
+    {
+        return format!("{}{}{}", &line[..start], field, &line[start + len + 1..]);
+    }
+
+    append_to_line(line, &field)
+}
+
+/// Mark an incomplete task line as completed on `date`
+///
+/// Flips the first `[ ]` checkbox to `[x]` and appends a `✅ YYYY-MM-DD` marker.
+pub fn mark_line_completed(line: &str, date: chrono::NaiveDate) -> String {
+    let checked = line.replacen("[ ]", "[x]", 1);
+    append_to_line(&checked, &format!("✅ {}", date.format("%Y-%m-%d")))
+}
+
+/// Apply line edits to a file, skipping lines that changed since they were read
+///
+/// Returns the number of edits applied. Line endings are preserved.
+pub fn apply_line_edits(file_path: &Path, edits: &[LineEdit]) -> io::Result<usize> {
+    if edits.is_empty() {
+        return Ok(0);
+    }
+
+    let content = fs::read_to_string(file_path)?;
+    let mut lines: Vec<String> = content.split_inclusive('\n').map(String::from).collect();
+    let mut applied = 0;
+
+    for edit in edits {
+        let Some(line) = edit
+            .line_number
+            .checked_sub(1)
+            .and_then(|i| lines.get_mut(i))
+        else {
+            continue;
+        };
+
+        let body = line.trim_end_matches(['\n', '\r']);
+        if body != edit.expected {
+            continue;
+        }
+
+        let ending = line[body.len()..].to_string();
+        *line = format!("{}{}", edit.replacement, ending);
+        applied += 1;
+    }
+
+    if applied > 0 {
+        fs::write(file_path, lines.concat())?;
+    }
+
+    Ok(applied)
+}
+
+/// CliOperation implementation for the export command
+#[derive(Default)]
+pub struct ExportOperation;
+
+impl ExportOperation {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::operation::Operation for ExportOperation {
+    fn name(&self) -> &'static str {
+        "export"
+    }
+
+    fn path(&self) -> &'static str {
+        // ExportOperation is CLI-only and doesn't have an HTTP endpoint
+        ""
+    }
+
+    fn description(&self) -> &'static str {
+        "Export tasks to external services"
+    }
+
+    fn get_command(&self) -> clap::Command {
+        ExportCommand::command()
+    }
+
+    async fn execute_json(
+        &self,
+        _json: serde_json::Value,
+    ) -> Result<serde_json::Value, crate::error::Error> {
+        // ExportOperation is CLI-only and doesn't support JSON execution
+        Err(crate::error::unsupported(
+            "export command is only available via CLI",
+        ))
+    }
+
+    async fn execute_from_args(
+        &self,
+        matches: &clap::ArgMatches,
+        _registry: &CapabilityRegistry,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        let cmd = ExportCommand::from_arg_matches(matches)?;
+
+        match cmd.target {
+            // Export clients are blocking, so run exports off the async runtime
+            ExportTarget::Todoist(args) => {
+                let report = tokio::task::spawn_blocking(move || todoist::run(args))
+                    .await?
+                    .map_err(|e| e.to_string())?;
+                Ok(serde_json::to_string_pretty(&report)?)
+            }
+            ExportTarget::Github(args) => {
+                let report = tokio::task::spawn_blocking(move || github::run(args))
+                    .await?
+                    .map_err(|e| e.to_string())?;
+                Ok(serde_json::to_string_pretty(&report)?)
+            }
+            ExportTarget::Jira(args) => {
+                let report = tokio::task::spawn_blocking(move || jira::run(args))
+                    .await?
+                    .map_err(|e| e.to_string())?;
+                Ok(serde_json::to_string_pretty(&report)?)
+            }
+            ExportTarget::Journal(args) => {
+                let journal = tokio::task::spawn_blocking(move || journal::run(args))
+                    .await?
+                    .map_err(|e| e.to_string())?;
+                Ok(serde_json::to_string_pretty(&journal)?)
+            }
+        }
+    }
+
+    fn input_schema(&self) -> serde_json::Value {
+        // ExportOperation is CLI-only and doesn't have a meaningful JSON schema
+        serde_json::json!({
+            "type": "object",
+            "properties": {}
+        })
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
+        // ExportOperation is CLI-only and doesn't return a JSON response
+        serde_json::json!({
+            "type": "object",
+            "properties": {}
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_set_inline_field_appends() {
+        assert_eq!(
+            set_inline_field("- [ ] Task #work ", "todoist", "123"),
+            "- [ ] Task #work [todoist:: 123]"
+        );
+    }
+
+    #[test]
+    fn test_set_inline_field_replaces() {
+        assert_eq!(
+            set_inline_field("- [ ] Task [todoist:: 1] 📅 2025-01-01", "todoist", "2"),
+            "- [ ] Task [todoist:: 2] 📅 2025-01-01"
+        );
+    }
+
+    #[test]
+    fn test_mark_line_completed() {
+        let date = chrono::NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+        assert_eq!(
+            mark_line_completed("  - [ ] Task [todoist:: 1]", date),
+            "  - [x] Task [todoist:: 1] ✅ 2025-01-06"
+        );
+    }
+
+    #[test]
+    fn test_line_edits_keep_block_id_last() {
+        use crate::config::Config;
+        use crate::extractor::TaskExtractor;
+        use std::sync::Arc;
+
+        let date = chrono::NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+        let completed = mark_line_completed("- [ ] Ship it ^ship-1", date);
+        assert_eq!(completed, "- [x] Ship it ✅ 2025-01-06 ^ship-1");
+        let exported = set_inline_field("- [ ] Ship it ^ship-1 ", "todoist", "123");
+        assert_eq!(exported, "- [ ] Ship it [todoist:: 123] ^ship-1");
+
+        // The rewritten lines still parse with their block ID
+        let extractor = TaskExtractor::new(Arc::new(Config::default()));
+        let path = Path::new("note.md");
+        let tasks = extractor.extract_tasks_from_content(&format!("{}\n", completed), path);
+        assert_eq!(tasks[0].block_id.as_deref(), Some("ship-1"));
+        assert_eq!(tasks[0].content, "Ship it");
+        let tasks = extractor.extract_tasks_from_content(&format!("{}\n", exported), path);
+        assert_eq!(tasks[0].block_id.as_deref(), Some("ship-1"));
+
+        // A caret inside the text isn't a block ID
+        assert_eq!(
+            append_to_line("- [ ] Raise 2^10", "✅ 2025-01-06"),
+            "- [ ] Raise 2^10 ✅ 2025-01-06"
+        );
+    }
+
+    #[test]
+    fn test_apply_line_edits() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("tasks.md");
+        fs::write(&file, "# Tasks\r\n- [ ] One\r\n- [ ] Two\r\n- [ ] Three").unwrap();
+
+        let edits = vec![
+            LineEdit {
+                line_number: 2,
+                expected: "- [ ] One".to_string(),
+                replacement: "- [ ] One [todoist:: 1]".to_string(),
+            },
+            // Stale edit: line 3 no longer matches, so it is skipped
+            LineEdit {
+                line_number: 3,
+                expected: "- [ ] Changed".to_string(),
+                replacement: "- [ ] Changed [todoist:: 2]".to_string(),
+            },
+            LineEdit {
+                line_number: 4,
+                expected: "- [ ] Three".to_string(),
+                replacement: "- [ ] Three [todoist:: 3]".to_string(),
+            },
+        ];
+
+        assert_eq!(apply_line_edits(&file, &edits).unwrap(), 2);
+        assert_eq!(
+            fs::read_to_string(&file).unwrap(),
+            "# Tasks\r\n- [ ] One [todoist:: 1]\r\n- [ ] Two\r\n- [ ] Three [todoist:: 3]"
+        );
+    }
+}
@@ -0,0 +1,464 @@
+use super::{ExportedTask, LineEdit, apply_line_edits, mark_line_completed, set_inline_field};
+use crate::config::Config;
+use crate::extractor::{Priority, Task, TaskExtractor, TaskStatus};
+use crate::filter::{FilterOptions, filter_tasks};
+use crate::warnings::ExtractionWarning;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Inline field used to store the Todoist task ID on the task line
+pub const TODOIST_FIELD: &str = "todoist";
+
+const DEFAULT_API_URL: &str = "https://api.todoist.com/api/v1";
+
+type TodoistResult<T> = Result<T, Box<dyn Error + Send + Sync>>;
+
+/// Arguments for `export todoist`
+#[derive(Debug, clap::Args)]
+pub struct TodoistExportArgs {
+    /// Path to file or folder to scan
+    #[arg(index = 1, required = true)]
+    pub path: PathBuf,
+
+    /// Todoist API token
+    #[arg(long, env = "TODOIST_API_TOKEN", hide_env_values = true)]
+    pub token: String,
+
+    /// Project to create tasks in (defaults to the Inbox)
+    #[arg(long)]
+    pub project_id: Option<String>,
+
+    /// Only export tasks with all of these tags
+    #[arg(long, value_delimiter = ',')]
+    pub tags: Option<Vec<String>>,
+
+    /// Also sync completion both ways for previously exported tasks
+    #[arg(long)]
+    pub sync: bool,
+
+    /// Report what would change without calling write APIs or editing files
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+/// Fields sent when creating a Todoist task
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct NewTodoistTask {
+    pub content: String,
+    pub description: String,
+    pub labels: Vec<String>,
+    /// Todoist priority from 1 (normal) to 4 (urgent)
+    pub priority: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+}
+
+/// The subset of the Todoist API used by the export
+pub trait TodoistApi {
+    /// Create a task and return its ID
+    fn create_task(&self, task: &NewTodoistTask) -> TodoistResult<String>;
+
+    /// Whether a task is completed, or `None` if it no longer exists
+    fn is_completed(&self, id: &str) -> TodoistResult<Option<bool>>;
+
+    /// Mark a task as completed
+    fn close_task(&self, id: &str) -> TodoistResult<()>;
+}
+
+/// Todoist REST client
+pub struct TodoistClient {
+    token: String,
+    api_url: String,
+}
+
+impl TodoistClient {
+    pub fn new(token: impl Into<String>) -> Self {
+        Self {
+            token: token.into(),
+            api_url: DEFAULT_API_URL.to_string(),
+        }
+    }
+
+    fn auth_header(&self) -> String {
+        format!("Bearer {}", self.token)
+    }
+}
+
+impl TodoistApi for TodoistClient {
+    fn create_task(&self, task: &NewTodoistTask) -> TodoistResult<String> {
+        let created: Value = ureq::post(&format!("{}/tasks", self.api_url))
+            .header("Authorization", &self.auth_header())
+            .send_json(task)?
+            .body_mut()
+            .read_json()?;
+
+        created["id"]
+            .as_str()
+            .map(String::from)
+            .ok_or_else(|| "Todoist response did not include a task id".into())
+    }
+
+    fn is_completed(&self, id: &str) -> TodoistResult<Option<bool>> {
+        let response = ureq::get(&format!("{}/tasks/{}", self.api_url, id))
+            .header("Authorization", &self.auth_header())
+            .call();
+
+        let remote: Value = match response {
+            Ok(mut response) => response.body_mut().read_json()?,
+            Err(ureq::Error::StatusCode(404)) => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(Some(
+            remote["checked"]
+                .as_bool()
+                .or_else(|| remote["is_completed"].as_bool())
+                .unwrap_or(false),
+        ))
+    }
+
+    fn close_task(&self, id: &str) -> TodoistResult<()> {
+        ureq::post(&format!("{}/tasks/{}/close", self.api_url, id))
+            .header("Authorization", &self.auth_header())
+            .send_empty()?;
+        Ok(())
+    }
+}
+
+/// Options controlling an export run
+#[derive(Debug, Clone, Default)]
+pub struct TodoistExportOptions {
+    pub project_id: Option<String>,
+    pub sync: bool,
+    pub dry_run: bool,
+}
+
+/// Summary of an export run, printed as JSON
+#[derive(Debug, Default, Serialize)]
+pub struct TodoistExportReport {
+    pub dry_run: bool,
+    /// Incomplete tasks pushed to Todoist
+    pub created: Vec<ExportedTask>,
+    /// Tasks completed in the vault and closed in Todoist (sync mode)
+    pub closed_in_todoist: Vec<ExportedTask>,
+    /// Tasks completed in Todoist and checked off in the vault (sync mode)
+    pub completed_in_vault: Vec<ExportedTask>,
+    /// Per-task failures; other tasks are still exported
+    pub errors: Vec<String>,
+    /// Files that were skipped (unreadable, invalid frontmatter, larger
+    /// than `max_file_size`, or cut off by a truncating scan limit)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ExtractionWarning>,
+}
+
+/// Map a task priority onto Todoist's 1 (normal) to 4 (urgent) scale
+fn todoist_priority(priority: Option<Priority>) -> u8 {
+    match priority {
+        Some(Priority::Urgent) => 4,
+        Some(Priority::High) => 3,
+        Some(Priority::Medium) => 2,
+        Some(Priority::Low) | Some(Priority::Lowest) | None => 1,
+    }
+}
+
+/// Build the Todoist payload for a vault task
+pub fn new_todoist_task(task: &Task, project_id: Option<&str>) -> NewTodoistTask {
+    NewTodoistTask {
+        content: task.content.clone(),
+        description: format!("From {} (line {})", task.file_name, task.line_number),
+        labels: task.tags.clone(),
+        priority: todoist_priority(task.priority),
+        due_date: task.due_date.map(|d| d.format("%Y-%m-%d").to_string()),
+        project_id: project_id.map(String::from),
+    }
+}
+
+/// Export tasks to Todoist and compute the task line edits to write back
+///
+/// Incomplete tasks without a `[todoist:: id]` field are created. In sync mode,
+/// previously exported tasks are reconciled: completing a task in either place
+/// completes it in the other. In dry-run mode no write calls are made and no
+/// edits are returned.
+pub fn export_tasks(
+    tasks: &[Task],
+    api: &dyn TodoistApi,
+    options: &TodoistExportOptions,
+    today: chrono::NaiveDate,
+) -> (TodoistExportReport, BTreeMap<String, Vec<LineEdit>>) {
+    let mut report = TodoistExportReport {
+        dry_run: options.dry_run,
+        ..Default::default()
+    };
+    let mut edits: BTreeMap<String, Vec<LineEdit>> = BTreeMap::new();
+
+    for task in tasks {
+        match task.fields.get(TODOIST_FIELD) {
+            None if task.status == TaskStatus::Incomplete => {
+                if options.dry_run {
+                    report.created.push(ExportedTask::new(task, ""));
+                    continue;
+                }
+
+                let payload = new_todoist_task(task, options.project_id.as_deref());
+                match api.create_task(&payload) {
+                    Ok(id) => {
+                        edits
+                            .entry(task.file_path.clone())
+                            .or_default()
+                            .push(LineEdit {
+                                line_number: task.line_number,
+                                expected: task.raw_line.clone(),
+                                replacement: set_inline_field(&task.raw_line, TODOIST_FIELD, &id),
+                            });
+                        report.created.push(ExportedTask::new(task, &id));
+                    }
+                    Err(e) => report.errors.push(format!(
+                        "{}:{}: failed to create task: {}",
+                        task.file_path, task.line_number, e
+                    )),
+                }
+            }
+            Some(id) if options.sync => {
+                let remote_completed = match api.is_completed(id) {
+                    Ok(Some(completed)) => completed,
+                    // Deleted in Todoist; leave the vault task alone
+                    Ok(None) => continue,
+                    Err(e) => {
+                        report.errors.push(format!(
+                            "{}:{}: failed to fetch Todoist task {}: {}",
+                            task.file_path, task.line_number, id, e
+                        ));
+                        continue;
+                    }
+                };
+
+                match task.status {
+                    TaskStatus::Incomplete if remote_completed => {
+                        if !options.dry_run {
+                            edits
+                                .entry(task.file_path.clone())
+                                .or_default()
+                                .push(LineEdit {
+                                    line_number: task.line_number,
+                                    expected: task.raw_line.clone(),
+                                    replacement: mark_line_completed(&task.raw_line, today),
+                                });
+                        }
+                        report.completed_in_vault.push(ExportedTask::new(task, id));
+                    }
+                    TaskStatus::Completed if !remote_completed => {
+                        if !options.dry_run
+                            && let Err(e) = api.close_task(id)
+                        {
+                            report.errors.push(format!(
+                                "{}:{}: failed to close Todoist task {}: {}",
+                                task.file_path, task.line_number, id, e
+                            ));
+                            continue;
+                        }
+                        report.closed_in_todoist.push(ExportedTask::new(task, id));
+                    }
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
+    (report, edits)
+}
+
+/// Run `export todoist` from parsed CLI arguments
+pub fn run(args: TodoistExportArgs) -> TodoistResult<TodoistExportReport> {
+    let config = Arc::new(Config::load_from_base_path(&args.path));
+    let extractor = TaskExtractor::new(config);
+    let (tasks, warnings) = extractor
+        .extract_tasks_with_warnings(&args.path)
+        .map_err(|e| e.to_string())?;
+
+    let filter_options = FilterOptions {
+        tags: args.tags.clone(),
+        ..Default::default()
+    };
+    let tasks = filter_tasks(tasks, &filter_options);
+
+    let client = TodoistClient::new(args.token);
+    let options = TodoistExportOptions {
+        project_id: args.project_id,
+        sync: args.sync,
+        dry_run: args.dry_run,
+    };
+    let today = chrono::Local::now().date_naive();
+
+    let (mut report, edits) = export_tasks(&tasks, &client, &options, today);
+    report.warnings = warnings;
+
+    // Write IDs and completions back even if some tasks failed, so tasks that
+    // were created aren't created again on the next run
+    for (file_path, file_edits) in edits {
+        if let Err(e) = apply_line_edits(Path::new(&file_path), &file_edits) {
+            report
+                .errors
+                .push(format!("{}: failed to update file: {}", file_path, e));
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    #[derive(Default)]
+    struct FakeTodoist {
+        created: RefCell<Vec<NewTodoistTask>>,
+        closed: RefCell<Vec<String>>,
+        completed: HashMap<String, bool>,
+    }
+
+    impl TodoistApi for FakeTodoist {
+        fn create_task(&self, task: &NewTodoistTask) -> TodoistResult<String> {
+            let mut created = self.created.borrow_mut();
+            created.push(task.clone());
+            Ok(format!("id-{}", created.len()))
+        }
+
+        fn is_completed(&self, id: &str) -> TodoistResult<Option<bool>> {
+            Ok(self.completed.get(id).copied())
+        }
+
+        fn close_task(&self, id: &str) -> TodoistResult<()> {
+            self.closed.borrow_mut().push(id.to_string());
+            Ok(())
+        }
+    }
+
+    fn today() -> chrono::NaiveDate {
+        chrono::NaiveDate::from_ymd_opt(2025, 1, 6).unwrap()
+    }
+
+    fn tasks(content: &str) -> Vec<Task> {
+        TaskExtractor::default().extract_tasks_from_content(content, Path::new("vault/todo.md"))
+    }
+
+    #[test]
+    fn test_new_todoist_task() {
+        let tasks = tasks("- [ ] Write report #work ⏫ 📅 2025-01-10");
+        let payload = new_todoist_task(&tasks[0], Some("42"));
+
+        assert_eq!(
+            payload,
+            NewTodoistTask {
+                content: "Write report #work".to_string(),
+                description: "From todo.md (line 1)".to_string(),
+                labels: vec!["work".to_string()],
+                priority: 4,
+                due_date: Some("2025-01-10".to_string()),
+                project_id: Some("42".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_export_creates_incomplete_tasks_once() {
+        let tasks = tasks(
+            "- [ ] New task\n- [x] Done task\n- [ ] Already exported [todoist:: 7]\n- [ ] Other",
+        );
+        let api = FakeTodoist::default();
+
+        let (report, edits) = export_tasks(&tasks, &api, &TodoistExportOptions::default(), today());
+
+        assert_eq!(api.created.borrow().len(), 2);
+        assert_eq!(report.created.len(), 2);
+        assert_eq!(report.created[0].remote_id, "id-1");
+        assert!(report.errors.is_empty());
+
+        let file_edits = &edits["vault/todo.md"];
+        assert_eq!(file_edits.len(), 2);
+        assert_eq!(file_edits[0].line_number, 1);
+        assert_eq!(file_edits[0].replacement, "- [ ] New task [todoist:: id-1]");
+        assert_eq!(file_edits[1].replacement, "- [ ] Other [todoist:: id-2]");
+    }
+
+    #[test]
+    fn test_dry_run_makes_no_changes() {
+        let tasks = tasks("- [ ] New task\n- [x] Done [todoist:: 1]");
+        let api = FakeTodoist {
+            completed: HashMap::from([("1".to_string(), false)]),
+            ..Default::default()
+        };
+        let options = TodoistExportOptions {
+            sync: true,
+            dry_run: true,
+            ..Default::default()
+        };
+
+        let (report, edits) = export_tasks(&tasks, &api, &options, today());
+
+        assert!(api.created.borrow().is_empty());
+        assert!(api.closed.borrow().is_empty());
+        assert!(edits.is_empty());
+        assert_eq!(report.created.len(), 1);
+        assert_eq!(report.closed_in_todoist.len(), 1);
+    }
+
+    #[test]
+    fn test_sync_completion_both_ways() {
+        let tasks = tasks(
+            "- [ ] Done remotely [todoist:: 1]\n\
+             - [x] Done locally [todoist:: 2]\n\
+             - [ ] Open both [todoist:: 3]\n\
+             - [ ] Deleted remotely [todoist:: 4]",
+        );
+        let api = FakeTodoist {
+            completed: HashMap::from([
+                ("1".to_string(), true),
+                ("2".to_string(), false),
+                ("3".to_string(), false),
+            ]),
+            ..Default::default()
+        };
+        let options = TodoistExportOptions {
+            sync: true,
+            ..Default::default()
+        };
+
+        let (report, edits) = export_tasks(&tasks, &api, &options, today());
+
+        assert_eq!(*api.closed.borrow(), vec!["2"]);
+        assert_eq!(report.closed_in_todoist.len(), 1);
+        assert_eq!(report.completed_in_vault.len(), 1);
+        assert_eq!(report.completed_in_vault[0].remote_id, "1");
+
+        let file_edits = &edits["vault/todo.md"];
+        assert_eq!(file_edits.len(), 1);
+        assert_eq!(
+            file_edits[0].replacement,
+            "- [x] Done remotely [todoist:: 1] ✅ 2025-01-06"
+        );
+    }
+
+    #[test]
+    fn test_without_sync_exported_tasks_are_skipped() {
+        let tasks = tasks("- [x] Done locally [todoist:: 2]");
+        let api = FakeTodoist {
+            completed: HashMap::from([("2".to_string(), false)]),
+            ..Default::default()
+        };
+
+        let (report, edits) = export_tasks(&tasks, &api, &TodoistExportOptions::default(), today());
+
+        assert!(api.closed.borrow().is_empty());
+        assert!(report.closed_in_todoist.is_empty());
+        assert!(edits.is_empty());
+    }
+}
@@ -1,28 +1,507 @@
+#[cfg(feature = "fs")]
+use crate::cache::{self, TaskCache};
 use crate::config::Config;
+use crate::frontmatter::frontmatter_line_count;
+use crate::outline_extractor::{Heading, OutlineExtractor};
+#[cfg(feature = "fs")]
+use crate::scan_limits::ScanBudget;
+use crate::unicode::nfc;
+#[cfg(feature = "fs")]
+use crate::warnings::{ExtractionWarning, WarningKind, Warnings};
+use chrono::NaiveDate;
+#[cfg(feature = "fs")]
 use rayon::prelude::*;
 use regex::Regex;
-use schemars::JsonSchema;
+use schemars::{JsonSchema, Schema, SchemaGenerator, json_schema};
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+#[cfg(feature = "fs")]
 use std::fs;
 use std::path::Path;
+use std::str::FromStr;
 use std::sync::Arc;
+#[cfg(feature = "fs")]
+use std::sync::Mutex;
+
+/// Set `Task::blocked` on tasks that depend on an open task among `tasks`
+///
+/// Scans call this once they have every task, since dependencies cross
+/// notes.
+pub fn mark_blocked(tasks: &mut [Task]) {
+    let open: HashSet<String> = open_task_ids(tasks).collect();
+    mark_blocked_by(tasks, &open);
+}
+
+/// IDs of the tasks among `tasks` that are neither completed nor cancelled
+fn open_task_ids(tasks: &[Task]) -> impl Iterator<Item = String> + '_ {
+    tasks
+        .iter()
+        .filter(|task| !matches!(task.status, TaskStatus::Completed | TaskStatus::Cancelled))
+        .filter_map(|task| task.id.clone())
+}
+
+fn mark_blocked_by(tasks: &mut [Task], open: &HashSet<String>) {
+    for task in tasks {
+        task.blocked = task.depends_on.iter().any(|id| open.contains(id));
+    }
+}
+
+/// Callback given the tasks of each file as a scan reads it
+pub type OnFileTasks<'a> = dyn Fn(Vec<Task>) + Sync + 'a;
 
 /// Represents a task found in a markdown file
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Task {
     pub content: String,
-    pub status: String,
+    pub status: TaskStatus,
     pub file_path: String,
     pub file_name: String,
     pub line_number: usize,
     pub raw_line: String,
     pub tags: Vec<String>,
-    pub sub_items: Vec<String>,
+    /// List items indented under the task
+    #[serde(default)]
+    pub sub_items: SubItems,
+    /// Title of the nearest heading above the task
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub heading: Option<String>,
+    /// Titles of the headings the task is nested under, outermost first and
+    /// ending with `heading`, e.g. `["Project X", "Backlog"]`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub heading_path: Vec<String>,
+    /// Context for the task: the nearest heading above it, or the first
+    /// sentence of the note's introduction. Only filled when requested
+    /// (`include_summary`); `None` otherwise.
     pub summary: Option<String>,
-    pub due_date: Option<String>,
-    pub priority: Option<String>,
-    pub created_date: Option<String>,
-    pub completed_date: Option<String>,
+    pub due_date: Option<NaiveDate>,
+    /// Date the task is planned to be worked on (`⏳ 2025-01-20` or
+    /// `scheduled: 2025-01-20`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scheduled_date: Option<NaiveDate>,
+    /// Date before which the task can't be started (`🛫 2025-01-20` or
+    /// `start: 2025-01-20`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<NaiveDate>,
+    pub priority: Option<Priority>,
+    pub created_date: Option<NaiveDate>,
+    pub completed_date: Option<NaiveDate>,
+    /// Dataview-style inline fields (`[key:: value]`), e.g. `[todoist:: 123]`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub fields: BTreeMap<String, String>,
+    /// Minutes logged on the task via `⏱ 1h30m` or `spent:: 45m`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_spent: Option<u32>,
+    /// Whether the task is inside a blockquote or callout (`> - [ ] item`)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub quoted: bool,
+    /// Recurrence rule written after `🔁`, e.g. `every week on Monday`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recurrence: Option<String>,
+    /// Whether this is a future occurrence of a recurring task, generated by
+    /// `expand_recurrences` rather than read from the file
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub occurrence: bool,
+    /// Person the task is assigned to, from `@owner(alice)` or
+    /// `assignee:: alice` (keys set by `assignee_keys`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
+    /// Obsidian block ID written at the end of the line (`^task-abc`), for
+    /// links to the task such as `[[Note#^task-abc]]`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_id: Option<String>,
+    /// ID other tasks can depend on (`🆔 abc123`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// IDs of the tasks this one waits for (`⛔ abc123,def456`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+    /// Whether a task this one depends on is neither completed nor
+    /// cancelled; IDs that no task in the scan has don't block
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub blocked: bool,
+    /// How the line was parsed. Only filled when requested (`debug_parse`);
+    /// `None` otherwise.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub diagnostics: Option<ParseDiagnostics>,
+}
+
+/// A list item indented under a task, with the items indented under it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct SubTask {
+    pub content: String,
+    /// Checkbox status; `None` for a plain list item
+    pub status: Option<TaskStatus>,
+    /// Leading whitespace of the item's line, in characters
+    pub indent: usize,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<SubTask>,
+}
+
+/// The items nested under a task
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum SubItems {
+    /// Items as a tree, following their indentation
+    Tree(Vec<SubTask>),
+    /// The text of each item, outermost first (`flat_sub_items = true`)
+    Flat(Vec<String>),
+}
+
+impl Default for SubItems {
+    fn default() -> Self {
+        SubItems::Tree(Vec::new())
+    }
+}
+
+impl SubItems {
+    pub fn is_empty(&self) -> bool {
+        match self {
+            SubItems::Tree(items) => items.is_empty(),
+            SubItems::Flat(items) => items.is_empty(),
+        }
+    }
+
+    /// The text of every item, in document order
+    pub fn texts(&self) -> Vec<&str> {
+        fn walk<'a>(items: &'a [SubTask], texts: &mut Vec<&'a str>) {
+            for item in items {
+                texts.push(&item.content);
+                walk(&item.children, texts);
+            }
+        }
+        match self {
+            SubItems::Tree(items) => {
+                let mut texts = Vec::new();
+                walk(items, &mut texts);
+                texts
+            }
+            SubItems::Flat(items) => items.iter().map(String::as_str).collect(),
+        }
+    }
+
+    /// The same items in the string form
+    pub fn flatten(&self) -> SubItems {
+        SubItems::Flat(self.texts().into_iter().map(str::to_string).collect())
+    }
+
+    /// Add `item` under the last item indented less than it, or at the top
+    fn push(&mut self, item: SubTask) {
+        fn push_into(items: &mut Vec<SubTask>, item: SubTask) {
+            match items.last_mut() {
+                Some(last) if item.indent > last.indent => push_into(&mut last.children, item),
+                _ => items.push(item),
+            }
+        }
+        match self {
+            SubItems::Tree(items) => push_into(items, item),
+            SubItems::Flat(items) => items.push(item.content),
+        }
+    }
+}
+
+/// How a task line was parsed, to explain a task that wasn't read as expected
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ParseDiagnostics {
+    /// Line pattern that matched, as in `TaskExtractor::line_patterns`, or
+    /// `code_comment` for source-code TODOs
+    pub line_pattern: String,
+    pub confidence: ParseConfidence,
+    /// Metadata removed from the task text, in the order it was removed
+    pub stripped: Vec<StrippedMetadata>,
+    /// Constructs that may not have been read as intended
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ambiguities: Vec<String>,
+}
+
+/// How likely a task was read as its author intended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ParseConfidence {
+    /// Nothing looked ambiguous
+    High,
+    /// All metadata was read, but some of it may not mean what was intended,
+    /// such as a repeated marker or a tag inside a link
+    Medium,
+    /// Something that looks like metadata was ignored
+    Low,
+}
+
+/// A piece of metadata removed from a task's text
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct StrippedMetadata {
+    /// What it was read as (`due_date`, `priority`, `inline_field`, ...)
+    pub pattern: String,
+    /// The text removed
+    pub text: String,
+}
+
+/// Checkbox state of a task
+///
+/// Serialized as `incomplete`, `completed`, `cancelled`, or `other_<char>` for
+/// custom checkbox characters such as `- [>]` (`other_>`). `code_todo` marks
+/// `TODO:`/`FIXME:` comments found in source files (`code_todo_extensions`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub enum TaskStatus {
+    Incomplete,
+    Completed,
+    Cancelled,
+    Other(char),
+    CodeTodo,
+}
+
+impl TaskStatus {
+    /// Status written as `marker` in a `[ ]` checkbox
+    pub fn from_marker(marker: char) -> Self {
+        match marker {
+            ' ' => TaskStatus::Incomplete,
+            'x' | 'X' => TaskStatus::Completed,
+            '-' => TaskStatus::Cancelled,
+            other => TaskStatus::Other(other),
+        }
+    }
+}
+
+impl fmt::Display for TaskStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TaskStatus::Incomplete => write!(f, "incomplete"),
+            TaskStatus::Completed => write!(f, "completed"),
+            TaskStatus::Cancelled => write!(f, "cancelled"),
+            TaskStatus::Other(c) => write!(f, "other_{}", c),
+            TaskStatus::CodeTodo => write!(f, "code_todo"),
+        }
+    }
+}
+
+impl FromStr for TaskStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "incomplete" => Ok(TaskStatus::Incomplete),
+            "completed" => Ok(TaskStatus::Completed),
+            "cancelled" => Ok(TaskStatus::Cancelled),
+            "code_todo" => Ok(TaskStatus::CodeTodo),
+            _ => {
+                let mut chars = s.strip_prefix("other_").unwrap_or_default().chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Ok(TaskStatus::Other(c)),
+                    _ => Err(format!(
+                        "Invalid status '{}': expected incomplete, completed, cancelled, code_todo, or other_<char>",
+                        s
+                    )),
+                }
+            }
+        }
+    }
+}
+
+impl From<TaskStatus> for String {
+    fn from(status: TaskStatus) -> Self {
+        status.to_string()
+    }
+}
+
+impl TryFrom<String> for TaskStatus {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl JsonSchema for TaskStatus {
+    fn schema_name() -> Cow<'static, str> {
+        "TaskStatus".into()
+    }
+
+    fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "description": "Task status: incomplete, completed, cancelled, other_<char> for custom checkboxes (e.g. other_>), or code_todo for TODO/FIXME comments in source files",
+            "anyOf": [
+                { "enum": ["incomplete", "completed", "cancelled", "code_todo"] },
+                { "pattern": "^other_.$" }
+            ]
+        })
+    }
+}
+
+/// Task priority, ordered from lowest to most urgent
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Priority {
+    Lowest,
+    Low,
+    Medium,
+    High,
+    Urgent,
+}
+
+impl fmt::Display for Priority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Priority::Lowest => "lowest",
+            Priority::Low => "low",
+            Priority::Medium => "medium",
+            Priority::High => "high",
+            Priority::Urgent => "urgent",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Priority {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "lowest" => Ok(Priority::Lowest),
+            "low" => Ok(Priority::Low),
+            "medium" => Ok(Priority::Medium),
+            "high" => Ok(Priority::High),
+            "urgent" => Ok(Priority::Urgent),
+            _ => Err(format!(
+                "Invalid priority '{}': expected lowest, low, medium, high, or urgent",
+                s
+            )),
+        }
+    }
+}
+
+/// Parse a YYYY-MM-DD date, returning None for impossible dates like 2025-02-30
+fn parse_iso_date(s: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d").ok()
+}
+
+/// First date captured by `patterns`, in order, that is a real date; a
+/// marker like `📅 2025-02-30` is skipped for any later one on the line
+fn first_valid_date(patterns: &[Regex], content: &str) -> Option<NaiveDate> {
+    patterns
+        .iter()
+        .flat_map(|pattern| pattern.captures_iter(content))
+        .find_map(|caps| parse_iso_date(&caps[1]))
+}
+
+/// Parse a duration like `1h30m`, `1h 30m`, `2h`, or `45m` into minutes
+pub fn parse_duration(s: &str) -> Option<u32> {
+    let mut minutes: u32 = 0;
+    let mut digits = String::new();
+    let mut seen_unit = false;
+    for c in s.trim().chars() {
+        match c {
+            '0'..='9' => digits.push(c),
+            'h' | 'm' if !digits.is_empty() => {
+                let value: u32 = digits.parse().ok()?;
+                let value = if c == 'h' {
+                    value.checked_mul(60)?
+                } else {
+                    value
+                };
+                minutes = minutes.checked_add(value)?;
+                digits.clear();
+                seen_unit = true;
+            }
+            c if c.is_whitespace() && digits.is_empty() => {}
+            _ => return None,
+        }
+    }
+    (seen_unit && digits.is_empty()).then_some(minutes)
+}
+
+/// Strip leading blockquote/callout markers (`> `, `>> `, `> > `) from a line
+///
+/// Returns whether any markers were found, and the rest of the line. Unquoted
+/// lines are returned unchanged, indentation included.
+fn strip_blockquote(line: &str) -> (bool, &str) {
+    let mut quoted = false;
+    let mut rest = line;
+    while let Some(after) = rest.trim_start().strip_prefix('>') {
+        quoted = true;
+        rest = after.strip_prefix(' ').unwrap_or(after);
+    }
+    (quoted, rest)
+}
+
+/// Summary for a task on `line_number`: the nearest heading above it, else the intro
+fn section_summary(
+    headings: &[Heading],
+    intro: Option<&str>,
+    line_number: usize,
+) -> Option<String> {
+    match headings.iter().rev().find(|h| h.line_number < line_number) {
+        Some(heading) => Some(heading.title.clone()),
+        None => intro.map(str::to_string),
+    }
+}
+
+/// Set `heading` and `heading_path` of `tasks`, which are in line order, from
+/// the note's `headings`
+fn fill_heading_paths(headings: &[Heading], tasks: &mut [Task]) {
+    let mut open: Vec<&Heading> = Vec::new();
+    let mut headings = headings.iter().peekable();
+    for task in tasks {
+        while let Some(heading) = headings.next_if(|h| h.line_number < task.line_number) {
+            // A heading closes the sections at its level and below
+            while open.last().is_some_and(|h| h.level >= heading.level) {
+                open.pop();
+            }
+            open.push(heading);
+        }
+        task.heading_path = open.iter().map(|h| h.title.clone()).collect();
+        task.heading = task.heading_path.last().cloned();
+    }
+}
+
+/// First sentence of the prose before a note's first heading
+///
+/// Frontmatter, list items, quotes, tables, and code fences are skipped.
+fn intro_sentence(content: &str) -> Option<String> {
+    let mut lines = content.lines().peekable();
+    if lines.peek().map(|l| l.trim_end()) == Some("---") {
+        lines.next();
+        for line in lines.by_ref() {
+            if line.trim_end() == "---" {
+                break;
+            }
+        }
+    }
+
+    let mut in_code_block = false;
+    for line in lines {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block || trimmed.is_empty() {
+            continue;
+        }
+        if trimmed.starts_with('#') && trimmed.trim_start_matches('#').starts_with(' ') {
+            // Reached the first heading
+            return None;
+        }
+        let is_block = trimmed.starts_with(['-', '*', '+', '>', '|', '!', '%', '<'])
+            || trimmed
+                .split_once(". ")
+                .is_some_and(|(n, _)| n.chars().all(|c| c.is_ascii_digit()));
+        if is_block {
+            continue;
+        }
+
+        let end = trimmed
+            .match_indices(['.', '!', '?'])
+            .map(|(i, m)| i + m.len())
+            .find(|&i| trimmed[i..].is_empty() || trimmed[i..].starts_with(' '))
+            .unwrap_or(trimmed.len());
+        return Some(trimmed[..end].to_string());
+    }
+    None
 }
 
 /// Extracts tasks from markdown files
@@ -33,9 +512,23 @@ pub struct TaskExtractor {
     task_other: Regex,
     tag_pattern: Regex,
     due_date_patterns: Vec<Regex>,
+    scheduled_patterns: Vec<Regex>,
+    start_patterns: Vec<Regex>,
     priority_pattern: Regex,
     created_patterns: Vec<Regex>,
     completion_patterns: Vec<Regex>,
+    inline_field_pattern: Regex,
+    time_spent_pattern: Regex,
+    recurrence_pattern: Regex,
+    // `@key(name)` / `key:: name` for each configured assignee key
+    assignee_pattern: Option<Regex>,
+    // `^block-id` at the end of the line
+    block_id_pattern: Regex,
+    // `🆔 id` and `⛔ id,id` task dependencies
+    id_pattern: Regex,
+    depends_on_pattern: Regex,
+    // `// TODO: ...` and `# FIXME: ...` comments in source files
+    code_comment_pattern: Regex,
     // Cleaning patterns (moved from clean_content())
     timestamp_pattern: Regex,
     priority_emoji_pattern: Regex,
@@ -43,12 +536,28 @@ pub struct TaskExtractor {
     whitespace_pattern: Regex,
     // Sub-item pattern (moved from parse_sub_item())
     checkbox_pattern: Regex,
+    // Heading detection for summaries
+    outline_extractor: OutlineExtractor,
     // Configuration for path exclusion
+    #[cfg_attr(not(feature = "fs"), allow(dead_code))]
     config: Arc<Config>,
 }
 
 impl TaskExtractor {
     pub fn new(config: Arc<Config>) -> Self {
+        let assignee_keys: Vec<String> = config
+            .assignee_keys
+            .iter()
+            .map(|key| regex::escape(key))
+            .collect();
+        let assignee_pattern = (!assignee_keys.is_empty()).then(|| {
+            Regex::new(&format!(
+                r"(?i)@(?:{0})\(\s*([^)]*?)\s*\)|\b(?:{0})::\s*([^\s\[\]]+)",
+                assignee_keys.join("|")
+            ))
+            .unwrap()
+        });
+
         TaskExtractor {
             task_incomplete: Regex::new(r"^(\s*)-\s*\[\s\]\s*(.+)$").unwrap(),
             task_completed: Regex::new(r"(?i)^(\s*)-\s*\[x\]\s*(.+)$").unwrap(),
@@ -60,6 +569,14 @@ impl TaskExtractor {
                 Regex::new(r"due:\s*(\d{4}-\d{2}-\d{2})").unwrap(),
                 Regex::new(r"@due\((\d{4}-\d{2}-\d{2})\)").unwrap(),
             ],
+            scheduled_patterns: vec![
+                Regex::new(r"⏳\s*(\d{4}-\d{2}-\d{2})").unwrap(),
+                Regex::new(r"scheduled:\s*(\d{4}-\d{2}-\d{2})").unwrap(),
+            ],
+            start_patterns: vec![
+                Regex::new(r"🛫\s*(\d{4}-\d{2}-\d{2})").unwrap(),
+                Regex::new(r"start:\s*(\d{4}-\d{2}-\d{2})").unwrap(),
+            ],
             priority_pattern: Regex::new(r"[⏫🔼🔽⏬]|priority:\s*(high|medium|low)").unwrap(),
             created_patterns: vec![
                 Regex::new(r"➕\s*(\d{4}-\d{2}-\d{2})").unwrap(),
@@ -69,113 +586,382 @@ impl TaskExtractor {
                 Regex::new(r"✅\s*(\d{4}-\d{2}-\d{2})").unwrap(),
                 Regex::new(r"completed:\s*(\d{4}-\d{2}-\d{2})").unwrap(),
             ],
+            inline_field_pattern: Regex::new(r"\[([\w-]+)::\s*([^\]]*?)\s*\]").unwrap(),
+            time_spent_pattern: Regex::new(
+                r"(?:⏱\x{FE0F}?|\bspent::)\s*(\d+\s*[hm](?:\s*\d+\s*m)?)\b",
+            )
+            .unwrap(),
+            recurrence_pattern: Regex::new(r"🔁\x{FE0F}?\s*([^📅⏳🛫➕✅❌⏫🔼🔽⏬⏱🆔⛔#\[]*)")
+                .unwrap(),
+            assignee_pattern,
+            block_id_pattern: Regex::new(r"(?:^|\s)\^([A-Za-z0-9-]+)\s*$").unwrap(),
+            id_pattern: Regex::new(r"🆔\s*([\w-]+)").unwrap(),
+            depends_on_pattern: Regex::new(r"⛔\x{FE0F}?\s*([\w-]+(?:\s*,\s*[\w-]+)*)").unwrap(),
+            code_comment_pattern: Regex::new(
+                r"(?:^\s*\*|(?:^|\s)(?://+|#+|--|/\*+|;+|<!--))\s*(TODO|FIXME)\b(?:\([^)]*\))?:?\s*(.*?)\s*(?:\*/|-->)?\s*$",
+            )
+            .unwrap(),
             // Cleaning patterns
             timestamp_pattern: Regex::new(r"^\d{2}:\d{2} ").unwrap(),
             priority_emoji_pattern: Regex::new(r"[⏫🔼🔽⏬]").unwrap(),
             priority_text_pattern: Regex::new(r"(?i)priority:\s*(high|medium|low)").unwrap(),
             whitespace_pattern: Regex::new(r"\s+").unwrap(),
             // Sub-item pattern
-            checkbox_pattern: Regex::new(r"^-\s*\[.\]\s*(.+)$").unwrap(),
+            checkbox_pattern: Regex::new(r"^-\s*\[(.)\]\s*(.+)$").unwrap(),
+            outline_extractor: OutlineExtractor::new(),
             config,
         }
     }
 
+    /// Regexes tried against every line of a note, by name
+    pub fn line_patterns(&self) -> Vec<(&'static str, &Regex)> {
+        vec![
+            ("task_incomplete", &self.task_incomplete),
+            ("task_completed", &self.task_completed),
+            ("task_cancelled", &self.task_cancelled),
+            ("task_other", &self.task_other),
+        ]
+    }
+
+    /// Regexes run on the text of each task to extract and strip metadata,
+    /// by name (variants of one field share a name)
+    pub fn metadata_patterns(&self) -> Vec<(&'static str, &Regex)> {
+        let mut patterns = vec![("tag", &self.tag_pattern)];
+        patterns.extend(self.due_date_patterns.iter().map(|p| ("due_date", p)));
+        patterns.extend(
+            self.scheduled_patterns
+                .iter()
+                .map(|p| ("scheduled_date", p)),
+        );
+        patterns.extend(self.start_patterns.iter().map(|p| ("start_date", p)));
+        patterns.push(("priority", &self.priority_pattern));
+        patterns.extend(self.created_patterns.iter().map(|p| ("created_date", p)));
+        patterns.extend(
+            self.completion_patterns
+                .iter()
+                .map(|p| ("completed_date", p)),
+        );
+        patterns.push(("inline_field", &self.inline_field_pattern));
+        patterns.push(("time_spent", &self.time_spent_pattern));
+        patterns.push(("recurrence", &self.recurrence_pattern));
+        if let Some(pattern) = &self.assignee_pattern {
+            patterns.push(("assignee", pattern));
+        }
+        patterns.push(("block_id", &self.block_id_pattern));
+        patterns.push(("id", &self.id_pattern));
+        patterns.push(("depends_on", &self.depends_on_pattern));
+        patterns.push(("clean_priority_emoji", &self.priority_emoji_pattern));
+        patterns.push(("clean_priority_text", &self.priority_text_pattern));
+        patterns.push(("clean_whitespace", &self.whitespace_pattern));
+        patterns
+    }
+
     fn extract_tags(&self, content: &str) -> Vec<String> {
         self.tag_pattern
             .captures_iter(content)
-            .map(|cap| cap.get(1).unwrap().as_str().to_string())
+            .map(|cap| nfc(cap.get(1).unwrap().as_str()).into_owned())
             .collect()
     }
 
-    fn extract_due_date(&self, content: &str) -> Option<String> {
-        for pattern in &self.due_date_patterns {
-            if let Some(caps) = pattern.captures(content) {
-                return Some(caps.get(1).unwrap().as_str().to_string());
-            }
-        }
-        None
+    fn extract_due_date(&self, content: &str) -> Option<NaiveDate> {
+        first_valid_date(&self.due_date_patterns, content)
+    }
+
+    fn extract_scheduled_date(&self, content: &str) -> Option<NaiveDate> {
+        first_valid_date(&self.scheduled_patterns, content)
+    }
+
+    fn extract_start_date(&self, content: &str) -> Option<NaiveDate> {
+        first_valid_date(&self.start_patterns, content)
     }
 
-    fn extract_priority(&self, content: &str) -> Option<String> {
+    fn extract_priority(&self, content: &str) -> Option<Priority> {
         if let Some(caps) = self.priority_pattern.captures(content) {
             let matched = caps.get(0).unwrap().as_str();
             match matched {
-                "⏫" => Some("urgent".to_string()),
-                "🔼" => Some("high".to_string()),
-                "🔽" => Some("low".to_string()),
-                "⏬" => Some("lowest".to_string()),
-                _ => caps.get(1).map(|m| m.as_str().to_lowercase()),
+                "⏫" => Some(Priority::Urgent),
+                "🔼" => Some(Priority::High),
+                "🔽" => Some(Priority::Low),
+                "⏬" => Some(Priority::Lowest),
+                _ => caps.get(1).and_then(|m| m.as_str().parse().ok()),
             }
         } else {
             None
         }
     }
 
-    fn extract_created_date(&self, content: &str) -> Option<String> {
-        for pattern in &self.created_patterns {
-            if let Some(caps) = pattern.captures(content) {
-                return Some(caps.get(1).unwrap().as_str().to_string());
-            }
-        }
-        None
+    fn extract_created_date(&self, content: &str) -> Option<NaiveDate> {
+        first_valid_date(&self.created_patterns, content)
     }
 
-    fn extract_completed_date(&self, content: &str) -> Option<String> {
-        for pattern in &self.completion_patterns {
-            if let Some(caps) = pattern.captures(content) {
-                return Some(caps.get(1).unwrap().as_str().to_string());
-            }
+    fn extract_completed_date(&self, content: &str) -> Option<NaiveDate> {
+        first_valid_date(&self.completion_patterns, content)
+    }
+
+    fn extract_inline_fields(&self, content: &str) -> BTreeMap<String, String> {
+        self.inline_field_pattern
+            .captures_iter(content)
+            .map(|caps| {
+                (
+                    caps.get(1).unwrap().as_str().to_string(),
+                    caps.get(2).unwrap().as_str().to_string(),
+                )
+            })
+            .collect()
+    }
+
+    fn extract_recurrence(&self, content: &str) -> Option<String> {
+        self.recurrence_pattern
+            .captures(content)
+            .map(|caps| caps.get(1).unwrap().as_str().trim().to_string())
+            .filter(|rule| !rule.is_empty())
+    }
+
+    /// Assignee from a `[key:: name]` inline field or `@key(name)` / `key:: name`
+    fn extract_assignee(&self, content: &str, fields: &BTreeMap<String, String>) -> Option<String> {
+        let from_field = fields.iter().find_map(|(key, value)| {
+            self.config
+                .assignee_keys
+                .iter()
+                .any(|wanted| wanted.eq_ignore_ascii_case(key))
+                .then_some(value.as_str())
+        });
+        let name = from_field.or_else(|| {
+            let caps = self.assignee_pattern.as_ref()?.captures(content)?;
+            caps.get(1).or_else(|| caps.get(2)).map(|m| m.as_str())
+        })?;
+
+        let name = name.trim().trim_start_matches('@');
+        (!name.is_empty()).then(|| name.to_string())
+    }
+
+    /// Split a trailing `^block-id` off task text
+    fn split_block_id<'a>(&self, content: &'a str) -> (Cow<'a, str>, Option<String>) {
+        match self.block_id_pattern.captures(content) {
+            Some(caps) => (
+                Cow::Owned(content[..caps.get(0).unwrap().start()].to_string()),
+                Some(caps[1].to_string()),
+            ),
+            None => (Cow::Borrowed(content), None),
         }
-        None
+    }
+
+    fn extract_id(&self, content: &str) -> Option<String> {
+        self.id_pattern
+            .captures(content)
+            .map(|caps| caps[1].to_string())
+    }
+
+    fn extract_depends_on(&self, content: &str) -> Vec<String> {
+        self.depends_on_pattern
+            .captures_iter(content)
+            .flat_map(|caps| {
+                caps[1]
+                    .split(',')
+                    .map(|id| id.trim().to_string())
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    fn extract_time_spent(&self, content: &str) -> Option<u32> {
+        self.time_spent_pattern
+            .captures(content)
+            .and_then(|caps| parse_duration(caps.get(1).unwrap().as_str()))
     }
 
     fn clean_content(&self, content: &str) -> String {
-        use std::borrow::Cow;
+        self.strip_metadata(content, None)
+    }
+
+    /// Remove metadata markers from task text, adding what was removed to
+    /// `stripped` if given
+    fn strip_metadata(
+        &self,
+        content: &str,
+        mut stripped: Option<&mut Vec<StrippedMetadata>>,
+    ) -> String {
+        // Removed in this order, e.g. the block ID before a recurrence rule
+        // would run into it, and bracketed fields like [assignee:: alice] and
+        // [spent:: 45m] before bare assignees and time tracking; the
+        // timestamp prefix becomes a space
+        let steps = [("block_id", &self.block_id_pattern, "")]
+            .into_iter()
+            .chain(self.due_date_patterns.iter().map(|p| ("due_date", p, "")))
+            .chain(
+                self.scheduled_patterns
+                    .iter()
+                    .map(|p| ("scheduled_date", p, "")),
+            )
+            .chain(self.start_patterns.iter().map(|p| ("start_date", p, "")))
+            .chain([
+                ("timestamp", &self.timestamp_pattern, " "),
+                ("priority", &self.priority_emoji_pattern, ""),
+                ("priority", &self.priority_text_pattern, ""),
+            ])
+            .chain(
+                self.created_patterns
+                    .iter()
+                    .map(|p| ("created_date", p, "")),
+            )
+            .chain(
+                self.completion_patterns
+                    .iter()
+                    .map(|p| ("completed_date", p, "")),
+            )
+            .chain([("inline_field", &self.inline_field_pattern, "")])
+            .chain(self.assignee_pattern.iter().map(|p| ("assignee", p, "")))
+            .chain([
+                ("time_spent", &self.time_spent_pattern, ""),
+                ("recurrence", &self.recurrence_pattern, ""),
+                ("id", &self.id_pattern, ""),
+                ("depends_on", &self.depends_on_pattern, ""),
+            ]);
 
         // Start with borrowed content
         let mut cleaned = Cow::Borrowed(content);
-
-        // Remove due date patterns
-        for pattern in &self.due_date_patterns {
-            if let Cow::Owned(s) = pattern.replace_all(&cleaned, "") {
+        for (name, pattern, replacement) in steps {
+            if let Some(stripped) = stripped.as_deref_mut() {
+                stripped.extend(pattern.find_iter(&cleaned).map(|m| StrippedMetadata {
+                    pattern: name.to_string(),
+                    text: m.as_str().trim().to_string(),
+                }));
+            }
+            if let Cow::Owned(s) = pattern.replace_all(&cleaned, replacement) {
                 cleaned = Cow::Owned(s);
             }
         }
 
-        // Remove timestamp prefix
-        if let Cow::Owned(s) = self.timestamp_pattern.replace_all(&cleaned, " ") {
+        // Clean up extra whitespace
+        if let Cow::Owned(s) = self.whitespace_pattern.replace_all(&cleaned, " ") {
             cleaned = Cow::Owned(s);
         }
 
-        // Remove priority indicators
-        if let Cow::Owned(s) = self.priority_emoji_pattern.replace_all(&cleaned, "") {
-            cleaned = Cow::Owned(s);
+        // Final trim and convert to owned String
+        cleaned.trim().to_string()
+    }
+
+    /// Explain how `task` was parsed from its `raw_line`
+    pub fn diagnose_task(&self, task: &Task) -> ParseDiagnostics {
+        let (_, line) = strip_blockquote(&task.raw_line);
+        let (line_pattern, content) = if task.status == TaskStatus::CodeTodo {
+            let text = self
+                .code_comment_pattern
+                .captures(line)
+                .and_then(|caps| caps.get(2));
+            ("code_comment", text.map_or(line, |m| m.as_str()))
+        } else {
+            // The text is the last capture of the first pattern that matches,
+            // as in parse_task_line
+            self.line_patterns()
+                .into_iter()
+                .find_map(|(name, pattern)| {
+                    let caps = pattern.captures(line)?;
+                    Some((name, caps.get(caps.len() - 1)?.as_str()))
+                })
+                .unwrap_or(("none", line))
+        };
+
+        let mut stripped = Vec::new();
+        let cleaned = self.strip_metadata(content, Some(&mut stripped));
+        let mut ambiguities = Vec::new();
+        let mut ignored = false;
+
+        for field in [
+            "due_date",
+            "scheduled_date",
+            "start_date",
+            "priority",
+            "created_date",
+            "completed_date",
+            "recurrence",
+        ] {
+            let count = stripped.iter().filter(|s| s.pattern == field).count();
+            if count > 1 {
+                ambiguities.push(format!("{} {} markers; only one is used", count, field));
+            }
         }
-        if let Cow::Owned(s) = self.priority_text_pattern.replace_all(&cleaned, "") {
-            cleaned = Cow::Owned(s);
+        for item in &stripped {
+            let date = item
+                .text
+                .split(|c: char| !c.is_ascii_digit() && c != '-')
+                .find(|part| part.len() == 10);
+            if let Some(date) = date
+                && item.pattern.ends_with("_date")
+                && parse_iso_date(date).is_none()
+            {
+                ambiguities.push(format!(
+                    "'{}' is not a valid date and was ignored",
+                    item.text
+                ));
+                ignored = true;
+            }
         }
-
-        // Remove created date patterns
-        for pattern in &self.created_patterns {
-            if let Cow::Owned(s) = pattern.replace_all(&cleaned, "") {
-                cleaned = Cow::Owned(s);
+        // The priority text pattern is case-insensitive but reading it isn't
+        if task.priority.is_none()
+            && let Some(item) = stripped.iter().find(|s| s.pattern == "priority")
+        {
+            ambiguities.push(format!(
+                "'{}' was removed from the text but not read as a priority",
+                item.text
+            ));
+            ignored = true;
+        }
+        for marker in ['📅', '⏳', '🛫', '➕', '✅', '❌', '⏱', '🔺'] {
+            if cleaned.contains(marker) {
+                ambiguities.push(format!(
+                    "'{}' was left in the text: it isn't followed by metadata that can be read",
+                    marker
+                ));
+                ignored = true;
             }
         }
-
-        // Remove completed date patterns
-        for pattern in &self.completion_patterns {
-            if let Cow::Owned(s) = pattern.replace_all(&cleaned, "") {
-                cleaned = Cow::Owned(s);
+        for m in self.tag_pattern.find_iter(content) {
+            let before = content[..m.start()].chars().next_back();
+            let after = content[m.end()..].chars().next();
+            if before.is_some_and(|c| !c.is_whitespace()) {
+                ambiguities.push(format!(
+                    "'{}' is read as a tag although it follows other text",
+                    m.as_str()
+                ));
+            } else if matches!(after, Some('/' | '-')) {
+                ambiguities.push(format!(
+                    "'{}' is read as the tag '{}'; only letters, digits, and _ are part of a tag",
+                    &content[m.start()..]
+                        .split_whitespace()
+                        .next()
+                        .unwrap_or_default(),
+                    &m.as_str()[1..]
+                ));
             }
         }
+        if task.completed_date.is_some() && task.status != TaskStatus::Completed {
+            ambiguities.push(format!(
+                "Completion date on a task that is {}, not completed",
+                task.status
+            ));
+        }
 
-        // Clean up extra whitespace
-        if let Cow::Owned(s) = self.whitespace_pattern.replace_all(&cleaned, " ") {
-            cleaned = Cow::Owned(s);
+        let confidence = if ignored {
+            ParseConfidence::Low
+        } else if ambiguities.is_empty() {
+            ParseConfidence::High
+        } else {
+            ParseConfidence::Medium
+        };
+        ParseDiagnostics {
+            line_pattern: line_pattern.to_string(),
+            confidence,
+            stripped,
+            ambiguities,
         }
+    }
 
-        // Final trim and convert to owned String
-        cleaned.trim().to_string()
+    /// Fill `Task::diagnostics` for each task
+    pub fn diagnose_tasks(&self, tasks: &mut [Task]) {
+        for task in tasks {
+            task.diagnostics = Some(self.diagnose_task(task));
+        }
     }
 
     fn is_sub_item(&self, line: &str, parent_line: &str) -> bool {
@@ -201,43 +987,134 @@ impl TaskExtractor {
             || stripped.starts_with("* [")
     }
 
-    fn parse_sub_item(&self, line: &str) -> Option<String> {
+    fn parse_sub_item(&self, line: &str) -> Option<SubTask> {
         let stripped = line.trim();
+        let indent = line.chars().take_while(|c| c.is_whitespace()).count();
+        let item = |content: &str, status| SubTask {
+            content: content.trim().to_string(),
+            status,
+            indent,
+            children: Vec::new(),
+        };
 
         // Handle checkbox sub-items
         if stripped.starts_with("- [")
             && let Some(caps) = self.checkbox_pattern.captures(stripped)
         {
-            return Some(caps.get(1).unwrap().as_str().trim().to_string());
+            let marker = caps[1].chars().next()?;
+            return Some(item(&caps[2], Some(TaskStatus::from_marker(marker))));
         }
 
         // Handle regular list items
         if stripped.starts_with('-') || stripped.starts_with('*') {
-            return Some(stripped[1..].trim().to_string());
+            return Some(item(&stripped[1..], None));
         }
 
         None
     }
 
+    #[cfg(feature = "fs")]
     fn extract_tasks_from_file(
         &self,
         file_path: &Path,
+        cache: Option<&TaskCache>,
+    ) -> Result<Vec<Task>, Box<dyn std::error::Error>> {
+        match cache {
+            Some(cache) => cache.get_or_extract(file_path, || self.read_tasks_from_file(file_path)),
+            None => self.read_tasks_from_file(file_path),
+        }
+    }
+
+    #[cfg(feature = "fs")]
+    fn read_tasks_from_file(
+        &self,
+        file_path: &Path,
     ) -> Result<Vec<Task>, Box<dyn std::error::Error>> {
         // Read file as bytes and validate UTF-8 with SIMD
         let bytes = fs::read(file_path)?;
         let content = simdutf8::basic::from_utf8(&bytes)
             .map_err(|e| format!("Invalid UTF-8 in {:?}: {}", file_path, e))?;
+        Ok(self.extract_tasks_from_file_content(content, file_path))
+    }
+
+    /// Tasks in the content of `file_path`: `TODO:` comments for a code
+    /// file, task checkboxes otherwise
+    #[cfg(feature = "fs")]
+    pub(crate) fn extract_tasks_from_file_content(
+        &self,
+        content: &str,
+        file_path: &Path,
+    ) -> Vec<Task> {
+        if self.config.is_code_file(file_path) {
+            self.extract_code_todos_from_content(content, file_path)
+        } else {
+            self.extract_tasks_from_content(content, file_path)
+        }
+    }
+
+    /// Fill `Task::summary` for tasks parsed from `content`
+    ///
+    /// The summary is the title of the nearest heading above the task or, for
+    /// tasks before the first heading, the first sentence of the note's
+    /// introduction.
+    pub fn summarize_tasks_from_content(&self, content: &str, tasks: &mut [Task]) {
+        self.fill_summaries(content, tasks.iter_mut());
+    }
+
+    fn fill_summaries<'a>(&self, content: &str, tasks: impl IntoIterator<Item = &'a mut Task>) {
+        let headings = self.outline_extractor.extract_headings(content);
+        let intro = intro_sentence(content);
+        for task in tasks {
+            task.summary = section_summary(&headings, intro.as_deref(), task.line_number);
+        }
+    }
+
+    /// Fill `Task::summary` for tasks extracted from disk, reading each file once
+    #[cfg(feature = "fs")]
+    pub fn summarize_tasks(&self, tasks: &mut [Task]) {
+        let mut by_file: BTreeMap<String, Vec<&mut Task>> = BTreeMap::new();
+        for task in tasks.iter_mut() {
+            by_file
+                .entry(task.file_path.clone())
+                .or_default()
+                .push(task);
+        }
+
+        for (file_path, file_tasks) in by_file {
+            if let Ok(content) = fs::read_to_string(&file_path) {
+                self.fill_summaries(&content, file_tasks);
+            }
+        }
+    }
+
+    /// Extract tasks from in-memory markdown content
+    ///
+    /// `file_path` is only used to populate `Task::file_path` and
+    /// `Task::file_name`; nothing is read from disk.
+    pub fn extract_tasks_from_content(&self, content: &str, file_path: &Path) -> Vec<Task> {
         let mut tasks = Vec::new();
 
-        // Use iterator instead of collecting into Vec
-        let mut lines = content.lines().enumerate().peekable();
+        // Use iterator instead of collecting into Vec; checkboxes in YAML
+        // frontmatter aren't tasks
+        let mut lines = content
+            .lines()
+            .enumerate()
+            .skip(frontmatter_line_count(content))
+            .peekable();
 
         while let Some((line_num, line)) = lines.next() {
-            if let Some(mut task) = self.parse_task_line(line, file_path, line_num + 1) {
-                // Look ahead for sub-items on subsequent lines
+            let (quoted, body) = strip_blockquote(line);
+            if let Some(mut task) = self.parse_task_line(body, file_path, line_num + 1) {
+                if quoted {
+                    task.quoted = true;
+                    task.raw_line = line.trim_end_matches(&['\n', '\r'][..]).to_string();
+                }
+
+                // Look ahead for sub-items on subsequent lines (at the same quote level)
                 while let Some(&(_, next_line)) = lines.peek() {
-                    if self.is_sub_item(next_line, &task.raw_line) {
-                        if let Some(sub_item) = self.parse_sub_item(next_line) {
+                    let (next_quoted, next_body) = strip_blockquote(next_line);
+                    if next_quoted == quoted && self.is_sub_item(next_body, body) {
+                        if let Some(sub_item) = self.parse_sub_item(next_body) {
                             task.sub_items.push(sub_item);
                         }
                         lines.next(); // Consume the sub-item line
@@ -245,30 +1122,212 @@ impl TaskExtractor {
                         break;
                     }
                 }
+                if self.config.flat_sub_items {
+                    task.sub_items = task.sub_items.flatten();
+                }
                 tasks.push(task);
             }
         }
 
+        if !tasks.is_empty() {
+            let headings = self.outline_extractor.extract_headings(content);
+            fill_heading_paths(&headings, &mut tasks);
+        }
+        tasks
+    }
+
+    /// Extract `TODO:`/`FIXME:` comments from in-memory source code
+    ///
+    /// Each comment becomes a task with status `code_todo`, tagged `todo` or
+    /// `fixme`; metadata in the comment text (due dates, tags, ...) is parsed
+    /// as for Markdown tasks.
+    pub fn extract_code_todos_from_content(&self, content: &str, file_path: &Path) -> Vec<Task> {
+        content
+            .lines()
+            .enumerate()
+            .filter_map(|(line_num, line)| {
+                let caps = self.code_comment_pattern.captures(line)?;
+                let text = caps.get(2)?.as_str();
+                if text.is_empty() {
+                    return None;
+                }
+
+                let mut task = self.create_task(
+                    text.to_string(),
+                    TaskStatus::CodeTodo,
+                    line.trim_end_matches('\r'),
+                    file_path,
+                    line_num + 1,
+                );
+                let keyword = caps[1].to_lowercase();
+                if !task.tags.contains(&keyword) {
+                    task.tags.insert(0, keyword);
+                }
+                Some(task)
+            })
+            .collect()
+    }
+
+    /// Extract tasks along with the files that were skipped
+    ///
+    /// Unreadable and oversized files don't fail the scan; they are returned
+    /// as warnings instead.
+    #[cfg(feature = "fs")]
+    pub fn extract_tasks_with_warnings(
+        &self,
+        path: &Path,
+    ) -> Result<(Vec<Task>, Vec<ExtractionWarning>), Box<dyn std::error::Error>> {
+        self.extract_tasks_observed(path, None)
+    }
+
+    /// Extract tasks along with the files that were skipped, handing the
+    /// tasks of each file to `on_file` as soon as it is read
+    ///
+    /// With `on_file`, the scan keeps no tasks: they all go to the callback,
+    /// with their `git_dates` dates filled in, and the returned list is
+    /// empty. `on_file` runs on the scan's worker threads, in no particular
+    /// order. Whether a task is `blocked` is only known once every note is
+    /// read, so tasks with `depends_on` are held back and handed over in one
+    /// last call after the scan, marked blocked or not.
+    #[cfg(feature = "fs")]
+    pub fn extract_tasks_observed(
+        &self,
+        path: &Path,
+        on_file: Option<&OnFileTasks<'_>>,
+    ) -> Result<(Vec<Task>, Vec<ExtractionWarning>), Box<dyn std::error::Error>> {
+        // Dates are filled in file by file, so each file's tasks are complete
+        // when they are handed over
+        let repo = on_file
+            .filter(|_| self.config.git_dates)
+            .and_then(|_| crate::git_dates::GitRepo::discover(path));
+        let open = Mutex::new(HashSet::new());
+        let waiting = Mutex::new(Vec::new());
+        let dated = |mut tasks: Vec<Task>| {
+            if let Some(repo) = &repo {
+                crate::git_dates::fill_task_dates_in(repo, &mut tasks);
+            }
+            open.lock().unwrap().extend(open_task_ids(&tasks));
+            let (dependent, tasks): (Vec<Task>, Vec<Task>) = tasks
+                .into_iter()
+                .partition(|task| !task.depends_on.is_empty());
+            waiting.lock().unwrap().extend(dependent);
+            if let Some(on_file) = on_file {
+                on_file(tasks);
+            }
+        };
+        let observed = on_file.map(|_| &dated as &OnFileTasks);
+
+        // With sqlite_index, a vault's tasks come from the SQLite index
+        #[cfg(feature = "sqlite")]
+        if let Some(index) = crate::sqlite_index::VaultIndex::for_vault(path, &self.config) {
+            let mut tasks = index.tasks(&self.config)?;
+            mark_blocked(&mut tasks);
+            if let Some(on_file) = on_file {
+                if let Some(repo) = &repo {
+                    crate::git_dates::fill_task_dates_in(repo, &mut tasks);
+                }
+                on_file(tasks);
+                return Ok((Vec::new(), Vec::new()));
+            }
+            if self.config.git_dates {
+                crate::git_dates::fill_task_dates(path, &mut tasks);
+            }
+            return Ok((tasks, Vec::new()));
+        }
+
+        let warnings = Warnings::default();
+
+        // With --cache, unchanged files are served from the on-disk cache
+        let tasks = if cache::is_enabled()
+            && let Some(cache) = TaskCache::for_vault(path, &self.config)
+        {
+            let tasks = self.scan_tasks(path, Some(&cache), &warnings, observed)?;
+            if let Err(e) = cache.save() {
+                eprintln!("Warning: Could not write task cache: {}", e);
+            }
+            tasks
+        } else {
+            self.scan_tasks(path, None, &warnings, observed)?
+        };
+
+        if let Some(on_file) = on_file {
+            let mut dependent = std::mem::take(&mut *waiting.lock().unwrap());
+            if !dependent.is_empty() {
+                mark_blocked_by(&mut dependent, &open.lock().unwrap());
+                on_file(dependent);
+            }
+        }
+
+        Ok((tasks, warnings.into_vec()))
+    }
+
+    /// Extract tasks along with the files that were skipped, re-parsing
+    /// only the files that changed since they were cached in `cache`
+    #[cfg(feature = "fs")]
+    pub fn extract_tasks_with_cache(
+        &self,
+        path: &Path,
+        cache: &TaskCache,
+    ) -> Result<(Vec<Task>, Vec<ExtractionWarning>), Box<dyn std::error::Error>> {
+        let warnings = Warnings::default();
+        let tasks = self.scan_tasks(path, Some(cache), &warnings, None)?;
+        Ok((tasks, warnings.into_vec()))
+    }
+
+    #[cfg(feature = "fs")]
+    fn scan_tasks(
+        &self,
+        path: &Path,
+        cache: Option<&TaskCache>,
+        warnings: &Warnings,
+        on_file: Option<&OnFileTasks<'_>>,
+    ) -> Result<Vec<Task>, Box<dyn std::error::Error>> {
+        let mut tasks = self.extract_tasks_from_path(path, cache, warnings, on_file)?;
+        if self.config.git_dates {
+            crate::git_dates::fill_task_dates(path, &mut tasks);
+        }
+        mark_blocked(&mut tasks);
         Ok(tasks)
     }
 
-    pub fn extract_tasks(&self, path: &Path) -> Result<Vec<Task>, Box<dyn std::error::Error>> {
+    #[cfg(feature = "fs")]
+    fn extract_tasks_from_path(
+        &self,
+        path: &Path,
+        cache: Option<&TaskCache>,
+        warnings: &Warnings,
+        on_file: Option<&OnFileTasks<'_>>,
+    ) -> Result<Vec<Task>, Box<dyn std::error::Error>> {
         if path.is_file() {
             // Single file
-            if path.extension().and_then(|s| s.to_str()) == Some("md") {
-                self.extract_tasks_from_file(path)
+            if path.extension().and_then(|s| s.to_str()) == Some("md")
+                || self.config.is_code_file(path)
+            {
+                Ok(self.scan_file(path, cache, warnings, on_file))
             } else {
                 Ok(Vec::new())
             }
         } else if path.is_dir() {
             // Directory - recursively find all .md files in parallel
-            self.extract_tasks_from_dir(path)
+            let budget = ScanBudget::new(&self.config, path);
+            let tasks = self.extract_tasks_from_dir(path, 0, &budget, cache, warnings, on_file)?;
+            budget.finish(warnings)?;
+            Ok(tasks)
         } else {
             Err(format!("Path does not exist: {}", path.display()).into())
         }
     }
 
-    fn extract_tasks_from_dir(&self, dir: &Path) -> Result<Vec<Task>, Box<dyn std::error::Error>> {
+    #[cfg(feature = "fs")]
+    fn extract_tasks_from_dir(
+        &self,
+        dir: &Path,
+        depth: usize,
+        budget: &ScanBudget,
+        cache: Option<&TaskCache>,
+        warnings: &Warnings,
+        on_file: Option<&OnFileTasks<'_>>,
+    ) -> Result<Vec<Task>, Box<dyn std::error::Error>> {
         // Collect all directory entries
         let entries: Vec<_> = fs::read_dir(dir)?.collect::<Result<Vec<_>, _>>()?;
 
@@ -284,23 +1343,33 @@ impl TaskExtractor {
                 }
 
                 if path.is_file() {
-                    if path.extension().and_then(|s| s.to_str()) == Some("md") {
-                        match self.extract_tasks_from_file(&path) {
-                            Ok(file_tasks) => file_tasks,
-                            Err(e) => {
-                                eprintln!("Warning: Could not read {:?}: {}", path, e);
-                                Vec::new()
-                            }
-                        }
+                    if (self.config.is_scannable_markdown(&path) || self.config.is_code_file(&path))
+                        && budget.admit_file(&path)
+                    {
+                        self.scan_file(&path, cache, warnings, on_file)
                     } else {
                         Vec::new()
                     }
                 } else if path.is_dir() {
+                    if !budget.enter_dir(depth + 1) {
+                        return Vec::new();
+                    }
                     // Recursively process subdirectories
-                    match self.extract_tasks_from_dir(&path) {
+                    match self.extract_tasks_from_dir(
+                        &path,
+                        depth + 1,
+                        budget,
+                        cache,
+                        warnings,
+                        on_file,
+                    ) {
                         Ok(dir_tasks) => dir_tasks,
                         Err(e) => {
-                            eprintln!("Warning: Could not read directory {:?}: {}", path, e);
+                            warnings.push(ExtractionWarning::new(
+                                &path,
+                                WarningKind::Unreadable,
+                                e,
+                            ));
                             Vec::new()
                         }
                     }
@@ -313,6 +1382,42 @@ impl TaskExtractor {
         Ok(tasks)
     }
 
+    /// Tasks of one file, or none with a warning if it is oversized or
+    /// can't be read
+    #[cfg(feature = "fs")]
+    fn scan_file(
+        &self,
+        path: &Path,
+        cache: Option<&TaskCache>,
+        warnings: &Warnings,
+        on_file: Option<&OnFileTasks<'_>>,
+    ) -> Vec<Task> {
+        if let Some(max) = self.config.max_file_size
+            && let Ok(metadata) = fs::metadata(path)
+            && metadata.len() > max
+        {
+            warnings.push(ExtractionWarning::oversized(path, metadata.len(), max));
+            return Vec::new();
+        }
+
+        match self.extract_tasks_from_file(path, cache) {
+            // A scan with a callback hands the tasks over instead of keeping them
+            Ok(tasks) => match on_file {
+                Some(on_file) => {
+                    if !tasks.is_empty() {
+                        on_file(tasks);
+                    }
+                    Vec::new()
+                }
+                None => tasks,
+            },
+            Err(e) => {
+                warnings.push(ExtractionWarning::new(path, WarningKind::Unreadable, e));
+                Vec::new()
+            }
+        }
+    }
+
     fn parse_task_line(&self, line: &str, file_path: &Path, line_number: usize) -> Option<Task> {
         let line = line.trim_end_matches(&['\n', '\r'][..]);
 
@@ -321,7 +1426,7 @@ impl TaskExtractor {
             let content = caps.get(2).unwrap().as_str().to_string();
             return Some(self.create_task(
                 content,
-                "incomplete".to_string(),
+                TaskStatus::Incomplete,
                 line,
                 file_path,
                 line_number,
@@ -333,7 +1438,7 @@ impl TaskExtractor {
             let content = caps.get(2).unwrap().as_str().to_string();
             return Some(self.create_task(
                 content,
-                "completed".to_string(),
+                TaskStatus::Completed,
                 line,
                 file_path,
                 line_number,
@@ -345,7 +1450,7 @@ impl TaskExtractor {
             let content = caps.get(2).unwrap().as_str().to_string();
             return Some(self.create_task(
                 content,
-                "cancelled".to_string(),
+                TaskStatus::Cancelled,
                 line,
                 file_path,
                 line_number,
@@ -362,9 +1467,10 @@ impl TaskExtractor {
                 return None;
             }
 
+            let status_char = char.chars().next()?;
             return Some(self.create_task(
                 content,
-                format!("other_{}", char),
+                TaskStatus::Other(status_char),
                 line,
                 file_path,
                 line_number,
@@ -377,17 +1483,28 @@ impl TaskExtractor {
     fn create_task(
         &self,
         content: String,
-        status: String,
+        status: TaskStatus,
         raw_line: &str,
         file_path: &Path,
         line_number: usize,
     ) -> Task {
+        // The block ID comes off first so no other marker runs into it
+        let (content, block_id) = self.split_block_id(&content);
+
         // Extract metadata from content
         let tags = self.extract_tags(&content);
         let due_date = self.extract_due_date(&content);
+        let scheduled_date = self.extract_scheduled_date(&content);
+        let start_date = self.extract_start_date(&content);
         let priority = self.extract_priority(&content);
         let created_date = self.extract_created_date(&content);
         let completed_date = self.extract_completed_date(&content);
+        let fields = self.extract_inline_fields(&content);
+        let time_spent = self.extract_time_spent(&content);
+        let recurrence = self.extract_recurrence(&content);
+        let assignee = self.extract_assignee(&content, &fields);
+        let id = self.extract_id(&content);
+        let depends_on = self.extract_depends_on(&content);
 
         // Clean content by removing metadata
         let clean_content = self.clean_content(&content);
@@ -404,12 +1521,27 @@ impl TaskExtractor {
             line_number,
             raw_line: raw_line.to_string(),
             tags,
-            sub_items: Vec::new(),
+            sub_items: SubItems::default(),
+            heading: None,
+            heading_path: Vec::new(),
             summary: None,
             due_date,
+            scheduled_date,
+            start_date,
             priority,
             created_date,
             completed_date,
+            fields,
+            time_spent,
+            quoted: false,
+            recurrence,
+            occurrence: false,
+            assignee,
+            block_id,
+            id,
+            depends_on,
+            blocked: false,
+            diagnostics: None,
         }
     }
 }
@@ -440,7 +1572,7 @@ mod tests {
 
             assert!(task.is_some());
             let task = task.unwrap();
-            assert_eq!(task.status, "incomplete");
+            assert_eq!(task.status, TaskStatus::Incomplete);
             assert_eq!(task.content, "Test task");
             assert_eq!(task.line_number, 1);
         }
@@ -453,7 +1585,7 @@ mod tests {
 
             assert!(task.is_some());
             let task = task.unwrap();
-            assert_eq!(task.status, "completed");
+            assert_eq!(task.status, TaskStatus::Completed);
             assert_eq!(task.content, "Completed task");
         }
 
@@ -465,7 +1597,7 @@ mod tests {
 
             assert!(task.is_some());
             let task = task.unwrap();
-            assert_eq!(task.status, "completed");
+            assert_eq!(task.status, TaskStatus::Completed);
             assert_eq!(task.content, "Completed task");
         }
 
@@ -477,7 +1609,7 @@ mod tests {
 
             assert!(task.is_some());
             let task = task.unwrap();
-            assert_eq!(task.status, "cancelled");
+            assert_eq!(task.status, TaskStatus::Cancelled);
             assert_eq!(task.content, "Cancelled task");
         }
 
@@ -489,7 +1621,7 @@ mod tests {
 
             assert!(task.is_some());
             let task = task.unwrap();
-            assert_eq!(task.status, "other_?");
+            assert_eq!(task.status, TaskStatus::Other('?'));
             assert_eq!(task.content, "Unknown status");
         }
 
@@ -501,7 +1633,7 @@ mod tests {
 
             assert!(task.is_some());
             let task = task.unwrap();
-            assert_eq!(task.status, "incomplete");
+            assert_eq!(task.status, TaskStatus::Incomplete);
             assert_eq!(task.content, "Indented task");
         }
 
@@ -579,7 +1711,10 @@ mod tests {
             let date = extractor.extract_due_date("Task 📅 2025-12-10");
 
             assert!(date.is_some());
-            assert_eq!(date.unwrap(), "2025-12-10");
+            assert_eq!(
+                date.unwrap(),
+                NaiveDate::from_ymd_opt(2025, 12, 10).unwrap()
+            );
         }
 
         #[test]
@@ -588,7 +1723,10 @@ mod tests {
             let date = extractor.extract_due_date("Task due: 2025-12-10");
 
             assert!(date.is_some());
-            assert_eq!(date.unwrap(), "2025-12-10");
+            assert_eq!(
+                date.unwrap(),
+                NaiveDate::from_ymd_opt(2025, 12, 10).unwrap()
+            );
         }
 
         #[test]
@@ -597,7 +1735,10 @@ mod tests {
             let date = extractor.extract_due_date("Task @due(2025-12-10)");
 
             assert!(date.is_some());
-            assert_eq!(date.unwrap(), "2025-12-10");
+            assert_eq!(
+                date.unwrap(),
+                NaiveDate::from_ymd_opt(2025, 12, 10).unwrap()
+            );
         }
 
         #[test]
@@ -608,13 +1749,34 @@ mod tests {
             assert!(date.is_none());
         }
 
+        #[test]
+        fn test_invalid_due_date_is_ignored() {
+            let extractor = create_test_extractor();
+            let date = extractor.extract_due_date("Task 📅 2025-02-30");
+
+            assert!(date.is_none());
+        }
+
+        #[test]
+        fn test_invalid_due_date_falls_through_to_valid_one() {
+            let extractor = create_test_extractor();
+            let expected = NaiveDate::from_ymd_opt(2025, 3, 1);
+
+            let date = extractor.extract_due_date("Task 📅 2025-02-30 due: 2025-03-01");
+            assert_eq!(date, expected);
+            let date = extractor.extract_due_date("Task 📅 2025-02-30 📅 2025-03-01");
+            assert_eq!(date, expected);
+            let date = extractor.extract_scheduled_date("Task ⏳ 2025-13-01 scheduled: 2025-03-01");
+            assert_eq!(date, expected);
+        }
+
         #[test]
         fn test_extract_created_date_emoji() {
             let extractor = create_test_extractor();
             let date = extractor.extract_created_date("Task ➕ 2025-12-01");
 
             assert!(date.is_some());
-            assert_eq!(date.unwrap(), "2025-12-01");
+            assert_eq!(date.unwrap(), NaiveDate::from_ymd_opt(2025, 12, 1).unwrap());
         }
 
         #[test]
@@ -623,7 +1785,7 @@ mod tests {
             let date = extractor.extract_created_date("Task created: 2025-12-01");
 
             assert!(date.is_some());
-            assert_eq!(date.unwrap(), "2025-12-01");
+            assert_eq!(date.unwrap(), NaiveDate::from_ymd_opt(2025, 12, 1).unwrap());
         }
 
         #[test]
@@ -632,7 +1794,10 @@ mod tests {
             let date = extractor.extract_completed_date("Task ✅ 2025-12-15");
 
             assert!(date.is_some());
-            assert_eq!(date.unwrap(), "2025-12-15");
+            assert_eq!(
+                date.unwrap(),
+                NaiveDate::from_ymd_opt(2025, 12, 15).unwrap()
+            );
         }
 
         #[test]
@@ -641,7 +1806,34 @@ mod tests {
             let date = extractor.extract_completed_date("Task completed: 2025-12-15");
 
             assert!(date.is_some());
-            assert_eq!(date.unwrap(), "2025-12-15");
+            assert_eq!(
+                date.unwrap(),
+                NaiveDate::from_ymd_opt(2025, 12, 15).unwrap()
+            );
+        }
+
+        #[test]
+        fn test_extract_scheduled_and_start_dates() {
+            let extractor = create_test_extractor();
+
+            assert_eq!(
+                extractor.extract_scheduled_date("Task ⏳ 2025-12-08"),
+                NaiveDate::from_ymd_opt(2025, 12, 8)
+            );
+            assert_eq!(
+                extractor.extract_scheduled_date("Task scheduled: 2025-12-08"),
+                NaiveDate::from_ymd_opt(2025, 12, 8)
+            );
+            assert_eq!(
+                extractor.extract_start_date("Task 🛫 2025-12-01"),
+                NaiveDate::from_ymd_opt(2025, 12, 1)
+            );
+            assert_eq!(
+                extractor.extract_start_date("Task start: 2025-12-01"),
+                NaiveDate::from_ymd_opt(2025, 12, 1)
+            );
+            assert_eq!(extractor.extract_scheduled_date("Task 📅 2025-12-08"), None);
+            assert_eq!(extractor.extract_start_date("Task ⏳ 2025-12-08"), None);
         }
 
         #[test]
@@ -650,7 +1842,7 @@ mod tests {
             let priority = extractor.extract_priority("Task ⏫");
 
             assert!(priority.is_some());
-            assert_eq!(priority.unwrap(), "urgent");
+            assert_eq!(priority.unwrap(), Priority::Urgent);
         }
 
         #[test]
@@ -659,7 +1851,7 @@ mod tests {
             let priority = extractor.extract_priority("Task 🔼");
 
             assert!(priority.is_some());
-            assert_eq!(priority.unwrap(), "high");
+            assert_eq!(priority.unwrap(), Priority::High);
         }
 
         #[test]
@@ -668,7 +1860,7 @@ mod tests {
             let priority = extractor.extract_priority("Task 🔽");
 
             assert!(priority.is_some());
-            assert_eq!(priority.unwrap(), "low");
+            assert_eq!(priority.unwrap(), Priority::Low);
         }
 
         #[test]
@@ -677,7 +1869,7 @@ mod tests {
             let priority = extractor.extract_priority("Task ⏬");
 
             assert!(priority.is_some());
-            assert_eq!(priority.unwrap(), "lowest");
+            assert_eq!(priority.unwrap(), Priority::Lowest);
         }
 
         #[test]
@@ -686,7 +1878,7 @@ mod tests {
             let priority = extractor.extract_priority("Task priority: high");
 
             assert!(priority.is_some());
-            assert_eq!(priority.unwrap(), "high");
+            assert_eq!(priority.unwrap(), Priority::High);
         }
 
         #[test]
@@ -695,7 +1887,7 @@ mod tests {
             let priority = extractor.extract_priority("Task priority: medium");
 
             assert!(priority.is_some());
-            assert_eq!(priority.unwrap(), "medium");
+            assert_eq!(priority.unwrap(), Priority::Medium);
         }
 
         #[test]
@@ -704,7 +1896,7 @@ mod tests {
             let priority = extractor.extract_priority("Task priority: low");
 
             assert!(priority.is_some());
-            assert_eq!(priority.unwrap(), "low");
+            assert_eq!(priority.unwrap(), Priority::Low);
         }
 
         #[test]
@@ -714,6 +1906,140 @@ mod tests {
 
             assert!(priority.is_none());
         }
+
+        #[test]
+        fn test_extract_inline_fields() {
+            let extractor = create_test_extractor();
+            let fields =
+                extractor.extract_inline_fields("Task [todoist:: 12345] [due-time::  10:00 ]");
+
+            assert_eq!(fields.len(), 2);
+            assert_eq!(fields["todoist"], "12345");
+            assert_eq!(fields["due-time"], "10:00");
+        }
+
+        #[test]
+        fn test_no_inline_fields() {
+            let extractor = create_test_extractor();
+            let fields = extractor.extract_inline_fields("Task [[Wiki Link]] [x]");
+
+            assert!(fields.is_empty());
+        }
+
+        #[test]
+        fn test_extract_time_spent() {
+            let extractor = create_test_extractor();
+
+            assert_eq!(extractor.extract_time_spent("Task ⏱ 1h30m"), Some(90));
+            assert_eq!(extractor.extract_time_spent("Task ⏱️ 2h #work"), Some(120));
+            assert_eq!(extractor.extract_time_spent("Task [spent:: 45m]"), Some(45));
+            assert_eq!(
+                extractor.extract_time_spent("Task spent:: 1h 15m"),
+                Some(75)
+            );
+            assert_eq!(extractor.extract_time_spent("Task spent 45m"), None);
+        }
+
+        #[test]
+        fn test_extract_assignee() {
+            let extractor = create_test_extractor();
+            let path = PathBuf::from("test.md");
+            let assignee = |line: &str| extractor.parse_task_line(line, &path, 1).unwrap().assignee;
+
+            assert_eq!(
+                assignee("- [ ] Review PR @owner(alice)"),
+                Some("alice".to_string())
+            );
+            assert_eq!(
+                assignee("- [ ] Review PR @Assignee( @bob )"),
+                Some("bob".to_string())
+            );
+            assert_eq!(
+                assignee("- [ ] Review PR assignee:: carol #work"),
+                Some("carol".to_string())
+            );
+            assert_eq!(
+                assignee("- [ ] Review PR [owner:: Dana Scully]"),
+                Some("Dana Scully".to_string())
+            );
+            assert_eq!(assignee("- [ ] Email alice@example.com"), None);
+
+            let task = extractor
+                .parse_task_line("- [ ] Review PR @owner(alice) #work", &path, 1)
+                .unwrap();
+            assert_eq!(task.content, "Review PR #work");
+        }
+
+        #[test]
+        fn test_extract_block_id() {
+            let extractor = create_test_extractor();
+            let path = PathBuf::from("test.md");
+            let parse = |line: &str| extractor.parse_task_line(line, &path, 1).unwrap();
+
+            let task = parse("- [ ] Water plants 🔁 every week #home ^task-abc");
+            assert_eq!(task.block_id.as_deref(), Some("task-abc"));
+            assert_eq!(task.recurrence.as_deref(), Some("every week"));
+            assert_eq!(task.content, "Water plants #home");
+
+            let task = parse("- [x] Done ✅ 2025-01-20 ^Done1  ");
+            assert_eq!(task.block_id.as_deref(), Some("Done1"));
+            assert_eq!(task.content, "Done");
+
+            assert_eq!(parse("- [ ] Raise 2^10 to a power").block_id, None);
+            assert_eq!(parse("- [ ] Note ^not_an_id").block_id, None);
+
+            let diagnostics = extractor.diagnose_task(&parse("- [ ] Call ^x1"));
+            assert_eq!(diagnostics.stripped[0].pattern, "block_id");
+            assert_eq!(diagnostics.stripped[0].text, "^x1");
+        }
+
+        #[test]
+        fn test_extract_dependencies() {
+            let extractor = create_test_extractor();
+            let path = PathBuf::from("test.md");
+            let parse = |line: &str| extractor.parse_task_line(line, &path, 1).unwrap();
+
+            let task = parse("- [ ] Write draft 🆔 draft1 📅 2025-02-01");
+            assert_eq!(task.id.as_deref(), Some("draft1"));
+            assert!(task.depends_on.is_empty());
+            assert_eq!(task.content, "Write draft");
+
+            let task = parse("- [ ] Publish 🔁 every week ⛔ draft1, review-2 #blog");
+            assert_eq!(task.depends_on, vec!["draft1", "review-2"]);
+            assert_eq!(task.recurrence.as_deref(), Some("every week"));
+            assert_eq!(task.content, "Publish #blog");
+        }
+
+        #[test]
+        fn test_custom_assignee_keys() {
+            let extractor = TaskExtractor::new(Arc::new(Config {
+                assignee_keys: vec!["who".to_string()],
+                ..Default::default()
+            }));
+            let path = PathBuf::from("test.md");
+
+            let task = extractor
+                .parse_task_line("- [ ] Ship it who:: erin", &path, 1)
+                .unwrap();
+            assert_eq!(task.assignee.as_deref(), Some("erin"));
+            assert_eq!(task.content, "Ship it");
+
+            let task = extractor
+                .parse_task_line("- [ ] Ship it @owner(alice)", &path, 1)
+                .unwrap();
+            assert_eq!(task.assignee, None);
+        }
+
+        #[test]
+        fn test_parse_duration() {
+            assert_eq!(parse_duration("1h30m"), Some(90));
+            assert_eq!(parse_duration("1h 30m"), Some(90));
+            assert_eq!(parse_duration("45m"), Some(45));
+            assert_eq!(parse_duration("3h"), Some(180));
+            assert_eq!(parse_duration(""), None);
+            assert_eq!(parse_duration("90"), None);
+            assert_eq!(parse_duration("1x"), None);
+        }
     }
 
     mod clean_content {
@@ -776,6 +2102,14 @@ mod tests {
             assert_eq!(cleaned, "Task");
         }
 
+        #[test]
+        fn test_removes_scheduled_and_start_dates() {
+            let extractor = create_test_extractor();
+            let cleaned = extractor.clean_content("Task 🛫 2025-12-01 ⏳ 2025-12-08");
+
+            assert_eq!(cleaned, "Task");
+        }
+
         #[test]
         fn test_removes_timestamp() {
             let extractor = create_test_extractor();
@@ -792,6 +2126,26 @@ mod tests {
             assert_eq!(cleaned, "Task #work");
         }
 
+        #[test]
+        fn test_removes_time_spent() {
+            let extractor = create_test_extractor();
+
+            assert_eq!(extractor.clean_content("Task ⏱ 1h30m #work"), "Task #work");
+            assert_eq!(
+                extractor.clean_content("Task [spent:: 45m] #work"),
+                "Task #work"
+            );
+            assert_eq!(extractor.clean_content("Task spent:: 45m"), "Task");
+        }
+
+        #[test]
+        fn test_removes_inline_fields() {
+            let extractor = create_test_extractor();
+            let cleaned = extractor.clean_content("Task [todoist:: 12345] #work");
+
+            assert_eq!(cleaned, "Task #work");
+        }
+
         #[test]
         fn test_cleans_extra_whitespace() {
             let extractor = create_test_extractor();
@@ -864,7 +2218,7 @@ mod tests {
 
             let parsed = extractor.parse_sub_item(sub);
             assert!(parsed.is_some());
-            assert_eq!(parsed.unwrap(), "Sub item text");
+            assert_eq!(parsed.unwrap().content, "Sub item text");
         }
 
         #[test]
@@ -874,7 +2228,7 @@ mod tests {
 
             let parsed = extractor.parse_sub_item(sub);
             assert!(parsed.is_some());
-            assert_eq!(parsed.unwrap(), "Sub task text");
+            assert_eq!(parsed.unwrap().content, "Sub task text");
         }
 
         #[test]
@@ -884,7 +2238,7 @@ mod tests {
 
             let parsed = extractor.parse_sub_item(sub);
             assert!(parsed.is_some());
-            assert_eq!(parsed.unwrap(), "Sub item with asterisk");
+            assert_eq!(parsed.unwrap().content, "Sub item with asterisk");
         }
 
         #[test]
@@ -894,7 +2248,10 @@ mod tests {
 
             let parsed = extractor.parse_sub_item(sub);
             assert!(parsed.is_some());
-            assert_eq!(parsed.unwrap(), "Completed sub task");
+            let parsed = parsed.unwrap();
+            assert_eq!(parsed.content, "Completed sub task");
+            assert_eq!(parsed.status, Some(TaskStatus::Completed));
+            assert_eq!(parsed.indent, 2);
         }
 
         #[test]
@@ -905,11 +2262,85 @@ mod tests {
             let parsed = extractor.parse_sub_item(sub);
             assert!(parsed.is_none());
         }
+
+        #[test]
+        fn test_sub_item_tree() {
+            let content = "- [ ] Plan trip\n  - [x] Book flights\n    - Compare prices\n  - [ ] Hotel\n- [ ] Next";
+            let path = PathBuf::from("test.md");
+
+            let tasks = create_test_extractor().extract_tasks_from_content(content, &path);
+            let SubItems::Tree(items) = &tasks[0].sub_items else {
+                panic!("expected a tree");
+            };
+            assert_eq!(items.len(), 2);
+            assert_eq!(items[0].status, Some(TaskStatus::Completed));
+            assert_eq!(items[0].children[0].content, "Compare prices");
+            assert_eq!(items[0].children[0].status, None);
+            assert_eq!(items[0].children[0].indent, 4);
+            assert_eq!(items[1].status, Some(TaskStatus::Incomplete));
+            assert!(tasks[1].sub_items.is_empty());
+
+            let json = serde_json::to_value(&tasks[0].sub_items).unwrap();
+            assert_eq!(json[0]["children"][0]["content"], "Compare prices");
+            let round_trip: SubItems = serde_json::from_value(json).unwrap();
+            assert_eq!(round_trip, tasks[0].sub_items);
+
+            let config = Config {
+                flat_sub_items: true,
+                ..Config::default()
+            };
+            let tasks =
+                TaskExtractor::new(Arc::new(config)).extract_tasks_from_content(content, &path);
+            assert_eq!(
+                serde_json::to_value(&tasks[0].sub_items).unwrap(),
+                serde_json::json!(["Book flights", "Compare prices", "Hotel"])
+            );
+        }
     }
 
     mod integration {
         use super::*;
 
+        #[test]
+        fn test_empty_and_frontmatter_only_notes() {
+            let extractor = create_test_extractor();
+            let path = PathBuf::from("test.md");
+
+            assert!(extractor.extract_tasks_from_content("", &path).is_empty());
+            assert!(
+                extractor
+                    .extract_tasks_from_content("---\ntags: [a]\n---", &path)
+                    .is_empty()
+            );
+            assert!(
+                extractor
+                    .extract_tasks_from_content("---\ntags: [a]\n---\n", &path)
+                    .is_empty()
+            );
+        }
+
+        #[test]
+        fn test_checkboxes_in_frontmatter_ignored() {
+            let extractor = create_test_extractor();
+            let path = PathBuf::from("test.md");
+            let content = "---\nchecklist:\n  - [ ] not a task\n---\n- [ ] Real task";
+
+            let tasks = extractor.extract_tasks_from_content(content, &path);
+            assert_eq!(tasks.len(), 1);
+            assert_eq!(tasks[0].content, "Real task");
+            assert_eq!(tasks[0].line_number, 5);
+        }
+
+        #[test]
+        fn test_sub_item_on_last_line_without_newline() {
+            let extractor = create_test_extractor();
+            let path = PathBuf::from("test.md");
+
+            let tasks = extractor.extract_tasks_from_content("- [ ] Task\n  - Last item", &path);
+            assert_eq!(tasks.len(), 1);
+            assert_eq!(tasks[0].sub_items.texts(), vec!["Last item"]);
+        }
+
         #[test]
         fn test_full_task_with_all_metadata() {
             let extractor = create_test_extractor();
@@ -920,13 +2351,26 @@ mod tests {
             assert!(task.is_some());
 
             let task = task.unwrap();
-            assert_eq!(task.status, "incomplete");
+            assert_eq!(task.status, TaskStatus::Incomplete);
             assert_eq!(task.content, "Write tests #testing");
             assert_eq!(task.line_number, 5);
             assert_eq!(task.tags, vec!["testing"]);
-            assert_eq!(task.priority, Some("urgent".to_string()));
-            assert_eq!(task.due_date, Some("2025-12-10".to_string()));
-            assert_eq!(task.created_date, Some("2025-12-01".to_string()));
+            assert_eq!(task.priority, Some(Priority::Urgent));
+            assert_eq!(task.due_date, NaiveDate::from_ymd_opt(2025, 12, 10));
+            assert_eq!(task.created_date, NaiveDate::from_ymd_opt(2025, 12, 1));
+        }
+
+        #[test]
+        fn test_task_with_tasks_plugin_dates() {
+            let extractor = create_test_extractor();
+            let path = PathBuf::from("test.md");
+            let line = "- [ ] Plan sprint 🛫 2025-12-01 ⏳ 2025-12-08 📅 2025-12-10";
+
+            let task = extractor.parse_task_line(line, &path, 1).unwrap();
+            assert_eq!(task.content, "Plan sprint");
+            assert_eq!(task.start_date, NaiveDate::from_ymd_opt(2025, 12, 1));
+            assert_eq!(task.scheduled_date, NaiveDate::from_ymd_opt(2025, 12, 8));
+            assert_eq!(task.due_date, NaiveDate::from_ymd_opt(2025, 12, 10));
         }
 
         #[test]
@@ -939,9 +2383,9 @@ mod tests {
             assert!(task.is_some());
 
             let task = task.unwrap();
-            assert_eq!(task.status, "completed");
+            assert_eq!(task.status, TaskStatus::Completed);
             assert_eq!(task.content, "Finished task #done");
-            assert_eq!(task.completed_date, Some("2025-12-15".to_string()));
+            assert_eq!(task.completed_date, NaiveDate::from_ymd_opt(2025, 12, 15));
             assert_eq!(task.tags, vec!["done"]);
         }
 
@@ -960,6 +2404,105 @@ mod tests {
             assert_eq!(task.content, "Task with metadata #work");
         }
 
+        #[test]
+        fn test_quoted_tasks() {
+            let extractor = create_test_extractor();
+            let content = "> [!todo] Callout\n\
+                           > - [ ] Quoted task #work\n\
+                           >   - Quoted sub-item\n\
+                           >> - [x] Nested quote\n\
+                           - [ ] Plain task\n\
+                           \x20 - Plain sub-item\n";
+
+            let tasks = extractor.extract_tasks_from_content(content, Path::new("test.md"));
+            assert_eq!(tasks.len(), 3);
+
+            assert!(tasks[0].quoted);
+            assert_eq!(tasks[0].content, "Quoted task #work");
+            assert_eq!(tasks[0].raw_line, "> - [ ] Quoted task #work");
+            assert_eq!(tasks[0].line_number, 2);
+            assert_eq!(tasks[0].sub_items.texts(), vec!["Quoted sub-item"]);
+
+            assert!(tasks[1].quoted);
+            assert_eq!(tasks[1].status, TaskStatus::Completed);
+            assert!(tasks[1].sub_items.is_empty());
+
+            assert!(!tasks[2].quoted);
+            assert_eq!(tasks[2].sub_items.texts(), vec!["Plain sub-item"]);
+        }
+
+        #[test]
+        fn test_summarize_tasks_from_content() {
+            let extractor = create_test_extractor();
+            let content = "---\ntitle: Plan\n---\n\
+                           Launch plan for Q3. Owned by ops.\n\
+                           - [ ] Intro task\n\
+                           ## Backend\n\
+                           ```\n# not a heading\n```\n\
+                           - [ ] Backend task\n";
+
+            let mut tasks = extractor.extract_tasks_from_content(content, Path::new("plan.md"));
+            assert!(tasks.iter().all(|t| t.summary.is_none()));
+
+            extractor.summarize_tasks_from_content(content, &mut tasks);
+            assert_eq!(tasks[0].summary.as_deref(), Some("Launch plan for Q3."));
+            assert_eq!(tasks[1].summary.as_deref(), Some("Backend"));
+        }
+
+        #[test]
+        fn test_heading_paths() {
+            let extractor = create_test_extractor();
+            let content = "- [ ] Loose task\n\
+                           # Project X\n\
+                           ## Backlog\n\
+                           ### Ideas\n\
+                           ```\n# not a heading\n```\n\
+                           - [ ] Idea\n\
+                           ## Doing\n\
+                           - [ ] Current\n\
+                           # Project Y\n\
+                           - [ ] Other\n";
+
+            let tasks = extractor.extract_tasks_from_content(content, Path::new("plan.md"));
+            let paths: Vec<Vec<&str>> = tasks
+                .iter()
+                .map(|t| t.heading_path.iter().map(String::as_str).collect())
+                .collect();
+            assert_eq!(
+                paths,
+                vec![
+                    vec![],
+                    vec!["Project X", "Backlog", "Ideas"],
+                    vec!["Project X", "Doing"],
+                    vec!["Project Y"],
+                ]
+            );
+            assert_eq!(tasks[0].heading, None);
+            assert_eq!(tasks[2].heading.as_deref(), Some("Doing"));
+        }
+
+        #[test]
+        fn test_intro_sentence() {
+            assert_eq!(
+                intro_sentence("- [ ] task\nv1.2 ships soon! Then more.\n"),
+                Some("v1.2 ships soon!".to_string())
+            );
+            assert_eq!(
+                intro_sentence("1. First\nNo terminator"),
+                Some("No terminator".to_string())
+            );
+            assert_eq!(intro_sentence("# Title\nBody."), None);
+            assert_eq!(intro_sentence(""), None);
+        }
+
+        #[test]
+        fn test_strip_blockquote() {
+            assert_eq!(strip_blockquote("> - [ ] a"), (true, "- [ ] a"));
+            assert_eq!(strip_blockquote("> >   - b"), (true, "  - b"));
+            assert_eq!(strip_blockquote(">- [ ] c"), (true, "- [ ] c"));
+            assert_eq!(strip_blockquote("  - [ ] d"), (false, "  - [ ] d"));
+        }
+
         #[test]
         fn test_file_path_and_name() {
             let extractor = create_test_extractor();
@@ -974,4 +2517,319 @@ mod tests {
             assert!(task.file_path.contains("tasks.md"));
         }
     }
+
+    mod code_todos {
+        use super::*;
+
+        #[test]
+        fn test_extract_code_todos_from_content() {
+            let extractor = create_test_extractor();
+            let content = "// TODO: Handle errors 📅 2025-03-01\n\
+                           fn main() {} // FIXME(alice): leaks memory #perf\n\
+                           # TODO refactor config loading\n\
+                           /* TODO: free the buffer */\n\
+                            * FIXME: doc comment task\n\
+                           #[derive(Debug)]\n\
+                           let todo = \"TODO: not a comment\";\n\
+                           // TODO:\n\
+                           // Regular comment mentioning TODOs\n";
+
+            let tasks =
+                extractor.extract_code_todos_from_content(content, Path::new("src/main.rs"));
+            let contents: Vec<&str> = tasks.iter().map(|t| t.content.as_str()).collect();
+            assert_eq!(
+                contents,
+                vec![
+                    "Handle errors",
+                    "leaks memory #perf",
+                    "refactor config loading",
+                    "free the buffer",
+                    "doc comment task",
+                ]
+            );
+            assert!(tasks.iter().all(|t| t.status == TaskStatus::CodeTodo));
+            assert_eq!(tasks[0].tags, vec!["todo"]);
+            assert_eq!(tasks[0].due_date, NaiveDate::from_ymd_opt(2025, 3, 1));
+            assert_eq!(tasks[1].tags, vec!["fixme", "perf"]);
+            assert_eq!(tasks[1].line_number, 2);
+            assert_eq!(
+                tasks[1].raw_line,
+                "fn main() {} // FIXME(alice): leaks memory #perf"
+            );
+            assert_eq!(tasks[1].file_name, "main.rs");
+        }
+
+        #[test]
+        fn test_extract_tasks_scans_configured_extensions() {
+            let temp_dir = tempfile::TempDir::new().unwrap();
+            std::fs::create_dir(temp_dir.path().join("src")).unwrap();
+            std::fs::write(temp_dir.path().join("notes.md"), "- [ ] Note task\n").unwrap();
+            std::fs::write(temp_dir.path().join("src/lib.rs"), "// TODO: Code task\n").unwrap();
+            std::fs::write(temp_dir.path().join("build.sh"), "# FIXME: Script task\n").unwrap();
+
+            let extract = |config: Config| {
+                let mut tasks: Vec<String> = TaskExtractor::new(Arc::new(config))
+                    .extract_tasks_with_warnings(temp_dir.path())
+                    .unwrap()
+                    .0
+                    .into_iter()
+                    .map(|t| format!("{} ({})", t.content, t.status))
+                    .collect();
+                tasks.sort();
+                tasks
+            };
+
+            assert_eq!(extract(Config::default()), vec!["Note task (incomplete)"]);
+            assert_eq!(
+                extract(Config {
+                    code_todo_extensions: vec!["rs".to_string()],
+                    ..Default::default()
+                }),
+                vec!["Code task (code_todo)", "Note task (incomplete)"]
+            );
+        }
+
+        #[test]
+        fn test_extract_tasks_marks_blocked() {
+            let temp_dir = tempfile::TempDir::new().unwrap();
+            std::fs::write(
+                temp_dir.path().join("a.md"),
+                "- [ ] Draft 🆔 draft\n- [x] Outline 🆔 outline\n",
+            )
+            .unwrap();
+            std::fs::write(
+                temp_dir.path().join("b.md"),
+                "- [ ] Publish ⛔ draft\n- [ ] Expand ⛔ outline\n- [ ] Ship ⛔ missing\n",
+            )
+            .unwrap();
+
+            let mut blocked: Vec<(String, bool)> = create_test_extractor()
+                .extract_tasks_with_warnings(temp_dir.path())
+                .unwrap()
+                .0
+                .into_iter()
+                .map(|t| (t.content, t.blocked))
+                .collect();
+            blocked.sort();
+            assert_eq!(
+                blocked,
+                vec![
+                    ("Draft".to_string(), false),
+                    ("Expand".to_string(), false),
+                    ("Outline".to_string(), false),
+                    ("Publish".to_string(), true),
+                    ("Ship".to_string(), false),
+                ]
+            );
+        }
+    }
+
+    mod warnings {
+        use super::*;
+        use crate::warnings::WarningKind;
+
+        #[test]
+        fn test_extract_tasks_with_warnings() {
+            let temp_dir = tempfile::TempDir::new().unwrap();
+            std::fs::write(temp_dir.path().join("good.md"), "- [ ] Good task\n").unwrap();
+            std::fs::write(temp_dir.path().join("binary.md"), b"- [ ] \xff\xfe\n").unwrap();
+            std::fs::write(
+                temp_dir.path().join("big.md"),
+                format!("- [ ] Big task\n{}", "x".repeat(200)),
+            )
+            .unwrap();
+
+            let extractor = TaskExtractor::new(Arc::new(Config {
+                max_file_size: Some(100),
+                ..Default::default()
+            }));
+            let (tasks, warnings) = extractor
+                .extract_tasks_with_warnings(temp_dir.path())
+                .unwrap();
+
+            assert_eq!(tasks.len(), 1);
+            assert_eq!(tasks[0].content, "Good task");
+            let kinds: Vec<(&str, WarningKind)> = warnings
+                .iter()
+                .map(|w| (w.file_path.rsplit('/').next().unwrap(), w.kind))
+                .collect();
+            assert_eq!(
+                kinds,
+                vec![
+                    ("big.md", WarningKind::Oversized),
+                    ("binary.md", WarningKind::Unreadable)
+                ]
+            );
+            assert!(
+                warnings[0]
+                    .message
+                    .contains("exceeds max_file_size of 100 bytes")
+            );
+        }
+
+        #[test]
+        fn test_scan_limits() {
+            use crate::config::ScanLimitAction;
+
+            let temp_dir = tempfile::TempDir::new().unwrap();
+            std::fs::write(
+                temp_dir.path().join("top.md"),
+                "- [ ] Top
+",
+            )
+            .unwrap();
+            std::fs::create_dir_all(temp_dir.path().join("a/b")).unwrap();
+            std::fs::write(
+                temp_dir.path().join("a/mid.md"),
+                "- [ ] Mid
+",
+            )
+            .unwrap();
+            std::fs::write(
+                temp_dir.path().join("a/b/deep.md"),
+                "- [ ] Deep
+",
+            )
+            .unwrap();
+
+            let extractor = TaskExtractor::new(Arc::new(Config {
+                max_depth: Some(1),
+                scan_limit_action: ScanLimitAction::Truncate,
+                ..Default::default()
+            }));
+            let (tasks, warnings) = extractor
+                .extract_tasks_with_warnings(temp_dir.path())
+                .unwrap();
+            let mut contents: Vec<&str> = tasks.iter().map(|t| t.content.as_str()).collect();
+            contents.sort();
+            assert_eq!(contents, vec!["Mid", "Top"]);
+            assert_eq!(warnings.len(), 1);
+            assert_eq!(warnings[0].kind, WarningKind::ScanLimit);
+
+            let extractor = TaskExtractor::new(Arc::new(Config {
+                max_files: Some(2),
+                ..Default::default()
+            }));
+            let error = extractor
+                .extract_tasks_with_warnings(temp_dir.path())
+                .unwrap_err();
+            assert!(error.to_string().contains("more than 2 files (max_files)"));
+        }
+    }
+
+    mod serialization {
+        use super::*;
+
+        #[test]
+        fn test_status_round_trips_as_string() {
+            for status in [
+                TaskStatus::Incomplete,
+                TaskStatus::Completed,
+                TaskStatus::Cancelled,
+                TaskStatus::Other('>'),
+                TaskStatus::CodeTodo,
+            ] {
+                let json = serde_json::to_string(&status).unwrap();
+                let parsed: TaskStatus = serde_json::from_str(&json).unwrap();
+                assert_eq!(parsed, status);
+            }
+            assert_eq!(
+                serde_json::to_string(&TaskStatus::Other('?')).unwrap(),
+                "\"other_?\""
+            );
+        }
+
+        #[test]
+        fn test_invalid_status_rejected() {
+            assert!("done".parse::<TaskStatus>().is_err());
+            assert!("other_".parse::<TaskStatus>().is_err());
+            assert!("other_ab".parse::<TaskStatus>().is_err());
+        }
+
+        #[test]
+        fn test_priority_ordering() {
+            assert!(Priority::Urgent > Priority::High);
+            assert!(Priority::Medium > Priority::Low);
+            assert!(Priority::Low > Priority::Lowest);
+        }
+
+        #[test]
+        fn test_task_dates_serialize_as_iso_strings() {
+            let extractor = create_test_extractor();
+            let path = PathBuf::from("test.md");
+            let task = extractor
+                .parse_task_line("- [ ] Task 📅 2025-12-10 🔼", &path, 1)
+                .unwrap();
+
+            let json = serde_json::to_value(&task).unwrap();
+            assert_eq!(json["due_date"], "2025-12-10");
+            assert_eq!(json["priority"], "high");
+            assert_eq!(json["status"], "incomplete");
+        }
+    }
+
+    mod diagnostics {
+        use super::*;
+
+        fn diagnose(content: &str) -> ParseDiagnostics {
+            let extractor = create_test_extractor();
+            let task = extractor
+                .extract_tasks_from_content(content, Path::new("test.md"))
+                .pop()
+                .unwrap();
+            extractor.diagnose_task(&task)
+        }
+
+        #[test]
+        fn test_clean_task() {
+            let diagnostics = diagnose("> - [x] Ship it #work 📅 2025-01-10 ⏫ ✅ 2025-01-09\n");
+            assert_eq!(diagnostics.line_pattern, "task_completed");
+            assert_eq!(diagnostics.confidence, ParseConfidence::High);
+            let stripped: Vec<(&str, &str)> = diagnostics
+                .stripped
+                .iter()
+                .map(|s| (s.pattern.as_str(), s.text.as_str()))
+                .collect();
+            assert_eq!(
+                stripped,
+                [
+                    ("due_date", "📅 2025-01-10"),
+                    ("priority", "⏫"),
+                    ("completed_date", "✅ 2025-01-09")
+                ]
+            );
+            assert!(diagnostics.ambiguities.is_empty());
+        }
+
+        #[test]
+        fn test_ambiguous_task() {
+            let diagnostics =
+                diagnose("- [ ] See site/#anchor #project/sub 📅 2025-01-10 due: 2025-01-11\n");
+            assert_eq!(diagnostics.line_pattern, "task_incomplete");
+            assert_eq!(diagnostics.confidence, ParseConfidence::Medium);
+            assert_eq!(
+                diagnostics.ambiguities,
+                [
+                    "2 due_date markers; only one is used",
+                    "'#anchor' is read as a tag although it follows other text",
+                    "'#project/sub' is read as the tag 'project'; only letters, digits, and _ are part of a tag",
+                ]
+            );
+        }
+
+        #[test]
+        fn test_ignored_metadata() {
+            let diagnostics = diagnose("- [>] Later 📅 2025-02-30 ⏳ tomorrow Priority: High\n");
+            assert_eq!(diagnostics.line_pattern, "task_other");
+            assert_eq!(diagnostics.confidence, ParseConfidence::Low);
+            assert_eq!(
+                diagnostics.ambiguities,
+                [
+                    "'📅 2025-02-30' is not a valid date and was ignored",
+                    "'Priority: High' was removed from the text but not read as a priority",
+                    "'⏳' was left in the text: it isn't followed by metadata that can be read",
+                ]
+            );
+        }
+    }
 }
@@ -0,0 +1,418 @@
+//! Atom feeds of vault activity
+//!
+//! - `/feed/notes.xml`: recently modified notes
+//! - `/feed/completed.xml`: recently completed tasks
+//!
+//! Both accept an optional `?limit=N` query parameter (default 50). Entries
+//! link to the note via an `obsidian://open` URI.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use axum::{
+    Router,
+    extract::{Query, State},
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+    routing::get,
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Deserialize;
+
+use crate::config::Config;
+use crate::extractor::{TaskExtractor, TaskStatus};
+use crate::frontmatter::body_after_frontmatter;
+use crate::http_router::xml_escape;
+use crate::ics::stable_hash;
+use crate::path_resolver::to_slash;
+use crate::tag_extractor::{TagExtractor, collect_markdown_files, note_excerpt, note_title};
+use crate::warnings::Warnings;
+
+/// Path of the recently modified notes feed
+pub const NOTES_FEED_PATH: &str = "/feed/notes.xml";
+
+/// Path of the recently completed tasks feed
+pub const COMPLETED_FEED_PATH: &str = "/feed/completed.xml";
+
+const DEFAULT_LIMIT: usize = 50;
+
+#[derive(Clone)]
+struct FeedState {
+    base_path: PathBuf,
+    config: Arc<Config>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FeedQuery {
+    limit: Option<usize>,
+}
+
+/// A single Atom entry
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeedEntry {
+    pub id: String,
+    pub title: String,
+    pub updated: DateTime<Utc>,
+    pub link: String,
+    pub summary: Option<String>,
+    pub categories: Vec<String>,
+}
+
+/// Build the feed routes for a vault
+///
+/// Like the CalDAV router, this carries its own state so it can be merged into
+/// the HTTP server's router.
+pub fn router<S>(base_path: PathBuf, config: Arc<Config>) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    Router::new()
+        .route(NOTES_FEED_PATH, get(notes_feed_handler))
+        .route(COMPLETED_FEED_PATH, get(completed_feed_handler))
+        .with_state(FeedState { base_path, config })
+}
+
+/// Percent-encode a URI query component
+fn encode_uri_component(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-_.~".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            let _ = write!(encoded, "%{:02X}", byte);
+        }
+    }
+    encoded
+}
+
+/// Build an `obsidian://open` URI for a note in the vault
+fn obsidian_uri(base_path: &Path, file_path: &Path) -> String {
+    let vault = base_path
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string();
+    let relative = to_slash(file_path.strip_prefix(base_path).unwrap_or(file_path));
+
+    format!(
+        "obsidian://open?vault={}&file={}",
+        encode_uri_component(&vault),
+        encode_uri_component(&relative)
+    )
+}
+
+/// Entries for the most recently modified notes, newest first
+pub fn recent_notes(
+    base_path: &Path,
+    config: Arc<Config>,
+    limit: usize,
+) -> Result<Vec<FeedEntry>, Box<dyn std::error::Error>> {
+    // Feeds have nowhere to report skipped files, so they are just left out
+    let mut files: Vec<(PathBuf, DateTime<Utc>)> =
+        collect_markdown_files(base_path, &config, &Warnings::default())?
+            .into_iter()
+            .filter_map(|path| {
+                let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+                Some((path, DateTime::<Utc>::from(modified)))
+            })
+            .collect();
+    files.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    files.truncate(limit);
+
+    let tag_extractor = TagExtractor::new(config);
+    let entries = files
+        .into_iter()
+        .filter_map(|(path, modified)| {
+            let content = fs::read_to_string(&path).ok()?;
+            let frontmatter = tag_extractor.extract_frontmatter(&content).ok().flatten();
+            let body = body_after_frontmatter(&content);
+            let relative = path.strip_prefix(base_path).unwrap_or(&path);
+
+            Some(FeedEntry {
+                id: format!(
+                    "urn:markdown-todo-extractor:note:{:016x}",
+                    stable_hash(&to_slash(relative))
+                ),
+                title: note_title(frontmatter.as_deref(), body, &path),
+                updated: modified,
+                link: obsidian_uri(base_path, &path),
+                summary: note_excerpt(body),
+                categories: tag_extractor
+                    .extract_tags_from_content(&content, false)
+                    .unwrap_or_default(),
+            })
+        })
+        .collect();
+
+    Ok(entries)
+}
+
+/// Entries for the most recently completed tasks, newest first
+///
+/// Only tasks with a completion date (`✅ YYYY-MM-DD` or `completed:`) are
+/// included, since the date is what orders the feed.
+pub fn recently_completed(
+    base_path: &Path,
+    config: Arc<Config>,
+    limit: usize,
+) -> Result<Vec<FeedEntry>, Box<dyn std::error::Error>> {
+    let (tasks, _warnings) = TaskExtractor::new(config).extract_tasks_with_warnings(base_path)?;
+    let mut tasks: Vec<_> = tasks
+        .into_iter()
+        .filter(|task| task.status == TaskStatus::Completed && !task.quoted)
+        .filter_map(|task| Some((task.completed_date?, task)))
+        .collect();
+    tasks.sort_by(|a, b| {
+        b.0.cmp(&a.0)
+            .then_with(|| a.1.file_path.cmp(&b.1.file_path))
+            .then_with(|| a.1.line_number.cmp(&b.1.line_number))
+    });
+    tasks.truncate(limit);
+
+    let entries = tasks
+        .into_iter()
+        .map(|(completed, task)| {
+            let path = Path::new(&task.file_path);
+            let relative = path.strip_prefix(base_path).unwrap_or(path);
+
+            FeedEntry {
+                id: format!(
+                    "urn:markdown-todo-extractor:task:{:016x}",
+                    stable_hash(&format!("{}\n{}", to_slash(relative), task.raw_line.trim()))
+                ),
+                title: task.content.clone(),
+                updated: date_to_utc(completed),
+                link: obsidian_uri(base_path, path),
+                summary: Some(format!("Completed in {}", task.file_name)),
+                categories: task.tags.clone(),
+            }
+        })
+        .collect();
+
+    Ok(entries)
+}
+
+fn date_to_utc(date: NaiveDate) -> DateTime<Utc> {
+    date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc()
+}
+
+/// Render entries as an Atom 1.0 document
+pub fn render_atom(title: &str, id: &str, self_path: &str, entries: &[FeedEntry]) -> String {
+    let updated = entries
+        .iter()
+        .map(|e| e.updated)
+        .max()
+        .unwrap_or(DateTime::<Utc>::UNIX_EPOCH);
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    xml.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    let _ = writeln!(xml, "  <title>{}</title>", xml_escape(title));
+    let _ = writeln!(xml, "  <id>{}</id>", xml_escape(id));
+    let _ = writeln!(xml, "  <updated>{}</updated>", updated.to_rfc3339());
+    let _ = writeln!(
+        xml,
+        "  <link rel=\"self\" href=\"{}\"/>",
+        xml_escape(self_path)
+    );
+    let _ = writeln!(xml, "  <generator>{}</generator>", env!("CARGO_PKG_NAME"));
+
+    for entry in entries {
+        xml.push_str("  <entry>\n");
+        let _ = writeln!(xml, "    <id>{}</id>", xml_escape(&entry.id));
+        let _ = writeln!(xml, "    <title>{}</title>", xml_escape(&entry.title));
+        let _ = writeln!(xml, "    <updated>{}</updated>", entry.updated.to_rfc3339());
+        let _ = writeln!(xml, "    <link href=\"{}\"/>", xml_escape(&entry.link));
+        if let Some(summary) = &entry.summary {
+            let _ = writeln!(xml, "    <summary>{}</summary>", xml_escape(summary));
+        }
+        for category in &entry.categories {
+            let _ = writeln!(xml, "    <category term=\"{}\"/>", xml_escape(category));
+        }
+        xml.push_str("  </entry>\n");
+    }
+
+    xml.push_str("</feed>\n");
+    xml
+}
+
+fn atom_response(result: Result<String, String>) -> Response {
+    match result {
+        Ok(xml) => (
+            StatusCode::OK,
+            [(header::CONTENT_TYPE, "application/atom+xml; charset=utf-8")],
+            xml,
+        )
+            .into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
+
+async fn notes_feed_handler(
+    State(state): State<FeedState>,
+    Query(query): Query<FeedQuery>,
+) -> Response {
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT);
+    let result = tokio::task::spawn_blocking(move || {
+        recent_notes(&state.base_path, state.config, limit)
+            .map(|entries| {
+                render_atom(
+                    "Recently modified notes",
+                    "urn:markdown-todo-extractor:feed:notes",
+                    NOTES_FEED_PATH,
+                    &entries,
+                )
+            })
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .unwrap_or_else(|e| Err(e.to_string()));
+
+    atom_response(result)
+}
+
+async fn completed_feed_handler(
+    State(state): State<FeedState>,
+    Query(query): Query<FeedQuery>,
+) -> Response {
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT);
+    let result = tokio::task::spawn_blocking(move || {
+        recently_completed(&state.base_path, state.config, limit)
+            .map(|entries| {
+                render_atom(
+                    "Recently completed tasks",
+                    "urn:markdown-todo-extractor:feed:completed",
+                    COMPLETED_FEED_PATH,
+                    &entries,
+                )
+            })
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .unwrap_or_else(|e| Err(e.to_string()));
+
+    atom_response(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::{Body, to_bytes};
+    use axum::http::Request;
+    use std::time::{Duration, SystemTime};
+    use tempfile::TempDir;
+    use tower::ServiceExt;
+
+    fn create_vault() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path().join("My Vault");
+        fs::create_dir_all(vault.join("Projects")).unwrap();
+
+        fs::write(
+            vault.join("old.md"),
+            "# Old note\n\nWritten long ago.\n\n- [x] Old task ✅ 2025-01-01\n",
+        )
+        .unwrap();
+        fs::write(
+            vault.join("Projects/new.md"),
+            "---\ntitle: New Project\ntags: [work]\n---\nFresh notes.\n\n\
+             - [x] Ship it #work ✅ 2025-01-05\n- [x] No date\n- [ ] Open\n",
+        )
+        .unwrap();
+
+        let old = fs::File::options()
+            .write(true)
+            .open(vault.join("old.md"))
+            .unwrap();
+        old.set_modified(SystemTime::now() - Duration::from_secs(3600))
+            .unwrap();
+
+        temp_dir
+    }
+
+    #[test]
+    fn test_encode_uri_component() {
+        assert_eq!(encode_uri_component("My Vault"), "My%20Vault");
+        assert_eq!(encode_uri_component("a/b é.md"), "a%2Fb%20%C3%A9.md");
+    }
+
+    #[test]
+    fn test_recent_notes() {
+        let temp_dir = create_vault();
+        let vault = temp_dir.path().join("My Vault");
+
+        let entries = recent_notes(&vault, Arc::new(Config::default()), 10).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].title, "New Project");
+        assert_eq!(entries[0].summary.as_deref(), Some("Fresh notes."));
+        assert_eq!(entries[0].categories, vec!["work"]);
+        assert_eq!(
+            entries[0].link,
+            "obsidian://open?vault=My%20Vault&file=Projects%2Fnew.md"
+        );
+        assert_eq!(entries[1].title, "Old note");
+
+        let entries = recent_notes(&vault, Arc::new(Config::default()), 1).unwrap();
+        assert_eq!(entries.len(), 1);
+    }
+
+    #[test]
+    fn test_recently_completed() {
+        let temp_dir = create_vault();
+        let vault = temp_dir.path().join("My Vault");
+
+        let entries = recently_completed(&vault, Arc::new(Config::default()), 10).unwrap();
+        let titles: Vec<_> = entries.iter().map(|e| e.title.as_str()).collect();
+        assert_eq!(titles, vec!["Ship it #work", "Old task"]);
+        assert_eq!(entries[0].updated.to_rfc3339(), "2025-01-05T00:00:00+00:00");
+        assert_eq!(entries[0].categories, vec!["work"]);
+    }
+
+    #[test]
+    fn test_render_atom() {
+        let entry = FeedEntry {
+            id: "urn:test:1".to_string(),
+            title: "Fish & <Chips>".to_string(),
+            updated: date_to_utc(NaiveDate::from_ymd_opt(2025, 1, 5).unwrap()),
+            link: "obsidian://open?vault=V&file=a.md".to_string(),
+            summary: None,
+            categories: vec!["work".to_string()],
+        };
+
+        let xml = render_atom("Feed", "urn:test", "/feed/test.xml", &[entry]);
+        assert!(xml.contains("<feed xmlns=\"http://www.w3.org/2005/Atom\">"));
+        assert!(xml.contains("  <updated>2025-01-05T00:00:00+00:00</updated>"));
+        assert!(xml.contains("<title>Fish &amp; &lt;Chips&gt;</title>"));
+        assert!(xml.contains("<link href=\"obsidian://open?vault=V&amp;file=a.md\"/>"));
+        assert!(xml.contains("<category term=\"work\"/>"));
+        assert!(!xml.contains("<summary>"));
+    }
+
+    #[tokio::test]
+    async fn test_feed_routes() {
+        let temp_dir = create_vault();
+        let router: Router = router(
+            temp_dir.path().join("My Vault"),
+            Arc::new(Config::default()),
+        );
+
+        for (uri, expected) in [
+            ("/feed/notes.xml?limit=1", "New Project"),
+            (COMPLETED_FEED_PATH, "Ship it #work"),
+        ] {
+            let response = router
+                .clone()
+                .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(
+                response.headers()[header::CONTENT_TYPE],
+                "application/atom+xml; charset=utf-8"
+            );
+            let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            assert!(String::from_utf8_lossy(&body).contains(expected));
+        }
+    }
+}
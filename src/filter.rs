@@ -1,21 +1,389 @@
-use crate::extractor::Task;
+use crate::extractor::{Priority, Task, TaskStatus};
+use crate::unicode::fold;
+use chrono::{Datelike, Days, Local, NaiveDate};
+use glob::{MatchOptions, Pattern};
+use regex::{Regex, RegexBuilder};
+use schemars::{JsonSchema, Schema, SchemaGenerator, json_schema};
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Value of a due or completion date filter: a date, or a keyword resolved
+/// against today's date when the filter runs
+///
+/// Keywords stand for a span of days. `*_on` matches dates in the span,
+/// `*_before` dates before its first day, and `*_after` dates after its last
+/// day. `overdue` is every day before today, so as `*_before` it also means
+/// before today, and as `*_after` today or later.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub enum DateValue {
+    Date(NaiveDate),
+    Today,
+    Tomorrow,
+    Yesterday,
+    /// Monday through Sunday of the current week
+    ThisWeek,
+    /// Today and the six days after it
+    Next7Days,
+    /// Every day before today
+    Overdue,
+}
+
+impl DateValue {
+    /// First (`None` for `overdue`) and last day of the span, given today
+    pub fn span(self, today: NaiveDate) -> (Option<NaiveDate>, Option<NaiveDate>) {
+        let day = |date: Option<NaiveDate>| (date, date);
+        match self {
+            DateValue::Date(date) => day(Some(date)),
+            DateValue::Today => day(Some(today)),
+            DateValue::Tomorrow => day(today.succ_opt()),
+            DateValue::Yesterday => day(today.pred_opt()),
+            DateValue::ThisWeek => {
+                let monday = today
+                    .checked_sub_days(Days::new(today.weekday().num_days_from_monday().into()));
+                (
+                    monday,
+                    monday.and_then(|d| d.checked_add_days(Days::new(6))),
+                )
+            }
+            DateValue::Next7Days => (Some(today), today.checked_add_days(Days::new(6))),
+            DateValue::Overdue => (None, today.pred_opt()),
+        }
+    }
+
+    fn is_relative(self) -> bool {
+        !matches!(self, DateValue::Date(_))
+    }
+}
+
+impl From<NaiveDate> for DateValue {
+    fn from(date: NaiveDate) -> Self {
+        DateValue::Date(date)
+    }
+}
+
+impl fmt::Display for DateValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DateValue::Date(date) => write!(f, "{}", date.format("%Y-%m-%d")),
+            DateValue::Today => write!(f, "today"),
+            DateValue::Tomorrow => write!(f, "tomorrow"),
+            DateValue::Yesterday => write!(f, "yesterday"),
+            DateValue::ThisWeek => write!(f, "this-week"),
+            DateValue::Next7Days => write!(f, "next-7-days"),
+            DateValue::Overdue => write!(f, "overdue"),
+        }
+    }
+}
+
+impl FromStr for DateValue {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "today" => Ok(DateValue::Today),
+            "tomorrow" => Ok(DateValue::Tomorrow),
+            "yesterday" => Ok(DateValue::Yesterday),
+            "this-week" => Ok(DateValue::ThisWeek),
+            "next-7-days" => Ok(DateValue::Next7Days),
+            "overdue" => Ok(DateValue::Overdue),
+            _ => NaiveDate::parse_from_str(s, "%Y-%m-%d")
+                .map(DateValue::Date)
+                .map_err(|_| {
+                    format!(
+                        "Invalid date '{}': expected YYYY-MM-DD, today, tomorrow, yesterday, this-week, next-7-days, or overdue",
+                        s
+                    )
+                }),
+        }
+    }
+}
+
+impl From<DateValue> for String {
+    fn from(value: DateValue) -> Self {
+        value.to_string()
+    }
+}
+
+impl TryFrom<String> for DateValue {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl JsonSchema for DateValue {
+    fn schema_name() -> Cow<'static, str> {
+        "DateValue".into()
+    }
+
+    fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "description": "A date (YYYY-MM-DD) or a keyword resolved when the search runs: today, tomorrow, yesterday, this-week (Monday to Sunday), next-7-days (today and the next six days), or overdue (every day before today)",
+            "anyOf": [
+                { "format": "date" },
+                { "enum": ["today", "tomorrow", "yesterday", "this-week", "next-7-days", "overdue"] }
+            ]
+        })
+    }
+}
+
+/// Inclusive range of dates one field must fall in, from its `on`, `before`,
+/// and `after` filters
+#[derive(Debug, Clone, Copy, Default)]
+struct DateRange {
+    first: Option<NaiveDate>,
+    last: Option<NaiveDate>,
+    /// Whether any filter was given, so tasks without the date are excluded
+    active: bool,
+}
+
+impl DateRange {
+    fn new(
+        on: Option<DateValue>,
+        before: Option<DateValue>,
+        after: Option<DateValue>,
+        today: impl Fn() -> NaiveDate,
+    ) -> Self {
+        let mut range = DateRange::default();
+        let mut narrow = |first: Option<NaiveDate>, last: Option<NaiveDate>| {
+            range.active = true;
+            if first > range.first {
+                range.first = first;
+            }
+            if let Some(last) = last
+                && range.last.is_none_or(|current| last < current)
+            {
+                range.last = Some(last);
+            }
+        };
+        let span = |value: DateValue| {
+            value.span(if value.is_relative() {
+                today()
+            } else {
+                NaiveDate::MIN
+            })
+        };
+
+        if let Some(on) = on {
+            let (first, last) = span(on);
+            narrow(first, last);
+        }
+        if let Some(before) = before {
+            // Before the first day; `overdue` has none, so before today
+            let (first, last) = span(before);
+            let limit = first.or_else(|| last.and_then(|d| d.succ_opt()));
+            narrow(None, limit.and_then(|d| d.pred_opt()));
+        }
+        if let Some(after) = after {
+            let (_, last) = span(after);
+            narrow(last.and_then(|d| d.succ_opt()), None);
+        }
+        range
+    }
+
+    fn contains(&self, date: Option<NaiveDate>) -> bool {
+        if !self.active {
+            return true;
+        }
+        date.is_some_and(|date| {
+            self.first.is_none_or(|first| date >= first)
+                && self.last.is_none_or(|last| date <= last)
+        })
+    }
+}
 
 /// Filter options for task search
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct FilterOptions {
-    pub status: Option<String>,
-    pub due_on: Option<String>,
-    pub due_before: Option<String>,
-    pub due_after: Option<String>,
-    pub completed_on: Option<String>,
-    pub completed_before: Option<String>,
-    pub completed_after: Option<String>,
+    pub status: Option<TaskStatus>,
+    /// Only include tasks with exactly this priority
+    #[serde(default)]
+    pub priority: Option<Priority>,
+    /// Only include tasks with at least this priority (`high` also matches
+    /// `urgent`)
+    #[serde(default)]
+    pub min_priority: Option<Priority>,
+    pub due_on: Option<DateValue>,
+    pub due_before: Option<DateValue>,
+    pub due_after: Option<DateValue>,
+    /// Only include tasks scheduled (`⏳`) on this date
+    #[serde(default)]
+    pub scheduled_on: Option<NaiveDate>,
+    /// Only include tasks scheduled before this date
+    #[serde(default)]
+    pub scheduled_before: Option<NaiveDate>,
+    /// Only include tasks scheduled after this date
+    #[serde(default)]
+    pub scheduled_after: Option<NaiveDate>,
+    /// Only include tasks whose start date (`🛫`) is after this date
+    #[serde(default)]
+    pub starts_after: Option<NaiveDate>,
+    pub completed_on: Option<DateValue>,
+    pub completed_before: Option<DateValue>,
+    pub completed_after: Option<DateValue>,
     pub tags: Option<Vec<String>>,
     pub exclude_tags: Option<Vec<String>>,
+    /// Only include tasks without a due date
+    #[serde(default)]
+    pub no_due_date: bool,
+    /// Include tasks quoted inside blockquotes and callouts
+    #[serde(default)]
+    pub include_quoted: bool,
+    /// Only include tasks assigned to this person (case-insensitive)
+    #[serde(default)]
+    pub assignee: Option<String>,
+    /// Only include tasks without an assignee
+    #[serde(default)]
+    pub unassigned: bool,
+    /// Only include recurring tasks (with a `🔁` rule)
+    #[serde(default)]
+    pub recurring: bool,
+    /// Only include tasks whose file path matches this glob, e.g.
+    /// `Projects/**` (`*` stays within a folder, `**` spans folders)
+    ///
+    /// The glob is matched against the task's `file_path` as extracted, with
+    /// `/` separators. Use [`FilterOptions::within`] to make it relative to
+    /// the scanned folder.
+    #[serde(default)]
+    pub path_glob: Option<String>,
+    /// Only include tasks whose content contains this text, ignoring case
+    /// and Unicode normalization form
+    #[serde(default)]
+    pub text: Option<String>,
+    /// Match `text` as a regular expression (case-insensitive unless it
+    /// starts with `(?-i)`) instead of a substring
+    #[serde(default)]
+    pub text_regex: bool,
+    /// Only include tasks under a heading containing this text, ignoring
+    /// case; a breadcrumb like `Project X > Backlog` matches nested headings
+    #[serde(default)]
+    pub heading: Option<String>,
+    /// Only include tasks waiting on an open task (`true`) or tasks that
+    /// aren't (`false`)
+    #[serde(default)]
+    pub blocked: Option<bool>,
+    /// Only include tasks whose file path contains this text, ignoring case
+    ///
+    /// The path is taken after `path_root`, which [`FilterOptions::within`]
+    /// sets to the scanned folder.
+    #[serde(default)]
+    pub path_includes: Option<String>,
+    /// Folder that `path_includes` matches below; not part of a request
+    #[serde(skip)]
+    pub path_root: Option<String>,
 }
 
+impl FilterOptions {
+    /// These filters, with `path_glob` and `path_includes` anchored to
+    /// `base_path`, the folder the tasks were extracted from
+    pub fn within(mut self, base_path: &Path) -> Self {
+        let base = base_path.to_string_lossy().replace('\\', "/");
+        if let Some(ref glob) = self.path_glob {
+            self.path_glob = Some(format!(
+                "{}/{}",
+                Pattern::escape(base.trim_end_matches('/')),
+                glob.trim_start_matches("./").trim_start_matches('/')
+            ));
+        }
+        self.path_root = Some(base.trim_end_matches('/').to_string());
+        self
+    }
+}
+
+/// Compile the `text` filter of a `text_regex` search
+///
+/// The `regex` crate runs in linear time; overly large patterns fail to
+/// compile.
+pub fn compile_text_regex(pattern: &str) -> Result<Regex, regex::Error> {
+    RegexBuilder::new(pattern)
+        .case_insensitive(true)
+        .size_limit(1 << 20)
+        .build()
+}
+
+/// How the `text` filter matches task content
+enum TextMatcher {
+    /// A folded substring
+    Substring(String),
+    Regex(Regex),
+    /// An invalid pattern, which matches nothing
+    Nothing,
+}
+
+impl TextMatcher {
+    fn new(text: &str, regex: bool) -> Self {
+        if !regex {
+            return TextMatcher::Substring(fold(text));
+        }
+        compile_text_regex(text).map_or(TextMatcher::Nothing, TextMatcher::Regex)
+    }
+
+    fn matches(&self, content: &str) -> bool {
+        match self {
+            TextMatcher::Substring(text) => fold(content).contains(text.as_str()),
+            TextMatcher::Regex(regex) => regex.is_match(content),
+            TextMatcher::Nothing => false,
+        }
+    }
+}
+
+/// Options for `path_glob`: `*` and `?` don't cross folders
+const PATH_GLOB_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+/// Filter tasks, resolving relative date filters (`today`, `this-week`, ...)
+/// against the local date
 pub fn filter_tasks(tasks: Vec<Task>, options: &FilterOptions) -> Vec<Task> {
+    filter_tasks_on(tasks, options, || Local::now().date_naive())
+}
+
+/// Filter tasks, resolving relative date filters against `today`, which is
+/// only called if there are any
+pub fn filter_tasks_on(
+    tasks: Vec<Task>,
+    options: &FilterOptions,
+    today: impl Fn() -> NaiveDate,
+) -> Vec<Task> {
+    let due = DateRange::new(
+        options.due_on,
+        options.due_before,
+        options.due_after,
+        &today,
+    );
+    let completed = DateRange::new(
+        options.completed_on,
+        options.completed_before,
+        options.completed_after,
+        &today,
+    );
+    // Tags match regardless of case and Unicode normalization form
+    let fold_all = |tags: &Option<Vec<String>>| {
+        tags.as_ref()
+            .map(|tags| tags.iter().map(|tag| fold(tag)).collect::<Vec<_>>())
+    };
+    let wanted_tags = fold_all(&options.tags);
+    let excluded_tags = fold_all(&options.exclude_tags);
+    // An invalid glob matches nothing
+    let path_glob = options
+        .path_glob
+        .as_deref()
+        .map(|glob| Pattern::new(glob).ok());
+    let text = options
+        .text
+        .as_deref()
+        .map(|text| TextMatcher::new(text, options.text_regex));
+    let heading = options.heading.as_deref().map(fold);
+    let path_includes = options.path_includes.as_deref().map(fold);
+
     tasks
         .into_iter()
         .filter(|task| {
@@ -26,28 +394,108 @@ pub fn filter_tasks(tasks: Vec<Task>, options: &FilterOptions) -> Vec<Task> {
                 return false;
             }
 
-            // Filter by exact due date
-            if let Some(ref due_on) = options.due_on
-                && task.due_date.as_ref() != Some(due_on)
+            // Filter by priority; tasks without one never match
+            if let Some(ref priority) = options.priority
+                && task.priority.as_ref() != Some(priority)
+            {
+                return false;
+            }
+            if let Some(ref min_priority) = options.min_priority
+                && task.priority.is_none_or(|p| p < *min_priority)
             {
                 return false;
             }
 
-            // Filter by due before date
-            if let Some(ref due_before) = options.due_before {
-                if let Some(ref due_date) = task.due_date {
-                    if due_date >= due_before {
-                        return false;
-                    }
-                } else {
+            // Filter by due date
+            if !due.contains(task.due_date) {
+                return false;
+            }
+
+            // Skip quoted tasks unless asked for
+            if task.quoted && !options.include_quoted {
+                return false;
+            }
+
+            // Filter by missing due date
+            if options.no_due_date && task.due_date.is_some() {
+                return false;
+            }
+
+            // Filter by assignee
+            if let Some(ref assignee) = options.assignee
+                && !task
+                    .assignee
+                    .as_ref()
+                    .is_some_and(|a| a.eq_ignore_ascii_case(assignee.trim_start_matches('@')))
+            {
+                return false;
+            }
+
+            // Filter by missing assignee
+            if options.unassigned && task.assignee.is_some() {
+                return false;
+            }
+
+            // Filter by open dependencies
+            if options
+                .blocked
+                .is_some_and(|blocked| blocked != task.blocked)
+            {
+                return false;
+            }
+
+            // Filter by recurrence rule
+            if options.recurring && task.recurrence.is_none() {
+                return false;
+            }
+
+            // Filter by content
+            if let Some(ref text) = text
+                && !text.matches(&task.content)
+            {
+                return false;
+            }
+
+            // Filter by heading breadcrumb
+            if let Some(ref heading) = heading
+                && !fold(&task.heading_path.join(" > ")).contains(heading.as_str())
+            {
+                return false;
+            }
+
+            // Filter by file path
+            if let Some(ref pattern) = path_glob
+                && !pattern.as_ref().is_some_and(|pattern| {
+                    pattern.matches_with(&task.file_path.replace('\\', "/"), PATH_GLOB_OPTIONS)
+                })
+            {
+                return false;
+            }
+
+            // Filter by text in the path below the scanned folder
+            if let Some(ref text) = path_includes {
+                let path = task.file_path.replace('\\', "/");
+                let relative = options
+                    .path_root
+                    .as_deref()
+                    .and_then(|root| path.strip_prefix(root))
+                    .unwrap_or(&path);
+                if !fold(relative).contains(text.as_str()) {
                     return false;
                 }
             }
 
-            // Filter by due after date
-            if let Some(ref due_after) = options.due_after {
-                if let Some(ref due_date) = task.due_date {
-                    if due_date <= due_after {
+            // Filter by exact scheduled date
+            if let Some(ref scheduled_on) = options.scheduled_on
+                && task.scheduled_date.as_ref() != Some(scheduled_on)
+            {
+                return false;
+            }
+
+            // Filter by scheduled before date
+            if let Some(ref scheduled_before) = options.scheduled_before {
+                if let Some(ref scheduled_date) = task.scheduled_date {
+                    if scheduled_date >= scheduled_before {
                         return false;
                     }
                 } else {
@@ -55,17 +503,10 @@ pub fn filter_tasks(tasks: Vec<Task>, options: &FilterOptions) -> Vec<Task> {
                 }
             }
 
-            // Filter by exact completed date
-            if let Some(ref completed_on) = options.completed_on
-                && task.completed_date.as_ref() != Some(completed_on)
-            {
-                return false;
-            }
-
-            // Filter by completed before date
-            if let Some(ref completed_before) = options.completed_before {
-                if let Some(ref completed_date) = task.completed_date {
-                    if completed_date >= completed_before {
+            // Filter by scheduled after date
+            if let Some(ref scheduled_after) = options.scheduled_after {
+                if let Some(ref scheduled_date) = task.scheduled_date {
+                    if scheduled_date <= scheduled_after {
                         return false;
                     }
                 } else {
@@ -73,10 +514,10 @@ pub fn filter_tasks(tasks: Vec<Task>, options: &FilterOptions) -> Vec<Task> {
                 }
             }
 
-            // Filter by completed after date
-            if let Some(ref completed_after) = options.completed_after {
-                if let Some(ref completed_date) = task.completed_date {
-                    if completed_date <= completed_after {
+            // Filter by start after date
+            if let Some(ref starts_after) = options.starts_after {
+                if let Some(ref start_date) = task.start_date {
+                    if start_date <= starts_after {
                         return false;
                     }
                 } else {
@@ -84,18 +525,25 @@ pub fn filter_tasks(tasks: Vec<Task>, options: &FilterOptions) -> Vec<Task> {
                 }
             }
 
-            // Filter by tags (must have all specified tags)
-            if let Some(ref tags) = options.tags
-                && !tags.iter().all(|tag| task.tags.contains(tag))
-            {
+            // Filter by completed date
+            if !completed.contains(task.completed_date) {
                 return false;
             }
 
-            // Filter by excluded tags (must not have any specified tags)
-            if let Some(ref exclude_tags) = options.exclude_tags
-                && exclude_tags.iter().any(|tag| task.tags.contains(tag))
-            {
-                return false;
+            // Filter by tags (must have all specified tags) and excluded tags
+            // (must not have any specified tags)
+            if wanted_tags.is_some() || excluded_tags.is_some() {
+                let task_tags: Vec<String> = task.tags.iter().map(|tag| fold(tag)).collect();
+                if let Some(ref tags) = wanted_tags
+                    && !tags.iter().all(|tag| task_tags.contains(tag))
+                {
+                    return false;
+                }
+                if let Some(ref exclude_tags) = excluded_tags
+                    && exclude_tags.iter().any(|tag| task_tags.contains(tag))
+                {
+                    return false;
+                }
             }
 
             true
@@ -107,6 +555,10 @@ pub fn filter_tasks(tasks: Vec<Task>, options: &FilterOptions) -> Vec<Task> {
 mod tests {
     use super::*;
 
+    fn date(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
     fn create_test_task(
         content: &str,
         status: &str,
@@ -116,7 +568,7 @@ mod tests {
     ) -> Task {
         Task {
             content: content.to_string(),
-            status: status.to_string(),
+            status: status.parse().unwrap(),
             file_path: "test.md".to_string(),
             file_name: "test.md".to_string(),
             line_number: 1,
@@ -126,12 +578,27 @@ mod tests {
                 content
             ),
             tags: tags.iter().map(|s| s.to_string()).collect(),
-            sub_items: vec![],
+            sub_items: Default::default(),
+            heading: None,
+            heading_path: vec![],
             summary: None,
-            due_date: due_date.map(String::from),
+            due_date: due_date.map(date),
+            scheduled_date: None,
+            start_date: None,
             priority: None,
             created_date: None,
-            completed_date: completed_date.map(String::from),
+            completed_date: completed_date.map(date),
+            fields: Default::default(),
+            time_spent: None,
+            quoted: false,
+            recurrence: None,
+            occurrence: false,
+            assignee: None,
+            block_id: None,
+            id: None,
+            depends_on: vec![],
+            blocked: false,
+            diagnostics: None,
         }
     }
 
@@ -139,19 +606,37 @@ mod tests {
     fn test_no_filters_returns_all_tasks() {
         let tasks = vec![
             create_test_task("Task 1", "incomplete", None, None, vec![]),
-            create_test_task("Task 2", "complete", None, None, vec![]),
+            create_test_task("Task 2", "completed", None, None, vec![]),
         ];
 
         let options = FilterOptions {
             status: None,
+            priority: None,
+            min_priority: None,
             due_on: None,
             due_before: None,
             due_after: None,
+            scheduled_on: None,
+            scheduled_before: None,
+            scheduled_after: None,
+            starts_after: None,
             completed_on: None,
             completed_before: None,
             completed_after: None,
             tags: None,
             exclude_tags: None,
+            no_due_date: false,
+            include_quoted: false,
+            assignee: None,
+            unassigned: false,
+            recurring: false,
+            path_glob: None,
+            text: None,
+            text_regex: false,
+            heading: None,
+            blocked: None,
+            path_includes: None,
+            path_root: None,
         };
 
         let filtered = filter_tasks(tasks.clone(), &options);
@@ -162,15 +647,33 @@ mod tests {
     fn test_empty_task_list() {
         let tasks: Vec<Task> = vec![];
         let options = FilterOptions {
-            status: Some("incomplete".to_string()),
+            status: Some(TaskStatus::Incomplete),
+            priority: None,
+            min_priority: None,
             due_on: None,
             due_before: None,
             due_after: None,
+            scheduled_on: None,
+            scheduled_before: None,
+            scheduled_after: None,
+            starts_after: None,
             completed_on: None,
             completed_before: None,
             completed_after: None,
             tags: None,
             exclude_tags: None,
+            no_due_date: false,
+            include_quoted: false,
+            assignee: None,
+            unassigned: false,
+            recurring: false,
+            path_glob: None,
+            text: None,
+            text_regex: false,
+            heading: None,
+            blocked: None,
+            path_includes: None,
+            path_root: None,
         };
 
         let filtered = filter_tasks(tasks, &options);
@@ -181,50 +684,86 @@ mod tests {
     fn test_status_filter_incomplete() {
         let tasks = vec![
             create_test_task("Task 1", "incomplete", None, None, vec![]),
-            create_test_task("Task 2", "complete", None, None, vec![]),
+            create_test_task("Task 2", "completed", None, None, vec![]),
             create_test_task("Task 3", "incomplete", None, None, vec![]),
         ];
 
         let options = FilterOptions {
-            status: Some("incomplete".to_string()),
+            status: Some(TaskStatus::Incomplete),
+            priority: None,
+            min_priority: None,
             due_on: None,
             due_before: None,
             due_after: None,
+            scheduled_on: None,
+            scheduled_before: None,
+            scheduled_after: None,
+            starts_after: None,
             completed_on: None,
             completed_before: None,
             completed_after: None,
             tags: None,
             exclude_tags: None,
+            no_due_date: false,
+            include_quoted: false,
+            assignee: None,
+            unassigned: false,
+            recurring: false,
+            path_glob: None,
+            text: None,
+            text_regex: false,
+            heading: None,
+            blocked: None,
+            path_includes: None,
+            path_root: None,
         };
 
         let filtered = filter_tasks(tasks, &options);
         assert_eq!(filtered.len(), 2);
-        assert!(filtered.iter().all(|t| t.status == "incomplete"));
+        assert!(filtered.iter().all(|t| t.status == TaskStatus::Incomplete));
     }
 
     #[test]
     fn test_status_filter_complete() {
         let tasks = vec![
             create_test_task("Task 1", "incomplete", None, None, vec![]),
-            create_test_task("Task 2", "complete", None, None, vec![]),
-            create_test_task("Task 3", "complete", None, None, vec![]),
+            create_test_task("Task 2", "completed", None, None, vec![]),
+            create_test_task("Task 3", "completed", None, None, vec![]),
         ];
 
         let options = FilterOptions {
-            status: Some("complete".to_string()),
+            status: Some(TaskStatus::Completed),
+            priority: None,
+            min_priority: None,
             due_on: None,
             due_before: None,
             due_after: None,
+            scheduled_on: None,
+            scheduled_before: None,
+            scheduled_after: None,
+            starts_after: None,
             completed_on: None,
             completed_before: None,
             completed_after: None,
             tags: None,
             exclude_tags: None,
+            no_due_date: false,
+            include_quoted: false,
+            assignee: None,
+            unassigned: false,
+            recurring: false,
+            path_glob: None,
+            text: None,
+            text_regex: false,
+            heading: None,
+            blocked: None,
+            path_includes: None,
+            path_root: None,
         };
 
         let filtered = filter_tasks(tasks, &options);
         assert_eq!(filtered.len(), 2);
-        assert!(filtered.iter().all(|t| t.status == "complete"));
+        assert!(filtered.iter().all(|t| t.status == TaskStatus::Completed));
     }
 
     #[test]
@@ -237,19 +776,37 @@ mod tests {
 
         let options = FilterOptions {
             status: None,
-            due_on: Some("2025-01-20".to_string()),
+            priority: None,
+            min_priority: None,
+            due_on: Some(date("2025-01-20").into()),
             due_before: None,
             due_after: None,
+            scheduled_on: None,
+            scheduled_before: None,
+            scheduled_after: None,
+            starts_after: None,
             completed_on: None,
             completed_before: None,
             completed_after: None,
             tags: None,
             exclude_tags: None,
+            no_due_date: false,
+            include_quoted: false,
+            assignee: None,
+            unassigned: false,
+            recurring: false,
+            path_glob: None,
+            text: None,
+            text_regex: false,
+            heading: None,
+            blocked: None,
+            path_includes: None,
+            path_root: None,
         };
 
         let filtered = filter_tasks(tasks, &options);
         assert_eq!(filtered.len(), 1);
-        assert_eq!(filtered[0].due_date, Some("2025-01-20".to_string()));
+        assert_eq!(filtered[0].due_date, Some(date("2025-01-20")));
     }
 
     #[test]
@@ -261,14 +818,32 @@ mod tests {
 
         let options = FilterOptions {
             status: None,
+            priority: None,
+            min_priority: None,
             due_on: None,
-            due_before: Some("2025-01-20".to_string()),
+            due_before: Some(date("2025-01-20").into()),
             due_after: None,
+            scheduled_on: None,
+            scheduled_before: None,
+            scheduled_after: None,
+            starts_after: None,
             completed_on: None,
             completed_before: None,
             completed_after: None,
             tags: None,
             exclude_tags: None,
+            no_due_date: false,
+            include_quoted: false,
+            assignee: None,
+            unassigned: false,
+            recurring: false,
+            path_glob: None,
+            text: None,
+            text_regex: false,
+            heading: None,
+            blocked: None,
+            path_includes: None,
+            path_root: None,
         };
 
         let filtered = filter_tasks(tasks, &options);
@@ -286,14 +861,32 @@ mod tests {
 
         let options = FilterOptions {
             status: None,
+            priority: None,
+            min_priority: None,
             due_on: None,
-            due_before: Some("2025-01-20".to_string()),
+            due_before: Some(date("2025-01-20").into()),
             due_after: None,
+            scheduled_on: None,
+            scheduled_before: None,
+            scheduled_after: None,
+            starts_after: None,
             completed_on: None,
             completed_before: None,
             completed_after: None,
             tags: None,
             exclude_tags: None,
+            no_due_date: false,
+            include_quoted: false,
+            assignee: None,
+            unassigned: false,
+            recurring: false,
+            path_glob: None,
+            text: None,
+            text_regex: false,
+            heading: None,
+            blocked: None,
+            path_includes: None,
+            path_root: None,
         };
 
         let filtered = filter_tasks(tasks, &options);
@@ -310,14 +903,32 @@ mod tests {
 
         let options = FilterOptions {
             status: None,
+            priority: None,
+            min_priority: None,
             due_on: None,
             due_before: None,
-            due_after: Some("2025-01-20".to_string()),
+            due_after: Some(date("2025-01-20").into()),
+            scheduled_on: None,
+            scheduled_before: None,
+            scheduled_after: None,
+            starts_after: None,
             completed_on: None,
             completed_before: None,
             completed_after: None,
             tags: None,
             exclude_tags: None,
+            no_due_date: false,
+            include_quoted: false,
+            assignee: None,
+            unassigned: false,
+            recurring: false,
+            path_glob: None,
+            text: None,
+            text_regex: false,
+            heading: None,
+            blocked: None,
+            path_includes: None,
+            path_root: None,
         };
 
         let filtered = filter_tasks(tasks, &options);
@@ -335,14 +946,32 @@ mod tests {
 
         let options = FilterOptions {
             status: None,
+            priority: None,
+            min_priority: None,
             due_on: None,
             due_before: None,
-            due_after: Some("2025-01-20".to_string()),
+            due_after: Some(date("2025-01-20").into()),
+            scheduled_on: None,
+            scheduled_before: None,
+            scheduled_after: None,
+            starts_after: None,
             completed_on: None,
             completed_before: None,
             completed_after: None,
             tags: None,
             exclude_tags: None,
+            no_due_date: false,
+            include_quoted: false,
+            assignee: None,
+            unassigned: false,
+            recurring: false,
+            path_glob: None,
+            text: None,
+            text_regex: false,
+            heading: None,
+            blocked: None,
+            path_includes: None,
+            path_root: None,
         };
 
         let filtered = filter_tasks(tasks, &options);
@@ -361,14 +990,32 @@ mod tests {
 
         let options = FilterOptions {
             status: None,
+            priority: None,
+            min_priority: None,
             due_on: None,
-            due_before: Some("2025-01-26".to_string()),
-            due_after: Some("2025-01-10".to_string()),
+            due_before: Some(date("2025-01-26").into()),
+            due_after: Some(date("2025-01-10").into()),
+            scheduled_on: None,
+            scheduled_before: None,
+            scheduled_after: None,
+            starts_after: None,
             completed_on: None,
             completed_before: None,
             completed_after: None,
             tags: None,
             exclude_tags: None,
+            no_due_date: false,
+            include_quoted: false,
+            assignee: None,
+            unassigned: false,
+            recurring: false,
+            path_glob: None,
+            text: None,
+            text_regex: false,
+            heading: None,
+            blocked: None,
+            path_includes: None,
+            path_root: None,
         };
 
         let filtered = filter_tasks(tasks, &options);
@@ -377,49 +1024,247 @@ mod tests {
         assert!(filtered.iter().any(|t| t.content == "Task 3"));
     }
 
+    #[test]
+    fn test_relative_date_filters() {
+        // 2025-01-08 is a Wednesday
+        let today = || date("2025-01-08");
+        let tasks = vec![
+            create_test_task("Last week", "incomplete", Some("2025-01-05"), None, vec![]),
+            create_test_task("Yesterday", "incomplete", Some("2025-01-07"), None, vec![]),
+            create_test_task("Today", "incomplete", Some("2025-01-08"), None, vec![]),
+            create_test_task("Sunday", "incomplete", Some("2025-01-12"), None, vec![]),
+            create_test_task("Tuesday", "incomplete", Some("2025-01-14"), None, vec![]),
+            create_test_task("Done today", "completed", None, Some("2025-01-08"), vec![]),
+            create_test_task("Undated", "incomplete", None, None, vec![]),
+        ];
+        let matching = |options: FilterOptions| -> Vec<String> {
+            filter_tasks_on(tasks.clone(), &options, today)
+                .into_iter()
+                .map(|t| t.content)
+                .collect()
+        };
+        let keyword = |s: &str| Some(s.parse::<DateValue>().unwrap());
+
+        assert_eq!(
+            matching(FilterOptions {
+                due_on: keyword("overdue"),
+                ..Default::default()
+            }),
+            ["Last week", "Yesterday"]
+        );
+        assert_eq!(
+            matching(FilterOptions {
+                due_before: keyword("overdue"),
+                ..Default::default()
+            }),
+            ["Last week", "Yesterday"]
+        );
+        assert_eq!(
+            matching(FilterOptions {
+                due_on: keyword("this-week"),
+                ..Default::default()
+            }),
+            ["Yesterday", "Today", "Sunday"]
+        );
+        assert_eq!(
+            matching(FilterOptions {
+                due_on: keyword("next-7-days"),
+                ..Default::default()
+            }),
+            ["Today", "Sunday", "Tuesday"]
+        );
+        assert_eq!(
+            matching(FilterOptions {
+                due_after: keyword("this-week"),
+                ..Default::default()
+            }),
+            ["Tuesday"]
+        );
+        assert_eq!(
+            matching(FilterOptions {
+                due_before: keyword("tomorrow"),
+                due_after: keyword("yesterday"),
+                ..Default::default()
+            }),
+            ["Today"]
+        );
+        assert_eq!(
+            matching(FilterOptions {
+                completed_on: keyword("today"),
+                ..Default::default()
+            }),
+            ["Done today"]
+        );
+    }
+
+    #[test]
+    fn test_heading_filter() {
+        let tasks: Vec<Task> = [
+            vec!["Project X", "Backlog"],
+            vec!["Project Y", "Backlog"],
+            vec![],
+        ]
+        .into_iter()
+        .map(|path| {
+            let mut task = create_test_task("Task", "incomplete", None, None, vec![]);
+            task.heading_path = path.into_iter().map(str::to_string).collect();
+            task
+        })
+        .collect();
+        let matching = |heading: &str| {
+            let options = FilterOptions {
+                heading: Some(heading.to_string()),
+                ..Default::default()
+            };
+            filter_tasks(tasks.clone(), &options).len()
+        };
+
+        assert_eq!(matching("backlog"), 2);
+        assert_eq!(matching("Project X > Backlog"), 1);
+        assert_eq!(matching("project"), 2);
+        assert_eq!(matching("Inbox"), 0);
+    }
+
+    #[test]
+    fn test_path_glob() {
+        let tasks: Vec<Task> = ["vault/Projects/a.md", "vault/Projects/x/b.md", "vault/c.md"]
+            .into_iter()
+            .map(|path| {
+                let mut task = create_test_task(path, "incomplete", None, None, vec![]);
+                task.file_path = path.to_string();
+                task
+            })
+            .collect();
+        let matching = |glob: &str, base: &str| -> Vec<String> {
+            let options = FilterOptions {
+                path_glob: Some(glob.to_string()),
+                ..Default::default()
+            }
+            .within(Path::new(base));
+            filter_tasks(tasks.clone(), &options)
+                .into_iter()
+                .map(|t| t.file_path)
+                .collect()
+        };
+
+        assert_eq!(
+            matching("Projects/**", "vault/"),
+            ["vault/Projects/a.md", "vault/Projects/x/b.md"]
+        );
+        assert_eq!(
+            matching("./Projects/*.md", "vault"),
+            ["vault/Projects/a.md"]
+        );
+        assert_eq!(matching("*.md", "vault"), ["vault/c.md"]);
+        assert!(matching("[", "vault").is_empty());
+    }
+
+    #[test]
+    fn test_date_value_parse() {
+        assert_eq!(
+            "2025-01-20".parse::<DateValue>(),
+            Ok(DateValue::Date(date("2025-01-20")))
+        );
+        assert_eq!("next-7-days".parse::<DateValue>(), Ok(DateValue::Next7Days));
+        assert!("next week".parse::<DateValue>().is_err());
+        assert_eq!(
+            serde_json::to_string(&DateValue::ThisWeek).unwrap(),
+            "\"this-week\""
+        );
+        // Plain dates never read the clock
+        let tasks = vec![create_test_task(
+            "Task",
+            "incomplete",
+            Some("2025-01-05"),
+            None,
+            vec![],
+        )];
+        let options = FilterOptions {
+            due_before: Some(date("2025-01-06").into()),
+            ..Default::default()
+        };
+        assert_eq!(filter_tasks_on(tasks, &options, || unreachable!()).len(), 1);
+    }
+
     #[test]
     fn test_completed_on_exact_match() {
         let tasks = vec![
-            create_test_task("Task 1", "complete", None, Some("2025-01-15"), vec![]),
-            create_test_task("Task 2", "complete", None, Some("2025-01-20"), vec![]),
-            create_test_task("Task 3", "complete", None, None, vec![]),
+            create_test_task("Task 1", "completed", None, Some("2025-01-15"), vec![]),
+            create_test_task("Task 2", "completed", None, Some("2025-01-20"), vec![]),
+            create_test_task("Task 3", "completed", None, None, vec![]),
         ];
 
         let options = FilterOptions {
             status: None,
+            priority: None,
+            min_priority: None,
             due_on: None,
             due_before: None,
             due_after: None,
-            completed_on: Some("2025-01-20".to_string()),
+            scheduled_on: None,
+            scheduled_before: None,
+            scheduled_after: None,
+            starts_after: None,
+            completed_on: Some(date("2025-01-20").into()),
             completed_before: None,
             completed_after: None,
             tags: None,
             exclude_tags: None,
+            no_due_date: false,
+            include_quoted: false,
+            assignee: None,
+            unassigned: false,
+            recurring: false,
+            path_glob: None,
+            text: None,
+            text_regex: false,
+            heading: None,
+            blocked: None,
+            path_includes: None,
+            path_root: None,
         };
 
         let filtered = filter_tasks(tasks, &options);
         assert_eq!(filtered.len(), 1);
-        assert_eq!(filtered[0].completed_date, Some("2025-01-20".to_string()));
+        assert_eq!(filtered[0].completed_date, Some(date("2025-01-20")));
     }
 
     #[test]
     fn test_completed_before_filters_correctly() {
         let tasks = vec![
-            create_test_task("Task 1", "complete", None, Some("2025-01-10"), vec![]),
-            create_test_task("Task 2", "complete", None, Some("2025-01-20"), vec![]),
-            create_test_task("Task 3", "complete", None, Some("2025-01-25"), vec![]),
+            create_test_task("Task 1", "completed", None, Some("2025-01-10"), vec![]),
+            create_test_task("Task 2", "completed", None, Some("2025-01-20"), vec![]),
+            create_test_task("Task 3", "completed", None, Some("2025-01-25"), vec![]),
         ];
 
         let options = FilterOptions {
             status: None,
+            priority: None,
+            min_priority: None,
             due_on: None,
             due_before: None,
             due_after: None,
+            scheduled_on: None,
+            scheduled_before: None,
+            scheduled_after: None,
+            starts_after: None,
             completed_on: None,
-            completed_before: Some("2025-01-20".to_string()),
+            completed_before: Some(date("2025-01-20").into()),
             completed_after: None,
             tags: None,
             exclude_tags: None,
+            no_due_date: false,
+            include_quoted: false,
+            assignee: None,
+            unassigned: false,
+            recurring: false,
+            path_glob: None,
+            text: None,
+            text_regex: false,
+            heading: None,
+            blocked: None,
+            path_includes: None,
+            path_root: None,
         };
 
         let filtered = filter_tasks(tasks, &options);
@@ -430,21 +1275,39 @@ mod tests {
     #[test]
     fn test_completed_after_filters_correctly() {
         let tasks = vec![
-            create_test_task("Task 1", "complete", None, Some("2025-01-10"), vec![]),
-            create_test_task("Task 2", "complete", None, Some("2025-01-20"), vec![]),
-            create_test_task("Task 3", "complete", None, Some("2025-01-25"), vec![]),
+            create_test_task("Task 1", "completed", None, Some("2025-01-10"), vec![]),
+            create_test_task("Task 2", "completed", None, Some("2025-01-20"), vec![]),
+            create_test_task("Task 3", "completed", None, Some("2025-01-25"), vec![]),
         ];
 
         let options = FilterOptions {
             status: None,
+            priority: None,
+            min_priority: None,
             due_on: None,
             due_before: None,
             due_after: None,
+            scheduled_on: None,
+            scheduled_before: None,
+            scheduled_after: None,
+            starts_after: None,
             completed_on: None,
             completed_before: None,
-            completed_after: Some("2025-01-20".to_string()),
+            completed_after: Some(date("2025-01-20").into()),
             tags: None,
             exclude_tags: None,
+            no_due_date: false,
+            include_quoted: false,
+            assignee: None,
+            unassigned: false,
+            recurring: false,
+            path_glob: None,
+            text: None,
+            text_regex: false,
+            heading: None,
+            blocked: None,
+            path_includes: None,
+            path_root: None,
         };
 
         let filtered = filter_tasks(tasks, &options);
@@ -462,14 +1325,32 @@ mod tests {
 
         let options = FilterOptions {
             status: None,
+            priority: None,
+            min_priority: None,
             due_on: None,
             due_before: None,
             due_after: None,
+            scheduled_on: None,
+            scheduled_before: None,
+            scheduled_after: None,
+            starts_after: None,
             completed_on: None,
             completed_before: None,
             completed_after: None,
             tags: Some(vec!["work".to_string()]),
             exclude_tags: None,
+            no_due_date: false,
+            include_quoted: false,
+            assignee: None,
+            unassigned: false,
+            recurring: false,
+            path_glob: None,
+            text: None,
+            text_regex: false,
+            heading: None,
+            blocked: None,
+            path_includes: None,
+            path_root: None,
         };
 
         let filtered = filter_tasks(tasks, &options);
@@ -491,14 +1372,32 @@ mod tests {
 
         let options = FilterOptions {
             status: None,
+            priority: None,
+            min_priority: None,
             due_on: None,
             due_before: None,
             due_after: None,
+            scheduled_on: None,
+            scheduled_before: None,
+            scheduled_after: None,
+            starts_after: None,
             completed_on: None,
             completed_before: None,
             completed_after: None,
             tags: Some(vec!["work".to_string(), "urgent".to_string()]),
             exclude_tags: None,
+            no_due_date: false,
+            include_quoted: false,
+            assignee: None,
+            unassigned: false,
+            recurring: false,
+            path_glob: None,
+            text: None,
+            text_regex: false,
+            heading: None,
+            blocked: None,
+            path_includes: None,
+            path_root: None,
         };
 
         let filtered = filter_tasks(tasks, &options);
@@ -515,14 +1414,32 @@ mod tests {
 
         let options = FilterOptions {
             status: None,
+            priority: None,
+            min_priority: None,
             due_on: None,
             due_before: None,
             due_after: None,
+            scheduled_on: None,
+            scheduled_before: None,
+            scheduled_after: None,
+            starts_after: None,
             completed_on: None,
             completed_before: None,
             completed_after: None,
             tags: Some(vec!["urgent".to_string()]),
             exclude_tags: None,
+            no_due_date: false,
+            include_quoted: false,
+            assignee: None,
+            unassigned: false,
+            recurring: false,
+            path_glob: None,
+            text: None,
+            text_regex: false,
+            heading: None,
+            blocked: None,
+            path_includes: None,
+            path_root: None,
         };
 
         let filtered = filter_tasks(tasks, &options);
@@ -539,14 +1456,32 @@ mod tests {
 
         let options = FilterOptions {
             status: None,
+            priority: None,
+            min_priority: None,
             due_on: None,
             due_before: None,
             due_after: None,
+            scheduled_on: None,
+            scheduled_before: None,
+            scheduled_after: None,
+            starts_after: None,
             completed_on: None,
             completed_before: None,
             completed_after: None,
             tags: None,
             exclude_tags: Some(vec!["work".to_string()]),
+            no_due_date: false,
+            include_quoted: false,
+            assignee: None,
+            unassigned: false,
+            recurring: false,
+            path_glob: None,
+            text: None,
+            text_regex: false,
+            heading: None,
+            blocked: None,
+            path_includes: None,
+            path_root: None,
         };
 
         let filtered = filter_tasks(tasks, &options);
@@ -565,14 +1500,32 @@ mod tests {
 
         let options = FilterOptions {
             status: None,
+            priority: None,
+            min_priority: None,
             due_on: None,
             due_before: None,
             due_after: None,
+            scheduled_on: None,
+            scheduled_before: None,
+            scheduled_after: None,
+            starts_after: None,
             completed_on: None,
             completed_before: None,
             completed_after: None,
             tags: None,
             exclude_tags: Some(vec!["work".to_string(), "urgent".to_string()]),
+            no_due_date: false,
+            include_quoted: false,
+            assignee: None,
+            unassigned: false,
+            recurring: false,
+            path_glob: None,
+            text: None,
+            text_regex: false,
+            heading: None,
+            blocked: None,
+            path_includes: None,
+            path_root: None,
         };
 
         let filtered = filter_tasks(tasks, &options);
@@ -590,7 +1543,13 @@ mod tests {
                 None,
                 vec!["work"],
             ),
-            create_test_task("Task 2", "complete", Some("2025-01-20"), None, vec!["work"]),
+            create_test_task(
+                "Task 2",
+                "completed",
+                Some("2025-01-20"),
+                None,
+                vec!["work"],
+            ),
             create_test_task(
                 "Task 3",
                 "incomplete",
@@ -608,20 +1567,38 @@ mod tests {
         ];
 
         let options = FilterOptions {
-            status: Some("incomplete".to_string()),
+            status: Some(TaskStatus::Incomplete),
+            priority: None,
+            min_priority: None,
             due_on: None,
-            due_before: Some("2025-01-28".to_string()),
-            due_after: Some("2025-01-12".to_string()),
+            due_before: Some(date("2025-01-28").into()),
+            due_after: Some(date("2025-01-12").into()),
+            scheduled_on: None,
+            scheduled_before: None,
+            scheduled_after: None,
+            starts_after: None,
             completed_on: None,
             completed_before: None,
             completed_after: None,
             tags: Some(vec!["work".to_string()]),
             exclude_tags: None,
+            no_due_date: false,
+            include_quoted: false,
+            assignee: None,
+            unassigned: false,
+            recurring: false,
+            path_glob: None,
+            text: None,
+            text_regex: false,
+            heading: None,
+            blocked: None,
+            path_includes: None,
+            path_root: None,
         };
 
         let filtered = filter_tasks(tasks, &options);
         assert_eq!(filtered.len(), 2);
-        assert!(filtered.iter().all(|t| t.status == "incomplete"));
+        assert!(filtered.iter().all(|t| t.status == TaskStatus::Incomplete));
         assert!(
             filtered
                 .iter()
@@ -639,14 +1616,32 @@ mod tests {
 
         let options = FilterOptions {
             status: None,
+            priority: None,
+            min_priority: None,
             due_on: None,
             due_before: None,
             due_after: None,
+            scheduled_on: None,
+            scheduled_before: None,
+            scheduled_after: None,
+            starts_after: None,
             completed_on: None,
             completed_before: None,
             completed_after: None,
             tags: Some(vec!["work".to_string()]),
             exclude_tags: Some(vec!["blocked".to_string()]),
+            no_due_date: false,
+            include_quoted: false,
+            assignee: None,
+            unassigned: false,
+            recurring: false,
+            path_glob: None,
+            text: None,
+            text_regex: false,
+            heading: None,
+            blocked: None,
+            path_includes: None,
+            path_root: None,
         };
 
         let filtered = filter_tasks(tasks, &options);
@@ -662,4 +1657,227 @@ mod tests {
                 .all(|t| !t.tags.contains(&"blocked".to_string()))
         );
     }
+
+    #[test]
+    fn test_quoted_tasks_excluded_by_default() {
+        let mut quoted = create_test_task("Quoted", "incomplete", None, None, vec![]);
+        quoted.quoted = true;
+        let tasks = vec![
+            quoted,
+            create_test_task("Plain", "incomplete", None, None, vec![]),
+        ];
+
+        let filtered = filter_tasks(tasks.clone(), &FilterOptions::default());
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].content, "Plain");
+
+        let options = FilterOptions {
+            include_quoted: true,
+            ..Default::default()
+        };
+        assert_eq!(filter_tasks(tasks, &options).len(), 2);
+    }
+
+    #[test]
+    fn test_assignee_filters() {
+        let mut alice = create_test_task("Alice's", "incomplete", None, None, vec![]);
+        alice.assignee = Some("Alice".to_string());
+        let mut bob = create_test_task("Bob's", "incomplete", None, None, vec![]);
+        bob.assignee = Some("bob".to_string());
+        let tasks = vec![
+            alice,
+            bob,
+            create_test_task("Nobody's", "incomplete", None, None, vec![]),
+        ];
+
+        let options = FilterOptions {
+            assignee: Some("@alice".to_string()),
+            ..Default::default()
+        };
+        let filtered = filter_tasks(tasks.clone(), &options);
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].content, "Alice's");
+
+        let options = FilterOptions {
+            unassigned: true,
+            ..Default::default()
+        };
+        let filtered = filter_tasks(tasks, &options);
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].content, "Nobody's");
+    }
+
+    #[test]
+    fn test_blocked_filter() {
+        let mut waiting = create_test_task("Publish", "incomplete", None, None, vec![]);
+        waiting.blocked = true;
+        let tasks = vec![
+            waiting,
+            create_test_task("Draft", "incomplete", None, None, vec![]),
+        ];
+
+        let matching = |blocked| {
+            let options = FilterOptions {
+                blocked: Some(blocked),
+                ..Default::default()
+            };
+            filter_tasks(tasks.clone(), &options)
+                .into_iter()
+                .map(|t| t.content)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(matching(true), vec!["Publish"]);
+        assert_eq!(matching(false), vec!["Draft"]);
+        assert_eq!(filter_tasks(tasks, &FilterOptions::default()).len(), 2);
+    }
+
+    #[test]
+    fn test_recurring_filter() {
+        let mut weekly = create_test_task("Weekly review", "incomplete", None, None, vec![]);
+        weekly.recurrence = Some("every week".to_string());
+        let tasks = vec![
+            weekly,
+            create_test_task("One-off", "incomplete", None, None, vec![]),
+        ];
+
+        let options = FilterOptions {
+            recurring: true,
+            path_glob: None,
+            text: None,
+            text_regex: false,
+            heading: None,
+            ..Default::default()
+        };
+        let filtered = filter_tasks(tasks.clone(), &options);
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].content, "Weekly review");
+
+        assert_eq!(filter_tasks(tasks, &FilterOptions::default()).len(), 2);
+    }
+
+    #[test]
+    fn test_tags_match_regardless_of_case_and_normalization() {
+        let tasks = vec![
+            create_test_task("Composed", "incomplete", None, None, vec!["caf\u{e9}"]),
+            create_test_task("Decomposed", "incomplete", None, None, vec!["Cafe\u{301}"]),
+            create_test_task("Plain", "incomplete", None, None, vec!["cafe"]),
+        ];
+
+        let options = FilterOptions {
+            tags: Some(vec!["CAF\u{c9}".to_string()]),
+            ..Default::default()
+        };
+        let filtered = filter_tasks(tasks.clone(), &options);
+        assert_eq!(filtered.len(), 2);
+        assert!(filtered.iter().all(|t| t.content != "Plain"));
+
+        let options = FilterOptions {
+            exclude_tags: Some(vec!["caf\u{e9}".to_string()]),
+            ..Default::default()
+        };
+        let filtered = filter_tasks(tasks, &options);
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].content, "Plain");
+    }
+
+    #[test]
+    fn test_scheduled_and_start_filters() {
+        let mut planned = create_test_task("Planned", "incomplete", None, None, vec![]);
+        planned.scheduled_date = Some(date("2025-01-15"));
+        planned.start_date = Some(date("2025-01-10"));
+        let mut later = create_test_task("Later", "incomplete", None, None, vec![]);
+        later.scheduled_date = Some(date("2025-01-20"));
+        later.start_date = Some(date("2025-01-18"));
+        let tasks = vec![
+            planned,
+            later,
+            create_test_task("Unplanned", "incomplete", None, None, vec![]),
+        ];
+
+        let contents = |options: FilterOptions| -> Vec<String> {
+            filter_tasks(tasks.clone(), &options)
+                .into_iter()
+                .map(|t| t.content)
+                .collect()
+        };
+
+        assert_eq!(
+            contents(FilterOptions {
+                scheduled_on: Some(date("2025-01-15")),
+                ..Default::default()
+            }),
+            vec!["Planned"]
+        );
+        assert_eq!(
+            contents(FilterOptions {
+                scheduled_before: Some(date("2025-01-20")),
+                ..Default::default()
+            }),
+            vec!["Planned"]
+        );
+        assert_eq!(
+            contents(FilterOptions {
+                scheduled_after: Some(date("2025-01-15")),
+                ..Default::default()
+            }),
+            vec!["Later"]
+        );
+        assert_eq!(
+            contents(FilterOptions {
+                starts_after: Some(date("2025-01-09")),
+                ..Default::default()
+            }),
+            vec!["Planned", "Later"]
+        );
+        assert_eq!(
+            contents(FilterOptions {
+                starts_after: Some(date("2025-01-10")),
+                ..Default::default()
+            }),
+            vec!["Later"]
+        );
+    }
+
+    #[test]
+    fn test_priority_filters() {
+        let with_priority = |content: &str, priority: Option<Priority>| {
+            let mut task = create_test_task(content, "incomplete", None, None, vec![]);
+            task.priority = priority;
+            task
+        };
+        let tasks = vec![
+            with_priority("Urgent", Some(Priority::Urgent)),
+            with_priority("High", Some(Priority::High)),
+            with_priority("Low", Some(Priority::Low)),
+            with_priority("None", None),
+        ];
+        let contents = |options: FilterOptions| -> Vec<String> {
+            filter_tasks(tasks.clone(), &options)
+                .into_iter()
+                .map(|t| t.content)
+                .collect()
+        };
+
+        assert_eq!(
+            contents(FilterOptions {
+                priority: Some(Priority::High),
+                ..Default::default()
+            }),
+            vec!["High"]
+        );
+        assert_eq!(
+            contents(FilterOptions {
+                min_priority: Some(Priority::High),
+                ..Default::default()
+            }),
+            vec!["Urgent", "High"]
+        );
+        assert_eq!(
+            contents(FilterOptions {
+                min_priority: Some(Priority::Lowest),
+                ..Default::default()
+            }),
+            vec!["Urgent", "High", "Low"]
+        );
+    }
 }
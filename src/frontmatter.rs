@@ -0,0 +1,218 @@
+//! YAML frontmatter splitting and editing
+//!
+//! Readers split a note into its leading `---` block and body with
+//! `frontmatter_block()` and `body_after_frontmatter()`. Writers go through
+//! [`set_property`], which rewrites the lines of a single top-level key and
+//! leaves every other byte of the note as it was, so comments, key order,
+//! and quoting elsewhere in the frontmatter survive an edit.
+
+use serde_yaml::{Mapping, Value};
+
+/// Return the note body with any leading YAML frontmatter removed
+pub(crate) fn body_after_frontmatter(content: &str) -> &str {
+    let mut lines = content.split_inclusive('\n');
+    match lines.next() {
+        Some(first) if first.trim() == "---" => {}
+        _ => return content,
+    }
+
+    let mut offset = content.split_inclusive('\n').next().map_or(0, str::len);
+    for line in lines {
+        offset += line.len();
+        if line.trim() == "---" {
+            return &content[offset..];
+        }
+    }
+
+    // Unterminated frontmatter is treated as regular content
+    content
+}
+
+/// Number of lines taken up by a note's leading frontmatter, 0 if it has none
+///
+/// Line-based extractors skip these lines but keep counting from the top of
+/// the file, so reported line numbers still match the note.
+pub(crate) fn frontmatter_line_count(content: &str) -> usize {
+    let body = body_after_frontmatter(content);
+    content[..content.len() - body.len()].lines().count()
+}
+
+/// Return the raw YAML between a note's leading `---` delimiters, if any
+pub(crate) fn frontmatter_block(content: &str) -> Option<&str> {
+    let body = body_after_frontmatter(content);
+    if body.len() == content.len() {
+        return None;
+    }
+
+    let block = &content[..content.len() - body.len()];
+    let start = block.find('\n').map_or(block.len(), |i| i + 1);
+    let end = block.trim_end().rfind('\n').unwrap_or(start).max(start);
+    Some(&block[start..end])
+}
+
+/// Parse a note's frontmatter as a YAML mapping
+///
+/// A note without frontmatter, or with an empty block, yields an empty mapping.
+pub fn parse_frontmatter(content: &str) -> Result<Mapping, String> {
+    let Some(block) = frontmatter_block(content) else {
+        return Ok(Mapping::new());
+    };
+    match serde_yaml::from_str(block) {
+        Ok(Value::Mapping(mapping)) => Ok(mapping),
+        Ok(Value::Null) => Ok(Mapping::new()),
+        Ok(_) => Err("Frontmatter is not a YAML mapping".to_string()),
+        Err(e) => Err(format!("Invalid frontmatter YAML: {}", e)),
+    }
+}
+
+/// The top-level key a frontmatter line starts, if it starts one
+fn line_key(line: &str) -> Option<&str> {
+    if line.starts_with([' ', '\t', '-', '#']) {
+        return None;
+    }
+    let line = line.trim_end();
+    let (key, rest) = match line.chars().next()? {
+        quote @ ('"' | '\'') => {
+            let end = line[1..].find(quote)? + 1;
+            (&line[1..end], line[end + 1..].trim_start())
+        }
+        _ => {
+            let colon = line.find(':')?;
+            (line[..colon].trim_end(), &line[colon..])
+        }
+    };
+    let value = rest.strip_prefix(':')?;
+    (value.is_empty() || value.starts_with([' ', '\t'])).then_some(key)
+}
+
+/// Whether a line continues the value of the key above it
+///
+/// Indented lines, blank lines, and block sequence items (`- item`) at the
+/// start of the line all belong to the preceding key.
+fn continues_value(line: &str) -> bool {
+    line.trim().is_empty() || line.starts_with([' ', '\t', '-'])
+}
+
+/// Set a top-level frontmatter key, returning the updated note
+///
+/// Only the lines holding `key` change: an existing entry (including an
+/// indented or list value below it) is replaced in place, a new key is
+/// appended before the closing `---`, and a note without frontmatter gets a
+/// new block. The note's line endings are kept. The result is parsed back to
+/// confirm that no other key changed; edits that can't be made that way
+/// (duplicate keys, multi-line flow values) fail instead of rewriting the
+/// frontmatter.
+pub fn set_property(content: &str, key: &str, value: &Value) -> Result<String, String> {
+    if key.trim().is_empty() || key.contains(['\n', '\r']) {
+        return Err(format!("Invalid property name '{}'", key));
+    }
+
+    let newline = if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let mut entry = Mapping::new();
+    entry.insert(Value::String(key.to_string()), value.clone());
+    let entry = serde_yaml::to_string(&entry)
+        .map_err(|e| format!("Failed to serialize property: {}", e))?
+        .replace('\n', newline);
+
+    let count = frontmatter_line_count(content);
+    if count == 0 {
+        return Ok(format!("---{0}{1}---{0}{2}", newline, entry, content));
+    }
+
+    let mut expected = parse_frontmatter(content)?;
+    expected.insert(Value::String(key.to_string()), value.clone());
+
+    // Lines 1..close hold the YAML; `close` is the closing delimiter
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
+    let close = count - 1;
+    let (start, end) = match (1..close).find(|&i| line_key(lines[i]) == Some(key)) {
+        Some(start) => {
+            let mut end = start + 1;
+            while end < close && continues_value(lines[end]) {
+                end += 1;
+            }
+            // Blank lines after the value stay where they are
+            while end > start + 1 && lines[end - 1].trim().is_empty() {
+                end -= 1;
+            }
+            (start, end)
+        }
+        None => (close, close),
+    };
+
+    let mut updated = lines[..start].concat();
+    updated.push_str(&entry);
+    updated.push_str(&lines[end..].concat());
+
+    if parse_frontmatter(&updated)? != expected {
+        return Err(format!(
+            "Could not update '{}' without changing other frontmatter",
+            key
+        ));
+    }
+    Ok(updated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(content: &str, key: &str, value: &str) -> Result<String, String> {
+        set_property(content, key, &serde_yaml::from_str(value).unwrap())
+    }
+
+    #[test]
+    fn test_set_property_preserves_other_lines() {
+        let note = "---\ntitle: \"Plan\"   # keep me\ntags:\n  - a\n  - b\n\nstatus: open\n---\n# Body\n---\n";
+
+        assert_eq!(
+            set(note, "status", "done").unwrap(),
+            "---\ntitle: \"Plan\"   # keep me\ntags:\n  - a\n  - b\n\nstatus: done\n---\n# Body\n---\n"
+        );
+        assert_eq!(
+            set(note, "tags", "[a, b, c]").unwrap(),
+            "---\ntitle: \"Plan\"   # keep me\ntags:\n- a\n- b\n- c\n\nstatus: open\n---\n# Body\n---\n"
+        );
+        assert_eq!(
+            set(note, "priority", "2").unwrap(),
+            "---\ntitle: \"Plan\"   # keep me\ntags:\n  - a\n  - b\n\nstatus: open\npriority: 2\n---\n# Body\n---\n"
+        );
+        // Values that would read as another type are quoted
+        assert_eq!(
+            set(note, "title", "'true'").unwrap(),
+            "---\ntitle: 'true'\ntags:\n  - a\n  - b\n\nstatus: open\n---\n# Body\n---\n"
+        );
+    }
+
+    #[test]
+    fn test_set_property_creates_frontmatter() {
+        assert_eq!(
+            set("# Note\r\nBody\r\n", "status", "done").unwrap(),
+            "---\r\nstatus: done\r\n---\r\n# Note\r\nBody\r\n"
+        );
+        assert_eq!(set("", "a", "1").unwrap(), "---\na: 1\n---\n");
+        assert_eq!(set("---\n---", "a", "1").unwrap(), "---\na: 1\n---");
+        assert_eq!(
+            set("---\r\nb: 2\r\n---\r\n", "a", "x").unwrap(),
+            "---\r\nb: 2\r\na: x\r\n---\r\n"
+        );
+    }
+
+    #[test]
+    fn test_set_property_refuses_ambiguous_edits() {
+        // Duplicate keys can't be edited without dropping one
+        assert!(set("---\na: 1\na: 2\n---\n", "a", "3").is_err());
+        assert!(set("---\n- a\n---\n", "a", "1").is_err());
+        assert!(set("---\na: [1,\n2]\n---\n", "a", "3").is_err());
+        assert!(set("---\na: 1\n---\n", " ", "3").is_err());
+
+        assert_eq!(line_key("\"my key\": 1"), Some("my key"));
+        assert_eq!(line_key("url: https://example.com"), Some("url"));
+        assert_eq!(line_key("note:text"), None);
+        assert_eq!(line_key("  nested: 1"), None);
+    }
+}
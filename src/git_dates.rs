@@ -0,0 +1,435 @@
+//! Task and note dates derived from git history
+//!
+//! Enabled with `git_dates = true` in the config. Explicit `➕`/`✅` markers
+//! always win; git is only consulted for tasks that lack them:
+//!
+//! - `completed_date`: the last commit touching the line (`git blame`), i.e.
+//!   when the checkbox was ticked
+//! - `created_date`: the first commit touching the line (`git log -L`)
+//!
+//! Lines that aren't committed yet are left alone. The `git` executable must be
+//! on `PATH`; if it's missing or the vault isn't a repository, dates are left
+//! unchanged.
+
+use crate::extractor::{Task, TaskStatus};
+use chrono::{DateTime, FixedOffset, NaiveDate, Utc};
+use rayon::prelude::*;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Attribution of a working-tree line to the commit that last touched it
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineOrigin {
+    /// Commit that last touched the line
+    pub commit: String,
+    /// Line number in that commit's version of the file
+    pub original_line: usize,
+    /// File path in that commit, relative to the repository root
+    pub original_path: String,
+    /// Author date of the commit, in the author's timezone
+    pub date: NaiveDate,
+}
+
+/// Handle on the git repository containing a vault
+#[derive(Debug, Clone)]
+pub struct GitRepo {
+    root: PathBuf,
+}
+
+impl GitRepo {
+    /// Find the repository containing `path`, if any
+    pub fn discover(path: &Path) -> Option<Self> {
+        let dir = if path.is_file() { path.parent()? } else { path };
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["rev-parse", "--show-toplevel"])
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+
+        let root = String::from_utf8(output.stdout).ok()?;
+        Some(Self {
+            root: PathBuf::from(root.trim()),
+        })
+    }
+
+    fn git(&self) -> Command {
+        let mut command = Command::new("git");
+        command.arg("-C").arg(&self.root);
+        command
+    }
+
+    /// Path relative to the repository root, which `git` reports canonicalized
+    fn relative(&self, file: &Path) -> PathBuf {
+        file.canonicalize()
+            .ok()
+            .and_then(|path| Some(path.strip_prefix(&self.root).ok()?.to_path_buf()))
+            .unwrap_or_else(|| file.to_path_buf())
+    }
+
+    /// Blame every line of the working-tree file, indexed by 1-based line number
+    ///
+    /// Uncommitted lines are omitted.
+    pub fn blame(&self, file: &Path) -> Option<HashMap<usize, LineOrigin>> {
+        let output = self
+            .git()
+            .args(["blame", "--porcelain", "--"])
+            .arg(self.relative(file))
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+
+        Some(parse_blame_porcelain(&String::from_utf8_lossy(
+            &output.stdout,
+        )))
+    }
+
+    /// Author date of the first commit that touched a line
+    ///
+    /// The line is identified as it appears in `origin.commit`, which lets this
+    /// follow the line back through history even if the working tree has moved
+    /// it since.
+    pub fn first_touched(&self, origin: &LineOrigin) -> Option<NaiveDate> {
+        let output = self
+            .git()
+            .arg("log")
+            .arg(format!(
+                "-L{0},{0}:{1}",
+                origin.original_line, origin.original_path
+            ))
+            .args(["-s", "--format=%ad", "--date=short"])
+            .arg(&origin.commit)
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+
+        // Commits are listed newest first
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| NaiveDate::parse_from_str(line.trim(), "%Y-%m-%d").ok())
+            .next_back()
+    }
+
+    /// Time of the last commit touching a file
+    pub fn last_modified(&self, file: &Path) -> Option<DateTime<Utc>> {
+        let output = self
+            .git()
+            .args(["log", "-1", "--format=%ct", "--"])
+            .arg(self.relative(file))
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+
+        let timestamp = String::from_utf8_lossy(&output.stdout)
+            .trim()
+            .parse()
+            .ok()?;
+        DateTime::from_timestamp(timestamp, 0)
+    }
+
+    /// Contents of a file at a revision (`HEAD`, or `""` for the index)
+    ///
+    /// Returns `None` if the file doesn't exist there.
+    pub fn show(&self, rev: &str, file: &Path) -> Option<String> {
+        let output = self
+            .git()
+            .arg("show")
+            .arg(format!("{}:{}", rev, self.relative(file).display()))
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+
+        Some(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
+
+/// Parse `git blame --porcelain` output into line origins keyed by final line
+fn parse_blame_porcelain(output: &str) -> HashMap<usize, LineOrigin> {
+    // Commit metadata is only printed the first time a commit appears
+    let mut dates: HashMap<String, NaiveDate> = HashMap::new();
+    let mut paths: HashMap<String, String> = HashMap::new();
+    let mut pending: Vec<(String, usize, usize)> = Vec::new();
+
+    let mut current: Option<(String, usize, usize)> = None;
+    let mut author_time: Option<i64> = None;
+
+    for line in output.lines() {
+        if line.starts_with('\t') {
+            // Content line ends the entry
+            if let Some(entry) = current.take() {
+                pending.push(entry);
+            }
+            continue;
+        }
+
+        let mut parts = line.split(' ');
+        let key = parts.next().unwrap_or_default();
+
+        if key.len() == 40 && key.bytes().all(|b| b.is_ascii_hexdigit()) {
+            let original = parts.next().and_then(|n| n.parse().ok());
+            let final_line = parts.next().and_then(|n| n.parse().ok());
+            if let (Some(original), Some(final_line)) = (original, final_line) {
+                current = Some((key.to_string(), original, final_line));
+            }
+            continue;
+        }
+
+        let Some((commit, _, _)) = &current else {
+            continue;
+        };
+        let value = line[key.len()..].trim_start();
+        match key {
+            "author-time" => author_time = value.parse().ok(),
+            "author-tz" => {
+                if let Some(date) = author_time
+                    .and_then(|t| DateTime::from_timestamp(t, 0))
+                    .zip(parse_tz(value))
+                    .map(|(time, tz)| time.with_timezone(&tz).date_naive())
+                {
+                    dates.insert(commit.clone(), date);
+                }
+            }
+            "filename" => {
+                paths.insert(commit.clone(), value.to_string());
+            }
+            _ => {}
+        }
+    }
+
+    pending
+        .into_iter()
+        .filter(|(commit, _, _)| commit.bytes().any(|b| b != b'0'))
+        .filter_map(|(commit, original_line, final_line)| {
+            let origin = LineOrigin {
+                date: *dates.get(&commit)?,
+                original_path: paths.get(&commit)?.clone(),
+                original_line,
+                commit,
+            };
+            Some((final_line, origin))
+        })
+        .collect()
+}
+
+/// Parse a git timezone offset such as `+0100` or `-0530`
+fn parse_tz(value: &str) -> Option<FixedOffset> {
+    let (sign, digits) = match value.split_at_checked(1)? {
+        ("+", digits) => (1, digits),
+        ("-", digits) => (-1, digits),
+        _ => return None,
+    };
+    if digits.len() != 4 {
+        return None;
+    }
+    let hours: i32 = digits[..2].parse().ok()?;
+    let minutes: i32 = digits[2..].parse().ok()?;
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+fn needs_dates(task: &Task) -> bool {
+    task.created_date.is_none()
+        || (task.status == TaskStatus::Completed && task.completed_date.is_none())
+}
+
+/// Fill missing created/completed dates on tasks from git history
+///
+/// Tasks outside a git repository are returned unchanged.
+pub fn fill_task_dates(base_path: &Path, tasks: &mut [Task]) {
+    if !tasks.iter().any(needs_dates) {
+        return;
+    }
+    if let Some(repo) = GitRepo::discover(base_path) {
+        fill_task_dates_in(&repo, tasks);
+    }
+}
+
+/// Fill missing created/completed dates on tasks from the history of an
+/// already discovered `repo`
+pub fn fill_task_dates_in(repo: &GitRepo, tasks: &mut [Task]) {
+    let mut by_file: BTreeMap<String, Vec<&mut Task>> = BTreeMap::new();
+    for task in tasks.iter_mut().filter(|t| needs_dates(t)) {
+        by_file
+            .entry(task.file_path.clone())
+            .or_default()
+            .push(task);
+    }
+
+    by_file.into_par_iter().for_each(|(file_path, file_tasks)| {
+        let Some(blame) = repo.blame(Path::new(&file_path)) else {
+            return;
+        };
+
+        for task in file_tasks {
+            let Some(origin) = blame.get(&task.line_number) else {
+                continue;
+            };
+            if task.status == TaskStatus::Completed && task.completed_date.is_none() {
+                task.completed_date = Some(origin.date);
+            }
+            if task.created_date.is_none() {
+                task.created_date = repo.first_touched(origin);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::extractor::TaskExtractor;
+    use std::fs;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str], date: &str) {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(args)
+            .env("GIT_AUTHOR_DATE", date)
+            .env("GIT_COMMITTER_DATE", date)
+            .env("GIT_AUTHOR_NAME", "Test")
+            .env("GIT_AUTHOR_EMAIL", "test@example.com")
+            .env("GIT_COMMITTER_NAME", "Test")
+            .env("GIT_COMMITTER_EMAIL", "test@example.com")
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    fn commit(dir: &Path, date: &str) {
+        git(dir, &["add", "-A"], date);
+        git(dir, &["commit", "-q", "-m", "update"], date);
+    }
+
+    fn date(s: &str) -> Option<NaiveDate> {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").ok()
+    }
+
+    #[test]
+    fn test_parse_tz() {
+        assert_eq!(parse_tz("+0000"), FixedOffset::east_opt(0));
+        assert_eq!(parse_tz("-0530"), FixedOffset::west_opt(5 * 3600 + 1800));
+        assert_eq!(parse_tz("0100"), None);
+    }
+
+    #[test]
+    fn test_parse_blame_porcelain() {
+        let sha_a = "a".repeat(40);
+        let sha_b = "b".repeat(40);
+        let uncommitted = "0".repeat(40);
+        let output = format!(
+            "{sha_a} 1 1 2\nauthor-time 1704067200\nauthor-tz -0500\nfilename old.md\n\tone\n\
+             {sha_a} 2 2\n\ttwo\n\
+             {sha_b} 3 3 1\nauthor-time 1706781600\nauthor-tz +0000\nfilename notes.md\n\tthree\n\
+             {uncommitted} 4 4 1\nauthor-time 1706781600\nauthor-tz +0000\nfilename notes.md\n\tfour\n"
+        );
+
+        let blame = parse_blame_porcelain(&output);
+        assert_eq!(blame.len(), 3);
+        // 2024-01-01T00:00Z is still Dec 31 in UTC-5
+        assert_eq!(blame[&1].date, date("2023-12-31").unwrap());
+        assert_eq!(blame[&2].original_path, "old.md");
+        assert_eq!(blame[&2].original_line, 2);
+        assert_eq!(blame[&3].commit, sha_b);
+        assert_eq!(blame[&3].date, date("2024-02-01").unwrap());
+        assert!(!blame.contains_key(&4));
+    }
+
+    #[test]
+    fn test_fill_task_dates_from_history() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        git(vault, &["init", "-q"], "2024-01-01T10:00:00+0000");
+
+        fs::write(
+            vault.join("tasks.md"),
+            "- [ ] Write report\n- [ ] Explicit ➕ 2023-06-01\n- [ ] Send invoice\n",
+        )
+        .unwrap();
+        commit(vault, "2024-01-01T10:00:00+0000");
+
+        fs::write(
+            vault.join("tasks.md"),
+            "# Tasks\n- [ ] Write report\n- [ ] Explicit ➕ 2023-06-01\n- [x] Send invoice\n",
+        )
+        .unwrap();
+        commit(vault, "2024-02-01T10:00:00+0000");
+
+        // Uncommitted task: no history to derive dates from
+        fs::write(
+            vault.join("tasks.md"),
+            "# Tasks\n- [ ] Write report\n- [ ] Explicit ➕ 2023-06-01\n- [x] Send invoice\n- [ ] New\n",
+        )
+        .unwrap();
+
+        let config = Arc::new(Config {
+            git_dates: true,
+            ..Default::default()
+        });
+        let (mut tasks, _) = TaskExtractor::new(Arc::clone(&config))
+            .extract_tasks_with_warnings(vault)
+            .unwrap();
+        tasks.sort_by_key(|t| t.line_number);
+
+        assert_eq!(tasks[0].content, "Write report");
+        assert_eq!(tasks[0].created_date, date("2024-01-01"));
+        assert_eq!(tasks[0].completed_date, None);
+
+        assert_eq!(tasks[1].created_date, date("2023-06-01"));
+
+        assert_eq!(tasks[2].content, "Send invoice");
+        assert_eq!(tasks[2].created_date, date("2024-01-01"));
+        assert_eq!(tasks[2].completed_date, date("2024-02-01"));
+
+        assert_eq!(tasks[3].created_date, None);
+
+        // Tasks handed to a scan callback already have their dates
+        let observed = std::sync::Mutex::new(Vec::new());
+        let on_file = |file_tasks: Vec<Task>| observed.lock().unwrap().extend(file_tasks);
+        let (kept, _) = TaskExtractor::new(config)
+            .extract_tasks_observed(vault, Some(&on_file))
+            .unwrap();
+        assert!(kept.is_empty());
+        let mut observed = observed.into_inner().unwrap();
+        observed.sort_by_key(|t| t.line_number);
+        let dates = |tasks: &[Task]| -> Vec<_> {
+            tasks
+                .iter()
+                .map(|t| (t.created_date, t.completed_date))
+                .collect()
+        };
+        assert_eq!(dates(&observed), dates(&tasks));
+    }
+
+    #[test]
+    fn test_last_modified() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        git(vault, &["init", "-q"], "2024-01-01T10:00:00+0000");
+        fs::write(vault.join("note.md"), "# Note\n").unwrap();
+        commit(vault, "2024-03-01T12:00:00+0000");
+
+        let repo = GitRepo::discover(vault).unwrap();
+        assert_eq!(
+            repo.last_modified(&vault.join("note.md"))
+                .unwrap()
+                .to_rfc3339(),
+            "2024-03-01T12:00:00+00:00"
+        );
+        assert_eq!(repo.last_modified(&vault.join("missing.md")), None);
+    }
+}
@@ -0,0 +1,534 @@
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::Instant;
+
+use prost::bytes::Buf;
+use tonic::codec::{Codec, DecodeBuf, Decoder, EncodeBuf, Encoder};
+use tonic::codegen::{BoxFuture, Context, Poll, Service, StdError, http};
+use tonic::metadata::MetadataMap;
+use tonic::server::{Grpc, NamedService, UnaryService};
+use tonic::service::Routes;
+use tonic::{Request, Response, Status};
+
+use crate::audit::{self, AuditEntry, Interface};
+use crate::auth;
+use crate::config::Config;
+use crate::error::Error;
+use crate::grpc_schema::{ProtoSchema, VAULT_PROTO};
+use crate::http_router::spawn_with_timeout;
+use crate::operation::Operation;
+
+/// Types and service stubs generated from `proto/markdown_todo_extractor.proto`
+pub mod proto {
+    tonic::include_proto!("markdown_todo_extractor");
+
+    /// Messages and client of the typed `Vault` service in `proto/vault.proto`
+    ///
+    /// The field comments are copied from the operation schemas, which aren't
+    /// written as rustdoc.
+    #[allow(rustdoc::all)]
+    pub mod vault {
+        tonic::include_proto!("markdown_todo_extractor.vault");
+    }
+}
+
+use proto::operations_server::{Operations, OperationsServer};
+use proto::{
+    ExecuteRequest, ExecuteResponse, ListOperationsRequest, ListOperationsResponse,
+    OperationDescriptor,
+};
+
+/// gRPC services that dispatch to the registered operations
+///
+/// Like the HTTP router, this is driven entirely by the operation registry.
+/// The `Operations` service calls operations by name with the same JSON
+/// documents the REST API uses. The typed `Vault` service has one RPC per
+/// operation, with messages built from the operations' schemas (see
+/// `grpc_schema.rs`), so every operation is available over gRPC without
+/// per-operation code.
+pub struct GrpcOperationService {
+    operations: Vec<Arc<dyn Operation>>,
+    by_name: HashMap<&'static str, Arc<dyn Operation>>,
+    /// Messages and RPCs of the typed service
+    schema: Arc<ProtoSchema>,
+    /// Configuration whose API keys calls must present, if any
+    config: Option<Arc<Config>>,
+}
+
+impl GrpcOperationService {
+    pub fn new(operations: Vec<Arc<dyn Operation>>) -> Self {
+        let by_name = operations
+            .iter()
+            .map(|op| (op.name(), Arc::clone(op)))
+            .collect();
+        let schema = Arc::new(ProtoSchema::build(&operations, VAULT_PROTO));
+
+        Self {
+            operations,
+            by_name,
+            schema,
+            config: None,
+        }
+    }
+
+    /// Require one of `config`'s API keys, if it has any, on every call
+    ///
+    /// gRPC calls reach the whole vault, so only unrestricted keys are
+    /// accepted. Keys are read from the `authorization` (`Bearer <key>`) or
+    /// `x-api-key` metadata.
+    pub fn with_api_keys(mut self, config: Arc<Config>) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    /// Check a call's metadata against the required API keys
+    fn check_api_key(&self, metadata: &MetadataMap) -> Result<(), Status> {
+        let Some(config) = &self.config else {
+            return Ok(());
+        };
+        match auth::authenticate(config, &metadata.clone().into_headers()) {
+            Ok(Some(key)) if !key.is_unrestricted() => Err(Status::permission_denied(format!(
+                "API key '{}' is limited to the REST API",
+                key.name
+            ))),
+            Ok(_) => Ok(()),
+            Err(e) => Err(to_status(e)),
+        }
+    }
+
+    /// Both services, ready to add to a tonic server
+    pub fn into_routes(self) -> Routes {
+        let service = Arc::new(self);
+        Routes::new(OperationsServer::from_arc(Arc::clone(&service)))
+            .add_service(VaultServer { service })
+    }
+
+    /// Run `operation` with JSON `params`, with the same timeout and audit
+    /// log entry as HTTP and MCP calls
+    async fn run(
+        &self,
+        operation: &str,
+        params: serde_json::Value,
+        metadata: &MetadataMap,
+    ) -> Result<serde_json::Value, Status> {
+        let client = metadata
+            .get("x-client-id")
+            .or_else(|| metadata.get("user-agent"))
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let handler = self
+            .by_name
+            .get(operation)
+            .ok_or_else(|| Status::not_found(format!("Unknown operation: {}", operation)))?;
+
+        let started = Instant::now();
+        let audit_params = audit::enabled().then(|| params.clone());
+        let handler = Arc::clone(handler);
+        let result = spawn_with_timeout(async move { handler.execute_json(params).await }).await;
+        if let Some(audit_params) = audit_params {
+            let mut entry = AuditEntry::new(
+                Interface::Grpc,
+                operation.to_string(),
+                audit_params,
+                started,
+            )
+            .outcome(&result);
+            entry.client = client;
+            audit::record(entry);
+        }
+        result.map_err(to_status)
+    }
+}
+
+/// Map an operation error onto a gRPC status
+fn to_status(error: Error) -> Status {
+    let message = error.to_string();
+    match error {
+        Error::InvalidParams(_) | Error::InvalidDate(_) | Error::ParseError(_) => {
+            Status::invalid_argument(message)
+        }
+        Error::NotFound(_) => Status::not_found(message),
+        Error::Forbidden(_) => Status::permission_denied(message),
+        Error::Unauthorized(_) => Status::unauthenticated(message),
+        Error::Conflict(_) => Status::failed_precondition(message),
+        Error::Unsupported(_) => Status::unimplemented(message),
+        Error::ScanLimit(_) => Status::resource_exhausted(message),
+        Error::Timeout(_) => Status::deadline_exceeded(message),
+        Error::IoError(_) | Error::Internal(_) => Status::internal(message),
+    }
+}
+
+#[tonic::async_trait]
+impl Operations for GrpcOperationService {
+    async fn list_operations(
+        &self,
+        request: Request<ListOperationsRequest>,
+    ) -> Result<Response<ListOperationsResponse>, Status> {
+        self.check_api_key(request.metadata())?;
+        let operations = self
+            .operations
+            .iter()
+            .map(|op| OperationDescriptor {
+                name: op.name().to_string(),
+                path: op.path().to_string(),
+                description: op.description().to_string(),
+                input_schema_json: op.input_schema().to_string(),
+                output_schema_json: op.output_schema().to_string(),
+            })
+            .collect();
+
+        Ok(Response::new(ListOperationsResponse { operations }))
+    }
+
+    async fn execute(
+        &self,
+        request: Request<ExecuteRequest>,
+    ) -> Result<Response<ExecuteResponse>, Status> {
+        self.check_api_key(request.metadata())?;
+        let (metadata, _, request) = request.into_parts();
+
+        if !self.by_name.contains_key(request.operation.as_str()) {
+            return Err(Status::not_found(format!(
+                "Unknown operation: {}",
+                request.operation
+            )));
+        }
+        let params = if request.params_json.trim().is_empty() {
+            serde_json::Value::Object(serde_json::Map::new())
+        } else {
+            serde_json::from_str(&request.params_json)
+                .map_err(|e| Status::invalid_argument(format!("Invalid params_json: {}", e)))?
+        };
+
+        let result = self.run(&request.operation, params, &metadata).await?;
+        Ok(Response::new(ExecuteResponse {
+            result_json: result.to_string(),
+        }))
+    }
+}
+
+/// The typed `Vault` service of `proto/vault.proto`
+///
+/// tonic only generates servers for fixed message types, so this routes
+/// `/markdown_todo_extractor.vault.Vault/<Rpc>` calls itself, with a codec
+/// that turns each RPC's messages into the operation's JSON documents.
+#[derive(Clone)]
+struct VaultServer {
+    service: Arc<GrpcOperationService>,
+}
+
+impl NamedService for VaultServer {
+    const NAME: &'static str = "markdown_todo_extractor.vault.Vault";
+}
+
+impl<B> Service<http::Request<B>> for VaultServer
+where
+    B: tonic::codegen::Body + Send + 'static,
+    B::Error: Into<StdError> + Send + 'static,
+{
+    type Response = http::Response<tonic::body::Body>;
+    type Error = Infallible;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: http::Request<B>) -> Self::Future {
+        let rpc = request
+            .uri()
+            .path()
+            .strip_prefix(&format!("/{}/", Self::NAME))
+            .and_then(|name| self.service.schema.rpc(name))
+            .cloned();
+        let service = Arc::clone(&self.service);
+        Box::pin(async move {
+            let Some(rpc) = rpc else {
+                return Ok(Status::unimplemented("Unknown RPC").into_http());
+            };
+            let codec = JsonCodec {
+                schema: Arc::clone(&service.schema),
+                request: rpc.request.clone(),
+                response: rpc.response.clone(),
+            };
+            let method = RpcCall {
+                service,
+                operation: rpc.operation,
+            };
+            Ok(Grpc::new(codec).unary(method, request).await)
+        })
+    }
+}
+
+/// One call of a typed RPC
+struct RpcCall {
+    service: Arc<GrpcOperationService>,
+    operation: String,
+}
+
+impl UnaryService<serde_json::Value> for RpcCall {
+    type Response = serde_json::Value;
+    type Future = BoxFuture<Response<serde_json::Value>, Status>;
+
+    fn call(&mut self, request: Request<serde_json::Value>) -> Self::Future {
+        let service = Arc::clone(&self.service);
+        let operation = self.operation.clone();
+        Box::pin(async move {
+            service.check_api_key(request.metadata())?;
+            let (metadata, _, params) = request.into_parts();
+            let result = service.run(&operation, params, &metadata).await?;
+            Ok(Response::new(result))
+        })
+    }
+}
+
+/// Codec between an RPC's protobuf messages and JSON documents
+struct JsonCodec {
+    schema: Arc<ProtoSchema>,
+    request: String,
+    response: String,
+}
+
+impl Codec for JsonCodec {
+    type Encode = serde_json::Value;
+    type Decode = serde_json::Value;
+    type Encoder = JsonEncoder;
+    type Decoder = JsonDecoder;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        JsonEncoder {
+            schema: Arc::clone(&self.schema),
+            message: self.response.clone(),
+        }
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        JsonDecoder {
+            schema: Arc::clone(&self.schema),
+            message: self.request.clone(),
+        }
+    }
+}
+
+struct JsonEncoder {
+    schema: Arc<ProtoSchema>,
+    message: String,
+}
+
+impl Encoder for JsonEncoder {
+    type Item = serde_json::Value;
+    type Error = Status;
+
+    fn encode(&mut self, item: Self::Item, dst: &mut EncodeBuf<'_>) -> Result<(), Self::Error> {
+        let mut buf = Vec::new();
+        self.schema
+            .encode(&self.message, &item, &mut buf)
+            .map_err(Status::internal)?;
+        prost::bytes::BufMut::put_slice(dst, &buf);
+        Ok(())
+    }
+}
+
+struct JsonDecoder {
+    schema: Arc<ProtoSchema>,
+    message: String,
+}
+
+impl Decoder for JsonDecoder {
+    type Item = serde_json::Value;
+    type Error = Status;
+
+    fn decode(&mut self, src: &mut DecodeBuf<'_>) -> Result<Option<Self::Item>, Self::Error> {
+        let bytes = src.copy_to_bytes(src.remaining());
+        self.schema
+            .decode(&self.message, &bytes)
+            .map(Some)
+            .map_err(Status::invalid_argument)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::capabilities::CapabilityRegistry;
+    use crate::config::Config;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn create_service() -> (TempDir, GrpcOperationService) {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("todo.md"),
+            "- [ ] Write report #work\n- [x] Send invoice\n",
+        )
+        .unwrap();
+
+        let registry =
+            CapabilityRegistry::new(temp_dir.path().to_path_buf(), Arc::new(Config::default()));
+        let service = GrpcOperationService::new(registry.create_operations());
+        (temp_dir, service)
+    }
+
+    #[tokio::test]
+    async fn test_list_operations_mirrors_registry() {
+        let (_temp_dir, service) = create_service();
+
+        let response = service
+            .list_operations(Request::new(ListOperationsRequest {}))
+            .await
+            .unwrap()
+            .into_inner();
+
+        let names: Vec<_> = response
+            .operations
+            .iter()
+            .map(|op| op.name.as_str())
+            .collect();
+        assert!(names.contains(&"tasks"));
+        assert!(names.contains(&"list-tags"));
+
+        let tasks = response
+            .operations
+            .iter()
+            .find(|op| op.name == "tasks")
+            .unwrap();
+        assert_eq!(tasks.path, "/api/tasks");
+        let schema: serde_json::Value = serde_json::from_str(&tasks.input_schema_json).unwrap();
+        assert!(schema.is_object());
+        let schema: serde_json::Value = serde_json::from_str(&tasks.output_schema_json).unwrap();
+        assert_eq!(schema["title"], "TaskSearchResponse");
+    }
+
+    #[tokio::test]
+    async fn test_typed_rpcs() {
+        use proto::vault::vault_client::VaultClient;
+        use proto::vault::{ListTagsRequest, SearchTasksRequest};
+
+        let (temp_dir, service) = create_service();
+        fs::write(
+            temp_dir.path().join("note.md"),
+            "---\ntags: [work]\n---\n- [ ] Call Ann 📅 2025-01-10\n",
+        )
+        .unwrap();
+        let incoming =
+            tonic::transport::server::TcpIncoming::bind("127.0.0.1:0".parse().unwrap()).unwrap();
+        let address = incoming.local_addr().unwrap();
+        tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_routes(service.into_routes())
+                .serve_with_incoming(incoming),
+        );
+        let mut client = VaultClient::connect(format!("http://{}", address))
+            .await
+            .unwrap();
+
+        let response = client
+            .search_tasks(SearchTasksRequest {
+                status: Some("incomplete".to_string()),
+                sort_by: Some("due_date".to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(response.total_count, Some(2));
+        assert_eq!(response.has_more, Some(false));
+        let task = &response.tasks[0];
+        assert_eq!(task.content, "Call Ann");
+        assert_eq!(task.due_date.as_deref(), Some("2025-01-10"));
+        assert_eq!(task.line_number, 4);
+        assert_eq!(response.tasks[1].tags, ["work"]);
+
+        let response = client
+            .list_tags(ListTagsRequest::default())
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(response.tags[0].tag, "work");
+        assert_eq!(response.tags[0].document_count, 1);
+
+        let status = client
+            .search_tasks(SearchTasksRequest {
+                status: Some("bogus".to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn test_execute_operation() {
+        let (_temp_dir, service) = create_service();
+
+        let response = service
+            .execute(Request::new(ExecuteRequest {
+                operation: "tasks".to_string(),
+                params_json: r#"{"status": "incomplete"}"#.to_string(),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        let result: serde_json::Value = serde_json::from_str(&response.result_json).unwrap();
+        let tasks = result["tasks"].as_array().unwrap();
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0]["content"], "Write report #work");
+    }
+
+    #[tokio::test]
+    async fn test_execute_empty_params() {
+        let (_temp_dir, service) = create_service();
+
+        let response = service
+            .execute(Request::new(ExecuteRequest {
+                operation: "tasks".to_string(),
+                params_json: String::new(),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        let result: serde_json::Value = serde_json::from_str(&response.result_json).unwrap();
+        assert_eq!(result["tasks"].as_array().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_execute_unknown_operation() {
+        let (_temp_dir, service) = create_service();
+
+        let status = service
+            .execute(Request::new(ExecuteRequest {
+                operation: "does-not-exist".to_string(),
+                params_json: String::new(),
+            }))
+            .await
+            .unwrap_err();
+
+        assert_eq!(status.code(), tonic::Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn test_execute_invalid_params() {
+        let (_temp_dir, service) = create_service();
+
+        let status = service
+            .execute(Request::new(ExecuteRequest {
+                operation: "tasks".to_string(),
+                params_json: "not json".to_string(),
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+        let status = service
+            .execute(Request::new(ExecuteRequest {
+                operation: "tasks".to_string(),
+                params_json: r#"{"status": 42}"#.to_string(),
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    }
+}
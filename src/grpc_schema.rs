@@ -0,0 +1,962 @@
+//! Typed protobuf messages and RPCs built from the operations' JSON Schemas
+//!
+//! `proto/vault.proto` declares the gRPC `Vault` service: one RPC per
+//! operation, with request and response messages that follow the schemars
+//! schemas of the `schema` command. `schema --proto` prints it, and a test
+//! keeps the checked-in file in sync with the registry. The server decodes and
+//! encodes those messages with the same [`ProtoSchema`], turning them into
+//! the JSON documents the operations take and return, so new operations get
+//! an RPC without per-operation code.
+//!
+//! JSON Schema types map to `string` (also enums and dates), `int64` or
+//! `uint64`, `double`, `bool`, nested messages, `repeated` fields, and
+//! `map<string, _>`. Values with no protobuf equivalent, such as a list of
+//! lists, are carried as JSON text in a `string` field. Fields that may be
+//! left out are `optional`.
+//!
+//! Fields of a new message are numbered in name order. Regenerating keeps the
+//! numbers already in the file, numbers new fields after them, and reserves
+//! the numbers of removed fields, so clients built from an older file keep
+//! working.
+
+use crate::operation::Operation;
+use serde_json::{Map, Number, Value};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::Write;
+use std::sync::Arc;
+
+/// Protobuf package of `proto/vault.proto`
+pub const PACKAGE: &str = "markdown_todo_extractor.vault";
+
+/// Name of the typed service
+pub const SERVICE: &str = "Vault";
+
+/// The checked-in file whose field numbers are kept
+pub const VAULT_PROTO: &str = include_str!("../proto/vault.proto");
+
+/// Type of a field's values
+#[derive(Debug, Clone, PartialEq)]
+enum Kind {
+    String,
+    Int64,
+    Uint64,
+    Double,
+    Bool,
+    /// Any other JSON value, as JSON text
+    Json,
+    Message(String),
+}
+
+impl Kind {
+    fn proto_type(&self) -> &str {
+        match self {
+            Kind::String | Kind::Json => "string",
+            Kind::Int64 => "int64",
+            Kind::Uint64 => "uint64",
+            Kind::Double => "double",
+            Kind::Bool => "bool",
+            Kind::Message(name) => name,
+        }
+    }
+
+    /// Protobuf wire type of a single value
+    fn wire_type(&self) -> u8 {
+        match self {
+            Kind::Int64 | Kind::Uint64 | Kind::Bool => VARINT,
+            Kind::Double => FIXED64,
+            Kind::String | Kind::Json | Kind::Message(_) => LEN,
+        }
+    }
+}
+
+/// How many values a field holds
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Label {
+    /// Always present; left out means the zero value
+    Required,
+    /// May be left out
+    Optional,
+    Repeated,
+    /// `map<string, _>`
+    Map,
+}
+
+#[derive(Debug, Clone)]
+struct Field {
+    name: String,
+    number: u32,
+    kind: Kind,
+    label: Label,
+    description: Option<String>,
+}
+
+#[derive(Debug, Default)]
+struct Message {
+    description: Option<String>,
+    fields: Vec<Field>,
+    reserved: Vec<u32>,
+}
+
+/// One typed RPC, calling an operation
+#[derive(Debug, Clone)]
+pub struct Rpc {
+    /// RPC name, e.g. `SearchTasks`
+    pub name: String,
+    /// Name of the operation it calls, e.g. `tasks`
+    pub operation: String,
+    /// Request message name
+    pub request: String,
+    /// Response message name
+    pub response: String,
+    description: String,
+}
+
+/// Messages and RPCs of the `Vault` service
+#[derive(Debug)]
+pub struct ProtoSchema {
+    messages: BTreeMap<String, Message>,
+    rpcs: Vec<Rpc>,
+}
+
+impl ProtoSchema {
+    /// Build the RPCs of the operations exposed over HTTP, keeping the field
+    /// numbers of `existing`, the text of a previously generated file
+    pub fn build(operations: &[Arc<dyn Operation>], existing: &str) -> Self {
+        let mut builder = Builder {
+            numbers: parse_numbers(existing),
+            messages: BTreeMap::new(),
+            sources: HashMap::new(),
+        };
+        let mut rpcs: Vec<Rpc> = operations
+            .iter()
+            .filter(|op| !op.path().is_empty())
+            .map(|op| {
+                let input = op.input_schema();
+                let output = op.output_schema();
+                let name = title(&input)
+                    .and_then(|title| title.strip_suffix("Request"))
+                    .map(str::to_string)
+                    .unwrap_or_else(|| pascal_case(op.name()));
+                let request = builder.root(&input, &name, "Request");
+                let response = builder.root(&output, &name, "Response");
+                Rpc {
+                    name,
+                    operation: op.name().to_string(),
+                    request,
+                    response,
+                    description: op.description().to_string(),
+                }
+            })
+            .collect();
+        rpcs.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Self {
+            messages: builder.messages,
+            rpcs,
+        }
+    }
+
+    /// The RPCs, by name
+    pub fn rpcs(&self) -> &[Rpc] {
+        &self.rpcs
+    }
+
+    /// The RPC named `name`, if any
+    pub fn rpc(&self, name: &str) -> Option<&Rpc> {
+        self.rpcs.iter().find(|rpc| rpc.name == name)
+    }
+
+    /// Render the `.proto` file
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        out.push_str(
+            "// Generated by `markdown-todo-extractor schema --proto` from the operations'\n\
+             // JSON Schemas. Don't edit by hand: regenerate it after changing a request\n\
+             // or response type.\n",
+        );
+        let _ = writeln!(out, "syntax = \"proto3\";\n\npackage {};\n", PACKAGE);
+        out.push_str(
+            "// One RPC per operation, taking and returning the same documents as the\n\
+             // REST API. Strings that hold JSON are marked as such.\n",
+        );
+        let _ = writeln!(out, "service {} {{", SERVICE);
+        for (i, rpc) in self.rpcs.iter().enumerate() {
+            if i > 0 {
+                out.push('\n');
+            }
+            comment(&mut out, "  ", &rpc.description);
+            let _ = writeln!(
+                out,
+                "  rpc {}({}) returns ({});",
+                rpc.name, rpc.request, rpc.response
+            );
+        }
+        out.push_str("}\n");
+
+        for (name, message) in &self.messages {
+            out.push('\n');
+            if let Some(description) = &message.description {
+                comment(&mut out, "", description);
+            }
+            let _ = writeln!(out, "message {} {{", name);
+            for field in &message.fields {
+                let mut description = field.description.clone().unwrap_or_default();
+                if field.kind == Kind::Json {
+                    if !description.is_empty() {
+                        description.push('\n');
+                    }
+                    description.push_str("JSON-encoded");
+                }
+                comment(&mut out, "  ", &description);
+                let declaration = match field.label {
+                    Label::Required => field.kind.proto_type().to_string(),
+                    Label::Optional => match field.kind {
+                        Kind::Message(_) => field.kind.proto_type().to_string(),
+                        _ => format!("optional {}", field.kind.proto_type()),
+                    },
+                    Label::Repeated => format!("repeated {}", field.kind.proto_type()),
+                    Label::Map => format!("map<string, {}>", field.kind.proto_type()),
+                };
+                let _ = writeln!(out, "  {} {} = {};", declaration, field.name, field.number);
+            }
+            if !message.reserved.is_empty() {
+                let numbers: Vec<String> = message.reserved.iter().map(u32::to_string).collect();
+                let _ = writeln!(out, "  reserved {};", numbers.join(", "));
+            }
+            out.push_str("}\n");
+        }
+        out
+    }
+
+    /// Decode a protobuf `message` into the JSON document it stands for
+    ///
+    /// Fields left out of the message get their zero value unless they are
+    /// optional, so the document deserializes like one sent over REST.
+    pub fn decode(&self, message: &str, mut bytes: &[u8]) -> Result<Value, String> {
+        let definition = self.message(message)?;
+        let mut object = Map::new();
+        while !bytes.is_empty() {
+            let key = read_varint(&mut bytes)?;
+            let (number, wire_type) = ((key >> 3) as u32, (key & 7) as u8);
+            let Some(field) = definition.fields.iter().find(|f| f.number == number) else {
+                skip_value(wire_type, &mut bytes)?;
+                continue;
+            };
+            match field.label {
+                Label::Repeated => {
+                    let mut items = Vec::new();
+                    // Numbers and booleans may come packed into one value
+                    if wire_type == LEN && field.kind.wire_type() != LEN {
+                        let mut packed = read_len(&mut bytes)?;
+                        while !packed.is_empty() {
+                            items.push(self.read_value(
+                                field,
+                                field.kind.wire_type(),
+                                &mut packed,
+                            )?);
+                        }
+                    } else {
+                        items.push(self.read_value(field, wire_type, &mut bytes)?);
+                    }
+                    if let Value::Array(list) = object
+                        .entry(field.name.clone())
+                        .or_insert_with(|| Value::Array(Vec::new()))
+                    {
+                        list.extend(items);
+                    }
+                }
+                Label::Map => {
+                    if wire_type != LEN {
+                        return Err(wrong_wire_type(field));
+                    }
+                    let mut entry = read_len(&mut bytes)?;
+                    let (mut key, mut value) = (String::new(), None);
+                    while !entry.is_empty() {
+                        let entry_key = read_varint(&mut entry)?;
+                        match (entry_key >> 3, (entry_key & 7) as u8) {
+                            (1, LEN) => key = read_string(&mut entry)?.to_string(),
+                            (2, wire_type) => {
+                                value = Some(self.read_value(field, wire_type, &mut entry)?)
+                            }
+                            (_, wire_type) => skip_value(wire_type, &mut entry)?,
+                        }
+                    }
+                    let value = value
+                        .or_else(|| self.zero(&field.kind))
+                        .unwrap_or(Value::Null);
+                    if let Value::Object(map) = object
+                        .entry(field.name.clone())
+                        .or_insert_with(|| Value::Object(Map::new()))
+                    {
+                        map.insert(key, value);
+                    }
+                }
+                Label::Required | Label::Optional => {
+                    let value = self.read_value(field, wire_type, &mut bytes)?;
+                    object.insert(field.name.clone(), value);
+                }
+            }
+        }
+
+        for field in &definition.fields {
+            if object.contains_key(&field.name) {
+                continue;
+            }
+            let zero = match field.label {
+                Label::Optional => None,
+                Label::Repeated => Some(Value::Array(Vec::new())),
+                Label::Map => Some(Value::Object(Map::new())),
+                Label::Required => self.zero(&field.kind),
+            };
+            if let Some(zero) = zero {
+                object.insert(field.name.clone(), zero);
+            }
+        }
+        Ok(Value::Object(object))
+    }
+
+    /// Encode a JSON document as a protobuf `message`
+    ///
+    /// Null values are left out, as are keys the message has no field for.
+    pub fn encode(&self, message: &str, value: &Value, buf: &mut Vec<u8>) -> Result<(), String> {
+        let definition = self.message(message)?;
+        let Value::Object(object) = value else {
+            return Err(format!("{} must be a JSON object", message));
+        };
+        for field in &definition.fields {
+            let Some(value) = object.get(&field.name).filter(|v| !v.is_null()) else {
+                continue;
+            };
+            match field.label {
+                Label::Repeated => {
+                    let items = value
+                        .as_array()
+                        .ok_or_else(|| format!("{}: expected a list", field.name))?;
+                    if field.kind.wire_type() == LEN {
+                        for item in items.iter().filter(|item| !item.is_null()) {
+                            write_key(field.number, LEN, buf);
+                            self.write_value(field, item, buf)?;
+                        }
+                    } else if !items.is_empty() {
+                        let mut packed = Vec::new();
+                        for item in items {
+                            self.write_value(field, item, &mut packed)?;
+                        }
+                        write_key(field.number, LEN, buf);
+                        write_bytes(&packed, buf);
+                    }
+                }
+                Label::Map => {
+                    let map = value
+                        .as_object()
+                        .ok_or_else(|| format!("{}: expected an object", field.name))?;
+                    for (key, value) in map.iter().filter(|(_, v)| !v.is_null()) {
+                        let mut entry = Vec::new();
+                        write_key(1, LEN, &mut entry);
+                        write_bytes(key.as_bytes(), &mut entry);
+                        write_key(2, field.kind.wire_type(), &mut entry);
+                        self.write_value(field, value, &mut entry)?;
+                        write_key(field.number, LEN, buf);
+                        write_bytes(&entry, buf);
+                    }
+                }
+                Label::Required | Label::Optional => {
+                    write_key(field.number, field.kind.wire_type(), buf);
+                    self.write_value(field, value, buf)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn message(&self, name: &str) -> Result<&Message, String> {
+        self.messages
+            .get(name)
+            .ok_or_else(|| format!("Unknown message: {}", name))
+    }
+
+    /// Zero value of a field that was left out; JSON text has none
+    fn zero(&self, kind: &Kind) -> Option<Value> {
+        match kind {
+            Kind::String => Some(Value::String(String::new())),
+            Kind::Int64 | Kind::Uint64 => Some(Value::from(0)),
+            Kind::Double => Some(Value::from(0.0)),
+            Kind::Bool => Some(Value::Bool(false)),
+            Kind::Json => None,
+            Kind::Message(name) => self.decode(name, &[]).ok(),
+        }
+    }
+
+    fn read_value(&self, field: &Field, wire_type: u8, bytes: &mut &[u8]) -> Result<Value, String> {
+        if wire_type != field.kind.wire_type() {
+            return Err(wrong_wire_type(field));
+        }
+        Ok(match &field.kind {
+            Kind::String => Value::String(read_string(bytes)?.to_string()),
+            Kind::Json => serde_json::from_str(read_string(bytes)?)
+                .map_err(|e| format!("{}: invalid JSON: {}", field.name, e))?,
+            Kind::Int64 => Value::from(read_varint(bytes)? as i64),
+            Kind::Uint64 => Value::from(read_varint(bytes)?),
+            Kind::Bool => Value::Bool(read_varint(bytes)? != 0),
+            Kind::Double => {
+                let (value, rest) = bytes
+                    .split_first_chunk::<8>()
+                    .ok_or_else(|| TRUNCATED.to_string())?;
+                *bytes = rest;
+                Number::from_f64(f64::from_le_bytes(*value))
+                    .map(Value::Number)
+                    .unwrap_or(Value::Null)
+            }
+            Kind::Message(name) => self.decode(name, read_len(bytes)?)?,
+        })
+    }
+
+    /// Write one value of `field`, without its key
+    fn write_value(&self, field: &Field, value: &Value, buf: &mut Vec<u8>) -> Result<(), String> {
+        let mismatch = |expected: &str| format!("{}: expected {}", field.name, expected);
+        match &field.kind {
+            Kind::String => {
+                let text = value.as_str().ok_or_else(|| mismatch("a string"))?;
+                write_bytes(text.as_bytes(), buf);
+            }
+            Kind::Json => write_bytes(value.to_string().as_bytes(), buf),
+            Kind::Int64 => write_varint(
+                value.as_i64().ok_or_else(|| mismatch("an integer"))? as u64,
+                buf,
+            ),
+            Kind::Uint64 => write_varint(
+                value
+                    .as_u64()
+                    .ok_or_else(|| mismatch("an unsigned integer"))?,
+                buf,
+            ),
+            Kind::Bool => write_varint(
+                u64::from(value.as_bool().ok_or_else(|| mismatch("a boolean"))?),
+                buf,
+            ),
+            Kind::Double => {
+                let number = value.as_f64().ok_or_else(|| mismatch("a number"))?;
+                buf.extend_from_slice(&number.to_le_bytes());
+            }
+            Kind::Message(name) => {
+                let mut nested = Vec::new();
+                self.encode(name, value, &mut nested)?;
+                write_bytes(&nested, buf);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Field numbers of one message in a previously generated file
+#[derive(Debug, Default)]
+struct Numbers {
+    fields: HashMap<String, u32>,
+    reserved: Vec<u32>,
+}
+
+struct Builder {
+    numbers: HashMap<String, Numbers>,
+    messages: BTreeMap<String, Message>,
+    /// Schema each message name was built from, to tell apart different
+    /// types that share a name
+    sources: HashMap<String, Value>,
+}
+
+impl Builder {
+    /// Message for an operation's request or response schema
+    fn root(&mut self, schema: &Value, rpc: &str, suffix: &str) -> String {
+        let name = title(schema)
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("{}{}", rpc, suffix));
+        self.message(&name, schema, schema, rpc)
+    }
+
+    /// Build the message `name` from an object schema, returning the name it
+    /// was given: types from different operations that share a name but not
+    /// a schema are told apart by the RPC name
+    fn message(&mut self, name: &str, schema: &Value, root: &Value, rpc: &str) -> String {
+        let mut name = name.to_string();
+        if let Some(known) = self.sources.get(&name) {
+            if known == schema {
+                return name;
+            }
+            name = format!("{}{}", rpc, name);
+            if self.sources.contains_key(&name) {
+                return name;
+            }
+        }
+        // Registered first so recursive types refer back to it
+        self.sources.insert(name.clone(), schema.clone());
+
+        let required: BTreeSet<&str> = schema["required"]
+            .as_array()
+            .map(|names| names.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+        let mut fields = Vec::new();
+        if let Some(properties) = schema["properties"].as_object() {
+            // serde_json keeps object keys sorted, so fields come in name order
+            for (field, property) in properties {
+                let (kind, label) = self.field_type(
+                    &name,
+                    field,
+                    property,
+                    root,
+                    rpc,
+                    required.contains(field.as_str()),
+                );
+                fields.push(Field {
+                    name: field.clone(),
+                    number: 0,
+                    kind,
+                    label,
+                    description: description(property),
+                });
+            }
+        }
+        let reserved = self.number(&name, &mut fields);
+        self.messages.insert(
+            name.clone(),
+            Message {
+                description: description(schema),
+                fields,
+                reserved,
+            },
+        );
+        name
+    }
+
+    fn field_type(
+        &mut self,
+        parent: &str,
+        field: &str,
+        schema: &Value,
+        root: &Value,
+        rpc: &str,
+        required: bool,
+    ) -> (Kind, Label) {
+        let (schema, nullable) = non_null(schema);
+        let label = if nullable || !required {
+            Label::Optional
+        } else {
+            Label::Required
+        };
+        match schema["type"].as_str() {
+            Some("array") => {
+                let (items, nullable_items) = non_null(&schema["items"]);
+                let kind = match self.kind(parent, field, &items, root, rpc) {
+                    kind if !nullable_items => kind,
+                    _ => Kind::Json,
+                };
+                let label = match kind {
+                    Kind::Json => label,
+                    _ => Label::Repeated,
+                };
+                (kind, label)
+            }
+            Some("object")
+                if schema.get("properties").is_none()
+                    && schema["additionalProperties"].is_object() =>
+            {
+                match self.kind(parent, field, &schema["additionalProperties"], root, rpc) {
+                    Kind::Json => (Kind::Json, label),
+                    kind => (kind, Label::Map),
+                }
+            }
+            _ => (self.kind(parent, field, &schema, root, rpc), label),
+        }
+    }
+
+    /// Kind of a single value; lists and maps are only supported as fields,
+    /// so anywhere else they are JSON
+    fn kind(&mut self, parent: &str, field: &str, schema: &Value, root: &Value, rpc: &str) -> Kind {
+        if let Some(reference) = schema["$ref"].as_str() {
+            let name = reference.trim_start_matches("#/$defs/");
+            let definition = &root["$defs"][name];
+            return match scalar(definition) {
+                Some(kind) => kind,
+                None if is_message(definition) => {
+                    Kind::Message(self.message(name, definition, root, rpc))
+                }
+                None => Kind::Json,
+            };
+        }
+        match scalar(schema) {
+            Some(kind) => kind,
+            None if is_message(schema) => {
+                let name = format!("{}{}", parent, pascal_case(field));
+                Kind::Message(self.message(&name, schema, root, rpc))
+            }
+            None => Kind::Json,
+        }
+    }
+
+    /// Give `fields` their numbers, returning the numbers to reserve
+    fn number(&self, message: &str, fields: &mut [Field]) -> Vec<u32> {
+        let empty = Numbers::default();
+        let known = self.numbers.get(message).unwrap_or(&empty);
+        let mut next = known
+            .fields
+            .values()
+            .chain(&known.reserved)
+            .max()
+            .map_or(1, |max| max + 1);
+        for field in fields.iter_mut() {
+            field.number = match known.fields.get(&field.name) {
+                Some(&number) => number,
+                None => {
+                    next += 1;
+                    next - 1
+                }
+            };
+        }
+        fields.sort_by_key(|field| field.number);
+
+        let mut reserved: Vec<u32> = known
+            .fields
+            .iter()
+            .filter(|(name, _)| !fields.iter().any(|field| &field.name == *name))
+            .map(|(_, &number)| number)
+            .chain(known.reserved.iter().copied())
+            .collect();
+        reserved.sort_unstable();
+        reserved
+    }
+}
+
+/// Kind of a schema that maps to a protobuf scalar
+fn scalar(schema: &Value) -> Option<Kind> {
+    match schema["type"].as_str() {
+        Some("string") => return Some(Kind::String),
+        Some("integer") => {
+            let unsigned = schema["format"]
+                .as_str()
+                .is_some_and(|f| f.starts_with("uint"));
+            return Some(if unsigned { Kind::Uint64 } else { Kind::Int64 });
+        }
+        Some("number") => return Some(Kind::Double),
+        Some("boolean") => return Some(Kind::Bool),
+        Some(_) => return None,
+        None => {}
+    }
+    // Enums, constants, and unions of them (with their own descriptions)
+    if let Some(values) = schema["enum"].as_array() {
+        return values.iter().all(Value::is_string).then_some(Kind::String);
+    }
+    if schema["const"].is_string() {
+        return Some(Kind::String);
+    }
+    let alternatives = schema["oneOf"].as_array().or(schema["anyOf"].as_array())?;
+    alternatives
+        .iter()
+        .all(|alternative| scalar(alternative) == Some(Kind::String))
+        .then_some(Kind::String)
+}
+
+fn is_message(schema: &Value) -> bool {
+    schema["type"] == "object" && !schema["additionalProperties"].is_object()
+}
+
+/// A schema without its `null` alternative, and whether it had one
+fn non_null(schema: &Value) -> (Value, bool) {
+    if let Some(types) = schema["type"].as_array()
+        && types.iter().any(|t| t == "null")
+    {
+        let others: Vec<&Value> = types.iter().filter(|t| *t != "null").collect();
+        if let [single] = others.as_slice() {
+            let mut schema = schema.clone();
+            schema["type"] = (*single).clone();
+            return (schema, true);
+        }
+    }
+    if let Some(alternatives) = schema["anyOf"].as_array()
+        && let [first, second] = alternatives.as_slice()
+    {
+        if second["type"] == "null" {
+            return (first.clone(), true);
+        }
+        if first["type"] == "null" {
+            return (second.clone(), true);
+        }
+    }
+    (schema.clone(), false)
+}
+
+fn title(schema: &Value) -> Option<&str> {
+    schema["title"].as_str()
+}
+
+fn description(schema: &Value) -> Option<String> {
+    schema["description"].as_str().map(str::to_string)
+}
+
+/// `list-tags` or `sub_items` to `ListTags` or `SubItems`
+fn pascal_case(name: &str) -> String {
+    name.split(['-', '_'])
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Write `text` as `//` comment lines
+fn comment(out: &mut String, indent: &str, text: &str) {
+    for line in text.lines() {
+        let line = line.trim_end();
+        if line.is_empty() {
+            let _ = writeln!(out, "{}//", indent);
+        } else {
+            let _ = writeln!(out, "{}// {}", indent, line);
+        }
+    }
+}
+
+/// Field numbers by message in the text of a generated file
+fn parse_numbers(text: &str) -> HashMap<String, Numbers> {
+    let mut numbers: HashMap<String, Numbers> = HashMap::new();
+    let mut current: Option<String> = None;
+    for line in text.lines().map(str::trim) {
+        if let Some(name) = line.strip_prefix("message ") {
+            current = Some(name.trim_end_matches('{').trim().to_string());
+            continue;
+        }
+        if line == "}" {
+            current = None;
+        }
+        let Some(message) = &current else {
+            continue;
+        };
+        let entry = numbers.entry(message.clone()).or_default();
+        if let Some(list) = line.strip_prefix("reserved ") {
+            entry.reserved.extend(
+                list.trim_end_matches(';')
+                    .split(',')
+                    .filter_map(|n| n.trim().parse::<u32>().ok()),
+            );
+        } else if let Some((declaration, number)) = line.split_once(" = ")
+            && !line.starts_with("//")
+            && let Some(name) = declaration.split_whitespace().last()
+            && let Ok(number) = number.trim_end_matches(';').parse()
+        {
+            entry.fields.insert(name.to_string(), number);
+        }
+    }
+    numbers
+}
+
+const VARINT: u8 = 0;
+const FIXED64: u8 = 1;
+const LEN: u8 = 2;
+const FIXED32: u8 = 5;
+const TRUNCATED: &str = "Truncated protobuf message";
+
+fn wrong_wire_type(field: &Field) -> String {
+    format!("{}: unexpected wire type", field.name)
+}
+
+fn write_varint(mut value: u64, buf: &mut Vec<u8>) {
+    while value >= 0x80 {
+        buf.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+fn write_key(number: u32, wire_type: u8, buf: &mut Vec<u8>) {
+    write_varint(u64::from(number) << 3 | u64::from(wire_type), buf);
+}
+
+fn write_bytes(bytes: &[u8], buf: &mut Vec<u8>) {
+    write_varint(bytes.len() as u64, buf);
+    buf.extend_from_slice(bytes);
+}
+
+fn read_varint(bytes: &mut &[u8]) -> Result<u64, String> {
+    let mut value = 0;
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = bytes.split_first().ok_or_else(|| TRUNCATED.to_string())?;
+        *bytes = rest;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte < 0x80 {
+            return Ok(value);
+        }
+    }
+    Err("Invalid varint in protobuf message".to_string())
+}
+
+fn read_len<'a>(bytes: &mut &'a [u8]) -> Result<&'a [u8], String> {
+    let len = read_varint(bytes)? as usize;
+    if len > bytes.len() {
+        return Err(TRUNCATED.to_string());
+    }
+    let (value, rest) = bytes.split_at(len);
+    *bytes = rest;
+    Ok(value)
+}
+
+fn read_string<'a>(bytes: &mut &'a [u8]) -> Result<&'a str, String> {
+    std::str::from_utf8(read_len(bytes)?).map_err(|e| format!("Invalid UTF-8 in string: {}", e))
+}
+
+/// Skip a value of a field this message doesn't know
+fn skip_value(wire_type: u8, bytes: &mut &[u8]) -> Result<(), String> {
+    let width = match wire_type {
+        VARINT => return read_varint(bytes).map(|_| ()),
+        LEN => return read_len(bytes).map(|_| ()),
+        FIXED64 => 8,
+        FIXED32 => 4,
+        other => return Err(format!("Unsupported wire type {}", other)),
+    };
+    if bytes.len() < width {
+        return Err(TRUNCATED.to_string());
+    }
+    *bytes = &bytes[width..];
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::capabilities::CapabilityRegistry;
+    use crate::config::Config;
+    use schemars::JsonSchema;
+    use serde_json::json;
+    use std::path::PathBuf;
+
+    /// A request type standing in for an operation's
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct SampleRequest {
+        /// Text to look for
+        query: String,
+        limit: Option<u32>,
+        tags: Vec<String>,
+        nested: Vec<Vec<String>>,
+        owner: Option<Owner>,
+    }
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct Owner {
+        name: String,
+        fields: std::collections::BTreeMap<String, i64>,
+    }
+
+    fn sample_schema(existing: &str) -> ProtoSchema {
+        let mut builder = Builder {
+            numbers: parse_numbers(existing),
+            messages: BTreeMap::new(),
+            sources: HashMap::new(),
+        };
+        let schema = serde_json::to_value(schemars::schema_for!(SampleRequest)).unwrap();
+        builder.root(&schema, "Sample", "Request");
+        ProtoSchema {
+            messages: builder.messages,
+            rpcs: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_vault_proto_is_up_to_date() {
+        let registry =
+            CapabilityRegistry::new(PathBuf::from("/nonexistent"), Arc::new(Config::default()));
+        let schema = ProtoSchema::build(&registry.create_operations(), VAULT_PROTO);
+        assert!(
+            schema.render() == VAULT_PROTO,
+            "proto/vault.proto is out of date; regenerate it with \
+             `cargo run --features grpc -- schema --proto > proto/vault.proto`"
+        );
+        let rpc = schema.rpc("SearchTasks").unwrap();
+        assert_eq!(rpc.operation, "tasks");
+        assert_eq!(rpc.response, "TaskSearchResponse");
+    }
+
+    #[test]
+    fn test_render_messages() {
+        let proto = sample_schema("").render();
+        assert!(proto.contains(
+            "message Owner {\n  map<string, int64> fields = 1;\n  string name = 2;\n}\n"
+        ));
+        assert!(proto.contains(
+            "message SampleRequest {\n  \
+             optional uint64 limit = 1;\n  \
+             // JSON-encoded\n  \
+             string nested = 2;\n  \
+             Owner owner = 3;\n  \
+             // Text to look for\n  \
+             string query = 4;\n  \
+             repeated string tags = 5;\n}\n"
+        ));
+    }
+
+    #[test]
+    fn test_field_numbers_are_kept() {
+        // `query` and `limit` were numbered differently, `removed` is gone,
+        // and number 9 was reserved before
+        let existing = "message SampleRequest {\n  \
+                        string query = 1;\n  \
+                        optional uint64 limit = 7;\n  \
+                        string removed = 8;\n  \
+                        reserved 9;\n}\n";
+        let proto = sample_schema(existing).render();
+        assert!(proto.contains(
+            "message SampleRequest {\n  \
+             // Text to look for\n  \
+             string query = 1;\n  \
+             optional uint64 limit = 7;\n  \
+             // JSON-encoded\n  \
+             string nested = 10;\n  \
+             Owner owner = 11;\n  \
+             repeated string tags = 12;\n  \
+             reserved 8, 9;\n}\n"
+        ));
+        // Regenerating from the result changes nothing
+        assert_eq!(sample_schema(&proto).render(), proto);
+    }
+
+    #[test]
+    fn test_encode_decode_round_trip() {
+        let schema = sample_schema("");
+        let value = json!({
+            "query": "invoice",
+            "limit": 5,
+            "tags": ["work", "home"],
+            "nested": [["a"], []],
+            "owner": { "name": "Ann", "fields": { "age": -3 } }
+        });
+        let mut buf = Vec::new();
+        schema.encode("SampleRequest", &value, &mut buf).unwrap();
+        assert_eq!(schema.decode("SampleRequest", &buf).unwrap(), value);
+
+        // Left-out fields are zero values unless optional
+        assert_eq!(
+            schema.decode("SampleRequest", &[]).unwrap(),
+            json!({ "query": "", "tags": [] })
+        );
+
+        let error = schema
+            .encode(
+                "SampleRequest",
+                &json!({ "limit": "five" }),
+                &mut Vec::new(),
+            )
+            .unwrap_err();
+        assert_eq!(error, "limit: expected an unsigned integer");
+        assert!(schema.decode("SampleRequest", &[0x22, 0x05, b'a']).is_err());
+    }
+
+    #[test]
+    fn test_decode_skips_unknown_fields() {
+        let schema = sample_schema("");
+        // Field 15 (varint 1), then query = "x" (field 4)
+        let bytes = [0x78, 0x01, 0x22, 0x01, b'x'];
+        assert_eq!(
+            schema.decode("SampleRequest", &bytes).unwrap(),
+            json!({ "query": "x", "tags": [] })
+        );
+    }
+}
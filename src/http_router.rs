@@ -1,22 +1,127 @@
 use axum::{
     Router,
     extract::{Json, Query},
+    http::{HeaderMap, header},
+    response::{IntoResponse, Response},
     routing::get,
 };
-use rmcp::model::ErrorData;
 use serde::{Serialize, de::DeserializeOwned};
-use std::{future::Future, sync::Arc};
+use std::{
+    future::Future,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
 
 use crate::{
-    capabilities::CapabilityResult,
-    error::{internal_error, invalid_params},
+    audit::{self, AuditEntry, Interface},
+    auth,
+    capabilities::{CapabilityRegistry, CapabilityResult, take_subvault},
+    error::{Error, internal_error, invalid_params, timeout},
 };
 
+/// How long an operation may run when `serve --timeout` isn't given
+pub const DEFAULT_OPERATION_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Operation timeout in milliseconds, 0 for none
+static OPERATION_TIMEOUT_MS: AtomicU64 =
+    AtomicU64::new(DEFAULT_OPERATION_TIMEOUT.as_millis() as u64);
+
+/// Set how long operations may run in this process (`None` for no limit)
+pub fn set_operation_timeout(limit: Option<Duration>) {
+    let millis = limit.map_or(0, |limit| {
+        limit.as_millis().clamp(1, u64::MAX as u128) as u64
+    });
+    OPERATION_TIMEOUT_MS.store(millis, Ordering::Relaxed);
+}
+
+/// How long operations may run, `None` if there is no limit
+pub fn operation_timeout() -> Option<Duration> {
+    match OPERATION_TIMEOUT_MS.load(Ordering::Relaxed) {
+        0 => None,
+        millis => Some(Duration::from_millis(millis)),
+    }
+}
+
+fn timed_out(limit: Duration) -> Error {
+    timeout(format!(
+        "Operation timed out after {:?}; the vault may be on a slow or stalled file system",
+        limit
+    ))
+}
+
+/// Await `future`, failing with a `timeout` error once the operation timeout
+/// has passed
+///
+/// The timer only fires when the operation yields. Use
+/// [`spawn_with_timeout`] where the call can run on a task of its own.
+pub async fn with_timeout<T, E>(future: impl Future<Output = Result<T, E>>) -> Result<T, E>
+where
+    E: From<Error>,
+{
+    within(operation_timeout(), future).await
+}
+
+async fn within<T, E>(
+    limit: Option<Duration>,
+    future: impl Future<Output = Result<T, E>>,
+) -> Result<T, E>
+where
+    E: From<Error>,
+{
+    match limit {
+        Some(limit) => tokio::time::timeout(limit, future)
+            .await
+            .unwrap_or_else(|_| Err(timed_out(limit).into())),
+        None => future.await,
+    }
+}
+
+/// Run `future` on its own task and stop waiting for it at the operation
+/// timeout
+///
+/// Operations read files synchronously, so a read stalled on a network mount
+/// never yields to a timer on the same task. The timed-out call is aborted at
+/// its next yield, or left to finish in the background.
+pub async fn spawn_with_timeout<T, E>(
+    future: impl Future<Output = Result<T, E>> + Send + 'static,
+) -> Result<T, E>
+where
+    T: Send + 'static,
+    E: From<Error> + Send + 'static,
+{
+    spawn_within(operation_timeout(), future).await
+}
+
+async fn spawn_within<T, E>(
+    limit: Option<Duration>,
+    future: impl Future<Output = Result<T, E>> + Send + 'static,
+) -> Result<T, E>
+where
+    T: Send + 'static,
+    E: From<Error> + Send + 'static,
+{
+    let mut task = tokio::spawn(future);
+    let joined = match limit {
+        Some(limit) => match tokio::time::timeout(limit, &mut task).await {
+            Ok(joined) => joined,
+            Err(_) => {
+                task.abort();
+                return Err(timed_out(limit).into());
+            }
+        },
+        None => task.await,
+    };
+    joined.unwrap_or_else(|e| Err(internal_error(format!("Operation failed: {}", e)).into()))
+}
+
 /// Generic helper to execute an HTTP operation with automatic JSON serialization/deserialization
 ///
 /// This function eliminates boilerplate by handling the common pattern of:
 /// 1. Deserializing the JSON request into a typed request
-/// 2. Calling the capability method
+/// 2. Calling the capability method, within the operation timeout
 /// 3. Serializing the response back to JSON
 ///
 /// # Type Parameters
@@ -26,15 +131,15 @@ use crate::{
 /// * `Fut` - The future returned by the operation
 ///
 /// # Example
-/// ```rust
-/// async fn execute_json(&self, json: Value) -> Result<Value, ErrorData> {
+/// ```ignore
+/// async fn execute_json(&self, json: Value) -> Result<Value, Error> {
 ///     execute_json_operation(json, |req| self.capability.list_tags(req)).await
 /// }
 /// ```
 pub async fn execute_json_operation<Req, Resp, F, Fut>(
     json: serde_json::Value,
     operation: F,
-) -> Result<serde_json::Value, ErrorData>
+) -> Result<serde_json::Value, Error>
 where
     Req: DeserializeOwned,
     Resp: Serialize,
@@ -46,20 +151,136 @@ where
         .map_err(|e| invalid_params(format!("Invalid request parameters: {}", e)))?;
 
     // Execute the operation
-    let response = operation(request).await?;
+    let response = with_timeout(operation(request)).await?;
 
     // Serialize the response
     serde_json::to_value(response)
         .map_err(|e| internal_error(format!("Failed to serialize response: {}", e)))
 }
 
+/// Execute an operation, scoped to the request's `subvault` if it has one
+///
+/// A scoped call runs the same operation from a registry rooted at the
+/// validated subdirectory.
+pub async fn execute_with_subvault(
+    operation: &Arc<dyn crate::operation::Operation>,
+    registry: &CapabilityRegistry,
+    mut json: serde_json::Value,
+) -> Result<serde_json::Value, Error> {
+    let subvault = match json.as_object_mut() {
+        Some(params) => take_subvault(params)?,
+        None => None,
+    };
+    let Some(subvault) = subvault else {
+        return operation.execute_json(json).await;
+    };
+
+    let scoped = registry.scoped(&subvault)?;
+    let scoped_operation = scoped
+        .create_operations()
+        .into_iter()
+        .find(|op| op.name() == operation.name())
+        .ok_or_else(|| invalid_params(format!("{} does not support subvault", operation.name())))?;
+    scoped_operation.execute_json(json).await
+}
+
+/// Escape text for inclusion in XML element content or attribute values
+pub(crate) fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Check an HTTP call of `operation` against the configured API keys
+///
+/// A key limited to a folder has the call's `subvault` set or checked.
+fn check_api_key(
+    operation: &dyn crate::operation::Operation,
+    registry: &CapabilityRegistry,
+    headers: &HeaderMap,
+    json_request: &mut serde_json::Value,
+) -> Result<(), Error> {
+    let config = registry.config();
+    match auth::authenticate(&config, headers)? {
+        Some(key) => auth::authorize(key, operation, json_request),
+        None => Ok(()),
+    }
+}
+
+/// Whether the client asked for CSV (`Accept: text/csv`)
+fn accepts_csv(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(header::ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|accept| accept.split(','))
+        .any(|media| media.split(';').next().map(str::trim) == Some("text/csv"))
+}
+
+/// Run an HTTP call of `operation` on its own task under the operation
+/// timeout once the API key allows it, recording it in the audit log
+///
+/// The response is JSON, or CSV for `Accept: text/csv` when the operation
+/// has a CSV form, or the operation's own document when it renders one.
+async fn call_operation(
+    operation: Arc<dyn crate::operation::Operation>,
+    registry: Arc<CapabilityRegistry>,
+    headers: HeaderMap,
+    mut json_request: serde_json::Value,
+) -> Result<Response, Error> {
+    let started = Instant::now();
+    let params = audit::enabled().then(|| json_request.clone());
+    let csv_request = accepts_csv(&headers).then(|| json_request.clone());
+    let render_operation = operation.clone();
+    let path = operation.path();
+    let result = match check_api_key(operation.as_ref(), &registry, &headers, &mut json_request) {
+        Ok(()) => {
+            spawn_with_timeout(async move {
+                execute_with_subvault(&operation, &registry, json_request).await
+            })
+            .await
+        }
+        Err(e) => Err(e),
+    };
+
+    if let Some(params) = params {
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+        };
+        let mut entry = AuditEntry::new(Interface::Http, path, params, started).outcome(&result);
+        entry.client = header("x-client-id").or_else(|| header("user-agent"));
+        audit::record(entry);
+    }
+    let value = result?;
+    if let Some(csv) = csv_request.and_then(|request| render_operation.render_csv(&request, &value))
+    {
+        return Ok(([(header::CONTENT_TYPE, "text/csv; charset=utf-8")], csv?).into_response());
+    }
+    match render_operation.render_http(&value) {
+        Some((content_type, body)) => {
+            Ok(([(header::CONTENT_TYPE, content_type)], body).into_response())
+        }
+        None => Ok(Json(value).into_response()),
+    }
+}
+
 /// Register an HTTP operation on a router
 ///
 /// Creates both GET and POST routes for the operation at its specified path.
 /// The router state type must remain generic to work with the application's state.
+/// Requests may pass `subvault` to scope the call to a subdirectory of the
+/// registry's vault. With API keys configured, each call needs a key that
+/// allows it. Each call runs on its own task under the operation timeout and
+/// is recorded in the audit log, if there is one.
 pub fn register_operation<S>(
     router: Router<S>,
     operation: Arc<dyn crate::operation::Operation>,
+    registry: Arc<CapabilityRegistry>,
 ) -> Router<S>
 where
     S: Clone + Send + Sync + 'static,
@@ -67,37 +288,195 @@ where
     let path = operation.path();
     let op_get = operation.clone();
     let op_post = operation;
+    let registry_get = registry.clone();
+    let registry_post = registry;
 
     router.route(
         path,
         get({
-            move |Query(params): Query<serde_json::Map<String, serde_json::Value>>| {
-                let op = op_get.clone();
-                async move {
-                    let json_request = serde_json::Value::Object(params);
-                    let json_response = op.execute_json(json_request).await.map_err(|e| {
-                        (
-                            axum::http::StatusCode::INTERNAL_SERVER_ERROR,
-                            format!("Operation failed: {}", e.message),
-                        )
-                    })?;
-                    Ok::<_, (axum::http::StatusCode, String)>(Json(json_response))
-                }
+            move |headers: HeaderMap,
+                  Query(params): Query<serde_json::Map<String, serde_json::Value>>| {
+                call_operation(
+                    op_get.clone(),
+                    registry_get.clone(),
+                    headers,
+                    serde_json::Value::Object(params),
+                )
             }
         })
         .post({
-            move |Json(json_request): Json<serde_json::Value>| {
-                let op = op_post.clone();
-                async move {
-                    let json_response = op.execute_json(json_request).await.map_err(|e| {
-                        (
-                            axum::http::StatusCode::INTERNAL_SERVER_ERROR,
-                            format!("Operation failed: {}", e.message),
-                        )
-                    })?;
-                    Ok::<_, (axum::http::StatusCode, String)>(Json(json_response))
-                }
+            move |headers: HeaderMap, Json(json_request): Json<serde_json::Value>| {
+                call_operation(
+                    op_post.clone(),
+                    registry_post.clone(),
+                    headers,
+                    json_request,
+                )
             }
         }),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use serde_json::json;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_execute_with_subvault() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir(temp_dir.path().join("Work")).unwrap();
+        fs::write(temp_dir.path().join("home.md"), "- [ ] Home task\n").unwrap();
+        fs::write(temp_dir.path().join("Work/todo.md"), "- [ ] Work task\n").unwrap();
+
+        let registry =
+            CapabilityRegistry::new(temp_dir.path().to_path_buf(), Arc::new(Config::default()));
+        let operation = registry
+            .create_operations()
+            .into_iter()
+            .find(|op| op.name() == "tasks")
+            .unwrap();
+        let contents = |result: serde_json::Value| -> Vec<String> {
+            let mut contents: Vec<String> = result["tasks"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|t| t["content"].as_str().unwrap().to_string())
+                .collect();
+            contents.sort();
+            contents
+        };
+
+        let result = execute_with_subvault(&operation, &registry, json!({}))
+            .await
+            .unwrap();
+        assert_eq!(contents(result), vec!["Home task", "Work task"]);
+
+        let result = execute_with_subvault(&operation, &registry, json!({"subvault": "Work"}))
+            .await
+            .unwrap();
+        assert_eq!(contents(result), vec!["Work task"]);
+
+        let error = execute_with_subvault(&operation, &registry, json!({"subvault": "../"}))
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("Invalid subvault"));
+    }
+
+    #[tokio::test]
+    async fn test_call_operation_csv() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("home.md"), "- [ ] Home task #chore\n").unwrap();
+        let registry = Arc::new(CapabilityRegistry::new(
+            temp_dir.path().to_path_buf(),
+            Arc::new(Config::default()),
+        ));
+        let operation = registry
+            .create_operations()
+            .into_iter()
+            .find(|op| op.name() == "tasks")
+            .unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ACCEPT, "text/csv, */*;q=0.8".parse().unwrap());
+
+        let response = call_operation(
+            operation.clone(),
+            registry.clone(),
+            headers.clone(),
+            json!({"columns": "content,tags"}),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "text/csv; charset=utf-8"
+        );
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(body, "content,tags\r\nHome task #chore,chore\r\n");
+
+        let error = call_operation(
+            operation.clone(),
+            registry.clone(),
+            headers,
+            json!({"columns": ["due"]}),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(error.code(), "invalid_params");
+
+        // Without the Accept header the response stays JSON
+        let response = call_operation(operation, registry, HeaderMap::new(), json!({}))
+            .await
+            .unwrap();
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+    }
+
+    #[tokio::test]
+    async fn test_call_operation_calendar() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("home.md"),
+            "- [ ] Pay rent 📅 2025-02-01\n",
+        )
+        .unwrap();
+        let registry = Arc::new(CapabilityRegistry::new(
+            temp_dir.path().to_path_buf(),
+            Arc::new(Config::default()),
+        ));
+        let operation = registry
+            .create_operations()
+            .into_iter()
+            .find(|op| op.path() == "/api/tasks.ics")
+            .unwrap();
+
+        let response = call_operation(operation, registry, HeaderMap::new(), json!({}))
+            .await
+            .unwrap();
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "text/calendar; charset=utf-8"
+        );
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert!(body.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(body.contains("SUMMARY:Pay rent\r\n"));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_operation_timeout() {
+        let limit = Some(Duration::from_millis(50));
+
+        let result: Result<u8, Error> = within(limit, async { Ok(1) }).await;
+        assert_eq!(result.unwrap(), 1);
+
+        let error = within(limit, async {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            Ok::<_, Error>(())
+        })
+        .await
+        .unwrap_err();
+        assert_eq!(error.code(), "timeout");
+        assert_eq!(error.http_status(), 504);
+
+        // A call blocked in a synchronous read still times out when spawned
+        let started = std::time::Instant::now();
+        let error = spawn_within(limit, async {
+            std::thread::sleep(Duration::from_millis(500));
+            Ok::<_, Error>(())
+        })
+        .await
+        .unwrap_err();
+        assert_eq!(error.code(), "timeout");
+        assert!(started.elapsed() < Duration::from_millis(400));
+
+        let result: Result<(), Error> = spawn_within(None, async { Ok(()) }).await;
+        assert!(result.is_ok());
+    }
+}
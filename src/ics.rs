@@ -0,0 +1,339 @@
+//! iCalendar (RFC 5545) rendering of tasks as VTODO and VEVENT components
+
+use crate::extractor::{Priority, Task, TaskStatus};
+use crate::path_resolver::to_slash;
+use chrono::NaiveDate;
+use std::collections::HashMap;
+use std::path::Path;
+
+const PRODID: &str = "-//markdown-todo-extractor//EN";
+
+/// Maximum line length in octets before folding (RFC 5545 §3.1)
+const MAX_LINE_OCTETS: usize = 75;
+
+/// Stable 64-bit FNV-1a hash, used for UIDs and ETags
+///
+/// `std`'s default hasher isn't guaranteed to be stable across releases, and
+/// these values are persisted by calendar clients.
+pub fn stable_hash(data: &str) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in data.as_bytes() {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+/// Derive a stable UID for a task from its vault-relative file path and line
+///
+/// The line number is deliberately not included so inserting lines above a
+/// task doesn't change its identity.
+pub fn task_uid(relative_path: &str, task: &Task) -> String {
+    format!(
+        "{:016x}@markdown-todo-extractor",
+        stable_hash(&format!("{}\n{}", relative_path, task.raw_line.trim()))
+    )
+}
+
+/// UIDs for `tasks` extracted from `base_path`, in order
+///
+/// Identical task lines in the same note get a counter suffix so every UID
+/// is unique.
+pub fn task_uids(base_path: &Path, tasks: &[Task]) -> Vec<String> {
+    let mut seen = HashMap::new();
+    tasks
+        .iter()
+        .map(|task| {
+            let relative = Path::new(&task.file_path)
+                .strip_prefix(base_path)
+                .map(to_slash)
+                .unwrap_or_else(|_| task.file_path.clone());
+            let uid = task_uid(&relative, task);
+            let count = seen.entry(uid.clone()).or_insert(0);
+            *count += 1;
+            if *count > 1 {
+                format!("{}-{}", *count, uid)
+            } else {
+                uid
+            }
+        })
+        .collect()
+}
+
+/// Escape a TEXT property value (RFC 5545 §3.3.11)
+pub fn escape_text(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Fold a content line to 75 octets, terminating each physical line with CRLF
+fn push_folded(output: &mut String, line: &str) {
+    let mut octets = 0;
+    for c in line.chars() {
+        let len = c.len_utf8();
+        if octets + len > MAX_LINE_OCTETS {
+            output.push_str("\r\n ");
+            // The leading space of a continuation line counts toward its length
+            octets = 1;
+        }
+        output.push(c);
+        octets += len;
+    }
+    output.push_str("\r\n");
+}
+
+fn format_date(date: NaiveDate) -> String {
+    date.format("%Y%m%d").to_string()
+}
+
+/// iCalendar PRIORITY: 1 is highest, 9 lowest, 0 undefined
+fn ical_priority(priority: Priority) -> u8 {
+    match priority {
+        Priority::Urgent => 1,
+        Priority::High => 3,
+        Priority::Medium => 5,
+        Priority::Low => 7,
+        Priority::Lowest => 9,
+    }
+}
+
+/// The DTSTAMP line, which is required
+///
+/// It is derived from the task so output (and ETags) are stable between
+/// requests.
+fn dtstamp(task: &Task) -> String {
+    let stamp = task
+        .created_date
+        .or(task.completed_date)
+        .unwrap_or(NaiveDate::from_ymd_opt(1970, 1, 1).unwrap());
+    format!("DTSTAMP:{}T000000Z", format_date(stamp))
+}
+
+/// PRIORITY, CATEGORIES, and DESCRIPTION lines shared by both components
+fn push_details(lines: &mut Vec<String>, task: &Task) {
+    if let Some(priority) = task.priority {
+        lines.push(format!("PRIORITY:{}", ical_priority(priority)));
+    }
+    if !task.tags.is_empty() {
+        let categories: Vec<_> = task.tags.iter().map(|t| escape_text(t)).collect();
+        lines.push(format!("CATEGORIES:{}", categories.join(",")));
+    }
+
+    let mut description = format!("{} (line {})", task.file_name, task.line_number);
+    for item in task.sub_items.texts() {
+        description.push_str(&format!("\n- {}", item));
+    }
+    lines.push(format!("DESCRIPTION:{}", escape_text(&description)));
+}
+
+fn fold_lines(lines: Vec<String>) -> String {
+    let mut output = String::new();
+    for line in lines {
+        push_folded(&mut output, &line);
+    }
+    output
+}
+
+/// Render a task as a VTODO component (without the VCALENDAR wrapper)
+pub fn vtodo(task: &Task, uid: &str) -> String {
+    let mut lines = vec!["BEGIN:VTODO".to_string(), format!("UID:{}", uid)];
+    lines.push(dtstamp(task));
+    lines.push(format!("SUMMARY:{}", escape_text(&task.content)));
+
+    let status = match task.status {
+        TaskStatus::Completed => "COMPLETED",
+        TaskStatus::Cancelled => "CANCELLED",
+        TaskStatus::Incomplete | TaskStatus::Other(_) | TaskStatus::CodeTodo => "NEEDS-ACTION",
+    };
+    lines.push(format!("STATUS:{}", status));
+
+    if let Some(start) = task.start_date.or(task.scheduled_date) {
+        lines.push(format!("DTSTART;VALUE=DATE:{}", format_date(start)));
+    }
+    if let Some(due) = task.due_date {
+        lines.push(format!("DUE;VALUE=DATE:{}", format_date(due)));
+    }
+    if let Some(created) = task.created_date {
+        lines.push(format!("CREATED:{}T000000Z", format_date(created)));
+    }
+    if let Some(completed) = task.completed_date {
+        lines.push(format!("COMPLETED:{}T000000Z", format_date(completed)));
+    }
+    push_details(&mut lines, task);
+    lines.push("END:VTODO".to_string());
+    fold_lines(lines)
+}
+
+/// Render a task as an all-day VEVENT on its due date (without the
+/// VCALENDAR wrapper), or `None` if it has no due date
+///
+/// Events are marked transparent so due dates don't block free/busy time.
+pub fn vevent(task: &Task, uid: &str) -> Option<String> {
+    let due = task.due_date?;
+    let mut lines = vec!["BEGIN:VEVENT".to_string(), format!("UID:{}", uid)];
+    lines.push(dtstamp(task));
+    lines.push(format!("SUMMARY:{}", escape_text(&task.content)));
+    lines.push(format!("DTSTART;VALUE=DATE:{}", format_date(due)));
+    lines.push(format!(
+        "DTEND;VALUE=DATE:{}",
+        format_date(due.succ_opt().unwrap_or(due))
+    ));
+    lines.push("TRANSP:TRANSPARENT".to_string());
+    if task.status == TaskStatus::Cancelled {
+        lines.push("STATUS:CANCELLED".to_string());
+    }
+    push_details(&mut lines, task);
+    lines.push("END:VEVENT".to_string());
+    Some(fold_lines(lines))
+}
+
+/// Wrap rendered components in a VCALENDAR object
+pub fn calendar<'a>(components: impl IntoIterator<Item = &'a str>) -> String {
+    let mut output = String::new();
+    push_folded(&mut output, "BEGIN:VCALENDAR");
+    push_folded(&mut output, "VERSION:2.0");
+    push_folded(&mut output, &format!("PRODID:{}", PRODID));
+    for component in components {
+        output.push_str(component);
+    }
+    push_folded(&mut output, "END:VCALENDAR");
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extractor::TaskExtractor;
+    use std::path::Path;
+
+    fn task(line: &str) -> Task {
+        TaskExtractor::default()
+            .extract_tasks_from_content(line, Path::new("vault/Projects/todo.md"))
+            .remove(0)
+    }
+
+    #[test]
+    fn test_escape_text() {
+        assert_eq!(escape_text("a,b;c\\d\ne\r"), "a\\,b\\;c\\\\d\\ne");
+    }
+
+    #[test]
+    fn test_fold_long_lines() {
+        let mut output = String::new();
+        push_folded(&mut output, &format!("SUMMARY:{}", "é".repeat(50)));
+
+        for line in output.split("\r\n").filter(|l| !l.is_empty()) {
+            assert!(line.len() <= MAX_LINE_OCTETS, "line too long: {}", line);
+        }
+        // Unfolding restores the original content
+        assert_eq!(
+            output.replace("\r\n ", "").trim_end(),
+            format!("SUMMARY:{}", "é".repeat(50))
+        );
+    }
+
+    #[test]
+    fn test_task_uid_is_stable() {
+        let a = task("- [ ] Write report #work");
+        let b = task("  - [ ] Write report #work  ");
+        assert_eq!(
+            task_uid("Projects/todo.md", &a),
+            task_uid("Projects/todo.md", &b)
+        );
+        assert_ne!(task_uid("Projects/todo.md", &a), task_uid("Other.md", &a));
+        assert!(task_uid("Projects/todo.md", &a).ends_with("@markdown-todo-extractor"));
+    }
+
+    #[test]
+    fn test_vtodo_incomplete() {
+        let todo = vtodo(
+            &task("- [ ] Write report, draft #work ⏫ 📅 2025-01-10 ➕ 2025-01-01"),
+            "uid-1",
+        );
+
+        assert_eq!(
+            todo,
+            "BEGIN:VTODO\r\n\
+             UID:uid-1\r\n\
+             DTSTAMP:20250101T000000Z\r\n\
+             SUMMARY:Write report\\, draft #work\r\n\
+             STATUS:NEEDS-ACTION\r\n\
+             DUE;VALUE=DATE:20250110\r\n\
+             CREATED:20250101T000000Z\r\n\
+             PRIORITY:1\r\n\
+             CATEGORIES:work\r\n\
+             DESCRIPTION:todo.md (line 1)\r\n\
+             END:VTODO\r\n"
+        );
+    }
+
+    #[test]
+    fn test_vtodo_start_date() {
+        let start = vtodo(&task("- [ ] Plan 🛫 2025-01-03 ⏳ 2025-01-06"), "uid-4");
+        assert!(start.contains("DTSTART;VALUE=DATE:20250103\r\n"));
+
+        let scheduled = vtodo(&task("- [ ] Plan ⏳ 2025-01-06"), "uid-5");
+        assert!(scheduled.contains("DTSTART;VALUE=DATE:20250106\r\n"));
+    }
+
+    #[test]
+    fn test_vtodo_completed() {
+        let todo = vtodo(&task("- [x] Send invoice ✅ 2025-01-05"), "uid-2");
+
+        assert!(todo.contains("STATUS:COMPLETED\r\n"));
+        assert!(todo.contains("COMPLETED:20250105T000000Z\r\n"));
+        assert!(todo.contains("DTSTAMP:20250105T000000Z\r\n"));
+    }
+
+    #[test]
+    fn test_vevent() {
+        let event = vevent(&task("- [ ] Pay rent #home 📅 2025-01-31"), "uid-6").unwrap();
+
+        assert_eq!(
+            event,
+            "BEGIN:VEVENT\r\n\
+             UID:uid-6\r\n\
+             DTSTAMP:19700101T000000Z\r\n\
+             SUMMARY:Pay rent #home\r\n\
+             DTSTART;VALUE=DATE:20250131\r\n\
+             DTEND;VALUE=DATE:20250201\r\n\
+             TRANSP:TRANSPARENT\r\n\
+             CATEGORIES:home\r\n\
+             DESCRIPTION:todo.md (line 1)\r\n\
+             END:VEVENT\r\n"
+        );
+        assert_eq!(vevent(&task("- [ ] Someday"), "uid-7"), None);
+    }
+
+    #[test]
+    fn test_task_uids_are_unique() {
+        let tasks = TaskExtractor::default().extract_tasks_from_content(
+            "- [ ] Water plants\n- [ ] Water plants\n",
+            Path::new("vault/Projects/todo.md"),
+        );
+
+        let uids = task_uids(Path::new("vault"), &tasks);
+        assert_eq!(uids[0], task_uid("Projects/todo.md", &tasks[0]));
+        assert_eq!(uids[1], format!("2-{}", uids[0]));
+    }
+
+    #[test]
+    fn test_calendar_wrapper() {
+        let todo = vtodo(&task("- [-] Cancelled task"), "uid-3");
+        let ics = calendar([todo.as_str()]);
+
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:"));
+        assert!(ics.contains("STATUS:CANCELLED\r\n"));
+        assert!(ics.ends_with("END:VTODO\r\nEND:VCALENDAR\r\n"));
+    }
+}
@@ -0,0 +1,561 @@
+//! Import tasks into the vault from other tools
+//!
+//! Imports are CLI-only (`markdown-todo-extractor import <source> <path>`).
+//! `import csv` turns the rows of a spreadsheet export into checklist items:
+//! rows with a project are appended to that project's note, the rest to
+//! today's daily note. A row whose item is already in its note is skipped, so
+//! an import can be re-run after fixing a few rows.
+
+use crate::capabilities::CapabilityRegistry;
+use crate::capabilities::daily_notes::pattern::daily_note_target;
+use crate::capabilities::task_write::task_line;
+use crate::config::Config;
+use crate::operation::Operation;
+use crate::path_resolver::to_slash;
+use chrono::{Local, NaiveDate};
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
+use serde::Serialize;
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Date formats tried for the due column when `--date-format` isn't given
+const DUE_DATE_FORMATS: &[&str] = &["%Y-%m-%d", "%Y/%m/%d", "%m/%d/%Y", "%d.%m.%Y"];
+
+/// Import tasks from other tools
+#[derive(Parser, Debug)]
+#[command(
+    name = "import",
+    about = "Import tasks into the vault from other tools"
+)]
+pub struct ImportCommand {
+    #[command(subcommand)]
+    pub source: ImportSource,
+}
+
+/// Supported import sources
+#[derive(Debug, Subcommand)]
+pub enum ImportSource {
+    /// Append the rows of a CSV file as checklist items to project notes or today's daily note
+    Csv(CsvImportArgs),
+}
+
+/// Arguments for `import csv`
+#[derive(Debug, Clone, clap::Args)]
+pub struct CsvImportArgs {
+    /// Vault to import into
+    #[arg(index = 1, required = true)]
+    pub path: PathBuf,
+
+    /// CSV file with a header row
+    #[arg(index = 2, required = true)]
+    pub file: PathBuf,
+
+    /// Header of the column holding the task text
+    #[arg(long, default_value = "content")]
+    pub content_column: String,
+
+    /// Header of the column holding the due date
+    #[arg(long, default_value = "due")]
+    pub due_column: String,
+
+    /// Header of the column holding tags (separated by commas, semicolons, or spaces)
+    #[arg(long, default_value = "tags")]
+    pub tags_column: String,
+
+    /// Header of the column holding the project name
+    #[arg(long, default_value = "project")]
+    pub project_column: String,
+
+    /// Folder, relative to the vault, holding one note per project
+    #[arg(long, default_value = "Projects")]
+    pub project_folder: String,
+
+    /// chrono format of the due column, e.g. %d/%m/%Y (defaults to trying
+    /// YYYY-MM-DD, YYYY/MM/DD, MM/DD/YYYY, and DD.MM.YYYY)
+    #[arg(long)]
+    pub date_format: Option<String>,
+
+    /// Report what would be imported without writing any notes
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+/// A note items were (or would be) appended to
+#[derive(Debug, Serialize, PartialEq)]
+pub struct ImportedNote {
+    /// Vault-relative path of the note
+    pub file_path: String,
+    /// Whether the note didn't exist before the import
+    pub created: bool,
+    /// Checklist items appended, as written
+    pub tasks: Vec<String>,
+}
+
+/// A CSV row that wasn't imported
+#[derive(Debug, Serialize, PartialEq)]
+pub struct SkippedRow {
+    /// 1-based row number in the CSV file, counting the header row
+    pub row: usize,
+    pub reason: String,
+}
+
+/// Result of `import csv`, as printed on stdout
+#[derive(Debug, Serialize)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    pub imported: usize,
+    pub notes: Vec<ImportedNote>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub skipped: Vec<SkippedRow>,
+}
+
+/// Column positions of the mapped fields in the header row
+struct Columns {
+    content: usize,
+    due: Option<usize>,
+    tags: Option<usize>,
+    project: Option<usize>,
+}
+
+impl Columns {
+    fn find(header: &[String], args: &CsvImportArgs) -> Result<Self, Box<dyn Error>> {
+        let position = |name: &str| {
+            header
+                .iter()
+                .position(|column| column.trim().eq_ignore_ascii_case(name.trim()))
+        };
+        let content = position(&args.content_column).ok_or_else(|| {
+            format!(
+                "CSV has no '{}' column (columns: {})",
+                args.content_column,
+                header.join(", ")
+            )
+        })?;
+        Ok(Self {
+            content,
+            due: position(&args.due_column),
+            tags: position(&args.tags_column),
+            project: position(&args.project_column),
+        })
+    }
+}
+
+/// Import the rows of `csv` into the vault at `base_path`
+///
+/// Rows without a project go to the daily note for `today`, which is created
+/// from the first `daily_note_patterns` entry if it doesn't exist.
+pub fn import_csv(
+    base_path: &Path,
+    config: &Config,
+    args: &CsvImportArgs,
+    csv: &str,
+    today: NaiveDate,
+) -> Result<ImportReport, Box<dyn Error>> {
+    let mut rows = parse_csv(csv)?.into_iter();
+    let header = rows.next().ok_or("CSV file is empty")?;
+    let columns = Columns::find(&header, args)?;
+
+    let mut skipped = Vec::new();
+    // Items per target note, in row order, and whether the note is a project's
+    let mut items: BTreeMap<PathBuf, (bool, Vec<String>)> = BTreeMap::new();
+    let mut daily_note: Option<PathBuf> = None;
+
+    for (index, row) in rows.enumerate() {
+        let row_number = index + 2;
+        let field = |column: Option<usize>| {
+            column
+                .and_then(|column| row.get(column))
+                .map(|value| value.trim())
+                .filter(|value| !value.is_empty())
+        };
+
+        let Some(content) = field(Some(columns.content)) else {
+            if row.iter().any(|value| !value.trim().is_empty()) {
+                skipped.push(SkippedRow {
+                    row: row_number,
+                    reason: "empty content".to_string(),
+                });
+            }
+            continue;
+        };
+
+        let due = match field(columns.due) {
+            Some(due) => match parse_due(due, args.date_format.as_deref()) {
+                Some(due) => Some(due),
+                None => {
+                    skipped.push(SkippedRow {
+                        row: row_number,
+                        reason: format!("unrecognized due date '{}'", due),
+                    });
+                    continue;
+                }
+            },
+            None => None,
+        };
+
+        let target = match field(columns.project) {
+            Some(project) => match project_note(&args.project_folder, project) {
+                Some(note) => (note, true),
+                None => {
+                    skipped.push(SkippedRow {
+                        row: row_number,
+                        reason: format!("invalid project name '{}'", project),
+                    });
+                    continue;
+                }
+            },
+            None => match &daily_note {
+                Some(note) => (note.clone(), false),
+                None => {
+                    let note = daily_note_target(
+                        base_path,
+                        &today.format("%Y-%m-%d").to_string(),
+                        config,
+                    )?;
+                    daily_note = Some(note.clone());
+                    (note, false)
+                }
+            },
+        };
+
+        let tags = field(columns.tags).map(split_tags).unwrap_or_default();
+        let (note, is_project) = target;
+        items
+            .entry(note)
+            .or_insert_with(|| (is_project, Vec::new()))
+            .1
+            .push(task_line(content, &tags, due));
+    }
+
+    let mut notes = Vec::new();
+    for (relative, (is_project, lines)) in items {
+        let full_path = base_path.join(&relative);
+        let existing = fs::read_to_string(&full_path).ok();
+
+        // Skip items already in the note, including earlier rows of this import
+        let mut present: Vec<&str> = existing
+            .as_deref()
+            .map(|content| content.lines().map(str::trim).collect())
+            .unwrap_or_default();
+        let mut tasks = Vec::new();
+        for line in &lines {
+            if present.contains(&line.as_str()) {
+                continue;
+            }
+            present.push(line);
+            tasks.push(line.clone());
+        }
+        if tasks.is_empty() {
+            continue;
+        }
+
+        if !args.dry_run {
+            let mut content = match &existing {
+                Some(existing) if existing.is_empty() || existing.ends_with('\n') => {
+                    existing.clone()
+                }
+                Some(existing) => format!("{}\n", existing),
+                None if is_project => {
+                    let title = relative.file_stem().unwrap_or_default().to_string_lossy();
+                    format!("# {}\n\n", title)
+                }
+                None => String::new(),
+            };
+            for task in &tasks {
+                content.push_str(task);
+                content.push('\n');
+            }
+            if let Some(parent) = full_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&full_path, content)?;
+        }
+
+        notes.push(ImportedNote {
+            file_path: to_slash(&relative),
+            created: existing.is_none(),
+            tasks,
+        });
+    }
+
+    Ok(ImportReport {
+        dry_run: args.dry_run,
+        imported: notes.iter().map(|note| note.tasks.len()).sum(),
+        notes,
+        skipped,
+    })
+}
+
+/// Parse CSV text into rows of fields
+///
+/// Fields may be quoted with `"`, with `""` for a literal quote; quoted fields
+/// may contain commas and line breaks. Lines end with `\n` or `\r\n`, and a
+/// leading byte order mark is ignored.
+pub fn parse_csv(text: &str) -> Result<Vec<Vec<String>>, Box<dyn Error>> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes => {
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                    field.push('"');
+                } else {
+                    in_quotes = false;
+                }
+            }
+            '"' if field.is_empty() => in_quotes = true,
+            ',' if !in_quotes => row.push(std::mem::take(&mut field)),
+            '\r' if !in_quotes && chars.peek() == Some(&'\n') => {}
+            '\n' if !in_quotes => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+                line += 1;
+            }
+            c => {
+                if c == '\n' {
+                    line += 1;
+                }
+                field.push(c);
+            }
+        }
+    }
+    if in_quotes {
+        return Err(format!("Unterminated quoted field at line {}", line).into());
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+/// Parse a due date with `format`, or with each of [`DUE_DATE_FORMATS`]
+fn parse_due(value: &str, format: Option<&str>) -> Option<NaiveDate> {
+    match format {
+        Some(format) => NaiveDate::parse_from_str(value, format).ok(),
+        None => {
+            // Timestamps like 2025-03-01T09:00:00Z keep only their date
+            let date = value.split(['T', ' ']).next().unwrap_or(value);
+            DUE_DATE_FORMATS
+                .iter()
+                .find_map(|format| NaiveDate::parse_from_str(date, format).ok())
+        }
+    }
+}
+
+/// Tags from a cell like `work, urgent` or `#work #urgent`
+fn split_tags(value: &str) -> Vec<String> {
+    value
+        .split([',', ';', ' '])
+        .map(|tag| tag.trim().trim_start_matches('#'))
+        .filter(|tag| !tag.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Vault-relative path of a project's note, `None` if the name can't be a
+/// file name
+fn project_note(folder: &str, project: &str) -> Option<PathBuf> {
+    let name: String = project
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '-',
+            c => c,
+        })
+        .collect();
+    let name = name.trim().trim_start_matches('.');
+    if name.is_empty() {
+        return None;
+    }
+    Some(Path::new(folder).join(format!("{}.md", name)))
+}
+
+/// CliOperation implementation for the import command
+#[derive(Default)]
+pub struct ImportOperation;
+
+impl ImportOperation {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait::async_trait]
+impl Operation for ImportOperation {
+    fn name(&self) -> &'static str {
+        "import"
+    }
+
+    fn path(&self) -> &'static str {
+        // ImportOperation is CLI-only and doesn't have an HTTP endpoint
+        ""
+    }
+
+    fn description(&self) -> &'static str {
+        "Import tasks into the vault from other tools (CSV)"
+    }
+
+    fn get_command(&self) -> clap::Command {
+        ImportCommand::command()
+    }
+
+    async fn execute_json(&self, _json: Value) -> Result<Value, crate::error::Error> {
+        // ImportOperation is CLI-only and doesn't support JSON execution
+        Err(crate::error::unsupported(
+            "import command is only available via CLI",
+        ))
+    }
+
+    async fn execute_from_args(
+        &self,
+        matches: &clap::ArgMatches,
+        _registry: &CapabilityRegistry,
+    ) -> Result<String, Box<dyn Error>> {
+        let cmd = ImportCommand::from_arg_matches(matches)?;
+        match cmd.source {
+            ImportSource::Csv(args) => {
+                let csv = fs::read_to_string(&args.file)
+                    .map_err(|e| format!("Failed to read {}: {}", args.file.display(), e))?;
+                let config = Config::load_from_base_path(&args.path);
+                let today = Local::now().date_naive();
+                let report = import_csv(&args.path, &config, &args, &csv, today)?;
+                Ok(serde_json::to_string_pretty(&report)?)
+            }
+        }
+    }
+
+    fn input_schema(&self) -> Value {
+        // ImportOperation is CLI-only and doesn't have a meaningful JSON schema
+        json!({
+            "type": "object",
+            "properties": {}
+        })
+    }
+
+    fn output_schema(&self) -> Value {
+        // ImportOperation is CLI-only and doesn't return a JSON response
+        json!({
+            "type": "object",
+            "properties": {}
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn args(vault: &Path) -> CsvImportArgs {
+        CsvImportArgs {
+            path: vault.to_path_buf(),
+            file: PathBuf::from("tasks.csv"),
+            content_column: "content".to_string(),
+            due_column: "due".to_string(),
+            tags_column: "tags".to_string(),
+            project_column: "project".to_string(),
+            project_folder: "Projects".to_string(),
+            date_format: None,
+            dry_run: false,
+        }
+    }
+
+    #[test]
+    fn test_parse_csv() {
+        let rows = parse_csv("\u{feff}a,b\r\n\"x, \"\"y\"\"\",\"two\nlines\"\n,\n").unwrap();
+        assert_eq!(
+            rows,
+            vec![vec!["a", "b"], vec!["x, \"y\"", "two\nlines"], vec!["", ""],]
+        );
+        assert!(parse_csv("a\n\"open").is_err());
+    }
+
+    #[test]
+    fn test_import_csv() {
+        let vault = TempDir::new().unwrap();
+        fs::create_dir_all(vault.path().join("Projects")).unwrap();
+        fs::write(
+            vault.path().join("Projects/Garden.md"),
+            "# Garden\n- [ ] Buy seeds #garden",
+        )
+        .unwrap();
+        let csv = "Content,Due,Tags,Project\n\
+                   Buy seeds,,garden,Garden\n\
+                   Build raised bed,03/15/2025,\"garden, diy\",Garden\n\
+                   Call plumber,2025-03-02T09:00:00Z,#home,\n\
+                   Renew passport,someday,,\n\
+                   File taxes,,,Admin/Home\n";
+        let today = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+        let config = Config::default();
+
+        let dry_run = CsvImportArgs {
+            dry_run: true,
+            ..args(vault.path())
+        };
+        let report = import_csv(vault.path(), &config, &dry_run, csv, today).unwrap();
+        assert_eq!(report.imported, 3);
+        assert!(!vault.path().join("2025-03-01.md").exists());
+
+        let report = import_csv(vault.path(), &config, &args(vault.path()), csv, today).unwrap();
+        assert_eq!(report.imported, 3);
+        assert_eq!(
+            report.notes,
+            vec![
+                ImportedNote {
+                    file_path: "2025-03-01.md".to_string(),
+                    created: true,
+                    tasks: vec!["- [ ] Call plumber #home 📅 2025-03-02".to_string()],
+                },
+                ImportedNote {
+                    file_path: "Projects/Admin-Home.md".to_string(),
+                    created: true,
+                    tasks: vec!["- [ ] File taxes".to_string()],
+                },
+                ImportedNote {
+                    file_path: "Projects/Garden.md".to_string(),
+                    created: false,
+                    tasks: vec!["- [ ] Build raised bed #garden #diy 📅 2025-03-15".to_string()],
+                },
+            ]
+        );
+        assert_eq!(
+            report.skipped,
+            vec![SkippedRow {
+                row: 5,
+                reason: "unrecognized due date 'someday'".to_string(),
+            }]
+        );
+        assert_eq!(
+            fs::read_to_string(vault.path().join("Projects/Garden.md")).unwrap(),
+            "# Garden\n- [ ] Buy seeds #garden\n- [ ] Build raised bed #garden #diy 📅 2025-03-15\n"
+        );
+        assert_eq!(
+            fs::read_to_string(vault.path().join("Projects/Admin-Home.md")).unwrap(),
+            "# Admin-Home\n\n- [ ] File taxes\n"
+        );
+
+        // Importing again adds nothing
+        let report = import_csv(vault.path(), &config, &args(vault.path()), csv, today).unwrap();
+        assert_eq!(report.imported, 0);
+
+        let error = import_csv(
+            vault.path(),
+            &config,
+            &args(vault.path()),
+            "title\nx\n",
+            today,
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("no 'content' column"));
+    }
+}
@@ -0,0 +1,397 @@
+//! `index` command: manage the on-disk index used by `--cache`
+//!
+//! `index rebuild` re-extracts the tasks and outline of every note in a vault
+//! and replaces its cache file, so the first `--cache` run after it doesn't
+//! pay for parsing. `index status` compares the index with the vault without
+//! parsing anything. `index verify` also checks that the file is a usable
+//! index and reports its size and age; `index compact` drops the entries
+//! `verify` flags and replaces a corrupt or outdated file with an empty index.
+
+use crate::cache::{self, IndexCheck, IndexState, IndexStatus, TaskCache};
+use crate::capabilities::CapabilityRegistry;
+use crate::config::Config;
+use crate::extractor::TaskExtractor;
+use crate::operation::Operation;
+use crate::outline_extractor::OutlineExtractor;
+use crate::tag_extractor::collect_markdown_files;
+use crate::warnings::{ExtractionWarning, Warnings};
+use chrono::{DateTime, Local};
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
+use rayon::prelude::*;
+use serde::Serialize;
+use serde_json::{Value, json};
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Instant, SystemTime};
+
+/// Manage the index used by `--cache`
+#[derive(Parser, Debug)]
+#[command(
+    name = "index",
+    about = "Rebuild, inspect, verify, or compact the on-disk index used by --cache"
+)]
+pub struct IndexCommand {
+    #[command(subcommand)]
+    pub action: IndexAction,
+}
+
+/// Index subcommands
+#[derive(Debug, Subcommand)]
+pub enum IndexAction {
+    /// Re-extract the tasks and outlines of every note and replace the index
+    Rebuild {
+        /// Vault to index
+        #[arg(index = 1, required = true)]
+        path: PathBuf,
+    },
+    /// Report how many indexed files are current, changed, or deleted
+    Status {
+        /// Vault to inspect
+        #[arg(index = 1, required = true)]
+        path: PathBuf,
+    },
+    /// Check that the index is readable and its entries are current, and
+    /// report its size and age
+    Verify {
+        /// Vault to check
+        #[arg(index = 1, required = true)]
+        path: PathBuf,
+    },
+    /// Drop stale entries and replace a corrupt or outdated index
+    Compact {
+        /// Vault to compact
+        #[arg(index = 1, required = true)]
+        path: PathBuf,
+    },
+}
+
+/// Result of `index rebuild`
+#[derive(Debug, Serialize)]
+pub struct RebuildReport {
+    pub cache_file: String,
+    /// Files indexed, notes and code files
+    pub files: usize,
+    pub tasks: usize,
+    pub headings: usize,
+    pub elapsed_ms: u128,
+    /// Files that were skipped (unreadable, invalid frontmatter, larger
+    /// than `max_file_size`, or cut off by a truncating scan limit)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ExtractionWarning>,
+}
+
+/// Result of `index status`
+#[derive(Debug, Serialize)]
+pub struct StatusReport {
+    pub cache_file: String,
+    /// Size of the cache file, 0 if there is none
+    pub bytes: u64,
+    #[serde(flatten)]
+    pub status: IndexStatus,
+    /// Notes left out of the comparison by a truncating scan limit
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ExtractionWarning>,
+}
+
+/// Result of `index verify`
+#[derive(Debug, Serialize)]
+pub struct VerifyReport {
+    pub cache_file: String,
+    /// Size of the cache file, 0 if there is none
+    pub bytes: u64,
+    /// When the index was last written, absent if there is none
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modified: Option<String>,
+    /// Seconds since the index was last written
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub age_seconds: Option<u64>,
+    pub state: IndexState,
+    #[serde(flatten)]
+    pub check: IndexCheck,
+    /// Whether the index is readable and every entry is current
+    pub healthy: bool,
+}
+
+/// Result of `index compact`
+#[derive(Debug, Serialize)]
+pub struct CompactReport {
+    pub cache_file: String,
+    /// State of the index before compacting
+    pub state: IndexState,
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+    /// Entries found before compacting
+    #[serde(flatten)]
+    pub check: IndexCheck,
+    /// Entries dropped
+    pub removed: usize,
+}
+
+fn cache_file(base_path: &Path) -> Result<PathBuf, Box<dyn Error>> {
+    cache::vault_cache_path(base_path)
+        .ok_or_else(|| "No cache directory: set HOME or XDG_CACHE_HOME".into())
+}
+
+/// Index every note and code file under `base_path` that a full scan sees,
+/// including archived notes
+pub fn rebuild_index(
+    base_path: &Path,
+    config: Arc<Config>,
+    cache_file: PathBuf,
+) -> Result<RebuildReport, Box<dyn Error>> {
+    let started = Instant::now();
+    let cache = TaskCache::empty(cache_file, &config);
+
+    let (tasks, warnings) =
+        TaskExtractor::new(Arc::clone(&config)).extract_tasks_with_cache(base_path, &cache)?;
+
+    // The task scan already reported any files left out
+    let outline_extractor = OutlineExtractor::new();
+    let headings: usize = collect_markdown_files(base_path, &config, &Warnings::default())?
+        .par_iter()
+        .filter_map(|file| outline_extractor.file_headings(file, Some(&cache)).ok())
+        .map(|headings| headings.len())
+        .sum();
+
+    let cache_file = cache.path().to_string_lossy().to_string();
+    let files = cache.len();
+    cache.save()?;
+
+    Ok(RebuildReport {
+        cache_file,
+        files,
+        tasks: tasks.len(),
+        headings,
+        elapsed_ms: started.elapsed().as_millis(),
+        warnings,
+    })
+}
+
+/// Compare the index at `cache_file` with the notes under `base_path`
+pub fn index_status(
+    base_path: &Path,
+    config: &Config,
+    cache_file: PathBuf,
+) -> Result<StatusReport, Box<dyn Error>> {
+    let warnings = Warnings::default();
+    let notes = collect_markdown_files(base_path, config, &warnings)?;
+    let bytes = fs::metadata(&cache_file).map(|m| m.len()).unwrap_or(0);
+    let cache = TaskCache::open(cache_file, config);
+
+    Ok(StatusReport {
+        cache_file: cache.path().to_string_lossy().to_string(),
+        bytes,
+        status: cache.status(&notes),
+        warnings: warnings.into_vec(),
+    })
+}
+
+/// Check the index at `cache_file` against the vault at `base_path`
+pub fn verify_index(base_path: &Path, config: &Config, cache_file: PathBuf) -> VerifyReport {
+    let metadata = fs::metadata(&cache_file).ok();
+    let modified = metadata.as_ref().and_then(|m| m.modified().ok());
+    let (state, check) = cache::verify(&cache_file, base_path, config);
+
+    VerifyReport {
+        cache_file: cache_file.to_string_lossy().to_string(),
+        bytes: metadata.map_or(0, |m| m.len()),
+        modified: modified.map(|time| DateTime::<Local>::from(time).to_rfc3339()),
+        age_seconds: modified
+            .and_then(|time| SystemTime::now().duration_since(time).ok())
+            .map(|age| age.as_secs()),
+        healthy: state == IndexState::Ok && check.is_clean(),
+        state,
+        check,
+    }
+}
+
+/// Compact the index at `cache_file` for the vault at `base_path`
+pub fn compact_index(
+    base_path: &Path,
+    config: &Config,
+    cache_file: PathBuf,
+) -> Result<CompactReport, Box<dyn Error>> {
+    let size = |path: &Path| fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+    let bytes_before = size(&cache_file);
+    let (state, check) = cache::compact(&cache_file, base_path, config)?;
+
+    Ok(CompactReport {
+        cache_file: cache_file.to_string_lossy().to_string(),
+        state,
+        bytes_before,
+        bytes_after: size(&cache_file),
+        removed: check.files - check.current,
+        check,
+    })
+}
+
+/// CliOperation implementation for the index command
+#[derive(Default)]
+pub struct IndexOperation;
+
+impl IndexOperation {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait::async_trait]
+impl Operation for IndexOperation {
+    fn name(&self) -> &'static str {
+        "index"
+    }
+
+    fn path(&self) -> &'static str {
+        // IndexOperation is CLI-only and doesn't have an HTTP endpoint
+        ""
+    }
+
+    fn description(&self) -> &'static str {
+        "Rebuild, inspect, verify, or compact the on-disk index of tasks and outlines used by --cache"
+    }
+
+    fn get_command(&self) -> clap::Command {
+        IndexCommand::command()
+    }
+
+    async fn execute_json(&self, _json: Value) -> Result<Value, crate::error::Error> {
+        // IndexOperation is CLI-only and doesn't support JSON execution
+        Err(crate::error::unsupported(
+            "index command is only available via CLI",
+        ))
+    }
+
+    async fn execute_from_args(
+        &self,
+        matches: &clap::ArgMatches,
+        _registry: &CapabilityRegistry,
+    ) -> Result<String, Box<dyn Error>> {
+        let cmd = IndexCommand::from_arg_matches(matches)?;
+        match cmd.action {
+            IndexAction::Rebuild { path } => {
+                let config = Arc::new(Config::load_from_base_path(&path).search_scope(true));
+                let report = rebuild_index(&path, config, cache_file(&path)?)?;
+                Ok(serde_json::to_string_pretty(&report)?)
+            }
+            IndexAction::Status { path } => {
+                let config = Config::load_from_base_path(&path).search_scope(true);
+                let report = index_status(&path, &config, cache_file(&path)?)?;
+                Ok(serde_json::to_string_pretty(&report)?)
+            }
+            IndexAction::Verify { path } => {
+                let config = Config::load_from_base_path(&path).search_scope(true);
+                let report = verify_index(&path, &config, cache_file(&path)?);
+                Ok(serde_json::to_string_pretty(&report)?)
+            }
+            IndexAction::Compact { path } => {
+                let config = Config::load_from_base_path(&path).search_scope(true);
+                let report = compact_index(&path, &config, cache_file(&path)?)?;
+                Ok(serde_json::to_string_pretty(&report)?)
+            }
+        }
+    }
+
+    fn input_schema(&self) -> Value {
+        // IndexOperation is CLI-only and doesn't have a meaningful JSON schema
+        json!({
+            "type": "object",
+            "properties": {}
+        })
+    }
+
+    fn output_schema(&self) -> Value {
+        // IndexOperation is CLI-only and doesn't return a JSON response
+        json!({
+            "type": "object",
+            "properties": {}
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_rebuild_and_status() {
+        let vault = TempDir::new().unwrap();
+        let cache_dir = TempDir::new().unwrap();
+        let cache_file = cache_dir.path().join("vault.json");
+        fs::write(
+            vault.path().join("a.md"),
+            "# Plan\n- [ ] Alpha\n## Next\n- [ ] Beta\n",
+        )
+        .unwrap();
+        fs::write(vault.path().join("b.md"), "No tasks or headings\n").unwrap();
+
+        let config = Arc::new(Config::default());
+        let report = rebuild_index(vault.path(), Arc::clone(&config), cache_file.clone()).unwrap();
+        assert_eq!(report.files, 2);
+        assert_eq!(report.tasks, 2);
+        assert_eq!(report.headings, 2);
+
+        let report = index_status(vault.path(), &config, cache_file.clone()).unwrap();
+        assert!(report.bytes > 0);
+        assert_eq!(report.status.files, 2);
+        assert_eq!(report.status.current, 2);
+        assert_eq!(report.status.with_tasks, 2);
+        assert_eq!(report.status.with_outlines, 2);
+        assert_eq!(report.status.not_indexed, 0);
+
+        fs::write(vault.path().join("a.md"), "# Changed\n").unwrap();
+        fs::remove_file(vault.path().join("b.md")).unwrap();
+        fs::write(vault.path().join("c.md"), "# New\n").unwrap();
+        let status = index_status(vault.path(), &config, cache_file)
+            .unwrap()
+            .status;
+        assert_eq!(status.current, 0);
+        assert_eq!(status.changed, 1);
+        assert_eq!(status.deleted, 1);
+        assert_eq!(status.not_indexed, 1);
+    }
+
+    #[test]
+    fn test_verify_and_compact() {
+        let vault = TempDir::new().unwrap();
+        let cache_dir = TempDir::new().unwrap();
+        let cache_file = cache_dir.path().join("vault.json");
+        let config = Arc::new(Config::default());
+        let report = verify_index(vault.path(), &config, cache_file.clone());
+        assert_eq!(report.state, IndexState::Missing);
+        assert!(!report.healthy);
+        assert!(report.modified.is_none());
+
+        fs::write(vault.path().join("a.md"), "- [ ] Alpha\n").unwrap();
+        fs::write(vault.path().join("b.md"), "- [ ] Beta\n").unwrap();
+        rebuild_index(vault.path(), Arc::clone(&config), cache_file.clone()).unwrap();
+        let report = verify_index(vault.path(), &config, cache_file.clone());
+        assert_eq!(report.state, IndexState::Ok);
+        assert!(report.healthy);
+        assert!(report.age_seconds.is_some());
+
+        fs::remove_file(vault.path().join("b.md")).unwrap();
+        let report = verify_index(vault.path(), &config, cache_file.clone());
+        assert!(!report.healthy);
+        assert_eq!(report.check.deleted, 1);
+
+        let report = compact_index(vault.path(), &config, cache_file.clone()).unwrap();
+        assert_eq!(report.removed, 1);
+        assert!(report.bytes_after < report.bytes_before);
+        assert!(verify_index(vault.path(), &config, cache_file.clone()).healthy);
+
+        // A corrupt index is replaced with an empty one
+        fs::write(&cache_file, "{\"version\": ").unwrap();
+        assert_eq!(
+            verify_index(vault.path(), &config, cache_file.clone()).state,
+            IndexState::Corrupt
+        );
+        let report = compact_index(vault.path(), &config, cache_file.clone()).unwrap();
+        assert_eq!(report.state, IndexState::Corrupt);
+        let report = verify_index(vault.path(), &config, cache_file);
+        assert_eq!(report.state, IndexState::Ok);
+        assert_eq!(report.check.files, 0);
+    }
+}
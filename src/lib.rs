@@ -0,0 +1,134 @@
+//! Extract todo items, tags, and document structure from Markdown files.
+//!
+//! This crate powers the `markdown-todo-extractor` binary but can also be
+//! embedded in other Rust tools. The core building blocks are:
+//!
+//! - [`TaskExtractor`] parses Obsidian-style task checkboxes and their metadata
+//!   (due dates, priorities, tags) from files, directories, or in-memory strings.
+//! - [`filter_tasks`] and [`FilterOptions`] narrow extracted tasks by status,
+//!   dates, and tags.
+//! - [`TagExtractor`] reads tags from YAML frontmatter (and optionally note bodies).
+//! - [`OutlineExtractor`] extracts heading outlines and sections.
+//! - [`Config`] holds vault-level settings loaded from `.markdown-todo-extractor.toml`.
+//!
+//! Higher-level, interface-agnostic operations (the same ones exposed over MCP,
+//! HTTP, and the CLI) live in `capabilities`, grouped by a `CapabilityRegistry`.
+//!
+//! # Features
+//!
+//! - `fs`: filesystem scanning (directory walking and parallel file reads)
+//! - `server` (default, implies `fs`): capabilities, MCP/HTTP server, and CLI
+//! - `grpc` (implies `server`): tonic gRPC server mirroring the operation registry
+//! - `sqlite` (implies `server`): SQLite vault index (`reindex` command, `sqlite_index` config option)
+//!
+//! With `default-features = false` only the in-memory parsing and filtering core
+//! is built. It has no filesystem or threading requirements and compiles to
+//! `wasm32-unknown-unknown`, so the same task parser can run in a browser or an
+//! Obsidian plugin.
+//!
+//! # Example
+//!
+//! ```
+//! use markdown_todo_extractor::{Config, FilterOptions, TaskExtractor, TaskStatus, filter_tasks};
+//! use std::path::Path;
+//! use std::sync::Arc;
+//!
+//! let extractor = TaskExtractor::new(Arc::new(Config::default()));
+//! let tasks = extractor.extract_tasks_from_content(
+//!     "- [ ] Write report 📅 2025-01-10 #work\n- [x] Send invoice",
+//!     Path::new("notes/todo.md"),
+//! );
+//!
+//! let options = FilterOptions {
+//!     status: Some(TaskStatus::Incomplete),
+//!     ..Default::default()
+//! };
+//! let open = filter_tasks(tasks, &options);
+//!
+//! assert_eq!(open.len(), 1);
+//! assert_eq!(open[0].content, "Write report #work");
+//! assert_eq!(open[0].tags, vec!["work"]);
+//! ```
+
+#[cfg(feature = "server")]
+pub mod audit;
+#[cfg(feature = "server")]
+pub mod auth;
+#[cfg(feature = "fs")]
+pub mod cache;
+#[cfg(feature = "server")]
+pub mod caldav;
+#[cfg(feature = "server")]
+pub mod capabilities;
+#[cfg(feature = "server")]
+pub mod check;
+#[cfg(feature = "server")]
+pub mod cli;
+#[cfg(feature = "server")]
+pub mod cli_router;
+#[cfg(feature = "server")]
+pub mod completion_dates;
+pub mod config;
+#[cfg(feature = "server")]
+pub mod error;
+#[cfg(feature = "server")]
+pub mod export;
+pub mod extractor;
+#[cfg(feature = "server")]
+pub mod feed;
+pub mod filter;
+pub mod frontmatter;
+#[cfg(feature = "fs")]
+pub mod git_dates;
+#[cfg(feature = "grpc")]
+pub mod grpc_router;
+#[cfg(feature = "grpc")]
+pub mod grpc_schema;
+#[cfg(feature = "server")]
+pub mod http_router;
+pub mod ics;
+#[cfg(feature = "server")]
+pub mod import;
+#[cfg(feature = "server")]
+pub mod index;
+pub mod link_extractor;
+pub mod locale;
+#[cfg(feature = "server")]
+pub mod mcp;
+pub mod nl_query;
+#[cfg(feature = "server")]
+pub mod notifier;
+#[cfg(feature = "server")]
+pub mod operation;
+pub mod outline_extractor;
+pub mod path_resolver;
+#[cfg(feature = "server")]
+pub mod profile;
+#[cfg(feature = "server")]
+pub mod profiles;
+pub mod recurrence;
+#[cfg(feature = "server")]
+pub mod run;
+#[cfg(feature = "fs")]
+pub mod scan_limits;
+#[cfg(feature = "server")]
+pub mod scheduler;
+#[cfg(feature = "server")]
+pub mod schema;
+#[cfg(feature = "server")]
+pub mod smtp;
+#[cfg(feature = "sqlite")]
+pub mod sqlite_index;
+pub mod tag_extractor;
+pub mod task_csv;
+pub mod tasks_query;
+pub mod unicode;
+pub mod warnings;
+#[cfg(feature = "server")]
+pub mod watcher;
+
+pub use config::Config;
+pub use extractor::{Priority, Task, TaskExtractor, TaskStatus};
+pub use filter::{FilterOptions, filter_tasks};
+pub use outline_extractor::OutlineExtractor;
+pub use tag_extractor::TagExtractor;
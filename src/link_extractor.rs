@@ -0,0 +1,960 @@
+//! Wikilink parsing and Obsidian-style link resolution
+//!
+//! A [`LinkIndex`] maps note names, vault-relative paths, and frontmatter
+//! `aliases` to notes so `[[Note#Heading|alias]]` links can be resolved the
+//! way Obsidian resolves them.
+
+#[cfg(feature = "fs")]
+use crate::config::Config;
+use crate::frontmatter::frontmatter_block;
+use crate::outline_extractor::OutlineExtractor;
+use crate::path_resolver::to_slash;
+use crate::unicode::fold;
+#[cfg(feature = "fs")]
+use crate::warnings::Warnings;
+#[cfg(feature = "fs")]
+use rayon::prelude::*;
+use regex::Regex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+#[cfg(feature = "fs")]
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A parsed `[[target#anchor|alias]]` wikilink
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct WikiLink {
+    /// Note name or path, without the `.md` extension (empty for links
+    /// within the same note, e.g. `[[#Heading]]`)
+    pub target: String,
+    /// Heading path after `#` (nested headings are separated by `#`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub heading: Option<String>,
+    /// Block id after `#^`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block: Option<String>,
+    /// Display text after `|`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+    /// Whether the link is an embed (`![[...]]`)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub embed: bool,
+}
+
+/// Parse a wikilink, with or without its surrounding `[[` `]]`
+///
+/// Returns `None` when nothing is left to link to.
+pub fn parse_wikilink(text: &str) -> Option<WikiLink> {
+    let mut inner = text.trim();
+    let embed = inner.starts_with("![[");
+    inner = inner.strip_prefix('!').unwrap_or(inner);
+    if let Some(stripped) = inner.strip_prefix("[[") {
+        inner = stripped.strip_suffix("]]").unwrap_or(stripped);
+    }
+
+    let (reference, alias) = match inner.split_once('|') {
+        Some((reference, alias)) => (reference, Some(alias.trim().to_string())),
+        None => (inner, None),
+    };
+    let (target, anchor) = match reference.split_once('#') {
+        Some((target, anchor)) => (target, Some(anchor.trim())),
+        None => (reference, None),
+    };
+
+    let target = target.trim();
+    let target = strip_markdown_extension(target).to_string();
+    let (heading, block) = match anchor.filter(|a| !a.is_empty()) {
+        Some(anchor) => match anchor.strip_prefix('^') {
+            Some(block) => (None, Some(block.to_string())),
+            None => (Some(anchor.to_string()), None),
+        },
+        None => (None, None),
+    };
+
+    if target.is_empty() && heading.is_none() && block.is_none() {
+        return None;
+    }
+
+    Some(WikiLink {
+        target,
+        heading,
+        block,
+        alias: alias.filter(|a| !a.is_empty()),
+        embed,
+    })
+}
+
+/// Drop a trailing `.md` (any case) from a link target or path
+fn strip_markdown_extension(target: &str) -> &str {
+    let len = target.len();
+    if len > 3 && target.is_char_boundary(len - 3) && target[len - 3..].eq_ignore_ascii_case(".md")
+    {
+        &target[..len - 3]
+    } else {
+        target
+    }
+}
+
+/// Read a note's `aliases` (or `alias`) from its frontmatter
+///
+/// Each key may hold a list or a comma-separated string.
+pub fn note_aliases(content: &str) -> Vec<String> {
+    let Some(yaml) = frontmatter_block(content)
+        .and_then(|fm| serde_yaml::from_str::<serde_yaml::Value>(fm).ok())
+    else {
+        return Vec::new();
+    };
+
+    let mut aliases: Vec<String> = Vec::new();
+    for key in ["aliases", "alias"] {
+        let values: Vec<&str> = match yaml.get(key) {
+            Some(serde_yaml::Value::Sequence(seq)) => {
+                seq.iter().filter_map(|v| v.as_str()).collect()
+            }
+            Some(serde_yaml::Value::String(s)) => s.split(',').collect(),
+            _ => continue,
+        };
+        for value in values {
+            let alias = value.trim();
+            if !alias.is_empty() && !aliases.iter().any(|a| a == alias) {
+                aliases.push(alias.to_string());
+            }
+        }
+    }
+    aliases
+}
+
+/// How a link target was matched to a note
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ResolvedBy {
+    /// The note's name or the end of its path matched the target
+    Path,
+    /// One of the note's frontmatter aliases matched the target
+    Alias,
+}
+
+/// A link target resolved to a note
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Resolution<'a> {
+    /// Vault-relative path of the chosen note
+    pub path: &'a Path,
+    pub resolved_by: ResolvedBy,
+    /// Other notes that matched equally well by name or alias
+    pub other_candidates: Vec<&'a Path>,
+}
+
+/// Lookup table from note names, paths, and aliases to notes
+#[derive(Debug, Default)]
+pub struct LinkIndex {
+    /// Vault-relative note paths
+    notes: Vec<PathBuf>,
+    /// Lowercased file stem → note indices
+    by_name: HashMap<String, Vec<usize>>,
+    /// Lowercased alias → note indices
+    by_alias: HashMap<String, Vec<usize>>,
+}
+
+impl LinkIndex {
+    /// Build an index from vault-relative note paths and their aliases
+    pub fn new(notes: impl IntoIterator<Item = (PathBuf, Vec<String>)>) -> Self {
+        let mut index = Self::default();
+        for (path, aliases) in notes {
+            let id = index.notes.len();
+            let name = path.file_stem().unwrap_or_default().to_string_lossy();
+            index.by_name.entry(fold(&name)).or_default().push(id);
+            for alias in aliases {
+                index.by_alias.entry(fold(&alias)).or_default().push(id);
+            }
+            index.notes.push(path);
+        }
+        index
+    }
+
+    /// Scan a vault for notes and their aliases
+    #[cfg(feature = "fs")]
+    pub fn build(
+        base_path: &Path,
+        config: &Config,
+        warnings: &Warnings,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let files = crate::tag_extractor::collect_markdown_files(base_path, config, warnings)?;
+        let mut notes: Vec<(PathBuf, Vec<String>)> = files
+            .par_iter()
+            .map(|path| {
+                let aliases = fs::read_to_string(path)
+                    .map(|content| note_aliases(&content))
+                    .unwrap_or_default();
+                let relative = path.strip_prefix(base_path).unwrap_or(path).to_path_buf();
+                (relative, aliases)
+            })
+            .collect();
+        notes.sort();
+        Ok(Self::new(notes))
+    }
+
+    /// Resolve a link target to a note
+    ///
+    /// Follows Obsidian: a target is matched case-insensitively against note
+    /// names, or against the end of note paths when it contains a `/`, and
+    /// falls back to frontmatter aliases. When several notes match, the one
+    /// in the linking note's folder wins, then the one with the shortest
+    /// path. `source` is the vault-relative path of the linking note.
+    pub fn resolve(&self, target: &str, source: Option<&Path>) -> Option<Resolution<'_>> {
+        let (best, resolved_by, candidates) = self.resolve_ids(target, source)?;
+        Some(Resolution {
+            path: &self.notes[best],
+            resolved_by,
+            other_candidates: candidates
+                .into_iter()
+                .filter(|&id| id != best)
+                .map(|id| self.notes[id].as_path())
+                .collect(),
+        })
+    }
+
+    /// Vault-relative paths of the indexed notes
+    pub fn notes(&self) -> &[PathBuf] {
+        &self.notes
+    }
+
+    /// Resolve a link target to the index of the best note, how it matched,
+    /// and every candidate
+    fn resolve_ids(
+        &self,
+        target: &str,
+        source: Option<&Path>,
+    ) -> Option<(usize, ResolvedBy, Vec<usize>)> {
+        let target = strip_markdown_extension(target.trim().trim_start_matches('/'));
+        if target.is_empty() {
+            return None;
+        }
+        let lowered = fold(target);
+
+        let name = lowered.rsplit('/').next().unwrap_or(&lowered);
+        let by_path: Vec<usize> = self
+            .by_name
+            .get(name)
+            .into_iter()
+            .flatten()
+            .copied()
+            .filter(|&id| {
+                !lowered.contains('/') || {
+                    let path = self.note_key(id);
+                    path == lowered || path.ends_with(&format!("/{}", lowered))
+                }
+            })
+            .collect();
+
+        let (candidates, resolved_by) = if !by_path.is_empty() {
+            (by_path, ResolvedBy::Path)
+        } else {
+            (self.by_alias.get(&lowered)?.clone(), ResolvedBy::Alias)
+        };
+
+        let source_dir = source.and_then(Path::parent);
+        let best = candidates.iter().copied().min_by_key(|&id| {
+            let path = &self.notes[id];
+            (
+                path.parent() != source_dir,
+                path.components().count(),
+                path.clone(),
+            )
+        })?;
+
+        Some((best, resolved_by, candidates))
+    }
+
+    /// Case-folded, `/`-separated note path without its extension
+    fn note_key(&self, id: usize) -> String {
+        let path = to_slash(&self.notes[id]);
+        fold(strip_markdown_extension(&path))
+    }
+}
+
+/// Find the 1-based line a heading or block anchor points to
+///
+/// Headings are compared case-insensitively. A nested heading path
+/// (`Parent#Child`) must appear in order, each part below the previous one.
+pub fn find_anchor_line(content: &str, link: &WikiLink) -> Option<usize> {
+    if let Some(ref block) = link.block {
+        let marker = format!("^{}", block);
+        return content
+            .lines()
+            .position(|line| {
+                line.trim_end()
+                    .strip_suffix(marker.as_str())
+                    .is_some_and(|rest| rest.is_empty() || rest.ends_with(char::is_whitespace))
+            })
+            .map(|index| index + 1);
+    }
+
+    let heading = link.heading.as_ref()?;
+    let headings = OutlineExtractor::new().extract_headings(content);
+    let mut from = 0;
+    let mut line_number = None;
+    for part in heading.split('#').map(normalize_heading) {
+        let offset = headings[from..]
+            .iter()
+            .position(|h| normalize_heading(&h.title) == part)?;
+        line_number = Some(headings[from + offset].line_number);
+        from += offset + 1;
+    }
+    line_number
+}
+
+/// Case-fold a heading and collapse its whitespace for comparison
+fn normalize_heading(heading: &str) -> String {
+    fold(&heading.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+/// A link found in a note, with where it was found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkOccurrence {
+    /// 1-based line of the link
+    pub line_number: usize,
+    pub link: WikiLink,
+    /// Whether it was written as a Markdown link (`[text](note.md)`)
+    pub markdown: bool,
+}
+
+/// Extracts outgoing links from note content
+pub struct LinkExtractor {
+    wikilink_pattern: Regex,
+    markdown_link_pattern: Regex,
+    inline_code_pattern: Regex,
+}
+
+impl Default for LinkExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LinkExtractor {
+    pub fn new() -> Self {
+        Self {
+            wikilink_pattern: Regex::new(r"!?\[\[[^\[\]\n]+\]\]").unwrap(),
+            // [text](path/to/note.md#anchor), skipping URLs with a scheme
+            markdown_link_pattern: Regex::new(
+                r"(!?)\[([^\]\n]*)\]\(([^)\s:]+?\.md)(?:#([^)\s]*))?\)",
+            )
+            .unwrap(),
+            inline_code_pattern: Regex::new(r"`[^`]*`").unwrap(),
+        }
+    }
+
+    /// Extract wikilinks and Markdown links to notes, skipping code
+    ///
+    /// Markdown link paths are relative to the linking note, so they are
+    /// rewritten as vault-relative targets using `source` (the note's
+    /// vault-relative path).
+    pub fn extract_links(&self, content: &str, source: &Path) -> Vec<WikiLink> {
+        self.extract_link_occurrences(content, source)
+            .into_iter()
+            .map(|occurrence| occurrence.link)
+            .collect()
+    }
+
+    /// Extract links like [`extract_links`](Self::extract_links), along with
+    /// their line numbers and syntax
+    ///
+    /// On each line, wikilinks come before Markdown links.
+    pub fn extract_link_occurrences(&self, content: &str, source: &Path) -> Vec<LinkOccurrence> {
+        let mut links = Vec::new();
+        let mut in_code_block = false;
+
+        for (index, line) in content.lines().enumerate() {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                in_code_block = !in_code_block;
+                continue;
+            }
+            if in_code_block {
+                continue;
+            }
+
+            let occurrence = |link, markdown| LinkOccurrence {
+                line_number: index + 1,
+                link,
+                markdown,
+            };
+            let line = self.inline_code_pattern.replace_all(line, "");
+            links.extend(
+                self.wikilink_pattern
+                    .find_iter(&line)
+                    .filter_map(|m| parse_wikilink(m.as_str()))
+                    .map(|link| occurrence(link, false)),
+            );
+            links.extend(
+                self.markdown_link_pattern
+                    .captures_iter(&line)
+                    .map(|caps| occurrence(markdown_link(&caps, source), true)),
+            );
+        }
+
+        links
+    }
+
+    /// Rewrite the heading anchors of links in a note, keeping the rest of
+    /// each link as written
+    ///
+    /// `rewrite` gets every wikilink and Markdown link with a heading anchor
+    /// and returns the new heading path, or `None` to leave the link alone.
+    /// Links in code are skipped, as in
+    /// [`extract_link_occurrences`](Self::extract_link_occurrences). Returns
+    /// the updated content and the number of links changed.
+    pub fn rewrite_heading_anchors(
+        &self,
+        content: &str,
+        source: &Path,
+        mut rewrite: impl FnMut(&WikiLink) -> Option<String>,
+    ) -> (String, usize) {
+        let mut updated = String::with_capacity(content.len());
+        let mut changed = 0;
+        let mut in_code_block = false;
+
+        for line in content.split_inclusive('\n') {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                in_code_block = !in_code_block;
+                updated.push_str(line);
+                continue;
+            }
+            if in_code_block {
+                updated.push_str(line);
+                continue;
+            }
+
+            let code: Vec<_> = self
+                .inline_code_pattern
+                .find_iter(line)
+                .map(|m| m.range())
+                .collect();
+            let in_code = |at: usize| code.iter().any(|range| range.contains(&at));
+
+            // Byte range of the anchor to replace, and its replacement
+            let mut edits = Vec::new();
+            for m in self.wikilink_pattern.find_iter(line) {
+                if in_code(m.start()) {
+                    continue;
+                }
+                let Some(link) = parse_wikilink(m.as_str()).filter(|l| l.heading.is_some()) else {
+                    continue;
+                };
+                let Some(heading) = rewrite(&link) else {
+                    continue;
+                };
+                // The anchor runs from the first `#` to the alias or the closing `]]`
+                let text = &m.as_str()[..m.len() - 2];
+                let reference = text.split('|').next().unwrap_or(text);
+                if let Some(hash) = reference.find('#') {
+                    let start = m.start() + hash + 1;
+                    edits.push((start..m.start() + reference.len(), heading));
+                }
+            }
+            for caps in self.markdown_link_pattern.captures_iter(line) {
+                let (Some(anchor), false) = (caps.get(4), in_code(caps.get(0).unwrap().start()))
+                else {
+                    continue;
+                };
+                let link = markdown_link(&caps, source);
+                if link.heading.is_none() {
+                    continue;
+                }
+                if let Some(heading) = rewrite(&link) {
+                    edits.push((anchor.range(), heading.replace(' ', "%20")));
+                }
+            }
+
+            edits.sort_by_key(|(range, _)| range.start);
+            let mut last = 0;
+            for (range, heading) in edits {
+                updated.push_str(&line[last..range.start]);
+                updated.push_str(&heading);
+                last = range.end;
+                changed += 1;
+            }
+            updated.push_str(&line[last..]);
+        }
+
+        (updated, changed)
+    }
+
+    /// Read every note in a vault once and build its link graph
+    #[cfg(feature = "fs")]
+    pub fn build_graph(
+        &self,
+        base_path: &Path,
+        config: &Config,
+        warnings: &Warnings,
+    ) -> Result<LinkGraph, Box<dyn std::error::Error>> {
+        let files = crate::tag_extractor::collect_markdown_files(base_path, config, warnings)?;
+        let mut notes: Vec<(PathBuf, Vec<String>, Vec<WikiLink>)> = files
+            .par_iter()
+            .map(|path| {
+                let relative = path.strip_prefix(base_path).unwrap_or(path).to_path_buf();
+                let content = fs::read_to_string(path).unwrap_or_default();
+                let aliases = note_aliases(&content);
+                let links = self.extract_links(&content, &relative);
+                (relative, aliases, links)
+            })
+            .collect();
+        notes.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut outgoing = Vec::with_capacity(notes.len());
+        let index = LinkIndex::new(notes.iter_mut().map(|(path, aliases, links)| {
+            outgoing.push(std::mem::take(links));
+            (path.clone(), std::mem::take(aliases))
+        }));
+
+        Ok(LinkGraph::new(&index, outgoing))
+    }
+}
+
+/// The link a `markdown_link_pattern` match points to
+fn markdown_link(caps: &regex::Captures, source: &Path) -> WikiLink {
+    let path = caps[3].replace("%20", " ");
+    let anchor = caps.get(4).map(|a| a.as_str().replace("%20", " "));
+    WikiLink {
+        target: relative_to(source, &path),
+        heading: anchor
+            .clone()
+            .filter(|a| !a.is_empty() && !a.starts_with('^')),
+        block: anchor.and_then(|a| a.strip_prefix('^').map(String::from)),
+        alias: Some(caps[2].trim().to_string()).filter(|a| !a.is_empty()),
+        embed: !caps[1].is_empty(),
+    }
+}
+
+/// Turn a Markdown link path into a vault-relative target without `.md`
+///
+/// Paths starting with `/` are already vault-relative; others are relative
+/// to the linking note's folder. `..` segments that would leave the vault are
+/// dropped.
+fn relative_to(source: &Path, path: &str) -> String {
+    let path = strip_markdown_extension(path);
+    let mut parts: Vec<&str> = match path.strip_prefix('/') {
+        Some(_) => Vec::new(),
+        None => source
+            .parent()
+            .into_iter()
+            .flat_map(Path::iter)
+            .filter_map(|part| part.to_str())
+            .collect(),
+    };
+
+    for part in path.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            part => parts.push(part),
+        }
+    }
+    parts.join("/")
+}
+
+/// Whether a link target names a non-note file such as `diagram.png`
+pub fn is_attachment(target: &str) -> bool {
+    Path::new(target)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            (1..=5).contains(&ext.len())
+                && ext.starts_with(|c: char| c.is_ascii_alphabetic())
+                && ext.chars().all(|c| c.is_ascii_alphanumeric())
+        })
+}
+
+/// Directed graph of resolved links between notes
+#[derive(Debug)]
+pub struct LinkGraph {
+    /// Vault-relative note paths (node ids are indices)
+    pub notes: Vec<PathBuf>,
+    /// Distinct notes each note links to (self-links excluded)
+    pub outgoing: Vec<Vec<usize>>,
+    /// Number of links per note that don't resolve to any note
+    pub unresolved: Vec<usize>,
+}
+
+impl LinkGraph {
+    /// Build the graph from each indexed note's outgoing links
+    ///
+    /// `links` is in the same order as `index.notes()`. Same-note links
+    /// (`[[#Heading]]`) are ignored.
+    pub fn new(index: &LinkIndex, links: Vec<Vec<WikiLink>>) -> Self {
+        let mut outgoing = vec![Vec::new(); index.notes().len()];
+        let mut unresolved = vec![0; index.notes().len()];
+
+        for (id, note_links) in links.into_iter().enumerate() {
+            let source = index.notes().get(id).map(PathBuf::as_path);
+            for link in note_links
+                .iter()
+                .filter(|link| !link.target.is_empty() && !is_attachment(&link.target))
+            {
+                match index.resolve_ids(&link.target, source) {
+                    Some((target, _, _)) => {
+                        if target != id && !outgoing[id].contains(&target) {
+                            outgoing[id].push(target);
+                        }
+                    }
+                    None => unresolved[id] += 1,
+                }
+            }
+        }
+
+        Self {
+            notes: index.notes().to_vec(),
+            outgoing,
+            unresolved,
+        }
+    }
+
+    /// Number of distinct notes linking to each note
+    pub fn in_degrees(&self) -> Vec<usize> {
+        let mut degrees = vec![0; self.notes.len()];
+        for targets in &self.outgoing {
+            for &target in targets {
+                degrees[target] += 1;
+            }
+        }
+        degrees
+    }
+
+    /// PageRank of each note (scores sum to 1)
+    ///
+    /// Uses a damping factor of 0.85. Notes without outgoing links spread
+    /// their score evenly over all notes.
+    pub fn pagerank(&self) -> Vec<f64> {
+        const DAMPING: f64 = 0.85;
+        const MAX_ITERATIONS: usize = 100;
+        const TOLERANCE: f64 = 1e-9;
+
+        let n = self.notes.len();
+        if n == 0 {
+            return Vec::new();
+        }
+
+        let mut ranks = vec![1.0 / n as f64; n];
+        for _ in 0..MAX_ITERATIONS {
+            let dangling: f64 = self
+                .outgoing
+                .iter()
+                .zip(&ranks)
+                .filter(|(targets, _)| targets.is_empty())
+                .map(|(_, rank)| rank)
+                .sum();
+            let base = (1.0 - DAMPING) / n as f64 + DAMPING * dangling / n as f64;
+
+            let mut next = vec![base; n];
+            for (targets, rank) in self.outgoing.iter().zip(&ranks) {
+                let share = DAMPING * rank / targets.len().max(1) as f64;
+                for &target in targets {
+                    next[target] += share;
+                }
+            }
+
+            let delta: f64 = next.iter().zip(&ranks).map(|(a, b)| (a - b).abs()).sum();
+            ranks = next;
+            if delta < TOLERANCE {
+                break;
+            }
+        }
+        ranks
+    }
+
+    /// Connected components, ignoring link direction
+    ///
+    /// Each component lists its note ids in ascending order; components are
+    /// sorted largest first.
+    pub fn components(&self) -> Vec<Vec<usize>> {
+        let n = self.notes.len();
+        let mut neighbours = vec![Vec::new(); n];
+        for (source, targets) in self.outgoing.iter().enumerate() {
+            for &target in targets {
+                neighbours[source].push(target);
+                neighbours[target].push(source);
+            }
+        }
+
+        let mut seen = vec![false; n];
+        let mut components = Vec::new();
+        for start in 0..n {
+            if seen[start] {
+                continue;
+            }
+            seen[start] = true;
+            let mut component = vec![start];
+            let mut stack = vec![start];
+            while let Some(node) = stack.pop() {
+                for &next in &neighbours[node] {
+                    if !seen[next] {
+                        seen[next] = true;
+                        component.push(next);
+                        stack.push(next);
+                    }
+                }
+            }
+            component.sort_unstable();
+            components.push(component);
+        }
+
+        components.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a[0].cmp(&b[0])));
+        components
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index() -> LinkIndex {
+        LinkIndex::new(vec![
+            (PathBuf::from("Inbox.md"), vec![]),
+            (
+                PathBuf::from("projects/Alpha.md"),
+                vec!["Project A".to_string()],
+            ),
+            (PathBuf::from("archive/2024/Alpha.md"), vec![]),
+            (
+                PathBuf::from("people/Bob Smith.md"),
+                vec!["Bob".to_string()],
+            ),
+            (PathBuf::from("Bob.md"), vec![]),
+        ])
+    }
+
+    #[test]
+    fn test_parse_wikilink() {
+        assert_eq!(
+            parse_wikilink("[[Projects/Alpha.md#Goals#Q1|the plan]]").unwrap(),
+            WikiLink {
+                target: "Projects/Alpha".to_string(),
+                heading: Some("Goals#Q1".to_string()),
+                block: None,
+                alias: Some("the plan".to_string()),
+                embed: false,
+            }
+        );
+
+        let link = parse_wikilink("![[Note#^abc123]]").unwrap();
+        assert_eq!(link.target, "Note");
+        assert_eq!(link.block.as_deref(), Some("abc123"));
+        assert!(link.embed);
+
+        // Brackets are optional, and same-note anchors have an empty target
+        assert_eq!(parse_wikilink("Inbox").unwrap().target, "Inbox");
+        assert_eq!(parse_wikilink("[[#Tasks]]").unwrap().target, "");
+        assert!(parse_wikilink("[[ ]]").is_none());
+        assert!(parse_wikilink("[[#]]").is_none());
+    }
+
+    #[test]
+    fn test_note_aliases() {
+        let content = "---\naliases:\n  - Project A\n  - PA\nalias: Alpha, PA\n---\n# Alpha\n";
+        assert_eq!(note_aliases(content), vec!["Project A", "PA", "Alpha"]);
+        assert!(note_aliases("# No frontmatter\n").is_empty());
+        assert_eq!(note_aliases("---\naliases: [PA]\n---"), vec!["PA"]);
+        assert!(note_aliases("").is_empty());
+        assert!(note_aliases("---\n---").is_empty());
+    }
+
+    #[test]
+    fn test_extract_links_edge_cases() {
+        let extractor = LinkExtractor::new();
+        let source = Path::new("Note.md");
+
+        assert!(extractor.extract_links("", source).is_empty());
+        let links = extractor.extract_links("---\nup: \"[[Home]]\"\n---", source);
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].target, "Home");
+        let links = extractor.extract_links("Last line [[Alpha]]", source);
+        assert_eq!(links.len(), 1);
+    }
+
+    #[test]
+    fn test_resolve_by_name_and_path() {
+        let index = index();
+
+        let resolved = index.resolve("inbox", None).unwrap();
+        assert_eq!(resolved.path, Path::new("Inbox.md"));
+        assert_eq!(resolved.resolved_by, ResolvedBy::Path);
+
+        // Ambiguous names prefer the shortest path
+        let resolved = index.resolve("Alpha", None).unwrap();
+        assert_eq!(resolved.path, Path::new("projects/Alpha.md"));
+        assert_eq!(
+            resolved.other_candidates,
+            vec![Path::new("archive/2024/Alpha.md")]
+        );
+
+        // ...unless one sits next to the linking note
+        let resolved = index
+            .resolve("Alpha", Some(Path::new("archive/2024/Notes.md")))
+            .unwrap();
+        assert_eq!(resolved.path, Path::new("archive/2024/Alpha.md"));
+
+        // Partial paths must match whole trailing components
+        let resolved = index.resolve("2024/Alpha.md", None).unwrap();
+        assert_eq!(resolved.path, Path::new("archive/2024/Alpha.md"));
+        assert!(resolved.other_candidates.is_empty());
+        assert!(index.resolve("024/Alpha", None).is_none());
+
+        assert!(index.resolve("Missing", None).is_none());
+    }
+
+    #[test]
+    fn test_resolve_by_alias() {
+        let index = index();
+
+        let resolved = index.resolve("project a", None).unwrap();
+        assert_eq!(resolved.path, Path::new("projects/Alpha.md"));
+        assert_eq!(resolved.resolved_by, ResolvedBy::Alias);
+
+        // A note name wins over another note's alias
+        let resolved = index.resolve("Bob", None).unwrap();
+        assert_eq!(resolved.path, Path::new("Bob.md"));
+        assert_eq!(resolved.resolved_by, ResolvedBy::Path);
+    }
+
+    #[test]
+    fn test_resolve_ignores_normalization_form() {
+        // A note synced from macOS has a decomposed name
+        let index = LinkIndex::new([(
+            PathBuf::from("Recipes/Cafe\u{301} au lait.md"),
+            vec!["Cafe\u{301}".to_string()],
+        )]);
+
+        let resolved = index.resolve("caf\u{e9} au lait", None).unwrap();
+        assert_eq!(resolved.resolved_by, ResolvedBy::Path);
+        let resolved = index.resolve("recipes/CAF\u{c9} AU LAIT", None).unwrap();
+        assert_eq!(resolved.resolved_by, ResolvedBy::Path);
+        let resolved = index.resolve("Caf\u{e9}", None).unwrap();
+        assert_eq!(resolved.resolved_by, ResolvedBy::Alias);
+
+        let content = "# Cafe\u{301} notes\n";
+        let link = parse_wikilink("[[Cafe#caf\u{e9} notes]]").unwrap();
+        assert_eq!(find_anchor_line(content, &link), Some(1));
+    }
+
+    #[test]
+    fn test_find_anchor_line() {
+        let content = "# Alpha\n\n## Goals\n\n### Q1\n\nShip it ^ship\n\n## Notes\n\n### Q1\n";
+        let link = |text| parse_wikilink(text).unwrap();
+
+        assert_eq!(find_anchor_line(content, &link("[[Alpha#goals]]")), Some(3));
+        assert_eq!(
+            find_anchor_line(content, &link("[[Alpha#Notes#Q1]]")),
+            Some(11)
+        );
+        assert_eq!(find_anchor_line(content, &link("[[Alpha#Q1]]")), Some(5));
+        assert_eq!(find_anchor_line(content, &link("[[Alpha#^ship]]")), Some(7));
+        assert_eq!(find_anchor_line(content, &link("[[Alpha#Missing]]")), None);
+        assert_eq!(find_anchor_line(content, &link("[[Alpha]]")), None);
+    }
+
+    #[test]
+    fn test_extract_links() {
+        let extractor = LinkExtractor::new();
+        let content = "See [[Alpha#Goals|goals]] and ![[diagram.png]].\n\
+                       Also [the plan](../plans/Q1%20Plan.md#Scope) and [site](https://example.com/a.md).\n\
+                       `[[Not a link]]`\n\
+                       ```\n[[Inside code]]\n```\n";
+
+        let links = extractor.extract_links(content, Path::new("projects/Alpha.md"));
+        let targets: Vec<_> = links.iter().map(|l| l.target.as_str()).collect();
+        assert_eq!(targets, vec!["Alpha", "diagram.png", "plans/Q1 Plan"]);
+        assert_eq!(links[2].heading.as_deref(), Some("Scope"));
+        assert_eq!(links[2].alias.as_deref(), Some("the plan"));
+        assert!(links[1].embed);
+
+        assert_eq!(relative_to(Path::new("a/b/Note.md"), "/x/Y.md"), "x/Y");
+        assert_eq!(relative_to(Path::new("Note.md"), "../../Y.md"), "Y");
+    }
+
+    #[test]
+    fn test_rewrite_heading_anchors() {
+        let extractor = LinkExtractor::new();
+        let content = "[[Alpha#Goals|goals]] ![[Alpha#goals]] [[Alpha#Notes]] [[#Goals]]\r\n\
+                       [plan](Alpha.md#Goals) `[[Alpha#Goals]]` [[Alpha#Goals#Q1]]\n\
+                       ```\n[[Alpha#Goals]]\n```\n";
+
+        let (updated, changed) =
+            extractor.rewrite_heading_anchors(content, Path::new("Beta.md"), |link| {
+                let heading = link.heading.as_deref()?;
+                let (first, rest) = heading.split_once('#').unwrap_or((heading, ""));
+                (fold(first) == "goals").then(|| {
+                    let mut heading = "Aims and Scope".to_string();
+                    if !rest.is_empty() {
+                        heading.push('#');
+                        heading.push_str(rest);
+                    }
+                    heading
+                })
+            });
+
+        assert_eq!(changed, 5);
+        assert_eq!(
+            updated,
+            "[[Alpha#Aims and Scope|goals]] ![[Alpha#Aims and Scope]] [[Alpha#Notes]] [[#Aims and Scope]]\r\n\
+             [plan](Alpha.md#Aims%20and%20Scope) `[[Alpha#Goals]]` [[Alpha#Aims and Scope#Q1]]\n\
+             ```\n[[Alpha#Goals]]\n```\n"
+        );
+    }
+
+    #[test]
+    fn test_link_graph_metrics() {
+        let index = LinkIndex::new(
+            [
+                "Hub.md",
+                "A.md",
+                "B.md",
+                "C.md",
+                "D.md",
+                "E.md",
+                "Orphan.md",
+            ]
+            .into_iter()
+            .map(|path| (PathBuf::from(path), Vec::new())),
+        );
+        let links = |targets: &[&str]| {
+            targets
+                .iter()
+                .map(|t| parse_wikilink(t).unwrap())
+                .collect::<Vec<_>>()
+        };
+        let graph = LinkGraph::new(
+            &index,
+            vec![
+                links(&["A", "Hub", "Missing", "pic.png"]),
+                links(&["Hub", "Hub"]),
+                links(&["Hub"]),
+                links(&["Hub", "#Local"]),
+                links(&["E"]),
+                links(&[]),
+                links(&[]),
+            ],
+        );
+
+        // Duplicate, self, same-note, and attachment links are ignored
+        assert_eq!(graph.outgoing[0], vec![1]);
+        assert_eq!(graph.outgoing[1], vec![0]);
+        assert_eq!(graph.unresolved, vec![1, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(graph.in_degrees(), vec![3, 1, 0, 0, 0, 1, 0]);
+
+        let ranks = graph.pagerank();
+        assert!((ranks.iter().sum::<f64>() - 1.0).abs() < 1e-6);
+        let top = (0..ranks.len())
+            .max_by(|&a, &b| ranks[a].total_cmp(&ranks[b]))
+            .unwrap();
+        assert_eq!(top, 0);
+
+        assert_eq!(
+            graph.components(),
+            vec![vec![0, 1, 2, 3], vec![4, 5], vec![6]]
+        );
+    }
+}
@@ -0,0 +1,258 @@
+//! Day and month names for non-English vaults
+//!
+//! The `locale` config option picks the language of the `dddd` (weekday) and
+//! `MMMM` (month) tokens in `daily_note_patterns`, e.g. `YYYY-MM-DD dddd.md`
+//! for `2025-01-20 Montag.md`. Free-text queries also understand its weekday
+//! names and its words for yesterday, today, and tomorrow, alongside the
+//! English ones; the rest of a query stays English ("due before Freitag").
+//! Names are spelled and capitalized as Obsidian writes them for the locale.
+
+use crate::unicode::fold;
+use chrono::Weekday;
+use serde::Deserialize;
+
+/// Language of day and month names
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    En,
+    De,
+    Fr,
+    Es,
+    It,
+    Nl,
+    Pt,
+}
+
+impl Locale {
+    /// Weekday names, Monday first
+    fn weekday_names(self) -> [&'static str; 7] {
+        match self {
+            Locale::En => [
+                "Monday",
+                "Tuesday",
+                "Wednesday",
+                "Thursday",
+                "Friday",
+                "Saturday",
+                "Sunday",
+            ],
+            Locale::De => [
+                "Montag",
+                "Dienstag",
+                "Mittwoch",
+                "Donnerstag",
+                "Freitag",
+                "Samstag",
+                "Sonntag",
+            ],
+            Locale::Fr => [
+                "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi", "dimanche",
+            ],
+            Locale::Es => [
+                "lunes",
+                "martes",
+                "miércoles",
+                "jueves",
+                "viernes",
+                "sábado",
+                "domingo",
+            ],
+            Locale::It => [
+                "lunedì",
+                "martedì",
+                "mercoledì",
+                "giovedì",
+                "venerdì",
+                "sabato",
+                "domenica",
+            ],
+            Locale::Nl => [
+                "maandag",
+                "dinsdag",
+                "woensdag",
+                "donderdag",
+                "vrijdag",
+                "zaterdag",
+                "zondag",
+            ],
+            Locale::Pt => [
+                "segunda-feira",
+                "terça-feira",
+                "quarta-feira",
+                "quinta-feira",
+                "sexta-feira",
+                "sábado",
+                "domingo",
+            ],
+        }
+    }
+
+    /// Month names, January first
+    fn month_names(self) -> [&'static str; 12] {
+        match self {
+            Locale::En => [
+                "January",
+                "February",
+                "March",
+                "April",
+                "May",
+                "June",
+                "July",
+                "August",
+                "September",
+                "October",
+                "November",
+                "December",
+            ],
+            Locale::De => [
+                "Januar",
+                "Februar",
+                "März",
+                "April",
+                "Mai",
+                "Juni",
+                "Juli",
+                "August",
+                "September",
+                "Oktober",
+                "November",
+                "Dezember",
+            ],
+            Locale::Fr => [
+                "janvier",
+                "février",
+                "mars",
+                "avril",
+                "mai",
+                "juin",
+                "juillet",
+                "août",
+                "septembre",
+                "octobre",
+                "novembre",
+                "décembre",
+            ],
+            Locale::Es => [
+                "enero",
+                "febrero",
+                "marzo",
+                "abril",
+                "mayo",
+                "junio",
+                "julio",
+                "agosto",
+                "septiembre",
+                "octubre",
+                "noviembre",
+                "diciembre",
+            ],
+            Locale::It => [
+                "gennaio",
+                "febbraio",
+                "marzo",
+                "aprile",
+                "maggio",
+                "giugno",
+                "luglio",
+                "agosto",
+                "settembre",
+                "ottobre",
+                "novembre",
+                "dicembre",
+            ],
+            Locale::Nl => [
+                "januari",
+                "februari",
+                "maart",
+                "april",
+                "mei",
+                "juni",
+                "juli",
+                "augustus",
+                "september",
+                "oktober",
+                "november",
+                "december",
+            ],
+            Locale::Pt => [
+                "janeiro",
+                "fevereiro",
+                "março",
+                "abril",
+                "maio",
+                "junho",
+                "julho",
+                "agosto",
+                "setembro",
+                "outubro",
+                "novembro",
+                "dezembro",
+            ],
+        }
+    }
+
+    /// Words for yesterday, today, and tomorrow
+    fn relative_days(self) -> [&'static str; 3] {
+        match self {
+            Locale::En => ["yesterday", "today", "tomorrow"],
+            Locale::De => ["gestern", "heute", "morgen"],
+            Locale::Fr => ["hier", "aujourd'hui", "demain"],
+            Locale::Es => ["ayer", "hoy", "mañana"],
+            Locale::It => ["ieri", "oggi", "domani"],
+            Locale::Nl => ["gisteren", "vandaag", "morgen"],
+            Locale::Pt => ["ontem", "hoje", "amanhã"],
+        }
+    }
+
+    /// Name of `weekday`
+    pub fn weekday_name(self, weekday: Weekday) -> &'static str {
+        self.weekday_names()[weekday.num_days_from_monday() as usize]
+    }
+
+    /// Name of `month` (1 for January)
+    pub fn month_name(self, month: u32) -> Option<&'static str> {
+        self.month_names()
+            .get(month.checked_sub(1)? as usize)
+            .copied()
+    }
+
+    /// The weekday `word` names, ignoring case
+    pub fn parse_weekday(self, word: &str) -> Option<Weekday> {
+        let word = fold(word);
+        let index = self
+            .weekday_names()
+            .iter()
+            .position(|name| fold(name) == word)?;
+        Weekday::try_from(index as u8).ok()
+    }
+
+    /// Days from today that `word` means: -1 for yesterday, 0 for today, 1
+    /// for tomorrow
+    pub fn parse_relative_day(self, word: &str) -> Option<i64> {
+        let word = fold(word);
+        let index = self
+            .relative_days()
+            .iter()
+            .position(|name| fold(name) == word)?;
+        Some(index as i64 - 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_names() {
+        assert_eq!(Locale::De.weekday_name(Weekday::Mon), "Montag");
+        assert_eq!(Locale::Fr.month_name(8), Some("août"));
+        assert_eq!(Locale::En.month_name(13), None);
+        assert_eq!(Locale::De.parse_weekday("FREITAG"), Some(Weekday::Fri));
+        assert_eq!(Locale::Es.parse_weekday("miércoles"), Some(Weekday::Wed));
+        assert_eq!(Locale::De.parse_weekday("friday"), None);
+        assert_eq!(Locale::Fr.parse_relative_day("demain"), Some(1));
+        assert_eq!(Locale::Nl.parse_relative_day("gisteren"), Some(-1));
+    }
+}
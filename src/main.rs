@@ -1,24 +1,19 @@
-mod capabilities;
-mod cli;
-mod cli_router;
-mod config;
-mod error;
-mod extractor;
-mod filter;
-mod http_router;
-mod mcp;
-mod operation;
-mod outline_extractor;
-mod tag_extractor;
-
 use clap::FromArgMatches;
-use cli::{ServeCommand, ServerMode};
-use mcp::TaskSearchService;
+use markdown_todo_extractor::cli::{self, ServeCommand, ServerMode};
+use markdown_todo_extractor::mcp::TaskSearchService;
+use markdown_todo_extractor::notifier::Notifier;
+use markdown_todo_extractor::scheduler::Scheduler;
+use markdown_todo_extractor::watcher::VaultWatcher;
+use markdown_todo_extractor::{
+    audit, auth, caldav, capabilities, check, cli_router, completion_dates, config, error, export,
+    feed, http_router, import, index, profile, run, schema,
+};
 use rmcp::{
     ServiceExt,
     transport::{stdio, streamable_http_server::session::local::LocalSessionManager},
 };
 use std::sync::Arc;
+use std::time::Duration;
 
 #[global_allocator]
 static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
@@ -47,6 +42,13 @@ async fn tools_handler(
     Json(json!({ "tools": tools }))
 }
 
+/// Handler for the schema listing: request and response schemas per operation
+async fn schemas_handler(
+    axum::extract::State(registry): axum::extract::State<Arc<capabilities::CapabilityRegistry>>,
+) -> impl axum::response::IntoResponse {
+    axum::Json(schema::operation_schemas(&registry.create_operations()))
+}
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     use capabilities::CapabilityRegistry;
@@ -60,6 +62,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Get all operations including serve
     let mut operations = registry.create_operations();
     operations.push(Arc::new(cli::ServeOperation::new()));
+    operations.push(Arc::new(export::ExportOperation::new()));
+    operations.push(Arc::new(schema::SchemaOperation::new()));
+    operations.push(Arc::new(run::RunOperation::new()));
+    operations.push(Arc::new(check::CheckOperation::new()));
+    operations.push(Arc::new(profile::ProfileOperation::new()));
+    operations.push(Arc::new(index::IndexOperation::new()));
+    operations.push(Arc::new(import::ImportOperation::new()));
+    operations.push(Arc::new(completion_dates::FixOperation::new()));
+    #[cfg(feature = "sqlite")]
+    operations.push(Arc::new(
+        markdown_todo_extractor::sqlite_index::ReindexOperation::new(),
+    ));
 
     // Build CLI from operations
     let cli = cli_router::build_cli(&operations);
@@ -72,6 +86,44 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         // Parse the serve command
         let serve_cmd = ServeCommand::from_arg_matches(serve_matches)?;
         let base_path = serve_cmd.mode.path().clone();
+        http_router::set_operation_timeout(
+            (serve_cmd.timeout > 0).then(|| Duration::from_secs(serve_cmd.timeout)),
+        );
+
+        let vault_config = Arc::new(config::Config::load_from_base_path(&base_path));
+
+        // Record calls over HTTP, MCP, and gRPC in the audit log
+        if let Some(log) = audit::AuditLog::open(&base_path, &vault_config)
+            .map_err(|e| format!("Failed to open audit log: {}", e))?
+        {
+            eprintln!("Recording calls in audit log {}", log.path().display());
+            audit::install(log);
+        }
+
+        // Start scheduled reports from the vault configuration
+        let scheduler = Scheduler::new(base_path.clone(), Arc::clone(&vault_config))
+            .map_err(|e| e.to_string())?;
+        if !scheduler.is_empty() {
+            eprintln!("Scheduled {} report(s)", scheduler.len());
+            tokio::spawn(scheduler.run());
+        }
+
+        // Start due-task push notifications
+        if let Some(notifier) =
+            Notifier::new(base_path.clone(), vault_config).map_err(|e| e.to_string())?
+        {
+            eprintln!("Due-task notifications enabled");
+            tokio::spawn(notifier.run());
+        }
+
+        // Keep the index in memory, evicting files as they change
+        let _watcher = if serve_cmd.watch {
+            let watcher = VaultWatcher::start(&base_path).map_err(|e| e.to_string())?;
+            eprintln!("Watching {} for changes", base_path.display());
+            Some(watcher)
+        } else {
+            None
+        };
 
         match serve_cmd.mode {
             ServerMode::Stdio { .. } => {
@@ -90,7 +142,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
                 return Ok(());
             }
-            ServerMode::Http { port, .. } => {
+            ServerMode::Http {
+                port,
+                #[cfg(feature = "grpc")]
+                grpc_port,
+                ..
+            } => {
                 // Start HTTP MCP server
                 use rmcp::transport::streamable_http_server::StreamableHttpService;
 
@@ -114,11 +171,28 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 let mut router = axum::Router::new()
                     .nest_service("/mcp", service)
                     .route("/tools", axum::routing::get(tools_handler))
-                    .with_state(capability_registry.clone());
+                    .route(schema::HTTP_PATH, axum::routing::get(schemas_handler))
+                    .with_state(capability_registry.clone())
+                    .merge(caldav::router(base_path.clone(), config.clone()))
+                    .merge(feed::router(base_path.clone(), config.clone()))
+                    // With API keys configured, these routes need an
+                    // unrestricted key; the REST operations check their own
+                    .layer(axum::middleware::from_fn_with_state(
+                        config.clone(),
+                        auth::require_unrestricted,
+                    ));
 
                 // Automatically register all HTTP operations
                 for operation in capability_registry.create_operations() {
-                    router = http_router::register_operation(router, operation);
+                    router = http_router::register_operation(
+                        router,
+                        operation,
+                        capability_registry.clone(),
+                    );
+                }
+
+                if !config.api_keys.is_empty() {
+                    eprintln!("Requiring one of {} API keys", config.api_keys.len());
                 }
 
                 let addr = format!("0.0.0.0:{}", port);
@@ -126,6 +200,23 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
                 eprintln!("HTTP MCP server listening on http://{}/mcp", addr);
                 eprintln!("Tools documentation available at http://{}/tools", addr);
+                eprintln!(
+                    "JSON Schemas available at http://{}{}",
+                    addr,
+                    schema::HTTP_PATH
+                );
+                eprintln!(
+                    "CalDAV task collection available at http://{}{}",
+                    addr,
+                    caldav::TASKS_COLLECTION
+                );
+                eprintln!(
+                    "Atom feeds available at http://{}{} and http://{}{}",
+                    addr,
+                    feed::NOTES_FEED_PATH,
+                    addr,
+                    feed::COMPLETED_FEED_PATH
+                );
                 eprintln!("REST API available at:");
 
                 // Dynamically print all registered operations
@@ -138,6 +229,30 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     );
                 }
 
+                #[cfg(feature = "grpc")]
+                if let Some(grpc_port) = grpc_port {
+                    let grpc_addr = std::net::SocketAddr::from(([0, 0, 0, 0], grpc_port));
+                    let grpc_service =
+                        markdown_todo_extractor::grpc_router::GrpcOperationService::new(
+                            capability_registry.create_operations(),
+                        )
+                        .with_api_keys(config.clone());
+
+                    eprintln!("gRPC server listening on {}", grpc_addr);
+
+                    tokio::spawn(async move {
+                        let result = tonic::transport::Server::builder()
+                            .add_routes(grpc_service.into_routes())
+                            .serve_with_shutdown(grpc_addr, async {
+                                tokio::signal::ctrl_c().await.ok();
+                            })
+                            .await;
+                        if let Err(e) = result {
+                            eprintln!("gRPC server error: {}", e);
+                        }
+                    });
+                }
+
                 axum::serve(listener, router.into_make_service())
                     .with_graceful_shutdown(async {
                         tokio::signal::ctrl_c().await.ok();
@@ -149,6 +264,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
-    // For all other commands, use the cli_router
-    cli_router::execute_cli(&operations, matches, &registry).await
+    // For all other commands, use the cli_router; errors exit with the code
+    // for their kind so scripts can tell them apart
+    if let Err(e) = cli_router::execute_cli(&operations, matches, &registry).await {
+        eprintln!("Error: {}", e);
+        std::process::exit(error::exit_code(e.as_ref()));
+    }
+    Ok(())
 }
@@ -1,27 +1,106 @@
-use crate::capabilities::CapabilityRegistry;
+use crate::audit::{self, AuditEntry, Interface};
+use crate::capabilities::activity::{
+    CompletionHeatmapRequest, CompletionHeatmapResponse, NoteTaskSummaryRequest,
+    NoteTaskSummaryResponse, ProjectBurndownRequest, ProjectBurndownResponse, TimeReportRequest,
+    TimeReportResponse,
+};
 use crate::capabilities::daily_notes::{
-    GetDailyNoteRequest, GetDailyNoteResponse, SearchDailyNotesRequest, SearchDailyNotesResponse,
+    AppendDailyNoteRequest, AppendDailyNoteResponse, GetDailyNoteRequest, GetDailyNoteResponse,
+    SearchDailyNotesRequest, SearchDailyNotesResponse,
 };
+use crate::capabilities::digest::{SendDigestRequest, SendDigestResponse};
 use crate::capabilities::files::{
     ListFilesRequest, ListFilesResponse, ReadFilesRequest, ReadFilesResponse,
 };
+use crate::capabilities::flashcards::{ExtractFlashcardsRequest, ExtractFlashcardsResponse};
+use crate::capabilities::highlights::{ExtractHighlightsRequest, ExtractHighlightsResponse};
+use crate::capabilities::inbox::{CaptureRequest, CaptureResponse};
+use crate::capabilities::links::{
+    GraphMetricsRequest, GraphMetricsResponse, OutgoingLinksRequest, OutgoingLinksResponse,
+    ResolveLinkRequest, ResolveLinkResponse,
+};
+use crate::capabilities::meetings::{
+    ExtractActionItemsRequest, ExtractActionItemsResponse, PersonReportRequest,
+    PersonReportResponse,
+};
+use crate::capabilities::outline::{RenameHeadingRequest, RenameHeadingResponse};
+use crate::capabilities::properties::{SetPropertyRequest, SetPropertyResponse};
+use crate::capabilities::search::{SearchContentRequest, SearchContentResponse};
 use crate::capabilities::tags::{
     ExtractTagsRequest, ExtractTagsResponse, ListTagsRequest, ListTagsResponse,
-    SearchByTagsRequest, SearchByTagsResponse,
+    SearchByTagsRequest, SearchByTagsResponse, SuggestTagsRequest, SuggestTagsResponse,
+    TagsByFolderRequest, TagsByFolderResponse,
+};
+use crate::capabilities::task_write::{
+    AddTaskRequest, AddTaskResponse, CompleteTaskRequest, CompleteTaskResponse, MoveTaskRequest,
+    MoveTaskResponse,
+};
+use crate::capabilities::tasks::{
+    AskTasksRequest, AskTasksResponse, ExportIcsRequest, ExportIcsResponse, QueryTasksRequest,
+    QueryTasksResponse, SearchTasksRequest, SuggestTaskMergesRequest, SuggestTaskMergesResponse,
+    TaskSearchResponse,
+};
+use crate::capabilities::views::{
+    ListViewsRequest, ListViewsResponse, MaterializeViewRequest, MaterializeViewResponse,
+    RunViewRequest, RunViewResponse,
+};
+use crate::capabilities::{
+    CapabilityRegistry, SUBVAULT_DESCRIPTION, SUBVAULT_PARAM, take_subvault,
 };
-use crate::capabilities::tasks::{SearchTasksRequest, TaskSearchResponse};
 use crate::config::Config;
+use crate::extractor::Task;
+use crate::http_router::spawn_with_timeout;
 use rmcp::{
-    ServerHandler,
+    Peer, RoleServer, ServerHandler,
+    handler::server::tool::ToolCallContext,
     handler::server::{
         router::tool::ToolRouter,
         wrapper::{Json, Parameters},
     },
     model::*,
-    tool, tool_handler, tool_router,
+    service::RequestContext,
+    tool, tool_router,
 };
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::mpsc;
+
+/// Most tasks sent in one progress notification by a streamed search
+const STREAM_BATCH: usize = 100;
+
+/// Send the matches of a streamed `search_tasks` call as progress
+/// notifications, each message a JSON `{"tasks": [...]}` batch
+///
+/// MCP has no partial tool results, so the batches ride in the progress
+/// `message`, which the spec meant for text. Clients that don't know this
+/// format just show the JSON as a status line; the tool result still holds
+/// every streamed task.
+async fn stream_matches(
+    peer: Peer<RoleServer>,
+    token: ProgressToken,
+    mut receiver: mpsc::UnboundedReceiver<Vec<Task>>,
+) {
+    let mut sent = 0;
+    while let Some(mut batch) = receiver.recv().await {
+        // Files scanned while the last batch was sent go out together
+        while batch.len() < STREAM_BATCH
+            && let Ok(more) = receiver.try_recv()
+        {
+            batch.extend(more);
+        }
+        sent += batch.len();
+        let notification = ProgressNotificationParam {
+            progress_token: token.clone(),
+            progress: sent as f64,
+            total: None,
+            message: serde_json::to_string(&serde_json::json!({ "tasks": batch })).ok(),
+        };
+        if peer.notify_progress(notification).await.is_err() {
+            break;
+        }
+    }
+}
 
 /// MCP Service for task searching and tag extraction
 #[derive(Clone)]
@@ -49,23 +128,151 @@ impl TaskSearchService {
     }
 
     #[tool(
-        description = "Search for tasks in Markdown files with optional filtering by status, dates, and tags"
+        description = "Search for tasks in Markdown files with optional filtering by status, dates, and tags. Results are paged with limit and offset; total_count and has_more tell whether more tasks match. With a progress token, the tasks of the page are also streamed as progress notifications (message: {\"tasks\": [...]}) while the vault is scanned; the page is then the first limit matches found, and searches with sort_by, sort_order, offset, group_by, blocked, or strict are not streamed"
     )]
     async fn search_tasks(
         &self,
         Parameters(request): Parameters<SearchTasksRequest>,
+        meta: Meta,
+        peer: Peer<RoleServer>,
     ) -> Result<Json<TaskSearchResponse>, ErrorData> {
+        // Delegate to TaskCapability
+        let capability = self.capability_registry.tasks();
+        let Some(token) = meta.get_progress_token() else {
+            return Ok(Json(capability.search_tasks(request).await?));
+        };
+
+        // With a progress token, matches go out as progress notifications
+        // while the vault is scanned
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let forward = tokio::spawn(stream_matches(peer, token, receiver));
+        let on_match = move |matches: Vec<Task>| {
+            let _ = sender.send(matches);
+        };
+        let response = capability
+            .search_tasks_streaming(request, Some(&on_match))
+            .await;
+        drop(on_match);
+        // Every notification is sent before the result
+        let _ = forward.await;
+
+        Ok(Json(response?))
+    }
+
+    #[tool(
+        description = "Search tasks with a free-text query such as \"incomplete work tasks due before next Friday\". Dates (today, tomorrow, next friday, this week, in 3 days, YYYY-MM-DD) and tags (fuzzy-matched) are interpreted server-side; the response includes the structured filter that was applied."
+    )]
+    async fn ask_tasks(
+        &self,
+        Parameters(request): Parameters<AskTasksRequest>,
+    ) -> Result<Json<AskTasksResponse>, ErrorData> {
+        // Delegate to TaskCapability
+        let response = self.capability_registry.tasks().ask_tasks(request).await?;
+
+        Ok(Json(response))
+    }
+
+    #[tool(
+        description = "Search tasks with an Obsidian Tasks plugin query, one instruction per line, e.g. \"not done\\ndue before today\\ntags include #work\\npath includes Projects\". Supports done/not done, due/scheduled/starts/done dates, no due date, tags include / do not include, path/description/heading includes, priority is (above), is recurring, is (not) blocked, and limit. Unsupported instructions fail with invalid_params; the response includes the structured filter that was applied."
+    )]
+    async fn query_tasks(
+        &self,
+        Parameters(request): Parameters<QueryTasksRequest>,
+    ) -> Result<Json<QueryTasksResponse>, ErrorData> {
         // Delegate to TaskCapability
         let response = self
             .capability_registry
             .tasks()
-            .search_tasks(request)
+            .query_tasks(request)
             .await?;
 
         Ok(Json(response))
     }
 
-    #[tool(description = "Extract all unique tags from YAML frontmatter in Markdown files")]
+    #[tool(
+        description = "Export tasks with due dates as an iCalendar (.ics) feed, as to-dos (VTODO) or all-day events on the due date (VEVENT), so due dates show up in calendar apps. Only incomplete tasks unless include_completed is set. Over HTTP the body is the calendar itself (text/calendar)"
+    )]
+    async fn export_ics(
+        &self,
+        Parameters(request): Parameters<ExportIcsRequest>,
+    ) -> Result<Json<ExportIcsResponse>, ErrorData> {
+        // Delegate to TaskCapability
+        let response = self.capability_registry.tasks().export_ics(request).await?;
+
+        Ok(Json(response))
+    }
+
+    #[tool(
+        description = "Find probable duplicate tasks across notes (e.g. the same chore captured in two daily notes) by comparing task text without case, tags, or punctuation. Returns pairs of tasks with their similarity from 0 to 1 for review; nothing is changed"
+    )]
+    async fn suggest_task_merges(
+        &self,
+        Parameters(request): Parameters<SuggestTaskMergesRequest>,
+    ) -> Result<Json<SuggestTaskMergesResponse>, ErrorData> {
+        // Delegate to TaskCapability
+        let response = self
+            .capability_registry
+            .tasks()
+            .suggest_task_merges(request)
+            .await?;
+
+        Ok(Json(response))
+    }
+
+    #[tool(
+        description = "Add a new '- [ ]' task to a note, appended at the end of the file or at the end of the section under a named heading. Due date and tags are written in Obsidian Tasks syntax ('#tag', '📅 YYYY-MM-DD'). Returns the line number of the new task."
+    )]
+    async fn add_task(
+        &self,
+        Parameters(request): Parameters<AddTaskRequest>,
+    ) -> Result<Json<AddTaskResponse>, ErrorData> {
+        // Delegate to TaskWriteCapability
+        let response = self
+            .capability_registry
+            .task_write()
+            .add_task(request)
+            .await?;
+
+        Ok(Json(response))
+    }
+
+    #[tool(
+        description = "Mark a task as done: flips '- [ ]' to '- [x]' on the given line of a note and appends a '✅ YYYY-MM-DD' completion date. Takes the file_path and line_number reported by search_tasks. Fails if the line is not an incomplete task."
+    )]
+    async fn complete_task(
+        &self,
+        Parameters(request): Parameters<CompleteTaskRequest>,
+    ) -> Result<Json<CompleteTaskResponse>, ErrorData> {
+        // Delegate to TaskWriteCapability
+        let response = self
+            .capability_registry
+            .task_write()
+            .complete_task(request)
+            .await?;
+
+        Ok(Json(response))
+    }
+
+    #[tool(
+        description = "Move a task and its indented sub-items from one note to another (or to another section of the same note), appended at the end of the target note or of a named heading's section. Takes the file_path and line_number reported by search_tasks. Optionally leaves a '> moved to [[Target]]' breadcrumb where the task was."
+    )]
+    async fn move_task(
+        &self,
+        Parameters(request): Parameters<MoveTaskRequest>,
+    ) -> Result<Json<MoveTaskResponse>, ErrorData> {
+        // Delegate to TaskWriteCapability
+        let response = self
+            .capability_registry
+            .task_write()
+            .move_task(request)
+            .await?;
+
+        Ok(Json(response))
+    }
+
+    #[tool(
+        description = "Extract all unique tags from YAML frontmatter in Markdown files (optionally including inline #tags)"
+    )]
     async fn extract_tags(
         &self,
         Parameters(request): Parameters<ExtractTagsRequest>,
@@ -94,7 +301,7 @@ impl TaskSearchService {
     }
 
     #[tool(
-        description = "Search for files by YAML frontmatter tags with AND/OR matching. Returns files that match the specified tags."
+        description = "Search for files by YAML frontmatter tags with AND/OR matching. Supports glob patterns (project/*) and tag prefixes. Returns files that match the specified tags."
     )]
     async fn search_by_tags(
         &self,
@@ -110,6 +317,40 @@ impl TaskSearchService {
         Ok(Json(response))
     }
 
+    #[tool(
+        description = "Suggest existing vault tags for a note or raw text based on term overlap with notes already carrying those tags. Helps keep the tag taxonomy consistent when filing new notes."
+    )]
+    async fn suggest_tags(
+        &self,
+        Parameters(request): Parameters<SuggestTagsRequest>,
+    ) -> Result<Json<SuggestTagsResponse>, ErrorData> {
+        // Delegate to TagCapability
+        let response = self
+            .capability_registry
+            .tags()
+            .suggest_tags(request)
+            .await?;
+
+        Ok(Json(response))
+    }
+
+    #[tool(
+        description = "Report the most common tags for each folder in the vault (top-level by default, or deeper with depth). Shows how different areas of the vault are organized."
+    )]
+    async fn tags_by_folder(
+        &self,
+        Parameters(request): Parameters<TagsByFolderRequest>,
+    ) -> Result<Json<TagsByFolderResponse>, ErrorData> {
+        // Delegate to TagCapability
+        let response = self
+            .capability_registry
+            .tags()
+            .tags_by_folder(request)
+            .await?;
+
+        Ok(Json(response))
+    }
+
     #[tool(
         description = "List the directory tree of the vault. Returns a hierarchical view of all files and folders. Useful for understanding vault structure and finding files."
     )]
@@ -167,14 +408,469 @@ impl TaskSearchService {
 
         Ok(Json(response))
     }
+
+    #[tool(
+        description = "Append a block of text to the daily note for a date (default today). Creates the note from the first configured daily note pattern if it doesn't exist. The block is separated from existing content by a blank line."
+    )]
+    async fn append_daily_note(
+        &self,
+        Parameters(request): Parameters<AppendDailyNoteRequest>,
+    ) -> Result<Json<AppendDailyNoteResponse>, ErrorData> {
+        // Delegate to DailyNoteCapability
+        let response = self
+            .capability_registry
+            .daily_notes()
+            .append_daily_note(request)
+            .await?;
+
+        Ok(Json(response))
+    }
+
+    #[tool(
+        description = "Get per-day counts of completed tasks over a date range (defaults to the last year), formatted for GitHub-style calendar heatmaps. Optionally includes words written in daily notes."
+    )]
+    async fn completion_heatmap(
+        &self,
+        Parameters(request): Parameters<CompletionHeatmapRequest>,
+    ) -> Result<Json<CompletionHeatmapResponse>, ErrorData> {
+        // Delegate to ActivityCapability
+        let response = self
+            .capability_registry
+            .activity()
+            .completion_heatmap(request)
+            .await?;
+
+        Ok(Json(response))
+    }
+
+    #[tool(
+        description = "Reconstruct open-task counts over time for a tag or folder from task created/completed dates. Returns a time series for charting project progress."
+    )]
+    async fn project_burndown(
+        &self,
+        Parameters(request): Parameters<ProjectBurndownRequest>,
+    ) -> Result<Json<ProjectBurndownResponse>, ErrorData> {
+        // Delegate to ActivityCapability
+        let response = self
+            .capability_registry
+            .activity()
+            .project_burndown(request)
+            .await?;
+
+        Ok(Json(response))
+    }
+
+    #[tool(
+        description = "Total the time logged on tasks (`⏱ 1h30m` or `spent:: 45m` annotations), grouped by tag, file, or day. Useful for lightweight timesheets kept in markdown."
+    )]
+    async fn time_report(
+        &self,
+        Parameters(request): Parameters<TimeReportRequest>,
+    ) -> Result<Json<TimeReportResponse>, ErrorData> {
+        // Delegate to ActivityCapability
+        let response = self
+            .capability_registry
+            .activity()
+            .time_report(request)
+            .await?;
+
+        Ok(Json(response))
+    }
+
+    #[tool(
+        description = "Count incomplete, completed, and cancelled tasks in each note (optionally only under a folder or only tasks with given tags) and report each note's percent complete. Use it to render per-project progress bars without fetching every task."
+    )]
+    async fn note_task_summary(
+        &self,
+        Parameters(request): Parameters<NoteTaskSummaryRequest>,
+    ) -> Result<Json<NoteTaskSummaryResponse>, ErrorData> {
+        // Delegate to ActivityCapability
+        let response = self
+            .capability_registry
+            .activity()
+            .note_task_summary(request)
+            .await?;
+
+        Ok(Json(response))
+    }
+
+    #[tool(
+        description = "Extract action items from meeting notes (tagged #meeting by default). Collects items under the \"Action Items\" heading and tasks with @mentions, and returns them grouped by assigned person along with each meeting's frontmatter attendees."
+    )]
+    async fn extract_action_items(
+        &self,
+        Parameters(request): Parameters<ExtractActionItemsRequest>,
+    ) -> Result<Json<ExtractActionItemsResponse>, ErrorData> {
+        // Delegate to MeetingCapability
+        let response = self
+            .capability_registry
+            .meetings()
+            .extract_action_items(request)
+            .await?;
+
+        Ok(Json(response))
+    }
+
+    #[tool(
+        description = "Prepare for a 1:1: given a name, @handle, or [[Person]] page, gather the tasks that mention the person (or live on their page), the meetings they attended (frontmatter attendees), and the notes that link to their page."
+    )]
+    async fn person_report(
+        &self,
+        Parameters(request): Parameters<PersonReportRequest>,
+    ) -> Result<Json<PersonReportResponse>, ErrorData> {
+        // Delegate to MeetingCapability
+        let response = self
+            .capability_registry
+            .meetings()
+            .person_report(request)
+            .await?;
+
+        Ok(Json(response))
+    }
+
+    #[tool(description = "List the saved task searches (views) defined in the vault configuration")]
+    async fn list_views(
+        &self,
+        Parameters(request): Parameters<ListViewsRequest>,
+    ) -> Result<Json<ListViewsResponse>, ErrorData> {
+        // Delegate to ViewCapability
+        let response = self.capability_registry.views().list_views(request).await?;
+
+        Ok(Json(response))
+    }
+
+    #[tool(
+        description = "Run a saved task search (view) by name. Use list_views to see the available views."
+    )]
+    async fn run_view(
+        &self,
+        Parameters(request): Parameters<RunViewRequest>,
+    ) -> Result<Json<RunViewResponse>, ErrorData> {
+        // Delegate to ViewCapability
+        let response = self.capability_registry.views().run_view(request).await?;
+
+        Ok(Json(response))
+    }
+
+    #[tool(
+        description = "Run a saved task search (view) and write its results into a note, replacing the block between <!-- view:<name> --> and <!-- /view:<name> --> markers (appended to the note, or a new note, the first time). Tasks are written as plain bullets linking to their notes, so they are not picked up as tasks again."
+    )]
+    async fn materialize_view(
+        &self,
+        Parameters(request): Parameters<MaterializeViewRequest>,
+    ) -> Result<Json<MaterializeViewResponse>, ErrorData> {
+        // Delegate to ViewCapability
+        let response = self
+            .capability_registry
+            .views()
+            .materialize_view(request)
+            .await?;
+
+        Ok(Json(response))
+    }
+
+    #[tool(
+        description = "Full-text search across note contents. Supports \"quoted phrases\" and AND/OR (terms are ANDed by default). Results are ranked by relevance and include character offsets of each match for highlighting."
+    )]
+    async fn search_content(
+        &self,
+        Parameters(request): Parameters<SearchContentRequest>,
+    ) -> Result<Json<SearchContentResponse>, ErrorData> {
+        // Delegate to SearchCapability
+        let response = self
+            .capability_registry
+            .search()
+            .search_content(request)
+            .await?;
+
+        Ok(Json(response))
+    }
+
+    #[tool(
+        description = "Resolve a wikilink such as [[Note#Heading|alias]] to the note it points to, using note names, partial paths, and frontmatter aliases like Obsidian. Returns the file path, the line of the linked heading or block, and whether the target exists."
+    )]
+    async fn resolve_link(
+        &self,
+        Parameters(request): Parameters<ResolveLinkRequest>,
+    ) -> Result<Json<ResolveLinkResponse>, ErrorData> {
+        // Delegate to LinkCapability
+        let response = self
+            .capability_registry
+            .links()
+            .resolve_link(request)
+            .await?;
+
+        Ok(Json(response))
+    }
+
+    #[tool(
+        description = "List the wikilinks and Markdown links leaving a note, in order, with their line numbers. Each link is marked as resolved (with the note or attachment it points to) or unresolved, embeds (![[...]]) are flagged, and heading/block anchors are checked."
+    )]
+    async fn outgoing_links(
+        &self,
+        Parameters(request): Parameters<OutgoingLinksRequest>,
+    ) -> Result<Json<OutgoingLinksResponse>, ErrorData> {
+        // Delegate to LinkCapability
+        let response = self
+            .capability_registry
+            .links()
+            .outgoing_links(request)
+            .await?;
+
+        Ok(Json(response))
+    }
+
+    #[tool(
+        description = "Analyze the vault's link graph: per-note in-degree and out-degree, PageRank centrality, and connected components. Returns the most central notes (hubs), clusters disconnected from the main graph, and orphan notes with no links."
+    )]
+    async fn graph_metrics(
+        &self,
+        Parameters(request): Parameters<GraphMetricsRequest>,
+    ) -> Result<Json<GraphMetricsResponse>, ErrorData> {
+        // Delegate to LinkCapability
+        let response = self
+            .capability_registry
+            .links()
+            .graph_metrics(request)
+            .await?;
+
+        Ok(Json(response))
+    }
+
+    #[tool(
+        description = "Email a digest of overdue tasks and tasks due today to the recipients in the vault's [smtp] configuration. Use dry_run to preview the message without sending it."
+    )]
+    async fn send_digest(
+        &self,
+        Parameters(request): Parameters<SendDigestRequest>,
+    ) -> Result<Json<SendDigestResponse>, ErrorData> {
+        // Delegate to DigestCapability
+        let response = self
+            .capability_registry
+            .digest()
+            .send_digest(request)
+            .await?;
+
+        Ok(Json(response))
+    }
+
+    #[tool(
+        description = "Extract flashcards from notes: 'Q:'/'A:' line pairs and bullets tagged #flashcard ('- Term :: Definition #flashcard', or a question bullet whose nested items are the answer). Optionally returns the cards as Anki-importable TSV."
+    )]
+    async fn extract_flashcards(
+        &self,
+        Parameters(request): Parameters<ExtractFlashcardsRequest>,
+    ) -> Result<Json<ExtractFlashcardsResponse>, ErrorData> {
+        // Delegate to FlashcardCapability
+        let response = self
+            .capability_registry
+            .flashcards()
+            .extract_flashcards(request)
+            .await?;
+
+        Ok(Json(response))
+    }
+
+    #[tool(
+        description = "Extract highlights across the vault: blockquoted passages (> ..., excluding callouts) and ==highlighted== spans, each with its note, line number, and nearest heading. Filter by kind and page with limit/offset."
+    )]
+    async fn extract_highlights(
+        &self,
+        Parameters(request): Parameters<ExtractHighlightsRequest>,
+    ) -> Result<Json<ExtractHighlightsResponse>, ErrorData> {
+        // Delegate to HighlightCapability
+        let response = self
+            .capability_registry
+            .highlights()
+            .extract_highlights(request)
+            .await?;
+
+        Ok(Json(response))
+    }
+
+    #[tool(
+        description = "Rename a heading in a markdown file and update links to it across the vault: [[Note#Old Heading]], [[#Old Heading]] within the note, and [text](Note.md#Old%20Heading). Returns every file whose links changed; with dry_run nothing is written."
+    )]
+    async fn rename_heading(
+        &self,
+        Parameters(request): Parameters<RenameHeadingRequest>,
+    ) -> Result<Json<RenameHeadingResponse>, ErrorData> {
+        // Delegate to OutlineCapability
+        let response = self
+            .capability_registry
+            .outline()
+            .rename_heading(request)
+            .await?;
+
+        Ok(Json(response))
+    }
+
+    #[tool(
+        description = "Set a property (top-level YAML frontmatter key) on a note, e.g. status: done. The value is read as YAML, so numbers, booleans, and [lists] keep their type. With append, the value is added to a list property instead (e.g. add a tag). Other frontmatter and the note body are left untouched; a note without frontmatter gets a new block."
+    )]
+    async fn set_property(
+        &self,
+        Parameters(request): Parameters<SetPropertyRequest>,
+    ) -> Result<Json<SetPropertyResponse>, ErrorData> {
+        // Delegate to PropertyCapability
+        let response = self
+            .capability_registry
+            .properties()
+            .set_property(request)
+            .await?;
+
+        Ok(Json(response))
+    }
+
+    #[tool(
+        description = "Quick capture: append a line to the vault's inbox note (inbox_note in the config, default Inbox.md), prefixed with the current date and time. With task, it is added as a '- [ ]' task with a '➕ YYYY-MM-DD' created date instead. The inbox is created if missing."
+    )]
+    async fn capture(
+        &self,
+        Parameters(request): Parameters<CaptureRequest>,
+    ) -> Result<Json<CaptureResponse>, ErrorData> {
+        // Delegate to CaptureCapability
+        let response = self.capability_registry.capture().capture(request).await?;
+
+        Ok(Json(response))
+    }
+}
+
+/// Add the optional `subvault` parameter to a tool's input schema
+fn with_subvault_param(mut tool: Tool) -> Tool {
+    let schema = Arc::make_mut(&mut tool.input_schema);
+    if let Some(properties) = schema
+        .entry("properties")
+        .or_insert_with(|| serde_json::json!({}))
+        .as_object_mut()
+    {
+        properties.insert(
+            SUBVAULT_PARAM.to_string(),
+            serde_json::json!({ "type": "string", "description": SUBVAULT_DESCRIPTION }),
+        );
+    }
+    tool
+}
+
+impl TaskSearchService {
+    /// Route a tool call, scoping it to `subvault` if the arguments have one,
+    /// on its own task under the operation timeout
+    async fn route_tool_call(
+        &self,
+        mut request: CallToolRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let subvault = match request.arguments.as_mut() {
+            Some(arguments) => take_subvault(arguments)?,
+            None => None,
+        };
+
+        let service = match subvault {
+            Some(subvault) => Self {
+                tool_router: self.tool_router.clone(),
+                capability_registry: Arc::new(self.capability_registry.scoped(&subvault)?),
+            },
+            None => self.clone(),
+        };
+        spawn_with_timeout(async move {
+            let tcc = ToolCallContext::new(&service, request, context);
+            service.tool_router.call(tcc).await
+        })
+        .await
+    }
 }
 
-#[tool_handler]
 impl ServerHandler for TaskSearchService {
+    /// Route a tool call, recording it in the audit log
+    async fn call_tool(
+        &self,
+        request: CallToolRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if !audit::enabled() {
+            return self.route_tool_call(request, context).await;
+        }
+
+        let started = Instant::now();
+        let name = request.name.to_string();
+        let params = serde_json::Value::Object(request.arguments.clone().unwrap_or_default());
+        let client = context
+            .peer
+            .peer_info()
+            .map(|info| info.client_info.name.clone());
+        // Present when the session runs over streamable HTTP
+        let session = context
+            .extensions
+            .get::<axum::http::request::Parts>()
+            .and_then(|parts| parts.headers.get("mcp-session-id"))
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+
+        let result = self.route_tool_call(request, context).await;
+        let mut entry = AuditEntry::new(Interface::Mcp, name, params, started).outcome(&result);
+        if let Ok(CallToolResult {
+            is_error: Some(true),
+            ..
+        }) = result
+        {
+            entry.ok = false;
+        }
+        entry.client = client;
+        entry.session = session;
+        audit::record(entry);
+        result
+    }
+
+    async fn list_tools(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListToolsResult, ErrorData> {
+        Ok(ListToolsResult {
+            tools: self
+                .tool_router
+                .list_all()
+                .into_iter()
+                .map(with_subvault_param)
+                .collect(),
+            meta: None,
+            next_cursor: None,
+        })
+    }
+
     fn get_info(&self) -> ServerInfo {
         // Build instructions from capability metadata
         let instructions = format!(
             "A Markdown task extraction service. Available operations:\n\
+             - {}\n\
+             - {}\n\
+             - {}\n\
+             - {}\n\
+             - {}\n\
+             - {}\n\
+             - {}\n\
+             - {}\n\
+             - {}\n\
+             - {}\n\
+             - {}\n\
+             - {}\n\
+             - {}\n\
+             - {}\n\
+             - {}\n\
+             - {}\n\
+             - {}\n\
+             - {}\n\
+             - {}\n\
+             - {}\n\
+             - {}\n\
+             - {}\n\
+             - {}\n\
+             - {}\n\
+             - {}\n\
+             - {}\n\
+             - {}\n\
+             - {}\n\
+             - {}\n\
              - {}\n\
              - {}\n\
              - {}\n\
@@ -184,13 +880,42 @@ impl ServerHandler for TaskSearchService {
              - {}\n\
              - {}",
             crate::capabilities::tasks::search_tasks::DESCRIPTION,
+            crate::capabilities::tasks::ask_tasks::DESCRIPTION,
+            crate::capabilities::tasks::query_tasks::DESCRIPTION,
+            crate::capabilities::tasks::export_ics::DESCRIPTION,
+            crate::capabilities::tasks::suggest_task_merges::DESCRIPTION,
+            crate::capabilities::task_write::add_task::DESCRIPTION,
+            crate::capabilities::task_write::complete_task::DESCRIPTION,
+            crate::capabilities::task_write::move_task::DESCRIPTION,
             crate::capabilities::tags::extract_tags::DESCRIPTION,
             crate::capabilities::tags::list_tags::DESCRIPTION,
             crate::capabilities::tags::search_by_tags::DESCRIPTION,
+            crate::capabilities::tags::suggest_tags::DESCRIPTION,
+            crate::capabilities::tags::tags_by_folder::DESCRIPTION,
             crate::capabilities::files::list_files::DESCRIPTION,
             crate::capabilities::files::read_files::DESCRIPTION,
             crate::capabilities::daily_notes::get_daily_note::DESCRIPTION,
-            crate::capabilities::daily_notes::search_daily_notes::DESCRIPTION
+            crate::capabilities::daily_notes::search_daily_notes::DESCRIPTION,
+            crate::capabilities::daily_notes::append_daily_note::DESCRIPTION,
+            crate::capabilities::activity::completion_heatmap::DESCRIPTION,
+            crate::capabilities::activity::project_burndown::DESCRIPTION,
+            crate::capabilities::activity::time_report::DESCRIPTION,
+            crate::capabilities::activity::note_task_summary::DESCRIPTION,
+            crate::capabilities::meetings::extract_action_items::DESCRIPTION,
+            crate::capabilities::meetings::person_report::DESCRIPTION,
+            crate::capabilities::views::list_views::DESCRIPTION,
+            crate::capabilities::views::run_view::DESCRIPTION,
+            crate::capabilities::views::materialize_view::DESCRIPTION,
+            crate::capabilities::search::search_content::DESCRIPTION,
+            crate::capabilities::links::resolve_link::DESCRIPTION,
+            crate::capabilities::links::outgoing_links::DESCRIPTION,
+            crate::capabilities::links::graph_metrics::DESCRIPTION,
+            crate::capabilities::digest::send_digest::DESCRIPTION,
+            crate::capabilities::flashcards::extract_flashcards::DESCRIPTION,
+            crate::capabilities::highlights::extract_highlights::DESCRIPTION,
+            crate::capabilities::outline::rename_heading::DESCRIPTION,
+            crate::capabilities::properties::set_property::DESCRIPTION,
+            crate::capabilities::inbox::capture::DESCRIPTION
         );
 
         ServerInfo {
@@ -0,0 +1,721 @@
+//! Natural-language task queries
+//!
+//! Turns free text such as "incomplete work tasks due before next Friday" into
+//! [`FilterOptions`]. Dates are resolved relative to a caller-supplied `today`
+//! and bare words are fuzzy-matched against the tags known in the vault.
+
+use crate::extractor::{Priority, TaskStatus};
+use crate::filter::FilterOptions;
+use crate::locale::Locale;
+use crate::unicode::fold;
+use chrono::{Datelike, Days, Months, NaiveDate, Weekday};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Words that carry no filtering meaning on their own
+const STOPWORDS: &[&str] = &[
+    "a",
+    "all",
+    "am",
+    "an",
+    "and",
+    "any",
+    "are",
+    "at",
+    "everything",
+    "find",
+    "for",
+    "from",
+    "get",
+    "give",
+    "i",
+    "in",
+    "is",
+    "it",
+    "item",
+    "items",
+    "list",
+    "me",
+    "my",
+    "of",
+    "on",
+    "or",
+    "please",
+    "show",
+    "tag",
+    "tagged",
+    "tags",
+    "task",
+    "tasks",
+    "that",
+    "the",
+    "to",
+    "todo",
+    "todos",
+    "was",
+    "were",
+    "what",
+    "which",
+    "with",
+];
+
+/// Words that exclude the tag or status following them
+const NEGATIONS: &[&str] = &["not", "without", "except", "excluding", "no"];
+
+/// A free-text query interpreted as structured filters
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct InterpretedQuery {
+    /// Filters the query was translated into
+    pub filter: FilterOptions,
+    /// Words that were not understood and were ignored
+    pub unrecognized: Vec<String>,
+}
+
+/// Inclusive range of days a date expression refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct DateSpan {
+    start: NaiveDate,
+    end: NaiveDate,
+}
+
+impl DateSpan {
+    fn day(date: NaiveDate) -> Self {
+        DateSpan {
+            start: date,
+            end: date,
+        }
+    }
+}
+
+/// How a date constraint relates to its date
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Comparison {
+    /// Strictly before the start ("before friday")
+    Before,
+    /// On or before the end ("by friday")
+    By,
+    /// Strictly after the end ("after friday")
+    After,
+    /// On or after the start ("since monday")
+    Since,
+    /// Within the span ("due tomorrow", "due next week")
+    On,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DateField {
+    Due,
+    Completed,
+}
+
+/// Interpret a free-text query as task filters
+///
+/// Understands statuses ("incomplete", "done", "cancelled"), "overdue", date
+/// constraints on due or completion dates ("due before next friday",
+/// "completed this week", "due in 3 days"), and tags (`#work` or a bare word
+/// close to a known tag). "not"/"without" before a tag excludes it, and "no
+/// due date" or "undated" selects tasks without a due date, and "high
+/// priority" selects tasks of at least that priority. Weekdays and
+/// yesterday/today/tomorrow may also be written in `locale`.
+pub fn interpret_query(
+    query: &str,
+    known_tags: &[String],
+    today: NaiveDate,
+    locale: Locale,
+) -> InterpretedQuery {
+    let tokens = tokenize(query);
+    let mut filter = FilterOptions::default();
+    let mut unrecognized = Vec::new();
+    let mut negate = false;
+    let mut i = 0;
+
+    while i < tokens.len() {
+        let word = tokens[i].as_str();
+
+        // "due <date>" / "completed <date>"
+        if let Some(field) = date_field(word)
+            && let Some((comparison, span, used)) =
+                parse_constraint(&tokens[i + 1..], today, locale)
+        {
+            apply_date(&mut filter, field, comparison, span);
+            if field == DateField::Completed && filter.status.is_none() {
+                filter.status = Some(TaskStatus::Completed);
+            }
+            negate = false;
+            i += 1 + used;
+            continue;
+        }
+
+        // A date without a field ("before friday") constrains the due date
+        if let Some((comparison, span, used)) = parse_constraint(&tokens[i..], today, locale) {
+            apply_date(&mut filter, DateField::Due, comparison, span);
+            negate = false;
+            i += used;
+            continue;
+        }
+
+        // "no due date" / "undated"
+        if word == "undated"
+            || (word == "no"
+                && tokens.get(i + 1).is_some_and(|w| w == "due")
+                && tokens
+                    .get(i + 2)
+                    .is_some_and(|w| w == "date" || w == "dates"))
+        {
+            filter.no_due_date = true;
+            negate = false;
+            i += if word == "undated" { 1 } else { 3 };
+            continue;
+        }
+
+        // "high priority" / "urgent priority": at least that priority
+        if let Ok(priority) = word.parse::<Priority>()
+            && tokens.get(i + 1).is_some_and(|w| w == "priority")
+        {
+            filter.min_priority = Some(priority);
+            negate = false;
+            i += 2;
+            continue;
+        }
+
+        // "recurring" / "repeating"
+        if word == "recurring" || word == "repeating" {
+            filter.recurring = true;
+            negate = false;
+            i += 1;
+            continue;
+        }
+
+        if NEGATIONS.contains(&word) {
+            negate = true;
+            i += 1;
+            continue;
+        }
+
+        if word == "overdue" {
+            filter.due_before = Some(today.into());
+            filter.status.get_or_insert(TaskStatus::Incomplete);
+        } else if let Some(status) = parse_status(word) {
+            filter.status = Some(match (negate, status) {
+                (true, TaskStatus::Incomplete) => TaskStatus::Completed,
+                (true, _) => TaskStatus::Incomplete,
+                (false, status) => status,
+            });
+        } else if STOPWORDS.contains(&word) {
+            // Filler words are ignored silently
+        } else if let Some(tag) = match_tag(word, known_tags)
+            .or_else(|| word.strip_prefix('#').map(str::to_string))
+            .filter(|tag| !tag.is_empty())
+        {
+            let tags = if negate {
+                filter.exclude_tags.get_or_insert_with(Vec::new)
+            } else {
+                filter.tags.get_or_insert_with(Vec::new)
+            };
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        } else {
+            unrecognized.push(tokens[i].clone());
+        }
+
+        negate = false;
+        i += 1;
+    }
+
+    InterpretedQuery {
+        filter,
+        unrecognized,
+    }
+}
+
+/// Resolve a natural-language date such as "tomorrow", "next friday",
+/// "in 2 weeks", or "2025-01-10" relative to `today`
+///
+/// Ranges ("next week", "this month") resolve to their first day. Weekdays
+/// and yesterday/today/tomorrow may also be written in `locale`.
+pub fn parse_natural_date(text: &str, today: NaiveDate, locale: Locale) -> Option<NaiveDate> {
+    let tokens = tokenize(text);
+    match parse_date(&tokens, today, locale)? {
+        (span, used) if used == tokens.len() => Some(span.start),
+        _ => None,
+    }
+}
+
+/// Lowercase the query and split it into words, trimming punctuation
+fn tokenize(query: &str) -> Vec<String> {
+    query
+        .split_whitespace()
+        .map(|word| {
+            word.trim_matches(|c: char| !(c.is_alphanumeric() || matches!(c, '#' | '-' | '_')))
+                .to_lowercase()
+        })
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+fn date_field(word: &str) -> Option<DateField> {
+    match word {
+        "due" => Some(DateField::Due),
+        "completed" | "done" | "finished" | "closed" => Some(DateField::Completed),
+        _ => None,
+    }
+}
+
+fn parse_status(word: &str) -> Option<TaskStatus> {
+    match word {
+        "incomplete" | "open" | "pending" | "unfinished" | "outstanding" | "remaining" => {
+            Some(TaskStatus::Incomplete)
+        }
+        "complete" | "completed" | "done" | "finished" | "closed" => Some(TaskStatus::Completed),
+        "cancelled" | "canceled" | "dropped" => Some(TaskStatus::Cancelled),
+        _ => None,
+    }
+}
+
+/// Parse an optional comparison word followed by a date expression
+fn parse_constraint(
+    tokens: &[String],
+    today: NaiveDate,
+    locale: Locale,
+) -> Option<(Comparison, DateSpan, usize)> {
+    let (comparison, skip) = match tokens.first()?.as_str() {
+        "before" | "until" | "till" => (Comparison::Before, 1),
+        "by" => (Comparison::By, 1),
+        "after" => (Comparison::After, 1),
+        "since" => (Comparison::Since, 1),
+        "on" | "during" => (Comparison::On, 1),
+        _ => (Comparison::On, 0),
+    };
+    let (span, used) = parse_date(&tokens[skip..], today, locale)?;
+    Some((comparison, span, skip + used))
+}
+
+/// Translate a date constraint into the filter's strict before/after bounds
+fn apply_date(
+    filter: &mut FilterOptions,
+    field: DateField,
+    comparison: Comparison,
+    span: DateSpan,
+) {
+    let (on, before, after) = match field {
+        DateField::Due => (
+            &mut filter.due_on,
+            &mut filter.due_before,
+            &mut filter.due_after,
+        ),
+        DateField::Completed => (
+            &mut filter.completed_on,
+            &mut filter.completed_before,
+            &mut filter.completed_after,
+        ),
+    };
+    match comparison {
+        Comparison::Before => *before = Some(span.start.into()),
+        Comparison::By => *before = span.end.succ_opt().map(Into::into),
+        Comparison::After => *after = Some(span.end.into()),
+        Comparison::Since => *after = span.start.pred_opt().map(Into::into),
+        Comparison::On if span.start == span.end => *on = Some(span.start.into()),
+        Comparison::On => {
+            *after = span.start.pred_opt().map(Into::into);
+            *before = span.end.succ_opt().map(Into::into);
+        }
+    }
+}
+
+/// Parse a date expression at the start of `tokens`, returning the span and
+/// the number of tokens consumed
+fn parse_date(tokens: &[String], today: NaiveDate, locale: Locale) -> Option<(DateSpan, usize)> {
+    let word = tokens.first()?.as_str();
+    let next = |n: usize| tokens.get(n).map(String::as_str);
+
+    if let Ok(date) = NaiveDate::parse_from_str(word, "%Y-%m-%d") {
+        return Some((DateSpan::day(date), 1));
+    }
+
+    match word {
+        "today" | "tonight" => return Some((DateSpan::day(today), 1)),
+        "tomorrow" => return Some((DateSpan::day(today.succ_opt()?), 1)),
+        "yesterday" => return Some((DateSpan::day(today.pred_opt()?), 1)),
+        _ => {}
+    }
+    if let Some(days) = locale.parse_relative_day(word) {
+        return Some((DateSpan::day(shift_days(today, days)?), 1));
+    }
+
+    if let Some(weekday) = parse_weekday(word, locale) {
+        return Some((DateSpan::day(upcoming(today, weekday)), 1));
+    }
+
+    match word {
+        "this" | "next" | "last" => {
+            let offset: i64 = match word {
+                "this" => 0,
+                "next" => 1,
+                _ => -1,
+            };
+            let unit = next(1)?;
+            let span = if let Some(weekday) = parse_weekday(unit, locale) {
+                match offset {
+                    0 => DateSpan::day(upcoming(today, weekday)),
+                    // The weekday in the following Monday-based week
+                    1 => DateSpan::day(
+                        week_span(today.checked_add_days(Days::new(7))?).start
+                            + Days::new(u64::from(weekday.num_days_from_monday())),
+                    ),
+                    _ => DateSpan::day(previous(today, weekday)),
+                }
+            } else {
+                match unit {
+                    "week" => week_span(shift_days(today, offset * 7)?),
+                    "month" => month_span(shift_months(today, offset)?)?,
+                    "year" => year_span(today.year() + offset as i32)?,
+                    _ => return None,
+                }
+            };
+            Some((span, 2))
+        }
+        // "end of (the) week/month/year"
+        "end" if next(1) == Some("of") => {
+            let skip = if next(2) == Some("the") { 3 } else { 2 };
+            let span = match next(skip)? {
+                "week" => week_span(today),
+                "month" => month_span(today)?,
+                "year" => year_span(today.year())?,
+                _ => return None,
+            };
+            Some((DateSpan::day(span.end), skip + 1))
+        }
+        // "in 3 days", "in a week"
+        "in" => {
+            let count = parse_number(next(1)?)?;
+            let date = shift_by_unit(today, count, next(2)?)?;
+            Some((DateSpan::day(date), 3))
+        }
+        // "3 days ago"
+        _ if next(2) == Some("ago") => {
+            let count = parse_number(word)?;
+            let date = shift_by_unit(today, -count, next(1)?)?;
+            Some((DateSpan::day(date), 3))
+        }
+        _ => None,
+    }
+}
+
+/// An English weekday name or abbreviation, or a weekday name in `locale`
+fn parse_weekday(word: &str, locale: Locale) -> Option<Weekday> {
+    match word {
+        "monday" | "mon" => Some(Weekday::Mon),
+        "tuesday" | "tue" | "tues" => Some(Weekday::Tue),
+        "wednesday" | "wed" => Some(Weekday::Wed),
+        "thursday" | "thu" | "thurs" => Some(Weekday::Thu),
+        "friday" | "fri" => Some(Weekday::Fri),
+        "saturday" | "sat" => Some(Weekday::Sat),
+        "sunday" | "sun" => Some(Weekday::Sun),
+        _ => locale.parse_weekday(word),
+    }
+}
+
+fn parse_number(word: &str) -> Option<i64> {
+    let number = match word {
+        "a" | "an" | "one" => 1,
+        "two" => 2,
+        "three" => 3,
+        "four" => 4,
+        "five" => 5,
+        "six" => 6,
+        "seven" => 7,
+        "eight" => 8,
+        "nine" => 9,
+        "ten" => 10,
+        _ => return word.parse().ok(),
+    };
+    Some(number)
+}
+
+/// Next occurrence of `weekday` on or after `today`
+fn upcoming(today: NaiveDate, weekday: Weekday) -> NaiveDate {
+    let days = (7 + weekday.num_days_from_monday() - today.weekday().num_days_from_monday()) % 7;
+    today + Days::new(u64::from(days))
+}
+
+/// Most recent occurrence of `weekday` strictly before `today`
+fn previous(today: NaiveDate, weekday: Weekday) -> NaiveDate {
+    let days = (7 + today.weekday().num_days_from_monday() - weekday.num_days_from_monday()) % 7;
+    today - Days::new(u64::from(if days == 0 { 7 } else { days }))
+}
+
+fn shift_days(date: NaiveDate, days: i64) -> Option<NaiveDate> {
+    if days >= 0 {
+        date.checked_add_days(Days::new(days as u64))
+    } else {
+        date.checked_sub_days(Days::new(days.unsigned_abs()))
+    }
+}
+
+fn shift_months(date: NaiveDate, months: i64) -> Option<NaiveDate> {
+    let amount = Months::new(u32::try_from(months.unsigned_abs()).ok()?);
+    if months >= 0 {
+        date.checked_add_months(amount)
+    } else {
+        date.checked_sub_months(amount)
+    }
+}
+
+fn shift_by_unit(date: NaiveDate, count: i64, unit: &str) -> Option<NaiveDate> {
+    match unit.trim_end_matches('s') {
+        "day" => shift_days(date, count),
+        "week" => shift_days(date, count.checked_mul(7)?),
+        "month" => shift_months(date, count),
+        "year" => shift_months(date, count.checked_mul(12)?),
+        _ => None,
+    }
+}
+
+/// Monday through Sunday of the week containing `date`
+fn week_span(date: NaiveDate) -> DateSpan {
+    let start = date - Days::new(u64::from(date.weekday().num_days_from_monday()));
+    DateSpan {
+        start,
+        end: start + Days::new(6),
+    }
+}
+
+fn month_span(date: NaiveDate) -> Option<DateSpan> {
+    let start = date.with_day(1)?;
+    let end = start.checked_add_months(Months::new(1))?.pred_opt()?;
+    Some(DateSpan { start, end })
+}
+
+fn year_span(year: i32) -> Option<DateSpan> {
+    Some(DateSpan {
+        start: NaiveDate::from_ymd_opt(year, 1, 1)?,
+        end: NaiveDate::from_ymd_opt(year, 12, 31)?,
+    })
+}
+
+/// Find the known tag closest to `word`
+///
+/// Tries, in order: an exact (case-insensitive) match, a singular/plural
+/// variant, a tag starting with the word, then a tag within a small edit
+/// distance. Words shorter than four letters only match exactly.
+fn match_tag(word: &str, known_tags: &[String]) -> Option<String> {
+    let word = fold(word.trim_start_matches('#'));
+    let word = word.as_str();
+    if word.is_empty() {
+        return None;
+    }
+    let lowered: Vec<(String, &String)> = known_tags.iter().map(|tag| (fold(tag), tag)).collect();
+    let find = |predicate: &dyn Fn(&str) -> bool| {
+        lowered
+            .iter()
+            .filter(|(lower, _)| predicate(lower))
+            .min_by_key(|(lower, _)| (lower.len(), lower.clone()))
+            .map(|(_, tag)| (*tag).clone())
+    };
+
+    if let Some(tag) = find(&|tag| tag == word) {
+        return Some(tag);
+    }
+    let length = word.chars().count();
+    if length < 4 {
+        return None;
+    }
+
+    find(&|tag| tag.strip_suffix('s') == Some(word) || word.strip_suffix('s') == Some(tag))
+        .or_else(|| find(&|tag| tag.starts_with(word)))
+        .or_else(|| {
+            let max_distance = if length < 8 { 1 } else { 2 };
+            lowered
+                .iter()
+                .map(|(lower, tag)| (levenshtein(word, lower), lower, tag))
+                .filter(|(distance, _, _)| *distance <= max_distance)
+                .min_by_key(|(distance, lower, _)| (*distance, (*lower).clone()))
+                .map(|(_, _, tag)| (*tag).clone())
+        })
+}
+
+/// Edit distance between two strings (insertions, deletions, substitutions)
+pub(crate) fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                diagonal
+            } else {
+                1 + diagonal.min(above).min(row[j])
+            };
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    /// A Wednesday
+    fn today() -> NaiveDate {
+        date("2025-01-08")
+    }
+
+    fn known_tags() -> Vec<String> {
+        ["work", "personal", "project-alpha", "Meeting"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect()
+    }
+
+    fn interpret(query: &str) -> InterpretedQuery {
+        interpret_query(query, &known_tags(), today(), Locale::En)
+    }
+
+    #[test]
+    fn test_parse_natural_date() {
+        let parse = |text| parse_natural_date(text, today(), Locale::En);
+
+        assert_eq!(parse("today"), Some(date("2025-01-08")));
+        assert_eq!(parse("Tomorrow"), Some(date("2025-01-09")));
+        assert_eq!(parse("yesterday"), Some(date("2025-01-07")));
+        assert_eq!(parse("2025-03-01"), Some(date("2025-03-01")));
+        assert_eq!(parse("friday"), Some(date("2025-01-10")));
+        assert_eq!(parse("wednesday"), Some(date("2025-01-08")));
+        assert_eq!(parse("next friday"), Some(date("2025-01-17")));
+        assert_eq!(parse("last friday"), Some(date("2025-01-03")));
+        assert_eq!(parse("next week"), Some(date("2025-01-13")));
+        assert_eq!(parse("next month"), Some(date("2025-02-01")));
+        assert_eq!(parse("end of the month"), Some(date("2025-01-31")));
+        assert_eq!(parse("in 3 days"), Some(date("2025-01-11")));
+        assert_eq!(parse("in a week"), Some(date("2025-01-15")));
+        assert_eq!(parse("two weeks ago"), Some(date("2024-12-25")));
+        assert_eq!(parse("someday"), None);
+        assert_eq!(parse("friday please"), None);
+    }
+
+    #[test]
+    fn test_parse_localized_dates() {
+        let parse = |text| parse_natural_date(text, today(), Locale::De);
+
+        assert_eq!(parse("morgen"), Some(date("2025-01-09")));
+        assert_eq!(parse("Freitag"), Some(date("2025-01-10")));
+        assert_eq!(parse("next Freitag"), Some(date("2025-01-17")));
+        assert_eq!(parse("friday"), Some(date("2025-01-10")));
+        assert_eq!(parse("vendredi"), None);
+        assert_eq!(
+            interpret_query("due before Freitag", &[], today(), Locale::De)
+                .filter
+                .due_before,
+            Some(date("2025-01-10").into())
+        );
+    }
+
+    #[test]
+    fn test_interpret_status_tag_and_due_date() {
+        let interpreted = interpret("incomplete work tasks due before next Friday");
+
+        assert_eq!(interpreted.filter.status, Some(TaskStatus::Incomplete));
+        assert_eq!(interpreted.filter.tags, Some(vec!["work".to_string()]));
+        assert_eq!(
+            interpreted.filter.due_before,
+            Some(date("2025-01-17").into())
+        );
+        assert!(interpreted.unrecognized.is_empty());
+    }
+
+    #[test]
+    fn test_interpret_date_comparisons() {
+        let filter = interpret("due by friday").filter;
+        assert_eq!(filter.due_before, Some(date("2025-01-11").into()));
+
+        let filter = interpret("due after 2025-02-01").filter;
+        assert_eq!(filter.due_after, Some(date("2025-02-01").into()));
+
+        let filter = interpret("due tomorrow").filter;
+        assert_eq!(filter.due_on, Some(date("2025-01-09").into()));
+
+        // Ranges become exclusive bounds around the span
+        let filter = interpret("due next week").filter;
+        assert_eq!(filter.due_after, Some(date("2025-01-12").into()));
+        assert_eq!(filter.due_before, Some(date("2025-01-20").into()));
+
+        // A date without a field applies to the due date
+        let filter = interpret("before friday").filter;
+        assert_eq!(filter.due_before, Some(date("2025-01-10").into()));
+    }
+
+    #[test]
+    fn test_interpret_completed_dates() {
+        let filter = interpret("tasks completed this week").filter;
+        assert_eq!(filter.status, Some(TaskStatus::Completed));
+        assert_eq!(filter.completed_after, Some(date("2025-01-05").into()));
+        assert_eq!(filter.completed_before, Some(date("2025-01-13").into()));
+
+        let filter = interpret("done since monday").filter;
+        assert_eq!(filter.completed_after, Some(date("2025-01-12").into()));
+
+        let filter = interpret("completed tasks").filter;
+        assert_eq!(filter.status, Some(TaskStatus::Completed));
+        assert_eq!(filter.completed_after, None);
+    }
+
+    #[test]
+    fn test_interpret_overdue_and_negation() {
+        let filter = interpret("overdue tasks without #personal").filter;
+        assert_eq!(filter.status, Some(TaskStatus::Incomplete));
+        assert_eq!(filter.due_before, Some(today().into()));
+        assert_eq!(filter.exclude_tags, Some(vec!["personal".to_string()]));
+
+        let filter = interpret("not done").filter;
+        assert_eq!(filter.status, Some(TaskStatus::Incomplete));
+
+        let filter = interpret("open tasks with no due date").filter;
+        assert!(filter.no_due_date);
+        assert_eq!(filter.due_before, None);
+
+        let filter = interpret("repeating tasks tagged #home").filter;
+        assert!(filter.recurring);
+        assert_eq!(filter.tags, Some(vec!["home".to_string()]));
+
+        let filter = interpret("incomplete high priority tasks").filter;
+        assert_eq!(filter.min_priority, Some(Priority::High));
+        assert_eq!(filter.status, Some(TaskStatus::Incomplete));
+    }
+
+    #[test]
+    fn test_interpret_fuzzy_tags() {
+        let interpreted = interpret("meetings and personl stuff #errands");
+
+        assert_eq!(
+            interpreted.filter.tags,
+            Some(vec![
+                "Meeting".to_string(),
+                "personal".to_string(),
+                "errands".to_string()
+            ])
+        );
+        assert_eq!(interpreted.unrecognized, vec!["stuff"]);
+
+        // Short words only match exactly
+        let filter = interpret("pro").filter;
+        assert_eq!(filter.tags, None);
+        let filter = interpret("proj").filter;
+        assert_eq!(filter.tags, Some(vec!["project-alpha".to_string()]));
+    }
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein("work", "work"), 0);
+        assert_eq!(levenshtein("personl", "personal"), 1);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("", "abc"), 3);
+    }
+}
@@ -0,0 +1,426 @@
+//! Push notifications for tasks becoming due or overdue
+//!
+//! While the server runs, the vault is checked on a timer. Incomplete tasks
+//! that are newly due today or newly overdue are announced once, in a single
+//! notification per check, through ntfy and/or Pushover. Nothing is sent
+//! during quiet hours; those tasks are announced when quiet hours end.
+
+use std::collections::HashSet;
+use std::error::Error;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{Local, NaiveDate, NaiveDateTime, NaiveTime};
+
+use crate::config::{Config, NotificationConfig, NtfyConfig, PushoverConfig};
+use crate::extractor::{Task, TaskExtractor, TaskStatus};
+use crate::filter::{FilterOptions, filter_tasks};
+
+type NotifierResult<T> = Result<T, Box<dyn Error + Send + Sync>>;
+
+const PUSHOVER_API_URL: &str = "https://api.pushover.net/1/messages.json";
+
+/// A push notification service
+pub trait PushService: Send + Sync {
+    fn push(&self, title: &str, message: &str) -> NotifierResult<()>;
+}
+
+/// Publishes to an ntfy topic
+pub struct NtfyClient {
+    url: String,
+    token: Option<String>,
+}
+
+impl NtfyClient {
+    pub fn new(config: &NtfyConfig) -> Self {
+        Self {
+            url: format!("{}/{}", config.server.trim_end_matches('/'), config.topic),
+            token: config.token(),
+        }
+    }
+}
+
+impl PushService for NtfyClient {
+    fn push(&self, title: &str, message: &str) -> NotifierResult<()> {
+        let mut request = ureq::post(&self.url)
+            .header("Title", title)
+            .header("Tags", "calendar");
+        if let Some(token) = &self.token {
+            request = request.header("Authorization", &format!("Bearer {}", token));
+        }
+        request.send(message)?;
+        Ok(())
+    }
+}
+
+/// Sends Pushover messages
+pub struct PushoverClient {
+    user: String,
+    token: String,
+}
+
+impl PushoverClient {
+    /// Fails if no application token is configured
+    pub fn new(config: &PushoverConfig) -> NotifierResult<Self> {
+        let token = config
+            .token()
+            .ok_or("notifications.pushover needs `token` or `token_env`")?;
+        Ok(Self {
+            user: config.user.clone(),
+            token,
+        })
+    }
+}
+
+impl PushService for PushoverClient {
+    fn push(&self, title: &str, message: &str) -> NotifierResult<()> {
+        ureq::post(PUSHOVER_API_URL).send_form([
+            ("token", self.token.as_str()),
+            ("user", self.user.as_str()),
+            ("title", title),
+            ("message", message),
+        ])?;
+        Ok(())
+    }
+}
+
+/// A local time range without notifications; may wrap past midnight
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuietHours {
+    start: NaiveTime,
+    end: NaiveTime,
+}
+
+impl QuietHours {
+    /// Parse `HH:MM-HH:MM`
+    pub fn parse(text: &str) -> Option<Self> {
+        let (start, end) = text.split_once('-')?;
+        Some(Self {
+            start: NaiveTime::parse_from_str(start.trim(), "%H:%M").ok()?,
+            end: NaiveTime::parse_from_str(end.trim(), "%H:%M").ok()?,
+        })
+    }
+
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+/// A task that is due today or overdue
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Alert {
+    overdue: bool,
+    due_date: NaiveDate,
+    content: String,
+    file_path: String,
+}
+
+impl Alert {
+    fn line(&self, file_name: &str) -> String {
+        if self.overdue {
+            format!(
+                "Overdue since {}: {} ({})",
+                self.due_date, self.content, file_name
+            )
+        } else {
+            format!("Due today: {} ({})", self.content, file_name)
+        }
+    }
+}
+
+/// Checks the vault for due tasks and pushes notifications
+pub struct Notifier {
+    base_path: PathBuf,
+    config: Arc<Config>,
+    task_extractor: TaskExtractor,
+    services: Vec<Box<dyn PushService>>,
+    quiet_hours: Option<QuietHours>,
+    interval: Duration,
+    /// Alerts already sent, so each is announced once
+    notified: HashSet<Alert>,
+}
+
+impl Notifier {
+    /// Build a notifier from the vault's `[notifications]` configuration
+    ///
+    /// Returns `None` when notifications aren't configured. Fails on invalid
+    /// quiet hours or when no service is configured.
+    pub fn new(base_path: PathBuf, config: Arc<Config>) -> NotifierResult<Option<Self>> {
+        let Some(notifications) = &config.notifications else {
+            return Ok(None);
+        };
+
+        let mut services: Vec<Box<dyn PushService>> = Vec::new();
+        if let Some(ntfy) = &notifications.ntfy {
+            services.push(Box::new(NtfyClient::new(ntfy)));
+        }
+        if let Some(pushover) = &notifications.pushover {
+            services.push(Box::new(PushoverClient::new(pushover)?));
+        }
+        if services.is_empty() {
+            return Err(
+                "notifications: configure [notifications.ntfy] or [notifications.pushover]".into(),
+            );
+        }
+
+        Self::with_services(base_path, Arc::clone(&config), notifications, services).map(Some)
+    }
+
+    fn with_services(
+        base_path: PathBuf,
+        config: Arc<Config>,
+        notifications: &NotificationConfig,
+        services: Vec<Box<dyn PushService>>,
+    ) -> NotifierResult<Self> {
+        let quiet_hours = match &notifications.quiet_hours {
+            Some(text) => Some(QuietHours::parse(text).ok_or_else(|| {
+                format!(
+                    "notifications: invalid quiet_hours '{}', expected HH:MM-HH:MM",
+                    text
+                )
+            })?),
+            None => None,
+        };
+
+        Ok(Self {
+            base_path,
+            task_extractor: TaskExtractor::new(Arc::clone(&config)),
+            config,
+            services,
+            quiet_hours,
+            interval: Duration::from_secs(notifications.check_interval_minutes.max(1) * 60),
+            notified: HashSet::new(),
+        })
+    }
+
+    /// Check the vault forever, on the configured interval
+    ///
+    /// Failures are logged to stderr and retried on the next check.
+    pub async fn run(mut self) {
+        loop {
+            let checked = tokio::task::spawn_blocking(move || {
+                let result = self.check(Local::now().naive_local());
+                (self, result)
+            })
+            .await;
+
+            let (notifier, result) = match checked {
+                Ok(checked) => checked,
+                Err(e) => {
+                    eprintln!("Notifier stopped: {}", e);
+                    return;
+                }
+            };
+            self = notifier;
+            if let Err(e) = result {
+                eprintln!("Notification check failed: {}", e);
+            }
+
+            tokio::time::sleep(self.interval).await;
+        }
+    }
+
+    /// Announce tasks that became due or overdue since the last check
+    ///
+    /// Returns the number of tasks announced.
+    pub fn check(&mut self, now: NaiveDateTime) -> NotifierResult<usize> {
+        if self
+            .quiet_hours
+            .is_some_and(|quiet| quiet.contains(now.time()))
+        {
+            return Ok(0);
+        }
+
+        // Tasks in skipped files can't be announced; the notifier runs
+        // unattended, so they are left out rather than reported
+        let (tasks, _warnings) = self
+            .task_extractor
+            .extract_tasks_with_warnings(&self.base_path)
+            .map_err(|e| e.to_string())?;
+        let alerts = due_alerts(tasks, &self.config, now.date());
+
+        // Forget tasks that were completed, rescheduled, or edited
+        self.notified
+            .retain(|alert| alerts.iter().any(|(a, _)| a == alert));
+
+        let new: Vec<&(Alert, String)> = alerts
+            .iter()
+            .filter(|(alert, _)| !self.notified.contains(alert))
+            .collect();
+        if new.is_empty() {
+            return Ok(0);
+        }
+
+        let title = match new.len() {
+            1 => "1 task needs attention".to_string(),
+            n => format!("{} tasks need attention", n),
+        };
+        let message = new
+            .iter()
+            .map(|(alert, file_name)| alert.line(file_name))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        // Mark as notified only once every service has it
+        for service in &self.services {
+            service.push(&title, &message)?;
+        }
+        self.notified
+            .extend(new.iter().map(|(alert, _)| alert.clone()));
+
+        Ok(new.len())
+    }
+}
+
+/// Incomplete tasks due on or before `today`, with their file names
+fn due_alerts(tasks: Vec<Task>, config: &Config, today: NaiveDate) -> Vec<(Alert, String)> {
+    let mut tasks = filter_tasks(
+        tasks,
+        &FilterOptions {
+            status: Some(TaskStatus::Incomplete),
+            due_before: today.succ_opt().map(Into::into),
+            ..Default::default()
+        },
+    );
+    tasks.retain(|task| !config.is_archived(std::path::Path::new(&task.file_path)));
+    tasks.sort_by_key(|task| task.due_date);
+
+    tasks
+        .into_iter()
+        .filter_map(|task| {
+            let due_date = task.due_date?;
+            Some((
+                Alert {
+                    overdue: due_date < today,
+                    due_date,
+                    content: task.content,
+                    file_path: task.file_path,
+                },
+                task.file_name,
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::sync::Mutex;
+    use tempfile::TempDir;
+
+    /// Records pushed notifications
+    #[derive(Default, Clone)]
+    struct Recorder(Arc<Mutex<Vec<(String, String)>>>);
+
+    impl PushService for Recorder {
+        fn push(&self, title: &str, message: &str) -> NotifierResult<()> {
+            self.0
+                .lock()
+                .unwrap()
+                .push((title.to_string(), message.to_string()));
+            Ok(())
+        }
+    }
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn notifier(temp_dir: &TempDir, quiet_hours: Option<&str>) -> (Notifier, Recorder) {
+        let recorder = Recorder::default();
+        let notifications = NotificationConfig {
+            check_interval_minutes: 15,
+            quiet_hours: quiet_hours.map(str::to_string),
+            ntfy: None,
+            pushover: None,
+        };
+        let notifier = Notifier::with_services(
+            temp_dir.path().to_path_buf(),
+            Arc::new(Config::default()),
+            &notifications,
+            vec![Box::new(recorder.clone())],
+        )
+        .unwrap();
+        (notifier, recorder)
+    }
+
+    #[test]
+    fn test_quiet_hours() {
+        let overnight = QuietHours::parse("22:00-07:00").unwrap();
+        assert!(overnight.contains(NaiveTime::from_hms_opt(23, 30, 0).unwrap()));
+        assert!(overnight.contains(NaiveTime::from_hms_opt(6, 59, 0).unwrap()));
+        assert!(!overnight.contains(NaiveTime::from_hms_opt(7, 0, 0).unwrap()));
+
+        let lunch = QuietHours::parse("12:00 - 13:00").unwrap();
+        assert!(lunch.contains(NaiveTime::from_hms_opt(12, 30, 0).unwrap()));
+        assert!(!lunch.contains(NaiveTime::from_hms_opt(13, 30, 0).unwrap()));
+
+        assert!(QuietHours::parse("late").is_none());
+    }
+
+    #[test]
+    fn test_check_announces_each_task_once() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("todo.md"),
+            "- [ ] Pay rent 📅 2025-01-06\n\
+             - [ ] File taxes 📅 2025-01-03\n\
+             - [x] Done already 📅 2025-01-02\n\
+             - [ ] Later 📅 2025-01-07\n",
+        )
+        .unwrap();
+        let (mut notifier, recorder) = notifier(&temp_dir, None);
+
+        assert_eq!(notifier.check(at("2025-01-06 09:00")).unwrap(), 2);
+        let (title, message) = recorder.0.lock().unwrap()[0].clone();
+        assert_eq!(title, "2 tasks need attention");
+        assert_eq!(
+            message,
+            "Overdue since 2025-01-03: File taxes (todo.md)\nDue today: Pay rent (todo.md)"
+        );
+
+        // Nothing new on the same day
+        assert_eq!(notifier.check(at("2025-01-06 09:15")).unwrap(), 0);
+
+        // Next day: rent becomes overdue and "Later" becomes due
+        assert_eq!(notifier.check(at("2025-01-07 09:00")).unwrap(), 2);
+        let (_, message) = recorder.0.lock().unwrap()[1].clone();
+        assert!(message.contains("Overdue since 2025-01-06: Pay rent"));
+        assert!(message.contains("Due today: Later"));
+    }
+
+    #[test]
+    fn test_quiet_hours_defer_notifications() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("todo.md"),
+            "- [ ] Pay rent 📅 2025-01-06\n",
+        )
+        .unwrap();
+        let (mut notifier, recorder) = notifier(&temp_dir, Some("22:00-07:00"));
+
+        assert_eq!(notifier.check(at("2025-01-06 06:00")).unwrap(), 0);
+        assert!(recorder.0.lock().unwrap().is_empty());
+
+        assert_eq!(notifier.check(at("2025-01-06 07:00")).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_new_requires_a_service() {
+        let config: Config = toml::from_str("[notifications]\n").unwrap();
+        assert!(Notifier::new(PathBuf::from("."), Arc::new(config)).is_err());
+
+        let config = Config::default();
+        assert!(
+            Notifier::new(PathBuf::from("."), Arc::new(config))
+                .unwrap()
+                .is_none()
+        );
+    }
+}
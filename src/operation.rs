@@ -1,5 +1,4 @@
 use async_trait::async_trait;
-use rmcp::model::ErrorData;
 use std::error::Error;
 
 use crate::capabilities::CapabilityRegistry;
@@ -20,6 +19,35 @@ pub trait Operation: Send + Sync + 'static {
     /// Human-readable description of the operation
     fn description(&self) -> &'static str;
 
+    /// Whether the operation only reads the vault
+    ///
+    /// API keys with `read_only = true` can only call operations that return
+    /// `true`. The default is `false`, so an operation that writes notes or
+    /// sends anything stays off limits to them even if it doesn't say so.
+    fn read_only(&self) -> bool {
+        false
+    }
+
+    /// Render a response as CSV, for HTTP calls that send `Accept: text/csv`
+    ///
+    /// `request` holds the call's parameters. Operations without a CSV form
+    /// return `None` and answer with JSON.
+    fn render_csv(
+        &self,
+        _request: &serde_json::Value,
+        _response: &serde_json::Value,
+    ) -> Option<Result<String, crate::error::Error>> {
+        None
+    }
+
+    /// A non-JSON body for every HTTP call, as `(content type, body)`
+    ///
+    /// For operations whose result is a document of its own, like a
+    /// calendar. Operations that return JSON return `None`.
+    fn render_http(&self, _response: &serde_json::Value) -> Option<(&'static str, String)> {
+        None
+    }
+
     /// Get the clap Command definition for CLI parsing
     ///
     /// This is typically derived from the request struct's `Parser` implementation.
@@ -29,7 +57,10 @@ pub trait Operation: Send + Sync + 'static {
     ///
     /// This method performs type erasure by accepting and returning JSON values,
     /// allowing dynamic dispatch across different operation types.
-    async fn execute_json(&self, json: serde_json::Value) -> Result<serde_json::Value, ErrorData>;
+    async fn execute_json(
+        &self,
+        json: serde_json::Value,
+    ) -> Result<serde_json::Value, crate::error::Error>;
 
     /// Execute the operation from parsed CLI arguments
     ///
@@ -49,4 +80,11 @@ pub trait Operation: Send + Sync + 'static {
     /// Returns the schema as a serde_json::Value for easy serialization.
     /// Implementations should use schemars::schema_for! on their request type.
     fn input_schema(&self) -> serde_json::Value;
+
+    /// Get the JSON Schema for this operation's response
+    ///
+    /// Describes the value returned by `execute_json` (the HTTP/MCP response
+    /// body). Implementations should use schemars::schema_for! on their
+    /// response type.
+    fn output_schema(&self) -> serde_json::Value;
 }
@@ -1,9 +1,25 @@
+#[cfg(feature = "fs")]
+use crate::cache::TaskCache;
+use crate::frontmatter::frontmatter_line_count;
+#[cfg(feature = "fs")]
+use crate::unicode::fold;
+use crate::unicode::fold_eq;
+#[cfg(feature = "fs")]
+use crate::warnings::{ExtractionWarning, WarningKind, Warnings};
+#[cfg(feature = "fs")]
+use rayon::prelude::*;
 use regex::Regex;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "fs")]
 use std::fs;
+#[cfg(feature = "fs")]
 use std::path::Path;
 
+/// Files searched in parallel at a time by [`OutlineExtractor::search_headings`]
+#[cfg(feature = "fs")]
+const SEARCH_BATCH_SIZE: usize = 256;
+
 /// Represents a heading found in a markdown file
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Heading {
@@ -59,48 +75,42 @@ impl OutlineExtractor {
         })
     }
 
-    /// Extract all headings from file content, filtering out headings in code blocks
+    /// Extract all headings from file content, filtering out headings in code
+    /// blocks and YAML comments (`# ...`) in frontmatter
     pub fn extract_headings(&self, content: &str) -> Vec<Heading> {
-        let mut headings = Vec::new();
-        let mut in_code_block = false;
+        self.headings(content).collect()
+    }
+
+    /// Headings in file order, parsed lazily so searches can stop early
+    fn headings<'a>(&'a self, content: &'a str) -> impl Iterator<Item = Heading> + 'a {
         let mut code_fence: Option<&str> = None;
 
-        for (line_num, line) in content.lines().enumerate() {
-            let trimmed = line.trim();
-
-            // Track code blocks (both ``` and ~~~ style)
-            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
-                if in_code_block {
-                    // Check if closing the same fence type
-                    let fence = code_fence.unwrap_or("```");
-                    if trimmed.starts_with(fence) {
-                        in_code_block = false;
-                        code_fence = None;
+        let lines = content.lines().enumerate();
+        lines
+            .skip(frontmatter_line_count(content))
+            .filter_map(move |(line_num, line)| {
+                let trimmed = line.trim();
+
+                // Track code blocks (both ``` and ~~~ style)
+                if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                    match code_fence {
+                        // Only the same fence type closes the block
+                        Some(fence) if trimmed.starts_with(fence) => code_fence = None,
+                        Some(_) => {}
+                        None if trimmed.starts_with("```") => code_fence = Some("```"),
+                        None => code_fence = Some("~~~"),
                     }
-                } else {
-                    // Opening a new code block
-                    in_code_block = true;
-                    code_fence = if trimmed.starts_with("```") {
-                        Some("```")
-                    } else {
-                        Some("~~~")
-                    };
+                    return None;
                 }
-                continue;
-            }
 
-            // Skip headings inside code blocks
-            if in_code_block {
-                continue;
-            }
-
-            // Try to parse this line as a heading
-            if let Some(heading) = self.parse_heading(line, line_num + 1) {
-                headings.push(heading);
-            }
-        }
+                // Skip headings inside code blocks
+                if code_fence.is_some() {
+                    return None;
+                }
 
-        headings
+                // Try to parse this line as a heading
+                self.parse_heading(line, line_num + 1)
+            })
     }
 
     /// Build hierarchical tree from flat list of headings
@@ -168,16 +178,34 @@ impl OutlineExtractor {
         current
     }
 
+    /// Flat list of the headings of a file, served from `cache` when it has
+    /// them for the file as it is now
+    #[cfg(feature = "fs")]
+    pub fn file_headings(
+        &self,
+        file_path: &Path,
+        cache: Option<&TaskCache>,
+    ) -> Result<Vec<Heading>, Box<dyn std::error::Error>> {
+        let read = || {
+            let content = fs::read_to_string(file_path)
+                .map_err(|e| format!("Failed to read file {:?}: {}", file_path, e))?;
+            Ok(self.extract_headings(&content))
+        };
+        match cache {
+            Some(cache) => cache.headings_or_extract(file_path, read),
+            None => read(),
+        }
+    }
+
     /// Get outline from a file (returns flat or hierarchical based on flag)
+    #[cfg(feature = "fs")]
     pub fn get_outline(
         &self,
         file_path: &Path,
         hierarchical: bool,
+        cache: Option<&TaskCache>,
     ) -> Result<Vec<Heading>, Box<dyn std::error::Error>> {
-        let content = fs::read_to_string(file_path)
-            .map_err(|e| format!("Failed to read file {:?}: {}", file_path, e))?;
-
-        let headings = self.extract_headings(&content);
+        let headings = self.file_headings(file_path, cache)?;
 
         if hierarchical {
             Ok(self.build_hierarchy(&headings))
@@ -187,24 +215,67 @@ impl OutlineExtractor {
     }
 
     /// Extract section content under a specific heading
+    ///
+    /// With a cache, the note is only read when the cached outline has a
+    /// matching heading.
+    #[cfg(feature = "fs")]
     pub fn get_section(
         &self,
         file_path: &Path,
         target_heading: &str,
         include_subsections: bool,
+        cache: Option<&TaskCache>,
     ) -> Result<Vec<Section>, Box<dyn std::error::Error>> {
-        let content = fs::read_to_string(file_path)
-            .map_err(|e| format!("Failed to read file {:?}: {}", file_path, e))?;
+        let read = || {
+            fs::read_to_string(file_path)
+                .map_err(|e| format!("Failed to read file {:?}: {}", file_path, e))
+        };
+
+        let Some(cache) = cache else {
+            let content = read()?;
+            return Ok(self.get_section_from_content(
+                &content,
+                target_heading,
+                include_subsections,
+            ));
+        };
+
+        let headings = self.file_headings(file_path, Some(cache))?;
+        if !headings.iter().any(|h| fold_eq(&h.title, target_heading)) {
+            return Ok(Vec::new());
+        }
+        let content = read()?;
+        Ok(self.sections(&content, &headings, target_heading, include_subsections))
+    }
+
+    /// Extract section content under a specific heading from in-memory content
+    pub fn get_section_from_content(
+        &self,
+        content: &str,
+        target_heading: &str,
+        include_subsections: bool,
+    ) -> Vec<Section> {
+        let headings = self.extract_headings(content);
+        self.sections(content, &headings, target_heading, include_subsections)
+    }
 
+    /// Sections of `content` under the headings titled `target_heading`,
+    /// given the content's flat list of headings
+    fn sections(
+        &self,
+        content: &str,
+        headings: &[Heading],
+        target_heading: &str,
+        include_subsections: bool,
+    ) -> Vec<Section> {
         let lines: Vec<&str> = content.lines().collect();
-        let headings = self.extract_headings(&content);
         let mut sections = Vec::new();
 
         // Find all headings matching the target
         let matching_indices: Vec<usize> = headings
             .iter()
             .enumerate()
-            .filter(|(_, h)| h.title.to_lowercase() == target_heading.to_lowercase())
+            .filter(|(_, h)| fold_eq(&h.title, target_heading))
             .map(|(i, _)| i)
             .collect();
 
@@ -251,10 +322,17 @@ impl OutlineExtractor {
             });
         }
 
-        Ok(sections)
+        sections
     }
 
     /// Search for headings matching a pattern across files in a directory
+    ///
+    /// Matches are returned sorted by file path, then line. Files are searched
+    /// in parallel batches in that order, and once `limit` matches have been
+    /// found the remaining batches are skipped, so a limit always returns the
+    /// first matches in the vault. With a cache, unchanged files aren't read.
+    #[cfg(feature = "fs")]
+    #[allow(clippy::too_many_arguments)]
     pub fn search_headings(
         &self,
         dir_path: &Path,
@@ -263,92 +341,85 @@ impl OutlineExtractor {
         max_level: Option<u8>,
         limit: Option<usize>,
         config: &crate::config::Config,
+        cache: Option<&TaskCache>,
+        warnings: &Warnings,
     ) -> Result<Vec<HeadingMatch>, Box<dyn std::error::Error>> {
-        let mut matches = Vec::new();
-        let pattern_lower = pattern.to_lowercase();
-
-        // Collect all markdown files
-        let mut files_to_search = Vec::new();
-        self.collect_markdown_files(dir_path, &mut files_to_search, config)?;
-
-        // Search each file
-        for file_path in files_to_search {
-            let content = match fs::read_to_string(&file_path) {
-                Ok(c) => c,
-                Err(_) => continue, // Skip files we can't read
-            };
-
-            let headings = self.extract_headings(&content);
-
-            for heading in headings {
-                // Filter by level if specified
-                if let Some(min) = min_level
-                    && heading.level < min
-                {
-                    continue;
-                }
-                if let Some(max) = max_level
-                    && heading.level > max
-                {
-                    continue;
-                }
-
-                // Case-insensitive substring match
-                if heading.title.to_lowercase().contains(&pattern_lower) {
-                    let file_name = file_path
-                        .file_name()
-                        .unwrap_or_default()
-                        .to_string_lossy()
-                        .to_string();
-
-                    matches.push(HeadingMatch {
-                        heading: Heading {
-                            title: heading.title,
-                            level: heading.level,
-                            line_number: heading.line_number,
-                            children: Vec::new(),
-                        },
-                        file_path: file_path.to_string_lossy().to_string(),
-                        file_name,
-                    });
-
-                    // Check limit
-                    if let Some(lim) = limit
-                        && matches.len() >= lim
-                    {
-                        return Ok(matches);
-                    }
+        let pattern_lower = fold(pattern);
+        let limit = limit.unwrap_or(usize::MAX);
+
+        // With sqlite_index, a vault's headings come from the SQLite index
+        #[cfg(feature = "sqlite")]
+        if let Some(index) = crate::sqlite_index::VaultIndex::for_vault(dir_path, config) {
+            let mut matches = Vec::new();
+            for (file_path, headings) in index.file_headings(config)? {
+                if matches.len() >= limit {
+                    break;
                 }
+                matches.extend(heading_matches(
+                    &file_path,
+                    headings.into_iter(),
+                    &pattern_lower,
+                    min_level,
+                    max_level,
+                    limit,
+                ));
             }
+            matches.truncate(limit);
+            return Ok(matches);
         }
 
-        Ok(matches)
-    }
+        // Collect all markdown files, in a stable order
+        let mut files_to_search =
+            crate::tag_extractor::collect_markdown_files(dir_path, config, warnings)?;
+        files_to_search.sort();
 
-    /// Recursively collect all markdown files in a directory
-    fn collect_markdown_files(
-        &self,
-        dir: &Path,
-        files: &mut Vec<std::path::PathBuf>,
-        config: &crate::config::Config,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        for entry in fs::read_dir(dir)? {
-            let entry = entry?;
-            let path = entry.path();
-
-            // Skip excluded paths
-            if config.should_exclude(&path) {
-                continue;
+        let mut matches = Vec::new();
+        for batch in files_to_search.chunks(SEARCH_BATCH_SIZE) {
+            if matches.len() >= limit {
+                break;
             }
+            let per_file: Vec<Vec<HeadingMatch>> = batch
+                .par_iter()
+                .map(|file_path| {
+                    // Skip files we can't read, reporting them
+                    let skip = |e: &dyn std::fmt::Display| {
+                        warnings.push(ExtractionWarning::new(
+                            file_path,
+                            WarningKind::Unreadable,
+                            e,
+                        ));
+                        Vec::new()
+                    };
+                    let content;
+                    let headings: Box<dyn Iterator<Item = Heading>> = match cache {
+                        Some(cache) => match self.file_headings(file_path, Some(cache)) {
+                            Ok(headings) => Box::new(headings.into_iter()),
+                            Err(e) => return skip(&e),
+                        },
+                        None => match fs::read_to_string(file_path) {
+                            Ok(read) => {
+                                content = read;
+                                Box::new(self.headings(&content))
+                            }
+                            Err(e) => return skip(&e),
+                        },
+                    };
 
-            if path.is_dir() {
-                self.collect_markdown_files(&path, files, config)?;
-            } else if path.extension().and_then(|s| s.to_str()) == Some("md") {
-                files.push(path);
-            }
+                    heading_matches(
+                        file_path,
+                        headings,
+                        &pattern_lower,
+                        min_level,
+                        max_level,
+                        limit,
+                    )
+                })
+                .collect();
+            matches.extend(per_file.into_iter().flatten());
         }
 
-        Ok(())
+        matches.truncate(limit);
+        Ok(matches)
     }
 }
 
@@ -358,6 +429,38 @@ impl Default for OutlineExtractor {
     }
 }
 
+/// Matches among the headings of one file: within the level bounds and
+/// containing `pattern_lower` case-insensitively, at most `limit` of them
+#[cfg(feature = "fs")]
+fn heading_matches(
+    file_path: &Path,
+    headings: impl Iterator<Item = Heading>,
+    pattern_lower: &str,
+    min_level: Option<u8>,
+    max_level: Option<u8>,
+    limit: usize,
+) -> Vec<HeadingMatch> {
+    headings
+        // Filter by level if specified
+        .filter(|heading| {
+            min_level.is_none_or(|min| heading.level >= min)
+                && max_level.is_none_or(|max| heading.level <= max)
+        })
+        // Case-insensitive substring match
+        .filter(|heading| fold(&heading.title).contains(pattern_lower))
+        .take(limit)
+        .map(|heading| HeadingMatch {
+            heading,
+            file_path: file_path.to_string_lossy().to_string(),
+            file_name: file_path
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string(),
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -490,6 +593,43 @@ Some code
             let headings = extractor.extract_headings(content);
             assert_eq!(headings.len(), 2);
         }
+
+        #[test]
+        fn test_frontmatter_comments_ignored() {
+            let extractor = create_test_extractor();
+            let content = "---\n# owner: bob\ntags: [a]\n---\n# Title\nText";
+
+            let headings = extractor.extract_headings(content);
+            assert_eq!(headings.len(), 1);
+            assert_eq!(headings[0].title, "Title");
+            assert_eq!(headings[0].line_number, 5);
+
+            // Frontmatter-only notes (with or without a trailing newline) and
+            // empty files have no headings
+            assert!(extractor.extract_headings("---\n# x\n---").is_empty());
+            assert!(extractor.extract_headings("---\n# x\n---\n").is_empty());
+            assert!(extractor.extract_headings("").is_empty());
+        }
+
+        #[test]
+        fn test_section_line_ranges_without_trailing_newline() {
+            let extractor = create_test_extractor();
+            for content in [
+                "---\ntags: [a]\n---\n## Target\nBody",
+                "---\ntags: [a]\n---\n## Target\nBody\n",
+            ] {
+                let sections = extractor.get_section_from_content(content, "Target", true);
+                assert_eq!(sections.len(), 1);
+                assert_eq!(sections[0].start_line, 4);
+                assert_eq!(sections[0].end_line, 5);
+                assert_eq!(sections[0].content, "Body");
+            }
+
+            // A heading on the last line has an empty section
+            let sections = extractor.get_section_from_content("Intro\n## Target", "Target", true);
+            assert_eq!((sections[0].start_line, sections[0].end_line), (2, 2));
+            assert_eq!(sections[0].content, "");
+        }
     }
 
     mod build_hierarchy {
@@ -528,6 +668,7 @@ Some code
         }
     }
 
+    #[cfg(feature = "fs")]
     mod get_section {
         use super::*;
         use std::io::Write;
@@ -549,7 +690,7 @@ Other content"
             .unwrap();
 
             let sections = extractor
-                .get_section(temp_file.path(), "Target Section", false)
+                .get_section(temp_file.path(), "Target Section", false, None)
                 .unwrap();
             assert_eq!(sections.len(), 1);
             assert_eq!(sections[0].content, "Content here\nMore content");
@@ -572,7 +713,7 @@ Other"
             .unwrap();
 
             let sections = extractor
-                .get_section(temp_file.path(), "Target Section", true)
+                .get_section(temp_file.path(), "Target Section", true, None)
                 .unwrap();
             assert_eq!(sections.len(), 1);
             assert!(sections[0].content.contains("Sub content"));
@@ -595,7 +736,7 @@ Other"
             .unwrap();
 
             let sections = extractor
-                .get_section(temp_file.path(), "Target Section", false)
+                .get_section(temp_file.path(), "Target Section", false, None)
                 .unwrap();
             assert_eq!(sections.len(), 1);
             assert!(!sections[0].content.contains("Sub content"));
@@ -618,16 +759,16 @@ Second content"
             .unwrap();
 
             let sections = extractor
-                .get_section(temp_file.path(), "Duplicate", false)
+                .get_section(temp_file.path(), "Duplicate", false, None)
                 .unwrap();
             assert_eq!(sections.len(), 2);
         }
     }
 
+    #[cfg(feature = "fs")]
     mod search_headings {
         use super::*;
         use std::io::Write;
-        use std::sync::Arc;
         use tempfile::TempDir;
 
         #[test]
@@ -643,7 +784,16 @@ Second content"
             write!(file2, "## Other Section\n# Search Target").unwrap();
 
             let matches = extractor
-                .search_headings(temp_dir.path(), "Search Target", None, None, None, &config)
+                .search_headings(
+                    temp_dir.path(),
+                    "Search Target",
+                    None,
+                    None,
+                    None,
+                    &config,
+                    None,
+                    &Warnings::default(),
+                )
                 .unwrap();
             assert_eq!(matches.len(), 2);
         }
@@ -658,7 +808,16 @@ Second content"
             write!(file, "# Target\n## Target\n### Target").unwrap();
 
             let matches = extractor
-                .search_headings(temp_dir.path(), "Target", Some(2), Some(2), None, &config)
+                .search_headings(
+                    temp_dir.path(),
+                    "Target",
+                    Some(2),
+                    Some(2),
+                    None,
+                    &config,
+                    None,
+                    &Warnings::default(),
+                )
                 .unwrap();
             assert_eq!(matches.len(), 1);
             assert_eq!(matches[0].heading.level, 2);
@@ -674,11 +833,77 @@ Second content"
             write!(file, "# Target 1\n# Target 2\n# Target 3").unwrap();
 
             let matches = extractor
-                .search_headings(temp_dir.path(), "Target", None, None, Some(2), &config)
+                .search_headings(
+                    temp_dir.path(),
+                    "Target",
+                    None,
+                    None,
+                    Some(2),
+                    &config,
+                    None,
+                    &Warnings::default(),
+                )
                 .unwrap();
             assert_eq!(matches.len(), 2);
         }
 
+        #[test]
+        fn test_search_limit_across_many_files() {
+            let extractor = create_test_extractor();
+            let temp_dir = TempDir::new().unwrap();
+            let config = crate::config::Config::default();
+
+            for i in 0..50 {
+                let mut file =
+                    std::fs::File::create(temp_dir.path().join(format!("file{:02}.md", i)))
+                        .unwrap();
+                write!(
+                    file,
+                    "# Target {}
+## Other
+## Target again",
+                    i
+                )
+                .unwrap();
+            }
+
+            let all = extractor
+                .search_headings(
+                    temp_dir.path(),
+                    "target",
+                    None,
+                    None,
+                    None,
+                    &config,
+                    None,
+                    &Warnings::default(),
+                )
+                .unwrap();
+            assert_eq!(all.len(), 100);
+
+            // Each file's matches stay together and in line order
+            for pair in all.chunks(2) {
+                assert_eq!(pair[0].file_path, pair[1].file_path);
+                assert!(pair[0].heading.line_number < pair[1].heading.line_number);
+            }
+
+            for limit in [0, 1, 7, 100, 500] {
+                let matches = extractor
+                    .search_headings(
+                        temp_dir.path(),
+                        "target",
+                        None,
+                        None,
+                        Some(limit),
+                        &config,
+                        None,
+                        &Warnings::default(),
+                    )
+                    .unwrap();
+                assert_eq!(matches.len(), limit.min(100));
+            }
+        }
+
         #[test]
         fn test_case_insensitive_search() {
             let extractor = create_test_extractor();
@@ -689,7 +914,16 @@ Second content"
             write!(file, "# UPPERCASE\n# lowercase\n# MixedCase").unwrap();
 
             let matches = extractor
-                .search_headings(temp_dir.path(), "case", None, None, None, &config)
+                .search_headings(
+                    temp_dir.path(),
+                    "case",
+                    None,
+                    None,
+                    None,
+                    &config,
+                    None,
+                    &Warnings::default(),
+                )
                 .unwrap();
             assert_eq!(matches.len(), 3);
         }
@@ -0,0 +1,300 @@
+//! Resolution of request paths against a vault
+//!
+//! Vaults are often reached through a symlink (an iCloud folder linked into
+//! the home directory, `/tmp` on macOS), so a requested path is only compared
+//! with the vault after both are canonicalized. Resolved paths are returned
+//! under the vault path as given, not the canonical one, so they match the
+//! `file_path`s reported by scans.
+//!
+//! Relative paths in responses always use `/` ([`to_slash`]), and request
+//! paths may use either separator ([`from_slash`]), so the same vault gives
+//! the same results on every platform.
+
+#[cfg(feature = "server")]
+use crate::capabilities::CapabilityResult;
+#[cfg(feature = "server")]
+use crate::error::{forbidden, internal_error, not_found};
+#[cfg(feature = "server")]
+use crate::unicode::fold;
+#[cfg(feature = "server")]
+use std::path::Component;
+use std::path::{Path, PathBuf};
+
+/// A relative path as a string with `/` separators on every platform
+pub fn to_slash(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// A request path that may use `/` or `\` as its separator
+pub fn from_slash(path: &str) -> PathBuf {
+    PathBuf::from(path.replace('\\', "/"))
+}
+
+/// Why a path couldn't be resolved
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathError {
+    /// Nothing exists at the path
+    NotFound,
+    /// The path (symlinks followed) leaves the vault
+    OutsideVault,
+}
+
+/// Resolves request paths inside one vault
+#[cfg(feature = "server")]
+#[derive(Debug, Clone)]
+pub struct PathResolver {
+    base: PathBuf,
+    canonical_base: PathBuf,
+}
+
+#[cfg(feature = "server")]
+impl PathResolver {
+    /// Resolver for the vault at `base`, which must exist
+    pub fn new(base: &Path) -> CapabilityResult<Self> {
+        let canonical_base = base
+            .canonicalize()
+            .map_err(|e| internal_error(format!("Failed to resolve base path: {}", e)))?;
+        Ok(Self {
+            base: base.to_path_buf(),
+            canonical_base,
+        })
+    }
+
+    /// The vault path as given
+    pub fn base(&self) -> &Path {
+        &self.base
+    }
+
+    /// Resolve a path relative to the vault, or an absolute path through
+    /// either the vault path or its canonical form
+    ///
+    /// A path that doesn't exist as spelled is matched against the directory
+    /// entries regardless of case and Unicode normalization form, so a
+    /// composed `café.md` finds a note stored decomposed (as macOS does).
+    pub fn resolve(&self, requested: &str) -> Result<PathBuf, PathError> {
+        let mut full = self.base.join(from_slash(requested));
+        if !full.exists() {
+            full = find_folded(&full).ok_or(PathError::NotFound)?;
+        }
+        let canonical = full.canonicalize().map_err(|_| PathError::NotFound)?;
+        let Ok(inside) = canonical.strip_prefix(&self.canonical_base) else {
+            return Err(PathError::OutsideVault);
+        };
+
+        // Keep the requested spelling (and any symlinks inside the vault)
+        // unless `..` makes it ambiguous which directory it went through
+        if let Some(relative) = self.relative(&full)
+            && relative
+                .components()
+                .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+        {
+            return Ok(self.join(&normalize(relative)));
+        }
+        Ok(self.join(inside))
+    }
+
+    /// Resolve the path of a note for reading, with the error messages used by
+    /// every file-based operation
+    pub fn resolve_note(&self, file_path: &str) -> CapabilityResult<PathBuf> {
+        let path = self.resolve(file_path).map_err(|e| match e {
+            PathError::NotFound => not_found(format!("File not found: {}", file_path)),
+            PathError::OutsideVault => forbidden(format!(
+                "Invalid path '{}': must be within vault",
+                file_path
+            )),
+        })?;
+
+        // Check the target, so a .md symlink can't expose another file type
+        let target = path.canonicalize().unwrap_or_else(|_| path.clone());
+        if target.extension().and_then(|s| s.to_str()) != Some("md") {
+            return Err(forbidden(format!(
+                "Invalid file type '{}': only .md files allowed",
+                file_path
+            )));
+        }
+        Ok(path)
+    }
+
+    fn join(&self, relative: &Path) -> PathBuf {
+        if relative.as_os_str().is_empty() {
+            self.base.clone()
+        } else {
+            self.base.join(relative)
+        }
+    }
+
+    /// Whether `path` exists and is inside the vault once symlinks are followed
+    pub fn contains(&self, path: &Path) -> bool {
+        path.canonicalize()
+            .is_ok_and(|canonical| canonical.starts_with(&self.canonical_base))
+    }
+
+    /// `path` relative to the vault, whether it is under the vault path as
+    /// given or its canonical form
+    pub fn relative<'a>(&self, path: &'a Path) -> Option<&'a Path> {
+        path.strip_prefix(&self.base)
+            .or_else(|_| path.strip_prefix(&self.canonical_base))
+            .ok()
+    }
+}
+
+/// The existing path that matches `path` component by component regardless
+/// of case and Unicode normalization form, preferring exact matches
+#[cfg(feature = "server")]
+fn find_folded(path: &Path) -> Option<PathBuf> {
+    let mut found = PathBuf::new();
+    for component in path.components() {
+        let exact = found.join(component);
+        if exact.symlink_metadata().is_ok() {
+            found = exact;
+            continue;
+        }
+        let Component::Normal(name) = component else {
+            return None;
+        };
+        let wanted = fold(&name.to_string_lossy());
+        let mut matches: Vec<PathBuf> = std::fs::read_dir(&found)
+            .ok()?
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| fold(&entry.file_name().to_string_lossy()) == wanted)
+            .map(|entry| entry.path())
+            .collect();
+        matches.sort();
+        found = matches.into_iter().next()?;
+    }
+    Some(found)
+}
+
+/// Drop `.` components
+#[cfg(feature = "server")]
+fn normalize(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|c| !matches!(c, Component::CurDir))
+        .collect()
+}
+
+#[cfg(all(test, feature = "server"))]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn vault() -> (TempDir, PathBuf) {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path().join("vault");
+        fs::create_dir_all(vault.join("Work")).unwrap();
+        fs::write(vault.join("Work/note.md"), "").unwrap();
+        fs::write(vault.join("data.txt"), "").unwrap();
+        fs::write(temp_dir.path().join("secret.md"), "").unwrap();
+        (temp_dir, vault)
+    }
+
+    #[test]
+    fn test_resolve() {
+        let (_temp_dir, vault) = vault();
+        let resolver = PathResolver::new(&vault).unwrap();
+
+        assert_eq!(
+            resolver.resolve("./Work/note.md"),
+            Ok(vault.join("Work/note.md"))
+        );
+        assert_eq!(
+            resolver.resolve("Work\\note.md"),
+            Ok(vault.join("Work/note.md"))
+        );
+        assert_eq!(
+            resolver.resolve("Work/../Work/note.md"),
+            Ok(vault.join("Work/note.md"))
+        );
+        assert_eq!(
+            resolver.resolve(vault.join("Work/note.md").to_str().unwrap()),
+            Ok(vault.join("Work/note.md"))
+        );
+        assert_eq!(resolver.resolve(""), Ok(vault.clone()));
+        assert_eq!(resolver.resolve("missing.md"), Err(PathError::NotFound));
+        assert_eq!(
+            resolver.resolve("../secret.md"),
+            Err(PathError::OutsideVault)
+        );
+
+        assert!(resolver.resolve_note("Work/note.md").is_ok());
+        assert_eq!(
+            resolver.resolve_note("data.txt").unwrap_err().code(),
+            "forbidden"
+        );
+        assert_eq!(
+            resolver.resolve_note("../secret.md").unwrap_err().code(),
+            "forbidden"
+        );
+        assert_eq!(
+            resolver.resolve_note("nope.md").unwrap_err().code(),
+            "not_found"
+        );
+    }
+
+    #[test]
+    fn test_slash_paths() {
+        assert_eq!(
+            to_slash(Path::new("Daily").join("2025-01-20.md").as_path()),
+            "Daily/2025-01-20.md"
+        );
+        assert_eq!(to_slash(Path::new("")), "");
+        assert_eq!(
+            from_slash("Daily\\2025-01-20.md"),
+            Path::new("Daily/2025-01-20.md")
+        );
+    }
+
+    #[test]
+    fn test_resolve_normalization_and_case() {
+        let (_temp_dir, vault) = vault();
+        fs::write(vault.join("Work/Cafe\u{301}.md"), "").unwrap();
+        let resolver = PathResolver::new(&vault).unwrap();
+
+        assert_eq!(
+            resolver.resolve("work/caf\u{e9}.md"),
+            Ok(vault.join("Work/Cafe\u{301}.md"))
+        );
+        assert!(resolver.resolve_note("Work/CAF\u{c9}.md").is_ok());
+        assert_eq!(resolver.resolve("Work/cafe.md"), Err(PathError::NotFound));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlinked_vault() {
+        use std::os::unix::fs::symlink;
+
+        let (temp_dir, vault) = vault();
+        let link = temp_dir.path().join("link");
+        symlink(&vault, &link).unwrap();
+        symlink(temp_dir.path().join("secret.md"), vault.join("escape.md")).unwrap();
+        symlink(vault.join("data.txt"), vault.join("disguised.md")).unwrap();
+
+        let resolver = PathResolver::new(&link).unwrap();
+        // Paths stay under the symlinked vault path, however they were given
+        assert_eq!(
+            resolver.resolve("Work/note.md"),
+            Ok(link.join("Work/note.md"))
+        );
+        assert_eq!(
+            resolver.resolve(vault.join("Work/note.md").to_str().unwrap()),
+            Ok(link.join("Work/note.md"))
+        );
+        assert_eq!(
+            resolver.relative(&vault.join("Work/note.md")),
+            Some(Path::new("Work/note.md"))
+        );
+        assert!(resolver.contains(&link.join("Work")));
+
+        // Symlinks inside the vault can't be used to leave it
+        assert_eq!(resolver.resolve("escape.md"), Err(PathError::OutsideVault));
+        assert!(!resolver.contains(&link.join("escape.md")));
+        assert_eq!(
+            resolver.resolve_note("disguised.md").unwrap_err().code(),
+            "forbidden"
+        );
+    }
+}
@@ -0,0 +1,376 @@
+//! `profile` command: find out what makes a vault slow to scan
+//!
+//! Scans every note the way the other commands do and reports the slowest
+//! files by parse time, the largest files, the time spent in each extractor,
+//! and the regexes that cost the most. Files are scanned one at a time so the
+//! timings aren't skewed by parallelism.
+
+use crate::capabilities::CapabilityRegistry;
+use crate::config::Config;
+use crate::extractor::TaskExtractor;
+use crate::link_extractor::LinkExtractor;
+use crate::operation::Operation;
+use crate::outline_extractor::OutlineExtractor;
+use crate::tag_extractor::{TagExtractor, collect_markdown_files};
+use crate::warnings::{ExtractionWarning, Warnings};
+use clap::{CommandFactory, FromArgMatches, Parser};
+use regex::Regex;
+use serde::Serialize;
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Profile a full vault scan
+#[derive(Parser, Debug)]
+#[command(
+    name = "profile",
+    about = "Scan a vault and report the slowest and largest files, time per extractor, and regex hot spots"
+)]
+pub struct ProfileCommand {
+    /// Vault to profile
+    #[arg(index = 1, required = true)]
+    pub path: PathBuf,
+
+    /// Number of files and patterns to list in each ranking
+    #[arg(long, default_value_t = 10)]
+    pub top: usize,
+}
+
+/// Time spent in one extractor across the vault
+#[derive(Debug, Clone, Serialize)]
+pub struct ExtractorTime {
+    pub extractor: String,
+    pub total_ms: f64,
+}
+
+/// Scan statistics for one file
+#[derive(Debug, Clone, Serialize)]
+pub struct FileProfile {
+    pub file_path: String,
+    pub bytes: u64,
+    pub lines: usize,
+    pub tasks: usize,
+    /// Time spent in all extractors, excluding reading the file
+    pub parse_ms: f64,
+}
+
+/// Cost of one regex across the vault
+#[derive(Debug, Clone, Serialize)]
+pub struct PatternProfile {
+    pub pattern: String,
+    pub total_ms: f64,
+    /// Lines (or task texts, for metadata patterns) it was run on
+    pub runs: usize,
+    pub matches: usize,
+}
+
+/// Result of profiling a vault
+#[derive(Debug, Serialize)]
+pub struct ProfileReport {
+    pub files: usize,
+    pub total_bytes: u64,
+    /// Wall time of the whole profile run
+    pub total_ms: f64,
+    /// Extractors, slowest first
+    pub extractors: Vec<ExtractorTime>,
+    pub slowest_files: Vec<FileProfile>,
+    pub largest_files: Vec<FileProfile>,
+    /// Task regexes, most expensive first
+    pub regex_hot_spots: Vec<PatternProfile>,
+    /// Notes left out of the profile by a truncating scan limit
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ExtractionWarning>,
+}
+
+#[derive(Default)]
+struct PatternTotals {
+    elapsed: Duration,
+    runs: usize,
+    matches: usize,
+}
+
+/// Milliseconds, rounded to microseconds
+fn millis(duration: Duration) -> f64 {
+    (duration.as_secs_f64() * 1_000_000.0).round() / 1000.0
+}
+
+/// Profile every note under `base_path` that the vault configuration scans
+pub fn profile_vault(
+    base_path: &Path,
+    config: Arc<Config>,
+    top: usize,
+) -> Result<ProfileReport, Box<dyn Error>> {
+    let started = Instant::now();
+    let task_extractor = TaskExtractor::new(Arc::clone(&config));
+    let tag_extractor = TagExtractor::new(Arc::clone(&config));
+    let outline_extractor = OutlineExtractor::new();
+    let link_extractor = LinkExtractor::new();
+
+    let warnings = Warnings::default();
+    let files = if base_path.is_file() {
+        vec![base_path.to_path_buf()]
+    } else {
+        collect_markdown_files(base_path, &config, &warnings)?
+    };
+
+    let mut extractor_totals: BTreeMap<&str, Duration> = BTreeMap::new();
+    let mut pattern_totals: BTreeMap<&str, PatternTotals> = BTreeMap::new();
+    let mut profiles = Vec::with_capacity(files.len());
+
+    for file in &files {
+        let timer = Instant::now();
+        // Unreadable files are skipped like in a normal scan
+        let Ok(content) = fs::read_to_string(file) else {
+            continue;
+        };
+        *extractor_totals.entry("read").or_default() += timer.elapsed();
+
+        let mut parse = Duration::ZERO;
+        let mut time = |name: &'static str, run: &mut dyn FnMut()| {
+            let timer = Instant::now();
+            run();
+            let elapsed = timer.elapsed();
+            *extractor_totals.entry(name).or_default() += elapsed;
+            parse += elapsed;
+        };
+
+        let mut tasks = 0;
+        time("tasks", &mut || {
+            tasks = task_extractor
+                .extract_tasks_from_content(&content, file)
+                .len();
+        });
+        time("tags", &mut || {
+            let _ = tag_extractor.extract_tags_from_content(&content, true);
+        });
+        time("headings", &mut || {
+            outline_extractor.extract_headings(&content);
+        });
+        let relative = file.strip_prefix(base_path).unwrap_or(file);
+        time("links", &mut || {
+            link_extractor.extract_links(&content, relative);
+        });
+
+        profile_patterns(&task_extractor, &content, &mut pattern_totals);
+
+        profiles.push(FileProfile {
+            file_path: file.to_string_lossy().to_string(),
+            bytes: content.len() as u64,
+            lines: content.lines().count(),
+            tasks,
+            parse_ms: millis(parse),
+        });
+    }
+
+    let mut extractors: Vec<ExtractorTime> = extractor_totals
+        .into_iter()
+        .map(|(extractor, total)| ExtractorTime {
+            extractor: extractor.to_string(),
+            total_ms: millis(total),
+        })
+        .collect();
+    extractors.sort_by(|a, b| b.total_ms.total_cmp(&a.total_ms));
+
+    let mut regex_hot_spots: Vec<PatternProfile> = pattern_totals
+        .into_iter()
+        .map(|(pattern, totals)| PatternProfile {
+            pattern: pattern.to_string(),
+            total_ms: millis(totals.elapsed),
+            runs: totals.runs,
+            matches: totals.matches,
+        })
+        .collect();
+    regex_hot_spots.sort_by(|a, b| b.total_ms.total_cmp(&a.total_ms));
+    regex_hot_spots.truncate(top);
+
+    let total_bytes = profiles.iter().map(|p| p.bytes).sum();
+    let mut slowest_files = profiles.clone();
+    slowest_files.sort_by(|a, b| b.parse_ms.total_cmp(&a.parse_ms));
+    slowest_files.truncate(top);
+    let mut largest_files = profiles.clone();
+    largest_files.sort_by_key(|p| std::cmp::Reverse(p.bytes));
+    largest_files.truncate(top);
+
+    Ok(ProfileReport {
+        files: profiles.len(),
+        total_bytes,
+        total_ms: millis(started.elapsed()),
+        extractors,
+        slowest_files,
+        largest_files,
+        regex_hot_spots,
+        warnings: warnings.into_vec(),
+    })
+}
+
+/// Time each task regex over one note, the way the task extractor uses them:
+/// line patterns on every line, metadata patterns on task lines only
+fn profile_patterns<'a>(
+    extractor: &'a TaskExtractor,
+    content: &str,
+    totals: &mut BTreeMap<&'a str, PatternTotals>,
+) {
+    let lines: Vec<&str> = content.lines().collect();
+    let line_patterns = extractor.line_patterns();
+    let task_lines: Vec<&str> = lines
+        .iter()
+        .copied()
+        .filter(|line| line_patterns.iter().any(|(_, p)| p.is_match(line)))
+        .collect();
+
+    let mut run = |name: &'a str, pattern: &Regex, inputs: &[&str]| {
+        let timer = Instant::now();
+        let matches: usize = inputs
+            .iter()
+            .map(|input| pattern.find_iter(input).count())
+            .sum();
+        let entry = totals.entry(name).or_default();
+        entry.elapsed += timer.elapsed();
+        entry.runs += inputs.len();
+        entry.matches += matches;
+    };
+
+    for (name, pattern) in line_patterns {
+        run(name, pattern, &lines);
+    }
+    for (name, pattern) in extractor.metadata_patterns() {
+        run(name, pattern, &task_lines);
+    }
+}
+
+/// CliOperation implementation for the profile command
+#[derive(Default)]
+pub struct ProfileOperation;
+
+impl ProfileOperation {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait::async_trait]
+impl Operation for ProfileOperation {
+    fn name(&self) -> &'static str {
+        "profile"
+    }
+
+    fn path(&self) -> &'static str {
+        // ProfileOperation is CLI-only and doesn't have an HTTP endpoint
+        ""
+    }
+
+    fn description(&self) -> &'static str {
+        "Profile a vault scan: slowest and largest files, time per extractor, and regex hot spots"
+    }
+
+    fn get_command(&self) -> clap::Command {
+        ProfileCommand::command()
+    }
+
+    async fn execute_json(&self, _json: Value) -> Result<Value, crate::error::Error> {
+        // ProfileOperation is CLI-only and doesn't support JSON execution
+        Err(crate::error::unsupported(
+            "profile command is only available via CLI",
+        ))
+    }
+
+    async fn execute_from_args(
+        &self,
+        matches: &clap::ArgMatches,
+        _registry: &CapabilityRegistry,
+    ) -> Result<String, Box<dyn Error>> {
+        let cmd = ProfileCommand::from_arg_matches(matches)?;
+        let config = Arc::new(Config::load_from_base_path(&cmd.path));
+        let report = profile_vault(&cmd.path, config, cmd.top)?;
+        Ok(serde_json::to_string_pretty(&report)?)
+    }
+
+    fn input_schema(&self) -> Value {
+        // ProfileOperation is CLI-only and doesn't have a meaningful JSON schema
+        json!({
+            "type": "object",
+            "properties": {}
+        })
+    }
+
+    fn output_schema(&self) -> Value {
+        // ProfileOperation is CLI-only and doesn't return a JSON response
+        json!({
+            "type": "object",
+            "properties": {}
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_profile_vault() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("small.md"),
+            "# Small\n- [ ] One #work\n",
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("big.md"),
+            "- [ ] Task 📅 2025-01-10\n".repeat(50),
+        )
+        .unwrap();
+        fs::create_dir(temp_dir.path().join("skip")).unwrap();
+        fs::write(temp_dir.path().join("skip/other.md"), "- [ ] Hidden\n").unwrap();
+
+        let config = Arc::new(Config {
+            exclude_paths: vec!["skip".to_string()],
+            ..Default::default()
+        });
+        let report = profile_vault(temp_dir.path(), config, 1).unwrap();
+
+        assert_eq!(report.files, 2);
+        assert!(report.total_bytes > 1000);
+        let mut extractors: Vec<&str> = report
+            .extractors
+            .iter()
+            .map(|e| e.extractor.as_str())
+            .collect();
+        extractors.sort();
+        assert_eq!(
+            extractors,
+            vec!["headings", "links", "read", "tags", "tasks"]
+        );
+
+        assert_eq!(report.largest_files.len(), 1);
+        assert!(report.largest_files[0].file_path.ends_with("big.md"));
+        assert_eq!(report.largest_files[0].tasks, 50);
+        assert_eq!(report.largest_files[0].lines, 50);
+        assert_eq!(report.slowest_files.len(), 1);
+        assert_eq!(report.regex_hot_spots.len(), 1);
+    }
+
+    #[test]
+    fn test_profile_patterns_counts_runs_and_matches() {
+        let extractor = TaskExtractor::new(Arc::new(Config::default()));
+        let mut totals = BTreeMap::new();
+        profile_patterns(
+            &extractor,
+            "# Notes\n- [ ] Call #work 📅 2025-01-10\nplain #text\n",
+            &mut totals,
+        );
+
+        // Line patterns see every line, metadata patterns only the task
+        assert_eq!(totals["task_incomplete"].runs, 3);
+        assert_eq!(totals["task_incomplete"].matches, 1);
+        assert_eq!(totals["tag"].runs, 1);
+        assert_eq!(totals["tag"].matches, 1);
+        // Three due date variants share a name
+        assert_eq!(totals["due_date"].runs, 3);
+        assert_eq!(totals["due_date"].matches, 1);
+    }
+}
@@ -0,0 +1,179 @@
+//! Named vaults for the CLI `--vault` flag
+//!
+//! Profiles live in `~/.config/markdown-todo-extractor/profiles.toml` (or the
+//! file named by `MARKDOWN_TODO_EXTRACTOR_PROFILES`), outside any single vault:
+//!
+//! ```toml
+//! [vaults]
+//! work = "~/Vaults/Work"
+//! personal = "~/Vaults/Personal"
+//! ```
+
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// `--vault` value that selects every configured vault
+pub const ALL_VAULTS: &str = "all";
+
+/// Named vault paths
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Profiles {
+    #[serde(default)]
+    pub vaults: BTreeMap<String, PathBuf>,
+}
+
+impl Profiles {
+    /// Load profiles from `MARKDOWN_TODO_EXTRACTOR_PROFILES`, or from
+    /// `markdown-todo-extractor/profiles.toml` in the user's config directory
+    /// (`$XDG_CONFIG_HOME`, falling back to `~/.config`)
+    pub fn load() -> Self {
+        let path = std::env::var_os("MARKDOWN_TODO_EXTRACTOR_PROFILES")
+            .map(PathBuf::from)
+            .or_else(|| {
+                std::env::var_os("XDG_CONFIG_HOME")
+                    .map(PathBuf::from)
+                    .or_else(|| home_dir().map(|home| home.join(".config")))
+                    .map(|dir| dir.join("markdown-todo-extractor").join("profiles.toml"))
+            });
+
+        path.map(|path| Self::load_from_file(&path))
+            .unwrap_or_default()
+    }
+
+    /// Load profiles from a file, falling back to no profiles if it doesn't
+    /// exist or can't be parsed
+    pub fn load_from_file(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| toml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Turn `--vault` values into (name, path) pairs
+    ///
+    /// Each value is a profile name, `all` for every profile, or a path to a
+    /// vault directory (named after its folder). Duplicates are dropped.
+    pub fn resolve(&self, values: &[String]) -> Result<Vec<(String, PathBuf)>, String> {
+        let mut vaults: Vec<(String, PathBuf)> = Vec::new();
+        let mut add = |name: String, path: PathBuf| {
+            if !vaults.iter().any(|(n, _)| *n == name) {
+                vaults.push((name, path));
+            }
+        };
+
+        for value in values {
+            if value == ALL_VAULTS {
+                if self.vaults.is_empty() {
+                    return Err("--vault all needs vaults defined in the profiles config".into());
+                }
+                for (name, path) in &self.vaults {
+                    add(name.clone(), expand_home(path));
+                }
+            } else if let Some(path) = self.vaults.get(value) {
+                add(value.clone(), expand_home(path));
+            } else if Path::new(value).is_dir() {
+                let path = PathBuf::from(value);
+                let name = path
+                    .canonicalize()
+                    .ok()
+                    .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+                    .unwrap_or_else(|| value.clone());
+                add(name, path);
+            } else {
+                let known: Vec<&str> = self.vaults.keys().map(String::as_str).collect();
+                return Err(format!(
+                    "Unknown vault '{}'. Configured vaults: {}",
+                    value,
+                    if known.is_empty() {
+                        "(none)".to_string()
+                    } else {
+                        known.join(", ")
+                    }
+                ));
+            }
+        }
+
+        Ok(vaults)
+    }
+}
+
+/// The user's home directory, from `$HOME`
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(PathBuf::from)
+}
+
+/// Expand a leading `~` to the user's home directory
+fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), home_dir()) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn profiles() -> Profiles {
+        toml::from_str(
+            r#"
+[vaults]
+work = "/vaults/work"
+personal = "/vaults/personal"
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_resolve_names_and_all() {
+        let profiles = profiles();
+
+        let vaults = profiles.resolve(&["work".to_string()]).unwrap();
+        assert_eq!(
+            vaults,
+            vec![("work".to_string(), PathBuf::from("/vaults/work"))]
+        );
+
+        // "all" expands in name order and duplicates are dropped
+        let vaults = profiles
+            .resolve(&["work".to_string(), ALL_VAULTS.to_string()])
+            .unwrap();
+        let names: Vec<_> = vaults.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["work", "personal"]);
+    }
+
+    #[test]
+    fn test_resolve_paths_and_errors() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path().join("Notes");
+        fs::create_dir(&vault).unwrap();
+
+        let vaults = profiles()
+            .resolve(&[vault.to_string_lossy().to_string()])
+            .unwrap();
+        assert_eq!(vaults, vec![("Notes".to_string(), vault)]);
+
+        let error = profiles().resolve(&["nope".to_string()]).unwrap_err();
+        assert!(error.contains("personal, work"));
+        assert!(
+            Profiles::default()
+                .resolve(&[ALL_VAULTS.to_string()])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_expand_home() {
+        assert_eq!(
+            expand_home(Path::new("/abs/path")),
+            PathBuf::from("/abs/path")
+        );
+        if let Some(home) = home_dir() {
+            assert_eq!(expand_home(Path::new("~/Vaults")), home.join("Vaults"));
+        }
+    }
+}
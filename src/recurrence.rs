@@ -0,0 +1,343 @@
+//! Recurrence rules for repeating tasks
+//!
+//! Parses the Obsidian Tasks style rule written after `🔁`, e.g.
+//! `🔁 every week on Monday`, and expands a recurring task into its future
+//! occurrences.
+
+use crate::extractor::{Task, TaskStatus};
+use chrono::{Datelike, Days, Months, NaiveDate, Weekday};
+
+/// Upper bound on occurrences generated for a single task
+const MAX_OCCURRENCES: usize = 366;
+
+/// Unit a recurrence interval is counted in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+/// A parsed recurrence rule
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recurrence {
+    /// Repeat every `interval` units
+    pub interval: u32,
+    pub frequency: Frequency,
+    /// Days of the week for weekly rules (`every week on Monday, Thursday`)
+    pub weekdays: Vec<Weekday>,
+    /// Day of the month for monthly rules (`every month on the 15th`)
+    pub month_day: Option<u32>,
+    /// The next occurrence is counted from the completion date (`when done`)
+    pub when_done: bool,
+}
+
+impl Recurrence {
+    /// Parse a rule such as `every day`, `every 2 weeks`, `every weekday`,
+    /// `every Monday and Friday`, `every month on the 1st`, `yearly` or
+    /// `every week when done`
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim().to_lowercase();
+        let (text, when_done) = match text.strip_suffix("when done") {
+            Some(rest) => (rest.trim_end(), true),
+            None => (text.as_str(), false),
+        };
+
+        let words: Vec<&str> = text
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|w| !w.is_empty() && *w != "and")
+            .collect();
+
+        let mut rule = Recurrence {
+            interval: 1,
+            frequency: Frequency::Day,
+            weekdays: Vec::new(),
+            month_day: None,
+            when_done,
+        };
+
+        let rest = match words.as_slice() {
+            [] => return None,
+            ["daily", rest @ ..] => rest,
+            ["weekly", rest @ ..] => {
+                rule.frequency = Frequency::Week;
+                rest
+            }
+            ["monthly", rest @ ..] => {
+                rule.frequency = Frequency::Month;
+                rest
+            }
+            ["yearly" | "annually", rest @ ..] => {
+                rule.frequency = Frequency::Year;
+                rest
+            }
+            ["every", "weekday", rest @ ..] => {
+                rule.frequency = Frequency::Week;
+                rule.weekdays = vec![
+                    Weekday::Mon,
+                    Weekday::Tue,
+                    Weekday::Wed,
+                    Weekday::Thu,
+                    Weekday::Fri,
+                ];
+                rest
+            }
+            ["every", rest @ ..] => {
+                let rest = match rest {
+                    ["other", rest @ ..] => {
+                        rule.interval = 2;
+                        rest
+                    }
+                    [n, rest @ ..] if n.parse::<u32>().is_ok() => {
+                        rule.interval = n.parse().ok().filter(|n| *n > 0)?;
+                        rest
+                    }
+                    _ => rest,
+                };
+                match rest {
+                    [unit, rest @ ..] if parse_unit(unit).is_some() => {
+                        rule.frequency = parse_unit(unit)?;
+                        rest
+                    }
+                    // "every Monday, Thursday"
+                    days if !days.is_empty() => {
+                        rule.frequency = Frequency::Week;
+                        rule.weekdays = days
+                            .iter()
+                            .map(|day| parse_weekday(day))
+                            .collect::<Option<_>>()?;
+                        &[]
+                    }
+                    _ => return None,
+                }
+            }
+            _ => return None,
+        };
+
+        match (rule.frequency, rest) {
+            (_, []) => {}
+            (Frequency::Week, ["on", days @ ..]) if !days.is_empty() => {
+                rule.weekdays = days
+                    .iter()
+                    .map(|day| parse_weekday(day))
+                    .collect::<Option<_>>()?;
+            }
+            (Frequency::Month, ["on", "the", day] | ["on", day]) => {
+                rule.month_day = Some(parse_ordinal(day)?);
+            }
+            _ => return None,
+        }
+
+        rule.weekdays.sort_by_key(Weekday::num_days_from_monday);
+        rule.weekdays.dedup();
+        Some(rule)
+    }
+
+    /// The first occurrence strictly after `date`
+    pub fn next_after(&self, date: NaiveDate) -> Option<NaiveDate> {
+        match self.frequency {
+            Frequency::Day => date.checked_add_days(Days::new(self.interval.into())),
+            Frequency::Week if self.weekdays.is_empty() => {
+                date.checked_add_days(Days::new(7 * u64::from(self.interval)))
+            }
+            Frequency::Week => {
+                // Later in the same week, else the first listed day of the
+                // week `interval` weeks on
+                let week_start = date.week(Weekday::Mon).first_day();
+                let later = self
+                    .weekdays
+                    .iter()
+                    .map(|day| day.num_days_from_monday())
+                    .find(|&day| day > date.weekday().num_days_from_monday());
+                match later {
+                    Some(day) => week_start.checked_add_days(Days::new(day.into())),
+                    None => week_start.checked_add_days(Days::new(
+                        7 * u64::from(self.interval)
+                            + u64::from(self.weekdays[0].num_days_from_monday()),
+                    )),
+                }
+            }
+            Frequency::Month => match self.month_day {
+                Some(day) if day > date.day() => on_day(date, day),
+                Some(day) => on_day(date.checked_add_months(Months::new(self.interval))?, day),
+                None => date.checked_add_months(Months::new(self.interval)),
+            },
+            Frequency::Year => date.checked_add_months(Months::new(12 * self.interval)),
+        }
+    }
+}
+
+/// `day` of `date`'s month, clamped to the month's last day
+fn on_day(date: NaiveDate, day: u32) -> Option<NaiveDate> {
+    (1..=day).rev().find_map(|d| date.with_day(d))
+}
+
+fn parse_unit(word: &str) -> Option<Frequency> {
+    match word {
+        "day" | "days" => Some(Frequency::Day),
+        "week" | "weeks" => Some(Frequency::Week),
+        "month" | "months" => Some(Frequency::Month),
+        "year" | "years" => Some(Frequency::Year),
+        _ => None,
+    }
+}
+
+fn parse_weekday(word: &str) -> Option<Weekday> {
+    word.trim_end_matches('s').parse().ok()
+}
+
+/// Parse `15`, `15th`, `1st`, `22nd` or `3rd`
+fn parse_ordinal(word: &str) -> Option<u32> {
+    let digits = word.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    digits.parse().ok().filter(|day| (1..=31).contains(day))
+}
+
+/// Add the future occurrences of recurring tasks, up to and including `horizon`
+///
+/// Each incomplete task with a due date and a `🔁` rule is followed by one
+/// virtual copy per later due date, marked as an occurrence. Scheduled and
+/// start dates move by the same number of days as the due date. `when done`
+/// rules depend on when the task gets completed, so they aren't expanded.
+pub fn expand_recurrences(tasks: Vec<Task>, horizon: NaiveDate) -> Vec<Task> {
+    let mut expanded = Vec::with_capacity(tasks.len());
+    for task in tasks {
+        let rule = task
+            .recurrence
+            .as_deref()
+            .and_then(Recurrence::parse)
+            .filter(|rule| !rule.when_done);
+        let due = task.due_date;
+        let template = match (rule, due) {
+            (Some(rule), Some(due)) if task.status == TaskStatus::Incomplete => {
+                Some((rule, due, task.clone()))
+            }
+            _ => None,
+        };
+        expanded.push(task);
+
+        if let Some((rule, first_due, template)) = template {
+            let mut due = first_due;
+            for _ in 0..MAX_OCCURRENCES {
+                match rule.next_after(due) {
+                    Some(next) if next <= horizon => due = next,
+                    _ => break,
+                }
+                let shift = |date: NaiveDate| date.checked_add_signed(due - first_due);
+                expanded.push(Task {
+                    due_date: Some(due),
+                    scheduled_date: template.scheduled_date.and_then(shift),
+                    start_date: template.start_date.and_then(shift),
+                    occurrence: true,
+                    ..template.clone()
+                });
+            }
+        }
+    }
+    expanded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_parse_rules() {
+        let rule = Recurrence::parse("every 2 weeks").unwrap();
+        assert_eq!(rule.interval, 2);
+        assert_eq!(rule.frequency, Frequency::Week);
+
+        let rule = Recurrence::parse("every week on Thursday, Monday when done").unwrap();
+        assert_eq!(rule.weekdays, vec![Weekday::Mon, Weekday::Thu]);
+        assert!(rule.when_done);
+
+        let rule = Recurrence::parse("every Monday and Friday").unwrap();
+        assert_eq!(rule.frequency, Frequency::Week);
+        assert_eq!(rule.weekdays, vec![Weekday::Mon, Weekday::Fri]);
+
+        let rule = Recurrence::parse("every month on the 31st").unwrap();
+        assert_eq!(rule.month_day, Some(31));
+
+        assert_eq!(
+            Recurrence::parse("monthly").unwrap().frequency,
+            Frequency::Month
+        );
+        assert_eq!(Recurrence::parse("every other day").unwrap().interval, 2);
+        assert!(Recurrence::parse("every 0 days").is_none());
+        assert!(Recurrence::parse("sometimes").is_none());
+        assert!(Recurrence::parse("every week on Caturday").is_none());
+    }
+
+    #[test]
+    fn test_next_after() {
+        let next = |rule: &str, from: &str| Recurrence::parse(rule).unwrap().next_after(date(from));
+
+        assert_eq!(next("every 3 days", "2025-01-30"), Some(date("2025-02-02")));
+        assert_eq!(next("every week", "2025-01-06"), Some(date("2025-01-13")));
+        // 2025-01-07 is a Tuesday
+        assert_eq!(
+            next("every weekday", "2025-01-07"),
+            Some(date("2025-01-08"))
+        );
+        assert_eq!(
+            next("every weekday", "2025-01-10"),
+            Some(date("2025-01-13"))
+        );
+        assert_eq!(
+            next("every 2 weeks on Monday, Wednesday", "2025-01-08"),
+            Some(date("2025-01-20"))
+        );
+        assert_eq!(next("every month", "2025-01-31"), Some(date("2025-02-28")));
+        assert_eq!(
+            next("every month on the 31st", "2025-01-31"),
+            Some(date("2025-02-28"))
+        );
+        assert_eq!(
+            next("every month on the 15th", "2025-01-10"),
+            Some(date("2025-01-15"))
+        );
+        assert_eq!(next("yearly", "2024-02-29"), Some(date("2025-02-28")));
+    }
+
+    #[test]
+    fn test_expand_recurrences() {
+        let extractor = crate::extractor::TaskExtractor::default();
+        let tasks = extractor.extract_tasks_from_content(
+            "- [ ] Water plants 🔁 every week ⏳ 2025-01-05 📅 2025-01-06\n\
+             - [ ] Pay rent 🔁 every month when done 📅 2025-01-01\n\
+             - [x] Old chore 🔁 every day 📅 2025-01-01\n",
+            std::path::Path::new("chores.md"),
+        );
+        assert_eq!(tasks[0].recurrence.as_deref(), Some("every week"));
+        assert_eq!(tasks[0].content, "Water plants");
+
+        let expanded = expand_recurrences(tasks, date("2025-01-27"));
+        let water: Vec<_> = expanded
+            .iter()
+            .filter(|t| t.content == "Water plants")
+            .map(|t| (t.due_date.unwrap(), t.occurrence))
+            .collect();
+        assert_eq!(
+            water,
+            vec![
+                (date("2025-01-06"), false),
+                (date("2025-01-13"), true),
+                (date("2025-01-20"), true),
+                (date("2025-01-27"), true),
+            ]
+        );
+        let scheduled: Vec<_> = expanded
+            .iter()
+            .filter(|t| t.content == "Water plants")
+            .map(|t| t.scheduled_date.unwrap())
+            .collect();
+        assert_eq!(scheduled[1], date("2025-01-12"));
+        assert_eq!(scheduled[3], date("2025-01-26"));
+        // "when done" rules and completed tasks aren't expanded
+        assert_eq!(expanded.len(), 6);
+    }
+}
@@ -0,0 +1,231 @@
+//! `run` command: execute an operation from a YAML or JSON request file
+//!
+//! ```yaml
+//! operation: tasks        # CLI name of the operation
+//! path: ../Vault          # optional, relative to the request file
+//! params:                 # the same parameters the HTTP API accepts
+//!   status: incomplete
+//!   tags: [work]
+//!   due_before: 2025-12-31
+//! ```
+
+use crate::capabilities::CapabilityRegistry;
+use crate::config::Config;
+use crate::operation::Operation;
+use clap::{CommandFactory, FromArgMatches, Parser};
+use serde::Deserialize;
+use serde_json::{Map, Value, json};
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Run an operation described in a request file
+#[derive(Parser, Debug)]
+#[command(
+    name = "run",
+    about = "Run an operation from a YAML or JSON request file"
+)]
+pub struct RunCommand {
+    /// Request file with `operation`, optional `path`, and `params`
+    #[arg(index = 1, required = true)]
+    pub file: PathBuf,
+
+    /// Vault to run against (overrides the file's `path`; defaults to the
+    /// current directory)
+    #[arg(index = 2)]
+    pub vault: Option<PathBuf>,
+}
+
+/// Contents of a request file
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RequestFile {
+    /// Operation to run, by CLI name (e.g. "tasks", "search")
+    pub operation: String,
+
+    /// Vault path, relative to the request file
+    #[serde(default)]
+    pub path: Option<PathBuf>,
+
+    /// Operation parameters, as accepted by the HTTP API
+    #[serde(default)]
+    pub params: Map<String, Value>,
+}
+
+impl RequestFile {
+    /// Read a request file; YAML is a superset of JSON, so both parse
+    pub fn load(file: &Path) -> Result<Self, Box<dyn Error>> {
+        let content = fs::read_to_string(file)
+            .map_err(|e| format!("Failed to read {}: {}", file.display(), e))?;
+        serde_yaml::from_str(&content)
+            .map_err(|e| format!("Invalid request file {}: {}", file.display(), e).into())
+    }
+}
+
+/// Execute a request file against a vault
+///
+/// The vault is `vault` if given, else the file's `path` (relative to the
+/// file's folder), else the current directory.
+pub async fn run_request_file(file: &Path, vault: Option<&Path>) -> Result<Value, Box<dyn Error>> {
+    let request = RequestFile::load(file)?;
+
+    let base_path = match (vault, &request.path) {
+        (Some(vault), _) => vault.to_path_buf(),
+        (None, Some(path)) => file.parent().unwrap_or(Path::new(".")).join(path),
+        (None, None) => PathBuf::from("."),
+    };
+
+    let config = Arc::new(Config::load_from_base_path(&base_path));
+    let operations = CapabilityRegistry::new(base_path, config).create_operations();
+    let Some(operation) = operations.iter().find(|op| op.name() == request.operation) else {
+        let names: Vec<&str> = operations.iter().map(|op| op.name()).collect();
+        return Err(format!(
+            "Unknown operation '{}'. Available: {}",
+            request.operation,
+            names.join(", ")
+        )
+        .into());
+    };
+
+    Ok(operation
+        .execute_json(Value::Object(request.params))
+        .await?)
+}
+
+/// CliOperation implementation for the run command
+#[derive(Default)]
+pub struct RunOperation;
+
+impl RunOperation {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait::async_trait]
+impl Operation for RunOperation {
+    fn name(&self) -> &'static str {
+        "run"
+    }
+
+    fn path(&self) -> &'static str {
+        // RunOperation is CLI-only and doesn't have an HTTP endpoint
+        ""
+    }
+
+    fn description(&self) -> &'static str {
+        "Run an operation from a YAML or JSON request file"
+    }
+
+    fn get_command(&self) -> clap::Command {
+        RunCommand::command()
+    }
+
+    async fn execute_json(&self, _json: Value) -> Result<Value, crate::error::Error> {
+        // RunOperation is CLI-only and doesn't support JSON execution
+        Err(crate::error::unsupported(
+            "run command is only available via CLI",
+        ))
+    }
+
+    async fn execute_from_args(
+        &self,
+        matches: &clap::ArgMatches,
+        _registry: &CapabilityRegistry,
+    ) -> Result<String, Box<dyn Error>> {
+        let cmd = RunCommand::from_arg_matches(matches)?;
+        let output = run_request_file(&cmd.file, cmd.vault.as_deref()).await?;
+        Ok(serde_json::to_string_pretty(&output)?)
+    }
+
+    fn input_schema(&self) -> Value {
+        // RunOperation is CLI-only and doesn't have a meaningful JSON schema
+        json!({
+            "type": "object",
+            "properties": {}
+        })
+    }
+
+    fn output_schema(&self) -> Value {
+        // RunOperation is CLI-only and doesn't return a JSON response
+        json!({
+            "type": "object",
+            "properties": {}
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_workspace() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("Vault")).unwrap();
+        fs::write(
+            temp_dir.path().join("Vault/todo.md"),
+            "- [ ] Ship release #work 📅 2025-01-10\n- [ ] Buy milk\n- [x] Done #work\n",
+        )
+        .unwrap();
+        temp_dir
+    }
+
+    fn contents(result: &Value) -> Vec<&str> {
+        result["tasks"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| t["content"].as_str().unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_run_yaml_request() {
+        let temp_dir = create_workspace();
+        let file = temp_dir.path().join("work.yaml");
+        fs::write(
+            &file,
+            "operation: tasks\npath: Vault\nparams:\n  status: incomplete\n  tags: [work]\n",
+        )
+        .unwrap();
+
+        let result = run_request_file(&file, None).await.unwrap();
+        assert_eq!(contents(&result), vec!["Ship release #work"]);
+    }
+
+    #[tokio::test]
+    async fn test_run_json_request_with_vault_override() {
+        let temp_dir = create_workspace();
+        let file = temp_dir.path().join("undated.json");
+        fs::write(
+            &file,
+            r#"{"operation": "tasks", "path": "elsewhere", "params": {"no_due_date": true, "status": "incomplete"}}"#,
+        )
+        .unwrap();
+
+        let vault = temp_dir.path().join("Vault");
+        let result = run_request_file(&file, Some(&vault)).await.unwrap();
+        assert_eq!(contents(&result), vec!["Buy milk"]);
+    }
+
+    #[tokio::test]
+    async fn test_run_errors() {
+        let temp_dir = create_workspace();
+        let vault = temp_dir.path().join("Vault");
+
+        let file = temp_dir.path().join("unknown.yaml");
+        fs::write(&file, "operation: nope\n").unwrap();
+        let error = run_request_file(&file, Some(&vault)).await.unwrap_err();
+        assert!(error.to_string().contains("Unknown operation 'nope'"));
+
+        let file = temp_dir.path().join("bad.yaml");
+        fs::write(&file, "operation: tasks\nparams:\n  status: sideways\n").unwrap();
+        assert!(run_request_file(&file, Some(&vault)).await.is_err());
+
+        let file = temp_dir.path().join("typo.yaml");
+        fs::write(&file, "operation: tasks\nparam: {}\n").unwrap();
+        assert!(run_request_file(&file, Some(&vault)).await.is_err());
+    }
+}
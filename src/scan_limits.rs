@@ -0,0 +1,221 @@
+//! Limits on how much of a directory tree a scan may traverse
+//!
+//! `max_files`, `max_depth`, and `max_total_bytes` guard against pointing the
+//! tool at something like `$HOME` by mistake. A scan that goes over a limit
+//! fails with [`ScanLimitExceeded`], or with `scan_limit_action = "truncate"`
+//! returns what it found so far along with a `scan_limit` warning.
+
+use crate::config::{Config, ScanLimitAction};
+use crate::warnings::{ExtractionWarning, WarningKind, Warnings};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+
+/// A scan went over one of the configured limits
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScanLimitExceeded {
+    /// Directory being scanned
+    pub path: PathBuf,
+    /// Config option that was exceeded, e.g. `max_files`
+    pub limit: &'static str,
+    /// Value of that option
+    pub max: u64,
+}
+
+impl fmt::Display for ScanLimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let unit = match self.limit {
+            "max_files" => "files",
+            "max_depth" => "directory levels",
+            _ => "bytes",
+        };
+        write!(
+            f,
+            "{} has more than {} {} ({}); narrow the path, add exclude_paths, or raise the limit",
+            self.path.display(),
+            self.max,
+            unit,
+            self.limit
+        )
+    }
+}
+
+impl std::error::Error for ScanLimitExceeded {}
+
+/// Tracks one scan against the configured limits
+///
+/// Safe to share between the threads of a parallel scan. Walkers ask
+/// [`enter_dir`](Self::enter_dir) before descending and
+/// [`admit_file`](Self::admit_file) before reading a file, then call
+/// [`finish`](Self::finish) once the walk is done.
+pub struct ScanBudget<'a> {
+    config: &'a Config,
+    root: PathBuf,
+    files: AtomicUsize,
+    bytes: AtomicU64,
+    /// Set once no more files may be read
+    stopped: AtomicBool,
+    /// First limit that was exceeded
+    exceeded: OnceLock<ScanLimitExceeded>,
+}
+
+impl<'a> ScanBudget<'a> {
+    pub fn new(config: &'a Config, root: &Path) -> Self {
+        Self {
+            config,
+            root: root.to_path_buf(),
+            files: AtomicUsize::new(0),
+            bytes: AtomicU64::new(0),
+            stopped: AtomicBool::new(false),
+            exceeded: OnceLock::new(),
+        }
+    }
+
+    /// Whether to descend into a directory `depth` levels below the root
+    /// (its direct subdirectories are depth 1)
+    pub fn enter_dir(&self, depth: usize) -> bool {
+        if self.stopped.load(Ordering::Relaxed) {
+            return false;
+        }
+        match self.config.max_depth {
+            Some(max) if depth > max => {
+                // Truncating only skips the deeper directories
+                self.exceed("max_depth", max as u64, false);
+                false
+            }
+            _ => true,
+        }
+    }
+
+    /// Whether `path` may be read, counting it against the limits
+    pub fn admit_file(&self, path: &Path) -> bool {
+        if self.stopped.load(Ordering::Relaxed) {
+            return false;
+        }
+
+        let files = self.files.fetch_add(1, Ordering::Relaxed) + 1;
+        if let Some(max) = self.config.max_files
+            && files > max
+        {
+            self.exceed("max_files", max as u64, true);
+            return false;
+        }
+
+        if let Some(max) = self.config.max_total_bytes {
+            let len = fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+            if self.bytes.fetch_add(len, Ordering::Relaxed) + len > max {
+                self.exceed("max_total_bytes", max, true);
+                return false;
+            }
+        }
+        true
+    }
+
+    fn exceed(&self, limit: &'static str, max: u64, stop: bool) {
+        let _ = self.exceeded.set(ScanLimitExceeded {
+            path: self.root.clone(),
+            limit,
+            max,
+        });
+        if stop || self.config.scan_limit_action == ScanLimitAction::Abort {
+            self.stopped.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// End the scan: an error if a limit was exceeded, unless the config
+    /// truncates, in which case a `scan_limit` warning is added instead
+    pub fn finish(self, warnings: &Warnings) -> Result<(), ScanLimitExceeded> {
+        let Some(exceeded) = self.exceeded.into_inner() else {
+            return Ok(());
+        };
+        match self.config.scan_limit_action {
+            ScanLimitAction::Abort => Err(exceeded),
+            ScanLimitAction::Truncate => {
+                warnings.push(ExtractionWarning::new(
+                    &exceeded.path,
+                    WarningKind::ScanLimit,
+                    format!("results truncated: {}", exceeded),
+                ));
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn config(action: ScanLimitAction) -> Config {
+        Config {
+            max_files: Some(2),
+            max_depth: Some(1),
+            scan_limit_action: action,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_abort_stops_at_first_limit() {
+        let config = config(ScanLimitAction::Abort);
+        let budget = ScanBudget::new(&config, Path::new("/vault"));
+        assert!(budget.enter_dir(1));
+        assert!(budget.admit_file(Path::new("/vault/a.md")));
+        assert!(budget.admit_file(Path::new("/vault/b.md")));
+        assert!(!budget.admit_file(Path::new("/vault/c.md")));
+        assert!(!budget.enter_dir(1));
+
+        let warnings = Warnings::default();
+        let error = budget.finish(&warnings).unwrap_err();
+        assert_eq!(error.limit, "max_files");
+        assert!(error.to_string().contains("more than 2 files (max_files)"));
+        assert!(warnings.into_vec().is_empty());
+    }
+
+    #[test]
+    fn test_truncate_skips_deep_dirs_and_warns() {
+        let config = config(ScanLimitAction::Truncate);
+        let budget = ScanBudget::new(&config, Path::new("/vault"));
+        assert!(!budget.enter_dir(2));
+        // Too-deep directories don't stop the rest of the scan
+        assert!(budget.enter_dir(1));
+        assert!(budget.admit_file(Path::new("/vault/a.md")));
+
+        let warnings = Warnings::default();
+        budget.finish(&warnings).unwrap();
+        let warnings = warnings.into_vec();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].kind, WarningKind::ScanLimit);
+        assert!(warnings[0].message.contains("(max_depth)"));
+    }
+
+    #[test]
+    fn test_max_total_bytes() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("a.md");
+        fs::write(&file, "x".repeat(60)).unwrap();
+        let config = Config {
+            max_total_bytes: Some(100),
+            ..Default::default()
+        };
+        let budget = ScanBudget::new(&config, temp_dir.path());
+        assert!(budget.admit_file(&file));
+        assert!(!budget.admit_file(&file));
+        assert_eq!(
+            budget.finish(&Warnings::default()).unwrap_err().limit,
+            "max_total_bytes"
+        );
+    }
+
+    #[test]
+    fn test_no_limits() {
+        let config = Config::default();
+        let budget = ScanBudget::new(&config, Path::new("/vault"));
+        assert!(budget.enter_dir(100));
+        assert!(budget.admit_file(Path::new("/vault/a.md")));
+        assert!(budget.finish(&Warnings::default()).is_ok());
+    }
+}
@@ -0,0 +1,428 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use chrono::{DateTime, Local};
+use croner::Cron;
+use serde_json::{Value, json};
+
+use crate::capabilities::CapabilityRegistry;
+use crate::capabilities::digest::send_digest;
+use crate::config::{Config, ReportConfig};
+use crate::operation::Operation;
+
+type SchedulerResult<T> = Result<T, Box<dyn Error + Send + Sync>>;
+
+/// A configured report with its parsed cron schedule
+struct ScheduledReport {
+    config: ReportConfig,
+    cron: Cron,
+}
+
+impl ScheduledReport {
+    fn next_after(&self, time: &DateTime<Local>) -> Option<DateTime<Local>> {
+        self.cron.find_next_occurrence(time, false).ok()
+    }
+}
+
+/// Runs the `[[reports]]` configured for a vault on their cron schedules
+///
+/// Each report executes a registered operation with saved parameters (the same
+/// JSON the HTTP API accepts), renders the result to Markdown, and writes it to
+/// a vault note and/or POSTs it to a webhook. When `[smtp]` has a `schedule`,
+/// the email digest is sent on it too, skipping days with nothing due.
+pub struct Scheduler {
+    base_path: PathBuf,
+    operations: HashMap<&'static str, Arc<dyn Operation>>,
+    reports: Vec<ScheduledReport>,
+}
+
+impl Scheduler {
+    /// Build a scheduler from the vault configuration
+    ///
+    /// Fails if a report has an invalid cron expression, names an unknown
+    /// operation, or has neither a note nor a webhook to deliver to.
+    pub fn new(base_path: PathBuf, config: Arc<Config>) -> SchedulerResult<Self> {
+        let registry = CapabilityRegistry::new(base_path.clone(), Arc::clone(&config));
+        let operations: HashMap<_, _> = registry
+            .create_operations()
+            .into_iter()
+            .map(|op| (op.name(), op))
+            .collect();
+
+        let mut reports = Vec::with_capacity(config.reports.len());
+        for report in &config.reports {
+            let cron = Cron::from_str(&report.schedule).map_err(|e| {
+                format!(
+                    "Report '{}': invalid schedule '{}': {}",
+                    report.name, report.schedule, e
+                )
+            })?;
+            if !operations.contains_key(report.operation.as_str()) {
+                return Err(format!(
+                    "Report '{}': unknown operation '{}'",
+                    report.name, report.operation
+                )
+                .into());
+            }
+            if report.note.is_none() && report.webhook.is_none() {
+                return Err(format!(
+                    "Report '{}': either 'note' or 'webhook' must be set",
+                    report.name
+                )
+                .into());
+            }
+            reports.push(ScheduledReport {
+                config: report.clone(),
+                cron,
+            });
+        }
+
+        // The email digest runs as a report that delivers itself
+        if let Some(schedule) = config.smtp.as_ref().and_then(|smtp| smtp.schedule.as_ref()) {
+            let cron = Cron::from_str(schedule)
+                .map_err(|e| format!("Email digest: invalid schedule '{}': {}", schedule, e))?;
+            let mut params = serde_json::Map::new();
+            params.insert("skip_if_empty".to_string(), Value::Bool(true));
+            reports.push(ScheduledReport {
+                config: ReportConfig {
+                    name: "Email digest".to_string(),
+                    schedule: schedule.clone(),
+                    operation: send_digest::CLI_NAME.to_string(),
+                    params,
+                    note: None,
+                    webhook: None,
+                },
+                cron,
+            });
+        }
+
+        Ok(Self {
+            base_path,
+            operations,
+            reports,
+        })
+    }
+
+    /// Whether any reports are configured
+    pub fn is_empty(&self) -> bool {
+        self.reports.is_empty()
+    }
+
+    /// Number of configured reports
+    pub fn len(&self) -> usize {
+        self.reports.len()
+    }
+
+    /// Run reports forever, sleeping until the next one is due
+    ///
+    /// Failures are logged to stderr and don't stop later runs.
+    pub async fn run(self) {
+        let now = Local::now();
+        let mut next_runs: Vec<_> = self.reports.iter().map(|r| r.next_after(&now)).collect();
+
+        loop {
+            let Some(wake_at) = next_runs.iter().flatten().min().copied() else {
+                return;
+            };
+
+            let wait = (wake_at - Local::now()).to_std().unwrap_or_default();
+            tokio::time::sleep(wait).await;
+
+            for (report, next_run) in self.reports.iter().zip(next_runs.iter_mut()) {
+                let Some(due_at) = *next_run else { continue };
+                if due_at > wake_at {
+                    continue;
+                }
+
+                match self.run_report(&report.config, due_at).await {
+                    Ok(()) => eprintln!("Report '{}' generated", report.config.name),
+                    Err(e) => eprintln!("Report '{}' failed: {}", report.config.name, e),
+                }
+                *next_run = report.next_after(&due_at);
+            }
+        }
+    }
+
+    /// Execute a single report and deliver it to its note and/or webhook
+    pub async fn run_report(
+        &self,
+        report: &ReportConfig,
+        generated_at: DateTime<Local>,
+    ) -> SchedulerResult<()> {
+        let operation = self
+            .operations
+            .get(report.operation.as_str())
+            .ok_or_else(|| format!("unknown operation '{}'", report.operation))?;
+
+        let result = operation
+            .execute_json(Value::Object(report.params.clone()))
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let markdown = render_report(&report.name, generated_at, &result);
+
+        if let Some(note) = &report.note {
+            let note_path = resolve_note_path(&self.base_path, note, generated_at)?;
+            if let Some(parent) = note_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&note_path, &markdown)?;
+        }
+
+        if let Some(url) = &report.webhook {
+            let payload = json!({
+                "report": report.name,
+                "generated_at": generated_at.to_rfc3339(),
+                "markdown": markdown,
+                "result": result,
+            });
+            let url = url.clone();
+            tokio::task::spawn_blocking(move || ureq::post(&url).send_json(&payload)).await??;
+        }
+
+        Ok(())
+    }
+}
+
+/// Resolve a report's note template to a path inside the vault
+///
+/// `{date}` is replaced with the run date (YYYY-MM-DD). The result must be a
+/// relative `.md` path without `..` components.
+fn resolve_note_path(
+    base_path: &Path,
+    template: &str,
+    generated_at: DateTime<Local>,
+) -> SchedulerResult<PathBuf> {
+    let relative =
+        PathBuf::from(template.replace("{date}", &generated_at.format("%Y-%m-%d").to_string()));
+
+    if !relative
+        .components()
+        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+    {
+        return Err(format!("note path must stay within the vault: {}", template).into());
+    }
+    if relative.extension().and_then(|e| e.to_str()) != Some("md") {
+        return Err(format!("note path must be a .md file: {}", template).into());
+    }
+
+    Ok(base_path.join(relative))
+}
+
+/// Render an operation result as a Markdown report
+///
+/// Task results become a bullet list linking back to the source notes. Plain
+/// bullets are used rather than checkboxes so a report written into the vault
+/// doesn't show up in later task searches. Any other result is included as a
+/// JSON code block.
+pub fn render_report(name: &str, generated_at: DateTime<Local>, result: &Value) -> String {
+    let mut output = format!(
+        "# {}\n\n_Generated {}_\n\n",
+        name,
+        generated_at.format("%Y-%m-%d %H:%M")
+    );
+
+    match result.get("tasks").and_then(Value::as_array) {
+        Some(tasks) if tasks.is_empty() => output.push_str("_No matching tasks._\n"),
+        Some(tasks) => {
+            for task in tasks {
+                output.push_str(&render_task(task));
+                output.push('\n');
+            }
+        }
+        None => {
+            let pretty = serde_json::to_string_pretty(result).unwrap_or_default();
+            output.push_str("```json\n");
+            output.push_str(&pretty);
+            output.push_str("\n```\n");
+        }
+    }
+
+    output
+}
+
+/// Render a task result as a plain bullet linking back to its note
+pub(crate) fn render_task(task: &Value) -> String {
+    let mut line = format!("- {}", task["content"].as_str().unwrap_or(""));
+
+    if let Some(file_name) = task["file_name"].as_str() {
+        let note = file_name.strip_suffix(".md").unwrap_or(file_name);
+        line.push_str(&format!(" ([[{}]])", note));
+    }
+
+    let mut details = Vec::new();
+    if let Some(status) = task["status"].as_str().filter(|s| *s != "incomplete") {
+        details.push(status.to_string());
+    }
+    if let Some(due) = task["due_date"].as_str() {
+        details.push(format!("due {}", due));
+    }
+    if !details.is_empty() {
+        line.push_str(" — ");
+        line.push_str(&details.join(", "));
+    }
+
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tempfile::TempDir;
+
+    fn report(operation: &str, note: Option<&str>) -> ReportConfig {
+        ReportConfig {
+            name: "Weekly review".to_string(),
+            schedule: "0 8 * * MON".to_string(),
+            operation: operation.to_string(),
+            params: serde_json::Map::new(),
+            note: note.map(str::to_string),
+            webhook: None,
+        }
+    }
+
+    fn config_with(reports: Vec<ReportConfig>) -> Arc<Config> {
+        Arc::new(Config {
+            reports,
+            ..Default::default()
+        })
+    }
+
+    fn generated_at() -> DateTime<Local> {
+        Local.with_ymd_and_hms(2025, 1, 6, 8, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_new_validates_reports() {
+        let temp_dir = TempDir::new().unwrap();
+        let base = temp_dir.path().to_path_buf();
+
+        let scheduler = Scheduler::new(
+            base.clone(),
+            config_with(vec![report("tasks", Some("r.md"))]),
+        )
+        .unwrap();
+        assert_eq!(scheduler.len(), 1);
+
+        let mut bad_schedule = report("tasks", Some("r.md"));
+        bad_schedule.schedule = "not a cron".to_string();
+        assert!(Scheduler::new(base.clone(), config_with(vec![bad_schedule])).is_err());
+
+        let unknown_op = report("nope", Some("r.md"));
+        assert!(Scheduler::new(base.clone(), config_with(vec![unknown_op])).is_err());
+
+        let no_target = report("tasks", None);
+        assert!(Scheduler::new(base, config_with(vec![no_target])).is_err());
+    }
+
+    #[test]
+    fn test_new_schedules_email_digest() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut smtp: crate::config::SmtpConfig = toml::from_str(
+            r#"
+host = "localhost"
+from = "vault@example.com"
+to = ["me@example.com"]
+schedule = "0 7 * * *"
+"#,
+        )
+        .unwrap();
+        let config = Arc::new(Config {
+            smtp: Some(smtp.clone()),
+            ..Default::default()
+        });
+
+        let scheduler = Scheduler::new(temp_dir.path().to_path_buf(), config).unwrap();
+        assert_eq!(scheduler.len(), 1);
+        assert_eq!(scheduler.reports[0].config.operation, "send-digest");
+
+        smtp.schedule = Some("whenever".to_string());
+        let config = Arc::new(Config {
+            smtp: Some(smtp),
+            ..Default::default()
+        });
+        assert!(Scheduler::new(temp_dir.path().to_path_buf(), config).is_err());
+    }
+
+    #[test]
+    fn test_next_after() {
+        let scheduled = ScheduledReport {
+            config: report("tasks", Some("r.md")),
+            cron: Cron::from_str("0 8 * * MON").unwrap(),
+        };
+
+        // 2025-01-06 is a Monday; the next run is a week later
+        let next = scheduled.next_after(&generated_at()).unwrap();
+        assert_eq!(next, Local.with_ymd_and_hms(2025, 1, 13, 8, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn test_resolve_note_path() {
+        let base = Path::new("/vault");
+
+        let path = resolve_note_path(base, "Reviews/{date}.md", generated_at()).unwrap();
+        assert_eq!(path, PathBuf::from("/vault/Reviews/2025-01-06.md"));
+
+        assert!(resolve_note_path(base, "../outside.md", generated_at()).is_err());
+        assert!(resolve_note_path(base, "/etc/report.md", generated_at()).is_err());
+        assert!(resolve_note_path(base, "Reviews/report.txt", generated_at()).is_err());
+    }
+
+    #[test]
+    fn test_render_tasks() {
+        let result = json!({
+            "tasks": [
+                {"content": "Write report", "status": "incomplete", "file_name": "Work.md", "due_date": "2025-01-03"},
+                {"content": "Send invoice", "status": "completed", "file_name": "Admin.md", "due_date": null},
+            ]
+        });
+
+        let markdown = render_report("Overdue", generated_at(), &result);
+        assert_eq!(
+            markdown,
+            "# Overdue\n\n_Generated 2025-01-06 08:00_\n\n\
+             - Write report ([[Work]]) — due 2025-01-03\n\
+             - Send invoice ([[Admin]]) — completed\n"
+        );
+
+        let markdown = render_report("Overdue", generated_at(), &json!({"tasks": []}));
+        assert!(markdown.ends_with("_No matching tasks._\n"));
+    }
+
+    #[test]
+    fn test_render_other_results_as_json() {
+        let result = json!({"tags": [{"tag": "work", "document_count": 2}]});
+        let markdown = render_report("Tags", generated_at(), &result);
+        assert!(markdown.contains("```json\n"));
+        assert!(markdown.contains("\"tag\": \"work\""));
+    }
+
+    #[tokio::test]
+    async fn test_run_report_writes_note() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("todo.md"),
+            "- [ ] Write report 📅 2025-01-03\n- [x] Send invoice\n",
+        )
+        .unwrap();
+
+        let mut report = report("tasks", Some("Reviews/{date}.md"));
+        report
+            .params
+            .insert("status".to_string(), json!("incomplete"));
+
+        let base = temp_dir.path().canonicalize().unwrap();
+        let scheduler = Scheduler::new(base, config_with(vec![report.clone()])).unwrap();
+        scheduler.run_report(&report, generated_at()).await.unwrap();
+
+        let written = fs::read_to_string(temp_dir.path().join("Reviews/2025-01-06.md")).unwrap();
+        assert!(written.starts_with("# Weekly review\n"));
+        assert!(written.contains("- Write report ([[todo]]) — due 2025-01-03"));
+        assert!(!written.contains("Send invoice"));
+    }
+}
@@ -0,0 +1,195 @@
+//! JSON Schemas for every operation's request and response
+//!
+//! Exposed as the `schema` CLI command and the `/api/schemas` endpoint so
+//! downstream code generators can build typed clients.
+
+use crate::capabilities::CapabilityRegistry;
+use crate::operation::Operation;
+use clap::{CommandFactory, FromArgMatches, Parser};
+use serde_json::{Map, Value, json};
+use std::sync::Arc;
+
+/// HTTP path of the schema listing
+pub const HTTP_PATH: &str = "/api/schemas";
+
+/// Print JSON Schemas for operation requests and responses
+#[derive(Parser, Debug)]
+#[command(
+    name = "schema",
+    about = "Print JSON Schemas for operation requests and responses"
+)]
+pub struct SchemaCommand {
+    /// Only print the schemas for this operation (CLI name, e.g. "tasks")
+    #[arg(long)]
+    pub operation: Option<String>,
+
+    /// Print the typed gRPC service (`proto/vault.proto`) instead
+    #[cfg(feature = "grpc")]
+    #[arg(long, conflicts_with = "operation")]
+    pub proto: bool,
+}
+
+/// Request and response schemas for operations exposed over HTTP/MCP
+///
+/// Keyed by operation name; CLI-only operations (those without an HTTP path)
+/// are skipped.
+pub fn operation_schemas(operations: &[Arc<dyn Operation>]) -> Value {
+    let schemas: Map<String, Value> = operations
+        .iter()
+        .filter(|op| !op.path().is_empty())
+        .map(|op| {
+            (
+                op.name().to_string(),
+                json!({
+                    "path": op.path(),
+                    "description": op.description(),
+                    "request": op.input_schema(),
+                    "response": op.output_schema(),
+                }),
+            )
+        })
+        .collect();
+
+    Value::Object(schemas)
+}
+
+/// CliOperation implementation for the schema command
+#[derive(Default)]
+pub struct SchemaOperation;
+
+impl SchemaOperation {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait::async_trait]
+impl Operation for SchemaOperation {
+    fn name(&self) -> &'static str {
+        "schema"
+    }
+
+    fn path(&self) -> &'static str {
+        // SchemaOperation is CLI-only; the HTTP listing is served by main.rs
+        ""
+    }
+
+    fn description(&self) -> &'static str {
+        "Print JSON Schemas for operation requests and responses"
+    }
+
+    fn get_command(&self) -> clap::Command {
+        SchemaCommand::command()
+    }
+
+    async fn execute_json(&self, _json: Value) -> Result<Value, crate::error::Error> {
+        // SchemaOperation is CLI-only and doesn't support JSON execution
+        Err(crate::error::unsupported(
+            "schema command is only available via CLI",
+        ))
+    }
+
+    async fn execute_from_args(
+        &self,
+        matches: &clap::ArgMatches,
+        registry: &CapabilityRegistry,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        let cmd = SchemaCommand::from_arg_matches(matches)?;
+        #[cfg(feature = "grpc")]
+        if cmd.proto {
+            let schema = crate::grpc_schema::ProtoSchema::build(
+                &registry.create_operations(),
+                crate::grpc_schema::VAULT_PROTO,
+            );
+            // The CLI adds the final line break
+            return Ok(schema.render().trim_end().to_string());
+        }
+        let schemas = operation_schemas(&registry.create_operations());
+
+        let output = match cmd.operation {
+            Some(name) => schemas
+                .get(&name)
+                .cloned()
+                .ok_or_else(|| format!("Unknown operation: {}", name))?,
+            None => schemas,
+        };
+
+        Ok(serde_json::to_string_pretty(&output)?)
+    }
+
+    fn input_schema(&self) -> Value {
+        // SchemaOperation is CLI-only and doesn't have a meaningful JSON schema
+        json!({
+            "type": "object",
+            "properties": {}
+        })
+    }
+
+    fn output_schema(&self) -> Value {
+        // SchemaOperation is CLI-only and doesn't return a JSON response
+        json!({
+            "type": "object",
+            "properties": {}
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use std::path::PathBuf;
+
+    fn registry() -> CapabilityRegistry {
+        CapabilityRegistry::new(PathBuf::from("."), Arc::new(Config::default()))
+    }
+
+    #[test]
+    fn test_operation_schemas_cover_registry() {
+        let registry = registry();
+        let mut operations = registry.create_operations();
+        operations.push(Arc::new(SchemaOperation::new()));
+
+        let schemas = operation_schemas(&operations);
+        let schemas = schemas.as_object().unwrap();
+
+        // Every HTTP operation is listed; the CLI-only schema command is not
+        assert_eq!(schemas.len(), registry.create_operations().len());
+        assert!(!schemas.contains_key("schema"));
+
+        let tasks = &schemas["tasks"];
+        assert_eq!(tasks["path"], "/api/tasks");
+        assert_eq!(tasks["request"]["title"], "SearchTasksRequest");
+        assert_eq!(tasks["response"]["title"], "TaskSearchResponse");
+        assert!(tasks["response"]["properties"]["tasks"].is_object());
+        assert!(tasks["response"]["$defs"]["Task"].is_object());
+    }
+
+    #[tokio::test]
+    async fn test_schema_command_single_operation() {
+        let registry = registry();
+        let operation = SchemaOperation::new();
+        let matches = operation
+            .get_command()
+            .try_get_matches_from(["schema", "--operation", "list-tags"])
+            .unwrap();
+
+        let output = operation
+            .execute_from_args(&matches, &registry)
+            .await
+            .unwrap();
+        let schema: Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(schema["response"]["title"], "ListTagsResponse");
+
+        let matches = operation
+            .get_command()
+            .try_get_matches_from(["schema", "--operation", "nope"])
+            .unwrap();
+        assert!(
+            operation
+                .execute_from_args(&matches, &registry)
+                .await
+                .is_err()
+        );
+    }
+}
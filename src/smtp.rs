@@ -0,0 +1,297 @@
+//! Minimal SMTP client for sending plain-text mail
+//!
+//! Supports STARTTLS, implicit TLS, and unencrypted connections, with AUTH
+//! PLAIN. Blocking; run it on a blocking thread from async code.
+
+use crate::config::{SmtpConfig, SmtpSecurity};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use chrono::Local;
+use rustls::pki_types::ServerName;
+use rustls::{ClientConfig, ClientConnection, RootCertStore, StreamOwned};
+use std::error::Error;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::sync::Arc;
+use std::time::Duration;
+
+type SmtpResult<T> = Result<T, Box<dyn Error + Send + Sync>>;
+
+/// Socket read/write timeout
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A plain or TLS-wrapped connection to the server
+enum Connection {
+    Plain(TcpStream),
+    Tls(Box<StreamOwned<ClientConnection, TcpStream>>),
+}
+
+impl Connection {
+    fn stream(&mut self) -> &mut dyn ReadWrite {
+        match self {
+            Connection::Plain(stream) => stream,
+            Connection::Tls(stream) => stream.as_mut(),
+        }
+    }
+}
+
+trait ReadWrite: Read + Write {}
+impl<T: Read + Write> ReadWrite for T {}
+
+/// An SMTP session
+struct Session {
+    connection: Connection,
+}
+
+impl Session {
+    /// Read a (possibly multi-line) reply and check its code
+    fn expect(&mut self, codes: &[u16]) -> SmtpResult<String> {
+        let mut reply = String::new();
+        loop {
+            let line = self.read_line()?;
+            reply.push_str(&line);
+            reply.push('\n');
+            // Continuation lines are "250-...", the last one "250 ..."
+            if line.as_bytes().get(3) != Some(&b'-') {
+                break;
+            }
+        }
+
+        let code: u16 = reply.get(..3).and_then(|c| c.parse().ok()).unwrap_or(0);
+        if codes.contains(&code) {
+            Ok(reply)
+        } else {
+            Err(format!("SMTP server replied: {}", reply.trim_end()).into())
+        }
+    }
+
+    fn read_line(&mut self) -> SmtpResult<String> {
+        let mut line = Vec::new();
+        let mut byte = [0u8; 1];
+        while !line.ends_with(b"\r\n") {
+            if self.connection.stream().read(&mut byte)? == 0 {
+                return Err("SMTP server closed the connection".into());
+            }
+            line.push(byte[0]);
+        }
+        line.truncate(line.len() - 2);
+        Ok(String::from_utf8_lossy(&line).into_owned())
+    }
+
+    fn send(&mut self, command: &str) -> SmtpResult<()> {
+        let stream = self.connection.stream();
+        stream.write_all(command.as_bytes())?;
+        stream.write_all(b"\r\n")?;
+        stream.flush()?;
+        Ok(())
+    }
+
+    fn command(&mut self, command: &str, codes: &[u16]) -> SmtpResult<String> {
+        self.send(command)?;
+        self.expect(codes)
+    }
+
+    /// Switch the connection to TLS
+    fn start_tls(self, host: &str) -> SmtpResult<Self> {
+        match self.connection {
+            Connection::Plain(stream) => Ok(Session {
+                connection: tls_connect(host, stream)?,
+            }),
+            tls => Ok(Session { connection: tls }),
+        }
+    }
+}
+
+fn tls_connect(host: &str, stream: TcpStream) -> SmtpResult<Connection> {
+    let roots = RootCertStore {
+        roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+    };
+    let config =
+        ClientConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+            .with_safe_default_protocol_versions()?
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+    let name = ServerName::try_from(host.to_string())?;
+    let connection = ClientConnection::new(Arc::new(config), name)?;
+    Ok(Connection::Tls(Box::new(StreamOwned::new(
+        connection, stream,
+    ))))
+}
+
+/// Send a plain-text message to the configured recipients
+pub fn send_mail(config: &SmtpConfig, subject: &str, body: &str) -> SmtpResult<()> {
+    if config.to.is_empty() {
+        return Err("smtp.to has no recipients".into());
+    }
+
+    let stream = TcpStream::connect((config.host.as_str(), config.port))?;
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+
+    let connection = match config.security {
+        SmtpSecurity::Tls => tls_connect(&config.host, stream)?,
+        SmtpSecurity::StartTls | SmtpSecurity::None => Connection::Plain(stream),
+    };
+    let mut session = Session { connection };
+    session.expect(&[220])?;
+    session.command("EHLO localhost", &[250])?;
+
+    if config.security == SmtpSecurity::StartTls {
+        session.command("STARTTLS", &[220])?;
+        session = session.start_tls(&config.host)?;
+        session.command("EHLO localhost", &[250])?;
+    }
+
+    if let Some(username) = &config.username {
+        let password = config.password().unwrap_or_default();
+        let credentials = BASE64.encode(format!("\0{}\0{}", username, password));
+        session.command(&format!("AUTH PLAIN {}", credentials), &[235])?;
+    }
+
+    session.command(
+        &format!("MAIL FROM:<{}>", mailbox_address(&config.from)),
+        &[250],
+    )?;
+    for recipient in &config.to {
+        session.command(
+            &format!("RCPT TO:<{}>", mailbox_address(recipient)),
+            &[250, 251],
+        )?;
+    }
+    session.command("DATA", &[354])?;
+    session.send(&format_message(config, subject, body))?;
+    session.command(".", &[250])?;
+    // The message is accepted; a failed QUIT doesn't matter
+    let _ = session.command("QUIT", &[221]);
+
+    Ok(())
+}
+
+/// The bare address of a mailbox such as `Name <me@example.com>`
+fn mailbox_address(mailbox: &str) -> &str {
+    match (mailbox.rfind('<'), mailbox.rfind('>')) {
+        (Some(start), Some(end)) if start < end => &mailbox[start + 1..end],
+        _ => mailbox.trim(),
+    }
+}
+
+/// Render the message headers and base64-encoded body, ready for DATA
+///
+/// Base64 keeps lines short and avoids dot-stuffing and 8BITMIME concerns.
+fn format_message(config: &SmtpConfig, subject: &str, body: &str) -> String {
+    let subject = if subject.is_ascii() {
+        subject.to_string()
+    } else {
+        format!("=?UTF-8?B?{}?=", BASE64.encode(subject))
+    };
+
+    let mut message = format!(
+        "From: {}\r\nTo: {}\r\nSubject: {}\r\nDate: {}\r\nMIME-Version: 1.0\r\n\
+         Content-Type: text/plain; charset=utf-8\r\nContent-Transfer-Encoding: base64\r\n\r\n",
+        config.from,
+        config.to.join(", "),
+        subject,
+        Local::now().to_rfc2822(),
+    );
+    let encoded = BASE64.encode(body.replace("\r\n", "\n").replace('\n', "\r\n"));
+    let lines: Vec<&str> = encoded
+        .as_bytes()
+        .chunks(76)
+        .map(|chunk| std::str::from_utf8(chunk).unwrap_or_default())
+        .collect();
+    message.push_str(&lines.join("\r\n"));
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader};
+    use std::net::TcpListener;
+
+    fn smtp_config(port: u16) -> SmtpConfig {
+        SmtpConfig {
+            host: "127.0.0.1".to_string(),
+            port,
+            security: SmtpSecurity::None,
+            username: Some("me".to_string()),
+            password: Some("secret".to_string()),
+            password_env: None,
+            from: "Vault <vault@example.com>".to_string(),
+            to: vec!["me@example.com".to_string()],
+            schedule: None,
+        }
+    }
+
+    #[test]
+    fn test_mailbox_address() {
+        assert_eq!(
+            mailbox_address("Vault <vault@example.com>"),
+            "vault@example.com"
+        );
+        assert_eq!(mailbox_address(" me@example.com "), "me@example.com");
+    }
+
+    #[test]
+    fn test_format_message() {
+        let message = format_message(&smtp_config(25), "Tâches", "Line 1\nLine 2");
+        assert!(message.contains("Subject: =?UTF-8?B?VMOiY2hlcw==?=\r\n"));
+        assert!(message.contains("To: me@example.com\r\n"));
+
+        let body = message.split("\r\n\r\n").nth(1).unwrap();
+        assert_eq!(BASE64.decode(body).unwrap(), b"Line 1\r\nLine 2");
+    }
+
+    #[test]
+    fn test_send_mail() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        // A scripted server that records the client's commands
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut writer = stream;
+            let mut commands = Vec::new();
+            writer.write_all(b"220 test ready\r\n").unwrap();
+            loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line).unwrap() == 0 {
+                    break;
+                }
+                let line = line.trim_end().to_string();
+                let reply: &[u8] = match line.as_str() {
+                    l if l.starts_with("EHLO") => b"250-test\r\n250 AUTH PLAIN\r\n",
+                    l if l.starts_with("AUTH") => b"235 ok\r\n",
+                    "DATA" => b"354 go ahead\r\n",
+                    "." => b"250 queued\r\n",
+                    "QUIT" => b"221 bye\r\n",
+                    l if l.starts_with("MAIL") || l.starts_with("RCPT") => b"250 ok\r\n",
+                    _ => b"",
+                };
+                writer.write_all(reply).unwrap();
+                let quit = line == "QUIT";
+                commands.push(line);
+                if quit {
+                    break;
+                }
+            }
+            commands
+        });
+
+        send_mail(&smtp_config(port), "Digest", "Hello").unwrap();
+        let commands = server.join().unwrap();
+
+        assert_eq!(commands[0], "EHLO localhost");
+        assert_eq!(
+            commands[1],
+            format!("AUTH PLAIN {}", BASE64.encode("\0me\0secret"))
+        );
+        assert_eq!(commands[2], "MAIL FROM:<vault@example.com>");
+        assert_eq!(commands[3], "RCPT TO:<me@example.com>");
+        assert_eq!(commands[4], "DATA");
+        assert!(commands.contains(&"Subject: Digest".to_string()));
+        assert!(commands.contains(&BASE64.encode("Hello")));
+        assert_eq!(commands.last().unwrap(), "QUIT");
+    }
+}
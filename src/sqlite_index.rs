@@ -0,0 +1,710 @@
+//! SQLite vault index (`sqlite` feature)
+//!
+//! `reindex` parses every note and code file of a vault into a SQLite
+//! database kept next to the `--cache` index (`<hash>.sqlite` under
+//! `~/.cache/markdown-todo-extractor`). It holds one row per file with the
+//! file's modification time and size, and the tasks, tags, headings, and
+//! links found in it; running `reindex` again only re-parses files that
+//! changed. With `sqlite_index = true` in the vault config, task, tag, and
+//! heading searches over the whole vault read the database instead of the
+//! files. Searches of a single file or subfolder still scan. The database is
+//! as current as the last `reindex`, or kept current by `serve --watch`.
+
+use crate::cache;
+use crate::capabilities::CapabilityRegistry;
+use crate::config::Config;
+use crate::extractor::{Task, TaskExtractor};
+use crate::link_extractor::{LinkExtractor, LinkOccurrence};
+use crate::operation::Operation;
+use crate::outline_extractor::{Heading, OutlineExtractor};
+use crate::path_resolver::{from_slash, to_slash};
+use crate::tag_extractor::TagExtractor;
+use clap::{CommandFactory, FromArgMatches, Parser};
+use rayon::prelude::*;
+use rusqlite::{Connection, OpenFlags, params};
+use serde::Serialize;
+use serde_json::{Value, json};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Instant;
+
+type IndexResult<T> = Result<T, Box<dyn Error>>;
+
+const SCHEMA: &str = "
+    PRAGMA journal_mode = WAL;
+    CREATE TABLE IF NOT EXISTS meta (key TEXT PRIMARY KEY, value TEXT NOT NULL);
+    CREATE TABLE IF NOT EXISTS files (
+        path TEXT PRIMARY KEY,
+        modified INTEGER NOT NULL,
+        len INTEGER NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS tasks (
+        file TEXT NOT NULL,
+        line INTEGER NOT NULL,
+        status TEXT NOT NULL,
+        content TEXT NOT NULL,
+        due_date TEXT,
+        data TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS tags (
+        file TEXT NOT NULL,
+        tag TEXT NOT NULL,
+        inline INTEGER NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS headings (
+        file TEXT NOT NULL,
+        line INTEGER NOT NULL,
+        level INTEGER NOT NULL,
+        title TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS links (
+        file TEXT NOT NULL,
+        line INTEGER NOT NULL,
+        target TEXT NOT NULL,
+        heading TEXT,
+        block TEXT,
+        alias TEXT,
+        embed INTEGER NOT NULL,
+        markdown INTEGER NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS tasks_file ON tasks (file);
+    CREATE INDEX IF NOT EXISTS tags_file ON tags (file);
+    CREATE INDEX IF NOT EXISTS tags_tag ON tags (tag);
+    CREATE INDEX IF NOT EXISTS headings_file ON headings (file);
+    CREATE INDEX IF NOT EXISTS links_file ON links (file);
+    CREATE INDEX IF NOT EXISTS links_target ON links (target);
+";
+
+/// Tables holding per-file rows, keyed by their file column
+const FILE_TABLES: [(&str, &str); 5] = [
+    ("files", "path"),
+    ("tasks", "file"),
+    ("tags", "file"),
+    ("headings", "file"),
+    ("links", "file"),
+];
+
+/// Database file for the vault at `base_path`, if a cache directory exists
+pub fn index_path(base_path: &Path) -> Option<PathBuf> {
+    cache::vault_cache_path(base_path).map(|path| path.with_extension("sqlite"))
+}
+
+/// Everything indexed for one file
+struct FileRecord {
+    /// Vault-relative path with `/` separators
+    path: String,
+    stamp: (u128, u64),
+    tasks: Vec<Task>,
+    /// Tags with whether they only appear inline in the body
+    tags: Vec<(String, bool)>,
+    headings: Vec<Heading>,
+    links: Vec<LinkOccurrence>,
+}
+
+/// Parses files into records
+struct Parsers {
+    config: Arc<Config>,
+    tasks: TaskExtractor,
+    tags: TagExtractor,
+    outline: OutlineExtractor,
+    links: LinkExtractor,
+}
+
+impl Parsers {
+    fn new(config: Arc<Config>) -> Self {
+        Self {
+            tasks: TaskExtractor::new(Arc::clone(&config)),
+            tags: TagExtractor::new(Arc::clone(&config)),
+            outline: OutlineExtractor::new(),
+            links: LinkExtractor::new(),
+            config,
+        }
+    }
+
+    /// Parse `file`, or `None` if it can't be read as UTF-8 text
+    fn parse(&self, base_path: &Path, file: &Path) -> Option<FileRecord> {
+        let stamp = cache::stamp(file).ok()?;
+        let content = fs::read_to_string(file).ok()?;
+        let path = to_slash(file.strip_prefix(base_path).ok()?);
+        let mut record = FileRecord {
+            path,
+            stamp,
+            tasks: self.tasks.extract_tasks_from_file_content(&content, file),
+            tags: Vec::new(),
+            headings: Vec::new(),
+            links: Vec::new(),
+        };
+        if self.config.is_code_file(file) {
+            return Some(record);
+        }
+
+        // Notes with invalid frontmatter are indexed without tags
+        let frontmatter: HashSet<String> = self
+            .tags
+            .extract_tags_from_content(&content, false)
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+        let all = self
+            .tags
+            .extract_tags_from_content(&content, true)
+            .unwrap_or_default();
+        record.tags = all
+            .into_iter()
+            .map(|tag| {
+                let inline = !frontmatter.contains(&tag);
+                (tag, inline)
+            })
+            .collect();
+        record.headings = self.outline.extract_headings(&content);
+        record.links = self.links.extract_link_occurrences(&content, file);
+        Some(record)
+    }
+}
+
+/// Notes and code files under `dir` that a task scan of the vault reads
+fn walk(dir: &Path, config: &Config, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if config.should_exclude(&path) {
+            continue;
+        }
+        if path.is_dir() {
+            walk(&path, config, files)?;
+        } else if config.is_scannable_markdown(&path) || config.is_code_file(&path) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Whether a scan of `base_path` with `config` skips `file`, because the file
+/// or one of its folders is excluded
+fn is_excluded(config: &Config, base_path: &Path, file: &Path) -> bool {
+    file.ancestors()
+        .take_while(|path| *path != base_path)
+        .any(|path| config.should_exclude(path))
+}
+
+/// Hash of the settings that change what a file's tasks and tags parse to:
+/// the `--cache` index's `cache::config_key()` plus the frontmatter tag keys
+fn config_key(config: &Config) -> String {
+    let tag_keys = format!("{:?}", config.frontmatter_tag_keys);
+    format!(
+        "{}-{:016x}",
+        cache::config_key(config),
+        cache::fnv1a(tag_keys.as_bytes())
+    )
+}
+
+/// Value of `key` in the `meta` table
+fn meta(conn: &Connection, key: &str) -> Option<String> {
+    conn.query_row("SELECT value FROM meta WHERE key = ?1", [key], |row| {
+        row.get(0)
+    })
+    .ok()
+}
+
+/// Whether the index was written by this version with settings that parse
+/// files like `config`
+fn is_current(conn: &Connection, config: &Config) -> bool {
+    meta(conn, "version").as_deref() == Some(env!("CARGO_PKG_VERSION"))
+        && meta(conn, "config") == Some(config_key(config))
+}
+
+/// Create the tables if needed and drop the contents of an index written by
+/// another version or under other settings, which may have parsed files
+/// differently
+fn prepare(conn: &Connection, config: &Config) -> rusqlite::Result<()> {
+    conn.execute_batch(SCHEMA)?;
+    if !is_current(conn, config) {
+        for (table, _) in FILE_TABLES {
+            conn.execute(&format!("DELETE FROM {}", table), [])?;
+        }
+        conn.execute(
+            "INSERT OR REPLACE INTO meta (key, value) VALUES ('version', ?1), ('config', ?2)",
+            [env!("CARGO_PKG_VERSION"), &config_key(config)],
+        )?;
+    }
+    Ok(())
+}
+
+/// Delete the rows of `path` and of every file under it
+fn delete(conn: &Connection, path: &str) -> rusqlite::Result<()> {
+    let prefix = format!("{}/", path);
+    let prefix_len = prefix.chars().count() as i64;
+    for (table, column) in FILE_TABLES {
+        conn.execute(
+            &format!(
+                "DELETE FROM {0} WHERE {1} = ?1 OR substr({1}, 1, ?3) = ?2",
+                table, column
+            ),
+            params![path, prefix, prefix_len],
+        )?;
+    }
+    Ok(())
+}
+
+fn insert(conn: &Connection, record: &FileRecord) -> Result<(), Box<dyn Error>> {
+    conn.execute(
+        "INSERT INTO files (path, modified, len) VALUES (?1, ?2, ?3)",
+        params![record.path, record.stamp.0 as i64, record.stamp.1 as i64],
+    )?;
+    let mut task_row = conn.prepare_cached(
+        "INSERT INTO tasks (file, line, status, content, due_date, data)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+    )?;
+    for task in &record.tasks {
+        let status = serde_json::to_value(task.status)?;
+        task_row.execute(params![
+            record.path,
+            task.line_number as i64,
+            status.as_str(),
+            task.content,
+            task.due_date.map(|date| date.to_string()),
+            serde_json::to_string(task)?,
+        ])?;
+    }
+    let mut tag_row =
+        conn.prepare_cached("INSERT INTO tags (file, tag, inline) VALUES (?1, ?2, ?3)")?;
+    for (tag, inline) in &record.tags {
+        tag_row.execute(params![record.path, tag, inline])?;
+    }
+    let mut heading_row = conn.prepare_cached(
+        "INSERT INTO headings (file, line, level, title) VALUES (?1, ?2, ?3, ?4)",
+    )?;
+    for heading in &record.headings {
+        heading_row.execute(params![
+            record.path,
+            heading.line_number as i64,
+            heading.level,
+            heading.title
+        ])?;
+    }
+    let mut link_row = conn.prepare_cached(
+        "INSERT INTO links (file, line, target, heading, block, alias, embed, markdown)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+    )?;
+    for occurrence in &record.links {
+        let link = &occurrence.link;
+        link_row.execute(params![
+            record.path,
+            occurrence.line_number as i64,
+            link.target,
+            link.heading,
+            link.block,
+            link.alias,
+            link.embed,
+            occurrence.markdown
+        ])?;
+    }
+    Ok(())
+}
+
+/// Result of `reindex`
+#[derive(Debug, Serialize)]
+pub struct ReindexReport {
+    pub index_file: String,
+    /// Files in the index
+    pub files: usize,
+    /// Files parsed because they were new or changed
+    pub parsed: usize,
+    /// Files dropped because they were deleted or are now excluded
+    pub removed: usize,
+    /// Files that couldn't be read
+    pub unreadable: usize,
+    pub tasks: usize,
+    pub tags: usize,
+    pub headings: usize,
+    pub links: usize,
+    pub elapsed_ms: u128,
+}
+
+/// Bring the index at `index_file` up to date with the vault at `base_path`,
+/// parsing only new and changed files
+pub fn reindex(
+    base_path: &Path,
+    config: Arc<Config>,
+    index_file: &Path,
+) -> Result<ReindexReport, Box<dyn Error>> {
+    let started = Instant::now();
+    if let Some(dir) = index_file.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut conn = Connection::open(index_file)?;
+    prepare(&conn, &config)?;
+
+    let indexed: HashMap<String, (i64, i64)> = conn
+        .prepare("SELECT path, modified, len FROM files")?
+        .query_map([], |row| Ok((row.get(0)?, (row.get(1)?, row.get(2)?))))?
+        .collect::<Result<_, _>>()?;
+
+    let mut files = Vec::new();
+    walk(base_path, &config, &mut files)?;
+    let mut seen = HashSet::new();
+    let changed: Vec<&PathBuf> = files
+        .iter()
+        .filter(|file| {
+            let Ok(relative) = file.strip_prefix(base_path) else {
+                return false;
+            };
+            let relative = to_slash(relative);
+            let current = cache::stamp(file)
+                .ok()
+                .map(|(modified, len)| (modified as i64, len as i64));
+            let unchanged = current.is_some() && indexed.get(&relative) == current.as_ref();
+            seen.insert(relative);
+            !unchanged
+        })
+        .collect();
+
+    let parsers = Parsers::new(config);
+    let records: Vec<FileRecord> = changed
+        .par_iter()
+        .filter_map(|file| parsers.parse(base_path, file))
+        .collect();
+    let removed: Vec<&String> = indexed
+        .keys()
+        .filter(|path| !seen.contains(*path))
+        .collect();
+
+    let tx = conn.transaction()?;
+    for path in &removed {
+        delete(&tx, path)?;
+    }
+    for record in &records {
+        delete(&tx, &record.path)?;
+        insert(&tx, record)?;
+    }
+    tx.commit()?;
+
+    let count = |table: &str| -> rusqlite::Result<usize> {
+        conn.query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| {
+            row.get::<_, i64>(0)
+        })
+        .map(|count| count as usize)
+    };
+    Ok(ReindexReport {
+        index_file: index_file.to_string_lossy().to_string(),
+        files: count("files")?,
+        parsed: records.len(),
+        removed: removed.len(),
+        unreadable: changed.len() - records.len(),
+        tasks: count("tasks")?,
+        tags: count("tags")?,
+        headings: count("headings")?,
+        links: count("links")?,
+        elapsed_ms: started.elapsed().as_millis(),
+    })
+}
+
+/// Re-index `paths` (files or folders that changed, were created, or were
+/// deleted) in the vault's index, if `config` enables it and one exists
+pub fn refresh(
+    base_path: &Path,
+    config: Arc<Config>,
+    paths: &[PathBuf],
+) -> Result<(), Box<dyn Error>> {
+    let Some(index) = VaultIndex::for_vault(base_path, &config) else {
+        return Ok(());
+    };
+    let mut conn = index.conn;
+    let parsers = Parsers::new(Arc::clone(&config));
+
+    let tx = conn.transaction()?;
+    for path in paths {
+        let Ok(relative) = path.strip_prefix(base_path) else {
+            continue;
+        };
+        if relative.as_os_str().is_empty() {
+            continue;
+        }
+        delete(&tx, &to_slash(relative))?;
+        if is_excluded(&config, base_path, path) {
+            continue;
+        }
+
+        let mut files = Vec::new();
+        if path.is_dir() {
+            walk(path, &config, &mut files)?;
+        } else if config.is_scannable_markdown(path) || config.is_code_file(path) {
+            files.push(path.clone());
+        }
+        for record in files
+            .iter()
+            .filter_map(|file| parsers.parse(base_path, file))
+        {
+            delete(&tx, &record.path)?;
+            insert(&tx, &record)?;
+        }
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+/// Read access to a vault's index
+pub struct VaultIndex {
+    base_path: PathBuf,
+    conn: Connection,
+}
+
+impl VaultIndex {
+    /// The index of the vault at `base_path`, if `config` enables it and
+    /// `reindex` has built one with this version and settings
+    pub fn for_vault(base_path: &Path, config: &Config) -> Option<Self> {
+        if !config.sqlite_index || !base_path.is_dir() {
+            return None;
+        }
+        Self::open(base_path, &index_path(base_path)?, config)
+    }
+
+    /// The index at `index_file`, if it exists and was built with this version
+    /// and with settings that parse files like `config`
+    pub fn open(base_path: &Path, index_file: &Path, config: &Config) -> Option<Self> {
+        if !index_file.is_file() {
+            return None;
+        }
+        let conn =
+            Connection::open_with_flags(index_file, OpenFlags::SQLITE_OPEN_READ_WRITE).ok()?;
+        is_current(&conn, config).then(|| Self {
+            base_path: base_path.to_path_buf(),
+            conn,
+        })
+    }
+
+    /// Path of an indexed file as a scan of the vault reports it, or `None`
+    /// if such a scan with `config` would skip it
+    fn scan_path(&self, config: &Config, path: &str) -> Option<PathBuf> {
+        let file = self.base_path.join(from_slash(path));
+        (!is_excluded(config, &self.base_path, &file)).then_some(file)
+    }
+
+    /// All tasks, as a scan of the vault with `config` would find them
+    pub fn tasks(&self, config: &Config) -> IndexResult<Vec<Task>> {
+        let mut statement = self
+            .conn
+            .prepare("SELECT file, data FROM tasks ORDER BY file, line")?;
+        let rows = statement.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?;
+
+        let mut tasks = Vec::new();
+        for row in rows {
+            let (path, data) = row?;
+            let Some(file) = self.scan_path(config, &path) else {
+                continue;
+            };
+            let mut task: Task = serde_json::from_str(&data)?;
+            task.file_path = file.to_string_lossy().to_string();
+            tasks.push(task);
+        }
+        Ok(tasks)
+    }
+
+    /// Tags of each note, frontmatter only unless `include_inline`
+    pub fn file_tags(
+        &self,
+        config: &Config,
+        include_inline: bool,
+    ) -> IndexResult<Vec<(PathBuf, Vec<String>)>> {
+        let mut statement = self
+            .conn
+            .prepare("SELECT file, tag FROM tags WHERE inline = 0 OR ?1")?;
+        let rows = statement.query_map([include_inline], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?;
+
+        let mut tags: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for row in rows {
+            let (path, tag) = row?;
+            tags.entry(path).or_default().push(tag);
+        }
+        Ok(tags
+            .into_iter()
+            .filter_map(|(path, tags)| Some((self.scan_path(config, &path)?, tags)))
+            .collect())
+    }
+
+    /// Flat headings of each note, sorted by path
+    pub fn file_headings(&self, config: &Config) -> IndexResult<Vec<(PathBuf, Vec<Heading>)>> {
+        let mut statement = self
+            .conn
+            .prepare("SELECT file, line, level, title FROM headings ORDER BY file, line")?;
+        let rows = statement.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                Heading {
+                    line_number: row.get::<_, i64>(1)? as usize,
+                    level: row.get(2)?,
+                    title: row.get(3)?,
+                    children: Vec::new(),
+                },
+            ))
+        })?;
+
+        let mut headings: BTreeMap<PathBuf, Vec<Heading>> = BTreeMap::new();
+        for row in rows {
+            let (path, heading) = row?;
+            if let Some(file) = self.scan_path(config, &path) {
+                headings.entry(file).or_default().push(heading);
+            }
+        }
+        Ok(headings.into_iter().collect())
+    }
+}
+
+/// Build or update the SQLite index of a vault
+#[derive(Parser, Debug)]
+#[command(
+    name = "reindex",
+    about = "Build or update the SQLite index of a vault (used with sqlite_index = true)"
+)]
+pub struct ReindexCommand {
+    /// Vault to index
+    #[arg(index = 1, required = true)]
+    pub path: PathBuf,
+}
+
+/// CliOperation implementation for the reindex command
+#[derive(Default)]
+pub struct ReindexOperation;
+
+impl ReindexOperation {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait::async_trait]
+impl Operation for ReindexOperation {
+    fn name(&self) -> &'static str {
+        "reindex"
+    }
+
+    fn path(&self) -> &'static str {
+        // ReindexOperation is CLI-only and doesn't have an HTTP endpoint
+        ""
+    }
+
+    fn description(&self) -> &'static str {
+        "Build or update the SQLite index of tasks, tags, headings, and links in a vault"
+    }
+
+    fn get_command(&self) -> clap::Command {
+        ReindexCommand::command()
+    }
+
+    async fn execute_json(&self, _json: Value) -> Result<Value, crate::error::Error> {
+        // ReindexOperation is CLI-only and doesn't support JSON execution
+        Err(crate::error::unsupported(
+            "reindex command is only available via CLI",
+        ))
+    }
+
+    async fn execute_from_args(
+        &self,
+        matches: &clap::ArgMatches,
+        _registry: &CapabilityRegistry,
+    ) -> Result<String, Box<dyn Error>> {
+        let cmd = ReindexCommand::from_arg_matches(matches)?;
+        let config = Arc::new(Config::load_from_base_path(&cmd.path));
+        let index_file =
+            index_path(&cmd.path).ok_or("No cache directory: set HOME or XDG_CACHE_HOME")?;
+        let report = reindex(&cmd.path, config, &index_file)?;
+        Ok(serde_json::to_string_pretty(&report)?)
+    }
+
+    fn input_schema(&self) -> Value {
+        // ReindexOperation is CLI-only and doesn't have a meaningful JSON schema
+        json!({
+            "type": "object",
+            "properties": {}
+        })
+    }
+
+    fn output_schema(&self) -> Value {
+        // ReindexOperation is CLI-only and doesn't return a JSON response
+        json!({
+            "type": "object",
+            "properties": {}
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_reindex_and_query() {
+        let vault = TempDir::new().unwrap();
+        let index_dir = TempDir::new().unwrap();
+        let index_file = index_dir.path().join("vault.sqlite");
+        fs::write(
+            vault.path().join("a.md"),
+            "---\ntags: [project]\n---\n# Plan\n- [ ] Alpha #work\n## Notes\nSee [[b]]\n",
+        )
+        .unwrap();
+        fs::write(vault.path().join("b.md"), "- [x] Beta\n").unwrap();
+        let config = Arc::new(Config::default());
+
+        let report = reindex(vault.path(), Arc::clone(&config), &index_file).unwrap();
+        assert_eq!((report.files, report.parsed, report.removed), (2, 2, 0));
+        assert_eq!((report.tasks, report.headings, report.links), (2, 2, 1));
+
+        let index = VaultIndex::open(vault.path(), &index_file, &config).unwrap();
+        let tasks = index.tasks(&config).unwrap();
+        let contents: Vec<&str> = tasks.iter().map(|task| task.content.as_str()).collect();
+        assert_eq!(contents, ["Alpha #work", "Beta"]);
+        assert_eq!(
+            tasks[0].file_path,
+            vault.path().join("a.md").to_string_lossy()
+        );
+
+        let tags = index.file_tags(&config, false).unwrap();
+        assert_eq!(
+            tags,
+            [(vault.path().join("a.md"), vec!["project".to_string()])]
+        );
+        let tags = index.file_tags(&config, true).unwrap();
+        assert_eq!(tags[0].1.len(), 2);
+
+        let headings = index.file_headings(&config).unwrap();
+        let titles: Vec<&str> = headings[0].1.iter().map(|h| h.title.as_str()).collect();
+        assert_eq!(titles, ["Plan", "Notes"]);
+
+        // Only changed files are parsed again
+        fs::remove_file(vault.path().join("b.md")).unwrap();
+        let report = reindex(vault.path(), config, &index_file).unwrap();
+        assert_eq!((report.files, report.parsed, report.removed), (1, 0, 1));
+        assert_eq!(report.tasks, 1);
+    }
+
+    #[test]
+    fn test_index_rebuilt_when_parse_settings_change() {
+        let vault = TempDir::new().unwrap();
+        let index_dir = TempDir::new().unwrap();
+        let index_file = index_dir.path().join("vault.sqlite");
+        fs::write(vault.path().join("a.md"), "- [ ] Ship it who:: bob\n").unwrap();
+        fs::write(vault.path().join("b.md"), "- [ ] Other\n").unwrap();
+        reindex(vault.path(), Arc::new(Config::default()), &index_file).unwrap();
+
+        // An index built under other settings isn't served
+        let config = Arc::new(Config {
+            assignee_keys: vec!["who".to_string()],
+            ..Config::default()
+        });
+        assert!(VaultIndex::open(vault.path(), &index_file, &config).is_none());
+
+        // and reindexing parses every file again
+        let report = reindex(vault.path(), Arc::clone(&config), &index_file).unwrap();
+        assert_eq!((report.files, report.parsed), (2, 2));
+        let index = VaultIndex::open(vault.path(), &index_file, &config).unwrap();
+        let tasks = index.tasks(&config).unwrap();
+        assert_eq!(tasks[0].assignee.as_deref(), Some("bob"));
+    }
+}
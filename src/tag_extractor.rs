@@ -1,15 +1,34 @@
 use crate::config::Config;
+use crate::frontmatter::{body_after_frontmatter, frontmatter_block};
+#[cfg(feature = "fs")]
+use crate::scan_limits::ScanBudget;
+#[cfg(feature = "fs")]
+use crate::unicode::fold;
+use crate::unicode::nfc;
+#[cfg(feature = "fs")]
+use crate::warnings::{ExtractionWarning, WarningKind, Warnings};
+use chrono::{DateTime, Utc};
+#[cfg(feature = "fs")]
+use glob::Pattern;
+#[cfg(feature = "fs")]
 use rayon::prelude::*;
+use regex::Regex;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeSet;
+#[cfg(feature = "fs")]
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+#[cfg(feature = "fs")]
 use std::fs;
-use std::path::{Path, PathBuf};
+#[cfg(feature = "fs")]
+use std::path::Path;
+use std::path::PathBuf;
 use std::sync::Arc;
 
-/// Extractor for YAML frontmatter tags
+/// Extractor for YAML frontmatter tags (and optionally inline body `#tags`)
 pub struct TagExtractor {
     config: Arc<Config>,
+    inline_tag_pattern: Regex,
+    inline_code_pattern: Regex,
 }
 
 /// Tag with occurrence statistics
@@ -21,6 +40,100 @@ pub struct TagCount {
     pub document_count: usize,
 }
 
+/// Options controlling which documents and tags are counted by `list_tags`
+#[derive(Debug, Clone, Default)]
+pub struct TagListOptions {
+    /// Also count inline `#tags` from note bodies
+    pub include_inline: bool,
+    /// Directories whose files are skipped
+    pub exclude_subpaths: Vec<PathBuf>,
+    /// Tags to drop (case-insensitive); nested tags under them are dropped too
+    pub exclude_tags: Vec<String>,
+}
+
+impl TagListOptions {
+    /// Whether `tag` matches an excluded tag or is nested beneath one
+    #[cfg(feature = "fs")]
+    fn is_excluded_tag(&self, tag: &str) -> bool {
+        let tag = fold(tag);
+        self.exclude_tags.iter().any(|excluded| {
+            let excluded = fold(excluded);
+            tag == excluded
+                || tag
+                    .strip_prefix(&excluded)
+                    .is_some_and(|rest| rest.starts_with('/'))
+        })
+    }
+}
+
+/// Tag statistics for a single folder
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FolderTags {
+    /// Folder path relative to the search root ("." for files directly in the root)
+    pub folder: String,
+    /// Number of markdown documents in this folder (including subfolders beyond the depth)
+    pub document_count: usize,
+    /// Number of unique tags used in this folder
+    pub unique_tags: usize,
+    /// Tags sorted by document count descending, then alphabetically
+    pub tags: Vec<TagCount>,
+}
+
+/// Node in a hierarchical tag tree built from `parent/child` style tags
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TagTreeNode {
+    /// Name of this level (the last path segment, e.g. "alpha")
+    pub name: String,
+    /// Full tag path up to this level (e.g. "project/alpha")
+    pub tag: String,
+    /// Number of documents tagged with this tag or any of its descendants
+    pub document_count: usize,
+    /// Number of documents tagged with exactly this tag
+    pub direct_count: usize,
+    /// Child tags nested under this level
+    pub children: Vec<TagTreeNode>,
+}
+
+/// Intermediate node used while building the tag tree
+#[cfg(feature = "fs")]
+#[derive(Default)]
+struct TagTreeBuilder {
+    documents: HashSet<PathBuf>,
+    direct_documents: HashSet<PathBuf>,
+    children: BTreeMap<String, TagTreeBuilder>,
+}
+
+#[cfg(feature = "fs")]
+impl TagTreeBuilder {
+    fn into_nodes(self, prefix: &str) -> Vec<TagTreeNode> {
+        let mut nodes: Vec<TagTreeNode> = self
+            .children
+            .into_iter()
+            .map(|(name, child)| {
+                let tag = if prefix.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{}/{}", prefix, name)
+                };
+                TagTreeNode {
+                    document_count: child.documents.len(),
+                    direct_count: child.direct_documents.len(),
+                    children: child.into_nodes(&tag),
+                    name,
+                    tag,
+                }
+            })
+            .collect();
+
+        nodes.sort_by(|a, b| {
+            b.document_count
+                .cmp(&a.document_count)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        nodes
+    }
+}
+
 /// Represents a file that matches tag search criteria
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TaggedFile {
@@ -28,19 +141,190 @@ pub struct TaggedFile {
     pub file_path: String,
     /// File name without path
     pub file_name: String,
-    /// Tags that matched the search criteria
+    /// The file's own tags that matched the search criteria, as written in
+    /// the file
     pub matched_tags: Vec<String>,
     /// All tags found in the file's frontmatter
     pub all_tags: Vec<String>,
+    /// Note title (frontmatter `title`, first `# ` heading, or file name without extension)
+    pub title: String,
+    /// Last modification time of the file (last commit time with `git_dates`)
+    pub modified: Option<DateTime<Utc>>,
+    /// Short plain-text excerpt: the text around the first inline occurrence
+    /// of a matched tag, or else the note's first paragraph
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub excerpt: Option<String>,
+}
+
+/// Tag recommended for a note based on term overlap with other notes
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TagSuggestion {
+    /// The suggested tag (without # prefix)
+    pub tag: String,
+    /// Relevance score (higher is better); only meaningful relative to other suggestions
+    pub score: f64,
+    /// Number of documents currently carrying this tag
+    pub document_count: usize,
+    /// Most significant terms shared between the input and notes with this tag
+    pub shared_terms: Vec<String>,
+}
+
+/// Common English words ignored when comparing note terms
+#[cfg(feature = "fs")]
+const STOP_WORDS: &[&str] = &[
+    "about", "after", "also", "and", "are", "been", "but", "can", "could", "for", "from", "had",
+    "has", "have", "her", "his", "how", "into", "its", "just", "more", "not", "now", "our", "out",
+    "she", "should", "some", "than", "that", "the", "their", "them", "then", "there", "these",
+    "they", "this", "was", "were", "what", "when", "which", "who", "will", "with", "would", "you",
+    "your",
+];
+
+/// Split text into the set of lowercase terms used for tag suggestions
+#[cfg(feature = "fs")]
+fn note_terms(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.chars().count() >= 3 && !w.chars().all(|c| c.is_ascii_digit()))
+        .map(|w| w.to_lowercase())
+        .filter(|w| !STOP_WORDS.contains(&w.as_str()))
+        .collect()
+}
+
+/// Maximum number of characters in a `TaggedFile` excerpt
+#[cfg(feature = "fs")]
+const EXCERPT_MAX_CHARS: usize = 200;
+
+/// Determine a note's title from frontmatter, its first H1 heading, or its file name
+#[cfg(feature = "fs")]
+pub(crate) fn note_title(frontmatter: Option<&str>, body: &str, file_path: &Path) -> String {
+    let frontmatter_title = frontmatter
+        .and_then(|fm| serde_yaml::from_str::<serde_yaml::Value>(fm).ok())
+        .and_then(|yaml| yaml.get("title")?.as_str().map(|t| t.trim().to_string()))
+        .filter(|t| !t.is_empty());
+    if let Some(title) = frontmatter_title {
+        return title;
+    }
+
+    body.lines()
+        .find_map(|line| line.strip_prefix("# ").map(|h| h.trim().to_string()))
+        .filter(|h| !h.is_empty())
+        .unwrap_or_else(|| {
+            file_path
+                .file_stem()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string()
+        })
+}
+
+/// Lines of the note body outside fenced code blocks
+fn body_lines_outside_fences(content: &str) -> impl Iterator<Item = &str> {
+    let mut fence: Option<&str> = None;
+    body_after_frontmatter(content).lines().filter(move |line| {
+        let trimmed = line.trim_start();
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+            return false;
+        }
+        if trimmed.starts_with("```") {
+            fence = Some("```");
+            return false;
+        }
+        if trimmed.starts_with("~~~") {
+            fence = Some("~~~");
+            return false;
+        }
+        true
+    })
+}
+
+/// Up to `EXCERPT_MAX_CHARS` characters of `line` around the byte offset `at`,
+/// with `…` where the line was cut
+#[cfg(feature = "fs")]
+fn excerpt_around(line: &str, at: usize) -> String {
+    let chars: Vec<char> = line.chars().collect();
+    let center = line[..at].chars().count();
+    let start = center
+        .saturating_sub(EXCERPT_MAX_CHARS / 2)
+        .min(chars.len().saturating_sub(EXCERPT_MAX_CHARS));
+    let end = (start + EXCERPT_MAX_CHARS).min(chars.len());
+
+    let text: String = chars[start..end].iter().collect();
+    let mut excerpt = String::new();
+    if start > 0 {
+        excerpt.push('…');
+    }
+    excerpt.push_str(text.trim());
+    if end < chars.len() {
+        excerpt.push('…');
+    }
+    excerpt
+}
+
+/// Build a short excerpt from the first paragraph of prose in the note body
+#[cfg(feature = "fs")]
+pub(crate) fn note_excerpt(body: &str) -> Option<String> {
+    let mut in_fence = false;
+    let mut paragraph: Vec<&str> = Vec::new();
+
+    for line in body.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence || trimmed.starts_with('#') {
+            continue;
+        }
+        if trimmed.is_empty() {
+            if !paragraph.is_empty() {
+                break;
+            }
+            continue;
+        }
+        paragraph.push(trimmed);
+    }
+
+    if paragraph.is_empty() {
+        return None;
+    }
+
+    let text = paragraph.join(" ");
+    if text.chars().count() <= EXCERPT_MAX_CHARS {
+        Some(text)
+    } else {
+        let truncated: String = text.chars().take(EXCERPT_MAX_CHARS).collect();
+        Some(format!("{}…", truncated.trim_end()))
+    }
 }
 
-/// Recursively collect all markdown files in a directory
-fn collect_markdown_files(
+/// Recursively collect all markdown files in a directory, within the
+/// configured scan limits
+///
+/// Files left out because of `scan_limit_action = "truncate"` are reported
+/// in `warnings`.
+#[cfg(feature = "fs")]
+pub(crate) fn collect_markdown_files(
     dir: &Path,
     config: &Config,
+    warnings: &Warnings,
 ) -> Result<Vec<PathBuf>, Box<dyn std::error::Error>> {
     let mut files = Vec::new();
+    let budget = ScanBudget::new(config, dir);
+    walk_markdown_files(dir, 0, config, &budget, &mut files)?;
+    budget.finish(warnings)?;
+    Ok(files)
+}
 
+#[cfg(feature = "fs")]
+fn walk_markdown_files(
+    dir: &Path,
+    depth: usize,
+    config: &Config,
+    budget: &ScanBudget,
+    files: &mut Vec<PathBuf>,
+) -> Result<(), Box<dyn std::error::Error>> {
     if dir.is_dir() {
         for entry in fs::read_dir(dir)? {
             let entry = entry?;
@@ -52,93 +336,197 @@ fn collect_markdown_files(
             }
 
             if path.is_dir() {
-                files.extend(collect_markdown_files(&path, config)?);
-            } else if path.extension().and_then(|s| s.to_str()) == Some("md") {
+                if budget.enter_dir(depth + 1) {
+                    walk_markdown_files(&path, depth + 1, config, budget, files)?;
+                }
+            } else if config.is_scannable_markdown(&path) && budget.admit_file(&path) {
                 files.push(path);
             }
         }
     }
 
-    Ok(files)
+    Ok(())
 }
 
 impl TagExtractor {
     pub fn new(config: Arc<Config>) -> Self {
-        Self { config }
+        Self {
+            config,
+            inline_tag_pattern: Regex::new(r"(?:^|\s)#([\w/-]+)").unwrap(),
+            inline_code_pattern: Regex::new(r"`[^`]*`").unwrap(),
+        }
     }
 
     /// Extract all unique tags from markdown files in the given path
-    pub fn extract_tags(&self, path: &Path) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    ///
+    /// When `include_inline` is true, `#tags` in note bodies are indexed too.
+    #[cfg(feature = "fs")]
+    pub fn extract_tags(
+        &self,
+        path: &Path,
+        include_inline: bool,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        self.extract_tags_with_warnings(path, include_inline)
+            .map(|(tags, _)| tags)
+    }
+
+    /// Extract all unique tags along with the files that were skipped
+    /// because they are unreadable, oversized, or have invalid frontmatter
+    /// (or left out by a truncating scan limit)
+    #[cfg(feature = "fs")]
+    pub fn extract_tags_with_warnings(
+        &self,
+        path: &Path,
+        include_inline: bool,
+    ) -> Result<(Vec<String>, Vec<ExtractionWarning>), Box<dyn std::error::Error>> {
+        #[cfg(feature = "sqlite")]
+        if let Some(index) = crate::sqlite_index::VaultIndex::for_vault(path, &self.config) {
+            let tags: BTreeSet<String> = index
+                .file_tags(&self.config, include_inline)?
+                .into_iter()
+                .flat_map(|(_, tags)| tags)
+                .collect();
+            return Ok((tags.into_iter().collect(), Vec::new()));
+        }
+
+        let warnings = Warnings::default();
         let files = if path.is_file() {
             vec![path.to_path_buf()]
         } else {
-            collect_markdown_files(path, &self.config)?
+            collect_markdown_files(path, &self.config, &warnings)?
         };
 
         // Use a BTreeSet to automatically sort and deduplicate tags
         let tags: BTreeSet<String> = files
             .par_iter()
-            .filter_map(|file_path| self.extract_tags_from_file(file_path).ok())
+            .filter_map(|file_path| {
+                self.extract_tags_from_file(file_path, include_inline)
+                    .map_err(|warning| warnings.push(warning))
+                    .ok()
+            })
             .flatten()
             .collect();
 
-        Ok(tags.into_iter().collect())
+        Ok((tags.into_iter().collect(), warnings.into_vec()))
     }
 
     /// Extract tags from a single markdown file
+    #[cfg(feature = "fs")]
     fn extract_tags_from_file(
         &self,
         file_path: &Path,
-    ) -> Result<Vec<String>, Box<dyn std::error::Error>> {
-        let content = fs::read_to_string(file_path)?;
-        self.extract_tags_from_content(&content)
+        include_inline: bool,
+    ) -> Result<Vec<String>, ExtractionWarning> {
+        self.read_tagged_note(file_path, include_inline)
+            .map(|(_, tags)| tags)
+    }
+
+    /// Read a markdown file along with its tags, or the warning it is
+    /// skipped with
+    #[cfg(feature = "fs")]
+    fn read_tagged_note(
+        &self,
+        file_path: &Path,
+        include_inline: bool,
+    ) -> Result<(String, Vec<String>), ExtractionWarning> {
+        let metadata = fs::metadata(file_path)
+            .map_err(|e| ExtractionWarning::new(file_path, WarningKind::Unreadable, e))?;
+        if let Some(max) = self.config.max_file_size
+            && metadata.len() > max
+        {
+            return Err(ExtractionWarning::oversized(file_path, metadata.len(), max));
+        }
+
+        let content = fs::read_to_string(file_path)
+            .map_err(|e| ExtractionWarning::new(file_path, WarningKind::Unreadable, e))?;
+        let tags = self
+            .extract_tags_from_content(&content, include_inline)
+            .map_err(|e| ExtractionWarning::new(file_path, WarningKind::InvalidYaml, e))?;
+        Ok((content, tags))
     }
 
-    /// Extract tags from markdown content by parsing YAML frontmatter
-    fn extract_tags_from_content(
+    /// Extract tags from markdown content by parsing YAML frontmatter,
+    /// optionally merged with inline `#tags` from the body
+    pub fn extract_tags_from_content(
         &self,
         content: &str,
+        include_inline: bool,
     ) -> Result<Vec<String>, Box<dyn std::error::Error>> {
         let frontmatter = self.extract_frontmatter(content)?;
 
-        if let Some(fm) = frontmatter {
-            self.parse_tags_from_frontmatter(&fm)
+        let mut tags = if let Some(fm) = frontmatter {
+            self.parse_tags_from_frontmatter(&fm)?
         } else {
-            Ok(vec![])
+            vec![]
+        };
+
+        if include_inline {
+            for tag in self.extract_inline_tags(content) {
+                if !tags.contains(&tag) {
+                    tags.push(tag);
+                }
+            }
+        }
+
+        Ok(tags)
+    }
+
+    /// Extract inline `#tags` from the note body
+    ///
+    /// Frontmatter, fenced code blocks, and inline code spans are skipped.
+    /// Purely numeric matches such as `#123` are not treated as tags.
+    fn extract_inline_tags(&self, content: &str) -> Vec<String> {
+        let mut tags: Vec<String> = Vec::new();
+
+        for line in body_lines_outside_fences(content) {
+            let without_code = self.inline_code_pattern.replace_all(line, "");
+            for caps in self.inline_tag_pattern.captures_iter(&without_code) {
+                let tag = nfc(caps[1].trim_end_matches('/'));
+                if tag.is_empty() || tag.chars().all(|c| c.is_ascii_digit()) {
+                    continue;
+                }
+                if !tags.iter().any(|t| *t == tag) {
+                    tags.push(tag.into_owned());
+                }
+            }
         }
+
+        tags
+    }
+
+    /// Excerpt of the line with the first inline occurrence of one of
+    /// `folded_tags` (already passed through `fold`), centered on the tag
+    #[cfg(feature = "fs")]
+    fn tag_excerpt(&self, content: &str, folded_tags: &[String]) -> Option<String> {
+        body_lines_outside_fences(content).find_map(|line| {
+            let code: Vec<_> = self
+                .inline_code_pattern
+                .find_iter(line)
+                .map(|m| m.range())
+                .collect();
+            self.inline_tag_pattern
+                .captures_iter(line)
+                .filter_map(|caps| caps.get(1))
+                .filter(|tag| !code.iter().any(|range| range.contains(&tag.start())))
+                .find(|tag| folded_tags.contains(&fold(tag.as_str().trim_end_matches('/'))))
+                .map(|tag| excerpt_around(line, tag.start()))
+        })
     }
 
     /// Extract YAML frontmatter from markdown content
     /// Frontmatter is expected to be at the start of the file between --- delimiters
-    fn extract_frontmatter(
+    pub(crate) fn extract_frontmatter(
         &self,
         content: &str,
     ) -> Result<Option<String>, Box<dyn std::error::Error>> {
-        let lines: Vec<&str> = content.lines().collect();
-
-        // Check if the file starts with ---
-        if lines.is_empty() || lines[0].trim() != "---" {
-            return Ok(None);
-        }
-
-        // Find the closing ---
-        let mut end_index = None;
-        for (i, line) in lines.iter().enumerate().skip(1) {
-            if line.trim() == "---" {
-                end_index = Some(i);
-                break;
-            }
-        }
-
-        if let Some(end) = end_index {
-            let frontmatter_lines = &lines[1..end];
-            Ok(Some(frontmatter_lines.join("\n")))
-        } else {
-            Ok(None)
-        }
+        Ok(frontmatter_block(content).map(str::to_string))
     }
 
     /// Parse tags from YAML frontmatter
+    ///
+    /// Values from every configured tag key (`tags` and `tag` by default) are
+    /// merged. Each value may be a list or a single string; strings are split
+    /// on commas so `tags: rust, cli` yields two tags.
     fn parse_tags_from_frontmatter(
         &self,
         frontmatter: &str,
@@ -146,60 +534,88 @@ impl TagExtractor {
         // Parse YAML frontmatter
         let yaml: serde_yaml::Value = serde_yaml::from_str(frontmatter)?;
 
-        // Extract tags field
-        if let Some(tags_value) = yaml.get("tags") {
-            match tags_value {
+        let mut tags: Vec<String> = Vec::new();
+        for key in &self.config.frontmatter_tag_keys {
+            let values: Vec<&str> = match yaml.get(key.as_str()) {
                 // Handle array of tags
-                serde_yaml::Value::Sequence(seq) => {
-                    let tags: Vec<String> = seq
-                        .iter()
-                        .filter_map(|v| v.as_str().map(|s| s.to_string()))
-                        .filter(|s| !s.trim().is_empty())
-                        .collect();
-                    Ok(tags)
+                Some(serde_yaml::Value::Sequence(seq)) => {
+                    seq.iter().filter_map(|v| v.as_str()).collect()
                 }
-                // Handle single tag as string
-                serde_yaml::Value::String(s) => {
-                    if s.trim().is_empty() {
-                        Ok(vec![])
-                    } else {
-                        Ok(vec![s.clone()])
-                    }
+                // Handle single tag or comma-separated tags as string
+                Some(serde_yaml::Value::String(s)) => s.split(',').collect(),
+                _ => continue,
+            };
+
+            for value in values {
+                let tag = nfc(value.trim());
+                if !tag.is_empty() && !tags.iter().any(|t| *t == tag) {
+                    tags.push(tag.into_owned());
                 }
-                _ => Ok(vec![]),
             }
-        } else {
-            Ok(vec![])
         }
+
+        Ok(tags)
     }
 
-    /// Extract all tags with document counts from markdown files in the given path
-    /// Returns tags sorted by document_count descending, then alphabetically
-    pub fn extract_tags_with_counts(
+    /// Map each tag to the set of documents containing it
+    #[cfg(feature = "fs")]
+    fn collect_tag_documents(
         &self,
         path: &Path,
-    ) -> Result<Vec<TagCount>, Box<dyn std::error::Error>> {
-        let files = if path.is_file() {
-            vec![path.to_path_buf()]
-        } else {
-            collect_markdown_files(path, &self.config)?
+        options: &TagListOptions,
+        warnings: &Warnings,
+    ) -> Result<HashMap<String, HashSet<PathBuf>>, Box<dyn std::error::Error>> {
+        let is_excluded_file = |file: &PathBuf| {
+            options
+                .exclude_subpaths
+                .iter()
+                .any(|ex| file.starts_with(ex))
+        };
+
+        // With sqlite_index, a vault's tags come from the SQLite index
+        #[cfg(feature = "sqlite")]
+        let indexed = crate::sqlite_index::VaultIndex::for_vault(path, &self.config)
+            .map(|index| index.file_tags(&self.config, options.include_inline))
+            .transpose()?;
+        #[cfg(not(feature = "sqlite"))]
+        let indexed: Option<Vec<(PathBuf, Vec<String>)>> = None;
+
+        let file_tags: Vec<(PathBuf, Vec<String>)> = match indexed {
+            Some(mut file_tags) => {
+                file_tags.retain(|(file, _)| !is_excluded_file(file));
+                file_tags
+            }
+            None => {
+                let mut files = if path.is_file() {
+                    vec![path.to_path_buf()]
+                } else {
+                    collect_markdown_files(path, &self.config, warnings)?
+                };
+                files.retain(|file| !is_excluded_file(file));
+                files
+                    .par_iter()
+                    .filter_map(|file_path| {
+                        self.extract_tags_from_file(file_path, options.include_inline)
+                            .map_err(|warning| warnings.push(warning))
+                            .ok()
+                            .map(|tags| (file_path.clone(), tags))
+                    })
+                    .collect()
+            }
         };
 
         // Track which documents contain each tag
         // Key: tag name, Value: set of file paths that contain this tag
-        use std::collections::{HashMap, HashSet};
-        let tag_documents: HashMap<String, HashSet<PathBuf>> = files
-            .par_iter()
-            .filter_map(|file_path| {
-                self.extract_tags_from_file(file_path)
-                    .ok()
-                    .map(|tags| (file_path.clone(), tags))
-            })
+        let tag_documents: HashMap<String, HashSet<PathBuf>> = file_tags
+            .into_par_iter()
             .fold(
                 HashMap::new,
                 |mut acc: HashMap<String, HashSet<PathBuf>>, (file_path, tags)| {
                     // Deduplicate tags within the same file (a file counts once per tag)
-                    let unique_tags: HashSet<String> = tags.into_iter().collect();
+                    let unique_tags: HashSet<String> = tags
+                        .into_iter()
+                        .filter(|tag| !options.is_excluded_tag(tag))
+                        .collect();
                     for tag in unique_tags {
                         acc.entry(tag).or_default().insert(file_path.clone());
                     }
@@ -213,6 +629,137 @@ impl TagExtractor {
                 a
             });
 
+        Ok(tag_documents)
+    }
+
+    /// Build a hierarchical tag tree from `parent/child` style tags
+    ///
+    /// Each node's `document_count` counts unique documents tagged with the node
+    /// or any descendant, so a note tagged both `project/alpha` and
+    /// `project/beta` counts once for `project`.
+    #[cfg(feature = "fs")]
+    pub fn extract_tag_tree(
+        &self,
+        path: &Path,
+        options: &TagListOptions,
+        warnings: &Warnings,
+    ) -> Result<Vec<TagTreeNode>, Box<dyn std::error::Error>> {
+        let tag_documents = self.collect_tag_documents(path, options, warnings)?;
+
+        let mut root = TagTreeBuilder::default();
+        for (tag, files) in tag_documents {
+            let mut node = &mut root;
+            let mut segments = tag.split('/').filter(|s| !s.is_empty()).peekable();
+            while let Some(segment) = segments.next() {
+                node = node.children.entry(segment.to_string()).or_default();
+                node.documents.extend(files.iter().cloned());
+                if segments.peek().is_none() {
+                    node.direct_documents.extend(files.iter().cloned());
+                }
+            }
+        }
+
+        Ok(root.into_nodes(""))
+    }
+
+    /// Group tag counts by folder
+    ///
+    /// Files are grouped by the first `depth` components of their folder relative
+    /// to `path`, so with `depth = 1` everything under `Projects/Alpha/` is counted
+    /// towards `Projects`. Folders are returned in alphabetical order.
+    #[cfg(feature = "fs")]
+    pub fn extract_tags_by_folder(
+        &self,
+        path: &Path,
+        depth: usize,
+        options: &TagListOptions,
+        warnings: &Warnings,
+    ) -> Result<Vec<FolderTags>, Box<dyn std::error::Error>> {
+        let mut files = if path.is_file() {
+            vec![path.to_path_buf()]
+        } else {
+            collect_markdown_files(path, &self.config, warnings)?
+        };
+        files.retain(|file| {
+            !options
+                .exclude_subpaths
+                .iter()
+                .any(|ex| file.starts_with(ex))
+        });
+
+        let file_tags: Vec<(String, HashSet<String>)> = files
+            .par_iter()
+            .filter_map(|file_path| {
+                let tags = self
+                    .extract_tags_from_file(file_path, options.include_inline)
+                    .map_err(|warning| warnings.push(warning))
+                    .ok()?;
+                let folder = file_path
+                    .parent()
+                    .and_then(|parent| parent.strip_prefix(path).ok())
+                    .map(|relative| {
+                        relative
+                            .components()
+                            .take(depth.max(1))
+                            .map(|c| c.as_os_str().to_string_lossy())
+                            .collect::<Vec<_>>()
+                            .join("/")
+                    })
+                    .filter(|folder| !folder.is_empty())
+                    .unwrap_or_else(|| ".".to_string());
+                let tags = tags
+                    .into_iter()
+                    .filter(|tag| !options.is_excluded_tag(tag))
+                    .collect();
+                Some((folder, tags))
+            })
+            .collect();
+
+        let mut folders: BTreeMap<String, (usize, HashMap<String, usize>)> = BTreeMap::new();
+        for (folder, tags) in file_tags {
+            let (document_count, tag_counts) = folders.entry(folder).or_default();
+            *document_count += 1;
+            for tag in tags {
+                *tag_counts.entry(tag).or_default() += 1;
+            }
+        }
+
+        Ok(folders
+            .into_iter()
+            .map(|(folder, (document_count, tag_counts))| {
+                let mut tags: Vec<TagCount> = tag_counts
+                    .into_iter()
+                    .map(|(tag, document_count)| TagCount {
+                        tag,
+                        document_count,
+                    })
+                    .collect();
+                tags.sort_by(|a, b| {
+                    b.document_count
+                        .cmp(&a.document_count)
+                        .then_with(|| a.tag.cmp(&b.tag))
+                });
+                FolderTags {
+                    folder,
+                    document_count,
+                    unique_tags: tags.len(),
+                    tags,
+                }
+            })
+            .collect())
+    }
+
+    /// Extract all tags with document counts from markdown files in the given path
+    /// Returns tags sorted by document_count descending, then alphabetically
+    #[cfg(feature = "fs")]
+    pub fn extract_tags_with_counts(
+        &self,
+        path: &Path,
+        options: &TagListOptions,
+        warnings: &Warnings,
+    ) -> Result<Vec<TagCount>, Box<dyn std::error::Error>> {
+        let tag_documents = self.collect_tag_documents(path, options, warnings)?;
+
         // Convert to Vec<TagCount> sorted by document_count desc, then tag name asc
         let mut result: Vec<TagCount> = tag_documents
             .into_iter()
@@ -231,63 +778,242 @@ impl TagExtractor {
         Ok(result)
     }
 
+    /// Recommend existing vault tags for a note based on term overlap
+    ///
+    /// Each tag is scored by how often the input's terms appear in notes that
+    /// already carry the tag, weighted by inverse document frequency so rare
+    /// shared terms count more than common ones. Tags already present in the
+    /// input's frontmatter or body are not suggested. `exclude_file` removes the
+    /// note being tagged from the corpus.
+    #[cfg(feature = "fs")]
+    pub fn suggest_tags(
+        &self,
+        path: &Path,
+        text: &str,
+        exclude_file: Option<&Path>,
+        limit: usize,
+        warnings: &Warnings,
+    ) -> Result<Vec<TagSuggestion>, Box<dyn std::error::Error>> {
+        let files = if path.is_file() {
+            vec![path.to_path_buf()]
+        } else {
+            collect_markdown_files(path, &self.config, warnings)?
+        };
+        // Compare canonical paths, so the note matches however it was reached
+        let exclude_file =
+            exclude_file.map(|file| fs::canonicalize(file).unwrap_or(file.to_path_buf()));
+
+        let existing_tags: HashSet<String> = self
+            .extract_tags_from_content(text, true)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|t| fold(&t))
+            .collect();
+        let input_terms = note_terms(body_after_frontmatter(text));
+        if input_terms.is_empty() {
+            return Ok(vec![]);
+        }
+
+        // Collect (tags, terms) for every other note in the vault
+        let documents: Vec<(Vec<String>, HashSet<String>)> = files
+            .par_iter()
+            .filter(|file_path| {
+                exclude_file.as_deref().is_none_or(|excluded| {
+                    fs::canonicalize(file_path).ok().as_deref() != Some(excluded)
+                })
+            })
+            .filter_map(|file_path| {
+                let (content, tags) = self
+                    .read_tagged_note(file_path, false)
+                    .map_err(|warning| warnings.push(warning))
+                    .ok()?;
+                Some((tags, note_terms(body_after_frontmatter(&content))))
+            })
+            .collect();
+
+        // Document frequency of each input term across the corpus
+        let total_documents = documents.len() as f64;
+        let idf: HashMap<&str, f64> = input_terms
+            .iter()
+            .map(|term| {
+                let df = documents.iter().filter(|(_, t)| t.contains(term)).count() as f64;
+                (term.as_str(), (total_documents / (1.0 + df)).ln() + 1.0)
+            })
+            .collect();
+
+        // Per tag: number of documents, and per-term counts of documents containing it
+        let mut tag_stats: HashMap<&str, (usize, HashMap<&str, usize>)> = HashMap::new();
+        for (tags, terms) in &documents {
+            for tag in tags {
+                if existing_tags.contains(&fold(tag)) {
+                    continue;
+                }
+                let (count, term_counts) = tag_stats.entry(tag.as_str()).or_default();
+                *count += 1;
+                for term in input_terms.iter().filter(|t| terms.contains(*t)) {
+                    *term_counts.entry(term.as_str()).or_default() += 1;
+                }
+            }
+        }
+
+        let mut suggestions: Vec<TagSuggestion> = tag_stats
+            .into_iter()
+            .filter(|(_, (_, term_counts))| !term_counts.is_empty())
+            .map(|(tag, (document_count, term_counts))| {
+                let mut weighted: Vec<(&str, f64)> = term_counts
+                    .into_iter()
+                    .map(|(term, n)| (term, idf[term] * n as f64 / document_count as f64))
+                    .collect();
+                weighted.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+
+                let score = weighted.iter().map(|(_, w)| w).sum::<f64>() / input_terms.len() as f64;
+                TagSuggestion {
+                    tag: tag.to_string(),
+                    score: (score * 1000.0).round() / 1000.0,
+                    document_count,
+                    shared_terms: weighted
+                        .iter()
+                        .take(5)
+                        .map(|(t, _)| t.to_string())
+                        .collect(),
+                }
+            })
+            .collect();
+
+        suggestions.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| b.document_count.cmp(&a.document_count))
+                .then_with(|| a.tag.cmp(&b.tag))
+        });
+        suggestions.truncate(limit);
+
+        Ok(suggestions)
+    }
+
     /// Search for files by tags with AND/OR logic
     ///
+    /// Search tags containing glob characters (`*`, `?`, `[`) are matched as
+    /// patterns, so `project/*` matches `project/alpha` and `project/alpha/backend`.
+    ///
     /// # Arguments
     /// * `path` - Directory to search
-    /// * `tags` - Tags to search for
+    /// * `tags` - Tags or tag glob patterns to search for
     /// * `match_all` - If true, file must have ALL tags (AND logic). If false, file must have ANY tag (OR logic)
+    /// * `include_inline` - If true, inline `#tags` in note bodies are matched as well
+    #[cfg(feature = "fs")]
     pub fn search_by_tags(
         &self,
         path: &Path,
         tags: &[String],
         match_all: bool,
+        include_inline: bool,
+        warnings: &Warnings,
     ) -> Result<Vec<TaggedFile>, Box<dyn std::error::Error>> {
         let files = if path.is_file() {
             vec![path.to_path_buf()]
         } else {
-            collect_markdown_files(path, &self.config)?
+            collect_markdown_files(path, &self.config, warnings)?
         };
 
-        // Normalize search tags to lowercase for case-insensitive comparison
-        let search_tags: Vec<String> = tags.iter().map(|t| t.to_lowercase()).collect();
+        // Case-fold search tags for case- and normalization-insensitive comparison
+        let search_tags: Vec<String> = tags.iter().map(|t| fold(t)).collect();
+
+        // Compile glob patterns for search tags that contain wildcards
+        let search_patterns: Vec<Option<Pattern>> = search_tags
+            .iter()
+            .map(|t| {
+                if t.contains(['*', '?', '[']) {
+                    Pattern::new(t).ok()
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        let git_repo = self
+            .config
+            .git_dates
+            .then(|| crate::git_dates::GitRepo::discover(path))
+            .flatten();
 
         let results: Vec<TaggedFile> = files
             .par_iter()
             .filter_map(|file_path| {
                 // Extract tags from file
-                let all_tags = self.extract_tags_from_file(file_path).ok()?;
+                let (content, all_tags) = self
+                    .read_tagged_note(file_path, include_inline)
+                    .map_err(|warning| warnings.push(warning))
+                    .ok()?;
 
                 if all_tags.is_empty() {
                     return None;
                 }
 
                 // Normalize file tags for comparison
-                let normalized_tags: Vec<String> =
-                    all_tags.iter().map(|t| t.to_lowercase()).collect();
-
-                // Find which search tags match this file
-                let matched_tags: Vec<String> = search_tags
-                    .iter()
-                    .filter(|search_tag| normalized_tags.contains(search_tag))
-                    .cloned()
-                    .collect();
+                let normalized_tags: Vec<String> = all_tags.iter().map(|t| fold(t)).collect();
+
+                // Find which of the file's tags match each search tag, by index
+                // so they are reported as written in the file
+                let mut matched: Vec<usize> = Vec::new();
+                let mut matched_terms = 0;
+                for (search_tag, pattern) in search_tags.iter().zip(&search_patterns) {
+                    let term_matches: Vec<usize> = match pattern {
+                        Some(pattern) => (0..normalized_tags.len())
+                            .filter(|&i| pattern.matches(&normalized_tags[i]))
+                            .collect(),
+                        None => (0..normalized_tags.len())
+                            .filter(|&i| normalized_tags[i] == *search_tag)
+                            .take(1)
+                            .collect(),
+                    };
+                    if !term_matches.is_empty() {
+                        matched_terms += 1;
+                    }
+                    for i in term_matches {
+                        if !matched.contains(&i) {
+                            matched.push(i);
+                        }
+                    }
+                }
 
                 // Apply match logic
                 let matches = if match_all {
                     // AND logic: all search tags must be present
-                    matched_tags.len() == search_tags.len()
+                    matched_terms == search_tags.len()
                 } else {
                     // OR logic: at least one search tag must be present
-                    !matched_tags.is_empty()
+                    matched_terms > 0
                 };
 
                 if matches {
+                    let frontmatter = self.extract_frontmatter(&content).ok().flatten();
+                    let body = body_after_frontmatter(&content);
+                    let folded: Vec<String> = matched
+                        .iter()
+                        .map(|&i| normalized_tags[i].clone())
+                        .collect();
+                    let excerpt = self
+                        .tag_excerpt(&content, &folded)
+                        .or_else(|| note_excerpt(body));
+                    let modified = git_repo
+                        .as_ref()
+                        .and_then(|repo| repo.last_modified(file_path))
+                        .or_else(|| {
+                            fs::metadata(file_path)
+                                .and_then(|m| m.modified())
+                                .ok()
+                                .map(DateTime::<Utc>::from)
+                        });
+
                     Some(TaggedFile {
                         file_path: file_path.to_string_lossy().to_string(),
                         file_name: file_path.file_name()?.to_string_lossy().to_string(),
-                        matched_tags,
+                        matched_tags: matched.iter().map(|&i| all_tags[i].clone()).collect(),
                         all_tags,
+                        title: note_title(frontmatter.as_deref(), body, file_path),
+                        modified,
+                        excerpt,
                     })
                 } else {
                     None
@@ -302,12 +1028,15 @@ impl TagExtractor {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::frontmatter::frontmatter_line_count;
+    #[cfg(feature = "fs")]
     use std::io::Write;
 
     fn create_test_config() -> Arc<Config> {
         Arc::new(Config::default())
     }
 
+    #[cfg(feature = "fs")]
     fn create_test_file(dir: &std::path::Path, name: &str, content: &str) -> PathBuf {
         let path = dir.join(name);
         let mut file = std::fs::File::create(&path).unwrap();
@@ -366,40 +1095,143 @@ tags: single-tag
     }
 
     #[test]
-    fn test_extract_tags_from_content() {
+    fn test_parse_tags_comma_separated_string() {
         let extractor = TagExtractor::new(create_test_config());
 
-        let content = r#"---
-title: My Document
-tags:
-  - rust
-  - programming
----
-
-# My Document
-
-Some content here.
+        let frontmatter = r#"title: My Document
+tags: rust, cli ,programming
 "#;
 
-        let tags = extractor.extract_tags_from_content(content).unwrap();
-        assert_eq!(tags.len(), 2);
-        assert!(tags.contains(&"rust".to_string()));
-        assert!(tags.contains(&"programming".to_string()));
+        let tags = extractor.parse_tags_from_frontmatter(frontmatter).unwrap();
+        assert_eq!(tags, vec!["rust", "cli", "programming"]);
     }
 
     #[test]
-    fn test_no_frontmatter() {
+    fn test_parse_tags_merges_default_keys() {
         let extractor = TagExtractor::new(create_test_config());
 
-        let content = r#"# My Document
-
-Some content here without frontmatter.
+        let frontmatter = r#"tags:
+  - rust
+  - cli
+tag: cli
+"#;
+
+        let tags = extractor.parse_tags_from_frontmatter(frontmatter).unwrap();
+        assert_eq!(tags, vec!["rust", "cli"]);
+    }
+
+    #[test]
+    fn test_parse_tags_custom_keys() {
+        let config = Arc::new(Config {
+            frontmatter_tag_keys: vec!["tags".to_string(), "keywords".to_string()],
+            ..Default::default()
+        });
+        let extractor = TagExtractor::new(config);
+
+        let frontmatter = r#"tags: [rust]
+tag: ignored
+keywords:
+  - search
+"#;
+
+        let tags = extractor.parse_tags_from_frontmatter(frontmatter).unwrap();
+        assert_eq!(tags, vec!["rust", "search"]);
+    }
+
+    #[test]
+    fn test_extract_tags_from_content() {
+        let extractor = TagExtractor::new(create_test_config());
+
+        let content = r#"---
+title: My Document
+tags:
+  - rust
+  - programming
+---
+
+# My Document
+
+Some content here.
 "#;
 
-        let tags = extractor.extract_tags_from_content(content).unwrap();
+        let tags = extractor.extract_tags_from_content(content, false).unwrap();
+        assert_eq!(tags.len(), 2);
+        assert!(tags.contains(&"rust".to_string()));
+        assert!(tags.contains(&"programming".to_string()));
+    }
+
+    #[test]
+    fn test_no_frontmatter() {
+        let extractor = TagExtractor::new(create_test_config());
+
+        let content = r#"# My Document
+
+Some content here without frontmatter.
+"#;
+
+        let tags = extractor.extract_tags_from_content(content, false).unwrap();
         assert_eq!(tags.len(), 0);
     }
 
+    #[test]
+    fn test_empty_and_frontmatter_only_notes() {
+        let extractor = TagExtractor::new(create_test_config());
+
+        for content in ["---\ntags: [a]\n---", "---\ntags: [a]\n---\n"] {
+            let tags = extractor.extract_tags_from_content(content, true).unwrap();
+            assert_eq!(tags, vec!["a"]);
+            assert_eq!(frontmatter_block(content), Some("tags: [a]"));
+            assert_eq!(body_after_frontmatter(content), "");
+            assert_eq!(frontmatter_line_count(content), 3);
+        }
+
+        // Empty frontmatter, empty files, and a lone delimiter
+        for content in ["---\n---", "", "---\n"] {
+            let tags = extractor.extract_tags_from_content(content, true).unwrap();
+            assert!(tags.is_empty(), "{:?}", content);
+        }
+        assert_eq!(frontmatter_block("---\n---"), Some(""));
+        assert_eq!(frontmatter_line_count(""), 0);
+        assert_eq!(frontmatter_line_count("---\n"), 0);
+        assert_eq!(frontmatter_line_count("# Title\n"), 0);
+    }
+
+    #[test]
+    fn test_extract_inline_tags() {
+        let extractor = TagExtractor::new(create_test_config());
+
+        let content = r#"---
+tags: [meta]
+---
+# Heading
+
+Working on #project/alpha and #rust today. Issue #123 is not a tag.
+Links like page#anchor are ignored, as is `#inline-code`.
+
+```bash
+# comment #not-a-tag
+```
+
+- [ ] Follow up #rust #todo
+"#;
+
+        let tags = extractor.extract_inline_tags(content);
+        assert_eq!(tags, vec!["project/alpha", "rust", "todo"]);
+    }
+
+    #[test]
+    fn test_extract_tags_from_content_with_inline() {
+        let extractor = TagExtractor::new(create_test_config());
+
+        let content = "---\ntags:\n  - rust\n---\nNotes about #rust and #cli\n";
+
+        let tags = extractor.extract_tags_from_content(content, false).unwrap();
+        assert_eq!(tags, vec!["rust"]);
+
+        let tags = extractor.extract_tags_from_content(content, true).unwrap();
+        assert_eq!(tags, vec!["rust", "cli"]);
+    }
+
     #[test]
     fn test_empty_tags_filtered() {
         let extractor = TagExtractor::new(create_test_config());
@@ -435,6 +1267,7 @@ tags: ""
     }
 
     #[test]
+    #[cfg(feature = "fs")]
     fn test_extract_tags_with_counts_single_file() {
         use tempfile::TempDir;
         let temp_dir = TempDir::new().unwrap();
@@ -450,7 +1283,13 @@ tags:
 "#;
         create_test_file(temp_dir.path(), "test1.md", content);
 
-        let counts = extractor.extract_tags_with_counts(temp_dir.path()).unwrap();
+        let counts = extractor
+            .extract_tags_with_counts(
+                temp_dir.path(),
+                &TagListOptions::default(),
+                &Warnings::default(),
+            )
+            .unwrap();
 
         assert_eq!(counts.len(), 2);
         assert!(
@@ -466,6 +1305,7 @@ tags:
     }
 
     #[test]
+    #[cfg(feature = "fs")]
     fn test_extract_tags_with_counts_multiple_files() {
         use tempfile::TempDir;
         let temp_dir = TempDir::new().unwrap();
@@ -490,7 +1330,13 @@ tags:
 "#;
         create_test_file(temp_dir.path(), "file2.md", content2);
 
-        let counts = extractor.extract_tags_with_counts(temp_dir.path()).unwrap();
+        let counts = extractor
+            .extract_tags_with_counts(
+                temp_dir.path(),
+                &TagListOptions::default(),
+                &Warnings::default(),
+            )
+            .unwrap();
 
         // rust appears in 2 documents, programming and cli in 1 each
         let rust = counts.iter().find(|t| t.tag == "rust").unwrap();
@@ -507,6 +1353,7 @@ tags:
     }
 
     #[test]
+    #[cfg(feature = "fs")]
     fn test_extract_tags_with_counts_duplicate_in_same_file() {
         use tempfile::TempDir;
         let temp_dir = TempDir::new().unwrap();
@@ -523,13 +1370,247 @@ tags:
 "#;
         create_test_file(temp_dir.path(), "file.md", content);
 
-        let counts = extractor.extract_tags_with_counts(temp_dir.path()).unwrap();
+        let counts = extractor
+            .extract_tags_with_counts(
+                temp_dir.path(),
+                &TagListOptions::default(),
+                &Warnings::default(),
+            )
+            .unwrap();
 
         let rust = counts.iter().find(|t| t.tag == "rust").unwrap();
         assert_eq!(rust.document_count, 1); // Should be 1, not 2
     }
 
     #[test]
+    #[cfg(feature = "fs")]
+    fn test_extract_tag_tree_aggregates_counts() {
+        use tempfile::TempDir;
+        let temp_dir = TempDir::new().unwrap();
+        let extractor = TagExtractor::new(create_test_config());
+
+        create_test_file(
+            temp_dir.path(),
+            "a.md",
+            "---\ntags:\n  - project/alpha/backend\n  - project/beta\n---\n",
+        );
+        create_test_file(
+            temp_dir.path(),
+            "b.md",
+            "---\ntags:\n  - project/alpha\n  - rust\n---\n",
+        );
+
+        let tree = extractor
+            .extract_tag_tree(
+                temp_dir.path(),
+                &TagListOptions::default(),
+                &Warnings::default(),
+            )
+            .unwrap();
+
+        assert_eq!(tree.len(), 2);
+        let project = &tree[0];
+        assert_eq!(project.tag, "project");
+        // a.md has two project tags but counts once
+        assert_eq!(project.document_count, 2);
+        assert_eq!(project.direct_count, 0);
+
+        let alpha = &project.children[0];
+        assert_eq!(alpha.tag, "project/alpha");
+        assert_eq!(alpha.document_count, 2);
+        assert_eq!(alpha.direct_count, 1);
+        assert_eq!(alpha.children[0].name, "backend");
+        assert_eq!(alpha.children[0].tag, "project/alpha/backend");
+        assert_eq!(alpha.children[0].document_count, 1);
+
+        assert_eq!(project.children[1].tag, "project/beta");
+        assert_eq!(tree[1].tag, "rust");
+        assert!(tree[1].children.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "fs")]
+    fn test_extract_tags_with_counts_exclusions() {
+        use tempfile::TempDir;
+        let temp_dir = TempDir::new().unwrap();
+        let extractor = TagExtractor::new(create_test_config());
+
+        std::fs::create_dir(temp_dir.path().join("Templates")).unwrap();
+        create_test_file(
+            &temp_dir.path().join("Templates"),
+            "daily.md",
+            "---\ntags: [template, rust]\n---\n",
+        );
+        create_test_file(
+            temp_dir.path(),
+            "note.md",
+            "---\ntags: [rust, archive/2023, Archive]\n---\n",
+        );
+
+        let options = TagListOptions {
+            exclude_subpaths: vec![temp_dir.path().join("Templates")],
+            exclude_tags: vec!["archive".to_string()],
+            ..Default::default()
+        };
+        let counts = extractor
+            .extract_tags_with_counts(temp_dir.path(), &options, &Warnings::default())
+            .unwrap();
+
+        assert_eq!(counts.len(), 1);
+        assert_eq!(counts[0].tag, "rust");
+        assert_eq!(counts[0].document_count, 1);
+    }
+
+    #[test]
+    #[cfg(feature = "fs")]
+    fn test_extract_tags_with_warnings() {
+        use tempfile::TempDir;
+        let temp_dir = TempDir::new().unwrap();
+        let extractor = TagExtractor::new(Arc::new(Config {
+            max_file_size: Some(100),
+            ..Default::default()
+        }));
+
+        create_test_file(temp_dir.path(), "good.md", "---\ntags: [rust]\n---\n");
+        let broken = create_test_file(temp_dir.path(), "broken.md", "---\ntags: [rust\n---\n");
+        let big = create_test_file(
+            temp_dir.path(),
+            "big.md",
+            &format!("---\ntags: [big]\n---\n{}", "x".repeat(200)),
+        );
+
+        let (tags, warnings) = extractor
+            .extract_tags_with_warnings(temp_dir.path(), false)
+            .unwrap();
+        assert_eq!(tags, vec!["rust"]);
+        let skipped: Vec<(String, WarningKind)> = warnings
+            .into_iter()
+            .map(|w| (w.file_path, w.kind))
+            .collect();
+        assert_eq!(
+            skipped,
+            vec![
+                (big.to_string_lossy().to_string(), WarningKind::Oversized),
+                (
+                    broken.to_string_lossy().to_string(),
+                    WarningKind::InvalidYaml
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "fs")]
+    fn test_collect_markdown_files_scan_limits() {
+        use crate::config::ScanLimitAction;
+        use tempfile::TempDir;
+        let temp_dir = TempDir::new().unwrap();
+        create_test_file(temp_dir.path(), "a.md", "a");
+        create_test_file(temp_dir.path(), "b.md", "b");
+        create_test_file(temp_dir.path(), "c.md", "c");
+
+        let config = Config {
+            max_files: Some(2),
+            scan_limit_action: ScanLimitAction::Truncate,
+            ..Default::default()
+        };
+        let warnings = Warnings::default();
+        let files = collect_markdown_files(temp_dir.path(), &config, &warnings).unwrap();
+        assert_eq!(files.len(), 2);
+        let warnings = warnings.into_vec();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].kind, WarningKind::ScanLimit);
+
+        let config = Config {
+            max_total_bytes: Some(2),
+            ..Default::default()
+        };
+        assert!(collect_markdown_files(temp_dir.path(), &config, &Warnings::default()).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "fs")]
+    fn test_extract_tags_skips_generated_notes() {
+        use tempfile::TempDir;
+        let temp_dir = TempDir::new().unwrap();
+        let extractor = TagExtractor::new(create_test_config());
+
+        create_test_file(temp_dir.path(), "note.md", "---\ntags: [rust]\n---\n");
+        create_test_file(
+            temp_dir.path(),
+            "Drawing.excalidraw.md",
+            "---\ntags: [excalidraw]\nexcalidraw-plugin: parsed\n---\n",
+        );
+
+        let counts = extractor
+            .extract_tags_with_counts(
+                temp_dir.path(),
+                &TagListOptions::default(),
+                &Warnings::default(),
+            )
+            .unwrap();
+
+        assert_eq!(counts.len(), 1);
+        assert_eq!(counts[0].tag, "rust");
+    }
+
+    #[test]
+    #[cfg(feature = "fs")]
+    fn test_extract_tags_by_folder() {
+        use tempfile::TempDir;
+        let temp_dir = TempDir::new().unwrap();
+        let extractor = TagExtractor::new(create_test_config());
+
+        let alpha = temp_dir.path().join("Projects").join("Alpha");
+        std::fs::create_dir_all(&alpha).unwrap();
+        std::fs::create_dir(temp_dir.path().join("Journal")).unwrap();
+        create_test_file(&alpha, "a.md", "---\ntags: [rust, work]\n---\n");
+        create_test_file(
+            &temp_dir.path().join("Projects"),
+            "b.md",
+            "---\ntags: [rust]\n---\n",
+        );
+        create_test_file(
+            &temp_dir.path().join("Journal"),
+            "c.md",
+            "---\ntags: [daily]\n---\n",
+        );
+        create_test_file(temp_dir.path(), "root.md", "no tags here\n");
+
+        let folders = extractor
+            .extract_tags_by_folder(
+                temp_dir.path(),
+                1,
+                &TagListOptions::default(),
+                &Warnings::default(),
+            )
+            .unwrap();
+
+        let names: Vec<&str> = folders.iter().map(|f| f.folder.as_str()).collect();
+        assert_eq!(names, vec![".", "Journal", "Projects"]);
+        assert_eq!(folders[0].document_count, 1);
+        assert!(folders[0].tags.is_empty());
+
+        let projects = &folders[2];
+        assert_eq!(projects.document_count, 2);
+        assert_eq!(projects.tags[0].tag, "rust");
+        assert_eq!(projects.tags[0].document_count, 2);
+        assert_eq!(projects.tags[1].tag, "work");
+
+        let folders = extractor
+            .extract_tags_by_folder(
+                temp_dir.path(),
+                2,
+                &TagListOptions::default(),
+                &Warnings::default(),
+            )
+            .unwrap();
+        let names: Vec<&str> = folders.iter().map(|f| f.folder.as_str()).collect();
+        assert_eq!(names, vec![".", "Journal", "Projects", "Projects/Alpha"]);
+    }
+
+    #[test]
+    #[cfg(feature = "fs")]
     fn test_search_by_tags_or_logic() {
         use tempfile::TempDir;
         let temp_dir = TempDir::new().unwrap();
@@ -559,6 +1640,8 @@ tags:
                 temp_dir.path(),
                 &["rust".to_string(), "python".to_string()],
                 false,
+                false,
+                &Warnings::default(),
             )
             .unwrap();
 
@@ -568,6 +1651,7 @@ tags:
     }
 
     #[test]
+    #[cfg(feature = "fs")]
     fn test_search_by_tags_and_logic() {
         use tempfile::TempDir;
         let temp_dir = TempDir::new().unwrap();
@@ -592,6 +1676,8 @@ tags:
                 temp_dir.path(),
                 &["rust".to_string(), "cli".to_string()],
                 true,
+                false,
+                &Warnings::default(),
             )
             .unwrap();
 
@@ -600,6 +1686,7 @@ tags:
     }
 
     #[test]
+    #[cfg(feature = "fs")]
     fn test_search_by_tags_case_insensitive() {
         use tempfile::TempDir;
         let temp_dir = TempDir::new().unwrap();
@@ -615,18 +1702,336 @@ tags:
 
         // Search with lowercase
         let results = extractor
-            .search_by_tags(temp_dir.path(), &["rust".to_string()], false)
+            .search_by_tags(
+                temp_dir.path(),
+                &["rust".to_string()],
+                false,
+                false,
+                &Warnings::default(),
+            )
             .unwrap();
         assert_eq!(results.len(), 1);
 
         // Search with uppercase
         let results = extractor
-            .search_by_tags(temp_dir.path(), &["RUST".to_string()], false)
+            .search_by_tags(
+                temp_dir.path(),
+                &["RUST".to_string()],
+                false,
+                false,
+                &Warnings::default(),
+            )
+            .unwrap();
+        assert_eq!(results.len(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "fs")]
+    fn test_tags_ignore_normalization_form() {
+        use tempfile::TempDir;
+        let temp_dir = TempDir::new().unwrap();
+        let extractor = TagExtractor::new(create_test_config());
+
+        // Decomposed tags, as written by some macOS apps, come back composed
+        let tags = extractor
+            .extract_tags_from_content(
+                "---\ntags: [cafe\u{301}]\n---\n#re\u{301}sume\u{301}\n",
+                true,
+            )
+            .unwrap();
+        assert_eq!(tags, vec!["caf\u{e9}", "r\u{e9}sum\u{e9}"]);
+
+        create_test_file(
+            temp_dir.path(),
+            "file1.md",
+            "---\ntags:\n  - Cafe\u{301}\n---\n# File 1",
+        );
+        let results = extractor
+            .search_by_tags(
+                temp_dir.path(),
+                &["CAF\u{c9}".to_string()],
+                false,
+                false,
+                &Warnings::default(),
+            )
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].matched_tags, vec!["Caf\u{e9}"]);
+    }
+
+    #[test]
+    #[cfg(feature = "fs")]
+    fn test_search_by_tags_glob_pattern() {
+        use tempfile::TempDir;
+        let temp_dir = TempDir::new().unwrap();
+        let extractor = TagExtractor::new(create_test_config());
+
+        create_test_file(
+            temp_dir.path(),
+            "alpha.md",
+            "---\ntags:\n  - project/alpha/backend\n  - rust\n---\n",
+        );
+        create_test_file(
+            temp_dir.path(),
+            "beta.md",
+            "---\ntags:\n  - Project/Beta\n---\n",
+        );
+        create_test_file(
+            temp_dir.path(),
+            "other.md",
+            "---\ntags:\n  - projects\n---\n",
+        );
+
+        let mut results = extractor
+            .search_by_tags(
+                temp_dir.path(),
+                &["project/*".to_string()],
+                false,
+                false,
+                &Warnings::default(),
+            )
+            .unwrap();
+        results.sort_by(|a, b| a.file_name.cmp(&b.file_name));
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].file_name, "alpha.md");
+        assert_eq!(results[0].matched_tags, vec!["project/alpha/backend"]);
+        assert_eq!(results[1].file_name, "beta.md");
+        assert_eq!(results[1].matched_tags, vec!["Project/Beta"]);
+
+        // AND logic mixes patterns and exact tags
+        let results = extractor
+            .search_by_tags(
+                temp_dir.path(),
+                &["project/*".to_string(), "rust".to_string()],
+                true,
+                false,
+                &Warnings::default(),
+            )
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].file_name, "alpha.md");
+    }
+
+    #[test]
+    #[cfg(feature = "fs")]
+    fn test_search_by_tags_includes_note_metadata() {
+        use tempfile::TempDir;
+        let temp_dir = TempDir::new().unwrap();
+        let extractor = TagExtractor::new(create_test_config());
+
+        create_test_file(
+            temp_dir.path(),
+            "titled.md",
+            "---\ntitle: Project Plan\ntags: [rust]\n---\n# Heading\n\nFirst paragraph\ncontinues here.\n\nSecond paragraph.\n",
+        );
+        create_test_file(
+            temp_dir.path(),
+            "heading.md",
+            "---\ntags: [cli]\n---\n# From Heading\n",
+        );
+        create_test_file(temp_dir.path(), "untitled.md", "---\ntags: [web]\n---\n");
+
+        let search = |tag: &str| {
+            extractor
+                .search_by_tags(
+                    temp_dir.path(),
+                    &[tag.to_string()],
+                    false,
+                    false,
+                    &Warnings::default(),
+                )
+                .unwrap()
+                .remove(0)
+        };
+
+        let titled = search("rust");
+        assert_eq!(titled.title, "Project Plan");
+        assert_eq!(
+            titled.excerpt.as_deref(),
+            Some("First paragraph continues here.")
+        );
+        assert!(titled.modified.is_some());
+
+        let heading = search("cli");
+        assert_eq!(heading.title, "From Heading");
+        assert!(heading.excerpt.is_none());
+
+        assert_eq!(search("web").title, "untitled");
+    }
+
+    #[test]
+    #[cfg(feature = "fs")]
+    fn test_search_excerpt_around_first_tag() {
+        use tempfile::TempDir;
+        let temp_dir = TempDir::new().unwrap();
+        let extractor = TagExtractor::new(create_test_config());
+        let long = "filler ".repeat(40);
+        create_test_file(
+            temp_dir.path(),
+            "note.md",
+            &format!(
+                "---\ntags: [rust]\n---\nIntro paragraph.\n\n```\n#Rust in code\n```\nSkip `#rust` here.\n{}then #Rust/ appears {}\n",
+                long, long
+            ),
+        );
+
+        let file = extractor
+            .search_by_tags(
+                temp_dir.path(),
+                &["rust".to_string()],
+                false,
+                false,
+                &Warnings::default(),
+            )
+            .unwrap()
+            .remove(0);
+        let excerpt = file.excerpt.unwrap();
+        assert!(excerpt.starts_with('…') && excerpt.ends_with('…'));
+        assert!(excerpt.contains("then #Rust/ appears"));
+        assert!(excerpt.chars().count() <= EXCERPT_MAX_CHARS + 2);
+
+        assert_eq!(excerpt_around("short #tag", 6), "short #tag");
+    }
+
+    #[test]
+    #[cfg(feature = "fs")]
+    fn test_note_excerpt_truncates_long_text() {
+        let body = "word ".repeat(100);
+        let excerpt = note_excerpt(&body).unwrap();
+        assert!(excerpt.ends_with('…'));
+        assert!(excerpt.chars().count() <= EXCERPT_MAX_CHARS + 1);
+    }
+
+    #[test]
+    #[cfg(feature = "fs")]
+    fn test_suggest_tags_by_term_overlap() {
+        use tempfile::TempDir;
+        let temp_dir = TempDir::new().unwrap();
+        let extractor = TagExtractor::new(create_test_config());
+
+        create_test_file(
+            temp_dir.path(),
+            "borrow.md",
+            "---\ntags: [rust]\n---\nOwnership and the borrow checker in cargo crates.\n",
+        );
+        create_test_file(
+            temp_dir.path(),
+            "traits.md",
+            "---\ntags: [rust, programming]\n---\nTraits, generics and cargo workspaces.\n",
+        );
+        create_test_file(
+            temp_dir.path(),
+            "bread.md",
+            "---\ntags: [cooking]\n---\nSourdough bread needs flour and patience.\n",
+        );
+
+        let suggestions = extractor
+            .suggest_tags(
+                temp_dir.path(),
+                "Fighting the borrow checker while splitting cargo crates",
+                None,
+                10,
+                &Warnings::default(),
+            )
+            .unwrap();
+
+        assert_eq!(suggestions[0].tag, "rust");
+        assert_eq!(suggestions[0].document_count, 2);
+        assert!(suggestions[0].shared_terms.contains(&"borrow".to_string()));
+        assert!(!suggestions.iter().any(|s| s.tag == "cooking"));
+
+        // Tags already on the input are not suggested
+        let suggestions = extractor
+            .suggest_tags(
+                temp_dir.path(),
+                "---\ntags: [rust]\n---\nborrow checker and cargo",
+                None,
+                10,
+                &Warnings::default(),
+            )
+            .unwrap();
+        assert!(!suggestions.iter().any(|s| s.tag == "rust"));
+    }
+
+    /// Paths and kinds of the files skipped in `warnings`
+    #[cfg(feature = "fs")]
+    fn skipped(warnings: Warnings) -> Vec<(String, WarningKind)> {
+        warnings
+            .into_vec()
+            .into_iter()
+            .map(|w| (w.file_path, w.kind))
+            .collect()
+    }
+
+    /// A vault with one tagged note, one with broken frontmatter, and one
+    /// that isn't UTF-8
+    #[cfg(feature = "fs")]
+    fn create_vault_with_broken_notes() -> (tempfile::TempDir, Vec<(String, WarningKind)>) {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        create_test_file(
+            temp_dir.path(),
+            "good.md",
+            "---\ntags: [rust]\n---\nThe borrow checker.\n",
+        );
+        let broken = create_test_file(
+            temp_dir.path(),
+            "broken.md",
+            "---\ntags: [rust\n---\nThe borrow checker.\n",
+        );
+        let binary = temp_dir.path().join("binary.md");
+        fs::write(&binary, b"---\ntags: [rust]\n---\n\xff\xfe borrow\n").unwrap();
+        let expected = vec![
+            (
+                binary.to_string_lossy().to_string(),
+                WarningKind::Unreadable,
+            ),
+            (
+                broken.to_string_lossy().to_string(),
+                WarningKind::InvalidYaml,
+            ),
+        ];
+        (temp_dir, expected)
+    }
+
+    #[test]
+    #[cfg(feature = "fs")]
+    fn test_search_by_tags_reports_skipped_files() {
+        let (temp_dir, expected) = create_vault_with_broken_notes();
+        let extractor = TagExtractor::new(create_test_config());
+
+        let warnings = Warnings::default();
+        let results = extractor
+            .search_by_tags(
+                temp_dir.path(),
+                &["rust".to_string()],
+                false,
+                false,
+                &warnings,
+            )
             .unwrap();
         assert_eq!(results.len(), 1);
+        assert_eq!(results[0].file_name, "good.md");
+        assert_eq!(skipped(warnings), expected);
     }
 
     #[test]
+    #[cfg(feature = "fs")]
+    fn test_suggest_tags_reports_skipped_files() {
+        let (temp_dir, expected) = create_vault_with_broken_notes();
+        let extractor = TagExtractor::new(create_test_config());
+
+        let warnings = Warnings::default();
+        let suggestions = extractor
+            .suggest_tags(temp_dir.path(), "borrow checker", None, 10, &warnings)
+            .unwrap();
+        assert_eq!(suggestions[0].tag, "rust");
+        assert_eq!(suggestions[0].document_count, 1);
+        assert_eq!(skipped(warnings), expected);
+    }
+
+    #[test]
+    #[cfg(feature = "fs")]
     fn test_search_by_tags_empty_result() {
         use tempfile::TempDir;
         let temp_dir = TempDir::new().unwrap();
@@ -642,18 +2047,25 @@ tags:
 
         // Search for non-existent tag
         let results = extractor
-            .search_by_tags(temp_dir.path(), &["nonexistent".to_string()], false)
+            .search_by_tags(
+                temp_dir.path(),
+                &["nonexistent".to_string()],
+                false,
+                false,
+                &Warnings::default(),
+            )
             .unwrap();
         assert!(results.is_empty());
     }
 
     #[test]
+    #[cfg(feature = "fs")]
     fn test_search_by_tags_respects_exclusions() {
         use tempfile::TempDir;
         let temp_dir = TempDir::new().unwrap();
         let config = Arc::new(Config {
             exclude_paths: vec!["excluded".to_string()],
-            daily_note_patterns: crate::config::default_daily_note_patterns(),
+            ..Default::default()
         });
         let extractor = TagExtractor::new(config);
 
@@ -675,13 +2087,20 @@ tags:
 
         // Search should not include excluded file
         let results = extractor
-            .search_by_tags(temp_dir.path(), &["rust".to_string()], false)
+            .search_by_tags(
+                temp_dir.path(),
+                &["rust".to_string()],
+                false,
+                false,
+                &Warnings::default(),
+            )
             .unwrap();
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].file_name, "file1.md");
     }
 
     #[test]
+    #[cfg(feature = "fs")]
     fn test_tagged_file_contains_all_tags() {
         use tempfile::TempDir;
         let temp_dir = TempDir::new().unwrap();
@@ -697,7 +2116,13 @@ tags:
 
         // Search for one tag
         let results = extractor
-            .search_by_tags(temp_dir.path(), &["rust".to_string()], false)
+            .search_by_tags(
+                temp_dir.path(),
+                &["rust".to_string()],
+                false,
+                false,
+                &Warnings::default(),
+            )
             .unwrap();
 
         assert_eq!(results.len(), 1);
@@ -0,0 +1,165 @@
+//! CSV rendering of tasks
+//!
+//! Used by `tasks --format csv` and by `/api/tasks` for `Accept: text/csv`.
+//! Columns are named after `Task` fields, so any field can be exported.
+
+use crate::extractor::Task;
+use serde_json::Value;
+
+/// Columns written when none are given
+pub const DEFAULT_COLUMNS: &[&str] = &[
+    "file_path",
+    "line_number",
+    "status",
+    "content",
+    "due_date",
+    "priority",
+    "tags",
+];
+
+/// Render `tasks` as CSV with a header row, one column per `columns` entry
+///
+/// Lists (tags, dependencies) are joined with `, `, nested values are written
+/// as JSON, and missing values are empty. Rows end with CRLF as RFC 4180
+/// asks. An unknown column is an error listing the valid ones.
+///
+/// Cells starting with `=`, `+`, `-`, `@`, a tab, or a carriage return get a
+/// leading `'` so a spreadsheet shows a task like `=HYPERLINK(...)` as text
+/// instead of running it as a formula.
+pub fn render_csv(tasks: &[Task], columns: &[String]) -> Result<String, String> {
+    let fields = task_fields();
+    if let Some(unknown) = columns.iter().find(|column| !fields.contains(column)) {
+        return Err(format!(
+            "Unknown column '{}': expected one of {}",
+            unknown,
+            fields.join(", ")
+        ));
+    }
+
+    let mut csv = String::new();
+    push_row(&mut csv, columns.iter().map(String::as_str));
+    for task in tasks {
+        let value = serde_json::to_value(task).map_err(|e| e.to_string())?;
+        let cells: Vec<String> = columns
+            .iter()
+            .map(|column| value.get(column).map(cell).map(defuse).unwrap_or_default())
+            .collect();
+        push_row(&mut csv, cells.iter().map(String::as_str));
+    }
+    Ok(csv)
+}
+
+/// Columns from a request: a comma-separated string or a list, or the
+/// defaults when there are none
+pub fn columns_from(value: Option<&Value>) -> Vec<String> {
+    let columns: Vec<String> = match value {
+        Some(Value::String(list)) => list.split(',').map(str::to_string).collect(),
+        Some(Value::Array(items)) => items.iter().map(cell).collect(),
+        _ => Vec::new(),
+    };
+    let columns: Vec<String> = columns
+        .into_iter()
+        .map(|column| column.trim().to_string())
+        .filter(|column| !column.is_empty())
+        .collect();
+    if columns.is_empty() {
+        DEFAULT_COLUMNS.iter().map(|c| c.to_string()).collect()
+    } else {
+        columns
+    }
+}
+
+/// Names of the `Task` fields, from its JSON schema
+fn task_fields() -> Vec<String> {
+    let schema = serde_json::to_value(schemars::schema_for!(Task)).unwrap_or_default();
+    schema["properties"]
+        .as_object()
+        .map(|properties| properties.keys().cloned().collect())
+        .unwrap_or_default()
+}
+
+fn cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        Value::Array(items) => items.iter().map(cell).collect::<Vec<_>>().join(", "),
+        other => other.to_string(),
+    }
+}
+
+/// Keep spreadsheets from reading a cell as a formula
+fn defuse(field: String) -> String {
+    if field.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        format!("'{}", field)
+    } else {
+        field
+    }
+}
+
+fn push_row<'a>(csv: &mut String, cells: impl Iterator<Item = &'a str>) {
+    let row: Vec<String> = cells.map(escape).collect();
+    csv.push_str(&row.join(","));
+    csv.push_str("\r\n");
+}
+
+/// Quote a field holding a separator, quote, or line break
+fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::extractor::TaskExtractor;
+    use std::path::PathBuf;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_render_csv() {
+        let tasks = TaskExtractor::new(Arc::new(Config::default())).extract_tasks_from_content(
+            "- [ ] Call \"Bob\", then Ann #work #calls 📅 2025-01-10 ⏫\n- [x] Done\n",
+            &PathBuf::from("Work/plan.md"),
+        );
+
+        let csv = render_csv(&tasks, &columns_from(None)).unwrap();
+        assert_eq!(
+            csv,
+            "file_path,line_number,status,content,due_date,priority,tags\r\n\
+             Work/plan.md,1,incomplete,\"Call \"\"Bob\"\", then Ann #work #calls\",2025-01-10,urgent,\"work, calls\"\r\n\
+             Work/plan.md,2,completed,Done,,,\r\n"
+        );
+
+        let columns = columns_from(Some(&Value::String("content, status".to_string())));
+        assert_eq!(
+            render_csv(&tasks[1..], &columns).unwrap(),
+            "content,status\r\nDone,completed\r\n"
+        );
+
+        let error = render_csv(&tasks, &["due".to_string()]).unwrap_err();
+        assert!(error.starts_with("Unknown column 'due': expected one of"));
+    }
+
+    #[test]
+    fn test_render_csv_defuses_formulas() {
+        let tasks = TaskExtractor::new(Arc::new(Config::default())).extract_tasks_from_content(
+            "- [ ] =HYPERLINK(\"http://evil\",\"x\")\n- [ ] +1\n- [ ] -2\n- [ ] @SUM(A1)\n- [ ] a = b\n",
+            &PathBuf::from("plan.md"),
+        );
+
+        let csv = render_csv(&tasks, &["content".to_string()]).unwrap();
+        assert_eq!(
+            csv,
+            "content\r\n\
+             \"'=HYPERLINK(\"\"http://evil\"\",\"\"x\"\")\"\r\n\
+             '+1\r\n\
+             '-2\r\n\
+             '@SUM(A1)\r\n\
+             a = b\r\n"
+        );
+    }
+}
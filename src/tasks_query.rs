@@ -0,0 +1,278 @@
+//! Obsidian Tasks query language
+//!
+//! Translates a subset of the Tasks plugin's query blocks into
+//! [`FilterOptions`]. A query has one instruction per line, such as
+//! `not done`, `due before today`, `tags include #work`, or
+//! `path includes Projects`; every line must hold for a task to match.
+
+use crate::extractor::{Priority, TaskStatus};
+use crate::filter::{DateValue, FilterOptions};
+use crate::locale::Locale;
+use crate::nl_query::parse_natural_date;
+use chrono::NaiveDate;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// A Tasks query translated into filters
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct TasksQuery {
+    /// Filters the query was translated into
+    pub filter: FilterOptions,
+    /// Number of tasks from a `limit` line
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+}
+
+/// Which date a date instruction constrains
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DateField {
+    Due,
+    Scheduled,
+    Starts,
+    Done,
+}
+
+/// Parse a Tasks query block
+///
+/// Understands `done`/`not done`, `due`/`scheduled`/`starts`/`done` with
+/// `before`, `after`, or `on` and a date (`today`, `next friday`,
+/// `2025-01-10`), `no due date`, `tags include`/`tags do not include`,
+/// `path includes`, `description includes`, `heading includes`,
+/// `priority is` and `priority is above`, `is recurring`, `is blocked`/`is
+/// not blocked`, and `limit`. Instructions are case-insensitive. Blank lines,
+/// `#` comments, and layout instructions (`hide ...`, `show ...`, `short
+/// mode`, `explain`) are ignored. Anything else is an error naming the line,
+/// since silently dropping it would widen the results.
+pub fn parse_tasks_query(
+    query: &str,
+    today: NaiveDate,
+    locale: Locale,
+) -> Result<TasksQuery, String> {
+    let mut parsed = TasksQuery::default();
+    for (index, line) in query.lines().enumerate() {
+        let line = line.trim();
+        parse_instruction(&mut parsed, line, today, locale)
+            .map_err(|e| format!("Line {} ('{}'): {}", index + 1, line, e))?;
+    }
+    Ok(parsed)
+}
+
+fn parse_instruction(
+    parsed: &mut TasksQuery,
+    line: &str,
+    today: NaiveDate,
+    locale: Locale,
+) -> Result<(), String> {
+    let filter = &mut parsed.filter;
+    let lower = line.to_ascii_lowercase();
+    // The rest of the line after a case-insensitive keyword prefix, keeping
+    // the value's case
+    let after = |prefix: &str| {
+        lower
+            .starts_with(prefix)
+            .then(|| line[prefix.len()..].trim())
+    };
+
+    if line.is_empty() || line.starts_with('#') || is_layout(&lower) {
+        return Ok(());
+    }
+    match lower.as_str() {
+        "done" => filter.status = Some(TaskStatus::Completed),
+        "not done" => filter.status = Some(TaskStatus::Incomplete),
+        "no due date" => filter.no_due_date = true,
+        "is recurring" => filter.recurring = true,
+        "is blocked" => filter.blocked = Some(true),
+        "is not blocked" => filter.blocked = Some(false),
+        _ => {
+            if let Some(tag) = after("tags include ").or_else(|| after("tag includes ")) {
+                push_tag(&mut filter.tags, tag);
+            } else if let Some(tag) =
+                after("tags do not include ").or_else(|| after("tag does not include "))
+            {
+                push_tag(&mut filter.exclude_tags, tag);
+            } else if let Some(path) = after("path includes ") {
+                filter.path_includes = Some(path.to_string());
+            } else if let Some(text) = after("description includes ") {
+                filter.text = Some(text.to_string());
+            } else if let Some(heading) = after("heading includes ") {
+                filter.heading = Some(heading.to_string());
+            } else if let Some(priority) = after("priority is above ") {
+                filter.min_priority = Some(above(parse_priority(priority)?)?);
+            } else if let Some(priority) = after("priority is ") {
+                filter.priority = Some(parse_priority(priority)?);
+            } else if let Some(limit) = after("limit to ").or_else(|| after("limit ")) {
+                let count = limit.trim_end_matches("tasks").trim();
+                parsed.limit = Some(
+                    count
+                        .parse()
+                        .map_err(|_| format!("'{}' is not a number of tasks", count))?,
+                );
+            } else if let Some((field, rest)) = date_instruction(&lower) {
+                apply_date(filter, field, rest, today, locale)?;
+            } else {
+                return Err("unsupported instruction".to_string());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Instructions that only change how the Tasks plugin renders results
+fn is_layout(lower: &str) -> bool {
+    lower.starts_with("hide ")
+        || lower.starts_with("show ")
+        || matches!(
+            lower,
+            "short" | "short mode" | "full" | "full mode" | "explain"
+        )
+}
+
+fn push_tag(tags: &mut Option<Vec<String>>, tag: &str) {
+    let tag = tag.trim_start_matches('#').to_string();
+    let tags = tags.get_or_insert_with(Vec::new);
+    if !tags.contains(&tag) {
+        tags.push(tag);
+    }
+}
+
+fn parse_priority(text: &str) -> Result<Priority, String> {
+    text.parse()
+        .map_err(|_| format!("unknown priority '{}'", text))
+}
+
+/// The next priority up, for `priority is above`
+fn above(priority: Priority) -> Result<Priority, String> {
+    match priority {
+        Priority::Lowest => Ok(Priority::Low),
+        Priority::Low => Ok(Priority::Medium),
+        Priority::Medium => Ok(Priority::High),
+        Priority::High => Ok(Priority::Urgent),
+        Priority::Urgent => Err("no priority is above urgent".to_string()),
+    }
+}
+
+/// Split `due before today` into the date field and `before today`
+fn date_instruction(lower: &str) -> Option<(DateField, &str)> {
+    let (word, rest) = lower.split_once(' ')?;
+    let field = match word {
+        "due" => DateField::Due,
+        "scheduled" => DateField::Scheduled,
+        "starts" => DateField::Starts,
+        "done" => DateField::Done,
+        _ => return None,
+    };
+    Some((field, rest.trim()))
+}
+
+fn apply_date(
+    filter: &mut FilterOptions,
+    field: DateField,
+    constraint: &str,
+    today: NaiveDate,
+    locale: Locale,
+) -> Result<(), String> {
+    let (comparison, text) = match constraint.split_once(' ') {
+        Some((word @ ("before" | "after" | "on"), text)) => (word, text.trim()),
+        _ => ("on", constraint),
+    };
+    // Keywords the date filters understand stay relative; other dates are
+    // resolved against `today`
+    let value = text
+        .replace(' ', "-")
+        .parse::<DateValue>()
+        .ok()
+        .or_else(|| parse_natural_date(text, today, locale).map(DateValue::Date))
+        .ok_or_else(|| format!("unknown date '{}'", text))?;
+    let date = || match value {
+        DateValue::Date(date) => Ok(date),
+        _ => parse_natural_date(text, today, locale)
+            .ok_or_else(|| format!("'{}' can only be used with due and done dates", text)),
+    };
+
+    match (field, comparison) {
+        (DateField::Due, "before") => filter.due_before = Some(value),
+        (DateField::Due, "after") => filter.due_after = Some(value),
+        (DateField::Due, _) => filter.due_on = Some(value),
+        (DateField::Done, "before") => filter.completed_before = Some(value),
+        (DateField::Done, "after") => filter.completed_after = Some(value),
+        (DateField::Done, _) => filter.completed_on = Some(value),
+        (DateField::Scheduled, "before") => filter.scheduled_before = Some(date()?),
+        (DateField::Scheduled, "after") => filter.scheduled_after = Some(date()?),
+        (DateField::Scheduled, _) => filter.scheduled_on = Some(date()?),
+        (DateField::Starts, "after") => filter.starts_after = Some(date()?),
+        (DateField::Starts, _) => return Err("only 'starts after' is supported".to_string()),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn parse(query: &str) -> Result<TasksQuery, String> {
+        // A Wednesday
+        parse_tasks_query(query, date("2025-01-08"), Locale::En)
+    }
+
+    #[test]
+    fn test_parse_tasks_query() {
+        let query = parse(
+            "not done\n\
+             due before today\n\
+             Tags include #work\n\
+             tags do not include #someday\n\
+             path includes Projects/Launch\n\
+             # a comment\n\
+             \n\
+             hide edit button\n\
+             limit to 20 tasks",
+        )
+        .unwrap();
+
+        let filter = query.filter;
+        assert_eq!(filter.status, Some(TaskStatus::Incomplete));
+        assert_eq!(filter.due_before, Some(DateValue::Today));
+        assert_eq!(filter.tags, Some(vec!["work".to_string()]));
+        assert_eq!(filter.exclude_tags, Some(vec!["someday".to_string()]));
+        assert_eq!(filter.path_includes.as_deref(), Some("Projects/Launch"));
+        assert_eq!(query.limit, Some(20));
+    }
+
+    #[test]
+    fn test_parse_tasks_query_dates_and_flags() {
+        let filter = parse(
+            "done after 2025-01-01\n\
+             scheduled on next friday\n\
+             starts after tomorrow\n\
+             due this week\n\
+             priority is above medium\n\
+             is not blocked\n\
+             is recurring",
+        )
+        .unwrap()
+        .filter;
+
+        assert_eq!(filter.completed_after, Some(date("2025-01-01").into()));
+        assert_eq!(filter.scheduled_on, Some(date("2025-01-17")));
+        assert_eq!(filter.starts_after, Some(date("2025-01-09")));
+        assert_eq!(filter.due_on, Some(DateValue::ThisWeek));
+        assert_eq!(filter.min_priority, Some(Priority::High));
+        assert_eq!(filter.blocked, Some(false));
+        assert!(filter.recurring);
+    }
+
+    #[test]
+    fn test_parse_tasks_query_errors() {
+        assert_eq!(
+            parse("not done\nsort by due").unwrap_err(),
+            "Line 2 ('sort by due'): unsupported instruction"
+        );
+        assert!(parse("due before someday").is_err());
+        assert!(parse("priority is above urgent").is_err());
+        assert!(parse("scheduled before overdue").is_err());
+    }
+}
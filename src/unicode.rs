@@ -0,0 +1,50 @@
+//! Normalization-aware text comparison
+//!
+//! The same text can be stored in different Unicode forms: macOS writes file
+//! names decomposed (NFD, `e` followed by a combining accent) while most
+//! editors write composed text (NFC), so a `café` typed in a query is not
+//! byte-equal to the `café` in a file name or a note synced from a Mac.
+//! Extracted tags are stored in NFC ([`nfc`]), and tags, headings, note
+//! names, and paths are compared through [`fold`].
+
+use std::borrow::Cow;
+use unicode_normalization::{IsNormalized, UnicodeNormalization, is_nfc_quick};
+
+/// `text` in Normalization Form C, borrowed when it already is
+pub fn nfc(text: &str) -> Cow<'_, str> {
+    match is_nfc_quick(text.chars()) {
+        IsNormalized::Yes => Cow::Borrowed(text),
+        _ => Cow::Owned(text.nfc().collect()),
+    }
+}
+
+/// Key for comparing text regardless of Unicode form and case
+pub fn fold(text: &str) -> String {
+    nfc(text).to_lowercase()
+}
+
+/// Whether two strings are equal regardless of Unicode form and case
+pub fn fold_eq(a: &str, b: &str) -> bool {
+    a == b || fold(a) == fold(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COMPOSED: &str = "caf\u{e9}";
+    const DECOMPOSED: &str = "cafe\u{301}";
+
+    #[test]
+    fn test_nfc() {
+        assert_eq!(nfc(DECOMPOSED), COMPOSED);
+        assert!(matches!(nfc(COMPOSED), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_fold() {
+        assert_eq!(fold(DECOMPOSED), fold("CAF\u{c9}"));
+        assert!(fold_eq(DECOMPOSED, "Caf\u{e9}"));
+        assert!(!fold_eq(COMPOSED, "cafe"));
+    }
+}
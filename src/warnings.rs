@@ -0,0 +1,100 @@
+//! Per-file problems found while scanning a vault
+//!
+//! Files that can't be read or parsed are skipped rather than failing a whole
+//! scan. Each skip is recorded as an [`ExtractionWarning`] so API responses can
+//! report it in their `warnings` array (or fail, with `strict: true`).
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Why a file was skipped
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WarningKind {
+    /// The file or directory couldn't be read (permissions, invalid UTF-8)
+    Unreadable,
+    /// The YAML frontmatter doesn't parse
+    InvalidYaml,
+    /// The file is larger than `max_file_size`
+    Oversized,
+    /// The scan stopped at `max_files`, `max_depth`, or `max_total_bytes`
+    /// (with `scan_limit_action = "truncate"`); `file_path` is the scanned
+    /// directory
+    ScanLimit,
+}
+
+impl fmt::Display for WarningKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WarningKind::Unreadable => write!(f, "unreadable"),
+            WarningKind::InvalidYaml => write!(f, "invalid_yaml"),
+            WarningKind::Oversized => write!(f, "oversized"),
+            WarningKind::ScanLimit => write!(f, "scan_limit"),
+        }
+    }
+}
+
+/// A file that was skipped during a scan
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ExtractionWarning {
+    pub file_path: String,
+    pub kind: WarningKind,
+    /// Underlying error, e.g. "Permission denied (os error 13)"
+    pub message: String,
+}
+
+impl ExtractionWarning {
+    pub fn new(file_path: &Path, kind: WarningKind, message: impl fmt::Display) -> Self {
+        Self {
+            file_path: file_path.to_string_lossy().to_string(),
+            kind,
+            message: message.to_string(),
+        }
+    }
+
+    /// Warning for a file of `len` bytes over the `max` from `max_file_size`
+    pub fn oversized(file_path: &Path, len: u64, max: u64) -> Self {
+        Self::new(
+            file_path,
+            WarningKind::Oversized,
+            format!("{} bytes exceeds max_file_size of {} bytes", len, max),
+        )
+    }
+}
+
+impl fmt::Display for ExtractionWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}): {}", self.file_path, self.kind, self.message)
+    }
+}
+
+/// Collects warnings from parallel file scans
+#[derive(Debug, Default)]
+pub struct Warnings(Mutex<Vec<ExtractionWarning>>);
+
+impl Warnings {
+    pub fn push(&self, warning: ExtractionWarning) {
+        self.0.lock().unwrap().push(warning);
+    }
+
+    /// Warnings sorted by file path, so output doesn't depend on scan order
+    ///
+    /// A file reported twice, e.g. by two scans of the same folder in one
+    /// call, is listed once.
+    pub fn into_vec(self) -> Vec<ExtractionWarning> {
+        let mut warnings = self.0.into_inner().unwrap();
+        warnings.sort_by(|a, b| (&a.file_path, &a.message).cmp(&(&b.file_path, &b.message)));
+        warnings.dedup();
+        warnings
+    }
+}
+
+/// Report warnings on stderr, for callers without a response to put them in
+pub fn print_warnings(warnings: &[ExtractionWarning]) {
+    for warning in warnings {
+        eprintln!("Warning: Skipped {}", warning);
+    }
+}
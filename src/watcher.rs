@@ -0,0 +1,120 @@
+//! Vault watcher for `serve --watch`
+//!
+//! Keeps the vault's index (see `crate::cache`) resident in memory and
+//! watches the vault with `notify`, evicting files from the index as they are
+//! created, changed, renamed, or deleted. Requests then only parse the files
+//! that changed since they were last seen, without reading the index file.
+//! The index still checks each file's modification time and size, which
+//! covers the moment between a write and its event arriving. With the
+//! `sqlite` feature and `sqlite_index = true`, the changed paths are also
+//! re-indexed in the vault's SQLite index, if `reindex` has built one.
+
+use crate::cache;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Watches a vault for as long as it is kept
+pub struct VaultWatcher {
+    _watcher: RecommendedWatcher,
+}
+
+impl VaultWatcher {
+    /// Keep the index of the vault at `base_path` in memory and start watching
+    /// the vault
+    pub fn start(base_path: &Path) -> Result<Self, Box<dyn Error>> {
+        let cache_file = cache::vault_cache_path(base_path)
+            .ok_or("No cache directory: set HOME or XDG_CACHE_HOME")?;
+        let config = std::sync::Arc::new(crate::config::Config::load_from_base_path(base_path));
+        cache::enable();
+        cache::keep_resident(&cache_file, &config);
+
+        let root = base_path.to_path_buf();
+        let canonical = fs::canonicalize(base_path).unwrap_or_else(|_| root.clone());
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+            match event {
+                // Events were dropped, so any file may have changed
+                Ok(event) if event.need_rescan() => {
+                    cache::invalidate_all();
+                    #[cfg(feature = "sqlite")]
+                    refresh_sqlite_index(&root, &config, std::slice::from_ref(&root));
+                }
+                Ok(Event {
+                    kind: EventKind::Access(_),
+                    ..
+                }) => {}
+                Ok(event) => {
+                    let paths: Vec<PathBuf> = event
+                        .paths
+                        .iter()
+                        .map(|path| vault_path(&root, &canonical, path))
+                        .collect();
+                    cache::invalidate(&paths);
+                    #[cfg(feature = "sqlite")]
+                    refresh_sqlite_index(&root, &config, &paths);
+                }
+                Err(e) => {
+                    eprintln!("Warning: Vault watcher error: {}", e);
+                    cache::invalidate_all();
+                }
+            }
+        })?;
+        watcher.watch(base_path, RecursiveMode::Recursive)?;
+
+        Ok(Self { _watcher: watcher })
+    }
+}
+
+/// Re-index `paths` in the vault's SQLite index (the whole vault if `paths`
+/// is just the vault)
+#[cfg(feature = "sqlite")]
+fn refresh_sqlite_index(
+    root: &Path,
+    config: &std::sync::Arc<crate::config::Config>,
+    paths: &[PathBuf],
+) {
+    use crate::sqlite_index;
+
+    let result = if paths == [root] {
+        match sqlite_index::index_path(root) {
+            Some(index_file) if config.sqlite_index && index_file.is_file() => {
+                sqlite_index::reindex(root, std::sync::Arc::clone(config), &index_file).map(|_| ())
+            }
+            _ => Ok(()),
+        }
+    } else {
+        sqlite_index::refresh(root, std::sync::Arc::clone(config), paths)
+    };
+    if let Err(e) = result {
+        eprintln!("Warning: Could not update SQLite index: {}", e);
+    }
+}
+
+/// `path` as the scans see it, under `root` as given rather than its
+/// canonical form, which some platforms report events with
+fn vault_path(root: &Path, canonical: &Path, path: &Path) -> PathBuf {
+    match path.strip_prefix(canonical) {
+        Ok(relative) if !path.starts_with(root) => root.join(relative),
+        _ => path.to_path_buf(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vault_path() {
+        let root = Path::new("vault");
+        let canonical = Path::new("/home/me/vault");
+        assert_eq!(
+            vault_path(root, canonical, Path::new("/home/me/vault/notes/a.md")),
+            PathBuf::from("vault/notes/a.md")
+        );
+        assert_eq!(
+            vault_path(root, canonical, Path::new("vault/a.md")),
+            PathBuf::from("vault/a.md")
+        );
+    }
+}